@@ -124,6 +124,8 @@ impl LogoTrack {
                 rotation: VideoRotation::VideoRotation0,
                 buffer: I420Buffer::new(FB_WIDTH as u32, FB_HEIGHT as u32),
                 timestamp_us: 0,
+                rtp_timestamp: 0,
+                ntp_time_ms: 0,
             })),
             pos: (0, 0),
             direction: (1, 1),
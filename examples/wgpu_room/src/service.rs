@@ -119,6 +119,7 @@ async fn service_task(inner: Arc<ServiceInner>, mut cmd_rx: mpsc::UnboundedRecei
                 let e2ee = enable_e2ee.then_some(E2eeOptions {
                     encryption_type: EncryptionType::Gcm,
                     key_provider,
+                    key_rotation: None,
                 });
 
                 let res = Room::connect(
@@ -186,10 +187,12 @@ async fn service_task(inner: Arc<ServiceInner>, mut cmd_rx: mpsc::UnboundedRecei
                 }
             }
             AsyncCmd::SubscribeTrack { publication } => {
-                publication.set_subscribed(true);
+                if let Err(err) = publication.set_subscribed(true).await {
+                    log::error!("failed to subscribe: {:?}", err);
+                }
             }
             AsyncCmd::UnsubscribeTrack { publication } => {
-                publication.set_subscribed(false);
+                let _ = publication.set_subscribed(false).await;
             }
             AsyncCmd::E2eeKeyRatchet => {
                 if let Some(state) = running_state.as_ref() {
@@ -15,6 +15,7 @@
 use crate::get_env_keys;
 use jsonwebtoken::{self, DecodingKey, EncodingKey, Header};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::env;
 use std::fmt::Debug;
 use std::ops::Add;
@@ -37,6 +38,7 @@ pub enum AccessTokenError {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 #[serde(rename_all = "camelCase")]
 pub struct VideoGrants {
     // actions on rooms
@@ -48,11 +50,15 @@ pub struct VideoGrants {
     pub room_admin: bool,
     pub room_join: bool,
     pub room: String,
+    // room a participant can be forwarded to (with room_admin)
+    pub destination_room: String,
 
     // permissions within a room
     pub can_publish: bool,
     pub can_subscribe: bool,
     pub can_publish_data: bool,
+    // the metrics published by the other participants can be subscribed to
+    pub can_subscribe_metrics: bool,
 
     // TrackSource types that a participant may publish.
     // When set, it supercedes CanPublish. Only sources explicitly set here can be published
@@ -69,6 +75,9 @@ pub struct VideoGrants {
 
     // indicates to the room that current participant is a recorder
     pub recorder: bool,
+
+    // indicates to the room that current participant is an agent
+    pub agent: bool,
 }
 
 impl Default for VideoGrants {
@@ -80,18 +89,57 @@ impl Default for VideoGrants {
             room_admin: false,
             room_join: false,
             room: "".to_string(),
+            destination_room: "".to_string(),
             can_publish: true,
             can_subscribe: true,
             can_publish_data: true,
+            can_subscribe_metrics: false,
             can_publish_sources: Vec::default(),
             can_update_own_metadata: false,
             ingress_admin: false,
             hidden: false,
             recorder: false,
+            agent: false,
         }
     }
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+#[serde(rename_all = "camelCase")]
+pub struct SIPGrants {
+    // manage the sip trunks and dispatch rules
+    pub admin: bool,
+    // make outbound calls
+    pub call: bool,
+}
+
+/// Created with the room when the participant is the first to join it
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomConfiguration {
+    pub name: String,
+    // in seconds
+    pub empty_timeout: u32,
+    pub departure_timeout: u32,
+    pub max_participants: u32,
+    // in milliseconds
+    pub min_playout_delay: u32,
+    pub max_playout_delay: u32,
+    pub sync_streams: bool,
+    // agents dispatched to the room when it is created
+    pub agents: Vec<RoomAgentDispatch>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomAgentDispatch {
+    pub agent_name: String,
+    pub metadata: String,
+}
+
 #[derive(Debug, Clone, Serialize, Default, Deserialize)]
 #[serde(default)]
 #[serde(rename_all = "camelCase")]
@@ -103,8 +151,30 @@ pub struct Claims {
 
     pub name: String,
     pub video: VideoGrants,
+    pub sip: SIPGrants,
     pub sha256: String, // Used to verify the integrity of the message body
     pub metadata: String,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub attributes: HashMap<String, String>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub room_preset: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub room_config: Option<RoomConfiguration>,
+}
+
+impl Claims {
+    /// Read the claims of a token without verifying its signature,
+    /// useful on the client side where the api secret isn't available
+    pub fn from_unverified(token: &str) -> Result<Self, AccessTokenError> {
+        let mut validation = jsonwebtoken::Validation::new(jsonwebtoken::Algorithm::HS256);
+        validation.insecure_disable_signature_validation();
+        validation.validate_exp = false;
+
+        let token =
+            jsonwebtoken::decode::<Claims>(token, &DecodingKey::from_secret(&[]), &validation)?;
+
+        Ok(token.claims)
+    }
 }
 
 #[derive(Clone)]
@@ -137,8 +207,12 @@ impl AccessToken {
                 sub: Default::default(),
                 name: Default::default(),
                 video: VideoGrants::default(),
+                sip: SIPGrants::default(),
                 sha256: Default::default(),
                 metadata: Default::default(),
+                attributes: Default::default(),
+                room_preset: Default::default(),
+                room_config: None,
             },
         }
     }
@@ -159,6 +233,11 @@ impl AccessToken {
         self
     }
 
+    pub fn with_sip_grants(mut self, grants: SIPGrants) -> Self {
+        self.claims.sip = grants;
+        self
+    }
+
     pub fn with_identity(mut self, identity: &str) -> Self {
         self.claims.sub = identity.to_owned();
         self
@@ -174,6 +253,22 @@ impl AccessToken {
         self
     }
 
+    pub fn with_attributes(mut self, attributes: HashMap<String, String>) -> Self {
+        self.claims.attributes = attributes;
+        self
+    }
+
+    /// Name of a room preset defined on the server, applied when the room is created
+    pub fn with_room_preset(mut self, preset: &str) -> Self {
+        self.claims.room_preset = preset.to_owned();
+        self
+    }
+
+    pub fn with_room_config(mut self, config: RoomConfiguration) -> Self {
+        self.claims.room_config = Some(config);
+        self
+    }
+
     pub fn with_sha256(mut self, sha256: &str) -> Self {
         self.claims.sha256 = sha256.to_owned();
         self
@@ -243,7 +338,11 @@ impl TokenVerifier {
 
 #[cfg(test)]
 mod tests {
-    use super::{AccessToken, TokenVerifier, VideoGrants};
+    use super::{
+        AccessToken, Claims, RoomAgentDispatch, RoomConfiguration, SIPGrants, TokenVerifier,
+        VideoGrants,
+    };
+    use std::collections::HashMap;
     use std::time::Duration;
 
     const TEST_API_KEY: &str = "myapikey";
@@ -272,4 +371,109 @@ mod tests {
         let incorrect_token = TokenVerifier::with_api_key(TEST_API_KEY, "incorrect");
         assert!(incorrect_token.verify(&token).is_err());
     }
+
+    #[test]
+    fn test_unverified_claims() {
+        let token = AccessToken::with_api_key(TEST_API_KEY, TEST_API_SECRET)
+            .with_identity("test")
+            .with_grants(VideoGrants {
+                room_admin: true,
+                room: "my-room".to_owned(),
+                ..Default::default()
+            })
+            .to_jwt()
+            .unwrap();
+
+        let claims = Claims::from_unverified(&token).unwrap();
+        assert_eq!(claims.sub, "test");
+        assert!(claims.video.room_admin);
+        assert_eq!(claims.video.room, "my-room");
+
+        assert!(Claims::from_unverified("invalid").is_err());
+    }
+
+    #[test]
+    fn test_full_grants() {
+        let attributes = HashMap::from([("role".to_owned(), "host".to_owned())]);
+        let room_config = RoomConfiguration {
+            empty_timeout: 300,
+            max_participants: 10,
+            agents: vec![RoomAgentDispatch {
+                agent_name: "assistant".to_owned(),
+                metadata: "{}".to_owned(),
+            }],
+            ..Default::default()
+        };
+
+        let token = AccessToken::with_api_key(TEST_API_KEY, TEST_API_SECRET)
+            .with_identity("test")
+            .with_grants(VideoGrants {
+                room_join: true,
+                room: "my-room".to_owned(),
+                can_update_own_metadata: true,
+                can_subscribe_metrics: true,
+                ingress_admin: true,
+                agent: true,
+                ..Default::default()
+            })
+            .with_sip_grants(SIPGrants {
+                admin: true,
+                call: true,
+            })
+            .with_attributes(attributes.clone())
+            .with_room_config(room_config.clone())
+            .to_jwt()
+            .unwrap();
+
+        let verifier = TokenVerifier::with_api_key(TEST_API_KEY, TEST_API_SECRET);
+        let claims = verifier.verify(&token).unwrap();
+        assert!(claims.video.can_update_own_metadata);
+        assert!(claims.video.can_subscribe_metrics);
+        assert!(claims.video.ingress_admin);
+        assert!(claims.video.agent);
+        assert_eq!(
+            claims.sip,
+            SIPGrants {
+                admin: true,
+                call: true
+            }
+        );
+        assert_eq!(claims.attributes, attributes);
+        assert_eq!(claims.room_config, Some(room_config));
+    }
+
+    #[test]
+    fn test_claims_json() {
+        let claims = Claims {
+            video: VideoGrants {
+                can_subscribe_metrics: true,
+                ..Default::default()
+            },
+            room_config: Some(RoomConfiguration {
+                agents: vec![RoomAgentDispatch {
+                    agent_name: "assistant".to_owned(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        // Same keys as the claims of the other server SDKs
+        let json = serde_json::to_value(&claims).unwrap();
+        assert_eq!(json["video"]["canSubscribeMetrics"], true);
+        assert_eq!(json["sip"]["admin"], false);
+        assert_eq!(json["roomConfig"]["agents"][0]["agentName"], "assistant");
+        assert!(json.get("attributes").is_none());
+
+        let json = r#"{"sub":"test","video":{"roomJoin":true,"agent":true},
+            "sip":{"call":true},"attributes":{"role":"host"},"roomPreset":"default"}"#;
+        let claims: Claims = serde_json::from_str(json).unwrap();
+        assert!(claims.video.room_join && claims.video.agent);
+        assert!(claims.video.can_publish); // missing grants keep their default
+        assert!(claims.sip.call && !claims.sip.admin);
+        assert_eq!(claims.attributes["role"], "host");
+        assert_eq!(claims.room_preset, "default");
+        assert_eq!(claims.room_config, None);
+    }
 }
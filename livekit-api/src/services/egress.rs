@@ -45,6 +45,8 @@ pub enum EgressOutput {
     File(proto::EncodedFileOutput),
     Stream(proto::StreamOutput),
     Segments(proto::SegmentedFileOutput),
+    /// Periodic snapshots of the video
+    Images(proto::ImageOutput),
 }
 
 #[derive(Debug, Clone)]
@@ -53,6 +55,166 @@ pub enum TrackEgressOutput {
     WebSocket(String),
 }
 
+/// Cloud storage of the recorded files, they're kept on the egress instance when not set
+#[derive(Debug, Clone)]
+pub enum EgressStorage {
+    S3(proto::S3Upload),
+    Gcp(proto::GcpUpload),
+    Azure(proto::AzureBlobUpload),
+    AliOss(proto::AliOssUpload),
+}
+
+impl EgressStorage {
+    /// For S3 compatible storages (e.g. MinIO, R2), set the endpoint and force_path_style
+    /// of the returned upload
+    pub fn s3(bucket: &str, region: &str, access_key: &str, secret: &str) -> Self {
+        Self::S3(proto::S3Upload {
+            bucket: bucket.to_owned(),
+            region: region.to_owned(),
+            access_key: access_key.to_owned(),
+            secret: secret.to_owned(),
+            ..Default::default()
+        })
+    }
+
+    /// `credentials` is the JSON of a service account key
+    pub fn gcp(bucket: &str, credentials: &str) -> Self {
+        Self::Gcp(proto::GcpUpload {
+            bucket: bucket.to_owned(),
+            credentials: credentials.to_owned(),
+            ..Default::default()
+        })
+    }
+
+    pub fn azure(account_name: &str, account_key: &str, container_name: &str) -> Self {
+        Self::Azure(proto::AzureBlobUpload {
+            account_name: account_name.to_owned(),
+            account_key: account_key.to_owned(),
+            container_name: container_name.to_owned(),
+        })
+    }
+
+    pub fn ali_oss(bucket: &str, region: &str, access_key: &str, secret: &str) -> Self {
+        Self::AliOss(proto::AliOssUpload {
+            bucket: bucket.to_owned(),
+            region: region.to_owned(),
+            access_key: access_key.to_owned(),
+            secret: secret.to_owned(),
+            ..Default::default()
+        })
+    }
+}
+
+impl From<EgressStorage> for proto::encoded_file_output::Output {
+    fn from(storage: EgressStorage) -> Self {
+        match storage {
+            EgressStorage::S3(s3) => Self::S3(s3),
+            EgressStorage::Gcp(gcp) => Self::Gcp(gcp),
+            EgressStorage::Azure(azure) => Self::Azure(azure),
+            EgressStorage::AliOss(ali_oss) => Self::AliOss(ali_oss),
+        }
+    }
+}
+
+impl From<EgressStorage> for proto::segmented_file_output::Output {
+    fn from(storage: EgressStorage) -> Self {
+        match storage {
+            EgressStorage::S3(s3) => Self::S3(s3),
+            EgressStorage::Gcp(gcp) => Self::Gcp(gcp),
+            EgressStorage::Azure(azure) => Self::Azure(azure),
+            EgressStorage::AliOss(ali_oss) => Self::AliOss(ali_oss),
+        }
+    }
+}
+
+impl From<EgressStorage> for proto::image_output::Output {
+    fn from(storage: EgressStorage) -> Self {
+        match storage {
+            EgressStorage::S3(s3) => Self::S3(s3),
+            EgressStorage::Gcp(gcp) => Self::Gcp(gcp),
+            EgressStorage::Azure(azure) => Self::Azure(azure),
+            EgressStorage::AliOss(ali_oss) => Self::AliOss(ali_oss),
+        }
+    }
+}
+
+impl From<EgressStorage> for proto::direct_file_output::Output {
+    fn from(storage: EgressStorage) -> Self {
+        match storage {
+            EgressStorage::S3(s3) => Self::S3(s3),
+            EgressStorage::Gcp(gcp) => Self::Gcp(gcp),
+            EgressStorage::Azure(azure) => Self::Azure(azure),
+            EgressStorage::AliOss(ali_oss) => Self::AliOss(ali_oss),
+        }
+    }
+}
+
+impl EgressOutput {
+    /// A single file, the file type is chosen from the codecs with EncodedFileType::DefaultFiletype
+    pub fn file(
+        file_type: proto::EncodedFileType,
+        filepath: &str,
+        storage: Option<EgressStorage>,
+    ) -> Self {
+        Self::File(proto::EncodedFileOutput {
+            file_type: file_type as i32,
+            filepath: filepath.to_owned(),
+            disable_manifest: false,
+            output: storage.map(Into::into),
+        })
+    }
+
+    /// RTMP streams, the urls can be updated with EgressClient::update_stream
+    pub fn stream(urls: Vec<String>) -> Self {
+        Self::Stream(proto::StreamOutput {
+            protocol: proto::StreamProtocol::Rtmp as i32,
+            urls,
+        })
+    }
+
+    /// HLS segments of `segment_duration` seconds and their playlist
+    pub fn segments(
+        filename_prefix: &str,
+        playlist_name: &str,
+        segment_duration: u32,
+        storage: Option<EgressStorage>,
+    ) -> Self {
+        Self::Segments(proto::SegmentedFileOutput {
+            protocol: proto::SegmentedFileProtocol::HlsProtocol as i32,
+            filename_prefix: filename_prefix.to_owned(),
+            playlist_name: playlist_name.to_owned(),
+            segment_duration,
+            output: storage.map(Into::into),
+            ..Default::default()
+        })
+    }
+
+    /// A snapshot of the video every `capture_interval` seconds, at the size of the track
+    pub fn images(
+        filename_prefix: &str,
+        capture_interval: u32,
+        storage: Option<EgressStorage>,
+    ) -> Self {
+        Self::Images(proto::ImageOutput {
+            capture_interval,
+            filename_prefix: filename_prefix.to_owned(),
+            output: storage.map(Into::into),
+            ..Default::default()
+        })
+    }
+}
+
+impl TrackEgressOutput {
+    /// The track is exported without transcoding
+    pub fn file(filepath: &str, storage: Option<EgressStorage>) -> Self {
+        Self::File(Box::new(proto::DirectFileOutput {
+            filepath: filepath.to_owned(),
+            disable_manifest: false,
+            output: storage.map(Into::into),
+        }))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum EgressListFilter {
     All,
@@ -93,7 +255,7 @@ impl EgressClient {
         outputs: Vec<EgressOutput>,
         options: RoomCompositeOptions,
     ) -> ServiceResult<proto::EgressInfo> {
-        let (file_outputs, stream_outputs, segment_outputs) = get_outputs(outputs);
+        let (file_outputs, stream_outputs, segment_outputs, image_outputs) = get_outputs(outputs);
         self.client
             .request(
                 SVC,
@@ -110,6 +272,7 @@ impl EgressClient {
                     file_outputs,
                     stream_outputs,
                     segment_outputs,
+                    image_outputs,
                     output: None, // Deprecated
                 },
                 self.base.auth_header(VideoGrants {
@@ -127,7 +290,7 @@ impl EgressClient {
         outputs: Vec<EgressOutput>,
         options: WebOptions,
     ) -> ServiceResult<proto::EgressInfo> {
-        let (file_outputs, stream_outputs, segment_outputs) = get_outputs(outputs);
+        let (file_outputs, stream_outputs, segment_outputs, image_outputs) = get_outputs(outputs);
         self.client
             .request(
                 SVC,
@@ -142,6 +305,7 @@ impl EgressClient {
                     file_outputs,
                     stream_outputs,
                     segment_outputs,
+                    image_outputs,
                     output: None,              // Deprecated
                     await_start_signal: false, // TODO Expose
                 },
@@ -160,7 +324,7 @@ impl EgressClient {
         outputs: Vec<EgressOutput>,
         options: TrackCompositeOptions,
     ) -> ServiceResult<proto::EgressInfo> {
-        let (file_outputs, stream_outputs, segment_outputs) = get_outputs(outputs);
+        let (file_outputs, stream_outputs, segment_outputs, image_outputs) = get_outputs(outputs);
         self.client
             .request(
                 SVC,
@@ -175,6 +339,7 @@ impl EgressClient {
                     file_outputs,
                     stream_outputs,
                     segment_outputs,
+                    image_outputs,
                     output: None, // Deprecated
                 },
                 self.base.auth_header(VideoGrants {
@@ -320,20 +485,23 @@ fn get_outputs(
     Vec<proto::EncodedFileOutput>,
     Vec<proto::StreamOutput>,
     Vec<proto::SegmentedFileOutput>,
+    Vec<proto::ImageOutput>,
 ) {
     let mut file_outputs = Vec::new();
     let mut stream_outputs = Vec::new();
     let mut segment_outputs = Vec::new();
+    let mut image_outputs = Vec::new();
 
     for output in outputs {
         match output {
             EgressOutput::File(f) => file_outputs.push(f),
             EgressOutput::Stream(s) => stream_outputs.push(s),
             EgressOutput::Segments(s) => segment_outputs.push(s),
+            EgressOutput::Images(i) => image_outputs.push(i),
         }
     }
 
-    (file_outputs, stream_outputs, segment_outputs)
+    (file_outputs, stream_outputs, segment_outputs, image_outputs)
 }
 
 pub mod encoding {
@@ -366,6 +534,7 @@ pub mod encoding {
                 video_codec: opts.video_codec as i32,
                 video_bitrate: opts.video_bitrate,
                 key_frame_interval: opts.keyframe_interval,
+                ..Default::default()
             }
         }
     }
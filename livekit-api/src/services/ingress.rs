@@ -23,8 +23,13 @@ pub struct IngressOptions {
     pub room_name: String,
     pub participant_identity: String,
     pub participant_name: String,
+    pub participant_metadata: String,
     pub audio: proto::IngressAudioOptions,
     pub video: proto::IngressVideoOptions,
+    /// Transcode the input, the default depends on the input type
+    pub enable_transcoding: Option<bool>,
+    /// Url to pull the media from, for the URL input type
+    pub url: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -55,6 +60,7 @@ impl IngressClient {
         Ok(Self::with_api_key(host, &api_key, &api_secret))
     }
 
+    #[allow(deprecated)]
     pub async fn create_ingress(
         &self,
         input_type: proto::IngressInput,
@@ -70,9 +76,13 @@ impl IngressClient {
                     room_name: options.room_name,
                     participant_identity: options.participant_identity,
                     participant_name: options.participant_name,
+                    participant_metadata: options.participant_metadata,
                     audio: Some(options.audio),
                     video: Some(options.video),
-                    bypass_transcoding: false, // TODO Expose
+                    bypass_transcoding: false,
+                    enable_transcoding: options.enable_transcoding,
+                    url: options.url,
+                    enabled: None,
                 },
                 self.base.auth_header(VideoGrants {
                     ingress_admin: true,
@@ -83,6 +93,7 @@ impl IngressClient {
             .map_err(Into::into)
     }
 
+    #[allow(deprecated)]
     pub async fn update_ingress(
         &self,
         ingress_id: &str,
@@ -98,9 +109,12 @@ impl IngressClient {
                     room_name: options.room_name,
                     participant_identity: options.participant_identity,
                     participant_name: options.participant_name,
+                    participant_metadata: options.participant_metadata,
                     audio: Some(options.audio),
                     video: Some(options.video),
-                    bypass_transcoding: None, // TODO Expose
+                    bypass_transcoding: None,
+                    enable_transcoding: options.enable_transcoding,
+                    enabled: None,
                 },
                 self.base.auth_header(VideoGrants {
                     ingress_admin: true,
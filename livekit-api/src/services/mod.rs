@@ -23,6 +23,8 @@ pub mod room;
 
 mod twirp_client;
 
+pub use twirp_client::{TwirpError, TwirpErrorCode};
+
 pub const LIVEKIT_PACKAGE: &str = "livekit";
 
 #[derive(Debug, Error)]
@@ -33,6 +35,8 @@ pub enum ServiceError {
     AccessToken(#[from] AccessTokenError),
     #[error("twirp error: {0}")]
     Twirp(#[from] twirp_client::TwirpError),
+    #[error("invalid response: {0}")]
+    InvalidResponse(&'static str),
 }
 
 pub type ServiceResult<T> = Result<T, ServiceError>;
@@ -40,6 +44,7 @@ pub type ServiceResult<T> = Result<T, ServiceError>;
 struct ServiceBase {
     api_key: String,
     api_secret: String,
+    token: Option<String>,
 }
 
 impl Debug for ServiceBase {
@@ -55,13 +60,26 @@ impl ServiceBase {
         Self {
             api_key: api_key.to_owned(),
             api_secret: api_secret.to_owned(),
+            token: None,
+        }
+    }
+
+    /// Authenticate every request with an already signed token
+    pub fn with_token(token: &str) -> Self {
+        Self {
+            api_key: String::default(),
+            api_secret: String::default(),
+            token: Some(token.to_owned()),
         }
     }
 
     pub fn auth_header(&self, grants: VideoGrants) -> Result<HeaderMap, AccessTokenError> {
-        let token = AccessToken::with_api_key(&self.api_key, &self.api_secret)
-            .with_grants(grants)
-            .to_jwt()?;
+        let token = match &self.token {
+            Some(token) => token.clone(),
+            None => AccessToken::with_api_key(&self.api_key, &self.api_secret)
+                .with_grants(grants)
+                .to_jwt()?,
+        };
 
         let mut headers = HeaderMap::new();
         headers.insert(
@@ -12,10 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{ServiceBase, ServiceResult, LIVEKIT_PACKAGE};
+use super::{ServiceBase, ServiceError, ServiceResult, LIVEKIT_PACKAGE};
 use crate::services::twirp_client::TwirpClient;
 use crate::{access_token::VideoGrants, get_env_keys};
 use livekit_protocol as proto;
+use std::collections::HashMap;
 
 const SVC: &str = "RoomService";
 
@@ -31,6 +32,8 @@ pub struct CreateRoomOptions {
 #[derive(Debug, Clone, Default)]
 pub struct UpdateParticipantOptions {
     pub metadata: String,
+    /// Attributes to set, an empty value removes the attribute
+    pub attributes: HashMap<String, String>,
     pub permission: Option<proto::ParticipantPermission>,
     pub name: String, // No effect if left empty
 }
@@ -38,10 +41,14 @@ pub struct UpdateParticipantOptions {
 #[derive(Debug, Clone, Default)]
 pub struct SendDataOptions {
     pub kind: proto::data_packet::Kind,
+    // Deprecated by the server, use destination_identities
     pub destination_sids: Vec<String>,
+    pub destination_identities: Vec<String>,
     pub topic: Option<String>,
 }
 
+/// Client of the RoomService API (rooms, participants and their tracks)
+#[doc(alias = "RoomServiceClient")]
 #[derive(Debug)]
 pub struct RoomClient {
     base: ServiceBase,
@@ -56,6 +63,14 @@ impl RoomClient {
         }
     }
 
+    /// Use a token signed elsewhere, its grants must allow the requests made
+    pub fn with_token(host: &str, token: &str) -> Self {
+        Self {
+            base: ServiceBase::with_token(token),
+            client: TwirpClient::new(host, LIVEKIT_PACKAGE, None),
+        }
+    }
+
     pub fn new(host: &str) -> ServiceResult<Self> {
         let (api_key, api_secret) = get_env_keys()?;
         Ok(Self::with_api_key(host, &api_key, &api_secret))
@@ -77,6 +92,7 @@ impl RoomClient {
                     node_id: options.node_id,
                     metadata: options.metadata,
                     egress: options.egress,
+                    ..Default::default()
                 },
                 self.base.auth_header(VideoGrants {
                     room_create: true,
@@ -235,7 +251,8 @@ impl RoomClient {
             )
             .await?;
 
-        Ok(resp.track.unwrap())
+        resp.track
+            .ok_or(ServiceError::InvalidResponse("the muted track is missing"))
     }
 
     pub async fn update_participant(
@@ -253,6 +270,7 @@ impl RoomClient {
                     identity: identity.to_owned(),
                     permission: options.permission,
                     metadata: options.metadata,
+                    attributes: options.attributes,
                     name: options.name,
                 },
                 self.base.auth_header(VideoGrants {
@@ -293,6 +311,7 @@ impl RoomClient {
             .map_err(Into::into)
     }
 
+    #[allow(deprecated)]
     pub async fn send_data(
         &self,
         room: &str,
@@ -307,8 +326,10 @@ impl RoomClient {
                     room: room.to_owned(),
                     data,
                     destination_sids: options.destination_sids,
+                    destination_identities: options.destination_identities,
                     topic: options.topic,
                     kind: options.kind as i32,
+                    ..Default::default()
                 },
                 self.base.auth_header(VideoGrants {
                     room_admin: true,
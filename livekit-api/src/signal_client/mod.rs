@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::signal_client::requests::{PendingRequests, RequestKey, ResponseReceiver};
 use crate::signal_client::signal_stream::SignalStream;
 use livekit_protocol as proto;
 use parking_lot::Mutex;
@@ -19,20 +20,35 @@ use reqwest::StatusCode;
 use std::fmt::Debug;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use thiserror::Error;
-use tokio::sync::mpsc;
 use tokio::sync::Mutex as AsyncMutex;
 use tokio::sync::RwLock as AsyncRwLock;
+use tokio::sync::{mpsc, oneshot};
 use tokio_tungstenite::tungstenite::Error as WsError;
 
+mod proxy;
+pub mod region;
+mod requests;
 mod signal_stream;
+mod transport;
+
+pub use proxy::ProxyOptions;
+pub use signal_stream::PREPARED_STREAM_TTL;
+pub use transport::{
+    ClientCertificate, SignalTransport, TlsOptions, TransportFuture, TransportStream,
+};
 
 pub type SignalEmitter = mpsc::UnboundedSender<SignalEvent>;
 pub type SignalEvents = mpsc::UnboundedReceiver<SignalEvent>;
 pub type SignalResult<T> = Result<T, SignalError>;
 
+pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
 pub const JOIN_RESPONSE_TIMEOUT: Duration = Duration::from_secs(5);
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// The subscriptions neither added to the subscriber nor failed within this delay (e.g. the
+/// track was already subscribed) are considered successful
+pub const SUBSCRIPTION_RESPONSE_TIMEOUT: Duration = Duration::from_secs(5);
 pub const PROTOCOL_VERSION: u32 = 8;
 
 #[derive(Error, Debug)]
@@ -51,12 +67,40 @@ pub enum SignalError {
     Timeout(String),
     #[error("failed to send message to server")]
     SendError,
+    #[error("failed to fetch the region settings: {0}")]
+    RegionError(String),
+    #[error("proxy error: {0}")]
+    Proxy(String),
+    #[error("invalid TLS configuration: {0}")]
+    Tls(String),
+    #[error("no response from the server to {0}")]
+    ResponseTimeout(String),
+    #[error("{0} cancelled before the server responded")]
+    RequestCancelled(String),
+    #[error("a request is already pending for {0}")]
+    DuplicateRequest(String),
+    #[error("connection closed before the server responded")]
+    ConnectionClosed,
+    #[error("connection closed before the request was sent")]
+    NotSent,
+    #[error("failed to subscribe to {0}: {1:?}")]
+    SubscriptionFailed(String, proto::SubscriptionError),
 }
 
 #[derive(Debug, Clone)]
 pub struct SignalOptions {
     pub auto_subscribe: bool,
     pub adaptive_stream: bool,
+    /// Proxy used for the WebSocket, the HTTP requests and the TURN TCP/TLS connections,
+    /// when None the HTTPS_PROXY/HTTP_PROXY env vars are used
+    pub proxy: Option<ProxyOptions>,
+    /// Opens the connection instead of TCP, the proxy is ignored when set
+    pub transport: Option<Arc<dyn SignalTransport>>,
+    pub tls: TlsOptions,
+    /// Timeout of the WebSocket connection, including the TLS and WebSocket handshakes
+    pub connect_timeout: Duration,
+    /// Timeout of the JoinResponse (or ReconnectResponse) once the WebSocket is connected
+    pub join_timeout: Duration,
 }
 
 impl Default for SignalOptions {
@@ -64,6 +108,11 @@ impl Default for SignalOptions {
         Self {
             auto_subscribe: true,
             adaptive_stream: false,
+            proxy: None,
+            transport: None,
+            tls: TlsOptions::default(),
+            connect_timeout: CONNECT_TIMEOUT,
+            join_timeout: JOIN_RESPONSE_TIMEOUT,
         }
     }
 }
@@ -76,13 +125,23 @@ pub enum SignalEvent {
 struct SignalInner {
     stream: AsyncRwLock<Option<SignalStream>>,
     token: Mutex<String>, // Token can be refreshed
+    requests: Mutex<PendingRequests>,
+    rtt: Mutex<Option<Duration>>, // Updated by the pongs of the server
+}
+
+/// Signal waiting for the connection to be resumed
+struct QueuedSignal {
+    signal: proto::signal_request::Message,
+    // Resolved once the signal is sent, see send_or_queue
+    sent_tx: Option<oneshot::Sender<SignalResult<()>>>,
 }
 
 pub struct SignalClient {
     inner: Arc<SignalInner>,
     emitter: SignalEmitter,
     reconnecting: AtomicBool,
-    queue: AsyncMutex<Vec<proto::signal_request::Message>>,
+    closed: AtomicBool,
+    queue: AsyncMutex<Vec<QueuedSignal>>,
 
     url: String,
     options: SignalOptions,
@@ -109,29 +168,42 @@ impl SignalClient {
         let lk_url = get_livekit_url(url, token, &options)?;
 
         // Try to connect to the SignalClient
-        let (stream, mut stream_events) = match SignalStream::connect(lk_url.clone()).await {
-            Ok(stream) => stream,
-            Err(err) => {
-                // Connection failed, try to retrieve more informations
-                Self::validate(lk_url).await?;
-                return Err(err);
-            }
-        };
+        let (stream, mut stream_events) =
+            match SignalStream::connect(lk_url.clone(), &options).await {
+                Ok(stream) => stream,
+                Err(err) => {
+                    // Connection failed, try to retrieve more informations
+                    Self::validate(lk_url, &options).await?;
+                    return Err(err);
+                }
+            };
 
         // Successfully connected to the SignalClient
+        let join_response = get_join_response(&mut stream_events, options.join_timeout).await?;
+        let mut requests = PendingRequests::default();
+        requests.handle_response(&proto::signal_response::Message::Join(
+            join_response.clone(),
+        ));
         let inner = Arc::new(SignalInner {
             stream: AsyncRwLock::new(Some(stream)),
             token: Mutex::new(token.to_owned()),
+            requests: Mutex::new(requests),
+            rtt: Default::default(),
         });
 
-        let join_response = get_join_response(&mut stream_events).await?;
         let (emitter, events) = mpsc::unbounded_channel();
-        tokio::spawn(signal_task(inner.clone(), emitter.clone(), stream_events));
+        tokio::spawn(signal_task(
+            inner.clone(),
+            emitter.clone(),
+            stream_events,
+            ping_interval(&join_response),
+        ));
 
         let client = Self {
             inner,
             emitter,
             reconnecting: AtomicBool::new(false),
+            closed: AtomicBool::new(false),
             queue: Default::default(),
             options,
             url: url.to_string(),
@@ -141,8 +213,16 @@ impl SignalClient {
         Ok((client, join_response, events))
     }
 
+    /// Validate the token and open the connection to the server without joining the room,
+    /// the next connect to the same server within PREPARED_STREAM_TTL reuses the connection
+    pub async fn prepare(url: &str, token: &str, options: &SignalOptions) -> SignalResult<()> {
+        let lk_url = get_livekit_url(url, token, options)?;
+        Self::validate(lk_url.clone(), options).await?;
+        SignalStream::prepare(lk_url, options).await
+    }
+
     /// Validate the connection by calling rtc/validate
-    async fn validate(mut ws_url: url::Url) -> SignalResult<()> {
+    async fn validate(mut ws_url: url::Url, options: &SignalOptions) -> SignalResult<()> {
         ws_url
             .set_scheme(if ws_url.scheme() == "wss" {
                 "https"
@@ -152,7 +232,8 @@ impl SignalClient {
             .unwrap();
         ws_url.set_path("/rtc/validate");
 
-        if let Ok(res) = reqwest::get(ws_url.as_str()).await {
+        let client = proxy::http_client(options.proxy.clone(), &ws_url)?;
+        if let Ok(res) = client.get(ws_url.as_str()).send().await {
             let status = res.status();
             let body = res.text().await.ok().unwrap_or_default();
 
@@ -171,7 +252,7 @@ impl SignalClient {
         self.reconnecting.store(true, Ordering::Release);
         scopeguard::defer!(self.reconnecting.store(false, Ordering::Release));
 
-        self.close().await;
+        self.close_stream().await;
 
         // Lock while we are reconnecting
         let mut stream = self.inner.stream.write().await;
@@ -184,12 +265,14 @@ impl SignalClient {
             .append_pair("reconnect", "1")
             .append_pair("sid", sid);
 
-        let (new_stream, mut signal_events) = SignalStream::connect(lk_url).await?;
-        let reconnect_response = get_reconnect_response(&mut signal_events).await?;
+        let (new_stream, mut signal_events) = SignalStream::connect(lk_url, &self.options).await?;
+        let reconnect_response =
+            get_reconnect_response(&mut signal_events, self.options.join_timeout).await?;
         tokio::spawn(signal_task(
             self.inner.clone(),
             self.emitter.clone(),
             signal_events,
+            ping_interval(&self.join_response),
         ));
 
         *stream = Some(new_stream);
@@ -199,8 +282,22 @@ impl SignalClient {
         Ok(reconnect_response)
     }
 
-    /// Close the connection
+    /// Close the connection, the queued signals are dropped and send_or_queue fails
+    /// with NotSent. The requests waiting for a response fail with ConnectionClosed,
+    /// they are kept while the connection is resumed (see restart)
     pub async fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        self.close_stream().await;
+        self.inner.requests.lock().close();
+
+        for queued in self.queue.lock().await.drain(..) {
+            if let Some(sent_tx) = queued.sent_tx {
+                let _ = sent_tx.send(Err(SignalError::NotSent));
+            }
+        }
+    }
+
+    async fn close_stream(&self) {
         if let Some(stream) = self.inner.stream.write().await.take() {
             stream.close().await;
         }
@@ -222,9 +319,111 @@ impl SignalClient {
         }
     }
 
+    /// Publish a track, resolves with its info once the server accepted it
+    pub async fn add_track(&self, req: proto::AddTrackRequest) -> SignalResult<proto::TrackInfo> {
+        let key = RequestKey::AddTrack(req.cid.clone());
+        let response = self.inner.requests.lock().register(vec![key])?;
+        self.send(proto::signal_request::Message::AddTrack(req))
+            .await;
+
+        wait_response(response, REQUEST_TIMEOUT, "AddTrackRequest")
+            .await?
+            .ok_or_else(|| SignalError::RequestCancelled("AddTrackRequest".to_owned()))
+    }
+
+    /// Make add_track fail with RequestCancelled, e.g. when the track is unpublished
+    /// while waiting for the server
+    pub fn cancel_add_track(&self, cid: &str) {
+        let key = RequestKey::AddTrack(cid.to_owned());
+        self.inner.requests.lock().remove(&key);
+    }
+
+    /// Resolves with the info of the track once the server applied its muted state
+    pub async fn mute_track(&self, req: proto::MuteTrackRequest) -> SignalResult<proto::TrackInfo> {
+        let key = RequestKey::Mute {
+            sid: req.sid.clone(),
+            muted: req.muted,
+        };
+        let response = self.inner.requests.lock().register(vec![key])?;
+        self.send_or_queue(proto::signal_request::Message::Mute(req))
+            .await?;
+
+        // A newer request for the same track replaced this one
+        wait_response(response, REQUEST_TIMEOUT, "MuteTrackRequest")
+            .await?
+            .ok_or_else(|| SignalError::RequestCancelled("MuteTrackRequest".to_owned()))
+    }
+
+    /// Resolves once the server offers one of the tracks to the subscriber, and fails if it
+    /// answers with a SubscriptionResponse error. Unsubscribing resolves once the request
+    /// is sent (see send_or_queue)
+    pub async fn update_subscription(&self, req: proto::UpdateSubscription) -> SignalResult<()> {
+        if !req.subscribe {
+            return self
+                .send_or_queue(proto::signal_request::Message::Subscription(req))
+                .await;
+        }
+
+        let keys = req
+            .track_sids
+            .iter()
+            .cloned()
+            .map(RequestKey::Subscription)
+            .collect();
+        let response = self.inner.requests.lock().register(keys)?;
+        self.send_or_queue(proto::signal_request::Message::Subscription(req))
+            .await?;
+
+        match wait_response(
+            response,
+            SUBSCRIPTION_RESPONSE_TIMEOUT,
+            "UpdateSubscription",
+        )
+        .await
+        {
+            Err(SignalError::ResponseTimeout(_)) => Ok(()),
+            res => res.map(|_| ()),
+        }
+    }
+
+    /// Unlike send, the signal is queued whatever its type while the connection is down
+    /// and resolves once it is sent after the connection resumed. Fails with NotSent if
+    /// the connection is closed instead (e.g. the engine had to fully reconnect)
+    pub async fn send_or_queue(&self, signal: proto::signal_request::Message) -> SignalResult<()> {
+        if !self.reconnecting.load(Ordering::Acquire) {
+            self.flush_queue().await;
+
+            if let Some(stream) = self.inner.stream.read().await.as_ref() {
+                if stream.send(signal.clone()).await.is_ok() {
+                    return Ok(());
+                }
+            }
+        }
+
+        let (sent_tx, sent_rx) = oneshot::channel();
+        {
+            // Checked with the queue locked, close drains it right after setting the flag
+            let mut queue = self.queue.lock().await;
+            if self.closed.load(Ordering::Acquire) {
+                return Err(SignalError::NotSent);
+            }
+
+            queue.push(QueuedSignal {
+                signal,
+                sent_tx: Some(sent_tx),
+            });
+        }
+        sent_rx.await.unwrap_or(Err(SignalError::NotSent))
+    }
+
     async fn queue_message(&self, signal: proto::signal_request::Message) {
         if is_queuable(&signal) {
-            self.queue.lock().await.push(signal);
+            self.queue.lock().await.push(QueuedSignal {
+                signal,
+                sent_tx: None,
+            });
+        } else {
+            log::debug!("dropping signal sent while disconnected: {:?}", signal);
         }
     }
 
@@ -235,12 +434,16 @@ impl SignalClient {
         }
 
         if let Some(stream) = self.inner.stream.read().await.as_ref() {
-            for signal in queue.drain(..) {
-                log::warn!("sending queued signal: {:?}", signal);
+            for queued in queue.drain(..) {
+                log::warn!("sending queued signal: {:?}", queued.signal);
 
-                if let Err(err) = stream.send(signal).await {
+                let res = stream.send(queued.signal).await;
+                if let Err(err) = &res {
                     log::error!("failed to send queued signal: {}", err); // Lost message
                 }
+                if let Some(sent_tx) = queued.sent_tx {
+                    let _ = sent_tx.send(res);
+                }
             }
         }
     }
@@ -249,6 +452,12 @@ impl SignalClient {
         self.join_response.clone()
     }
 
+    /// Round trip time of the signal connection, measured by the pings sent at the
+    /// interval given by the server. None until the first pong is received
+    pub fn rtt(&self) -> Option<Duration> {
+        *self.inner.rtt.lock()
+    }
+
     pub fn options(&self) -> SignalOptions {
         self.options.clone()
     }
@@ -261,6 +470,21 @@ impl SignalClient {
     pub fn token(&self) -> String {
         self.inner.token.lock().clone()
     }
+
+    /// Replace the token used for the next reconnections
+    pub fn set_token(&self, token: String) {
+        *self.inner.token.lock() = token;
+    }
+}
+
+impl Drop for SignalClient {
+    // signal_task keeps the inner state alive, drop the stream to stop it when the
+    // client isn't closed (e.g. the connection was cancelled)
+    fn drop(&mut self) {
+        if let Ok(mut stream) = self.inner.stream.try_write() {
+            stream.take();
+        }
+    }
 }
 
 /// Middleware task to receive SignalStream events and handle SignalClient specific logic
@@ -269,20 +493,83 @@ async fn signal_task(
     inner: Arc<SignalInner>,
     emitter: SignalEmitter, // Public emitter
     mut internal_events: mpsc::UnboundedReceiver<Box<proto::signal_response::Message>>,
+    ping_interval: Option<Duration>,
 ) {
-    while let Some(signal) = internal_events.recv().await {
-        if let proto::signal_response::Message::RefreshToken(ref token) = signal.as_ref() {
-            *inner.token.lock() = token.clone(); // Refresh the token so the client can still reconnect if the initial join token expired
-        }
+    let mut ping_interval = ping_interval
+        .map(|interval| tokio::time::interval_at(tokio::time::Instant::now() + interval, interval));
+
+    loop {
+        tokio::select! {
+            signal = internal_events.recv() => {
+                let Some(signal) = signal else {
+                    break;
+                };
+
+                match signal.as_ref() {
+                    proto::signal_response::Message::RefreshToken(token) => {
+                        *inner.token.lock() = token.clone(); // Refresh the token so the client can still reconnect if the initial join token expired
+                    }
+                    proto::signal_response::Message::PongResp(pong) => {
+                        let rtt = unix_time_ms().saturating_sub(pong.last_ping_timestamp);
+                        *inner.rtt.lock() = Some(Duration::from_millis(rtt.max(0) as u64));
+                    }
+                    _ => {}
+                }
 
-        // TODO(theomonnom): should we handle signal ping pong on native side?
-        let _ = emitter.send(SignalEvent::Message(signal));
+                inner.requests.lock().handle_response(&signal);
+                let _ = emitter.send(SignalEvent::Message(signal));
+            }
+            _ = async { ping_interval.as_mut().unwrap().tick().await }, if ping_interval.is_some() => {
+                send_ping(&inner).await;
+            }
+        }
     }
 
-    // internal_events is closed, send an event to notify the close
+    // internal_events is closed, send an event to notify the close. The pending requests
+    // are answered on the resumed connection or failed by SignalClient::close
     let _ = emitter.send(SignalEvent::Close);
 }
 
+async fn send_ping(inner: &SignalInner) {
+    let rtt = inner.rtt.lock().map(|rtt| rtt.as_millis() as i64);
+    let ping = proto::signal_request::Message::PingReq(proto::Ping {
+        timestamp: unix_time_ms(),
+        rtt: rtt.unwrap_or_default(),
+    });
+
+    if let Some(stream) = inner.stream.read().await.as_ref() {
+        if let Err(err) = stream.send(ping).await {
+            log::warn!("failed to send ping: {}", err);
+        }
+    }
+}
+
+/// The server disables the pings with a zero interval (in seconds)
+fn ping_interval(join_response: &proto::JoinResponse) -> Option<Duration> {
+    (join_response.ping_interval > 0)
+        .then(|| Duration::from_secs(join_response.ping_interval as u64))
+}
+
+fn unix_time_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+/// Wait for the response of a request, None when the request was cancelled or replaced
+async fn wait_response(
+    mut response: ResponseReceiver,
+    timeout: Duration,
+    request: &str,
+) -> SignalResult<Option<proto::TrackInfo>> {
+    match tokio::time::timeout(timeout, response.recv()).await {
+        Ok(Some(result)) => result.map(Some),
+        Ok(None) => Ok(None),
+        Err(_) => Err(SignalError::ResponseTimeout(request.to_owned())),
+    }
+}
+
 /// Check if the signal is queuable
 /// Not every signal should be sent after signal reconnection
 fn is_queuable(signal: &proto::signal_request::Message) -> bool {
@@ -317,10 +604,18 @@ fn get_livekit_url(url: &str, token: &str, options: &SignalOptions) -> SignalRes
     Ok(lk_url)
 }
 
+fn get_proxy(url: &url::Url, options: &SignalOptions) -> Option<ProxyOptions> {
+    options
+        .proxy
+        .clone()
+        .or_else(|| ProxyOptions::from_env(url))
+}
+
 macro_rules! get_async_message {
     ($fnc:ident, $pattern:pat => $result:expr, $ty:ty) => {
         async fn $fnc(
             receiver: &mut mpsc::UnboundedReceiver<Box<proto::signal_response::Message>>,
+            timeout: Duration,
         ) -> SignalResult<$ty> {
             let join = async {
                 while let Some(event) = receiver.recv().await {
@@ -332,14 +627,12 @@ macro_rules! get_async_message {
                 Err(WsError::ConnectionClosed)?
             };
 
-            tokio::time::timeout(JOIN_RESPONSE_TIMEOUT, join)
-                .await
-                .map_err(|_| {
-                    SignalError::Timeout(format!(
-                        "failed to receive {}",
-                        std::any::type_name::<$ty>()
-                    ))
-                })?
+            tokio::time::timeout(timeout, join).await.map_err(|_| {
+                SignalError::Timeout(format!(
+                    "failed to receive {}",
+                    std::any::type_name::<$ty>()
+                ))
+            })?
         }
     };
 }
@@ -0,0 +1,114 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{proxy, ProxyOptions, SignalError, SignalResult};
+use serde::Deserialize;
+
+/// A region returned by the LiveKit Cloud region settings endpoint
+#[derive(Debug, Clone, Deserialize)]
+pub struct RegionInfo {
+    pub region: String,
+    pub url: String,
+    #[serde(default, deserialize_with = "deserialize_distance")]
+    pub distance: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct RegionSettings {
+    #[serde(default)]
+    regions: Vec<RegionInfo>,
+}
+
+/// The region settings are only served by LiveKit Cloud
+pub fn is_cloud_url(url: &str) -> bool {
+    let Ok(url) = url::Url::parse(url) else {
+        return false;
+    };
+
+    url.host_str()
+        .map(|host| host.ends_with(".livekit.cloud") || host.ends_with(".livekit.run"))
+        .unwrap_or(false)
+}
+
+/// Fetch the available regions for a LiveKit Cloud project, sorted by distance.
+/// The request goes through the proxy, or the one of the env vars when None
+pub async fn fetch_regions(
+    url: &str,
+    token: &str,
+    proxy: Option<ProxyOptions>,
+) -> SignalResult<Vec<RegionInfo>> {
+    let mut settings_url = url::Url::parse(url)?;
+    let scheme = if matches!(settings_url.scheme(), "wss" | "https") {
+        "https"
+    } else {
+        "http"
+    };
+    settings_url.set_scheme(scheme).unwrap();
+    settings_url.set_path("/settings/regions");
+    settings_url.set_query(None);
+
+    let res = proxy::http_client(proxy, &settings_url)?
+        .get(settings_url.as_str())
+        .bearer_auth(token)
+        .send()
+        .await
+        .map_err(|err| SignalError::RegionError(err.to_string()))?;
+
+    let status = res.status();
+    if !status.is_success() {
+        let body = res.text().await.ok().unwrap_or_default();
+        if status.is_client_error() {
+            return Err(SignalError::Client(status, body));
+        }
+        return Err(SignalError::Server(status, body));
+    }
+
+    let mut settings: RegionSettings = res
+        .json()
+        .await
+        .map_err(|err| SignalError::RegionError(err.to_string()))?;
+
+    settings.regions.sort_by_key(|region| region.distance);
+    Ok(settings.regions)
+}
+
+// int64 fields are encoded as strings in the protobuf JSON mapping
+fn deserialize_distance<'de, D>(deserializer: D) -> Result<i64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Distance {
+        Number(i64),
+        String(String),
+    }
+
+    match Distance::deserialize(deserializer)? {
+        Distance::Number(distance) => Ok(distance),
+        Distance::String(distance) => distance.parse().map_err(serde::de::Error::custom),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_cloud_url() {
+        assert!(is_cloud_url("wss://myproject.livekit.cloud"));
+        assert!(!is_cloud_url("ws://localhost:7880"));
+        assert!(!is_cloud_url("not a url"));
+    }
+}
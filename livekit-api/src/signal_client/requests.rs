@@ -0,0 +1,246 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{SignalError, SignalResult};
+use livekit_protocol as proto;
+use std::collections::HashMap;
+use tokio::sync::mpsc;
+
+pub(super) type ResponseReceiver = mpsc::UnboundedReceiver<SignalResult<proto::TrackInfo>>;
+
+/// Identifies the server response answering a request
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(super) enum RequestKey {
+    /// TrackPublished with the cid of the AddTrackRequest
+    AddTrack(String),
+    /// Update of the local participant with the requested muted state of the track
+    Mute { sid: String, muted: bool },
+    /// Offer of the subscriber containing the track, or a SubscriptionResponse when the
+    /// subscription failed
+    Subscription(String),
+}
+
+/// Requests waiting for their response, resolved by the signal task
+#[derive(Default)]
+pub(super) struct PendingRequests {
+    // Taken from the last JoinResponse, a rejoin assigns a new sid
+    local_sid: String,
+    pending: HashMap<RequestKey, mpsc::UnboundedSender<SignalResult<proto::TrackInfo>>>,
+}
+
+impl PendingRequests {
+    /// The receiver gets the response of the first key answered. A newer mute or
+    /// subscription request replaces the pending one, whose receiver is then closed
+    pub fn register(&mut self, keys: Vec<RequestKey>) -> SignalResult<ResponseReceiver> {
+        self.pending.retain(|_, tx| !tx.is_closed());
+
+        for key in &keys {
+            if let RequestKey::AddTrack(cid) = key {
+                if self.pending.contains_key(key) {
+                    return Err(SignalError::DuplicateRequest(cid.clone()));
+                }
+            }
+        }
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        for key in keys {
+            self.pending.insert(key, tx.clone());
+        }
+        Ok(rx)
+    }
+
+    /// Forget the request, its receiver is closed. The requests are otherwise forgotten
+    /// once their receiver is dropped
+    pub fn remove(&mut self, key: &RequestKey) {
+        self.pending.remove(key);
+    }
+
+    /// Resolve the requests answered by this message
+    pub fn handle_response(&mut self, message: &proto::signal_response::Message) {
+        use proto::signal_response::Message;
+
+        match message {
+            Message::Join(join) => {
+                if let Some(participant) = &join.participant {
+                    self.local_sid = participant.sid.clone();
+                }
+            }
+            Message::TrackPublished(response) => {
+                if let Some(track) = &response.track {
+                    let key = RequestKey::AddTrack(response.cid.clone());
+                    self.resolve(&key, Ok(track.clone()));
+                }
+            }
+            Message::Update(update) => {
+                let local_tracks = update
+                    .participants
+                    .iter()
+                    .filter(|participant| participant.sid == self.local_sid)
+                    .flat_map(|participant| participant.tracks.clone())
+                    .collect::<Vec<_>>();
+
+                for track in local_tracks {
+                    let key = RequestKey::Mute {
+                        sid: track.sid.clone(),
+                        muted: track.muted,
+                    };
+                    self.resolve(&key, Ok(track));
+                }
+            }
+            Message::Offer(offer) => {
+                // The server only answers the failed subscriptions, the others are
+                // added to the subscriber
+                let subscribed = self
+                    .pending
+                    .keys()
+                    .filter(|key| {
+                        matches!(key, RequestKey::Subscription(sid) if offer.sdp.contains(sid.as_str()))
+                    })
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                for key in subscribed {
+                    let RequestKey::Subscription(sid) = &key else {
+                        continue;
+                    };
+                    let track = proto::TrackInfo {
+                        sid: sid.clone(),
+                        ..Default::default()
+                    };
+                    self.resolve(&key, Ok(track));
+                }
+            }
+            Message::SubscriptionResponse(response) => {
+                let key = RequestKey::Subscription(response.track_sid.clone());
+                let err =
+                    SignalError::SubscriptionFailed(response.track_sid.clone(), response.err());
+                self.resolve(&key, Err(err));
+            }
+            _ => {}
+        }
+    }
+
+    /// Fail the pending requests, the connection closed before their response
+    pub fn close(&mut self) {
+        for (_, tx) in self.pending.drain() {
+            let _ = tx.send(Err(SignalError::ConnectionClosed));
+        }
+    }
+
+    fn resolve(&mut self, key: &RequestKey, result: SignalResult<proto::TrackInfo>) {
+        if let Some(tx) = self.pending.remove(key) {
+            let _ = tx.send(result);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(sid: &str, muted: bool) -> proto::TrackInfo {
+        proto::TrackInfo {
+            sid: sid.to_owned(),
+            muted,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_responses() {
+        use proto::signal_response::Message;
+
+        let mut requests = PendingRequests::default();
+        requests.handle_response(&Message::Join(proto::JoinResponse {
+            participant: Some(proto::ParticipantInfo {
+                sid: "PA_local".to_owned(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }));
+        let add_track = RequestKey::AddTrack("cid".to_owned());
+        let mut published = requests.register(vec![add_track.clone()]).unwrap();
+        assert!(matches!(
+            requests.register(vec![add_track]),
+            Err(SignalError::DuplicateRequest(_))
+        ));
+
+        requests.handle_response(&Message::TrackPublished(proto::TrackPublishedResponse {
+            cid: "cid".to_owned(),
+            track: Some(track("TR_1", false)),
+        }));
+        assert_eq!(published.try_recv().unwrap().unwrap().sid, "TR_1");
+
+        // Only the updates of the local participant with the requested state answer a mute
+        let mute = RequestKey::Mute {
+            sid: "TR_1".to_owned(),
+            muted: true,
+        };
+        let mut muted = requests.register(vec![mute]).unwrap();
+        let update = |sid: &str, muted| {
+            Message::Update(proto::ParticipantUpdate {
+                participants: vec![proto::ParticipantInfo {
+                    sid: sid.to_owned(),
+                    tracks: vec![track("TR_1", muted)],
+                    ..Default::default()
+                }],
+            })
+        };
+        requests.handle_response(&update("PA_remote", true));
+        requests.handle_response(&update("PA_local", false));
+        assert!(muted.try_recv().is_err());
+        requests.handle_response(&update("PA_local", true));
+        assert!(muted.try_recv().unwrap().unwrap().muted);
+
+        let keys = vec![
+            RequestKey::Subscription("TR_2".to_owned()),
+            RequestKey::Subscription("TR_3".to_owned()),
+        ];
+        let mut subscription = requests.register(keys).unwrap();
+        requests.handle_response(&Message::SubscriptionResponse(
+            proto::SubscriptionResponse {
+                track_sid: "TR_3".to_owned(),
+                err: proto::SubscriptionError::SeTrackNotfound as i32,
+            },
+        ));
+        assert!(matches!(
+            subscription.try_recv(),
+            Ok(Err(SignalError::SubscriptionFailed(sid, _))) if sid == "TR_3"
+        ));
+
+        // The successful subscriptions resolve with the offer adding the track
+        let mut subscription = requests
+            .register(vec![RequestKey::Subscription("TR_4".to_owned())])
+            .unwrap();
+        let offer = |sdp: &str| {
+            Message::Offer(proto::SessionDescription {
+                r#type: "offer".to_owned(),
+                sdp: sdp.to_owned(),
+            })
+        };
+        requests.handle_response(&offer("a=msid:PA_remote|TR_5 TR_5"));
+        assert!(subscription.try_recv().is_err());
+        requests.handle_response(&offer("a=msid:PA_remote|TR_4 TR_4"));
+        assert_eq!(subscription.try_recv().unwrap().unwrap().sid, "TR_4");
+
+        let mut subscription = requests
+            .register(vec![RequestKey::Subscription("TR_6".to_owned())])
+            .unwrap();
+        requests.close();
+        assert!(matches!(
+            subscription.try_recv(),
+            Ok(Err(SignalError::ConnectionClosed))
+        ));
+    }
+}
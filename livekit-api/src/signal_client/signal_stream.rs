@@ -12,18 +12,35 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{SignalError, SignalResult};
+use super::transport::TransportStream;
+use super::{get_proxy, proxy, SignalError, SignalOptions, SignalResult};
 use futures_util::stream::{SplitSink, SplitStream};
 use futures_util::{SinkExt, StreamExt};
 use livekit_protocol as proto;
+use parking_lot::Mutex;
 use prost::Message as ProstMessage;
+use std::time::{Duration, Instant};
 use tokio::net::TcpStream;
 use tokio::sync::{mpsc, oneshot};
 use tokio::task::JoinHandle;
-use tokio_tungstenite::tungstenite::Message;
-use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+use tokio_tungstenite::tungstenite::error::UrlError;
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
 
-type WebSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+type WebSocket = WebSocketStream<MaybeTlsStream<Box<dyn TransportStream>>>;
+
+/// Connections opened by SignalStream::prepare are only reused within this delay, the
+/// server may close them once idle for too long
+pub const PREPARED_STREAM_TTL: Duration = Duration::from_secs(30);
+
+// Connection opened ahead of the WebSocket, used by the next connection to its address
+struct PreparedStream {
+    addr: String,
+    opened_at: Instant,
+    stream: Box<dyn TransportStream>,
+}
+
+static PREPARED_STREAM: Mutex<Option<PreparedStream>> = parking_lot::const_mutex(None);
 
 #[derive(Debug)]
 enum InternalMessage {
@@ -55,6 +72,7 @@ impl SignalStream {
     /// closed.
     pub async fn connect(
         mut url: url::Url,
+        options: &SignalOptions,
     ) -> SignalResult<(
         Self,
         mpsc::UnboundedReceiver<Box<proto::signal_response::Message>>,
@@ -68,7 +86,12 @@ impl SignalStream {
             url.set_scheme("ws").unwrap();
         }
 
-        let (ws_stream, _) = connect_async(url).await?;
+        let ws_stream =
+            tokio::time::timeout(options.connect_timeout, Self::connect_ws(url, options))
+                .await
+                .map_err(|_| {
+                    SignalError::Timeout("failed to connect the WebSocket".to_owned())
+                })??;
         let (ws_writer, ws_reader) = ws_stream.split();
 
         let (emitter, events) = mpsc::unbounded_channel();
@@ -86,12 +109,96 @@ impl SignalStream {
         ))
     }
 
+    /// Open the connection to the server of the url (TCP, proxy or custom transport) without
+    /// joining the room, the next connect to the same server reuses it. The TLS and WebSocket
+    /// handshakes are left to connect, the WebSocket handshake joins the room
+    pub async fn prepare(mut url: url::Url, options: &SignalOptions) -> SignalResult<()> {
+        if url.scheme() == "https" {
+            url.set_scheme("wss").unwrap();
+        } else if url.scheme() == "http" {
+            url.set_scheme("ws").unwrap();
+        }
+
+        let addr = stream_addr(&url).ok_or(WsError::Url(UrlError::NoHostName))?;
+        let stream =
+            tokio::time::timeout(options.connect_timeout, Self::open_stream(&url, options))
+                .await
+                .map_err(|_| SignalError::Timeout("failed to open the connection".to_owned()))??;
+
+        *PREPARED_STREAM.lock() = Some(PreparedStream {
+            addr,
+            opened_at: Instant::now(),
+            stream,
+        });
+        Ok(())
+    }
+
+    async fn connect_ws(url: url::Url, options: &SignalOptions) -> SignalResult<WebSocket> {
+        if let Some(stream) = take_prepared_stream(&url) {
+            match Self::handshake(url.clone(), stream, options).await {
+                Ok(ws_stream) => return Ok(ws_stream),
+                // e.g. closed by the server in the meantime
+                Err(err) => log::debug!("prepared connection unusable, reconnecting: {}", err),
+            }
+        }
+
+        let stream = Self::open_stream(&url, options).await?;
+        Self::handshake(url, stream, options).await
+    }
+
+    async fn open_stream(
+        url: &url::Url,
+        options: &SignalOptions,
+    ) -> SignalResult<Box<dyn TransportStream>> {
+        let stream: Box<dyn TransportStream> = if let Some(transport) = &options.transport {
+            transport.connect(url).await.map_err(WsError::Io)?
+        } else if let Some(proxy) = get_proxy(url, options) {
+            log::info!("connecting to SignalClient through a proxy");
+            proxy::connect(&proxy, url, &options.tls).await?
+        } else {
+            let host = url.host_str().ok_or(WsError::Url(UrlError::NoHostName))?;
+            let port = url
+                .port_or_known_default()
+                .ok_or(WsError::Url(UrlError::UnsupportedUrlScheme))?;
+            let stream = TcpStream::connect((host, port))
+                .await
+                .map_err(WsError::Io)?;
+            stream.set_nodelay(true).map_err(WsError::Io)?;
+            Box::new(stream)
+        };
+        Ok(stream)
+    }
+
+    async fn handshake(
+        url: url::Url,
+        stream: Box<dyn TransportStream>,
+        options: &SignalOptions,
+    ) -> SignalResult<WebSocket> {
+        let connector = options.tls.connector()?;
+
+        #[cfg(any(feature = "native-tls", feature = "__rustls-tls"))]
+        let (ws_stream, _) =
+            tokio_tungstenite::client_async_tls_with_config(url, stream, None, connector).await?;
+
+        #[cfg(not(any(feature = "native-tls", feature = "__rustls-tls")))]
+        let (ws_stream, _) = {
+            let _ = connector;
+            if url.scheme() == "wss" {
+                Err(WsError::Url(UrlError::TlsFeatureNotEnabled))?;
+            }
+            let stream = MaybeTlsStream::Plain(stream);
+            tokio_tungstenite::client_async_with_config(url, stream, None).await?
+        };
+
+        Ok(ws_stream)
+    }
+
     /// Close the websocket
     /// It sends a CloseFrame to the server before closing
-    pub async fn close(self) {
+    pub async fn close(mut self) {
         let _ = self.internal_tx.send(InternalMessage::Close).await;
-        let _ = self.write_handle.await;
-        let _ = self.read_handle.await;
+        let _ = (&mut self.write_handle).await;
+        let _ = (&mut self.read_handle).await;
     }
 
     /// Send a SignalRequest to the websocket
@@ -180,3 +287,56 @@ impl SignalStream {
         let _ = internal_tx.send(InternalMessage::Close).await;
     }
 }
+
+impl Drop for SignalStream {
+    // The WebSocket is dropped without a close frame when the stream isn't closed,
+    // e.g. when the connect future is cancelled
+    fn drop(&mut self) {
+        self.read_handle.abort();
+        self.write_handle.abort();
+    }
+}
+
+fn stream_addr(url: &url::Url) -> Option<String> {
+    Some(format!(
+        "{}:{}",
+        url.host_str()?,
+        url.port_or_known_default()?
+    ))
+}
+
+/// The prepared connection is used once, by the first connection to its address
+fn take_prepared_stream(url: &url::Url) -> Option<Box<dyn TransportStream>> {
+    let addr = stream_addr(url)?;
+    let mut prepared = PREPARED_STREAM.lock();
+    if prepared.as_ref()?.addr != addr {
+        return None;
+    }
+
+    let prepared = prepared.take()?;
+    (prepared.opened_at.elapsed() < PREPARED_STREAM_TTL).then_some(prepared.stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn test_prepared_stream() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let url = |port: u16| url::Url::parse(&format!("http://127.0.0.1:{}/rtc", port)).unwrap();
+
+        SignalStream::prepare(url(port), &SignalOptions::default())
+            .await
+            .unwrap();
+        let (_accepted, _) = listener.accept().await.unwrap();
+
+        // Only the connections to the same server use it, once
+        let ws_url = url::Url::parse(&format!("ws://127.0.0.1:{}/rtc", port)).unwrap();
+        assert!(take_prepared_stream(&url(port + 1)).is_none());
+        assert!(take_prepared_stream(&ws_url).is_some());
+        assert!(take_prepared_stream(&ws_url).is_none());
+    }
+}
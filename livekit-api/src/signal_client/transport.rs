@@ -0,0 +1,162 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{SignalError, SignalResult};
+use std::fmt::Debug;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_tungstenite::Connector;
+
+/// Byte stream the signal WebSocket runs on
+pub trait TransportStream: AsyncRead + AsyncWrite + Send + Unpin {}
+
+impl<T: AsyncRead + AsyncWrite + Send + Unpin> TransportStream for T {}
+
+pub type TransportFuture<'a> =
+    Pin<Box<dyn Future<Output = io::Result<Box<dyn TransportStream>>> + Send + 'a>>;
+
+/// Opens the connection to the signal server, e.g. to go through a custom tunnel.
+/// The TLS (for wss urls, see TlsOptions) and the WebSocket handshakes are done on top
+/// of the returned stream
+pub trait SignalTransport: Debug + Send + Sync {
+    fn connect<'a>(&'a self, url: &'a url::Url) -> TransportFuture<'a>;
+}
+
+/// PEM encoded certificate chain and private key presented to the server
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct ClientCertificate {
+    pub certificate_chain: Vec<u8>,
+    pub private_key: Vec<u8>,
+}
+
+impl Debug for ClientCertificate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientCertificate").finish_non_exhaustive()
+    }
+}
+
+/// TLS of the signal connection, for deployments with a private PKI.
+/// Requires one of the rustls features, the default options keep the TLS of the
+/// enabled TLS feature untouched
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TlsOptions {
+    /// PEM encoded CA certificates trusted in addition to the system roots
+    pub ca_certificates: Vec<Vec<u8>>,
+    /// Only trust ca_certificates
+    pub disable_system_roots: bool,
+    pub client_certificate: Option<ClientCertificate>,
+}
+
+impl TlsOptions {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// None uses the connector of the enabled TLS feature
+    pub(super) fn connector(&self) -> SignalResult<Option<Connector>> {
+        if self.is_default() {
+            return Ok(None);
+        }
+
+        #[cfg(feature = "__rustls-tls")]
+        {
+            Ok(Some(Connector::Rustls(std::sync::Arc::new(
+                self.rustls_config()?,
+            ))))
+        }
+
+        #[cfg(not(feature = "__rustls-tls"))]
+        Err(SignalError::Tls(
+            "custom TLS options require one of the rustls features".to_owned(),
+        ))
+    }
+
+    #[cfg(feature = "__rustls-tls")]
+    pub(super) fn rustls_config(&self) -> SignalResult<rustls::ClientConfig> {
+        let mut roots = rustls::RootCertStore::empty();
+        if !self.disable_system_roots {
+            #[cfg(feature = "rustls-tls-native-roots")]
+            for cert in rustls_native_certs::load_native_certs().map_err(tls_err)? {
+                // Skip the system certificates rustls can't parse
+                let _ = roots.add(&rustls::Certificate(cert.0));
+            }
+
+            #[cfg(feature = "rustls-tls-webpki-roots")]
+            roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|anchor| {
+                rustls::OwnedTrustAnchor::from_subject_spki_name_constraints(
+                    anchor.subject,
+                    anchor.spki,
+                    anchor.name_constraints,
+                )
+            }));
+        }
+
+        for pem in &self.ca_certificates {
+            for cert in rustls_pemfile::certs(&mut pem.as_slice()).map_err(tls_err)? {
+                roots.add(&rustls::Certificate(cert)).map_err(tls_err)?;
+            }
+        }
+
+        let builder = rustls::ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots);
+
+        let Some(client) = &self.client_certificate else {
+            return Ok(builder.with_no_client_auth());
+        };
+
+        let chain = rustls_pemfile::certs(&mut client.certificate_chain.as_slice())
+            .map_err(tls_err)?
+            .into_iter()
+            .map(rustls::Certificate)
+            .collect();
+        let key = rustls_pemfile::read_all(&mut client.private_key.as_slice())
+            .map_err(tls_err)?
+            .into_iter()
+            .find_map(|item| match item {
+                rustls_pemfile::Item::RSAKey(key)
+                | rustls_pemfile::Item::PKCS8Key(key)
+                | rustls_pemfile::Item::ECKey(key) => Some(rustls::PrivateKey(key)),
+                _ => None,
+            })
+            .ok_or_else(|| SignalError::Tls("no private key found".to_owned()))?;
+
+        builder.with_client_auth_cert(chain, key).map_err(tls_err)
+    }
+}
+
+#[cfg(feature = "__rustls-tls")]
+fn tls_err(err: impl std::fmt::Display) -> SignalError {
+    SignalError::Tls(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tls_connector() {
+        assert!(TlsOptions::default().connector().unwrap().is_none());
+
+        let options = TlsOptions {
+            ca_certificates: vec![b"not a certificate".to_vec()],
+            disable_system_roots: true,
+            ..Default::default()
+        };
+        // Without any certificate in the PEM, only the system roots are disabled
+        assert_eq!(options.connector().is_ok(), cfg!(feature = "__rustls-tls"));
+    }
+}
@@ -30,6 +30,49 @@ pub enum WebhookError {
     InvalidData(#[from] serde_json::Error),
 }
 
+/// The `event` field of a WebhookEvent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebhookEventType {
+    RoomStarted,
+    RoomFinished,
+    ParticipantJoined,
+    ParticipantLeft,
+    TrackPublished,
+    TrackUnpublished,
+    EgressStarted,
+    EgressUpdated,
+    EgressEnded,
+    IngressStarted,
+    IngressEnded,
+    /// Sent by a newer server
+    Other(String),
+}
+
+impl WebhookEventType {
+    pub fn of(event: &proto::WebhookEvent) -> Self {
+        Self::from(event.event.as_str())
+    }
+}
+
+impl From<&str> for WebhookEventType {
+    fn from(event: &str) -> Self {
+        match event {
+            "room_started" => Self::RoomStarted,
+            "room_finished" => Self::RoomFinished,
+            "participant_joined" => Self::ParticipantJoined,
+            "participant_left" => Self::ParticipantLeft,
+            "track_published" => Self::TrackPublished,
+            "track_unpublished" => Self::TrackUnpublished,
+            "egress_started" => Self::EgressStarted,
+            "egress_updated" => Self::EgressUpdated,
+            "egress_ended" => Self::EgressEnded,
+            "ingress_started" => Self::IngressStarted,
+            "ingress_ended" => Self::IngressEnded,
+            other => Self::Other(other.to_owned()),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct WebhookReceiver {
     token_verifier: TokenVerifier,
@@ -40,12 +83,21 @@ impl WebhookReceiver {
         Self { token_verifier }
     }
 
+    /// Verify and decode a webhook request, `auth_token` is the value of its Authorization
+    /// header (the token signed by the server, with or without the Bearer scheme)
     pub fn receive(
         &self,
         body: &str,
         auth_token: &str,
     ) -> Result<proto::WebhookEvent, WebhookError> {
+        let auth_token = auth_token
+            .strip_prefix("Bearer ")
+            .unwrap_or(auth_token)
+            .trim();
         let claims = self.token_verifier.verify(auth_token)?;
+        if claims.sha256.is_empty() {
+            return Err(WebhookError::InvalidSignature);
+        }
 
         let mut hasher = Sha256::new();
         hasher.update(body);
@@ -59,3 +111,93 @@ impl WebhookReceiver {
         Ok(serde_json::from_str(body)?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::access_token::AccessToken;
+
+    const TEST_API_KEY: &str = "myapikey";
+    const TEST_API_SECRET: &str = "thiskeyistotallyunsafe";
+    const TEST_BODY: &str = r#"{"event":"participant_joined","room":{"sid":"RM_1","name":"my-room"},
+        "participant":{"sid":"PA_1","identity":"alice"},"id":"EV_1","createdAt":"1700000000"}"#;
+
+    fn auth_token(body: &str) -> String {
+        let hash = Sha256::digest(body.as_bytes());
+        AccessToken::with_api_key(TEST_API_KEY, TEST_API_SECRET)
+            .with_sha256(&base64::engine::general_purpose::STANDARD.encode(hash))
+            .to_jwt()
+            .unwrap()
+    }
+
+    #[test]
+    fn receive_event() {
+        let receiver =
+            WebhookReceiver::new(TokenVerifier::with_api_key(TEST_API_KEY, TEST_API_SECRET));
+        let token = auth_token(TEST_BODY);
+
+        let event = receiver.receive(TEST_BODY, &token).unwrap();
+        assert_eq!(
+            WebhookEventType::of(&event),
+            WebhookEventType::ParticipantJoined
+        );
+        assert_eq!(event.room.unwrap().name, "my-room");
+        assert_eq!(event.participant.unwrap().identity, "alice");
+
+        let bearer = format!("Bearer {}", token);
+        assert!(receiver.receive(TEST_BODY, &bearer).is_ok());
+
+        let tampered = TEST_BODY.replace("alice", "mallory");
+        assert!(matches!(
+            receiver.receive(&tampered, &token),
+            Err(WebhookError::InvalidSignature)
+        ));
+
+        let other_secret = WebhookReceiver::new(TokenVerifier::with_api_key(TEST_API_KEY, "other"));
+        assert!(matches!(
+            other_secret.receive(TEST_BODY, &token),
+            Err(WebhookError::InvalidAuth(_))
+        ));
+    }
+
+    #[test]
+    fn participant_info() {
+        let body = r#"{"event":"participant_joined","room":{"sid":"RM_1","name":"my-room"},
+            "participant":{"sid":"PA_1","identity":"alice","attributes":{"role":"host"},
+            "kind":"AGENT"},
+            "id":"EV_2","createdAt":"1700000000"}"#;
+        let receiver =
+            WebhookReceiver::new(TokenVerifier::with_api_key(TEST_API_KEY, TEST_API_SECRET));
+
+        let event = receiver.receive(body, &auth_token(body)).unwrap();
+        let participant = event.participant.unwrap();
+        assert_eq!(participant.attributes["role"], "host");
+        assert_eq!(participant.kind(), proto::participant_info::Kind::Agent);
+    }
+
+    #[test]
+    fn unsigned_body() {
+        let receiver =
+            WebhookReceiver::new(TokenVerifier::with_api_key(TEST_API_KEY, TEST_API_SECRET));
+        let token = AccessToken::with_api_key(TEST_API_KEY, TEST_API_SECRET)
+            .to_jwt()
+            .unwrap();
+
+        assert!(matches!(
+            receiver.receive("", &token),
+            Err(WebhookError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn event_types() {
+        assert_eq!(
+            WebhookEventType::from("egress_ended"),
+            WebhookEventType::EgressEnded
+        );
+        assert_eq!(
+            WebhookEventType::from("sip_call_started"),
+            WebhookEventType::Other("sip_call_started".to_owned())
+        );
+    }
+}
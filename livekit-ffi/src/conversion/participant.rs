@@ -14,6 +14,7 @@
 
 use crate::proto;
 use crate::server::room::FfiParticipant;
+use livekit::participant::ParticipantKind;
 
 impl From<&FfiParticipant> for proto::ParticipantInfo {
     fn from(value: &FfiParticipant) -> Self {
@@ -23,6 +24,19 @@ impl From<&FfiParticipant> for proto::ParticipantInfo {
             name: participant.name(),
             identity: participant.identity().into(),
             metadata: participant.metadata(),
+            kind: proto::ParticipantKind::from(participant.kind()).into(),
+        }
+    }
+}
+
+impl From<ParticipantKind> for proto::ParticipantKind {
+    fn from(value: ParticipantKind) -> Self {
+        match value {
+            ParticipantKind::Standard => Self::Standard,
+            ParticipantKind::Ingress => Self::Ingress,
+            ParticipantKind::Egress => Self::Egress,
+            ParticipantKind::Sip => Self::Sip,
+            ParticipantKind::Agent => Self::Agent,
         }
     }
 }
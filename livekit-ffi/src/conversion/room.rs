@@ -15,10 +15,11 @@
 use crate::proto;
 use crate::server::room::FfiRoom;
 use livekit::e2ee::key_provider::{KeyProvider, KeyProviderOptions};
-use livekit::e2ee::{E2eeOptions, EncryptionType};
+use livekit::e2ee::{E2eeOptions, EncryptionAlgorithm, EncryptionType};
 use livekit::options::{AudioEncoding, TrackPublishOptions, VideoEncoding};
 use livekit::prelude::*;
 use livekit::webrtc::native::frame_cryptor::EncryptionState;
+use livekit::DisconnectReason;
 
 impl From<EncryptionState> for proto::EncryptionState {
     fn from(value: EncryptionState) -> Self {
@@ -40,6 +41,29 @@ impl From<ConnectionQuality> for proto::ConnectionQuality {
             ConnectionQuality::Excellent => Self::QualityExcellent,
             ConnectionQuality::Good => Self::QualityGood,
             ConnectionQuality::Poor => Self::QualityPoor,
+            ConnectionQuality::Lost => Self::QualityLost,
+        }
+    }
+}
+
+impl From<DisconnectReason> for proto::DisconnectReason {
+    fn from(value: DisconnectReason) -> Self {
+        match value {
+            DisconnectReason::UnknownReason => Self::UnknownReason,
+            DisconnectReason::ClientInitiated => Self::ClientInitiated,
+            DisconnectReason::DuplicateIdentity => Self::DuplicateIdentity,
+            DisconnectReason::ServerShutdown => Self::ServerShutdown,
+            DisconnectReason::ParticipantRemoved => Self::ParticipantRemoved,
+            DisconnectReason::RoomDeleted => Self::RoomDeleted,
+            DisconnectReason::StateMismatch => Self::StateMismatch,
+            DisconnectReason::JoinFailure => Self::JoinFailure,
+            DisconnectReason::Migration => Self::Migration,
+            DisconnectReason::SignalClose => Self::SignalClose,
+            DisconnectReason::RoomClosed => Self::RoomClosed,
+            DisconnectReason::UserUnavailable => Self::UserUnavailable,
+            DisconnectReason::UserRejected => Self::UserRejected,
+            DisconnectReason::SipTrunkFailure => Self::SipTrunkFailure,
+            DisconnectReason::ReconnectFailed => Self::ReconnectFailed,
         }
     }
 }
@@ -74,12 +98,25 @@ impl From<EncryptionType> for proto::EncryptionType {
     }
 }
 
+impl From<proto::EncryptionAlgorithm> for EncryptionAlgorithm {
+    fn from(value: proto::EncryptionAlgorithm) -> Self {
+        match value {
+            proto::EncryptionAlgorithm::AesGcm => Self::AesGcm,
+            proto::EncryptionAlgorithm::AesCbc => Self::AesCbc,
+        }
+    }
+}
+
 impl From<proto::KeyProviderOptions> for KeyProviderOptions {
     fn from(value: proto::KeyProviderOptions) -> Self {
         Self {
+            algorithm: value.algorithm().into(),
             ratchet_window_size: value.ratchet_window_size,
             ratchet_salt: value.ratchet_salt,
             uncrypted_magic_bytes: value.uncrypted_magic_bytes,
+            sif_trailer: value.sif_trailer,
+            disable_server_sif_trailer: value.disable_server_sif_trailer,
+            key_store: None,
         }
     }
 }
@@ -100,6 +137,7 @@ impl From<proto::RoomOptions> for RoomOptions {
                 } else {
                     KeyProvider::new(provider_opts.into())
                 },
+                key_rotation: None,
             })
         });
 
@@ -108,6 +146,7 @@ impl From<proto::RoomOptions> for RoomOptions {
             auto_subscribe: value.auto_subscribe,
             dynacast: value.dynacast,
             e2ee,
+            ..Default::default()
         }
     }
 }
@@ -140,6 +179,13 @@ impl From<proto::TrackPublishOptions> for TrackPublishOptions {
             dtx: opts.dtx,
             red: opts.red,
             simulcast: opts.simulcast,
+            simulcast_layers: Vec::new(),
+            encryption: None,
+            degradation_preference: None,
+            content_hint: None,
+            scalability_mode: None,
+            backup_codec: None,
+            stereo: false,
         }
     }
 }
@@ -36,6 +36,8 @@ impl proto::VideoFrameInfo {
     {
         Self {
             timestamp_us: frame.timestamp_us,
+            rtp_timestamp: frame.rtp_timestamp,
+            ntp_time_ms: frame.ntp_time_ms,
             rotation: proto::VideoRotation::from(frame.rotation).into(),
         }
     }
@@ -132,6 +134,7 @@ impl From<proto::VideoCodec> for VideoCodec {
             proto::VideoCodec::Vp8 => Self::VP8,
             proto::VideoCodec::H264 => Self::H264,
             proto::VideoCodec::Av1 => Self::AV1,
+            proto::VideoCodec::Vp9 => Self::VP9,
         }
     }
 }
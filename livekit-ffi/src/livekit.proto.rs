@@ -23,6 +23,17 @@ pub struct KeyProviderOptions {
     pub ratchet_salt: ::prost::alloc::vec::Vec<u8>,
     #[prost(bytes="vec", tag="4")]
     pub uncrypted_magic_bytes: ::prost::alloc::vec::Vec<u8>,
+    /// Custom Server-Injected-Frame trailer, the server one is used if not specified
+    #[prost(bytes="vec", optional, tag="5")]
+    pub sif_trailer: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    #[prost(bool, tag="6")]
+    pub disable_server_sif_trailer: bool,
+    /// Emit E2eeKeyRequested events when a participant key is missing (per-participant keys only)
+    /// The key can then be provided using a SetKeyRequest
+    #[prost(bool, tag="7")]
+    pub request_keys: bool,
+    #[prost(enumeration="EncryptionAlgorithm", tag="8")]
+    pub algorithm: i32,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -259,6 +270,32 @@ impl EncryptionType {
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum EncryptionAlgorithm {
+    AesGcm = 0,
+    AesCbc = 1,
+}
+impl EncryptionAlgorithm {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            EncryptionAlgorithm::AesGcm => "AES_GCM",
+            EncryptionAlgorithm::AesCbc => "AES_CBC",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "AES_GCM" => Some(Self::AesGcm),
+            "AES_CBC" => Some(Self::AesCbc),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum EncryptionState {
     New = 0,
     Ok = 1,
@@ -513,6 +550,8 @@ pub struct ParticipantInfo {
     pub identity: ::prost::alloc::string::String,
     #[prost(string, tag="4")]
     pub metadata: ::prost::alloc::string::String,
+    #[prost(enumeration="ParticipantKind", tag="5")]
+    pub kind: i32,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -522,6 +561,41 @@ pub struct OwnedParticipant {
     #[prost(message, optional, tag="2")]
     pub info: ::core::option::Option<ParticipantInfo>,
 }
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ParticipantKind {
+    Standard = 0,
+    Ingress = 1,
+    Egress = 2,
+    Sip = 3,
+    Agent = 4,
+}
+impl ParticipantKind {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ParticipantKind::Standard => "PARTICIPANT_KIND_STANDARD",
+            ParticipantKind::Ingress => "PARTICIPANT_KIND_INGRESS",
+            ParticipantKind::Egress => "PARTICIPANT_KIND_EGRESS",
+            ParticipantKind::Sip => "PARTICIPANT_KIND_SIP",
+            ParticipantKind::Agent => "PARTICIPANT_KIND_AGENT",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "PARTICIPANT_KIND_STANDARD" => Some(Self::Standard),
+            "PARTICIPANT_KIND_INGRESS" => Some(Self::Ingress),
+            "PARTICIPANT_KIND_EGRESS" => Some(Self::Egress),
+            "PARTICIPANT_KIND_SIP" => Some(Self::Sip),
+            "PARTICIPANT_KIND_AGENT" => Some(Self::Agent),
+            _ => None,
+        }
+    }
+}
 /// Allocate a new VideoFrameBuffer
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -677,6 +751,12 @@ pub struct VideoFrameInfo {
     pub timestamp_us: i64,
     #[prost(enumeration="VideoRotation", tag="2")]
     pub rotation: i32,
+    /// Only set on received frames
+    #[prost(uint32, tag="3")]
+    pub rtp_timestamp: u32,
+    /// Capture time in the NTP clock of the sender, 0 if unknown
+    #[prost(int64, tag="4")]
+    pub ntp_time_ms: i64,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -835,6 +915,7 @@ pub enum VideoCodec {
     Vp8 = 0,
     H264 = 1,
     Av1 = 2,
+    Vp9 = 3,
 }
 impl VideoCodec {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -846,6 +927,7 @@ impl VideoCodec {
             VideoCodec::Vp8 => "VP8",
             VideoCodec::H264 => "H264",
             VideoCodec::Av1 => "AV1",
+            VideoCodec::Vp9 => "VP9",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -854,6 +936,7 @@ impl VideoCodec {
             "VP8" => Some(Self::Vp8),
             "H264" => Some(Self::H264),
             "AV1" => Some(Self::Av1),
+            "VP9" => Some(Self::Vp9),
             _ => None,
         }
     }
@@ -1247,7 +1330,7 @@ pub struct OwnedBuffer {
 pub struct RoomEvent {
     #[prost(uint64, tag="1")]
     pub room_handle: u64,
-    #[prost(oneof="room_event::Message", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 18, 19, 20, 21")]
+    #[prost(oneof="room_event::Message", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 18, 19, 20, 21, 22")]
     pub message: ::core::option::Option<room_event::Message>,
 }
 /// Nested message and enum types in `RoomEvent`.
@@ -1294,6 +1377,8 @@ pub mod room_event {
         Reconnected(super::Reconnected),
         #[prost(message, tag="21")]
         E2eeStateChanged(super::E2eeStateChanged),
+        #[prost(message, tag="22")]
+        E2eeKeyRequested(super::E2eeKeyRequested),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -1409,6 +1494,16 @@ pub struct E2eeStateChanged {
     pub participant_sid: ::prost::alloc::string::String,
     #[prost(enumeration="EncryptionState", tag="2")]
     pub state: i32,
+    #[prost(string, tag="3")]
+    pub track_sid: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct E2eeKeyRequested {
+    #[prost(string, tag="1")]
+    pub participant_identity: ::prost::alloc::string::String,
+    #[prost(int32, tag="2")]
+    pub key_index: i32,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -1448,6 +1543,8 @@ pub struct Connected {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Disconnected {
+    #[prost(enumeration="DisconnectReason", tag="1")]
+    pub reason: i32,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -1467,6 +1564,7 @@ pub enum ConnectionQuality {
     QualityPoor = 0,
     QualityGood = 1,
     QualityExcellent = 2,
+    QualityLost = 3,
 }
 impl ConnectionQuality {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1478,6 +1576,7 @@ impl ConnectionQuality {
             ConnectionQuality::QualityPoor => "QUALITY_POOR",
             ConnectionQuality::QualityGood => "QUALITY_GOOD",
             ConnectionQuality::QualityExcellent => "QUALITY_EXCELLENT",
+            ConnectionQuality::QualityLost => "QUALITY_LOST",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1486,6 +1585,7 @@ impl ConnectionQuality {
             "QUALITY_POOR" => Some(Self::QualityPoor),
             "QUALITY_GOOD" => Some(Self::QualityGood),
             "QUALITY_EXCELLENT" => Some(Self::QualityExcellent),
+            "QUALITY_LOST" => Some(Self::QualityLost),
             _ => None,
         }
     }
@@ -1521,6 +1621,71 @@ impl ConnectionState {
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum DisconnectReason {
+    UnknownReason = 0,
+    ClientInitiated = 1,
+    DuplicateIdentity = 2,
+    ServerShutdown = 3,
+    ParticipantRemoved = 4,
+    RoomDeleted = 5,
+    StateMismatch = 6,
+    JoinFailure = 7,
+    ReconnectFailed = 8,
+    Migration = 9,
+    SignalClose = 10,
+    RoomClosed = 11,
+    UserUnavailable = 12,
+    UserRejected = 13,
+    SipTrunkFailure = 14,
+}
+impl DisconnectReason {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            DisconnectReason::UnknownReason => "UNKNOWN_REASON",
+            DisconnectReason::ClientInitiated => "CLIENT_INITIATED",
+            DisconnectReason::DuplicateIdentity => "DUPLICATE_IDENTITY",
+            DisconnectReason::ServerShutdown => "SERVER_SHUTDOWN",
+            DisconnectReason::ParticipantRemoved => "PARTICIPANT_REMOVED",
+            DisconnectReason::RoomDeleted => "ROOM_DELETED",
+            DisconnectReason::StateMismatch => "STATE_MISMATCH",
+            DisconnectReason::JoinFailure => "JOIN_FAILURE",
+            DisconnectReason::ReconnectFailed => "RECONNECT_FAILED",
+            DisconnectReason::Migration => "MIGRATION",
+            DisconnectReason::SignalClose => "SIGNAL_CLOSE",
+            DisconnectReason::RoomClosed => "ROOM_CLOSED",
+            DisconnectReason::UserUnavailable => "USER_UNAVAILABLE",
+            DisconnectReason::UserRejected => "USER_REJECTED",
+            DisconnectReason::SipTrunkFailure => "SIP_TRUNK_FAILURE",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "UNKNOWN_REASON" => Some(Self::UnknownReason),
+            "CLIENT_INITIATED" => Some(Self::ClientInitiated),
+            "DUPLICATE_IDENTITY" => Some(Self::DuplicateIdentity),
+            "SERVER_SHUTDOWN" => Some(Self::ServerShutdown),
+            "PARTICIPANT_REMOVED" => Some(Self::ParticipantRemoved),
+            "ROOM_DELETED" => Some(Self::RoomDeleted),
+            "STATE_MISMATCH" => Some(Self::StateMismatch),
+            "JOIN_FAILURE" => Some(Self::JoinFailure),
+            "RECONNECT_FAILED" => Some(Self::ReconnectFailed),
+            "MIGRATION" => Some(Self::Migration),
+            "SIGNAL_CLOSE" => Some(Self::SignalClose),
+            "ROOM_CLOSED" => Some(Self::RoomClosed),
+            "USER_UNAVAILABLE" => Some(Self::UserUnavailable),
+            "USER_REJECTED" => Some(Self::UserRejected),
+            "SIP_TRUNK_FAILURE" => Some(Self::SipTrunkFailure),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum DataPacketKind {
     KindLossy = 0,
     KindReliable = 1,
@@ -146,8 +146,12 @@ fn on_set_subscribed(
             return Err(FfiError::InvalidRequest("publication is not a RemotePublication".into()));
         };
 
-    let _guard = server.async_runtime.enter();
-    publication.set_subscribed(set_subscribed.subscribe);
+    let publication = publication.clone();
+    server.async_runtime.spawn(async move {
+        if let Err(err) = publication.set_subscribed(set_subscribed.subscribe).await {
+            log::warn!("failed to update the subscription: {:?}", err);
+        }
+    });
     Ok(proto::SetSubscribedResponse {})
 }
 
@@ -14,6 +14,7 @@
 
 use crate::server::{FfiHandle, FfiServer};
 use crate::{proto, FfiError, FfiHandleId, FfiResult};
+use livekit::e2ee::key_provider::{KeyProvider, KeyResolver, KeyResolverFuture};
 use livekit::prelude::*;
 use parking_lot::Mutex;
 use std::collections::HashSet;
@@ -73,6 +74,18 @@ struct Handle {
     close_tx: broadcast::Sender<()>,
 }
 
+/// Forward the missing keys to the FfiClient, which answers using a SetKeyRequest
+struct FfiKeyResolver {
+    tx: mpsc::UnboundedSender<(ParticipantIdentity, i32)>,
+}
+
+impl KeyResolver for FfiKeyResolver {
+    fn resolve_key(&self, identity: ParticipantIdentity, key_index: i32) -> KeyResolverFuture {
+        let _ = self.tx.send((identity, key_index));
+        Box::pin(async { None })
+    }
+}
+
 struct DataPacket {
     data: Vec<u8>,
     kind: DataPacketKind,
@@ -87,13 +100,25 @@ impl FfiRoom {
     ) -> proto::ConnectResponse {
         let async_id = server.next_id();
 
+        let (key_request_tx, key_request_rx) = mpsc::unbounded_channel();
+        let mut options: RoomOptions = connect.options.clone().map(Into::into).unwrap_or_default();
+        let provider_opts = connect
+            .options
+            .as_ref()
+            .and_then(|opts| opts.e2ee.as_ref())
+            .and_then(|e2ee| e2ee.key_provider_options.clone());
+
+        if let (Some(e2ee), Some(provider_opts)) = (options.e2ee.as_mut(), provider_opts) {
+            if provider_opts.request_keys && provider_opts.shared_key.is_none() {
+                e2ee.key_provider = KeyProvider::with_key_resolver(
+                    provider_opts.into(),
+                    FfiKeyResolver { tx: key_request_tx },
+                );
+            }
+        }
+
         let connect = async move {
-            match Room::connect(
-                &connect.url,
-                &connect.token,
-                connect.options.map(Into::into).unwrap_or_default(),
-            )
-            .await
+            match Room::connect(&connect.url, &connect.token, options).await
             {
                 Ok((room, mut events)) => {
                     // Successfully connected to the room
@@ -147,7 +172,13 @@ impl FfiRoom {
                     // Forward events
                     let event_handle = {
                         let close_rx = close_rx.resubscribe();
-                        tokio::spawn(room_task(server, inner.clone(), events, close_rx))
+                        tokio::spawn(room_task(
+                            server,
+                            inner.clone(),
+                            events,
+                            key_request_rx,
+                            close_rx,
+                        ))
                     };
                     let data_handle =
                         tokio::spawn(data_task(server, inner.clone(), data_rx, close_rx)); // Publish data
@@ -364,6 +395,7 @@ async fn room_task(
     server: &'static FfiServer,
     inner: Arc<RoomInner>,
     mut events: mpsc::UnboundedReceiver<livekit::RoomEvent>,
+    mut key_requests: mpsc::UnboundedReceiver<(ParticipantIdentity, i32)>,
     mut close_rx: broadcast::Receiver<()>,
 ) {
     loop {
@@ -371,6 +403,17 @@ async fn room_task(
             Some(event) = events.recv() => {
                 forward_event(server, &inner, event).await;
             },
+            Some((identity, key_index)) = key_requests.recv() => {
+                let _ = server.send_event(proto::ffi_event::Message::RoomEvent(proto::RoomEvent {
+                    room_handle: inner.handle_id,
+                    message: Some(proto::room_event::Message::E2eeKeyRequested(
+                        proto::E2eeKeyRequested {
+                            participant_identity: identity.to_string(),
+                            key_index,
+                        },
+                    )),
+                })).await;
+            },
             _ = close_rx.recv() => {
                 break;
             }
@@ -594,6 +637,7 @@ async fn forward_event(server: &'static FfiServer, inner: &Arc<RoomInner>, event
             payload,
             kind,
             participant,
+            ..
         } => {
             let handle_id = server.next_id();
             let buffer_info = proto::BufferInfo {
@@ -631,9 +675,11 @@ async fn forward_event(server: &'static FfiServer, inner: &Arc<RoomInner>, event
         RoomEvent::Connected { .. } => {
             // Ignore here, we're already sent the event on connect (see above)
         }
-        RoomEvent::Disconnected { reason: _ } => {
+        RoomEvent::Disconnected { reason } => {
             let _ = send_event(proto::room_event::Message::Disconnected(
-                proto::Disconnected {},
+                proto::Disconnected {
+                    reason: proto::DisconnectReason::from(reason).into(),
+                },
             ))
             .await;
         }
@@ -649,10 +695,15 @@ async fn forward_event(server: &'static FfiServer, inner: &Arc<RoomInner>, event
             ))
             .await;
         }
-        RoomEvent::E2eeStateChanged { participant, state } => {
+        RoomEvent::E2eeStateChanged {
+            participant,
+            track_sid,
+            state,
+        } => {
             let _ = send_event(proto::room_event::Message::E2eeStateChanged(
                 proto::E2eeStateChanged {
                     participant_sid: participant.sid().to_string(),
+                    track_sid: track_sid.to_string(),
                     state: proto::EncryptionState::from(state).into(),
                 },
             ))
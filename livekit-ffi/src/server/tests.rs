@@ -332,6 +332,8 @@ fn publish_video_track() {
                             }),
                             frame: Some(proto::VideoFrameInfo {
                                 timestamp_us: 0,
+                                rtp_timestamp: 0,
+                                ntp_time_ms: 0,
                                 rotation: proto::VideoRotation::VideoRotation0 as i32,
                             }),
                         },
@@ -88,6 +88,8 @@ impl FfiVideoSource {
                 let frame = VideoFrame {
                     rotation: frame_info.rotation().into(),
                     timestamp_us: frame_info.timestamp_us,
+                    rtp_timestamp: 0,
+                    ntp_time_ms: 0,
                     buffer,
                 };
 
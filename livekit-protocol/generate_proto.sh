@@ -14,7 +14,7 @@
 # limitations under the License.
 
 
-PROTOCOL=protocol
+PROTOCOL=protocol/protobufs
 OUT_RUST=src
 
 protoc \
@@ -23,8 +23,10 @@ protoc \
     --prost_opt=compile_well_known_types \
     --prost_opt=extern_path=.google.protobuf=::pbjson_types \
     --prost-serde_out=$OUT_RUST \
+    --prost-serde_opt=ignore_unknown_fields \
     $PROTOCOL/livekit_egress.proto \
     $PROTOCOL/livekit_rtc.proto \
     $PROTOCOL/livekit_room.proto \
     $PROTOCOL/livekit_webhook.proto \
+    $PROTOCOL/livekit_sip.proto \
     $PROTOCOL/livekit_models.proto 
\ No newline at end of file
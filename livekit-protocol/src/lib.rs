@@ -19,7 +19,5 @@ pub mod debouncer;
 pub mod enum_dispatch;
 pub mod observer;
 
+// livekit.rs includes the generated serde impls (livekit.serde.rs)
 include!("livekit.rs");
-
-#[cfg(feature = "serde")]
-include!("livekit.serde.rs");
@@ -1,6 +1,205 @@
 // @generated
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MetricsBatch {
+    /// time at which this batch is sent based on a monotonic clock (millisecond resolution)
+    #[prost(int64, tag="1")]
+    pub timestamp_ms: i64,
+    #[prost(message, optional, tag="2")]
+    pub normalized_timestamp: ::core::option::Option<::pbjson_types::Timestamp>,
+    /// To avoid repeating string values, we store them in a separate list and reference them by index
+    /// This is useful for storing participant identities, track names, etc.
+    /// There is also a predefined list of labels that can be used to reference common metrics.
+    /// They have reserved indices from 0 to (METRIC_LABEL_PREDEFINED_MAX_VALUE - 1).
+    /// Indexes pointing at str_data should start from METRIC_LABEL_PREDEFINED_MAX_VALUE, 
+    /// such that str_data\[0\] == index of METRIC_LABEL_PREDEFINED_MAX_VALUE.
+    #[prost(string, repeated, tag="3")]
+    pub str_data: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(message, repeated, tag="4")]
+    pub time_series: ::prost::alloc::vec::Vec<TimeSeriesMetric>,
+    #[prost(message, repeated, tag="5")]
+    pub events: ::prost::alloc::vec::Vec<EventMetric>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TimeSeriesMetric {
+    /// Metric name e.g "speech_probablity". The string value is not directly stored in the message, but referenced by index
+    /// in the `str_data` field of `MetricsBatch`
+    #[prost(uint32, tag="1")]
+    pub label: u32,
+    /// index into `str_data`
+    #[prost(uint32, tag="2")]
+    pub participant_identity: u32,
+    /// index into `str_data`
+    #[prost(uint32, tag="3")]
+    pub track_sid: u32,
+    #[prost(message, repeated, tag="4")]
+    pub samples: ::prost::alloc::vec::Vec<MetricSample>,
+    /// index into 'str_data'
+    #[prost(uint32, tag="5")]
+    pub rid: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MetricSample {
+    /// time of metric based on a monotonic clock (in milliseconds)
+    #[prost(int64, tag="1")]
+    pub timestamp_ms: i64,
+    #[prost(message, optional, tag="2")]
+    pub normalized_timestamp: ::core::option::Option<::pbjson_types::Timestamp>,
+    #[prost(float, tag="3")]
+    pub value: f32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EventMetric {
+    #[prost(uint32, tag="1")]
+    pub label: u32,
+    /// index into `str_data`
+    #[prost(uint32, tag="2")]
+    pub participant_identity: u32,
+    /// index into `str_data`
+    #[prost(uint32, tag="3")]
+    pub track_sid: u32,
+    /// start time of event based on a monotonic clock (in milliseconds)
+    #[prost(int64, tag="4")]
+    pub start_timestamp_ms: i64,
+    /// end time of event based on a monotonic clock (in milliseconds), if needed
+    #[prost(int64, optional, tag="5")]
+    pub end_timestamp_ms: ::core::option::Option<i64>,
+    #[prost(message, optional, tag="6")]
+    pub normalized_start_timestamp: ::core::option::Option<::pbjson_types::Timestamp>,
+    #[prost(message, optional, tag="7")]
+    pub normalized_end_timestamp: ::core::option::Option<::pbjson_types::Timestamp>,
+    #[prost(string, tag="8")]
+    pub metadata: ::prost::alloc::string::String,
+    /// index into 'str_data'
+    #[prost(uint32, tag="9")]
+    pub rid: u32,
+}
+//
+// Protocol used to record metrics for a specific session.
+//
+// Clients send their timestamp in their own monotonically increasing time (e.g `performance.now` on JS).
+// These timestamps are then augmented by the SFU to its time base.
+//
+// A metric can be linked to a specific track by setting `track_sid`.
+
+/// index from [0: MAX_LABEL_PREDEFINED_MAX_VALUE) are for predefined labels (`MetricLabel`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum MetricLabel {
+    /// time to first token from LLM
+    AgentsLlmTtft = 0,
+    /// time to final transcription
+    AgentsSttTtft = 1,
+    /// time to first byte
+    AgentsTtsTtfb = 2,
+    /// Number of video freezes
+    ClientVideoSubscriberFreezeCount = 3,
+    /// total duration of freezes
+    ClientVideoSubscriberTotalFreezeDuration = 4,
+    /// number of video pauses
+    ClientVideoSubscriberPauseCount = 5,
+    /// total duration of pauses
+    ClientVideoSubscriberTotalPausesDuration = 6,
+    /// number of concealed (synthesized) audio samples
+    ClientAudioSubscriberConcealedSamples = 7,
+    /// number of silent concealed samples
+    ClientAudioSubscriberSilentConcealedSamples = 8,
+    /// number of concealment events
+    ClientAudioSubscriberConcealmentEvents = 9,
+    /// number of interruptions
+    ClientAudioSubscriberInterruptionCount = 10,
+    /// total duration of interruptions
+    ClientAudioSubscriberTotalInterruptionDuration = 11,
+    /// total time spent in jitter buffer
+    ClientSubscriberJitterBufferDelay = 12,
+    /// total time spent in jitter buffer
+    ClientSubscriberJitterBufferEmittedCount = 13,
+    /// total duration spent in bandwidth quality limitation
+    ClientVideoPublisherQualityLimitationDurationBandwidth = 14,
+    /// total duration spent in cpu quality limitation
+    ClientVideoPublisherQualityLimitationDurationCpu = 15,
+    /// total duration spent in other quality limitation
+    ClientVideoPublisherQualityLimitationDurationOther = 16,
+    /// Publisher RTT (participant -> server)
+    PublisherRtt = 17,
+    /// RTT between publisher node and subscriber node (could involve intermedia node(s))
+    ServerMeshRtt = 18,
+    /// Subscribe RTT (server -> participant)
+    SubscriberRtt = 19,
+    PredefinedMaxValue = 4096,
+}
+impl MetricLabel {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            MetricLabel::AgentsLlmTtft => "AGENTS_LLM_TTFT",
+            MetricLabel::AgentsSttTtft => "AGENTS_STT_TTFT",
+            MetricLabel::AgentsTtsTtfb => "AGENTS_TTS_TTFB",
+            MetricLabel::ClientVideoSubscriberFreezeCount => "CLIENT_VIDEO_SUBSCRIBER_FREEZE_COUNT",
+            MetricLabel::ClientVideoSubscriberTotalFreezeDuration => "CLIENT_VIDEO_SUBSCRIBER_TOTAL_FREEZE_DURATION",
+            MetricLabel::ClientVideoSubscriberPauseCount => "CLIENT_VIDEO_SUBSCRIBER_PAUSE_COUNT",
+            MetricLabel::ClientVideoSubscriberTotalPausesDuration => "CLIENT_VIDEO_SUBSCRIBER_TOTAL_PAUSES_DURATION",
+            MetricLabel::ClientAudioSubscriberConcealedSamples => "CLIENT_AUDIO_SUBSCRIBER_CONCEALED_SAMPLES",
+            MetricLabel::ClientAudioSubscriberSilentConcealedSamples => "CLIENT_AUDIO_SUBSCRIBER_SILENT_CONCEALED_SAMPLES",
+            MetricLabel::ClientAudioSubscriberConcealmentEvents => "CLIENT_AUDIO_SUBSCRIBER_CONCEALMENT_EVENTS",
+            MetricLabel::ClientAudioSubscriberInterruptionCount => "CLIENT_AUDIO_SUBSCRIBER_INTERRUPTION_COUNT",
+            MetricLabel::ClientAudioSubscriberTotalInterruptionDuration => "CLIENT_AUDIO_SUBSCRIBER_TOTAL_INTERRUPTION_DURATION",
+            MetricLabel::ClientSubscriberJitterBufferDelay => "CLIENT_SUBSCRIBER_JITTER_BUFFER_DELAY",
+            MetricLabel::ClientSubscriberJitterBufferEmittedCount => "CLIENT_SUBSCRIBER_JITTER_BUFFER_EMITTED_COUNT",
+            MetricLabel::ClientVideoPublisherQualityLimitationDurationBandwidth => "CLIENT_VIDEO_PUBLISHER_QUALITY_LIMITATION_DURATION_BANDWIDTH",
+            MetricLabel::ClientVideoPublisherQualityLimitationDurationCpu => "CLIENT_VIDEO_PUBLISHER_QUALITY_LIMITATION_DURATION_CPU",
+            MetricLabel::ClientVideoPublisherQualityLimitationDurationOther => "CLIENT_VIDEO_PUBLISHER_QUALITY_LIMITATION_DURATION_OTHER",
+            MetricLabel::PublisherRtt => "PUBLISHER_RTT",
+            MetricLabel::ServerMeshRtt => "SERVER_MESH_RTT",
+            MetricLabel::SubscriberRtt => "SUBSCRIBER_RTT",
+            MetricLabel::PredefinedMaxValue => "METRIC_LABEL_PREDEFINED_MAX_VALUE",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "AGENTS_LLM_TTFT" => Some(Self::AgentsLlmTtft),
+            "AGENTS_STT_TTFT" => Some(Self::AgentsSttTtft),
+            "AGENTS_TTS_TTFB" => Some(Self::AgentsTtsTtfb),
+            "CLIENT_VIDEO_SUBSCRIBER_FREEZE_COUNT" => Some(Self::ClientVideoSubscriberFreezeCount),
+            "CLIENT_VIDEO_SUBSCRIBER_TOTAL_FREEZE_DURATION" => Some(Self::ClientVideoSubscriberTotalFreezeDuration),
+            "CLIENT_VIDEO_SUBSCRIBER_PAUSE_COUNT" => Some(Self::ClientVideoSubscriberPauseCount),
+            "CLIENT_VIDEO_SUBSCRIBER_TOTAL_PAUSES_DURATION" => Some(Self::ClientVideoSubscriberTotalPausesDuration),
+            "CLIENT_AUDIO_SUBSCRIBER_CONCEALED_SAMPLES" => Some(Self::ClientAudioSubscriberConcealedSamples),
+            "CLIENT_AUDIO_SUBSCRIBER_SILENT_CONCEALED_SAMPLES" => Some(Self::ClientAudioSubscriberSilentConcealedSamples),
+            "CLIENT_AUDIO_SUBSCRIBER_CONCEALMENT_EVENTS" => Some(Self::ClientAudioSubscriberConcealmentEvents),
+            "CLIENT_AUDIO_SUBSCRIBER_INTERRUPTION_COUNT" => Some(Self::ClientAudioSubscriberInterruptionCount),
+            "CLIENT_AUDIO_SUBSCRIBER_TOTAL_INTERRUPTION_DURATION" => Some(Self::ClientAudioSubscriberTotalInterruptionDuration),
+            "CLIENT_SUBSCRIBER_JITTER_BUFFER_DELAY" => Some(Self::ClientSubscriberJitterBufferDelay),
+            "CLIENT_SUBSCRIBER_JITTER_BUFFER_EMITTED_COUNT" => Some(Self::ClientSubscriberJitterBufferEmittedCount),
+            "CLIENT_VIDEO_PUBLISHER_QUALITY_LIMITATION_DURATION_BANDWIDTH" => Some(Self::ClientVideoPublisherQualityLimitationDurationBandwidth),
+            "CLIENT_VIDEO_PUBLISHER_QUALITY_LIMITATION_DURATION_CPU" => Some(Self::ClientVideoPublisherQualityLimitationDurationCpu),
+            "CLIENT_VIDEO_PUBLISHER_QUALITY_LIMITATION_DURATION_OTHER" => Some(Self::ClientVideoPublisherQualityLimitationDurationOther),
+            "PUBLISHER_RTT" => Some(Self::PublisherRtt),
+            "SERVER_MESH_RTT" => Some(Self::ServerMeshRtt),
+            "SUBSCRIBER_RTT" => Some(Self::SubscriberRtt),
+            "METRIC_LABEL_PREDEFINED_MAX_VALUE" => Some(Self::PredefinedMaxValue),
+            _ => None,
+        }
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Pagination {
+    /// list entities which IDs are greater
+    #[prost(string, tag="1")]
+    pub after_id: ::prost::alloc::string::String,
+    #[prost(int32, tag="2")]
+    pub limit: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Room {
     #[prost(string, tag="1")]
     pub sid: ::prost::alloc::string::String,
@@ -8,10 +207,14 @@ pub struct Room {
     pub name: ::prost::alloc::string::String,
     #[prost(uint32, tag="3")]
     pub empty_timeout: u32,
+    #[prost(uint32, tag="14")]
+    pub departure_timeout: u32,
     #[prost(uint32, tag="4")]
     pub max_participants: u32,
     #[prost(int64, tag="5")]
     pub creation_time: i64,
+    #[prost(int64, tag="15")]
+    pub creation_time_ms: i64,
     #[prost(string, tag="6")]
     pub turn_password: ::prost::alloc::string::String,
     #[prost(message, repeated, tag="7")]
@@ -24,8 +227,8 @@ pub struct Room {
     pub num_publishers: u32,
     #[prost(bool, tag="10")]
     pub active_recording: bool,
-    #[prost(message, optional, tag="12")]
-    pub playout_delay: ::core::option::Option<PlayoutDelay>,
+    #[prost(message, optional, tag="13")]
+    pub version: ::core::option::Option<TimedVersion>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -64,11 +267,21 @@ pub struct ParticipantPermission {
     #[prost(bool, tag="7")]
     pub hidden: bool,
     /// indicates it's a recorder instance
+    /// deprecated: use ParticipantInfo.kind instead
+    #[deprecated]
     #[prost(bool, tag="8")]
     pub recorder: bool,
-    /// indicates that participant can update own metadata
+    /// indicates that participant can update own metadata and attributes
     #[prost(bool, tag="10")]
     pub can_update_metadata: bool,
+    /// indicates that participant is an agent
+    /// deprecated: use ParticipantInfo.kind instead
+    #[deprecated]
+    #[prost(bool, tag="11")]
+    pub agent: bool,
+    /// if a participant can subscribe to metrics
+    #[prost(bool, tag="12")]
+    pub can_subscribe_metrics: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -86,6 +299,9 @@ pub struct ParticipantInfo {
     /// timestamp when participant joined room, in seconds
     #[prost(int64, tag="6")]
     pub joined_at: i64,
+    /// timestamp when participant joined room, in milliseconds
+    #[prost(int64, tag="17")]
+    pub joined_at_ms: i64,
     #[prost(string, tag="9")]
     pub name: ::prost::alloc::string::String,
     #[prost(uint32, tag="10")]
@@ -98,6 +314,12 @@ pub struct ParticipantInfo {
     /// and can publish to the server
     #[prost(bool, tag="13")]
     pub is_publisher: bool,
+    #[prost(enumeration="participant_info::Kind", tag="14")]
+    pub kind: i32,
+    #[prost(map="string, string", tag="15")]
+    pub attributes: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+    #[prost(enumeration="DisconnectReason", tag="16")]
+    pub disconnect_reason: i32,
 }
 /// Nested message and enum types in `ParticipantInfo`.
 pub mod participant_info {
@@ -137,6 +359,46 @@ pub mod participant_info {
             }
         }
     }
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Kind {
+        /// standard participants, e.g. web clients
+        Standard = 0,
+        /// only ingests streams
+        Ingress = 1,
+        /// only consumes streams
+        Egress = 2,
+        /// SIP participants
+        Sip = 3,
+        /// LiveKit agents
+        Agent = 4,
+    }
+    impl Kind {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                Kind::Standard => "STANDARD",
+                Kind::Ingress => "INGRESS",
+                Kind::Egress => "EGRESS",
+                Kind::Sip => "SIP",
+                Kind::Agent => "AGENT",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "STANDARD" => Some(Self::Standard),
+                "INGRESS" => Some(Self::Ingress),
+                "EGRESS" => Some(Self::Egress),
+                "SIP" => Some(Self::Sip),
+                "AGENT" => Some(Self::Agent),
+                _ => None,
+            }
+        }
+    }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -231,6 +493,10 @@ pub struct TrackInfo {
     pub encryption: i32,
     #[prost(string, tag="17")]
     pub stream: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="18")]
+    pub version: ::core::option::Option<TimedVersion>,
+    #[prost(enumeration="AudioTrackFeature", repeated, tag="19")]
+    pub audio_features: ::prost::alloc::vec::Vec<i32>,
 }
 /// provide information about available spatial layers
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -253,9 +519,16 @@ pub struct VideoLayer {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct DataPacket {
+    #[deprecated]
     #[prost(enumeration="data_packet::Kind", tag="1")]
     pub kind: i32,
-    #[prost(oneof="data_packet::Value", tags="2, 3")]
+    /// participant identity of user that sent the message
+    #[prost(string, tag="4")]
+    pub participant_identity: ::prost::alloc::string::String,
+    /// identities of participants who will receive the message (sent to all by default)
+    #[prost(string, repeated, tag="5")]
+    pub destination_identities: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(oneof="data_packet::Value", tags="2, 3, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15")]
     pub value: ::core::option::Option<data_packet::Value>,
 }
 /// Nested message and enum types in `DataPacket`.
@@ -293,6 +566,26 @@ pub mod data_packet {
         User(super::UserPacket),
         #[prost(message, tag="3")]
         Speaker(super::ActiveSpeakerUpdate),
+        #[prost(message, tag="6")]
+        SipDtmf(super::SipDtmf),
+        #[prost(message, tag="7")]
+        Transcription(super::Transcription),
+        #[prost(message, tag="8")]
+        Metrics(super::MetricsBatch),
+        #[prost(message, tag="9")]
+        ChatMessage(super::ChatMessage),
+        #[prost(message, tag="10")]
+        RpcRequest(super::RpcRequest),
+        #[prost(message, tag="11")]
+        RpcAck(super::RpcAck),
+        #[prost(message, tag="12")]
+        RpcResponse(super::RpcResponse),
+        #[prost(message, tag="13")]
+        StreamHeader(super::data_stream::Header),
+        #[prost(message, tag="14")]
+        StreamChunk(super::data_stream::Chunk),
+        #[prost(message, tag="15")]
+        StreamTrailer(super::data_stream::Trailer),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -317,17 +610,141 @@ pub struct SpeakerInfo {
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct UserPacket {
     /// participant ID of user that sent the message
+    #[deprecated]
     #[prost(string, tag="1")]
     pub participant_sid: ::prost::alloc::string::String,
+    #[deprecated]
+    #[prost(string, tag="5")]
+    pub participant_identity: ::prost::alloc::string::String,
     /// user defined payload
     #[prost(bytes="vec", tag="2")]
     pub payload: ::prost::alloc::vec::Vec<u8>,
-    /// the ID of the participants who will receive the message (the message will be sent to all the people in the room if this variable is empty)
+    /// the ID of the participants who will receive the message (sent to all by default)
+    #[deprecated]
     #[prost(string, repeated, tag="3")]
     pub destination_sids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// identities of participants who will receive the message (sent to all by default)
+    #[deprecated]
+    #[prost(string, repeated, tag="6")]
+    pub destination_identities: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
     /// topic under which the message was published
     #[prost(string, optional, tag="4")]
     pub topic: ::core::option::Option<::prost::alloc::string::String>,
+    /// Unique ID to indentify the message
+    #[prost(string, optional, tag="8")]
+    pub id: ::core::option::Option<::prost::alloc::string::String>,
+    /// start and end time allow relating the message to specific media time
+    #[prost(uint64, optional, tag="9")]
+    pub start_time: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag="10")]
+    pub end_time: ::core::option::Option<u64>,
+    /// added by SDK to enable de-duping of messages, for INTERNAL USE ONLY
+    #[prost(bytes="vec", tag="11")]
+    pub nonce: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SipDtmf {
+    #[prost(uint32, tag="3")]
+    pub code: u32,
+    #[prost(string, tag="4")]
+    pub digit: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Transcription {
+    /// Participant that got its speech transcribed
+    #[prost(string, tag="2")]
+    pub transcribed_participant_identity: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub track_id: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag="4")]
+    pub segments: ::prost::alloc::vec::Vec<TranscriptionSegment>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TranscriptionSegment {
+    #[prost(string, tag="1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub text: ::prost::alloc::string::String,
+    #[prost(uint64, tag="3")]
+    pub start_time: u64,
+    #[prost(uint64, tag="4")]
+    pub end_time: u64,
+    #[prost(bool, tag="5")]
+    pub r#final: bool,
+    #[prost(string, tag="6")]
+    pub language: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChatMessage {
+    /// uuid
+    #[prost(string, tag="1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(int64, tag="2")]
+    pub timestamp: i64,
+    /// populated only if the intent is to edit/update an existing message
+    #[prost(int64, optional, tag="3")]
+    pub edit_timestamp: ::core::option::Option<i64>,
+    #[prost(string, tag="4")]
+    pub message: ::prost::alloc::string::String,
+    /// true to remove message
+    #[prost(bool, tag="5")]
+    pub deleted: bool,
+    /// true if the chat message has been generated by an agent from a participant's audio transcription
+    #[prost(bool, tag="6")]
+    pub generated: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RpcRequest {
+    #[prost(string, tag="1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub method: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub payload: ::prost::alloc::string::String,
+    #[prost(uint32, tag="4")]
+    pub response_timeout_ms: u32,
+    #[prost(uint32, tag="5")]
+    pub version: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RpcAck {
+    #[prost(string, tag="1")]
+    pub request_id: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RpcResponse {
+    #[prost(string, tag="1")]
+    pub request_id: ::prost::alloc::string::String,
+    #[prost(oneof="rpc_response::Value", tags="2, 3")]
+    pub value: ::core::option::Option<rpc_response::Value>,
+}
+/// Nested message and enum types in `RpcResponse`.
+pub mod rpc_response {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Value {
+        #[prost(string, tag="2")]
+        Payload(::prost::alloc::string::String),
+        #[prost(message, tag="3")]
+        Error(super::RpcError),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RpcError {
+    #[prost(uint32, tag="1")]
+    pub code: u32,
+    #[prost(string, tag="2")]
+    pub message: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub data: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -355,6 +772,8 @@ pub struct ServerInfo {
     /// additional debugging information. sent only if server is in development mode
     #[prost(string, tag="6")]
     pub debug_info: ::prost::alloc::string::String,
+    #[prost(int32, tag="7")]
+    pub agent_protocol: i32,
 }
 /// Nested message and enum types in `ServerInfo`.
 pub mod server_info {
@@ -410,6 +829,10 @@ pub struct ClientInfo {
     /// wifi, wired, cellular, vpn, empty if not known
     #[prost(string, tag="10")]
     pub network: ::prost::alloc::string::String,
+    /// comma separated list of additional LiveKit SDKs in use of this client, with versions
+    /// e.g. "components-js:1.2.3,track-processors-js:1.2.3"
+    #[prost(string, tag="11")]
+    pub other_sdks: ::prost::alloc::string::String,
 }
 /// Nested message and enum types in `ClientInfo`.
 pub mod client_info {
@@ -425,6 +848,10 @@ pub mod client_info {
         Unity = 6,
         ReactNative = 7,
         Rust = 8,
+        Python = 9,
+        Cpp = 10,
+        UnityWeb = 11,
+        Node = 12,
     }
     impl Sdk {
         /// String value of the enum field names used in the ProtoBuf definition.
@@ -442,6 +869,10 @@ pub mod client_info {
                 Sdk::Unity => "UNITY",
                 Sdk::ReactNative => "REACT_NATIVE",
                 Sdk::Rust => "RUST",
+                Sdk::Python => "PYTHON",
+                Sdk::Cpp => "CPP",
+                Sdk::UnityWeb => "UNITY_WEB",
+                Sdk::Node => "NODE",
             }
         }
         /// Creates an enum from field names used in the ProtoBuf definition.
@@ -456,6 +887,10 @@ pub mod client_info {
                 "UNITY" => Some(Self::Unity),
                 "REACT_NATIVE" => Some(Self::ReactNative),
                 "RUST" => Some(Self::Rust),
+                "PYTHON" => Some(Self::Python),
+                "CPP" => Some(Self::Cpp),
+                "UNITY_WEB" => Some(Self::UnityWeb),
+                "NODE" => Some(Self::Node),
                 _ => None,
             }
         }
@@ -494,6 +929,28 @@ pub struct DisabledCodecs {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RtpDrift {
+    #[prost(message, optional, tag="1")]
+    pub start_time: ::core::option::Option<::pbjson_types::Timestamp>,
+    #[prost(message, optional, tag="2")]
+    pub end_time: ::core::option::Option<::pbjson_types::Timestamp>,
+    #[prost(double, tag="3")]
+    pub duration: f64,
+    #[prost(uint64, tag="4")]
+    pub start_timestamp: u64,
+    #[prost(uint64, tag="5")]
+    pub end_timestamp: u64,
+    #[prost(uint64, tag="6")]
+    pub rtp_clock_ticks: u64,
+    #[prost(int64, tag="7")]
+    pub drift_samples: i64,
+    #[prost(double, tag="8")]
+    pub drift_ms: f64,
+    #[prost(double, tag="9")]
+    pub clock_rate: f64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct RtpStats {
     #[prost(message, optional, tag="1")]
     pub start_time: ::core::option::Option<::pbjson_types::Timestamp>,
@@ -577,11 +1034,97 @@ pub struct RtpStats {
     pub layer_lock_plis: u32,
     #[prost(message, optional, tag="36")]
     pub last_layer_lock_pli: ::core::option::Option<::pbjson_types::Timestamp>,
-    #[prost(double, tag="42")]
-    pub sample_rate: f64,
-    /// NEXT_ID: 44
-    #[prost(double, tag="43")]
-    pub drift_ms: f64,
+    #[prost(message, optional, tag="44")]
+    pub packet_drift: ::core::option::Option<RtpDrift>,
+    #[prost(message, optional, tag="45")]
+    pub ntp_report_drift: ::core::option::Option<RtpDrift>,
+    #[prost(message, optional, tag="46")]
+    pub rebased_report_drift: ::core::option::Option<RtpDrift>,
+    /// NEXT_ID: 48
+    #[prost(message, optional, tag="47")]
+    pub received_report_drift: ::core::option::Option<RtpDrift>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RtcpSenderReportState {
+    #[prost(uint32, tag="1")]
+    pub rtp_timestamp: u32,
+    #[prost(uint64, tag="2")]
+    pub rtp_timestamp_ext: u64,
+    #[prost(uint64, tag="3")]
+    pub ntp_timestamp: u64,
+    /// time at which this happened
+    #[prost(int64, tag="4")]
+    pub at: i64,
+    #[prost(int64, tag="5")]
+    pub at_adjusted: i64,
+    #[prost(uint32, tag="6")]
+    pub packets: u32,
+    #[prost(uint64, tag="7")]
+    pub octets: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RtpForwarderState {
+    #[prost(bool, tag="1")]
+    pub started: bool,
+    #[prost(int32, tag="2")]
+    pub reference_layer_spatial: i32,
+    #[prost(int64, tag="3")]
+    pub pre_start_time: i64,
+    #[prost(uint64, tag="4")]
+    pub ext_first_timestamp: u64,
+    #[prost(uint64, tag="5")]
+    pub dummy_start_timestamp_offset: u64,
+    #[prost(message, optional, tag="6")]
+    pub rtp_munger: ::core::option::Option<RtpMungerState>,
+    #[prost(message, repeated, tag="8")]
+    pub sender_report_state: ::prost::alloc::vec::Vec<RtcpSenderReportState>,
+    #[prost(oneof="rtp_forwarder_state::CodecMunger", tags="7")]
+    pub codec_munger: ::core::option::Option<rtp_forwarder_state::CodecMunger>,
+}
+/// Nested message and enum types in `RTPForwarderState`.
+pub mod rtp_forwarder_state {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum CodecMunger {
+        #[prost(message, tag="7")]
+        Vp8Munger(super::Vp8MungerState),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RtpMungerState {
+    #[prost(uint64, tag="1")]
+    pub ext_last_sequence_number: u64,
+    #[prost(uint64, tag="2")]
+    pub ext_second_last_sequence_number: u64,
+    #[prost(uint64, tag="3")]
+    pub ext_last_timestamp: u64,
+    #[prost(uint64, tag="4")]
+    pub ext_second_last_timestamp: u64,
+    #[prost(bool, tag="5")]
+    pub last_marker: bool,
+    #[prost(bool, tag="6")]
+    pub second_last_marker: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Vp8MungerState {
+    #[prost(int32, tag="1")]
+    pub ext_last_picture_id: i32,
+    #[prost(bool, tag="2")]
+    pub picture_id_used: bool,
+    #[prost(uint32, tag="3")]
+    pub last_tl0_pic_idx: u32,
+    #[prost(bool, tag="4")]
+    pub tl0_pic_idx_used: bool,
+    #[prost(bool, tag="5")]
+    pub tid_used: bool,
+    #[prost(uint32, tag="6")]
+    pub last_key_idx: u32,
+    #[prost(bool, tag="7")]
+    pub key_idx_used: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -591,6 +1134,142 @@ pub struct TimedVersion {
     #[prost(int32, tag="2")]
     pub ticks: i32,
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DataStream {
+}
+/// Nested message and enum types in `DataStream`.
+pub mod data_stream {
+    /// header properties specific to text streams
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct TextHeader {
+        #[prost(enumeration="OperationType", tag="1")]
+        pub operation_type: i32,
+        /// Optional: Version for updates/edits
+        #[prost(int32, tag="2")]
+        pub version: i32,
+        /// Optional: Reply to specific message
+        #[prost(string, tag="3")]
+        pub reply_to_stream_id: ::prost::alloc::string::String,
+        /// file attachments for text streams
+        #[prost(string, repeated, tag="4")]
+        pub attached_stream_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+        /// true if the text has been generated by an agent from a participant's audio transcription
+        #[prost(bool, tag="5")]
+        pub generated: bool,
+    }
+    /// header properties specific to byte or file streams
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct ByteHeader {
+        #[prost(string, tag="1")]
+        pub name: ::prost::alloc::string::String,
+    }
+    /// main DataStream.Header that contains a oneof for specific headers
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Header {
+        /// unique identifier for this data stream
+        #[prost(string, tag="1")]
+        pub stream_id: ::prost::alloc::string::String,
+        /// using int64 for Unix timestamp
+        #[prost(int64, tag="2")]
+        pub timestamp: i64,
+        #[prost(string, tag="3")]
+        pub topic: ::prost::alloc::string::String,
+        #[prost(string, tag="4")]
+        pub mime_type: ::prost::alloc::string::String,
+        /// only populated for finite streams, if it's a stream of unknown size this stays empty
+        #[prost(uint64, optional, tag="5")]
+        pub total_length: ::core::option::Option<u64>,
+        /// defaults to NONE
+        #[prost(enumeration="super::encryption::Type", tag="7")]
+        pub encryption_type: i32,
+        /// user defined attributes map that can carry additional info
+        #[prost(map="string, string", tag="8")]
+        pub attributes: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+        /// oneof to choose between specific header types
+        #[prost(oneof="header::ContentHeader", tags="9, 10")]
+        pub content_header: ::core::option::Option<header::ContentHeader>,
+    }
+    /// Nested message and enum types in `Header`.
+    pub mod header {
+        /// oneof to choose between specific header types
+        #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Oneof)]
+        pub enum ContentHeader {
+            #[prost(message, tag="9")]
+            TextHeader(super::TextHeader),
+            #[prost(message, tag="10")]
+            ByteHeader(super::ByteHeader),
+        }
+    }
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Chunk {
+        /// unique identifier for this data stream to map it to the correct header
+        #[prost(string, tag="1")]
+        pub stream_id: ::prost::alloc::string::String,
+        #[prost(uint64, tag="2")]
+        pub chunk_index: u64,
+        /// content as binary (bytes)
+        #[prost(bytes="vec", tag="3")]
+        pub content: ::prost::alloc::vec::Vec<u8>,
+        /// a version indicating that this chunk_index has been retroactively modified and the original one needs to be replaced
+        #[prost(int32, tag="4")]
+        pub version: i32,
+        /// optional, initialization vector for AES-GCM encryption
+        #[prost(bytes="vec", optional, tag="5")]
+        pub iv: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    }
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Trailer {
+        /// unique identifier for this data stream
+        #[prost(string, tag="1")]
+        pub stream_id: ::prost::alloc::string::String,
+        /// reason why the stream was closed (could contain "error" / "interrupted" / empty for expected end)
+        #[prost(string, tag="2")]
+        pub reason: ::prost::alloc::string::String,
+        /// finalizing updates for the stream, can also include additional insights for errors or endTime for transcription
+        #[prost(map="string, string", tag="3")]
+        pub attributes: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+    }
+    /// enum for operation types (specific to TextHeader)
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum OperationType {
+        Create = 0,
+        Update = 1,
+        Delete = 2,
+        Reaction = 3,
+    }
+    impl OperationType {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                OperationType::Create => "CREATE",
+                OperationType::Update => "UPDATE",
+                OperationType::Delete => "DELETE",
+                OperationType::Reaction => "REACTION",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "CREATE" => Some(Self::Create),
+                "UPDATE" => Some(Self::Update),
+                "DELETE" => Some(Self::Delete),
+                "REACTION" => Some(Self::Reaction),
+                _ => None,
+            }
+        }
+    }
+}
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
 pub enum AudioCodec {
@@ -657,39 +1336,65 @@ impl VideoCodec {
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
-pub enum TrackType {
-    Audio = 0,
-    Video = 1,
-    Data = 2,
+pub enum ImageCodec {
+    IcDefault = 0,
+    IcJpeg = 1,
 }
-impl TrackType {
+impl ImageCodec {
     /// String value of the enum field names used in the ProtoBuf definition.
     ///
     /// The values are not transformed in any way and thus are considered stable
     /// (if the ProtoBuf definition does not change) and safe for programmatic use.
     pub fn as_str_name(&self) -> &'static str {
         match self {
-            TrackType::Audio => "AUDIO",
-            TrackType::Video => "VIDEO",
-            TrackType::Data => "DATA",
+            ImageCodec::IcDefault => "IC_DEFAULT",
+            ImageCodec::IcJpeg => "IC_JPEG",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
     pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
         match value {
-            "AUDIO" => Some(Self::Audio),
-            "VIDEO" => Some(Self::Video),
-            "DATA" => Some(Self::Data),
+            "IC_DEFAULT" => Some(Self::IcDefault),
+            "IC_JPEG" => Some(Self::IcJpeg),
             _ => None,
         }
     }
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
-pub enum TrackSource {
-    Unknown = 0,
-    Camera = 1,
-    Microphone = 2,
+pub enum TrackType {
+    Audio = 0,
+    Video = 1,
+    Data = 2,
+}
+impl TrackType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            TrackType::Audio => "AUDIO",
+            TrackType::Video => "VIDEO",
+            TrackType::Data => "DATA",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "AUDIO" => Some(Self::Audio),
+            "VIDEO" => Some(Self::Video),
+            "DATA" => Some(Self::Data),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TrackSource {
+    Unknown = 0,
+    Camera = 1,
+    Microphone = 2,
     ScreenShare = 3,
     ScreenShareAudio = 4,
 }
@@ -757,6 +1462,7 @@ pub enum ConnectionQuality {
     Poor = 0,
     Good = 1,
     Excellent = 2,
+    Lost = 3,
 }
 impl ConnectionQuality {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -768,6 +1474,7 @@ impl ConnectionQuality {
             ConnectionQuality::Poor => "POOR",
             ConnectionQuality::Good => "GOOD",
             ConnectionQuality::Excellent => "EXCELLENT",
+            ConnectionQuality::Lost => "LOST",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -776,6 +1483,7 @@ impl ConnectionQuality {
             "POOR" => Some(Self::Poor),
             "GOOD" => Some(Self::Good),
             "EXCELLENT" => Some(Self::Excellent),
+            "LOST" => Some(Self::Lost),
             _ => None,
         }
     }
@@ -813,13 +1521,32 @@ impl ClientConfigSetting {
 #[repr(i32)]
 pub enum DisconnectReason {
     UnknownReason = 0,
+    /// the client initiated the disconnect
     ClientInitiated = 1,
+    /// another participant with the same identity has joined the room
     DuplicateIdentity = 2,
+    /// the server instance is shutting down
     ServerShutdown = 3,
+    /// RoomService.RemoveParticipant was called
     ParticipantRemoved = 4,
+    /// RoomService.DeleteRoom was called
     RoomDeleted = 5,
+    /// the client is attempting to resume a session, but server is not aware of it
     StateMismatch = 6,
+    /// client was unable to connect fully
     JoinFailure = 7,
+    /// Cloud-only, the server requested Participant to migrate the connection elsewhere
+    Migration = 8,
+    /// the signal websocket was closed unexpectedly
+    SignalClose = 9,
+    /// the room was closed, due to all Standard and Ingress participants having left
+    RoomClosed = 10,
+    /// SIP callee did not respond in time
+    UserUnavailable = 11,
+    /// SIP callee rejected the call (busy)
+    UserRejected = 12,
+    /// SIP protocol failure or unexpected response
+    SipTrunkFailure = 13,
 }
 impl DisconnectReason {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -836,6 +1563,12 @@ impl DisconnectReason {
             DisconnectReason::RoomDeleted => "ROOM_DELETED",
             DisconnectReason::StateMismatch => "STATE_MISMATCH",
             DisconnectReason::JoinFailure => "JOIN_FAILURE",
+            DisconnectReason::Migration => "MIGRATION",
+            DisconnectReason::SignalClose => "SIGNAL_CLOSE",
+            DisconnectReason::RoomClosed => "ROOM_CLOSED",
+            DisconnectReason::UserUnavailable => "USER_UNAVAILABLE",
+            DisconnectReason::UserRejected => "USER_REJECTED",
+            DisconnectReason::SipTrunkFailure => "SIP_TRUNK_FAILURE",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -849,6 +1582,12 @@ impl DisconnectReason {
             "ROOM_DELETED" => Some(Self::RoomDeleted),
             "STATE_MISMATCH" => Some(Self::StateMismatch),
             "JOIN_FAILURE" => Some(Self::JoinFailure),
+            "MIGRATION" => Some(Self::Migration),
+            "SIGNAL_CLOSE" => Some(Self::SignalClose),
+            "ROOM_CLOSED" => Some(Self::RoomClosed),
+            "USER_UNAVAILABLE" => Some(Self::UserUnavailable),
+            "USER_REJECTED" => Some(Self::UserRejected),
+            "SIP_TRUNK_FAILURE" => Some(Self::SipTrunkFailure),
             _ => None,
         }
     }
@@ -917,6 +1656,44 @@ impl SubscriptionError {
         }
     }
 }
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum AudioTrackFeature {
+    TfStereo = 0,
+    TfNoDtx = 1,
+    TfAutoGainControl = 2,
+    TfEchoCancellation = 3,
+    TfNoiseSuppression = 4,
+    TfEnhancedNoiseCancellation = 5,
+}
+impl AudioTrackFeature {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            AudioTrackFeature::TfStereo => "TF_STEREO",
+            AudioTrackFeature::TfNoDtx => "TF_NO_DTX",
+            AudioTrackFeature::TfAutoGainControl => "TF_AUTO_GAIN_CONTROL",
+            AudioTrackFeature::TfEchoCancellation => "TF_ECHO_CANCELLATION",
+            AudioTrackFeature::TfNoiseSuppression => "TF_NOISE_SUPPRESSION",
+            AudioTrackFeature::TfEnhancedNoiseCancellation => "TF_ENHANCED_NOISE_CANCELLATION",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "TF_STEREO" => Some(Self::TfStereo),
+            "TF_NO_DTX" => Some(Self::TfNoDtx),
+            "TF_AUTO_GAIN_CONTROL" => Some(Self::TfAutoGainControl),
+            "TF_ECHO_CANCELLATION" => Some(Self::TfEchoCancellation),
+            "TF_NOISE_SUPPRESSION" => Some(Self::TfNoiseSuppression),
+            "TF_ENHANCED_NOISE_CANCELLATION" => Some(Self::TfEnhancedNoiseCancellation),
+            _ => None,
+        }
+    }
+}
 /// composite using a web browser
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -942,6 +1719,8 @@ pub struct RoomCompositeEgressRequest {
     pub stream_outputs: ::prost::alloc::vec::Vec<StreamOutput>,
     #[prost(message, repeated, tag="13")]
     pub segment_outputs: ::prost::alloc::vec::Vec<SegmentedFileOutput>,
+    #[prost(message, repeated, tag="14")]
+    pub image_outputs: ::prost::alloc::vec::Vec<ImageOutput>,
     /// deprecated (use _output fields)
     #[prost(oneof="room_composite_egress_request::Output", tags="6, 7, 10")]
     pub output: ::core::option::Option<room_composite_egress_request::Output>,
@@ -990,6 +1769,8 @@ pub struct WebEgressRequest {
     pub stream_outputs: ::prost::alloc::vec::Vec<StreamOutput>,
     #[prost(message, repeated, tag="11")]
     pub segment_outputs: ::prost::alloc::vec::Vec<SegmentedFileOutput>,
+    #[prost(message, repeated, tag="13")]
+    pub image_outputs: ::prost::alloc::vec::Vec<ImageOutput>,
     /// deprecated (use _output fields)
     #[prost(oneof="web_egress_request::Output", tags="4, 5, 6")]
     pub output: ::core::option::Option<web_egress_request::Output>,
@@ -1018,6 +1799,43 @@ pub mod web_egress_request {
         Advanced(super::EncodingOptions),
     }
 }
+/// record audio and video from a single participant
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ParticipantEgressRequest {
+    /// required
+    #[prost(string, tag="1")]
+    pub room_name: ::prost::alloc::string::String,
+    /// required
+    #[prost(string, tag="2")]
+    pub identity: ::prost::alloc::string::String,
+    /// (default false)
+    #[prost(bool, tag="3")]
+    pub screen_share: bool,
+    #[prost(message, repeated, tag="6")]
+    pub file_outputs: ::prost::alloc::vec::Vec<EncodedFileOutput>,
+    #[prost(message, repeated, tag="7")]
+    pub stream_outputs: ::prost::alloc::vec::Vec<StreamOutput>,
+    #[prost(message, repeated, tag="8")]
+    pub segment_outputs: ::prost::alloc::vec::Vec<SegmentedFileOutput>,
+    #[prost(message, repeated, tag="9")]
+    pub image_outputs: ::prost::alloc::vec::Vec<ImageOutput>,
+    #[prost(oneof="participant_egress_request::Options", tags="4, 5")]
+    pub options: ::core::option::Option<participant_egress_request::Options>,
+}
+/// Nested message and enum types in `ParticipantEgressRequest`.
+pub mod participant_egress_request {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Options {
+        /// (default H264_720P_30)
+        #[prost(enumeration="super::EncodingOptionsPreset", tag="4")]
+        Preset(i32),
+        /// (optional)
+        #[prost(message, tag="5")]
+        Advanced(super::EncodingOptions),
+    }
+}
 /// containerize up to one audio and one video track
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -1037,6 +1855,8 @@ pub struct TrackCompositeEgressRequest {
     pub stream_outputs: ::prost::alloc::vec::Vec<StreamOutput>,
     #[prost(message, repeated, tag="13")]
     pub segment_outputs: ::prost::alloc::vec::Vec<SegmentedFileOutput>,
+    #[prost(message, repeated, tag="14")]
+    pub image_outputs: ::prost::alloc::vec::Vec<ImageOutput>,
     /// deprecated (use _output fields)
     #[prost(oneof="track_composite_egress_request::Output", tags="4, 5, 8")]
     pub output: ::core::option::Option<track_composite_egress_request::Output>,
@@ -1136,6 +1956,9 @@ pub struct SegmentedFileOutput {
     /// (optional)
     #[prost(string, tag="3")]
     pub playlist_name: ::prost::alloc::string::String,
+    /// (optional, disabled if not provided). Path of a live playlist
+    #[prost(string, tag="11")]
+    pub live_playlist_name: ::prost::alloc::string::String,
     /// in seconds (optional)
     #[prost(uint32, tag="4")]
     pub segment_duration: u32,
@@ -1194,11 +2017,57 @@ pub mod direct_file_output {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ImageOutput {
+    /// in seconds (required)
+    #[prost(uint32, tag="1")]
+    pub capture_interval: u32,
+    /// (optional, defaults to track width)
+    #[prost(int32, tag="2")]
+    pub width: i32,
+    /// (optional, defaults to track height)
+    #[prost(int32, tag="3")]
+    pub height: i32,
+    /// (optional)
+    #[prost(string, tag="4")]
+    pub filename_prefix: ::prost::alloc::string::String,
+    /// (optional, default INDEX)
+    #[prost(enumeration="ImageFileSuffix", tag="5")]
+    pub filename_suffix: i32,
+    /// (optional)
+    #[prost(enumeration="ImageCodec", tag="6")]
+    pub image_codec: i32,
+    /// disable upload of manifest file (default false)
+    #[prost(bool, tag="7")]
+    pub disable_manifest: bool,
+    /// required
+    #[prost(oneof="image_output::Output", tags="8, 9, 10, 11")]
+    pub output: ::core::option::Option<image_output::Output>,
+}
+/// Nested message and enum types in `ImageOutput`.
+pub mod image_output {
+    /// required
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Output {
+        #[prost(message, tag="8")]
+        S3(super::S3Upload),
+        #[prost(message, tag="9")]
+        Gcp(super::GcpUpload),
+        #[prost(message, tag="10")]
+        Azure(super::AzureBlobUpload),
+        #[prost(message, tag="11")]
+        AliOss(super::AliOssUpload),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct S3Upload {
     #[prost(string, tag="1")]
     pub access_key: ::prost::alloc::string::String,
     #[prost(string, tag="2")]
     pub secret: ::prost::alloc::string::String,
+    #[prost(string, tag="11")]
+    pub session_token: ::prost::alloc::string::String,
     #[prost(string, tag="3")]
     pub region: ::prost::alloc::string::String,
     #[prost(string, tag="4")]
@@ -1211,14 +2080,22 @@ pub struct S3Upload {
     pub metadata: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
     #[prost(string, tag="8")]
     pub tagging: ::prost::alloc::string::String,
+    /// Content-Disposition header
+    #[prost(string, tag="9")]
+    pub content_disposition: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="10")]
+    pub proxy: ::core::option::Option<ProxyConfig>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GcpUpload {
+    /// service account credentials serialized in JSON "credentials.json"
     #[prost(string, tag="1")]
     pub credentials: ::prost::alloc::string::String,
     #[prost(string, tag="2")]
     pub bucket: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="3")]
+    pub proxy: ::core::option::Option<ProxyConfig>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -1246,6 +2123,16 @@ pub struct AliOssUpload {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ProxyConfig {
+    #[prost(string, tag="1")]
+    pub url: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub username: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub password: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct StreamOutput {
     /// required
     #[prost(enumeration="StreamProtocol", tag="1")]
@@ -1275,6 +2162,9 @@ pub struct EncodingOptions {
     /// (default 128)
     #[prost(int32, tag="6")]
     pub audio_bitrate: i32,
+    /// quality setting on audio encoder
+    #[prost(int32, tag="11")]
+    pub audio_quality: i32,
     /// (default 44100)
     #[prost(int32, tag="7")]
     pub audio_frequency: i32,
@@ -1284,6 +2174,9 @@ pub struct EncodingOptions {
     /// (default 4500)
     #[prost(int32, tag="9")]
     pub video_bitrate: i32,
+    /// quality setting on video encoder
+    #[prost(int32, tag="12")]
+    pub video_quality: i32,
     /// in seconds (default 4s for streaming, segment duration for segmented output, encoder default for files)
     #[prost(double, tag="10")]
     pub key_frame_interval: f64,
@@ -1340,6 +2233,8 @@ pub struct EgressInfo {
     pub room_id: ::prost::alloc::string::String,
     #[prost(string, tag="13")]
     pub room_name: ::prost::alloc::string::String,
+    #[prost(enumeration="EgressSourceType", tag="26")]
+    pub source_type: i32,
     #[prost(enumeration="EgressStatus", tag="3")]
     pub status: i32,
     #[prost(int64, tag="10")]
@@ -1348,15 +2243,26 @@ pub struct EgressInfo {
     pub ended_at: i64,
     #[prost(int64, tag="18")]
     pub updated_at: i64,
+    #[prost(string, tag="21")]
+    pub details: ::prost::alloc::string::String,
     #[prost(string, tag="9")]
     pub error: ::prost::alloc::string::String,
+    #[prost(int32, tag="22")]
+    pub error_code: i32,
     #[prost(message, repeated, tag="15")]
     pub stream_results: ::prost::alloc::vec::Vec<StreamInfo>,
     #[prost(message, repeated, tag="16")]
     pub file_results: ::prost::alloc::vec::Vec<FileInfo>,
     #[prost(message, repeated, tag="17")]
     pub segment_results: ::prost::alloc::vec::Vec<SegmentsInfo>,
-    #[prost(oneof="egress_info::Request", tags="4, 5, 6, 14")]
+    #[prost(message, repeated, tag="20")]
+    pub image_results: ::prost::alloc::vec::Vec<ImagesInfo>,
+    #[prost(string, tag="23")]
+    pub manifest_location: ::prost::alloc::string::String,
+    /// next ID: 27
+    #[prost(bool, tag="25")]
+    pub backup_storage_used: bool,
+    #[prost(oneof="egress_info::Request", tags="4, 14, 19, 5, 6")]
     pub request: ::core::option::Option<egress_info::Request>,
     /// deprecated (use _result fields)
     #[prost(oneof="egress_info::Result", tags="7, 8, 12")]
@@ -1369,12 +2275,14 @@ pub mod egress_info {
     pub enum Request {
         #[prost(message, tag="4")]
         RoomComposite(super::RoomCompositeEgressRequest),
+        #[prost(message, tag="14")]
+        Web(super::WebEgressRequest),
+        #[prost(message, tag="19")]
+        Participant(super::ParticipantEgressRequest),
         #[prost(message, tag="5")]
         TrackComposite(super::TrackCompositeEgressRequest),
         #[prost(message, tag="6")]
         Track(super::TrackEgressRequest),
-        #[prost(message, tag="14")]
-        Web(super::WebEgressRequest),
     }
     /// deprecated (use _result fields)
     #[allow(clippy::derive_partial_eq_without_eq)]
@@ -1463,12 +2371,16 @@ pub struct FileInfo {
 pub struct SegmentsInfo {
     #[prost(string, tag="1")]
     pub playlist_name: ::prost::alloc::string::String,
+    #[prost(string, tag="8")]
+    pub live_playlist_name: ::prost::alloc::string::String,
     #[prost(int64, tag="2")]
     pub duration: i64,
     #[prost(int64, tag="3")]
     pub size: i64,
     #[prost(string, tag="4")]
     pub playlist_location: ::prost::alloc::string::String,
+    #[prost(string, tag="9")]
+    pub live_playlist_location: ::prost::alloc::string::String,
     #[prost(int64, tag="5")]
     pub segment_count: i64,
     #[prost(int64, tag="6")]
@@ -1478,6 +2390,41 @@ pub struct SegmentsInfo {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ImagesInfo {
+    #[prost(string, tag="4")]
+    pub filename_prefix: ::prost::alloc::string::String,
+    #[prost(int64, tag="1")]
+    pub image_count: i64,
+    #[prost(int64, tag="2")]
+    pub started_at: i64,
+    #[prost(int64, tag="3")]
+    pub ended_at: i64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AutoParticipantEgress {
+    #[prost(message, repeated, tag="3")]
+    pub file_outputs: ::prost::alloc::vec::Vec<EncodedFileOutput>,
+    #[prost(message, repeated, tag="4")]
+    pub segment_outputs: ::prost::alloc::vec::Vec<SegmentedFileOutput>,
+    #[prost(oneof="auto_participant_egress::Options", tags="1, 2")]
+    pub options: ::core::option::Option<auto_participant_egress::Options>,
+}
+/// Nested message and enum types in `AutoParticipantEgress`.
+pub mod auto_participant_egress {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Options {
+        /// (default H264_720P_30)
+        #[prost(enumeration="super::EncodingOptionsPreset", tag="1")]
+        Preset(i32),
+        /// (optional)
+        #[prost(message, tag="2")]
+        Advanced(super::EncodingOptions),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct AutoTrackEgress {
     /// see docs for templating (default {track_id}-{time})
     #[prost(string, tag="1")]
@@ -1485,7 +2432,7 @@ pub struct AutoTrackEgress {
     /// disables upload of json manifest file (default false)
     #[prost(bool, tag="5")]
     pub disable_manifest: bool,
-    #[prost(oneof="auto_track_egress::Output", tags="2, 3, 4")]
+    #[prost(oneof="auto_track_egress::Output", tags="2, 3, 4, 6")]
     pub output: ::core::option::Option<auto_track_egress::Output>,
 }
 /// Nested message and enum types in `AutoTrackEgress`.
@@ -1499,6 +2446,8 @@ pub mod auto_track_egress {
         Gcp(super::GcpUpload),
         #[prost(message, tag="4")]
         Azure(super::AzureBlobUpload),
+        #[prost(message, tag="6")]
+        AliOss(super::AliOssUpload),
     }
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
@@ -1585,10 +2534,37 @@ impl SegmentedFileSuffix {
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum ImageFileSuffix {
+    ImageSuffixIndex = 0,
+    ImageSuffixTimestamp = 1,
+}
+impl ImageFileSuffix {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ImageFileSuffix::ImageSuffixIndex => "IMAGE_SUFFIX_INDEX",
+            ImageFileSuffix::ImageSuffixTimestamp => "IMAGE_SUFFIX_TIMESTAMP",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "IMAGE_SUFFIX_INDEX" => Some(Self::ImageSuffixIndex),
+            "IMAGE_SUFFIX_TIMESTAMP" => Some(Self::ImageSuffixTimestamp),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum StreamProtocol {
     /// protocol chosen based on urls
     DefaultProtocol = 0,
     Rtmp = 1,
+    Srt = 2,
 }
 impl StreamProtocol {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1599,6 +2575,7 @@ impl StreamProtocol {
         match self {
             StreamProtocol::DefaultProtocol => "DEFAULT_PROTOCOL",
             StreamProtocol::Rtmp => "RTMP",
+            StreamProtocol::Srt => "SRT",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1606,6 +2583,7 @@ impl StreamProtocol {
         match value {
             "DEFAULT_PROTOCOL" => Some(Self::DefaultProtocol),
             "RTMP" => Some(Self::Rtmp),
+            "SRT" => Some(Self::Srt),
             _ => None,
         }
     }
@@ -1703,10 +2681,36 @@ impl EgressStatus {
         }
     }
 }
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum EgressSourceType {
+    Web = 0,
+    Sdk = 1,
+}
+impl EgressSourceType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            EgressSourceType::Web => "EGRESS_SOURCE_TYPE_WEB",
+            EgressSourceType::Sdk => "EGRESS_SOURCE_TYPE_SDK",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "EGRESS_SOURCE_TYPE_WEB" => Some(Self::Web),
+            "EGRESS_SOURCE_TYPE_SDK" => Some(Self::Sdk),
+            _ => None,
+        }
+    }
+}
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SignalRequest {
-    #[prost(oneof="signal_request::Message", tags="1, 2, 3, 4, 5, 6, 7, 8, 10, 11, 12, 13, 14, 15, 16")]
+    #[prost(oneof="signal_request::Message", tags="1, 2, 3, 4, 5, 6, 7, 8, 10, 11, 12, 13, 14, 15, 16, 17, 18")]
     pub message: ::core::option::Option<signal_request::Message>,
 }
 /// Nested message and enum types in `SignalRequest`.
@@ -1753,17 +2757,24 @@ pub mod signal_request {
         /// deprecated by ping_req (message Ping)
         #[prost(int64, tag="14")]
         Ping(i64),
-        /// update a participant's own metadata and/or name
+        /// update a participant's own metadata, name, or attributes
+        /// requires canUpdateOwnParticipantMetadata permission
         #[prost(message, tag="15")]
         UpdateMetadata(super::UpdateParticipantMetadata),
         #[prost(message, tag="16")]
         PingReq(super::Ping),
+        /// Update local audio track settings
+        #[prost(message, tag="17")]
+        UpdateAudioTrack(super::UpdateLocalAudioTrack),
+        /// Update local video track settings
+        #[prost(message, tag="18")]
+        UpdateVideoTrack(super::UpdateLocalVideoTrack),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SignalResponse {
-    #[prost(oneof="signal_response::Message", tags="1, 2, 3, 4, 5, 6, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21")]
+    #[prost(oneof="signal_response::Message", tags="1, 2, 3, 4, 5, 6, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23")]
     pub message: ::core::option::Option<signal_response::Message>,
 }
 /// Nested message and enum types in `SignalResponse`.
@@ -1834,6 +2845,12 @@ pub mod signal_response {
         /// Subscription response, client should not expect any media from this subscription if it fails
         #[prost(message, tag="21")]
         SubscriptionResponse(super::SubscriptionResponse),
+        /// Response relating to user inititated requests that carry a `request_id`
+        #[prost(message, tag="22")]
+        RequestResponse(super::RequestResponse),
+        /// notify to the publisher when a published track has been subscribed for the first time
+        #[prost(message, tag="23")]
+        TrackSubscribed(super::TrackSubscribed),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -1843,8 +2860,6 @@ pub struct SimulcastCodec {
     pub codec: ::prost::alloc::string::String,
     #[prost(string, tag="2")]
     pub cid: ::prost::alloc::string::String,
-    #[prost(bool, tag="3")]
-    pub enable_simulcast_layers: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -1895,6 +2910,8 @@ pub struct TrickleRequest {
     pub candidate_init: ::prost::alloc::string::String,
     #[prost(enumeration="SignalTarget", tag="2")]
     pub target: i32,
+    #[prost(bool, tag="3")]
+    pub r#final: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -1939,6 +2956,11 @@ pub struct JoinResponse {
     /// Server-Injected-Frame byte trailer, used to identify unencrypted frames when e2ee is enabled
     #[prost(bytes="vec", tag="13")]
     pub sif_trailer: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, repeated, tag="14")]
+    pub enabled_publish_codecs: ::prost::alloc::vec::Vec<Codec>,
+    /// when set, client should attempt to establish publish peer connection when joining room to speed up publishing
+    #[prost(bool, tag="15")]
+    pub fast_publish: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -2018,13 +3040,72 @@ pub struct UpdateTrackSettings {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateLocalAudioTrack {
+    #[prost(string, tag="1")]
+    pub track_sid: ::prost::alloc::string::String,
+    #[prost(enumeration="AudioTrackFeature", repeated, tag="2")]
+    pub features: ::prost::alloc::vec::Vec<i32>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateLocalVideoTrack {
+    #[prost(string, tag="1")]
+    pub track_sid: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub width: u32,
+    #[prost(uint32, tag="3")]
+    pub height: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct LeaveRequest {
     /// sent when server initiates the disconnect due to server-restart
     /// indicates clients should attempt full-reconnect sequence
+    /// NOTE: `can_reconnect` obsoleted by `action` starting in protocol version 13
     #[prost(bool, tag="1")]
     pub can_reconnect: bool,
     #[prost(enumeration="DisconnectReason", tag="2")]
     pub reason: i32,
+    #[prost(enumeration="leave_request::Action", tag="3")]
+    pub action: i32,
+    #[prost(message, optional, tag="4")]
+    pub regions: ::core::option::Option<RegionSettings>,
+}
+/// Nested message and enum types in `LeaveRequest`.
+pub mod leave_request {
+    /// indicates action clients should take on receiving this message
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Action {
+        /// should disconnect
+        Disconnect = 0,
+        /// should attempt a resume with `reconnect=1` in join URL
+        Resume = 1,
+        /// should attempt a reconnect, i. e. no `reconnect=1`
+        Reconnect = 2,
+    }
+    impl Action {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                Action::Disconnect => "DISCONNECT",
+                Action::Resume => "RESUME",
+                Action::Reconnect => "RECONNECT",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "DISCONNECT" => Some(Self::Disconnect),
+                "RESUME" => Some(Self::Resume),
+                "RECONNECT" => Some(Self::Reconnect),
+                _ => None,
+            }
+        }
+    }
 }
 /// message to indicate published video track dimensions are changing
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -2042,6 +3123,12 @@ pub struct UpdateParticipantMetadata {
     pub metadata: ::prost::alloc::string::String,
     #[prost(string, tag="2")]
     pub name: ::prost::alloc::string::String,
+    /// attributes to update. it only updates attributes that have been set
+    /// to delete attributes, set the value to an empty string
+    #[prost(map="string, string", tag="3")]
+    pub attributes: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+    #[prost(uint32, tag="4")]
+    pub request_id: u32,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -2169,6 +3256,8 @@ pub struct SyncState {
     /// last received server side offer before reconnecting
     #[prost(message, optional, tag="5")]
     pub offer: ::core::option::Option<SessionDescription>,
+    #[prost(string, repeated, tag="6")]
+    pub track_sids_disabled: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -2183,7 +3272,7 @@ pub struct DataChannelInfo {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SimulateScenario {
-    #[prost(oneof="simulate_scenario::Scenario", tags="1, 2, 3, 4, 5, 6")]
+    #[prost(oneof="simulate_scenario::Scenario", tags="1, 2, 3, 4, 5, 6, 7, 8, 9")]
     pub scenario: ::core::option::Option<simulate_scenario::Scenario>,
 }
 /// Nested message and enum types in `SimulateScenario`.
@@ -2210,6 +3299,15 @@ pub mod simulate_scenario {
         /// when zero, clears artificial bandwidth limit
         #[prost(int64, tag="6")]
         SubscriberBandwidth(i64),
+        /// disconnect signal on resume
+        #[prost(bool, tag="7")]
+        DisconnectSignalOnResume(bool),
+        /// disconnect signal on resume before sending any messages from server
+        #[prost(bool, tag="8")]
+        DisconnectSignalOnResumeNoMessages(bool),
+        /// full reconnect leave request
+        #[prost(bool, tag="9")]
+        LeaveRequestFullReconnect(bool),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -2254,24 +3352,75 @@ pub struct SubscriptionResponse {
     #[prost(enumeration="SubscriptionError", tag="2")]
     pub err: i32,
 }
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
-#[repr(i32)]
-pub enum SignalTarget {
-    Publisher = 0,
-    Subscriber = 1,
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestResponse {
+    #[prost(uint32, tag="1")]
+    pub request_id: u32,
+    #[prost(enumeration="request_response::Reason", tag="2")]
+    pub reason: i32,
+    #[prost(string, tag="3")]
+    pub message: ::prost::alloc::string::String,
 }
-impl SignalTarget {
-    /// String value of the enum field names used in the ProtoBuf definition.
-    ///
-    /// The values are not transformed in any way and thus are considered stable
-    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
-    pub fn as_str_name(&self) -> &'static str {
-        match self {
-            SignalTarget::Publisher => "PUBLISHER",
-            SignalTarget::Subscriber => "SUBSCRIBER",
-        }
+/// Nested message and enum types in `RequestResponse`.
+pub mod request_response {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Reason {
+        Ok = 0,
+        NotFound = 1,
+        NotAllowed = 2,
+        LimitExceeded = 3,
     }
-    /// Creates an enum from field names used in the ProtoBuf definition.
+    impl Reason {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                Reason::Ok => "OK",
+                Reason::NotFound => "NOT_FOUND",
+                Reason::NotAllowed => "NOT_ALLOWED",
+                Reason::LimitExceeded => "LIMIT_EXCEEDED",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "OK" => Some(Self::Ok),
+                "NOT_FOUND" => Some(Self::NotFound),
+                "NOT_ALLOWED" => Some(Self::NotAllowed),
+                "LIMIT_EXCEEDED" => Some(Self::LimitExceeded),
+                _ => None,
+            }
+        }
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TrackSubscribed {
+    #[prost(string, tag="1")]
+    pub track_sid: ::prost::alloc::string::String,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum SignalTarget {
+    Publisher = 0,
+    Subscriber = 1,
+}
+impl SignalTarget {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            SignalTarget::Publisher => "PUBLISHER",
+            SignalTarget::Subscriber => "SUBSCRIBER",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
     pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
         match value {
             "PUBLISHER" => Some(Self::Publisher),
@@ -2337,555 +3486,1866 @@ impl CandidateProtocol {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct CreateRoomRequest {
-    /// name of the room
+pub struct Job {
     #[prost(string, tag="1")]
-    pub name: ::prost::alloc::string::String,
-    /// number of seconds to keep the room open if no one joins
-    #[prost(uint32, tag="2")]
-    pub empty_timeout: u32,
-    /// limit number of participants that can be in a room
-    #[prost(uint32, tag="3")]
-    pub max_participants: u32,
-    /// override the node room is allocated to, for debugging
-    #[prost(string, tag="4")]
-    pub node_id: ::prost::alloc::string::String,
-    /// metadata of room
+    pub id: ::prost::alloc::string::String,
+    #[prost(string, tag="9")]
+    pub dispatch_id: ::prost::alloc::string::String,
+    #[prost(enumeration="JobType", tag="2")]
+    pub r#type: i32,
+    #[prost(message, optional, tag="3")]
+    pub room: ::core::option::Option<Room>,
+    #[prost(message, optional, tag="4")]
+    pub participant: ::core::option::Option<ParticipantInfo>,
+    #[deprecated]
     #[prost(string, tag="5")]
+    pub namespace: ::prost::alloc::string::String,
+    #[prost(string, tag="6")]
     pub metadata: ::prost::alloc::string::String,
-    /// egress
-    #[prost(message, optional, tag="6")]
-    pub egress: ::core::option::Option<RoomEgress>,
+    #[prost(string, tag="7")]
+    pub agent_name: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="8")]
+    pub state: ::core::option::Option<JobState>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct RoomEgress {
-    #[prost(message, optional, tag="1")]
-    pub room: ::core::option::Option<RoomCompositeEgressRequest>,
-    #[prost(message, optional, tag="2")]
-    pub tracks: ::core::option::Option<AutoTrackEgress>,
+pub struct JobState {
+    #[prost(enumeration="JobStatus", tag="1")]
+    pub status: i32,
+    #[prost(string, tag="2")]
+    pub error: ::prost::alloc::string::String,
+    #[prost(int64, tag="3")]
+    pub started_at: i64,
+    #[prost(int64, tag="4")]
+    pub ended_at: i64,
+    #[prost(int64, tag="5")]
+    pub updated_at: i64,
+    #[prost(string, tag="6")]
+    pub participant_identity: ::prost::alloc::string::String,
 }
+/// from Worker to Server
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ListRoomsRequest {
-    /// when set, will only return rooms with name match
-    #[prost(string, repeated, tag="1")]
-    pub names: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+pub struct WorkerMessage {
+    #[prost(oneof="worker_message::Message", tags="1, 2, 3, 4, 5, 6, 7")]
+    pub message: ::core::option::Option<worker_message::Message>,
 }
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ListRoomsResponse {
-    #[prost(message, repeated, tag="1")]
-    pub rooms: ::prost::alloc::vec::Vec<Room>,
+/// Nested message and enum types in `WorkerMessage`.
+pub mod worker_message {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Message {
+        /// agent workers need to register themselves with the server first
+        #[prost(message, tag="1")]
+        Register(super::RegisterWorkerRequest),
+        /// worker confirms to server that it's available for a job, or declines it
+        #[prost(message, tag="2")]
+        Availability(super::AvailabilityResponse),
+        /// worker can update its status to the server, including taking itself out of the pool
+        #[prost(message, tag="3")]
+        UpdateWorker(super::UpdateWorkerStatus),
+        /// job can send status updates to the server, useful for tracking progress
+        #[prost(message, tag="4")]
+        UpdateJob(super::UpdateJobStatus),
+        #[prost(message, tag="5")]
+        Ping(super::WorkerPing),
+        #[prost(message, tag="6")]
+        SimulateJob(super::SimulateJobRequest),
+        #[prost(message, tag="7")]
+        MigrateJob(super::MigrateJobRequest),
+    }
 }
+/// from Server to Worker
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct DeleteRoomRequest {
-    /// name of the room
-    #[prost(string, tag="1")]
-    pub room: ::prost::alloc::string::String,
+pub struct ServerMessage {
+    #[prost(oneof="server_message::Message", tags="1, 2, 3, 5, 4")]
+    pub message: ::core::option::Option<server_message::Message>,
+}
+/// Nested message and enum types in `ServerMessage`.
+pub mod server_message {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Message {
+        /// server confirms the registration, from this moment on, the worker is considered active
+        #[prost(message, tag="1")]
+        Register(super::RegisterWorkerResponse),
+        /// server asks worker to confirm availability for a job
+        #[prost(message, tag="2")]
+        Availability(super::AvailabilityRequest),
+        #[prost(message, tag="3")]
+        Assignment(super::JobAssignment),
+        #[prost(message, tag="5")]
+        Termination(super::JobTermination),
+        #[prost(message, tag="4")]
+        Pong(super::WorkerPong),
+    }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct DeleteRoomResponse {
+pub struct SimulateJobRequest {
+    #[prost(enumeration="JobType", tag="1")]
+    pub r#type: i32,
+    #[prost(message, optional, tag="2")]
+    pub room: ::core::option::Option<Room>,
+    #[prost(message, optional, tag="3")]
+    pub participant: ::core::option::Option<ParticipantInfo>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ListParticipantsRequest {
-    /// name of the room
-    #[prost(string, tag="1")]
-    pub room: ::prost::alloc::string::String,
+pub struct WorkerPing {
+    #[prost(int64, tag="1")]
+    pub timestamp: i64,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ListParticipantsResponse {
-    #[prost(message, repeated, tag="1")]
-    pub participants: ::prost::alloc::vec::Vec<ParticipantInfo>,
+pub struct WorkerPong {
+    #[prost(int64, tag="1")]
+    pub last_timestamp: i64,
+    #[prost(int64, tag="2")]
+    pub timestamp: i64,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct RoomParticipantIdentity {
-    /// name of the room
-    #[prost(string, tag="1")]
-    pub room: ::prost::alloc::string::String,
-    /// identity of the participant
-    #[prost(string, tag="2")]
-    pub identity: ::prost::alloc::string::String,
+pub struct RegisterWorkerRequest {
+    #[prost(enumeration="JobType", tag="1")]
+    pub r#type: i32,
+    #[prost(string, tag="8")]
+    pub agent_name: ::prost::alloc::string::String,
+    /// string worker_id = 2;
+    #[prost(string, tag="3")]
+    pub version: ::prost::alloc::string::String,
+    /// string name = 4 [deprecated = true];
+    #[prost(uint32, tag="5")]
+    pub ping_interval: u32,
+    #[prost(string, optional, tag="6")]
+    pub namespace: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(message, optional, tag="7")]
+    pub allowed_permissions: ::core::option::Option<ParticipantPermission>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct RemoveParticipantResponse {
+pub struct RegisterWorkerResponse {
+    #[prost(string, tag="1")]
+    pub worker_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="3")]
+    pub server_info: ::core::option::Option<ServerInfo>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct MuteRoomTrackRequest {
-    /// name of the room
-    #[prost(string, tag="1")]
-    pub room: ::prost::alloc::string::String,
-    #[prost(string, tag="2")]
-    pub identity: ::prost::alloc::string::String,
-    /// sid of the track to mute
-    #[prost(string, tag="3")]
-    pub track_sid: ::prost::alloc::string::String,
-    /// set to true to mute, false to unmute
-    #[prost(bool, tag="4")]
-    pub muted: bool,
+pub struct MigrateJobRequest {
+    /// string job_id = 1 [deprecated = true];
+    #[prost(string, repeated, tag="2")]
+    pub job_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct MuteRoomTrackResponse {
+pub struct AvailabilityRequest {
     #[prost(message, optional, tag="1")]
-    pub track: ::core::option::Option<TrackInfo>,
+    pub job: ::core::option::Option<Job>,
+    /// True when the job was previously assigned to another worker but has been
+    /// migrated due to different reasons (e.g. worker failure, job migration)
+    #[prost(bool, tag="2")]
+    pub resuming: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct UpdateParticipantRequest {
+pub struct AvailabilityResponse {
     #[prost(string, tag="1")]
-    pub room: ::prost::alloc::string::String,
-    #[prost(string, tag="2")]
-    pub identity: ::prost::alloc::string::String,
-    /// metadata to update. skipping updates if left empty
-    #[prost(string, tag="3")]
-    pub metadata: ::prost::alloc::string::String,
-    /// set to update the participant's permissions
-    #[prost(message, optional, tag="4")]
-    pub permission: ::core::option::Option<ParticipantPermission>,
-    /// display name to update
+    pub job_id: ::prost::alloc::string::String,
+    #[prost(bool, tag="2")]
+    pub available: bool,
+    #[prost(bool, tag="3")]
+    pub supports_resume: bool,
+    #[prost(string, tag="4")]
+    pub participant_name: ::prost::alloc::string::String,
     #[prost(string, tag="5")]
-    pub name: ::prost::alloc::string::String,
+    pub participant_identity: ::prost::alloc::string::String,
+    #[prost(string, tag="6")]
+    pub participant_metadata: ::prost::alloc::string::String,
+    #[prost(map="string, string", tag="7")]
+    pub participant_attributes: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct UpdateSubscriptionsRequest {
+pub struct UpdateJobStatus {
     #[prost(string, tag="1")]
-    pub room: ::prost::alloc::string::String,
-    #[prost(string, tag="2")]
-    pub identity: ::prost::alloc::string::String,
-    /// list of sids of tracks
-    #[prost(string, repeated, tag="3")]
-    pub track_sids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
-    /// set to true to subscribe, false to unsubscribe from tracks
-    #[prost(bool, tag="4")]
-    pub subscribe: bool,
-    /// list of participants and their tracks
-    #[prost(message, repeated, tag="5")]
-    pub participant_tracks: ::prost::alloc::vec::Vec<ParticipantTracks>,
-}
-/// empty for now
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct UpdateSubscriptionsResponse {
+    pub job_id: ::prost::alloc::string::String,
+    /// The worker can indicate the job end by either specifying SUCCESS or FAILED
+    #[prost(enumeration="JobStatus", tag="2")]
+    pub status: i32,
+    /// metadata shown on the dashboard, useful for debugging
+    #[prost(string, tag="3")]
+    pub error: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct SendDataRequest {
-    #[prost(string, tag="1")]
-    pub room: ::prost::alloc::string::String,
-    #[prost(bytes="vec", tag="2")]
-    pub data: ::prost::alloc::vec::Vec<u8>,
-    #[prost(enumeration="data_packet::Kind", tag="3")]
-    pub kind: i32,
-    #[prost(string, repeated, tag="4")]
-    pub destination_sids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
-    #[prost(string, optional, tag="5")]
-    pub topic: ::core::option::Option<::prost::alloc::string::String>,
+pub struct UpdateWorkerStatus {
+    #[prost(enumeration="WorkerStatus", optional, tag="1")]
+    pub status: ::core::option::Option<i32>,
+    /// optional string metadata = 2 \[deprecated=true\];
+    #[prost(float, tag="3")]
+    pub load: f32,
+    #[prost(uint32, tag="4")]
+    pub job_count: u32,
 }
-///
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct SendDataResponse {
+pub struct JobAssignment {
+    #[prost(message, optional, tag="1")]
+    pub job: ::core::option::Option<Job>,
+    #[prost(string, optional, tag="2")]
+    pub url: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, tag="3")]
+    pub token: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct UpdateRoomMetadataRequest {
+pub struct JobTermination {
     #[prost(string, tag="1")]
-    pub room: ::prost::alloc::string::String,
-    /// metadata to update. skipping updates if left empty
-    #[prost(string, tag="2")]
-    pub metadata: ::prost::alloc::string::String,
+    pub job_id: ::prost::alloc::string::String,
 }
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct CreateIngressRequest {
-    #[prost(enumeration="IngressInput", tag="1")]
-    pub input_type: i32,
-    /// User provided identifier for the ingress
-    #[prost(string, tag="2")]
-    pub name: ::prost::alloc::string::String,
-    /// room to publish to
-    #[prost(string, tag="3")]
-    pub room_name: ::prost::alloc::string::String,
-    /// publish as participant
-    #[prost(string, tag="4")]
-    pub participant_identity: ::prost::alloc::string::String,
-    /// name of publishing participant (used for display only)
-    #[prost(string, tag="5")]
-    pub participant_name: ::prost::alloc::string::String,
-    /// whether to pass through the incoming media without transcoding, only compatible with some input types
-    #[prost(bool, tag="8")]
-    pub bypass_transcoding: bool,
-    #[prost(message, optional, tag="6")]
-    pub audio: ::core::option::Option<IngressAudioOptions>,
-    #[prost(message, optional, tag="7")]
-    pub video: ::core::option::Option<IngressVideoOptions>,
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum JobType {
+    JtRoom = 0,
+    JtPublisher = 1,
 }
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct IngressAudioOptions {
+impl JobType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            JobType::JtRoom => "JT_ROOM",
+            JobType::JtPublisher => "JT_PUBLISHER",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "JT_ROOM" => Some(Self::JtRoom),
+            "JT_PUBLISHER" => Some(Self::JtPublisher),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum WorkerStatus {
+    WsAvailable = 0,
+    WsFull = 1,
+}
+impl WorkerStatus {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            WorkerStatus::WsAvailable => "WS_AVAILABLE",
+            WorkerStatus::WsFull => "WS_FULL",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "WS_AVAILABLE" => Some(Self::WsAvailable),
+            "WS_FULL" => Some(Self::WsFull),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum JobStatus {
+    JsPending = 0,
+    JsRunning = 1,
+    JsSuccess = 2,
+    JsFailed = 3,
+}
+impl JobStatus {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            JobStatus::JsPending => "JS_PENDING",
+            JobStatus::JsRunning => "JS_RUNNING",
+            JobStatus::JsSuccess => "JS_SUCCESS",
+            JobStatus::JsFailed => "JS_FAILED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "JS_PENDING" => Some(Self::JsPending),
+            "JS_RUNNING" => Some(Self::JsRunning),
+            "JS_SUCCESS" => Some(Self::JsSuccess),
+            "JS_FAILED" => Some(Self::JsFailed),
+            _ => None,
+        }
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateAgentDispatchRequest {
+    #[prost(string, tag="1")]
+    pub agent_name: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub room: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub metadata: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RoomAgentDispatch {
+    #[prost(string, tag="1")]
+    pub agent_name: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub metadata: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteAgentDispatchRequest {
+    #[prost(string, tag="1")]
+    pub dispatch_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub room: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListAgentDispatchRequest {
+    /// if set, only the dispatch whose id is given will be returned
+    #[prost(string, tag="1")]
+    pub dispatch_id: ::prost::alloc::string::String,
+    /// name of the room to list agents for. Must be set.
+    #[prost(string, tag="2")]
+    pub room: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListAgentDispatchResponse {
+    #[prost(message, repeated, tag="1")]
+    pub agent_dispatches: ::prost::alloc::vec::Vec<AgentDispatch>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AgentDispatch {
+    #[prost(string, tag="1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub agent_name: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub room: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub metadata: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="5")]
+    pub state: ::core::option::Option<AgentDispatchState>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AgentDispatchState {
+    /// For dispatches of tyoe JT_ROOM, there will be at most 1 job. 
+    /// For dispatches of type JT_PUBLISHER, there will be 1 per publisher.
+    #[prost(message, repeated, tag="1")]
+    pub jobs: ::prost::alloc::vec::Vec<Job>,
+    #[prost(int64, tag="2")]
+    pub created_at: i64,
+    #[prost(int64, tag="3")]
+    pub deleted_at: i64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateRoomRequest {
+    /// name of the room
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+    /// configuration to use for this room parameters. Setting parameters below override the config defaults.
+    #[prost(string, tag="12")]
+    pub room_preset: ::prost::alloc::string::String,
+    /// number of seconds to keep the room open if no one joins
+    #[prost(uint32, tag="2")]
+    pub empty_timeout: u32,
+    /// number of seconds to keep the room open after everyone leaves
+    #[prost(uint32, tag="10")]
+    pub departure_timeout: u32,
+    /// limit number of participants that can be in a room
+    #[prost(uint32, tag="3")]
+    pub max_participants: u32,
+    /// override the node room is allocated to, for debugging
+    #[prost(string, tag="4")]
+    pub node_id: ::prost::alloc::string::String,
+    /// metadata of room
+    #[prost(string, tag="5")]
+    pub metadata: ::prost::alloc::string::String,
+    /// auto-egress configurations
+    #[prost(message, optional, tag="6")]
+    pub egress: ::core::option::Option<RoomEgress>,
+    /// playout delay of subscriber
+    #[prost(uint32, tag="7")]
+    pub min_playout_delay: u32,
+    #[prost(uint32, tag="8")]
+    pub max_playout_delay: u32,
+    /// improves A/V sync when playout_delay set to a value larger than 200ms. It will disables transceiver re-use
+    /// so not recommended for rooms with frequent subscription changes
+    #[prost(bool, tag="9")]
+    pub sync_streams: bool,
+    /// replay
+    #[prost(bool, tag="13")]
+    pub replay_enabled: bool,
+    /// Define agents that should be dispatched to this room
+    ///
+    /// NEXT-ID: 15
+    #[prost(message, repeated, tag="14")]
+    pub agents: ::prost::alloc::vec::Vec<RoomAgentDispatch>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RoomEgress {
+    #[prost(message, optional, tag="1")]
+    pub room: ::core::option::Option<RoomCompositeEgressRequest>,
+    #[prost(message, optional, tag="3")]
+    pub participant: ::core::option::Option<AutoParticipantEgress>,
+    #[prost(message, optional, tag="2")]
+    pub tracks: ::core::option::Option<AutoTrackEgress>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RoomAgent {
+    #[prost(message, repeated, tag="1")]
+    pub dispatches: ::prost::alloc::vec::Vec<RoomAgentDispatch>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListRoomsRequest {
+    /// when set, will only return rooms with name match
+    #[prost(string, repeated, tag="1")]
+    pub names: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListRoomsResponse {
+    #[prost(message, repeated, tag="1")]
+    pub rooms: ::prost::alloc::vec::Vec<Room>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteRoomRequest {
+    /// name of the room
+    #[prost(string, tag="1")]
+    pub room: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteRoomResponse {
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListParticipantsRequest {
+    /// name of the room
+    #[prost(string, tag="1")]
+    pub room: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListParticipantsResponse {
+    #[prost(message, repeated, tag="1")]
+    pub participants: ::prost::alloc::vec::Vec<ParticipantInfo>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RoomParticipantIdentity {
+    /// name of the room
+    #[prost(string, tag="1")]
+    pub room: ::prost::alloc::string::String,
+    /// identity of the participant
+    #[prost(string, tag="2")]
+    pub identity: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RemoveParticipantResponse {
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MuteRoomTrackRequest {
+    /// name of the room
+    #[prost(string, tag="1")]
+    pub room: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub identity: ::prost::alloc::string::String,
+    /// sid of the track to mute
+    #[prost(string, tag="3")]
+    pub track_sid: ::prost::alloc::string::String,
+    /// set to true to mute, false to unmute
+    #[prost(bool, tag="4")]
+    pub muted: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MuteRoomTrackResponse {
+    #[prost(message, optional, tag="1")]
+    pub track: ::core::option::Option<TrackInfo>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateParticipantRequest {
+    #[prost(string, tag="1")]
+    pub room: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub identity: ::prost::alloc::string::String,
+    /// metadata to update. skipping updates if left empty
+    #[prost(string, tag="3")]
+    pub metadata: ::prost::alloc::string::String,
+    /// set to update the participant's permissions
+    #[prost(message, optional, tag="4")]
+    pub permission: ::core::option::Option<ParticipantPermission>,
+    /// display name to update
+    #[prost(string, tag="5")]
+    pub name: ::prost::alloc::string::String,
+    /// attributes to update. it only updates attributes that have been set
+    /// to delete attributes, set the value to an empty string
+    #[prost(map="string, string", tag="6")]
+    pub attributes: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateSubscriptionsRequest {
+    #[prost(string, tag="1")]
+    pub room: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub identity: ::prost::alloc::string::String,
+    /// list of sids of tracks
+    #[prost(string, repeated, tag="3")]
+    pub track_sids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// set to true to subscribe, false to unsubscribe from tracks
+    #[prost(bool, tag="4")]
+    pub subscribe: bool,
+    /// list of participants and their tracks
+    #[prost(message, repeated, tag="5")]
+    pub participant_tracks: ::prost::alloc::vec::Vec<ParticipantTracks>,
+}
+/// empty for now
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateSubscriptionsResponse {
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SendDataRequest {
+    #[prost(string, tag="1")]
+    pub room: ::prost::alloc::string::String,
+    #[prost(bytes="vec", tag="2")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+    #[prost(enumeration="data_packet::Kind", tag="3")]
+    pub kind: i32,
+    /// mark deprecated
+    #[deprecated]
+    #[prost(string, repeated, tag="4")]
+    pub destination_sids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// when set, only forward to these identities
+    #[prost(string, repeated, tag="6")]
+    pub destination_identities: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="5")]
+    pub topic: ::core::option::Option<::prost::alloc::string::String>,
+    /// added by SDK to enable de-duping of messages, for INTERNAL USE ONLY
+    #[prost(bytes="vec", tag="7")]
+    pub nonce: ::prost::alloc::vec::Vec<u8>,
+}
+///
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SendDataResponse {
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateRoomMetadataRequest {
+    #[prost(string, tag="1")]
+    pub room: ::prost::alloc::string::String,
+    /// metadata to update. skipping updates if left empty
+    #[prost(string, tag="2")]
+    pub metadata: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RoomConfiguration {
+    /// Used as ID, must be unique
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+    /// number of seconds to keep the room open if no one joins
+    #[prost(uint32, tag="2")]
+    pub empty_timeout: u32,
+    /// number of seconds to keep the room open after everyone leaves
+    #[prost(uint32, tag="3")]
+    pub departure_timeout: u32,
+    /// limit number of participants that can be in a room, excluding Egress and Ingress participants
+    #[prost(uint32, tag="4")]
+    pub max_participants: u32,
+    /// egress
+    #[prost(message, optional, tag="5")]
+    pub egress: ::core::option::Option<RoomEgress>,
+    /// playout delay of subscriber
+    #[prost(uint32, tag="7")]
+    pub min_playout_delay: u32,
+    #[prost(uint32, tag="8")]
+    pub max_playout_delay: u32,
+    /// improves A/V sync when playout_delay set to a value larger than 200ms. It will disables transceiver re-use
+    /// so not recommended for rooms with frequent subscription changes
+    #[prost(bool, tag="9")]
+    pub sync_streams: bool,
+    /// Define agents that should be dispatched to this room
+    #[prost(message, repeated, tag="10")]
+    pub agents: ::prost::alloc::vec::Vec<RoomAgentDispatch>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateIngressRequest {
+    #[prost(enumeration="IngressInput", tag="1")]
+    pub input_type: i32,
+    /// Where to pull media from, only for URL input type
+    #[prost(string, tag="9")]
+    pub url: ::prost::alloc::string::String,
+    /// User provided identifier for the ingress
+    #[prost(string, tag="2")]
+    pub name: ::prost::alloc::string::String,
+    /// room to publish to
+    #[prost(string, tag="3")]
+    pub room_name: ::prost::alloc::string::String,
+    /// publish as participant
+    #[prost(string, tag="4")]
+    pub participant_identity: ::prost::alloc::string::String,
+    /// name of publishing participant (used for display only)
+    #[prost(string, tag="5")]
+    pub participant_name: ::prost::alloc::string::String,
+    /// metadata associated with the publishing participant
+    #[prost(string, tag="10")]
+    pub participant_metadata: ::prost::alloc::string::String,
+    /// [depreacted ] whether to pass through the incoming media without transcoding, only compatible with some input types. Use `enable_transcoding` instead.
+    #[deprecated]
+    #[prost(bool, tag="8")]
+    pub bypass_transcoding: bool,
+    /// Whether to transcode the ingested media. Only WHIP supports disabling transcoding currently. WHIP will default to transcoding disabled. Replaces `bypass_transcoding. 
+    #[prost(bool, optional, tag="11")]
+    pub enable_transcoding: ::core::option::Option<bool>,
+    #[prost(message, optional, tag="6")]
+    pub audio: ::core::option::Option<IngressAudioOptions>,
+    #[prost(message, optional, tag="7")]
+    pub video: ::core::option::Option<IngressVideoOptions>,
+    /// The default value is true and when set to false, the new connection attempts will be rejected
+    #[prost(bool, optional, tag="12")]
+    pub enabled: ::core::option::Option<bool>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IngressAudioOptions {
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(enumeration="TrackSource", tag="2")]
+    pub source: i32,
+    #[prost(oneof="ingress_audio_options::EncodingOptions", tags="3, 4")]
+    pub encoding_options: ::core::option::Option<ingress_audio_options::EncodingOptions>,
+}
+/// Nested message and enum types in `IngressAudioOptions`.
+pub mod ingress_audio_options {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum EncodingOptions {
+        #[prost(enumeration="super::IngressAudioEncodingPreset", tag="3")]
+        Preset(i32),
+        #[prost(message, tag="4")]
+        Options(super::IngressAudioEncodingOptions),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IngressVideoOptions {
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(enumeration="TrackSource", tag="2")]
+    pub source: i32,
+    #[prost(oneof="ingress_video_options::EncodingOptions", tags="3, 4")]
+    pub encoding_options: ::core::option::Option<ingress_video_options::EncodingOptions>,
+}
+/// Nested message and enum types in `IngressVideoOptions`.
+pub mod ingress_video_options {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum EncodingOptions {
+        #[prost(enumeration="super::IngressVideoEncodingPreset", tag="3")]
+        Preset(i32),
+        #[prost(message, tag="4")]
+        Options(super::IngressVideoEncodingOptions),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IngressAudioEncodingOptions {
+    /// desired audio codec to publish to room
+    #[prost(enumeration="AudioCodec", tag="1")]
+    pub audio_codec: i32,
+    #[prost(uint32, tag="2")]
+    pub bitrate: u32,
+    #[prost(bool, tag="3")]
+    pub disable_dtx: bool,
+    #[prost(uint32, tag="4")]
+    pub channels: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IngressVideoEncodingOptions {
+    /// desired codec to publish to room
+    #[prost(enumeration="VideoCodec", tag="1")]
+    pub video_codec: i32,
+    #[prost(double, tag="2")]
+    pub frame_rate: f64,
+    /// simulcast layers to publish, when empty, should usually be set to layers at 1/2 and 1/4 of the dimensions
+    #[prost(message, repeated, tag="3")]
+    pub layers: ::prost::alloc::vec::Vec<VideoLayer>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IngressInfo {
     #[prost(string, tag="1")]
+    pub ingress_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
     pub name: ::prost::alloc::string::String,
-    #[prost(enumeration="TrackSource", tag="2")]
-    pub source: i32,
-    #[prost(oneof="ingress_audio_options::EncodingOptions", tags="3, 4")]
-    pub encoding_options: ::core::option::Option<ingress_audio_options::EncodingOptions>,
+    #[prost(string, tag="3")]
+    pub stream_key: ::prost::alloc::string::String,
+    /// URL to point the encoder to for push (RTMP, WHIP), or location to pull media from for pull (URL)
+    #[prost(string, tag="4")]
+    pub url: ::prost::alloc::string::String,
+    /// for RTMP input, it'll be a rtmp:// URL
+    /// for FILE input, it'll be a http:// URL
+    /// for SRT input, it'll be a srt:// URL
+    #[prost(enumeration="IngressInput", tag="5")]
+    pub input_type: i32,
+    #[deprecated]
+    #[prost(bool, tag="13")]
+    pub bypass_transcoding: bool,
+    #[prost(bool, optional, tag="15")]
+    pub enable_transcoding: ::core::option::Option<bool>,
+    #[prost(message, optional, tag="6")]
+    pub audio: ::core::option::Option<IngressAudioOptions>,
+    #[prost(message, optional, tag="7")]
+    pub video: ::core::option::Option<IngressVideoOptions>,
+    #[prost(string, tag="8")]
+    pub room_name: ::prost::alloc::string::String,
+    #[prost(string, tag="9")]
+    pub participant_identity: ::prost::alloc::string::String,
+    #[prost(string, tag="10")]
+    pub participant_name: ::prost::alloc::string::String,
+    #[prost(string, tag="14")]
+    pub participant_metadata: ::prost::alloc::string::String,
+    #[prost(bool, tag="11")]
+    pub reusable: bool,
+    /// Description of error/stream non compliance and debug info for publisher otherwise (received bitrate, resolution, bandwidth)
+    #[prost(message, optional, tag="12")]
+    pub state: ::core::option::Option<IngressState>,
+    /// The default value is true and when set to false, the new connection attempts will be rejected
+    #[prost(bool, optional, tag="16")]
+    pub enabled: ::core::option::Option<bool>,
 }
-/// Nested message and enum types in `IngressAudioOptions`.
-pub mod ingress_audio_options {
-    #[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Oneof)]
-    pub enum EncodingOptions {
-        #[prost(enumeration="super::IngressAudioEncodingPreset", tag="3")]
-        Preset(i32),
-        #[prost(message, tag="4")]
-        Options(super::IngressAudioEncodingOptions),
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IngressState {
+    #[prost(enumeration="ingress_state::Status", tag="1")]
+    pub status: i32,
+    /// Error/non compliance description if any
+    #[prost(string, tag="2")]
+    pub error: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="3")]
+    pub video: ::core::option::Option<InputVideoState>,
+    #[prost(message, optional, tag="4")]
+    pub audio: ::core::option::Option<InputAudioState>,
+    /// ID of the current/previous room published to
+    #[prost(string, tag="5")]
+    pub room_id: ::prost::alloc::string::String,
+    #[prost(int64, tag="7")]
+    pub started_at: i64,
+    #[prost(int64, tag="8")]
+    pub ended_at: i64,
+    #[prost(int64, tag="10")]
+    pub updated_at: i64,
+    #[prost(string, tag="9")]
+    pub resource_id: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag="6")]
+    pub tracks: ::prost::alloc::vec::Vec<TrackInfo>,
+}
+/// Nested message and enum types in `IngressState`.
+pub mod ingress_state {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Status {
+        EndpointInactive = 0,
+        EndpointBuffering = 1,
+        EndpointPublishing = 2,
+        EndpointError = 3,
+        EndpointComplete = 4,
+    }
+    impl Status {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                Status::EndpointInactive => "ENDPOINT_INACTIVE",
+                Status::EndpointBuffering => "ENDPOINT_BUFFERING",
+                Status::EndpointPublishing => "ENDPOINT_PUBLISHING",
+                Status::EndpointError => "ENDPOINT_ERROR",
+                Status::EndpointComplete => "ENDPOINT_COMPLETE",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "ENDPOINT_INACTIVE" => Some(Self::EndpointInactive),
+                "ENDPOINT_BUFFERING" => Some(Self::EndpointBuffering),
+                "ENDPOINT_PUBLISHING" => Some(Self::EndpointPublishing),
+                "ENDPOINT_ERROR" => Some(Self::EndpointError),
+                "ENDPOINT_COMPLETE" => Some(Self::EndpointComplete),
+                _ => None,
+            }
+        }
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InputVideoState {
+    #[prost(string, tag="1")]
+    pub mime_type: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub average_bitrate: u32,
+    #[prost(uint32, tag="3")]
+    pub width: u32,
+    #[prost(uint32, tag="4")]
+    pub height: u32,
+    #[prost(double, tag="5")]
+    pub framerate: f64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InputAudioState {
+    #[prost(string, tag="1")]
+    pub mime_type: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub average_bitrate: u32,
+    #[prost(uint32, tag="3")]
+    pub channels: u32,
+    #[prost(uint32, tag="4")]
+    pub sample_rate: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateIngressRequest {
+    #[prost(string, tag="1")]
+    pub ingress_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub room_name: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub participant_identity: ::prost::alloc::string::String,
+    #[prost(string, tag="5")]
+    pub participant_name: ::prost::alloc::string::String,
+    #[prost(string, tag="9")]
+    pub participant_metadata: ::prost::alloc::string::String,
+    #[deprecated]
+    #[prost(bool, optional, tag="8")]
+    pub bypass_transcoding: ::core::option::Option<bool>,
+    #[prost(bool, optional, tag="10")]
+    pub enable_transcoding: ::core::option::Option<bool>,
+    #[prost(message, optional, tag="6")]
+    pub audio: ::core::option::Option<IngressAudioOptions>,
+    #[prost(message, optional, tag="7")]
+    pub video: ::core::option::Option<IngressVideoOptions>,
+    /// The default value is true and when set to false, the new connection attempts will be rejected
+    #[prost(bool, optional, tag="11")]
+    pub enabled: ::core::option::Option<bool>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListIngressRequest {
+    /// when blank, lists all ingress endpoints
+    ///
+    /// (optional, filter by room name)
+    #[prost(string, tag="1")]
+    pub room_name: ::prost::alloc::string::String,
+    /// (optional, filter by ingress ID)
+    #[prost(string, tag="2")]
+    pub ingress_id: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListIngressResponse {
+    #[prost(message, repeated, tag="1")]
+    pub items: ::prost::alloc::vec::Vec<IngressInfo>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteIngressRequest {
+    #[prost(string, tag="1")]
+    pub ingress_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum IngressInput {
+    RtmpInput = 0,
+    WhipInput = 1,
+    /// Pull from the provided URL. Only HTTP url are supported, serving either a single media file or a HLS stream
+    UrlInput = 2,
+}
+impl IngressInput {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            IngressInput::RtmpInput => "RTMP_INPUT",
+            IngressInput::WhipInput => "WHIP_INPUT",
+            IngressInput::UrlInput => "URL_INPUT",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "RTMP_INPUT" => Some(Self::RtmpInput),
+            "WHIP_INPUT" => Some(Self::WhipInput),
+            "URL_INPUT" => Some(Self::UrlInput),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum IngressAudioEncodingPreset {
+    /// OPUS, 2 channels, 96kbps
+    OpusStereo96kbps = 0,
+    /// OPUS, 1 channel, 64kbps
+    OpusMono64kbs = 1,
+}
+impl IngressAudioEncodingPreset {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            IngressAudioEncodingPreset::OpusStereo96kbps => "OPUS_STEREO_96KBPS",
+            IngressAudioEncodingPreset::OpusMono64kbs => "OPUS_MONO_64KBS",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "OPUS_STEREO_96KBPS" => Some(Self::OpusStereo96kbps),
+            "OPUS_MONO_64KBS" => Some(Self::OpusMono64kbs),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum IngressVideoEncodingPreset {
+    /// 1280x720,  30fps, 1900kbps main layer, 3 layers total
+    H264720p30fps3Layers = 0,
+    /// 1980x1080, 30fps, 3500kbps main layer, 3 layers total
+    H2641080p30fps3Layers = 1,
+    ///   960x540,  25fps, 1000kbps  main layer, 2 layers total
+    H264540p25fps2Layers = 2,
+    /// 1280x720,  30fps, 1900kbps, no simulcast
+    H264720p30fps1Layer = 3,
+    /// 1980x1080, 30fps, 3500kbps, no simulcast
+    H2641080p30fps1Layer = 4,
+    /// 1280x720,  30fps, 2500kbps main layer, 3 layers total, higher bitrate for high motion, harder to encode content
+    H264720p30fps3LayersHighMotion = 5,
+    /// 1980x1080, 30fps, 4500kbps main layer, 3 layers total, higher bitrate for high motion, harder to encode content
+    H2641080p30fps3LayersHighMotion = 6,
+    ///   960x540,  25fps, 1300kbps  main layer, 2 layers total, higher bitrate for high motion, harder to encode content
+    H264540p25fps2LayersHighMotion = 7,
+    /// 1280x720,  30fps, 2500kbps, no simulcast, higher bitrate for high motion, harder to encode content
+    H264720p30fps1LayerHighMotion = 8,
+    /// 1980x1080, 30fps, 4500kbps, no simulcast, higher bitrate for high motion, harder to encode content
+    H2641080p30fps1LayerHighMotion = 9,
+}
+impl IngressVideoEncodingPreset {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            IngressVideoEncodingPreset::H264720p30fps3Layers => "H264_720P_30FPS_3_LAYERS",
+            IngressVideoEncodingPreset::H2641080p30fps3Layers => "H264_1080P_30FPS_3_LAYERS",
+            IngressVideoEncodingPreset::H264540p25fps2Layers => "H264_540P_25FPS_2_LAYERS",
+            IngressVideoEncodingPreset::H264720p30fps1Layer => "H264_720P_30FPS_1_LAYER",
+            IngressVideoEncodingPreset::H2641080p30fps1Layer => "H264_1080P_30FPS_1_LAYER",
+            IngressVideoEncodingPreset::H264720p30fps3LayersHighMotion => "H264_720P_30FPS_3_LAYERS_HIGH_MOTION",
+            IngressVideoEncodingPreset::H2641080p30fps3LayersHighMotion => "H264_1080P_30FPS_3_LAYERS_HIGH_MOTION",
+            IngressVideoEncodingPreset::H264540p25fps2LayersHighMotion => "H264_540P_25FPS_2_LAYERS_HIGH_MOTION",
+            IngressVideoEncodingPreset::H264720p30fps1LayerHighMotion => "H264_720P_30FPS_1_LAYER_HIGH_MOTION",
+            IngressVideoEncodingPreset::H2641080p30fps1LayerHighMotion => "H264_1080P_30FPS_1_LAYER_HIGH_MOTION",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "H264_720P_30FPS_3_LAYERS" => Some(Self::H264720p30fps3Layers),
+            "H264_1080P_30FPS_3_LAYERS" => Some(Self::H2641080p30fps3Layers),
+            "H264_540P_25FPS_2_LAYERS" => Some(Self::H264540p25fps2Layers),
+            "H264_720P_30FPS_1_LAYER" => Some(Self::H264720p30fps1Layer),
+            "H264_1080P_30FPS_1_LAYER" => Some(Self::H2641080p30fps1Layer),
+            "H264_720P_30FPS_3_LAYERS_HIGH_MOTION" => Some(Self::H264720p30fps3LayersHighMotion),
+            "H264_1080P_30FPS_3_LAYERS_HIGH_MOTION" => Some(Self::H2641080p30fps3LayersHighMotion),
+            "H264_540P_25FPS_2_LAYERS_HIGH_MOTION" => Some(Self::H264540p25fps2LayersHighMotion),
+            "H264_720P_30FPS_1_LAYER_HIGH_MOTION" => Some(Self::H264720p30fps1LayerHighMotion),
+            "H264_1080P_30FPS_1_LAYER_HIGH_MOTION" => Some(Self::H2641080p30fps1LayerHighMotion),
+            _ => None,
+        }
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct IngressVideoOptions {
+pub struct WebhookEvent {
+    /// one of room_started, room_finished, participant_joined, participant_left,
+    /// track_published, track_unpublished, egress_started, egress_updated, egress_ended,
+    /// ingress_started, ingress_ended
     #[prost(string, tag="1")]
+    pub event: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="2")]
+    pub room: ::core::option::Option<Room>,
+    /// set when event is participant_* or track_*
+    #[prost(message, optional, tag="3")]
+    pub participant: ::core::option::Option<ParticipantInfo>,
+    /// set when event is egress_*
+    #[prost(message, optional, tag="9")]
+    pub egress_info: ::core::option::Option<EgressInfo>,
+    /// set when event is ingress_*
+    #[prost(message, optional, tag="10")]
+    pub ingress_info: ::core::option::Option<IngressInfo>,
+    /// set when event is track_*
+    #[prost(message, optional, tag="8")]
+    pub track: ::core::option::Option<TrackInfo>,
+    /// unique event uuid
+    #[prost(string, tag="6")]
+    pub id: ::prost::alloc::string::String,
+    /// timestamp in seconds
+    #[prost(int64, tag="7")]
+    pub created_at: i64,
+    #[prost(int32, tag="11")]
+    pub num_dropped: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateSipTrunkRequest {
+    /// CIDR or IPs that traffic is accepted from
+    /// An empty list means all inbound traffic is accepted.
+    #[prost(string, repeated, tag="1")]
+    pub inbound_addresses: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// IP that SIP INVITE is sent too
+    #[prost(string, tag="2")]
+    pub outbound_address: ::prost::alloc::string::String,
+    /// Number used to make outbound calls
+    #[prost(string, tag="3")]
+    pub outbound_number: ::prost::alloc::string::String,
+    #[deprecated]
+    #[prost(string, repeated, tag="4")]
+    pub inbound_numbers_regex: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Accepted `To` values. This Trunk will only accept a call made to
+    /// these numbers. This allows you to have distinct Trunks for different phone
+    /// numbers at the same provider.
+    #[prost(string, repeated, tag="9")]
+    pub inbound_numbers: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Username and password used to authenticate inbound and outbound SIP invites
+    /// May be empty to have no Authentication
+    #[prost(string, tag="5")]
+    pub inbound_username: ::prost::alloc::string::String,
+    #[prost(string, tag="6")]
+    pub inbound_password: ::prost::alloc::string::String,
+    #[prost(string, tag="7")]
+    pub outbound_username: ::prost::alloc::string::String,
+    #[prost(string, tag="8")]
+    pub outbound_password: ::prost::alloc::string::String,
+    /// Optional human-readable name for the Trunk.
+    #[prost(string, tag="10")]
     pub name: ::prost::alloc::string::String,
-    #[prost(enumeration="TrackSource", tag="2")]
-    pub source: i32,
-    #[prost(oneof="ingress_video_options::EncodingOptions", tags="3, 4")]
-    pub encoding_options: ::core::option::Option<ingress_video_options::EncodingOptions>,
+    /// Optional user-defined metadata for the Trunk.
+    #[prost(string, tag="11")]
+    pub metadata: ::prost::alloc::string::String,
 }
-/// Nested message and enum types in `IngressVideoOptions`.
-pub mod ingress_video_options {
-    #[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Oneof)]
-    pub enum EncodingOptions {
-        #[prost(enumeration="super::IngressVideoEncodingPreset", tag="3")]
-        Preset(i32),
-        #[prost(message, tag="4")]
-        Options(super::IngressVideoEncodingOptions),
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SipTrunkInfo {
+    #[prost(string, tag="1")]
+    pub sip_trunk_id: ::prost::alloc::string::String,
+    #[prost(enumeration="sip_trunk_info::TrunkKind", tag="14")]
+    pub kind: i32,
+    /// CIDR or IPs that traffic is accepted from
+    /// An empty list means all inbound traffic is accepted.
+    #[prost(string, repeated, tag="2")]
+    pub inbound_addresses: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// IP that SIP INVITE is sent too
+    #[prost(string, tag="3")]
+    pub outbound_address: ::prost::alloc::string::String,
+    /// Number used to make outbound calls
+    #[prost(string, tag="4")]
+    pub outbound_number: ::prost::alloc::string::String,
+    /// Transport used for inbound and outbound calls.
+    #[prost(enumeration="SipTransport", tag="13")]
+    pub transport: i32,
+    #[deprecated]
+    #[prost(string, repeated, tag="5")]
+    pub inbound_numbers_regex: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Accepted `To` values. This Trunk will only accept a call made to
+    /// these numbers. This allows you to have distinct Trunks for different phone
+    /// numbers at the same provider.
+    #[prost(string, repeated, tag="10")]
+    pub inbound_numbers: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Username and password used to authenticate inbound and outbound SIP invites
+    /// May be empty to have no Authentication
+    #[prost(string, tag="6")]
+    pub inbound_username: ::prost::alloc::string::String,
+    #[prost(string, tag="7")]
+    pub inbound_password: ::prost::alloc::string::String,
+    #[prost(string, tag="8")]
+    pub outbound_username: ::prost::alloc::string::String,
+    #[prost(string, tag="9")]
+    pub outbound_password: ::prost::alloc::string::String,
+    /// Human-readable name for the Trunk.
+    #[prost(string, tag="11")]
+    pub name: ::prost::alloc::string::String,
+    /// User-defined metadata for the Trunk.
+    #[prost(string, tag="12")]
+    pub metadata: ::prost::alloc::string::String,
+}
+/// Nested message and enum types in `SIPTrunkInfo`.
+pub mod sip_trunk_info {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum TrunkKind {
+        TrunkLegacy = 0,
+        TrunkInbound = 1,
+        TrunkOutbound = 2,
+    }
+    impl TrunkKind {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                TrunkKind::TrunkLegacy => "TRUNK_LEGACY",
+                TrunkKind::TrunkInbound => "TRUNK_INBOUND",
+                TrunkKind::TrunkOutbound => "TRUNK_OUTBOUND",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "TRUNK_LEGACY" => Some(Self::TrunkLegacy),
+                "TRUNK_INBOUND" => Some(Self::TrunkInbound),
+                "TRUNK_OUTBOUND" => Some(Self::TrunkOutbound),
+                _ => None,
+            }
+        }
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct IngressAudioEncodingOptions {
-    /// desired audio codec to publish to room
-    #[prost(enumeration="AudioCodec", tag="1")]
-    pub audio_codec: i32,
-    #[prost(uint32, tag="2")]
-    pub bitrate: u32,
-    #[prost(bool, tag="3")]
-    pub disable_dtx: bool,
-    #[prost(uint32, tag="4")]
-    pub channels: u32,
+pub struct CreateSipInboundTrunkRequest {
+    /// Trunk ID is ignored
+    #[prost(message, optional, tag="1")]
+    pub trunk: ::core::option::Option<SipInboundTrunkInfo>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct IngressVideoEncodingOptions {
-    /// desired codec to publish to room
-    #[prost(enumeration="VideoCodec", tag="1")]
-    pub video_codec: i32,
-    #[prost(double, tag="2")]
-    pub frame_rate: f64,
-    /// simulcast layers to publish, when empty, should usually be set to layers at 1/2 and 1/4 of the dimensions
-    #[prost(message, repeated, tag="3")]
-    pub layers: ::prost::alloc::vec::Vec<VideoLayer>,
+pub struct SipInboundTrunkInfo {
+    #[prost(string, tag="1")]
+    pub sip_trunk_id: ::prost::alloc::string::String,
+    /// Human-readable name for the Trunk.
+    #[prost(string, tag="2")]
+    pub name: ::prost::alloc::string::String,
+    /// User-defined metadata for the Trunk.
+    #[prost(string, tag="3")]
+    pub metadata: ::prost::alloc::string::String,
+    /// Numbers associated with LiveKit SIP. The Trunk will only accept calls made to these numbers.
+    /// Creating multiple Trunks with different phone numbers allows having different rules for a single provider.
+    #[prost(string, repeated, tag="4")]
+    pub numbers: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// CIDR or IPs that traffic is accepted from.
+    /// An empty list means all inbound traffic is accepted.
+    #[prost(string, repeated, tag="5")]
+    pub allowed_addresses: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Numbers that are allowed to make calls to this Trunk.
+    /// An empty list means calls from any phone number is accepted.
+    #[prost(string, repeated, tag="6")]
+    pub allowed_numbers: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Username and password used to authenticate inbound SIP invites.
+    /// May be empty to have no authentication.
+    #[prost(string, tag="7")]
+    pub auth_username: ::prost::alloc::string::String,
+    #[prost(string, tag="8")]
+    pub auth_password: ::prost::alloc::string::String,
+    /// Include these SIP X-* headers in 200 OK responses.
+    #[prost(map="string, string", tag="9")]
+    pub headers: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+    /// Map SIP X-* headers from INVITE to SIP participant attributes.
+    #[prost(map="string, string", tag="10")]
+    pub headers_to_attributes: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+    /// Map LiveKit attributes to SIP X-* headers when sending BYE or REFER requests.
+    /// Keys are the names of attributes and values are the names of X-* headers they will be mapped to.
+    #[prost(map="string, string", tag="14")]
+    pub attributes_to_headers: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+    /// Map SIP headers from INVITE to sip.h.* participant attributes automatically.
+    ///
+    /// When the names of required headers is known, using headers_to_attributes is strongly recommended.
+    ///
+    /// When mapping INVITE headers to response headers with attributes_to_headers map,
+    /// lowercase header names should be used, for example: sip.h.x-custom-header.
+    #[prost(enumeration="SipHeaderOptions", tag="15")]
+    pub include_headers: i32,
+    /// Max time for the caller to wait for track subscription.
+    #[prost(message, optional, tag="11")]
+    pub ringing_timeout: ::core::option::Option<::pbjson_types::Duration>,
+    /// Max call duration.
+    #[prost(message, optional, tag="12")]
+    pub max_call_duration: ::core::option::Option<::pbjson_types::Duration>,
+    #[prost(bool, tag="13")]
+    pub krisp_enabled: bool,
+    #[prost(enumeration="SipMediaEncryption", tag="16")]
+    pub media_encryption: i32,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct IngressInfo {
+pub struct CreateSipOutboundTrunkRequest {
+    /// Trunk ID is ignored
+    #[prost(message, optional, tag="1")]
+    pub trunk: ::core::option::Option<SipOutboundTrunkInfo>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SipOutboundTrunkInfo {
     #[prost(string, tag="1")]
-    pub ingress_id: ::prost::alloc::string::String,
+    pub sip_trunk_id: ::prost::alloc::string::String,
+    /// Human-readable name for the Trunk.
     #[prost(string, tag="2")]
     pub name: ::prost::alloc::string::String,
+    /// User-defined metadata for the Trunk.
     #[prost(string, tag="3")]
-    pub stream_key: ::prost::alloc::string::String,
+    pub metadata: ::prost::alloc::string::String,
+    /// Hostname or IP that SIP INVITE is sent too.
+    /// Note that this is not a SIP URI and should not contain the 'sip:' protocol prefix.
     #[prost(string, tag="4")]
-    pub url: ::prost::alloc::string::String,
-    /// for RTMP input, it'll be a rtmp:// URL
-    /// for FILE input, it'll be a http:// URL
-    /// for SRT input, it'll be a srt:// URL
-    #[prost(enumeration="IngressInput", tag="5")]
-    pub input_type: i32,
-    #[prost(bool, tag="13")]
-    pub bypass_transcoding: bool,
-    #[prost(message, optional, tag="6")]
-    pub audio: ::core::option::Option<IngressAudioOptions>,
-    #[prost(message, optional, tag="7")]
-    pub video: ::core::option::Option<IngressVideoOptions>,
+    pub address: ::prost::alloc::string::String,
+    /// SIP Transport used for outbound call.
+    #[prost(enumeration="SipTransport", tag="5")]
+    pub transport: i32,
+    /// Numbers used to make the calls. Random one from this list will be selected.
+    #[prost(string, repeated, tag="6")]
+    pub numbers: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Username and password used to authenticate with SIP server.
+    /// May be empty to have no authentication.
+    #[prost(string, tag="7")]
+    pub auth_username: ::prost::alloc::string::String,
     #[prost(string, tag="8")]
+    pub auth_password: ::prost::alloc::string::String,
+    /// Include these SIP X-* headers in INVITE request.
+    /// These headers are sent as-is and may help identify this call as coming from LiveKit for the other SIP endpoint.
+    #[prost(map="string, string", tag="9")]
+    pub headers: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+    /// Map SIP X-* headers from 200 OK to SIP participant attributes.
+    /// Keys are the names of X-* headers and values are the names of attributes they will be mapped to.
+    #[prost(map="string, string", tag="10")]
+    pub headers_to_attributes: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+    /// Map LiveKit attributes to SIP X-* headers when sending BYE or REFER requests.
+    /// Keys are the names of attributes and values are the names of X-* headers they will be mapped to.
+    #[prost(map="string, string", tag="11")]
+    pub attributes_to_headers: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+    /// Map SIP headers from 200 OK to sip.h.* participant attributes automatically.
+    ///
+    /// When the names of required headers is known, using headers_to_attributes is strongly recommended.
+    ///
+    /// When mapping 200 OK headers to follow-up request headers with attributes_to_headers map,
+    /// lowercase header names should be used, for example: sip.h.x-custom-header.
+    #[prost(enumeration="SipHeaderOptions", tag="12")]
+    pub include_headers: i32,
+    #[prost(enumeration="SipMediaEncryption", tag="13")]
+    pub media_encryption: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetSipInboundTrunkRequest {
+    #[prost(string, tag="1")]
+    pub sip_trunk_id: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetSipInboundTrunkResponse {
+    #[prost(message, optional, tag="1")]
+    pub trunk: ::core::option::Option<SipInboundTrunkInfo>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetSipOutboundTrunkRequest {
+    #[prost(string, tag="1")]
+    pub sip_trunk_id: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetSipOutboundTrunkResponse {
+    #[prost(message, optional, tag="1")]
+    pub trunk: ::core::option::Option<SipOutboundTrunkInfo>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListSipTrunkRequest {
+    #[prost(message, optional, tag="1")]
+    pub page: ::core::option::Option<Pagination>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListSipTrunkResponse {
+    #[prost(message, repeated, tag="1")]
+    pub items: ::prost::alloc::vec::Vec<SipTrunkInfo>,
+}
+/// ListSIPInboundTrunkRequest lists inbound trunks for given filters. If no filters are set, all trunks are listed.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListSipInboundTrunkRequest {
+    #[prost(message, optional, tag="3")]
+    pub page: ::core::option::Option<Pagination>,
+    /// Trunk IDs to list. If this option is set, the response will contains trunks in the same order.
+    /// If any of the trunks is missing, a nil item in that position will be sent in the response.
+    #[prost(string, repeated, tag="1")]
+    pub trunk_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Only list trunks that contain one of the numbers, including wildcard trunks.
+    #[prost(string, repeated, tag="2")]
+    pub numbers: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListSipInboundTrunkResponse {
+    #[prost(message, repeated, tag="1")]
+    pub items: ::prost::alloc::vec::Vec<SipInboundTrunkInfo>,
+}
+/// ListSIPOutboundTrunkRequest lists outbound trunks for given filters. If no filters are set, all trunks are listed.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListSipOutboundTrunkRequest {
+    #[prost(message, optional, tag="3")]
+    pub page: ::core::option::Option<Pagination>,
+    /// Trunk IDs to list. If this option is set, the response will contains trunks in the same order.
+    /// If any of the trunks is missing, a nil item in that position will be sent in the response.
+    #[prost(string, repeated, tag="1")]
+    pub trunk_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Only list trunks that contain one of the numbers, including wildcard trunks.
+    #[prost(string, repeated, tag="2")]
+    pub numbers: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListSipOutboundTrunkResponse {
+    #[prost(message, repeated, tag="1")]
+    pub items: ::prost::alloc::vec::Vec<SipOutboundTrunkInfo>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteSipTrunkRequest {
+    #[prost(string, tag="1")]
+    pub sip_trunk_id: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SipDispatchRuleDirect {
+    /// What room should call be directed into
+    #[prost(string, tag="1")]
     pub room_name: ::prost::alloc::string::String,
-    #[prost(string, tag="9")]
-    pub participant_identity: ::prost::alloc::string::String,
-    #[prost(string, tag="10")]
-    pub participant_name: ::prost::alloc::string::String,
-    #[prost(bool, tag="11")]
-    pub reusable: bool,
-    /// Description of error/stream non compliance and debug info for publisher otherwise (received bitrate, resolution, bandwidth)
-    #[prost(message, optional, tag="12")]
-    pub state: ::core::option::Option<IngressState>,
+    /// Optional pin required to enter room
+    #[prost(string, tag="2")]
+    pub pin: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct IngressState {
-    #[prost(enumeration="ingress_state::Status", tag="1")]
-    pub status: i32,
-    /// Error/non compliance description if any
+pub struct SipDispatchRuleIndividual {
+    /// Prefix used on new room name
+    #[prost(string, tag="1")]
+    pub room_prefix: ::prost::alloc::string::String,
+    /// Optional pin required to enter room
     #[prost(string, tag="2")]
-    pub error: ::prost::alloc::string::String,
-    #[prost(message, optional, tag="3")]
-    pub video: ::core::option::Option<InputVideoState>,
-    #[prost(message, optional, tag="4")]
-    pub audio: ::core::option::Option<InputAudioState>,
-    /// ID of the current/previous room published to
+    pub pin: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SipDispatchRuleCallee {
+    /// Prefix used on new room name
+    #[prost(string, tag="1")]
+    pub room_prefix: ::prost::alloc::string::String,
+    /// Optional pin required to enter room
+    #[prost(string, tag="2")]
+    pub pin: ::prost::alloc::string::String,
+    /// Optionally append random suffix
+    #[prost(bool, tag="3")]
+    pub randomize: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SipDispatchRule {
+    #[prost(oneof="sip_dispatch_rule::Rule", tags="1, 2, 3")]
+    pub rule: ::core::option::Option<sip_dispatch_rule::Rule>,
+}
+/// Nested message and enum types in `SIPDispatchRule`.
+pub mod sip_dispatch_rule {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Rule {
+        /// SIPDispatchRuleDirect is a `SIP Dispatch Rule` that puts a user directly into a room
+        /// This places users into an existing room. Optionally you can require a pin before a user can
+        /// enter the room
+        #[prost(message, tag="1")]
+        DispatchRuleDirect(super::SipDispatchRuleDirect),
+        /// SIPDispatchRuleIndividual is a `SIP Dispatch Rule` that creates a new room for each caller.
+        #[prost(message, tag="2")]
+        DispatchRuleIndividual(super::SipDispatchRuleIndividual),
+        /// SIPDispatchRuleCallee is a `SIP Dispatch Rule` that creates a new room for each callee.
+        #[prost(message, tag="3")]
+        DispatchRuleCallee(super::SipDispatchRuleCallee),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateSipDispatchRuleRequest {
+    #[prost(message, optional, tag="1")]
+    pub rule: ::core::option::Option<SipDispatchRule>,
+    /// What trunks are accepted for this dispatch rule
+    /// If empty all trunks will match this dispatch rule
+    #[prost(string, repeated, tag="2")]
+    pub trunk_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// By default the From value (Phone number) is used for participant name/identity and added to attributes.
+    /// If true, a random value for identity will be used and numbers will be omitted from attributes.
+    #[prost(bool, tag="3")]
+    pub hide_phone_number: bool,
+    /// Dispatch Rule will only accept a call made to these numbers (if set).
+    #[prost(string, repeated, tag="6")]
+    pub inbound_numbers: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Optional human-readable name for the Dispatch Rule.
+    #[prost(string, tag="4")]
+    pub name: ::prost::alloc::string::String,
+    /// User-defined metadata for the Dispatch Rule.
+    /// Participants created by this rule will inherit this metadata.
+    #[prost(string, tag="5")]
+    pub metadata: ::prost::alloc::string::String,
+    /// User-defined attributes for the Dispatch Rule.
+    /// Participants created by this rule will inherit these attributes.
+    #[prost(map="string, string", tag="7")]
+    pub attributes: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+    /// Cloud-only, config preset to use
+    #[prost(string, tag="8")]
+    pub room_preset: ::prost::alloc::string::String,
+    /// RoomConfiguration to use if the participant initiates the room
+    #[prost(message, optional, tag="9")]
+    pub room_config: ::core::option::Option<RoomConfiguration>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SipDispatchRuleInfo {
+    #[prost(string, tag="1")]
+    pub sip_dispatch_rule_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="2")]
+    pub rule: ::core::option::Option<SipDispatchRule>,
+    #[prost(string, repeated, tag="3")]
+    pub trunk_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(bool, tag="4")]
+    pub hide_phone_number: bool,
+    /// Dispatch Rule will only accept a call made to these numbers (if set).
+    #[prost(string, repeated, tag="7")]
+    pub inbound_numbers: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Human-readable name for the Dispatch Rule.
     #[prost(string, tag="5")]
-    pub room_id: ::prost::alloc::string::String,
-    #[prost(int64, tag="7")]
-    pub started_at: i64,
-    #[prost(int64, tag="8")]
-    pub ended_at: i64,
+    pub name: ::prost::alloc::string::String,
+    /// User-defined metadata for the Dispatch Rule.
+    /// Participants created by this rule will inherit this metadata.
+    #[prost(string, tag="6")]
+    pub metadata: ::prost::alloc::string::String,
+    /// User-defined attributes for the Dispatch Rule.
+    /// Participants created by this rule will inherit these attributes.
+    #[prost(map="string, string", tag="8")]
+    pub attributes: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+    /// Cloud-only, config preset to use
     #[prost(string, tag="9")]
-    pub resource_id: ::prost::alloc::string::String,
-    #[prost(message, repeated, tag="6")]
-    pub tracks: ::prost::alloc::vec::Vec<TrackInfo>,
+    pub room_preset: ::prost::alloc::string::String,
+    /// RoomConfiguration to use if the participant initiates the room
+    #[prost(message, optional, tag="10")]
+    pub room_config: ::core::option::Option<RoomConfiguration>,
+    #[prost(bool, tag="11")]
+    pub krisp_enabled: bool,
+    /// NEXT ID: 13
+    #[prost(enumeration="SipMediaEncryption", tag="12")]
+    pub media_encryption: i32,
 }
-/// Nested message and enum types in `IngressState`.
-pub mod ingress_state {
-    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
-    #[repr(i32)]
-    pub enum Status {
-        EndpointInactive = 0,
-        EndpointBuffering = 1,
-        EndpointPublishing = 2,
-        EndpointError = 3,
-    }
-    impl Status {
-        /// String value of the enum field names used in the ProtoBuf definition.
-        ///
-        /// The values are not transformed in any way and thus are considered stable
-        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
-        pub fn as_str_name(&self) -> &'static str {
-            match self {
-                Status::EndpointInactive => "ENDPOINT_INACTIVE",
-                Status::EndpointBuffering => "ENDPOINT_BUFFERING",
-                Status::EndpointPublishing => "ENDPOINT_PUBLISHING",
-                Status::EndpointError => "ENDPOINT_ERROR",
-            }
-        }
-        /// Creates an enum from field names used in the ProtoBuf definition.
-        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
-            match value {
-                "ENDPOINT_INACTIVE" => Some(Self::EndpointInactive),
-                "ENDPOINT_BUFFERING" => Some(Self::EndpointBuffering),
-                "ENDPOINT_PUBLISHING" => Some(Self::EndpointPublishing),
-                "ENDPOINT_ERROR" => Some(Self::EndpointError),
-                _ => None,
-            }
-        }
-    }
+/// ListSIPDispatchRuleRequest lists dispatch rules for given filters. If no filters are set, all rules are listed.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListSipDispatchRuleRequest {
+    #[prost(message, optional, tag="3")]
+    pub page: ::core::option::Option<Pagination>,
+    /// Rule IDs to list. If this option is set, the response will contains rules in the same order.
+    /// If any of the rules is missing, a nil item in that position will be sent in the response.
+    #[prost(string, repeated, tag="1")]
+    pub dispatch_rule_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Only list rules that contain one of the Trunk IDs, including wildcard rules.
+    #[prost(string, repeated, tag="2")]
+    pub trunk_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct InputVideoState {
-    #[prost(string, tag="1")]
-    pub mime_type: ::prost::alloc::string::String,
-    #[prost(uint32, tag="2")]
-    pub average_bitrate: u32,
-    #[prost(uint32, tag="3")]
-    pub width: u32,
-    #[prost(uint32, tag="4")]
-    pub height: u32,
-    #[prost(double, tag="5")]
-    pub framerate: f64,
+pub struct ListSipDispatchRuleResponse {
+    #[prost(message, repeated, tag="1")]
+    pub items: ::prost::alloc::vec::Vec<SipDispatchRuleInfo>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct InputAudioState {
+pub struct DeleteSipDispatchRuleRequest {
     #[prost(string, tag="1")]
-    pub mime_type: ::prost::alloc::string::String,
-    #[prost(uint32, tag="2")]
-    pub average_bitrate: u32,
-    #[prost(uint32, tag="3")]
-    pub channels: u32,
-    #[prost(uint32, tag="4")]
-    pub sample_rate: u32,
+    pub sip_dispatch_rule_id: ::prost::alloc::string::String,
 }
+/// A SIP Participant is a singular SIP session connected to a LiveKit room via
+/// a SIP Trunk into a SIP DispatchRule
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct UpdateIngressRequest {
+pub struct CreateSipParticipantRequest {
+    /// What SIP Trunk should be used to dial the user
     #[prost(string, tag="1")]
-    pub ingress_id: ::prost::alloc::string::String,
+    pub sip_trunk_id: ::prost::alloc::string::String,
+    /// What number should be dialed via SIP
     #[prost(string, tag="2")]
-    pub name: ::prost::alloc::string::String,
+    pub sip_call_to: ::prost::alloc::string::String,
+    /// Optional SIP From number to use. If empty, trunk number is used.
+    #[prost(string, tag="15")]
+    pub sip_number: ::prost::alloc::string::String,
+    /// What LiveKit room should this participant be connected too
     #[prost(string, tag="3")]
     pub room_name: ::prost::alloc::string::String,
+    /// Optional identity of the participant in LiveKit room
     #[prost(string, tag="4")]
     pub participant_identity: ::prost::alloc::string::String,
-    #[prost(string, tag="5")]
+    /// Optional name of the participant in LiveKit room
+    #[prost(string, tag="7")]
     pub participant_name: ::prost::alloc::string::String,
-    #[prost(bool, optional, tag="8")]
-    pub bypass_transcoding: ::core::option::Option<bool>,
-    #[prost(message, optional, tag="6")]
-    pub audio: ::core::option::Option<IngressAudioOptions>,
-    #[prost(message, optional, tag="7")]
-    pub video: ::core::option::Option<IngressVideoOptions>,
+    /// Optional user-defined metadata. Will be attached to a created Participant in the room.
+    #[prost(string, tag="8")]
+    pub participant_metadata: ::prost::alloc::string::String,
+    /// Optional user-defined attributes. Will be attached to a created Participant in the room.
+    #[prost(map="string, string", tag="9")]
+    pub participant_attributes: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+    /// Optionally send following DTMF digits (extension codes) when making a call.
+    /// Character 'w' can be used to add a 0.5 sec delay.
+    #[prost(string, tag="5")]
+    pub dtmf: ::prost::alloc::string::String,
+    /// Optionally play dialtone in the room as an audible indicator for existing participants. The `play_ringtone` option is deprectated but has the same effect.
+    #[deprecated]
+    #[prost(bool, tag="6")]
+    pub play_ringtone: bool,
+    #[prost(bool, tag="13")]
+    pub play_dialtone: bool,
+    /// By default the From value (Phone number) is used for participant name/identity (if not set) and added to attributes.
+    /// If true, a random value for identity will be used and numbers will be omitted from attributes.
+    #[prost(bool, tag="10")]
+    pub hide_phone_number: bool,
+    /// These headers are sent as-is and may help identify this call as coming from LiveKit for the other SIP endpoint.
+    #[prost(map="string, string", tag="16")]
+    pub headers: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+    /// Map SIP headers from 200 OK to sip.h.* participant attributes automatically.
+    ///
+    /// When the names of required headers is known, using headers_to_attributes is strongly recommended.
+    ///
+    /// When mapping 200 OK headers to follow-up request headers with attributes_to_headers map,
+    /// lowercase header names should be used, for example: sip.h.x-custom-header.
+    #[prost(enumeration="SipHeaderOptions", tag="17")]
+    pub include_headers: i32,
+    /// Max time for the callee to answer the call.
+    #[prost(message, optional, tag="11")]
+    pub ringing_timeout: ::core::option::Option<::pbjson_types::Duration>,
+    /// Max call duration.
+    #[prost(message, optional, tag="12")]
+    pub max_call_duration: ::core::option::Option<::pbjson_types::Duration>,
+    /// Enable voice isolation for the callee.
+    #[prost(bool, tag="14")]
+    pub krisp_enabled: bool,
+    /// NEXT ID: 19
+    #[prost(enumeration="SipMediaEncryption", tag="18")]
+    pub media_encryption: i32,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ListIngressRequest {
-    /// when blank, lists all ingress endpoints
-    ///
-    /// (optional, filter by room name)
+pub struct SipParticipantInfo {
     #[prost(string, tag="1")]
+    pub participant_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub participant_identity: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
     pub room_name: ::prost::alloc::string::String,
-    /// (optional, filter by ingress ID)
+    #[prost(string, tag="4")]
+    pub sip_call_id: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransferSipParticipantRequest {
+    #[prost(string, tag="1")]
+    pub participant_identity: ::prost::alloc::string::String,
     #[prost(string, tag="2")]
-    pub ingress_id: ::prost::alloc::string::String,
+    pub room_name: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub transfer_to: ::prost::alloc::string::String,
+    /// Optionally play dialtone to the SIP participant as an audible indicator of being transferred
+    #[prost(bool, tag="4")]
+    pub play_dialtone: bool,
+    /// Add the following headers to the REFER SIP request.
+    #[prost(map="string, string", tag="5")]
+    pub headers: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ListIngressResponse {
-    #[prost(message, repeated, tag="1")]
-    pub items: ::prost::alloc::vec::Vec<IngressInfo>,
+pub struct SipCallInfo {
+    #[prost(string, tag="1")]
+    pub call_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub trunk_id: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub room_name: ::prost::alloc::string::String,
+    /// ID of the current/previous room published to
+    #[prost(string, tag="4")]
+    pub room_id: ::prost::alloc::string::String,
+    #[prost(string, tag="5")]
+    pub participant_identity: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="6")]
+    pub from_uri: ::core::option::Option<SipUri>,
+    #[prost(message, optional, tag="7")]
+    pub to_uri: ::core::option::Option<SipUri>,
+    #[prost(enumeration="SipFeature", repeated, tag="14")]
+    pub enabled_features: ::prost::alloc::vec::Vec<i32>,
+    #[prost(enumeration="SipCallDirection", tag="15")]
+    pub call_direction: i32,
+    #[prost(enumeration="SipCallStatus", tag="8")]
+    pub call_status: i32,
+    #[prost(int64, tag="9")]
+    pub created_at: i64,
+    #[prost(int64, tag="10")]
+    pub started_at: i64,
+    #[prost(int64, tag="11")]
+    pub ended_at: i64,
+    #[prost(enumeration="DisconnectReason", tag="12")]
+    pub disconnect_reason: i32,
+    #[prost(string, tag="13")]
+    pub error: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct DeleteIngressRequest {
+pub struct SipUri {
     #[prost(string, tag="1")]
-    pub ingress_id: ::prost::alloc::string::String,
+    pub user: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub host: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub ip: ::prost::alloc::string::String,
+    #[prost(uint32, tag="4")]
+    pub port: u32,
+    #[prost(enumeration="SipTransport", tag="5")]
+    pub transport: i32,
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
-pub enum IngressInput {
-    RtmpInput = 0,
-    ///   FILE_INPUT = 2;
-    ///   SRT_INPUT = 3;
-    ///   URL_INPUT = 4;
-    WhipInput = 1,
+pub enum SipTransport {
+    Auto = 0,
+    Udp = 1,
+    Tcp = 2,
+    Tls = 3,
 }
-impl IngressInput {
+impl SipTransport {
     /// String value of the enum field names used in the ProtoBuf definition.
     ///
     /// The values are not transformed in any way and thus are considered stable
     /// (if the ProtoBuf definition does not change) and safe for programmatic use.
     pub fn as_str_name(&self) -> &'static str {
         match self {
-            IngressInput::RtmpInput => "RTMP_INPUT",
-            IngressInput::WhipInput => "WHIP_INPUT",
+            SipTransport::Auto => "SIP_TRANSPORT_AUTO",
+            SipTransport::Udp => "SIP_TRANSPORT_UDP",
+            SipTransport::Tcp => "SIP_TRANSPORT_TCP",
+            SipTransport::Tls => "SIP_TRANSPORT_TLS",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
     pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
         match value {
-            "RTMP_INPUT" => Some(Self::RtmpInput),
-            "WHIP_INPUT" => Some(Self::WhipInput),
+            "SIP_TRANSPORT_AUTO" => Some(Self::Auto),
+            "SIP_TRANSPORT_UDP" => Some(Self::Udp),
+            "SIP_TRANSPORT_TCP" => Some(Self::Tcp),
+            "SIP_TRANSPORT_TLS" => Some(Self::Tls),
             _ => None,
         }
     }
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
-pub enum IngressAudioEncodingPreset {
-    /// OPUS, 2 channels, 96kbps
-    OpusStereo96kbps = 0,
-    /// OPUS, 1 channel, 64kbps
-    OpusMono64kbs = 1,
+pub enum SipHeaderOptions {
+    /// do not map any headers, except ones mapped explicitly
+    SipNoHeaders = 0,
+    /// map all X-* headers to sip.h.x-* attributes
+    SipXHeaders = 1,
+    /// map all headers to sip.h.* attributes
+    SipAllHeaders = 2,
+}
+impl SipHeaderOptions {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            SipHeaderOptions::SipNoHeaders => "SIP_NO_HEADERS",
+            SipHeaderOptions::SipXHeaders => "SIP_X_HEADERS",
+            SipHeaderOptions::SipAllHeaders => "SIP_ALL_HEADERS",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "SIP_NO_HEADERS" => Some(Self::SipNoHeaders),
+            "SIP_X_HEADERS" => Some(Self::SipXHeaders),
+            "SIP_ALL_HEADERS" => Some(Self::SipAllHeaders),
+            _ => None,
+        }
+    }
 }
-impl IngressAudioEncodingPreset {
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum SipMediaEncryption {
+    /// do not enable encryption
+    SipMediaEncryptDisable = 0,
+    /// use encryption if available
+    SipMediaEncryptAllow = 1,
+    /// require encryption
+    SipMediaEncryptRequire = 2,
+}
+impl SipMediaEncryption {
     /// String value of the enum field names used in the ProtoBuf definition.
     ///
     /// The values are not transformed in any way and thus are considered stable
     /// (if the ProtoBuf definition does not change) and safe for programmatic use.
     pub fn as_str_name(&self) -> &'static str {
         match self {
-            IngressAudioEncodingPreset::OpusStereo96kbps => "OPUS_STEREO_96KBPS",
-            IngressAudioEncodingPreset::OpusMono64kbs => "OPUS_MONO_64KBS",
+            SipMediaEncryption::SipMediaEncryptDisable => "SIP_MEDIA_ENCRYPT_DISABLE",
+            SipMediaEncryption::SipMediaEncryptAllow => "SIP_MEDIA_ENCRYPT_ALLOW",
+            SipMediaEncryption::SipMediaEncryptRequire => "SIP_MEDIA_ENCRYPT_REQUIRE",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
     pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
         match value {
-            "OPUS_STEREO_96KBPS" => Some(Self::OpusStereo96kbps),
-            "OPUS_MONO_64KBS" => Some(Self::OpusMono64kbs),
+            "SIP_MEDIA_ENCRYPT_DISABLE" => Some(Self::SipMediaEncryptDisable),
+            "SIP_MEDIA_ENCRYPT_ALLOW" => Some(Self::SipMediaEncryptAllow),
+            "SIP_MEDIA_ENCRYPT_REQUIRE" => Some(Self::SipMediaEncryptRequire),
             _ => None,
         }
     }
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
-pub enum IngressVideoEncodingPreset {
-    /// 1280x720,  30fps, 1700kbps main layer, 3 layers total
-    H264720p30fps3Layers = 0,
-    /// 1980x1080, 30fps, 3000kbps main layer, 3 layers total
-    H2641080p30fps3Layers = 1,
-    ///   960x540,  25fps, 600kbps  main layer, 2 layers total
-    H264540p25fps2Layers = 2,
-    /// 1280x720,  30fps, 1700kbps, no simulcast
-    H264720p30fps1Layer = 3,
-    /// 1980x1080, 30fps, 3000kbps, no simulcast
-    H2641080p30fps1Layer = 4,
+pub enum SipCallStatus {
+    /// Incoming call is being handled by the SIP service. The SIP participant hasn't joined a LiveKit room yet
+    ScsCallIncoming = 0,
+    /// SIP participant for outgoing call has been created. The SIP outgoing call is being established
+    ScsParticipantJoined = 1,
+    /// Call is ongoing. SIP participant is active in the LiveKit room
+    ScsActive = 2,
+    /// Call has ended
+    ScsDisconnected = 3,
+    /// Call has ended or never succeeded because of an error
+    ScsError = 4,
+}
+impl SipCallStatus {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            SipCallStatus::ScsCallIncoming => "SCS_CALL_INCOMING",
+            SipCallStatus::ScsParticipantJoined => "SCS_PARTICIPANT_JOINED",
+            SipCallStatus::ScsActive => "SCS_ACTIVE",
+            SipCallStatus::ScsDisconnected => "SCS_DISCONNECTED",
+            SipCallStatus::ScsError => "SCS_ERROR",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "SCS_CALL_INCOMING" => Some(Self::ScsCallIncoming),
+            "SCS_PARTICIPANT_JOINED" => Some(Self::ScsParticipantJoined),
+            "SCS_ACTIVE" => Some(Self::ScsActive),
+            "SCS_DISCONNECTED" => Some(Self::ScsDisconnected),
+            "SCS_ERROR" => Some(Self::ScsError),
+            _ => None,
+        }
+    }
 }
-impl IngressVideoEncodingPreset {
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum SipFeature {
+    None = 0,
+    KrispEnabled = 1,
+}
+impl SipFeature {
     /// String value of the enum field names used in the ProtoBuf definition.
     ///
     /// The values are not transformed in any way and thus are considered stable
     /// (if the ProtoBuf definition does not change) and safe for programmatic use.
     pub fn as_str_name(&self) -> &'static str {
         match self {
-            IngressVideoEncodingPreset::H264720p30fps3Layers => "H264_720P_30FPS_3_LAYERS",
-            IngressVideoEncodingPreset::H2641080p30fps3Layers => "H264_1080P_30FPS_3_LAYERS",
-            IngressVideoEncodingPreset::H264540p25fps2Layers => "H264_540P_25FPS_2_LAYERS",
-            IngressVideoEncodingPreset::H264720p30fps1Layer => "H264_720P_30FPS_1_LAYER",
-            IngressVideoEncodingPreset::H2641080p30fps1Layer => "H264_1080P_30FPS_1_LAYER",
+            SipFeature::None => "NONE",
+            SipFeature::KrispEnabled => "KRISP_ENABLED",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
     pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
         match value {
-            "H264_720P_30FPS_3_LAYERS" => Some(Self::H264720p30fps3Layers),
-            "H264_1080P_30FPS_3_LAYERS" => Some(Self::H2641080p30fps3Layers),
-            "H264_540P_25FPS_2_LAYERS" => Some(Self::H264540p25fps2Layers),
-            "H264_720P_30FPS_1_LAYER" => Some(Self::H264720p30fps1Layer),
-            "H264_1080P_30FPS_1_LAYER" => Some(Self::H2641080p30fps1Layer),
+            "NONE" => Some(Self::None),
+            "KRISP_ENABLED" => Some(Self::KrispEnabled),
             _ => None,
         }
     }
 }
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct WebhookEvent {
-    /// one of room_started, room_finished, participant_joined, participant_left,
-    /// track_published, track_unpublished, egress_started, egress_updated, egress_ended,
-    /// ingress_started, ingress_ended
-    #[prost(string, tag="1")]
-    pub event: ::prost::alloc::string::String,
-    #[prost(message, optional, tag="2")]
-    pub room: ::core::option::Option<Room>,
-    /// set when event is participant_* or track_*
-    #[prost(message, optional, tag="3")]
-    pub participant: ::core::option::Option<ParticipantInfo>,
-    /// set when event is egress_*
-    #[prost(message, optional, tag="9")]
-    pub egress_info: ::core::option::Option<EgressInfo>,
-    /// set when event is ingress_*
-    #[prost(message, optional, tag="10")]
-    pub ingress_info: ::core::option::Option<IngressInfo>,
-    /// set when event is track_*
-    #[prost(message, optional, tag="8")]
-    pub track: ::core::option::Option<TrackInfo>,
-    /// unique event uuid
-    #[prost(string, tag="6")]
-    pub id: ::prost::alloc::string::String,
-    /// timestamp in seconds
-    #[prost(int64, tag="7")]
-    pub created_at: i64,
-    #[prost(int32, tag="11")]
-    pub num_dropped: i32,
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum SipCallDirection {
+    ScdUnknown = 0,
+    ScdInbound = 1,
+    ScdOutbound = 2,
+}
+impl SipCallDirection {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            SipCallDirection::ScdUnknown => "SCD_UNKNOWN",
+            SipCallDirection::ScdInbound => "SCD_INBOUND",
+            SipCallDirection::ScdOutbound => "SCD_OUTBOUND",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "SCD_UNKNOWN" => Some(Self::ScdUnknown),
+            "SCD_INBOUND" => Some(Self::ScdInbound),
+            "SCD_OUTBOUND" => Some(Self::ScdOutbound),
+            _ => None,
+        }
+    }
 }
 include!("livekit.serde.rs");
 // @@protoc_insertion_point(module)
@@ -32,4 +32,200 @@ impl AudioFrame<'_> {
             samples_per_channel,
         }
     }
+
+    /// Build an interleaved frame from one slice per channel, the slices must have the same length
+    pub fn from_planar(sample_rate: u32, channels: &[&[i16]]) -> Self {
+        let samples_per_channel = channels.first().map_or(0, |c| c.len());
+        assert!(
+            channels.iter().all(|c| c.len() == samples_per_channel),
+            "all the channels must have the same number of samples"
+        );
+
+        let data = (0..samples_per_channel)
+            .flat_map(|i| channels.iter().map(move |c| c[i]))
+            .collect::<Vec<_>>();
+
+        Self {
+            data: data.into(),
+            sample_rate,
+            num_channels: channels.len() as u32,
+            samples_per_channel: samples_per_channel as u32,
+        }
+    }
+
+    /// Build a frame from interleaved f32 samples (-1.0 - 1.0), out of range samples are clamped.
+    /// Panics if num_channels is 0 or if the samples can't be split evenly between the channels
+    pub fn from_f32(sample_rate: u32, num_channels: u32, data: &[f32]) -> Self {
+        assert!(num_channels > 0, "num_channels must be greater than 0");
+        assert!(
+            data.len() % num_channels as usize == 0,
+            "the samples can't be split evenly between the channels"
+        );
+
+        let data = data.iter().map(|s| f32_to_i16(*s)).collect::<Vec<_>>();
+        Self {
+            samples_per_channel: data.len() as u32 / num_channels,
+            data: data.into(),
+            sample_rate,
+            num_channels,
+        }
+    }
+
+    /// Samples of a single channel
+    pub fn channel(&self, channel: u32) -> impl Iterator<Item = i16> + '_ {
+        assert!(channel < self.num_channels, "channel out of range");
+        self.data
+            .iter()
+            .skip(channel as usize)
+            .step_by(self.num_channels as usize)
+            .copied()
+    }
+
+    /// One Vec per channel
+    pub fn to_planar(&self) -> Vec<Vec<i16>> {
+        (0..self.num_channels)
+            .map(|c| self.channel(c).collect())
+            .collect()
+    }
+
+    /// Interleaved samples converted to f32 (-1.0 - 1.0)
+    pub fn to_f32(&self) -> Vec<f32> {
+        self.data.iter().map(|s| i16_to_f32(*s)).collect()
+    }
+
+    /// One Vec per channel, converted to f32 (-1.0 - 1.0)
+    pub fn to_planar_f32(&self) -> Vec<Vec<f32>> {
+        (0..self.num_channels)
+            .map(|c| self.channel(c).map(i16_to_f32).collect())
+            .collect()
+    }
+
+    /// Mono frame containing a single channel
+    pub fn extract_channel(&self, channel: u32) -> AudioFrame<'static> {
+        AudioFrame {
+            data: self.channel(channel).collect::<Vec<_>>().into(),
+            sample_rate: self.sample_rate,
+            num_channels: 1,
+            samples_per_channel: self.samples_per_channel,
+        }
+    }
+
+    /// Average all the channels, the result can't clip
+    pub fn downmix_to_mono(&self) -> AudioFrame<'static> {
+        let num_channels = self.num_channels.max(1) as usize;
+        let data = self
+            .data
+            .chunks_exact(num_channels)
+            .map(|s| (s.iter().map(|s| *s as i32).sum::<i32>() / num_channels as i32) as i16)
+            .collect::<Vec<_>>();
+
+        AudioFrame {
+            data: data.into(),
+            sample_rate: self.sample_rate,
+            num_channels: 1,
+            samples_per_channel: self.samples_per_channel,
+        }
+    }
+
+    /// Build a frame with mapping.len() channels, output channel i is a copy of the input
+    /// channel mapping[i] or silence when None (e.g. [Some(0), Some(0)] upmixes mono to stereo,
+    /// [Some(1), Some(0)] swaps left and right)
+    pub fn remap_channels(&self, mapping: &[Option<u32>]) -> AudioFrame<'static> {
+        assert!(self.num_channels > 0, "the frame has no channel");
+        assert!(
+            mapping.iter().flatten().all(|c| *c < self.num_channels),
+            "channel out of range"
+        );
+
+        let num_channels = self.num_channels as usize;
+        let data = self
+            .data
+            .chunks_exact(num_channels)
+            .flat_map(|s| mapping.iter().map(|c| c.map_or(0, |c| s[c as usize])))
+            .collect::<Vec<_>>();
+
+        AudioFrame {
+            data: data.into(),
+            sample_rate: self.sample_rate,
+            num_channels: mapping.len() as u32,
+            samples_per_channel: self.samples_per_channel,
+        }
+    }
+}
+
+fn i16_to_f32(sample: i16) -> f32 {
+    sample as f32 / i16::MAX as f32
+}
+
+fn f32_to_i16(sample: f32) -> i16 {
+    (sample.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stereo() -> AudioFrame<'static> {
+        AudioFrame {
+            data: vec![100, -100, 200, 0, i16::MAX, i16::MAX].into(),
+            sample_rate: 48000,
+            num_channels: 2,
+            samples_per_channel: 3,
+        }
+    }
+
+    #[test]
+    fn planar() {
+        let frame = stereo();
+        let planar = frame.to_planar();
+        assert_eq!(
+            planar,
+            vec![vec![100, 200, i16::MAX], vec![-100, 0, i16::MAX]]
+        );
+
+        let rebuilt = AudioFrame::from_planar(48000, &[&planar[0], &planar[1]]);
+        assert_eq!(rebuilt.data, frame.data);
+        assert_eq!(rebuilt.samples_per_channel, 3);
+        assert_eq!(frame.extract_channel(1).data[..], [-100, 0, i16::MAX]);
+    }
+
+    #[test]
+    fn downmix_and_remap() {
+        let frame = stereo();
+        assert_eq!(frame.downmix_to_mono().data[..], [0, 100, i16::MAX]);
+
+        let swapped = frame.remap_channels(&[Some(1), Some(0), None]);
+        assert_eq!(swapped.num_channels, 3);
+        assert_eq!(swapped.data[..3], [-100, 100, 0]);
+
+        let upmixed = frame.extract_channel(0).remap_channels(&[Some(0), Some(0)]);
+        assert_eq!(upmixed.data[..4], [100, 100, 200, 200]);
+    }
+
+    #[test]
+    fn f32_conversion() {
+        let frame = stereo();
+        let samples = frame.to_f32();
+        assert_eq!(samples[4], 1.0);
+
+        let rebuilt = AudioFrame::from_f32(48000, 2, &samples);
+        assert_eq!(rebuilt.data, frame.data);
+        assert_eq!(
+            AudioFrame::from_f32(48000, 1, &[2.0, -2.0]).data[..],
+            [i16::MAX, -i16::MAX]
+        );
+        assert_eq!(frame.to_planar_f32()[1][2], 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "num_channels must be greater than 0")]
+    fn f32_without_channels() {
+        AudioFrame::from_f32(48000, 0, &[0.0; 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "the frame has no channel")]
+    fn remap_without_channels() {
+        AudioFrame::new(48000, 0, 480).remap_channels(&[Some(0)]);
+    }
 }
@@ -40,8 +40,23 @@ impl RtcAudioSource {
 #[cfg(not(target_arch = "wasm32"))]
 pub mod native {
     use super::*;
-    use crate::{audio_frame::AudioFrame, RtcError};
+    use crate::{
+        audio_frame::AudioFrame,
+        native::apm::AudioProcessingModule,
+        native::wav::{WavError, WavReader},
+        RtcError,
+    };
     use std::fmt::{Debug, Formatter};
+    use std::fs::File;
+    use std::io::{BufReader, Read};
+    use std::path::Path;
+    use tokio::task::JoinHandle;
+
+    /// Invoked on each 10ms frame before it is encoded (e.g. noise suppression, voice changers).
+    /// The processor modifies the samples in place and mustn't change the frame size.
+    pub trait AudioProcessor: Send {
+        fn process(&mut self, frame: &mut AudioFrame<'_>);
+    }
 
     #[derive(Clone)]
     pub struct NativeAudioSource {
@@ -65,6 +80,36 @@ pub mod native {
             }
         }
 
+        /// Publish a prerecorded 16-bit PCM WAV at real-time pace. The source uses the format
+        /// of the WAV and the returned task completes once all the samples have been captured
+        pub fn from_wav_reader<R: Read + Send + 'static>(
+            reader: R,
+            options: AudioSourceOptions,
+        ) -> Result<(NativeAudioSource, JoinHandle<Result<(), WavError>>), WavError> {
+            let mut wav = WavReader::new(reader)?;
+            let source = Self::new(options, wav.sample_rate(), wav.num_channels());
+            let handle = tokio::spawn({
+                let source = source.clone();
+                async move {
+                    // capture_frame waits for the previous frame to be consumed
+                    let samples_per_channel = wav.sample_rate() / 100;
+                    while let Some(frame) = wav.read_frame(samples_per_channel)? {
+                        source.capture_frame(&frame).await?;
+                    }
+                    Ok(())
+                }
+            });
+
+            Ok((source, handle))
+        }
+
+        pub fn from_wav_file(
+            path: impl AsRef<Path>,
+            options: AudioSourceOptions,
+        ) -> Result<(NativeAudioSource, JoinHandle<Result<(), WavError>>), WavError> {
+            Self::from_wav_reader(BufReader::new(File::open(path)?), options)
+        }
+
         pub async fn capture_frame(&self, frame: &AudioFrame<'_>) -> Result<(), RtcError> {
             self.handle.capture_frame(frame).await
         }
@@ -77,6 +122,13 @@ pub mod native {
             self.handle.audio_options()
         }
 
+        /// The APM applying the AudioSourceOptions to the captured audio.
+        /// Applications playing the remote audio themselves must feed it to
+        /// process_reverse_stream for the echo cancellation to work
+        pub fn apm(&self) -> AudioProcessingModule {
+            self.handle.apm()
+        }
+
         pub fn sample_rate(&self) -> u32 {
             self.handle.sample_rate()
         }
@@ -84,5 +136,14 @@ pub mod native {
         pub fn num_channels(&self) -> u32 {
             self.handle.num_channels()
         }
+
+        /// Processors are chained in the order they are added
+        pub fn add_processor(&self, processor: Box<dyn AudioProcessor>) {
+            self.handle.add_processor(processor)
+        }
+
+        pub fn clear_processors(&self) {
+            self.handle.clear_processors()
+        }
     }
 }
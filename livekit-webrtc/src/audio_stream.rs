@@ -19,10 +19,51 @@ pub mod native {
     use super::stream_imp;
     use crate::audio_frame::AudioFrame;
     use crate::audio_track::RtcAudioTrack;
+    use crate::native::wav::{WavError, WavWriter};
+    use crate::{RtcError, RtcErrorType};
     use std::fmt::{Debug, Formatter};
+    use std::fs::File;
+    use std::io::BufWriter;
+    use std::path::Path;
     use std::pin::Pin;
     use std::task::{Context, Poll};
-    use tokio_stream::Stream;
+    use tokio::task::JoinHandle;
+    use tokio_stream::{Stream, StreamExt};
+
+    /// The frames are converted to the given format, None keeps the format of the decoder
+    #[derive(Debug, Default, Clone)]
+    pub struct AudioStreamOptions {
+        pub sample_rate: Option<u32>,
+        pub num_channels: Option<u32>,
+        // Rebuffer the audio into frames of this duration (e.g. 10ms)
+        pub frame_duration_ms: Option<u32>,
+    }
+
+    impl AudioStreamOptions {
+        fn validate(&self) -> Result<(), RtcError> {
+            let invalid = |message: &str| {
+                Err(RtcError {
+                    error_type: RtcErrorType::InvalidState,
+                    message: message.to_owned(),
+                })
+            };
+
+            if self.sample_rate == Some(0) {
+                return invalid("sample_rate must be greater than 0");
+            }
+            if self.num_channels == Some(0) {
+                return invalid("num_channels must be greater than 0");
+            }
+            if let Some(frame_duration_ms) = self.frame_duration_ms.filter(|d| *d > 0) {
+                // The sample rate of the decoder is at least 8kHz
+                let sample_rate = self.sample_rate.unwrap_or(8000) as u64;
+                if sample_rate * frame_duration_ms as u64 / 1000 == 0 {
+                    return invalid("frame_duration_ms is too short to contain a sample");
+                }
+            }
+            Ok(())
+        }
+    }
 
     pub struct NativeAudioStream {
         pub(crate) handle: stream_imp::NativeAudioStream,
@@ -39,10 +80,24 @@ pub mod native {
     impl NativeAudioStream {
         pub fn new(audio_track: RtcAudioTrack) -> Self {
             Self {
-                handle: stream_imp::NativeAudioStream::new(audio_track),
+                handle: stream_imp::NativeAudioStream::new(
+                    audio_track,
+                    AudioStreamOptions::default(),
+                ),
             }
         }
 
+        /// Fails if sample_rate or num_channels is 0, or if a frame would contain no samples
+        pub fn with_options(
+            audio_track: RtcAudioTrack,
+            options: AudioStreamOptions,
+        ) -> Result<Self, RtcError> {
+            options.validate()?;
+            Ok(Self {
+                handle: stream_imp::NativeAudioStream::new(audio_track, options),
+            })
+        }
+
         pub fn track(&self) -> RtcAudioTrack {
             self.handle.track()
         }
@@ -50,6 +105,30 @@ pub mod native {
         pub fn close(&mut self) {
             self.handle.close()
         }
+
+        /// Record the stream to a 16-bit PCM WAV until the stream ends. The WAV uses the
+        /// format of the first frame, set sample_rate and num_channels in the
+        /// AudioStreamOptions if the decoder output may change.
+        pub fn write_wav(
+            mut self,
+            path: impl AsRef<Path>,
+        ) -> Result<JoinHandle<Result<(), WavError>>, WavError> {
+            let file = BufWriter::new(File::create(path)?);
+            Ok(tokio::spawn(async move {
+                let Some(first) = self.next().await else {
+                    // Still write a valid (empty) WAV
+                    WavWriter::new(file, 48000, 1)?.finalize()?;
+                    return Ok(());
+                };
+
+                let mut wav = WavWriter::new(file, first.sample_rate, first.num_channels)?;
+                wav.write_frame(&first)?;
+                while let Some(frame) = self.next().await {
+                    wav.write_frame(&frame)?;
+                }
+                wav.finalize()
+            }))
+        }
     }
 
     impl Stream for NativeAudioStream {
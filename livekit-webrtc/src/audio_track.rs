@@ -24,6 +24,24 @@ pub struct RtcAudioTrack {
 
 impl RtcAudioTrack {
     media_stream_track!();
+
+    /// Gain applied to the audio streams of this track, 1.0 being the original volume
+    pub fn set_volume(&self, volume: f32) {
+        self.handle.set_volume(volume)
+    }
+
+    pub fn volume(&self) -> f32 {
+        self.handle.volume()
+    }
+
+    /// Silence the audio streams of this track without touching the track itself
+    pub fn set_playback_enabled(&self, enabled: bool) {
+        self.handle.set_playback_enabled(enabled)
+    }
+
+    pub fn playback_enabled(&self) -> bool {
+        self.handle.playback_enabled()
+    }
 }
 
 impl Debug for RtcAudioTrack {
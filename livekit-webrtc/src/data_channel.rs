@@ -45,6 +45,8 @@ impl Default for DataChannelInit {
 pub enum DataChannelError {
     #[error("failed to send data, dc not open? send buffer is full ?")]
     Send,
+    #[error("the send buffer of the data channel is full")]
+    BufferFull,
     #[error("only utf8 strings can be sent")]
     Utf8(#[from] Utf8Error),
 }
@@ -89,6 +91,11 @@ impl DataChannel {
         self.handle.state()
     }
 
+    /// Bytes queued by send that didn't reach the SCTP transport yet
+    pub fn buffered_amount(&self) -> u64 {
+        self.handle.buffered_amount()
+    }
+
     pub fn close(&self) {
         self.handle.close()
     }
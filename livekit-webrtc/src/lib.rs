@@ -56,6 +56,7 @@ pub mod rtp_receiver;
 pub mod rtp_sender;
 pub mod rtp_transceiver;
 pub mod session_description;
+pub mod stats;
 pub mod video_frame;
 pub mod video_source;
 pub mod video_stream;
@@ -63,10 +64,24 @@ pub mod video_track;
 
 #[cfg(not(target_arch = "wasm32"))]
 pub mod native {
+    pub use crate::imp::apm;
+    pub use crate::imp::audio_device;
+    pub use crate::imp::audio_loopback;
+    pub use crate::imp::audio_mixer;
     pub use crate::imp::audio_resampler;
+    pub use crate::imp::audio_ring_buffer;
+    pub use crate::imp::av_sync;
+    pub use crate::imp::camera;
+    pub use crate::imp::encoded_audio_stream;
+    pub use crate::imp::encoded_video_stream;
     pub use crate::imp::frame_cryptor;
+    pub use crate::imp::screen_capture;
+    pub use crate::imp::vad;
+    pub use crate::imp::wav;
+    pub use crate::imp::y4m;
     pub use crate::imp::yuv_helper;
     pub use webrtc_sys::webrtc::ffi::create_random_uuid;
+    pub use webrtc_sys::webrtc::ffi::time_micros;
 }
 
 #[cfg(target_os = "android")]
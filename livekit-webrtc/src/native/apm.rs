@@ -0,0 +1,116 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::audio_frame::AudioFrame;
+use crate::audio_source::AudioSourceOptions;
+use crate::{RtcError, RtcErrorType};
+use parking_lot::Mutex;
+use std::sync::Arc;
+use webrtc_sys::apm as sys_apm;
+
+/// The WebRTC Audio Processing Module, frames must contain exactly 10ms of audio.
+/// For the echo canceller to work, the audio played to the user (the far-end) must be
+/// given to process_reverse_stream.
+#[derive(Clone)]
+pub struct AudioProcessingModule {
+    sys_handle: Arc<Mutex<cxx::UniquePtr<sys_apm::ffi::AudioProcessingModule>>>,
+}
+
+impl AudioProcessingModule {
+    pub fn new(options: &AudioSourceOptions) -> Self {
+        Self {
+            sys_handle: Arc::new(Mutex::new(sys_apm::ffi::create_apm(
+                options.echo_cancellation,
+                options.noise_suppression,
+                options.auto_gain_control,
+            ))),
+        }
+    }
+
+    pub fn set_options(&self, options: &AudioSourceOptions) {
+        self.sys_handle.lock().pin_mut().apply_config(
+            options.echo_cancellation,
+            options.noise_suppression,
+            options.auto_gain_control,
+        );
+    }
+
+    /// Process the captured (near-end) audio in place
+    pub fn process_stream(&self, frame: &mut AudioFrame<'_>) -> Result<(), RtcError> {
+        check_frame(frame)?;
+        let (sample_rate, num_channels) = (frame.sample_rate, frame.num_channels);
+        let res = self.sys_handle.lock().pin_mut().process_stream(
+            frame.data.to_mut(),
+            sample_rate as i32,
+            num_channels as i32,
+        );
+        apm_result(res)
+    }
+
+    /// Feed the played (far-end) audio, used as the reference of the echo canceller
+    pub fn process_reverse_stream(&self, frame: &mut AudioFrame<'_>) -> Result<(), RtcError> {
+        check_frame(frame)?;
+        let (sample_rate, num_channels) = (frame.sample_rate, frame.num_channels);
+        let res = self.sys_handle.lock().pin_mut().process_reverse_stream(
+            frame.data.to_mut(),
+            sample_rate as i32,
+            num_channels as i32,
+        );
+        apm_result(res)
+    }
+
+    /// Delay between the moment the far-end audio is given to process_reverse_stream and
+    /// the moment its echo is captured
+    pub fn set_stream_delay_ms(&self, delay_ms: i32) -> Result<(), RtcError> {
+        apm_result(
+            self.sys_handle
+                .lock()
+                .pin_mut()
+                .set_stream_delay_ms(delay_ms),
+        )
+    }
+}
+
+fn check_frame(frame: &AudioFrame<'_>) -> Result<(), RtcError> {
+    if frame.samples_per_channel != frame.sample_rate / 100 {
+        return Err(RtcError {
+            error_type: RtcErrorType::InvalidState,
+            message: "the APM only processes frames of 10ms".to_owned(),
+        });
+    }
+
+    let expected_len = frame.samples_per_channel as usize * frame.num_channels as usize;
+    if frame.data.len() != expected_len {
+        return Err(RtcError {
+            error_type: RtcErrorType::InvalidState,
+            message: format!(
+                "the frame has {} samples, expected {}",
+                frame.data.len(),
+                expected_len
+            ),
+        });
+    }
+
+    Ok(())
+}
+
+fn apm_result(res: i32) -> Result<(), RtcError> {
+    if res != 0 {
+        return Err(RtcError {
+            error_type: RtcErrorType::Internal,
+            message: format!("the APM failed with error {}", res),
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,471 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::audio_mixer::{AudioMixer, SourceId};
+use super::audio_resampler::AudioResampler;
+use crate::audio_source::native::NativeAudioSource;
+use crate::audio_source::AudioSourceOptions;
+use crate::audio_stream::native::{AudioStreamOptions, NativeAudioStream};
+use crate::audio_track::RtcAudioTrack;
+use crate::peer_connection_factory::PeerConnectionFactory;
+use crate::{RtcError, RtcErrorType};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use webrtc_sys::audio_device_manager as sys_adm;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const MICROPHONE_SAMPLE_RATE: u32 = 48000;
+const MICROPHONE_NUM_CHANNELS: u32 = 1;
+const PLAYOUT_SAMPLE_RATE: u32 = 48000;
+const PLAYOUT_NUM_CHANNELS: u32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioDeviceKind {
+    Input,
+    Output,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AudioDevice {
+    pub id: String,
+    pub name: String,
+    pub kind: AudioDeviceKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum AudioDeviceEvent {
+    /// A device of this kind has been plugged or unplugged
+    DeviceChanged {
+        kind: AudioDeviceKind,
+        devices: Vec<AudioDevice>,
+    },
+}
+
+struct ManagerInner {
+    sys_handle: cxx::SharedPtr<sys_adm::ffi::AudioDeviceManager>,
+    // Keeps the worker thread of the module alive, dropped after the sys_handle
+    _pc_factory: PeerConnectionFactory,
+    selected_input: Mutex<Option<String>>,
+    selected_output: Mutex<Option<String>>,
+    listeners: Mutex<Vec<mpsc::UnboundedSender<AudioDeviceEvent>>>,
+}
+
+/// Lists the audio devices of the platform and selects the ones to use.
+/// The platform audio device module is owned by the PeerConnectionFactory, so the managers of
+/// the same factory share it.
+/// The device list is polled in the background to report hot-plugs.
+/// Use a MicrophoneCapturer to record the selected input device and an AudioPlayer to play
+/// audio tracks on the selected output device.
+pub struct AudioDeviceManager {
+    inner: Arc<ManagerInner>,
+    stopped: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl AudioDeviceManager {
+    pub fn new(pc_factory: &PeerConnectionFactory) -> Result<Self, RtcError> {
+        let Some(sys_handle) = pc_factory.handle.audio_device_manager() else {
+            return Err(RtcError {
+                error_type: RtcErrorType::Internal,
+                message: "failed to initialize the audio device module".to_owned(),
+            });
+        };
+
+        let inner = Arc::new(ManagerInner {
+            sys_handle,
+            _pc_factory: pc_factory.clone(),
+            selected_input: Default::default(),
+            selected_output: Default::default(),
+            listeners: Default::default(),
+        });
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        let thread = thread::spawn({
+            let inner = inner.clone();
+            let stopped = stopped.clone();
+            move || {
+                let mut inputs = inner.devices(AudioDeviceKind::Input);
+                let mut outputs = inner.devices(AudioDeviceKind::Output);
+                while !stopped.load(Ordering::Acquire) {
+                    thread::sleep(POLL_INTERVAL);
+                    inner.poll_changes(AudioDeviceKind::Input, &mut inputs);
+                    inner.poll_changes(AudioDeviceKind::Output, &mut outputs);
+                }
+            }
+        });
+
+        Ok(Self {
+            inner,
+            stopped,
+            thread: Some(thread),
+        })
+    }
+
+    pub fn devices(&self, kind: AudioDeviceKind) -> Vec<AudioDevice> {
+        self.inner.devices(kind)
+    }
+
+    pub fn input_devices(&self) -> Vec<AudioDevice> {
+        self.devices(AudioDeviceKind::Input)
+    }
+
+    pub fn output_devices(&self) -> Vec<AudioDevice> {
+        self.devices(AudioDeviceKind::Output)
+    }
+
+    pub fn select_device(&self, kind: AudioDeviceKind, id: &str) -> Result<(), RtcError> {
+        let Some(index) = self.inner.device_index(kind, id) else {
+            return Err(RtcError {
+                error_type: RtcErrorType::InvalidState,
+                message: format!("unknown audio device {}", id),
+            });
+        };
+
+        let selected = match kind {
+            AudioDeviceKind::Input => self.inner.sys_handle.set_recording_device(index),
+            AudioDeviceKind::Output => self.inner.sys_handle.set_playout_device(index),
+        };
+
+        if !selected {
+            return Err(RtcError {
+                error_type: RtcErrorType::Internal,
+                message: format!("failed to select the audio device {}", id),
+            });
+        }
+
+        *self.inner.selected(kind).lock() = Some(id.to_owned());
+        Ok(())
+    }
+
+    /// The id of the selected device, None when the default device is used
+    pub fn selected_device(&self, kind: AudioDeviceKind) -> Option<String> {
+        self.inner.selected(kind).lock().clone()
+    }
+
+    /// Receive the DeviceChanged events
+    pub fn subscribe(&self) -> mpsc::UnboundedReceiver<AudioDeviceEvent> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.inner.listeners.lock().push(tx);
+        rx
+    }
+}
+
+impl Drop for AudioDeviceManager {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Release);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Records the selected input device with the audio processing (AEC, NS, AGC) of the
+/// AudioSourceOptions and forwards it to a NativeAudioSource.
+/// The audio played by the AudioPlayer of the same manager is used as the echo canceller
+/// reference. Only one capturer can be used per PeerConnectionFactory.
+/// The NativeAudioSource of the capturer doesn't process the audio again.
+/// The recording stops when every clone of the capturer is dropped.
+#[derive(Clone)]
+pub struct MicrophoneCapturer {
+    inner: Arc<CapturerInner>,
+}
+
+struct CapturerInner {
+    manager: Arc<ManagerInner>,
+    options: AudioSourceOptions,
+    source: NativeAudioSource,
+    recording: Mutex<bool>,
+}
+
+impl MicrophoneCapturer {
+    pub fn new(
+        manager: &AudioDeviceManager,
+        options: AudioSourceOptions,
+    ) -> Result<Self, RtcError> {
+        // The recording is already processed by the device APM, don't process it twice
+        let source = NativeAudioSource::new(
+            AudioSourceOptions::default(),
+            MICROPHONE_SAMPLE_RATE,
+            MICROPHONE_NUM_CHANNELS,
+        );
+
+        let capturer = Self {
+            inner: Arc::new(CapturerInner {
+                manager: manager.inner.clone(),
+                options,
+                source,
+                recording: Mutex::new(false),
+            }),
+        };
+
+        capturer.resume()?;
+        Ok(capturer)
+    }
+
+    pub fn audio_source(&self) -> NativeAudioSource {
+        self.inner.source.clone()
+    }
+
+    /// Stop recording the device (e.g. when the track is muted), the microphone is released
+    pub fn pause(&self) {
+        let mut recording = self.inner.recording.lock();
+        if *recording {
+            self.inner.manager.sys_handle.stop_recording();
+            *recording = false;
+        }
+    }
+
+    pub fn resume(&self) -> Result<(), RtcError> {
+        let mut recording = self.inner.recording.lock();
+        if *recording {
+            return Ok(());
+        }
+
+        let options = &self.inner.options;
+        let recording_options = sys_adm::ffi::RecordingOptions {
+            echo_cancellation: options.echo_cancellation,
+            noise_suppression: options.noise_suppression,
+            auto_gain_control: options.auto_gain_control,
+            sample_rate: MICROPHONE_SAMPLE_RATE,
+            num_channels: MICROPHONE_NUM_CHANNELS,
+        };
+
+        let observer = Arc::new(RecordingObserver {
+            source: self.inner.source.clone(),
+        });
+
+        let started = self.inner.manager.sys_handle.start_recording(
+            Box::new(sys_adm::AudioRecordingObserverWrapper::new(observer)),
+            recording_options,
+        );
+
+        if !started {
+            return Err(RtcError {
+                error_type: RtcErrorType::Internal,
+                message: "failed to start recording the microphone".to_owned(),
+            });
+        }
+
+        *recording = true;
+        Ok(())
+    }
+
+    pub fn is_paused(&self) -> bool {
+        !*self.inner.recording.lock()
+    }
+}
+
+impl Drop for CapturerInner {
+    fn drop(&mut self) {
+        if *self.recording.get_mut() {
+            self.manager.sys_handle.stop_recording();
+        }
+    }
+}
+
+struct RecordingObserver {
+    source: NativeAudioSource,
+}
+
+impl sys_adm::AudioRecordingObserver for RecordingObserver {
+    fn on_recorded_data(&self, data: &[i16]) {
+        self.source.handle.capture_10ms(data);
+    }
+}
+
+/// Plays the added tracks on the selected output device (managed playback), so the apps don't
+/// have to pull the frames of every track. Only one player can be used per PeerConnectionFactory.
+#[derive(Clone)]
+pub struct AudioPlayer {
+    inner: Arc<PlayerInner>,
+}
+
+struct PlayerInner {
+    manager: Arc<ManagerInner>,
+    mixer: AudioMixer,
+    tracks: Mutex<HashMap<String, SourceId>>, // Track id -> mixer source
+}
+
+impl AudioPlayer {
+    pub fn new(manager: &AudioDeviceManager) -> Result<Self, RtcError> {
+        let mixer = AudioMixer::new(PLAYOUT_SAMPLE_RATE, PLAYOUT_NUM_CHANNELS);
+        let source = Arc::new(PlayoutSource {
+            mixer: mixer.clone(),
+            resampler: Default::default(),
+        });
+
+        let started = manager
+            .inner
+            .sys_handle
+            .start_playout(Box::new(sys_adm::AudioPlayoutSourceWrapper::new(source)));
+
+        if !started {
+            return Err(RtcError {
+                error_type: RtcErrorType::Internal,
+                message: "failed to start the audio playout".to_owned(),
+            });
+        }
+
+        Ok(Self {
+            inner: Arc::new(PlayerInner {
+                manager: manager.inner.clone(),
+                mixer,
+                tracks: Default::default(),
+            }),
+        })
+    }
+
+    /// Play the track until it is removed or ended. The volume of the track
+    /// (RtcAudioTrack::set_volume) is applied.
+    pub fn add_track(&self, track: RtcAudioTrack) {
+        let track_id = track.id();
+        let stream = NativeAudioStream::with_options(
+            track,
+            AudioStreamOptions {
+                sample_rate: Some(PLAYOUT_SAMPLE_RATE),
+                num_channels: Some(PLAYOUT_NUM_CHANNELS),
+                frame_duration_ms: None,
+            },
+        )
+        .expect("the playout format is valid");
+
+        let id = self.inner.mixer.add_stream(stream);
+        if let Some(old) = self.inner.tracks.lock().insert(track_id, id) {
+            self.inner.mixer.remove_source(old);
+        }
+    }
+
+    pub fn remove_track(&self, track_id: &str) {
+        if let Some(id) = self.inner.tracks.lock().remove(track_id) {
+            self.inner.mixer.remove_source(id);
+        }
+    }
+
+    /// Gain of the track inside the player, on top of its own volume
+    pub fn set_track_gain(&self, track_id: &str, gain: f32) {
+        if let Some(id) = self.inner.tracks.lock().get(track_id) {
+            self.inner.mixer.set_gain(*id, gain);
+        }
+    }
+
+    pub fn track_ids(&self) -> Vec<String> {
+        self.inner.tracks.lock().keys().cloned().collect()
+    }
+}
+
+impl Drop for PlayerInner {
+    fn drop(&mut self) {
+        self.manager.sys_handle.stop_playout();
+        for (_, id) in self.tracks.lock().drain() {
+            self.mixer.remove_source(id);
+        }
+    }
+}
+
+struct PlayoutSource {
+    mixer: AudioMixer,
+    resampler: Mutex<AudioResampler>,
+}
+
+impl sys_adm::AudioPlayoutSource for PlayoutSource {
+    fn on_playout_data(&self, data: &mut [i16], sample_rate: u32, num_channels: u32) {
+        let frame = self.mixer.mix();
+        if frame.sample_rate == sample_rate && frame.num_channels == num_channels {
+            let len = data.len().min(frame.data.len());
+            data[..len].copy_from_slice(&frame.data[..len]);
+            return;
+        }
+
+        // The device doesn't use the format of the mixer
+        let mut resampler = self.resampler.lock();
+        let converted = resampler.remix_and_resample(
+            &frame.data,
+            frame.samples_per_channel,
+            frame.num_channels,
+            frame.sample_rate,
+            num_channels,
+            sample_rate,
+        );
+        let len = data.len().min(converted.len());
+        data[..len].copy_from_slice(&converted[..len]);
+    }
+}
+
+impl ManagerInner {
+    fn devices(&self, kind: AudioDeviceKind) -> Vec<AudioDevice> {
+        let devices = match kind {
+            AudioDeviceKind::Input => self.sys_handle.recording_devices(),
+            AudioDeviceKind::Output => self.sys_handle.playout_devices(),
+        };
+
+        devices
+            .into_iter()
+            .map(|device| AudioDevice {
+                // Some platforms don't provide a guid
+                id: if device.guid.is_empty() {
+                    device.name.clone()
+                } else {
+                    device.guid
+                },
+                name: device.name,
+                kind,
+            })
+            .collect()
+    }
+
+    fn device_index(&self, kind: AudioDeviceKind, id: &str) -> Option<u16> {
+        let devices = match kind {
+            AudioDeviceKind::Input => self.sys_handle.recording_devices(),
+            AudioDeviceKind::Output => self.sys_handle.playout_devices(),
+        };
+
+        devices
+            .into_iter()
+            .find(|device| device.guid == id || (device.guid.is_empty() && device.name == id))
+            .map(|device| device.index)
+    }
+
+    fn selected(&self, kind: AudioDeviceKind) -> &Mutex<Option<String>> {
+        match kind {
+            AudioDeviceKind::Input => &self.selected_input,
+            AudioDeviceKind::Output => &self.selected_output,
+        }
+    }
+
+    fn poll_changes(&self, kind: AudioDeviceKind, known: &mut Vec<AudioDevice>) {
+        let devices = self.devices(kind);
+        if devices == *known {
+            return;
+        }
+
+        // Forget the selected device once it has been unplugged
+        let mut selected = self.selected(kind).lock();
+        if let Some(id) = selected.as_ref() {
+            if !devices.iter().any(|device| &device.id == id) {
+                *selected = None;
+            }
+        }
+        drop(selected);
+
+        *known = devices.clone();
+        let event = AudioDeviceEvent::DeviceChanged { kind, devices };
+        self.listeners
+            .lock()
+            .retain(|listener| listener.send(event.clone()).is_ok());
+    }
+}
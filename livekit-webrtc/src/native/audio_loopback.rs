@@ -0,0 +1,430 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::audio_device::{AudioDeviceManager, MicrophoneCapturer};
+use crate::audio_frame::AudioFrame;
+use crate::audio_source::native::AudioProcessor;
+use crate::audio_source::AudioSourceOptions;
+use crate::audio_stream::native::{AudioStreamOptions, NativeAudioStream};
+use crate::media_stream_track::MediaStreamTrack;
+use crate::peer_connection::{AnswerOptions, OfferOptions, PeerConnection};
+use crate::peer_connection_factory::native::PeerConnectionFactoryExt;
+use crate::peer_connection_factory::{PeerConnectionFactory, RtcConfiguration};
+use crate::{RtcError, RtcErrorType};
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_stream::StreamExt;
+
+const SAMPLE_RATE: u32 = 48000;
+const CHUNK_MS: u32 = 10;
+const TRACK_TIMEOUT: Duration = Duration::from_secs(5);
+
+// 3s of 10ms levels are kept to estimate the latency, up to 500ms
+const ENVELOPE_LEN: usize = 300;
+const MAX_LAG_CHUNKS: usize = 50;
+const MIN_CORRELATION: f32 = 0.5;
+
+// A frame arriving this late or a digital silence inside the speech is a glitch
+const GLITCH_GAP: Duration = Duration::from_millis(50);
+const SILENCE_DBFS: f32 = -90.0;
+const SPEECH_DBFS: f32 = -50.0;
+
+#[derive(Debug, Clone, Default)]
+pub struct LoopbackStats {
+    /// Capture to playout latency, None until the microphone picked up enough sound
+    pub latency_ms: Option<u32>,
+    pub glitches: u32,
+    /// RMS level of the last decoded 10ms
+    pub level_dbfs: f32,
+    pub peak_dbfs: f32,
+}
+
+/// Routes the microphone through a local pair of PeerConnections (encode -> decode) and
+/// measures the latency, glitches and level of the decoded audio, e.g. for a "test your mic"
+/// screen. The capturer of the test is the only one the manager can use while it runs.
+pub struct AudioLoopbackTest {
+    _capturer: MicrophoneCapturer,
+    sender: PeerConnection,
+    receiver: PeerConnection,
+    analyzer: Arc<Mutex<LoopbackAnalyzer>>,
+    stream_task: JoinHandle<()>,
+}
+
+impl AudioLoopbackTest {
+    pub async fn start(
+        factory: &PeerConnectionFactory,
+        manager: &AudioDeviceManager,
+        options: AudioSourceOptions,
+    ) -> Result<Self, RtcError> {
+        let capturer = MicrophoneCapturer::new(manager, options)?;
+        let analyzer = Arc::new(Mutex::new(LoopbackAnalyzer::default()));
+
+        let source = capturer.audio_source();
+        source.add_processor(Box::new(CaptureTap {
+            analyzer: analyzer.clone(),
+        }));
+
+        let sender = factory.create_peer_connection(RtcConfiguration::default())?;
+        let receiver = factory.create_peer_connection(RtcConfiguration::default())?;
+
+        let (track_tx, mut track_rx) = mpsc::unbounded_channel();
+        receiver.on_track(Some(Box::new(move |event| {
+            if let MediaStreamTrack::Audio(track) = event.track {
+                let _ = track_tx.send(track);
+            }
+        })));
+
+        let track = factory.create_audio_track("loopback", source);
+        sender.add_track(MediaStreamTrack::Audio(track), &["loopback"])?;
+        connect(&sender, &receiver).await?;
+
+        let track = match tokio::time::timeout(TRACK_TIMEOUT, track_rx.recv()).await {
+            Ok(Some(track)) => track,
+            _ => {
+                sender.close();
+                receiver.close();
+                return Err(RtcError {
+                    error_type: RtcErrorType::Internal,
+                    message: "the loopback track wasn't received".to_owned(),
+                });
+            }
+        };
+
+        let mut stream = NativeAudioStream::with_options(
+            track,
+            AudioStreamOptions {
+                sample_rate: Some(SAMPLE_RATE),
+                num_channels: Some(1),
+                frame_duration_ms: Some(CHUNK_MS),
+            },
+        )?;
+
+        let stream_task = tokio::spawn({
+            let analyzer = analyzer.clone();
+            async move {
+                while let Some(frame) = stream.next().await {
+                    analyzer.lock().push_received(&frame, Instant::now());
+                }
+            }
+        });
+
+        Ok(Self {
+            _capturer: capturer,
+            sender,
+            receiver,
+            analyzer,
+            stream_task,
+        })
+    }
+
+    pub fn stats(&self) -> LoopbackStats {
+        self.analyzer.lock().stats()
+    }
+
+    /// Stop the test and return the final stats
+    pub fn stop(self) -> LoopbackStats {
+        self.stats()
+    }
+}
+
+impl Drop for AudioLoopbackTest {
+    fn drop(&mut self) {
+        self.stream_task.abort();
+        // Ends the candidate forwarding tasks
+        self.sender.on_ice_candidate(None);
+        self.receiver.on_ice_candidate(None);
+        self.receiver.on_track(None);
+        self.sender.close();
+        self.receiver.close();
+    }
+}
+
+/// Negotiates the two PeerConnections and forwards their candidates to each other
+async fn connect(sender: &PeerConnection, receiver: &PeerConnection) -> Result<(), RtcError> {
+    let (sender_ice_tx, mut sender_ice_rx) = mpsc::unbounded_channel();
+    let (receiver_ice_tx, mut receiver_ice_rx) = mpsc::unbounded_channel();
+    sender.on_ice_candidate(Some(Box::new(move |candidate| {
+        let _ = sender_ice_tx.send(candidate);
+    })));
+    receiver.on_ice_candidate(Some(Box::new(move |candidate| {
+        let _ = receiver_ice_tx.send(candidate);
+    })));
+
+    let offer = sender.create_offer(OfferOptions::default()).await?;
+    sender.set_local_description(offer.clone()).await?;
+    receiver.set_remote_description(offer).await?;
+
+    let answer = receiver.create_answer(AnswerOptions::default()).await?;
+    receiver.set_local_description(answer.clone()).await?;
+    sender.set_remote_description(answer).await?;
+
+    // The candidates are only added once both descriptions are set
+    tokio::spawn({
+        let receiver = receiver.clone();
+        async move {
+            while let Some(candidate) = sender_ice_rx.recv().await {
+                if let Err(err) = receiver.add_ice_candidate(candidate).await {
+                    log::warn!("failed to add the loopback candidate: {:?}", err);
+                }
+            }
+        }
+    });
+
+    tokio::spawn({
+        let sender = sender.clone();
+        async move {
+            while let Some(candidate) = receiver_ice_rx.recv().await {
+                if let Err(err) = sender.add_ice_candidate(candidate).await {
+                    log::warn!("failed to add the loopback candidate: {:?}", err);
+                }
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Records the level of the captured audio before it is encoded
+struct CaptureTap {
+    analyzer: Arc<Mutex<LoopbackAnalyzer>>,
+}
+
+impl AudioProcessor for CaptureTap {
+    fn process(&mut self, frame: &mut AudioFrame<'_>) {
+        self.analyzer.lock().push_captured(frame, Instant::now());
+    }
+}
+
+/// Compares the 10ms levels of the captured and decoded audio
+#[derive(Default)]
+struct LoopbackAnalyzer {
+    captured: VecDeque<f32>,
+    received: VecDeque<f32>,
+    first_captured: Option<Instant>,
+    first_received: Option<Instant>,
+    captured_chunks: u64,
+    received_chunks: u64,
+    last_received: Option<Instant>,
+    glitches: u32,
+    level_dbfs: f32,
+    peak_dbfs: f32,
+}
+
+impl LoopbackAnalyzer {
+    fn push_captured(&mut self, frame: &AudioFrame<'_>, now: Instant) {
+        self.first_captured.get_or_insert(now);
+        self.captured_chunks += 1;
+        push_level(&mut self.captured, rms_dbfs(&frame.data));
+    }
+
+    fn push_received(&mut self, frame: &AudioFrame<'_>, now: Instant) {
+        self.first_received.get_or_insert(now);
+        self.received_chunks += 1;
+
+        let level = rms_dbfs(&frame.data);
+        let late = self
+            .last_received
+            .is_some_and(|last| now.duration_since(last) > GLITCH_GAP);
+        let dropout =
+            self.received_chunks > 1 && level <= SILENCE_DBFS && self.level_dbfs >= SPEECH_DBFS;
+        if late || dropout {
+            self.glitches += 1;
+        }
+
+        if self.received_chunks == 1 {
+            self.peak_dbfs = level;
+        }
+        self.last_received = Some(now);
+        self.level_dbfs = level;
+        self.peak_dbfs = self.peak_dbfs.max(level);
+        push_level(&mut self.received, level);
+    }
+
+    fn stats(&self) -> LoopbackStats {
+        LoopbackStats {
+            latency_ms: self.latency_ms(),
+            glitches: self.glitches,
+            level_dbfs: self.level_dbfs,
+            peak_dbfs: self.peak_dbfs,
+        }
+    }
+
+    fn latency_ms(&self) -> Option<u32> {
+        let (first_captured, first_received) = (self.first_captured?, self.first_received?);
+        let start_ms = first_received
+            .checked_duration_since(first_captured)?
+            .as_millis() as i64;
+        let start_chunks = start_ms / CHUNK_MS as i64;
+
+        // Without latency, the first received chunk would be the one captured start_chunks
+        // after the first captured chunk
+        let first_captured_index = self.captured_chunks as i64 - self.captured.len() as i64;
+        let first_received_index = self.received_chunks as i64 - self.received.len() as i64;
+        let shift = first_received_index + start_chunks - first_captured_index;
+        let lag = estimate_lag(&self.captured, &self.received, shift, MAX_LAG_CHUNKS)?;
+
+        Some((start_ms % CHUNK_MS as i64) as u32 + lag as u32 * CHUNK_MS)
+    }
+}
+
+fn push_level(levels: &mut VecDeque<f32>, level: f32) {
+    if levels.len() == ENVELOPE_LEN {
+        levels.pop_front();
+    }
+    levels.push_back(level);
+}
+
+/// RMS level in dBFS, -100 for digital silence
+fn rms_dbfs(data: &[i16]) -> f32 {
+    if data.is_empty() {
+        return -100.0;
+    }
+
+    let sum: f64 = data.iter().map(|s| (*s as f64).powi(2)).sum();
+    let rms = (sum / data.len() as f64).sqrt() / i16::MAX as f64;
+    if rms <= 0.0 {
+        return -100.0;
+    }
+    (20.0 * rms.log10()).max(-100.0) as f32
+}
+
+/// Find the lag (in chunks) maximizing the correlation of the levels, the received chunk i
+/// is compared to the captured chunk i + shift - lag.
+/// Returns None when the levels are too flat to be matched.
+fn estimate_lag(
+    captured: &VecDeque<f32>,
+    received: &VecDeque<f32>,
+    shift: i64,
+    max_lag: usize,
+) -> Option<usize> {
+    let mut best: Option<(usize, f32)> = None;
+    for lag in 0..=max_lag {
+        let pairs: Vec<(f32, f32)> = received
+            .iter()
+            .enumerate()
+            .filter_map(|(i, r)| {
+                let c = usize::try_from(i as i64 + shift - lag as i64).ok()?;
+                captured.get(c).map(|c| (*c, *r))
+            })
+            .collect();
+
+        let Some(corr) = correlation(&pairs) else {
+            continue;
+        };
+        if best.is_some_and(|(_, best_corr)| best_corr >= corr) {
+            continue;
+        }
+        best = Some((lag, corr));
+    }
+
+    best.filter(|(_, corr)| *corr >= MIN_CORRELATION)
+        .map(|(lag, _)| lag)
+}
+
+fn correlation(pairs: &[(f32, f32)]) -> Option<f32> {
+    if pairs.len() < MAX_LAG_CHUNKS {
+        return None;
+    }
+
+    let n = pairs.len() as f32;
+    let mean_a = pairs.iter().map(|(a, _)| a).sum::<f32>() / n;
+    let mean_b = pairs.iter().map(|(_, b)| b).sum::<f32>() / n;
+    let (mut cov, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+    for (a, b) in pairs {
+        cov += (a - mean_a) * (b - mean_b);
+        var_a += (a - mean_a).powi(2);
+        var_b += (b - mean_b).powi(2);
+    }
+
+    // Silence or a constant noise can't be matched
+    if var_a < 1.0 || var_b < 1.0 {
+        return None;
+    }
+    Some(cov / (var_a * var_b).sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(value: i16) -> AudioFrame<'static> {
+        AudioFrame {
+            data: vec![value; 480].into(),
+            sample_rate: SAMPLE_RATE,
+            num_channels: 1,
+            samples_per_channel: 480,
+        }
+    }
+
+    // Pseudo random levels between -60 and -20 dBFS
+    fn envelope(len: usize) -> Vec<f32> {
+        let mut state = 0x2545f491u32;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                -60.0 + 40.0 * (state % 1000) as f32 / 1000.0
+            })
+            .collect()
+    }
+
+    #[test]
+    fn lag_estimation() {
+        let levels = envelope(200);
+        let captured: VecDeque<f32> = levels.iter().copied().collect();
+        // The received chunk i matches the captured chunk i, the shift is 12 chunks
+        let received: VecDeque<f32> = levels[..188].iter().copied().collect();
+        assert_eq!(estimate_lag(&captured, &received, 12, 50), Some(12));
+
+        let flat: VecDeque<f32> = vec![-100.0; 200].into();
+        assert_eq!(estimate_lag(&flat, &flat, 0, 50), None);
+    }
+
+    #[test]
+    fn measured_latency() {
+        let mut analyzer = LoopbackAnalyzer::default();
+        let start = Instant::now();
+        let levels = envelope(200);
+        let amplitude = |db: f32| (10f32.powf(db / 20.0) * i16::MAX as f32) as i16;
+
+        // Both streams start together, the received audio is 80ms late
+        for (i, level) in levels.iter().enumerate() {
+            let now = start + Duration::from_millis(i as u64 * 10);
+            analyzer.push_captured(&frame(amplitude(*level)), now);
+            let delayed = if i >= 8 { levels[i - 8] } else { -100.0 };
+            analyzer.push_received(&frame(amplitude(delayed)), now);
+        }
+
+        let latency = analyzer.stats().latency_ms.unwrap();
+        assert!((70..=90).contains(&latency), "latency {}", latency);
+    }
+
+    #[test]
+    fn glitches_and_level() {
+        let mut analyzer = LoopbackAnalyzer::default();
+        let start = Instant::now();
+        analyzer.push_received(&frame(8000), start);
+        analyzer.push_received(&frame(0), start + Duration::from_millis(10)); // Dropout
+        analyzer.push_received(&frame(8000), start + Duration::from_millis(100)); // Late
+
+        let stats = analyzer.stats();
+        assert_eq!(stats.glitches, 2);
+        assert!((stats.level_dbfs - -12.3).abs() < 0.1);
+        assert_eq!(stats.peak_dbfs, stats.level_dbfs);
+    }
+}
@@ -0,0 +1,357 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::audio_frame::AudioFrame;
+use crate::audio_stream::native::NativeAudioStream;
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_stream::{Stream, StreamExt};
+
+// Buffered audio above this duration is dropped so a source that drifts doesn't
+// accumulate latency
+const MAX_BUFFERED_MS: u32 = 100;
+
+pub type SourceId = u64;
+
+/// Attenuate the other sources while a priority source is speaking
+#[derive(Debug, Clone, Copy)]
+pub struct DuckingOptions {
+    // RMS level (0.0 - 1.0) above which a priority source is considered speaking
+    pub threshold: f32,
+    // Gain applied to the other sources while ducking
+    pub gain: f32,
+}
+
+impl Default for DuckingOptions {
+    fn default() -> Self {
+        Self {
+            threshold: 0.02,
+            gain: 0.3,
+        }
+    }
+}
+
+struct MixerSource {
+    buffer: VecDeque<i16>,
+    gain: f32,
+    priority: bool,
+    task: Option<JoinHandle<()>>,
+}
+
+struct MixerInner {
+    sample_rate: u32,
+    num_channels: u32,
+    next_id: SourceId,
+    sources: HashMap<SourceId, MixerSource>,
+    ducking: Option<DuckingOptions>,
+}
+
+/// Mixes several audio sources, all the frames given to the mixer must use its sample rate
+/// and number of channels (see AudioStreamOptions). Each call to mix produces 10ms of audio,
+/// sources which didn't receive enough audio are padded with silence.
+#[derive(Clone)]
+pub struct AudioMixer {
+    inner: Arc<Mutex<MixerInner>>,
+}
+
+impl AudioMixer {
+    pub fn new(sample_rate: u32, num_channels: u32) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(MixerInner {
+                sample_rate,
+                num_channels,
+                next_id: 0,
+                sources: HashMap::new(),
+                ducking: None,
+            })),
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.inner.lock().sample_rate
+    }
+
+    pub fn num_channels(&self) -> u32 {
+        self.inner.lock().num_channels
+    }
+
+    /// Add a source fed with push_frame
+    pub fn add_source(&self) -> SourceId {
+        let mut inner = self.inner.lock();
+        let id = inner.next_id;
+        inner.next_id += 1;
+        inner.sources.insert(
+            id,
+            MixerSource {
+                buffer: VecDeque::new(),
+                gain: 1.0,
+                priority: false,
+                task: None,
+            },
+        );
+        id
+    }
+
+    /// Add a source receiving the frames of the stream, it is removed once the stream ends
+    pub fn add_stream(&self, mut stream: NativeAudioStream) -> SourceId {
+        let id = self.add_source();
+        let task = tokio::spawn({
+            let mixer = self.clone();
+            async move {
+                while let Some(frame) = stream.next().await {
+                    mixer.push_frame(id, &frame);
+                }
+                mixer.inner.lock().sources.remove(&id);
+            }
+        });
+
+        if let Some(source) = self.inner.lock().sources.get_mut(&id) {
+            source.task = Some(task);
+        }
+        id
+    }
+
+    pub fn remove_source(&self, id: SourceId) {
+        if let Some(source) = self.inner.lock().sources.remove(&id) {
+            if let Some(task) = source.task {
+                task.abort();
+            }
+        }
+    }
+
+    pub fn set_gain(&self, id: SourceId, gain: f32) {
+        if let Some(source) = self.inner.lock().sources.get_mut(&id) {
+            source.gain = gain;
+        }
+    }
+
+    /// Priority sources trigger the ducking of the other sources
+    pub fn set_priority(&self, id: SourceId, priority: bool) {
+        if let Some(source) = self.inner.lock().sources.get_mut(&id) {
+            source.priority = priority;
+        }
+    }
+
+    pub fn set_ducking(&self, ducking: Option<DuckingOptions>) {
+        self.inner.lock().ducking = ducking;
+    }
+
+    pub fn push_frame(&self, id: SourceId, frame: &AudioFrame<'_>) {
+        self.inner.lock().push_frame(id, frame);
+    }
+
+    /// Mix 10ms of audio
+    pub fn mix(&self) -> AudioFrame<'static> {
+        self.inner.lock().mix()
+    }
+
+    /// A stream producing a mixed frame every 10ms
+    pub fn mixed_stream(&self) -> MixedAudioStream {
+        let (frame_tx, frame_rx) = mpsc::unbounded_channel();
+        let task = tokio::spawn({
+            let mixer = self.clone();
+            async move {
+                let mut interval = tokio::time::interval(Duration::from_millis(10));
+                loop {
+                    interval.tick().await;
+                    if frame_tx.send(mixer.mix()).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        MixedAudioStream { frame_rx, task }
+    }
+}
+
+impl MixerInner {
+    fn push_frame(&mut self, id: SourceId, frame: &AudioFrame<'_>) {
+        if frame.sample_rate != self.sample_rate || frame.num_channels != self.num_channels {
+            log::warn!("the frame format doesn't match the mixer, dropping it");
+            return;
+        }
+
+        let max_len = (self.sample_rate * MAX_BUFFERED_MS / 1000 * self.num_channels) as usize;
+        let Some(source) = self.sources.get_mut(&id) else {
+            return;
+        };
+
+        source.buffer.extend(frame.data.iter());
+        if source.buffer.len() > max_len {
+            let overflow = source.buffer.len() - max_len;
+            source.buffer.drain(..overflow);
+        }
+    }
+
+    fn mix(&mut self) -> AudioFrame<'static> {
+        let samples_per_channel = self.sample_rate / 100;
+        let len = (samples_per_channel * self.num_channels) as usize;
+
+        let chunks: Vec<(Vec<i16>, f32, bool)> = self
+            .sources
+            .values_mut()
+            .map(|source| {
+                let n = len.min(source.buffer.len());
+                let chunk: Vec<i16> = source.buffer.drain(..n).collect();
+                (chunk, source.gain, source.priority)
+            })
+            .collect();
+
+        let ducked = self.ducking.filter(|ducking| {
+            chunks
+                .iter()
+                .any(|(chunk, _, priority)| *priority && rms(chunk, len) > ducking.threshold)
+        });
+
+        let mut mixed = vec![0f32; len];
+        for (chunk, gain, priority) in &chunks {
+            let gain = match ducked {
+                Some(ducking) if !priority => gain * ducking.gain,
+                _ => *gain,
+            };
+
+            for (out, sample) in mixed.iter_mut().zip(chunk) {
+                *out += *sample as f32 * gain;
+            }
+        }
+
+        AudioFrame {
+            data: mixed
+                .into_iter()
+                .map(|sample| sample.clamp(i16::MIN as f32, i16::MAX as f32) as i16)
+                .collect::<Vec<_>>()
+                .into(),
+            sample_rate: self.sample_rate,
+            num_channels: self.num_channels,
+            samples_per_channel,
+        }
+    }
+}
+
+/// Root mean square of the chunk normalized to 0.0 - 1.0, missing samples are silence
+fn rms(chunk: &[i16], len: usize) -> f32 {
+    if len == 0 {
+        return 0.0;
+    }
+
+    let sum: f32 = chunk
+        .iter()
+        .map(|sample| {
+            let sample = *sample as f32 / i16::MAX as f32;
+            sample * sample
+        })
+        .sum();
+    (sum / len as f32).sqrt()
+}
+
+pub struct MixedAudioStream {
+    frame_rx: mpsc::UnboundedReceiver<AudioFrame<'static>>,
+    task: JoinHandle<()>,
+}
+
+impl Drop for MixedAudioStream {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl Stream for MixedAudioStream {
+    type Item = AudioFrame<'static>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.frame_rx.poll_recv(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(value: i16, samples_per_channel: u32) -> AudioFrame<'static> {
+        AudioFrame {
+            data: vec![value; samples_per_channel as usize].into(),
+            sample_rate: 48000,
+            num_channels: 1,
+            samples_per_channel,
+        }
+    }
+
+    #[test]
+    fn mix_sources() {
+        let mixer = AudioMixer::new(48000, 1);
+        let a = mixer.add_source();
+        let b = mixer.add_source();
+        mixer.set_gain(b, 0.5);
+
+        mixer.push_frame(a, &frame(1000, 480));
+        mixer.push_frame(b, &frame(1000, 240)); // Only 5ms, the rest is silence
+
+        let mixed = mixer.mix();
+        assert_eq!(mixed.data.len(), 480);
+        assert_eq!(mixed.data[0], 1500);
+        assert_eq!(mixed.data[479], 1000);
+
+        // Nothing left to mix
+        assert!(mixer.mix().data.iter().all(|sample| *sample == 0));
+    }
+
+    #[test]
+    fn saturate() {
+        let mixer = AudioMixer::new(48000, 1);
+        for _ in 0..2 {
+            let id = mixer.add_source();
+            mixer.push_frame(id, &frame(i16::MAX, 480));
+        }
+
+        assert!(mixer.mix().data.iter().all(|sample| *sample == i16::MAX));
+    }
+
+    #[test]
+    fn drift_is_bounded() {
+        let mixer = AudioMixer::new(48000, 1);
+        let id = mixer.add_source();
+        for _ in 0..20 {
+            mixer.push_frame(id, &frame(1, 480));
+        }
+
+        let buffered = mixer.inner.lock().sources[&id].buffer.len();
+        assert_eq!(buffered, 4800);
+    }
+
+    #[test]
+    fn ducking() {
+        let mixer = AudioMixer::new(48000, 1);
+        let speaker = mixer.add_source();
+        let music = mixer.add_source();
+        mixer.set_priority(speaker, true);
+        mixer.set_ducking(Some(DuckingOptions {
+            threshold: 0.01,
+            gain: 0.5,
+        }));
+
+        mixer.push_frame(speaker, &frame(1000, 480));
+        mixer.push_frame(music, &frame(1000, 480));
+        assert_eq!(mixer.mix().data[0], 1500);
+
+        // The speaker is silent, the music isn't ducked anymore
+        mixer.push_frame(music, &frame(1000, 480));
+        assert_eq!(mixer.mix().data[0], 1000);
+    }
+}
@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::{RtcError, RtcErrorType};
 use cxx::UniquePtr;
+use std::pin::Pin;
 use webrtc_sys::audio_resampler as sys_ar;
 
 pub struct AudioResampler {
@@ -56,3 +58,167 @@ impl AudioResampler {
         }
     }
 }
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for i16 {}
+    impl Sealed for f32 {}
+}
+
+/// Sample formats supported by the StreamResampler
+pub trait Sample: Copy + Default + private::Sealed {
+    #[doc(hidden)]
+    fn resample(
+        resampler: Pin<&mut sys_ar::ffi::PushAudioResampler>,
+        src: &[Self],
+        dst: &mut [Self],
+    ) -> i32;
+}
+
+impl Sample for i16 {
+    fn resample(
+        resampler: Pin<&mut sys_ar::ffi::PushAudioResampler>,
+        src: &[Self],
+        dst: &mut [Self],
+    ) -> i32 {
+        resampler.resample_i16(src, dst)
+    }
+}
+
+impl Sample for f32 {
+    fn resample(
+        resampler: Pin<&mut sys_ar::ffi::PushAudioResampler>,
+        src: &[Self],
+        dst: &mut [Self],
+    ) -> i32 {
+        resampler.resample_f32(src, dst)
+    }
+}
+
+/// Converts interleaved audio between any two sample rates multiple of 100Hz
+/// (e.g. 44100 <-> 48000). Audio of any length can be pushed, it is converted
+/// by chunks of 10ms and the remaining samples are kept for the next push.
+pub struct StreamResampler<T: Sample> {
+    sys_handle: UniquePtr<sys_ar::ffi::PushAudioResampler>,
+    num_channels: u32,
+    input_rate: u32,
+    output_rate: u32,
+    pending: Vec<T>,
+}
+
+impl<T: Sample> StreamResampler<T> {
+    pub fn new(num_channels: u32, input_rate: u32, output_rate: u32) -> Result<Self, RtcError> {
+        if num_channels == 0 {
+            return Err(RtcError {
+                error_type: RtcErrorType::InvalidState,
+                message: "num_channels must be greater than 0".to_owned(),
+            });
+        }
+
+        // The resampler converts chunks of 10ms, they must contain a whole number of samples
+        let valid_rate = |rate: u32| rate > 0 && rate % 100 == 0;
+        if !valid_rate(input_rate) || !valid_rate(output_rate) {
+            return Err(RtcError {
+                error_type: RtcErrorType::InvalidState,
+                message: format!(
+                    "the sample rates must be non-zero multiples of 100Hz (got {} and {})",
+                    input_rate, output_rate
+                ),
+            });
+        }
+
+        Ok(Self {
+            sys_handle: sys_ar::ffi::create_push_audio_resampler(
+                num_channels as usize,
+                input_rate as i32,
+                output_rate as i32,
+            ),
+            num_channels,
+            input_rate,
+            output_rate,
+            pending: Vec::new(),
+        })
+    }
+
+    pub fn num_channels(&self) -> u32 {
+        self.num_channels
+    }
+
+    pub fn input_rate(&self) -> u32 {
+        self.input_rate
+    }
+
+    pub fn output_rate(&self) -> u32 {
+        self.output_rate
+    }
+
+    /// Resample the given samples, the output only contains complete chunks of 10ms
+    pub fn push(&mut self, src: &[T]) -> Result<Vec<T>, RtcError> {
+        self.pending.extend_from_slice(src);
+
+        let input_10ms = self.chunk_len(self.input_rate);
+        let chunks = self.pending.len() / input_10ms;
+        let mut output = vec![T::default(); chunks * self.chunk_len(self.output_rate)];
+        for (src, dst) in self
+            .pending
+            .chunks_exact(input_10ms)
+            .zip(output.chunks_exact_mut(self.chunk_len(self.output_rate)))
+        {
+            if T::resample(self.sys_handle.pin_mut(), src, dst) < 0 {
+                return Err(RtcError {
+                    error_type: RtcErrorType::Internal,
+                    message: "failed to resample the audio".to_owned(),
+                });
+            }
+        }
+
+        self.pending.drain(..chunks * input_10ms);
+        Ok(output)
+    }
+
+    /// Resample the remaining samples, padded with silence
+    pub fn flush(&mut self) -> Result<Vec<T>, RtcError> {
+        if self.pending.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let frames = self.pending.len() / self.num_channels as usize;
+        self.pending
+            .resize(self.chunk_len(self.input_rate), T::default());
+
+        let mut output = self.push(&[])?;
+        let output_frames = frames * self.output_rate as usize / self.input_rate as usize;
+        output.truncate(output_frames * self.num_channels as usize);
+        Ok(output)
+    }
+
+    fn chunk_len(&self, sample_rate: u32) -> usize {
+        (sample_rate / 100 * self.num_channels) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stream_resampling() {
+        let mut resampler = StreamResampler::<i16>::new(2, 48000, 44100).unwrap();
+
+        // 25ms of audio, only 20ms can be converted
+        let output = resampler.push(&[0; 1200 * 2]).unwrap();
+        assert_eq!(output.len(), 441 * 2 * 2);
+
+        // The remaining 5ms
+        let output = resampler.flush().unwrap();
+        assert_eq!(output.len(), 220 * 2);
+        assert!(resampler.flush().unwrap().is_empty());
+    }
+
+    #[test]
+    fn invalid_rate() {
+        assert!(StreamResampler::<f32>::new(1, 22050, 48000).is_err());
+        assert!(StreamResampler::<f32>::new(1, 0, 48000).is_err());
+        assert!(StreamResampler::<i16>::new(0, 48000, 16000).is_err());
+    }
+}
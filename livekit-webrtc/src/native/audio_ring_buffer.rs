@@ -0,0 +1,268 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::audio_frame::AudioFrame;
+use crate::audio_stream::native::NativeAudioStream;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tokio_stream::StreamExt;
+
+/// What to do when a frame doesn't fit in the buffer
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OverrunPolicy {
+    // Drop the oldest audio to keep the latency low
+    #[default]
+    DropOldest,
+    // Drop the audio that doesn't fit
+    DropNewest,
+}
+
+#[derive(Debug, Clone)]
+pub struct AudioRingBufferOptions {
+    pub capacity_ms: u32,
+    // After an underrun, silence is read until this much audio is buffered again
+    pub prebuffer_ms: u32,
+    pub overrun_policy: OverrunPolicy,
+}
+
+impl Default for AudioRingBufferOptions {
+    fn default() -> Self {
+        Self {
+            capacity_ms: 200,
+            prebuffer_ms: 40,
+            overrun_policy: OverrunPolicy::default(),
+        }
+    }
+}
+
+struct RingInner {
+    buffer: VecDeque<i16>,
+    capacity: usize,
+    prebuffer: usize,
+    buffering: bool,
+    underruns: u64,
+    overruns: u64,
+}
+
+/// Buffers the received audio so it can be pulled from a render callback (cpal, CoreAudio..),
+/// the frames pushed must use the sample rate and channels of the buffer.
+#[derive(Clone)]
+pub struct AudioRingBuffer {
+    sample_rate: u32,
+    num_channels: u32,
+    overrun_policy: OverrunPolicy,
+    inner: Arc<Mutex<RingInner>>,
+}
+
+impl AudioRingBuffer {
+    pub fn new(sample_rate: u32, num_channels: u32, options: AudioRingBufferOptions) -> Self {
+        let samples_per_ms = (sample_rate / 1000 * num_channels) as usize;
+        let capacity = samples_per_ms * options.capacity_ms as usize;
+        let prebuffer = (samples_per_ms * options.prebuffer_ms as usize).min(capacity);
+
+        Self {
+            sample_rate,
+            num_channels,
+            overrun_policy: options.overrun_policy,
+            inner: Arc::new(Mutex::new(RingInner {
+                buffer: VecDeque::with_capacity(capacity),
+                capacity,
+                prebuffer,
+                buffering: prebuffer > 0,
+                underruns: 0,
+                overruns: 0,
+            })),
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn num_channels(&self) -> u32 {
+        self.num_channels
+    }
+
+    /// Push the frame, returns false if it doesn't match the format of the buffer
+    pub fn push(&self, frame: &AudioFrame<'_>) -> bool {
+        if frame.sample_rate != self.sample_rate || frame.num_channels != self.num_channels {
+            return false;
+        }
+
+        let mut inner = self.inner.lock();
+        let available = inner.capacity - inner.buffer.len();
+        let mut data = &frame.data[..];
+        if data.len() > available {
+            inner.overruns += 1;
+            match self.overrun_policy {
+                OverrunPolicy::DropOldest => {
+                    let overflow = (data.len() - available).min(inner.buffer.len());
+                    inner.buffer.drain(..overflow);
+                    // The frame alone may be larger than the buffer
+                    data = &data[data.len() - data.len().min(inner.capacity)..];
+                }
+                OverrunPolicy::DropNewest => data = &data[..available],
+            }
+        }
+
+        inner.buffer.extend(data.iter());
+        if inner.buffering && inner.buffer.len() >= inner.prebuffer {
+            inner.buffering = false;
+        }
+        true
+    }
+
+    /// Fill out with the buffered samples, the missing samples are silence.
+    /// Returns the number of samples read from the buffer.
+    pub fn read(&self, out: &mut [i16]) -> usize {
+        let mut inner = self.inner.lock();
+        let read = inner.read_len(out.len());
+        for (out, sample) in out.iter_mut().zip(inner.buffer.drain(..read)) {
+            *out = sample;
+        }
+        out[read..].fill(0);
+        read
+    }
+
+    /// Same as read, with samples converted to f32 (-1.0 - 1.0)
+    pub fn read_f32(&self, out: &mut [f32]) -> usize {
+        let mut inner = self.inner.lock();
+        let read = inner.read_len(out.len());
+        for (out, sample) in out.iter_mut().zip(inner.buffer.drain(..read)) {
+            *out = sample as f32 / i16::MAX as f32;
+        }
+        out[read..].fill(0.0);
+        read
+    }
+
+    /// Number of buffered samples
+    pub fn len(&self) -> usize {
+        self.inner.lock().buffer.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock();
+        inner.buffer.clear();
+        inner.buffering = inner.prebuffer > 0;
+    }
+
+    /// Number of reads which didn't find enough audio
+    pub fn underruns(&self) -> u64 {
+        self.inner.lock().underruns
+    }
+
+    /// Number of pushes which didn't fit in the buffer
+    pub fn overruns(&self) -> u64 {
+        self.inner.lock().overruns
+    }
+
+    /// Push the frames of the stream until it ends
+    pub fn attach_stream(&self, mut stream: NativeAudioStream) -> JoinHandle<()> {
+        let buffer = self.clone();
+        tokio::spawn(async move {
+            while let Some(frame) = stream.next().await {
+                if !buffer.push(&frame) {
+                    log::warn!("the frame format doesn't match the ring buffer, dropping it");
+                }
+            }
+        })
+    }
+}
+
+impl RingInner {
+    fn read_len(&mut self, wanted: usize) -> usize {
+        if self.buffering {
+            return 0;
+        }
+
+        if self.buffer.len() < wanted {
+            self.underruns += 1;
+            self.buffering = self.prebuffer > 0;
+        }
+
+        wanted.min(self.buffer.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(value: i16, len: usize) -> AudioFrame<'static> {
+        AudioFrame {
+            data: vec![value; len].into(),
+            sample_rate: 48000,
+            num_channels: 1,
+            samples_per_channel: len as u32,
+        }
+    }
+
+    fn options(overrun_policy: OverrunPolicy) -> AudioRingBufferOptions {
+        AudioRingBufferOptions {
+            capacity_ms: 20,
+            prebuffer_ms: 10,
+            overrun_policy,
+        }
+    }
+
+    #[test]
+    fn prebuffer_and_underrun() {
+        let buffer = AudioRingBuffer::new(48000, 1, options(OverrunPolicy::DropOldest));
+        let mut out = [1i16; 480];
+
+        buffer.push(&frame(5, 240));
+        assert_eq!(buffer.read(&mut out), 0); // Still prebuffering
+        assert!(out.iter().all(|s| *s == 0));
+
+        buffer.push(&frame(5, 240));
+        assert_eq!(buffer.read(&mut out[..240]), 240);
+        assert_eq!(out[0], 5);
+
+        // Not enough audio, the rest is silence
+        assert_eq!(buffer.read(&mut out), 240);
+        assert_eq!(out[239], 5);
+        assert_eq!(out[240], 0);
+        assert_eq!(buffer.underruns(), 1);
+    }
+
+    #[test]
+    fn overrun_policies() {
+        let buffer = AudioRingBuffer::new(48000, 1, options(OverrunPolicy::DropOldest));
+        buffer.push(&frame(1, 960));
+        buffer.push(&frame(2, 480));
+        assert_eq!(buffer.len(), 960);
+        assert_eq!(buffer.overruns(), 1);
+
+        let mut out = [0i16; 960];
+        buffer.read(&mut out);
+        assert_eq!((out[0], out[959]), (1, 2));
+
+        let buffer = AudioRingBuffer::new(48000, 1, options(OverrunPolicy::DropNewest));
+        buffer.push(&frame(1, 960));
+        buffer.push(&frame(2, 480));
+        buffer.read(&mut out);
+        assert!(out.iter().all(|s| *s == 1));
+    }
+
+    #[test]
+    fn invalid_format() {
+        let buffer = AudioRingBuffer::new(16000, 1, Default::default());
+        assert!(!buffer.push(&frame(1, 480)));
+    }
+}
@@ -12,9 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::audio_source::native::AudioProcessor;
+use crate::native::apm::AudioProcessingModule;
 use crate::{audio_frame::AudioFrame, audio_source::AudioSourceOptions, RtcError, RtcErrorType};
 use cxx::SharedPtr;
-use std::{sync::Arc, time::Duration};
+use parking_lot::Mutex;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 use tokio::{
     sync::{Mutex as AsyncMutex, MutexGuard},
     time::interval,
@@ -25,6 +34,9 @@ use webrtc_sys::audio_track as sys_at;
 pub struct NativeAudioSource {
     sys_handle: SharedPtr<sys_at::ffi::AudioTrackSource>,
     inner: Arc<AsyncMutex<AudioSourceInner>>,
+    processors: Arc<Mutex<Vec<Box<dyn AudioProcessor>>>>,
+    apm: AudioProcessingModule,
+    apm_enabled: Arc<AtomicBool>,
     sample_rate: u32,
     num_channels: u32,
     samples_10ms: usize,
@@ -50,6 +62,8 @@ impl NativeAudioSource {
         num_channels: u32,
     ) -> NativeAudioSource {
         let samples_10ms = (sample_rate / 100 * num_channels) as usize;
+        let apm = AudioProcessingModule::new(&options);
+        let apm_enabled = Arc::new(AtomicBool::new(apm_needed(&options)));
 
         Self {
             sys_handle: sys_at::ffi::new_audio_track_source(options.into()),
@@ -59,6 +73,9 @@ impl NativeAudioSource {
                 read_offset: 0,
                 interval: None, // interval must be created from a tokio runtime context
             })),
+            processors: Default::default(),
+            apm,
+            apm_enabled,
             sample_rate,
             num_channels,
             samples_10ms,
@@ -70,10 +87,17 @@ impl NativeAudioSource {
     }
 
     pub fn set_audio_options(&self, options: AudioSourceOptions) {
+        self.apm.set_options(&options);
+        self.apm_enabled
+            .store(apm_needed(&options), Ordering::Release);
         self.sys_handle
             .set_audio_options(&sys_at::ffi::AudioSourceOptions::from(options))
     }
 
+    pub fn apm(&self) -> AudioProcessingModule {
+        self.apm.clone()
+    }
+
     pub fn audio_options(&self) -> AudioSourceOptions {
         self.sys_handle.audio_options().into()
     }
@@ -86,6 +110,14 @@ impl NativeAudioSource {
         self.num_channels
     }
 
+    pub fn add_processor(&self, processor: Box<dyn AudioProcessor>) {
+        self.processors.lock().push(processor);
+    }
+
+    pub fn clear_processors(&self) {
+        self.processors.lock().clear();
+    }
+
     // Implemented inside another functions to allow unit testing
     fn next_frame<'a>(
         &self,
@@ -143,20 +175,65 @@ impl NativeAudioSource {
             };
 
             interval.tick().await;
+            self.capture_10ms(data);
+        }
+
+        Ok(())
+    }
 
-            let samples_per_channel = data.len() / self.num_channels as usize;
+    /// Send 10ms of audio to libwebrtc, used directly by realtime producers (e.g. the
+    /// microphone) which don't need to be paced
+    pub(crate) fn capture_10ms(&self, data: &[i16]) {
+        let samples_per_channel = data.len() / self.num_channels as usize;
+        let apm_enabled = self.apm_enabled.load(Ordering::Acquire);
+        let mut processors = self.processors.lock();
+        if !apm_enabled && processors.is_empty() {
             self.sys_handle.on_captured_frame(
                 data,
                 self.sample_rate as i32,
                 self.num_channels as usize,
                 samples_per_channel,
             );
+            return;
         }
 
-        Ok(())
+        let mut processed = AudioFrame {
+            data: data.to_vec().into(),
+            sample_rate: self.sample_rate,
+            num_channels: self.num_channels,
+            samples_per_channel: samples_per_channel as u32,
+        };
+
+        if apm_enabled {
+            if let Err(err) = self.apm.process_stream(&mut processed) {
+                log::warn!("failed to process the captured audio: {:?}", err);
+            }
+        }
+
+        for processor in processors.iter_mut() {
+            processor.process(&mut processed);
+        }
+        drop(processors);
+
+        // libwebrtc reads samples_per_channel * num_channels samples from the buffer
+        if processed.data.len() != samples_per_channel * self.num_channels as usize {
+            log::warn!("an audio processor changed the frame size, dropping the frame");
+            return;
+        }
+
+        self.sys_handle.on_captured_frame(
+            &processed.data,
+            self.sample_rate as i32,
+            self.num_channels as usize,
+            samples_per_channel,
+        );
     }
 }
 
+fn apm_needed(options: &AudioSourceOptions) -> bool {
+    options.echo_cancellation || options.noise_suppression || options.auto_gain_control
+}
+
 impl From<sys_at::ffi::AudioSourceOptions> for AudioSourceOptions {
     fn from(options: sys_at::ffi::AudioSourceOptions) -> Self {
         Self {
@@ -12,9 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::audio_resampler::AudioResampler;
+use super::audio_track::AudioGain;
 use crate::audio_frame::AudioFrame;
+use crate::audio_stream::native::AudioStreamOptions;
 use crate::audio_track::RtcAudioTrack;
 use cxx::SharedPtr;
+use parking_lot::Mutex;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
@@ -29,9 +33,14 @@ pub struct NativeAudioStream {
 }
 
 impl NativeAudioStream {
-    pub fn new(audio_track: RtcAudioTrack) -> Self {
+    pub fn new(audio_track: RtcAudioTrack, options: AudioStreamOptions) -> Self {
         let (frame_tx, frame_rx) = mpsc::unbounded_channel();
-        let observer = Arc::new(AudioTrackObserver { frame_tx });
+        let observer = Arc::new(AudioTrackObserver {
+            frame_tx,
+            gain: audio_track.handle.gain.clone(),
+            options,
+            conversion: Default::default(),
+        });
         let native_sink = sys_at::ffi::new_native_audio_sink(Box::new(
             sys_at::AudioSinkWrapper::new(observer.clone()),
         ));
@@ -74,15 +83,68 @@ impl Stream for NativeAudioStream {
 
 pub struct AudioTrackObserver {
     frame_tx: mpsc::UnboundedSender<AudioFrame<'static>>,
+    gain: Arc<Mutex<AudioGain>>,
+    options: AudioStreamOptions,
+    conversion: Mutex<Conversion>,
+}
+
+#[derive(Default)]
+struct Conversion {
+    resampler: AudioResampler,
+    pending: Vec<i16>, // Converted samples not yet sent because the frame isn't complete
 }
 
 impl sys_at::AudioSink for AudioTrackObserver {
     fn on_data(&self, data: &[i16], sample_rate: i32, nb_channels: usize, nb_frames: usize) {
-        let _ = self.frame_tx.send(AudioFrame {
-            data: data.to_owned().into(),
-            sample_rate: sample_rate as u32,
-            num_channels: nb_channels as u32,
-            samples_per_channel: nb_frames as u32,
-        });
+        let sample_rate = sample_rate as u32;
+        let num_channels = nb_channels as u32;
+        let dst_sample_rate = self.options.sample_rate.unwrap_or(sample_rate);
+        let dst_num_channels = self.options.num_channels.unwrap_or(num_channels);
+
+        let mut conversion = self.conversion.lock();
+        let mut data = if dst_sample_rate != sample_rate || dst_num_channels != num_channels {
+            conversion
+                .resampler
+                .remix_and_resample(
+                    data,
+                    nb_frames as u32,
+                    num_channels,
+                    sample_rate,
+                    dst_num_channels,
+                    dst_sample_rate,
+                )
+                .to_owned()
+        } else {
+            data.to_owned()
+        };
+        self.gain.lock().apply(&mut data);
+
+        // Rebuffer into frames of frame_duration, a frame must contain at least one sample
+        let samples_per_channel = self
+            .options
+            .frame_duration_ms
+            .map(|frame_duration| dst_sample_rate * frame_duration / 1000)
+            .unwrap_or_default();
+        if samples_per_channel == 0 {
+            let _ = self.frame_tx.send(AudioFrame {
+                samples_per_channel: data.len() as u32 / dst_num_channels,
+                data: data.into(),
+                sample_rate: dst_sample_rate,
+                num_channels: dst_num_channels,
+            });
+            return;
+        }
+
+        let frame_len = (samples_per_channel * dst_num_channels) as usize;
+        conversion.pending.extend_from_slice(&data);
+        while conversion.pending.len() >= frame_len {
+            let frame: Vec<i16> = conversion.pending.drain(..frame_len).collect();
+            let _ = self.frame_tx.send(AudioFrame {
+                data: frame.into(),
+                sample_rate: dst_sample_rate,
+                num_channels: dst_num_channels,
+                samples_per_channel,
+            });
+        }
     }
 }
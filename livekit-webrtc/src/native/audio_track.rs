@@ -15,18 +15,94 @@
 use super::media_stream_track::impl_media_stream_track;
 use crate::media_stream_track::RtcTrackState;
 use cxx::SharedPtr;
+use parking_lot::Mutex;
+use std::sync::Arc;
 use sys_at::ffi::audio_to_media;
 use webrtc_sys::audio_track as sys_at;
 
+/// Gain applied to the frames received by the NativeAudioStreams of a track
+#[derive(Debug)]
+pub(crate) struct AudioGain {
+    volume: f32,
+    playback_enabled: bool,
+}
+
+impl Default for AudioGain {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            playback_enabled: true,
+        }
+    }
+}
+
+impl AudioGain {
+    pub(crate) fn apply(&self, data: &mut [i16]) {
+        if !self.playback_enabled {
+            data.fill(0);
+        } else if self.volume != 1.0 {
+            for sample in data.iter_mut() {
+                *sample =
+                    (*sample as f32 * self.volume).clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+            }
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct RtcAudioTrack {
     pub(crate) sys_handle: SharedPtr<sys_at::ffi::AudioTrack>,
+    pub(crate) gain: Arc<Mutex<AudioGain>>,
 }
 
 impl RtcAudioTrack {
     impl_media_stream_track!(audio_to_media);
 
+    pub(crate) fn new(sys_handle: SharedPtr<sys_at::ffi::AudioTrack>) -> Self {
+        Self {
+            sys_handle,
+            gain: Default::default(),
+        }
+    }
+
+    pub fn set_volume(&self, volume: f32) {
+        self.gain.lock().volume = volume.max(0.0);
+    }
+
+    pub fn volume(&self) -> f32 {
+        self.gain.lock().volume
+    }
+
+    pub fn set_playback_enabled(&self, enabled: bool) {
+        self.gain.lock().playback_enabled = enabled;
+    }
+
+    pub fn playback_enabled(&self) -> bool {
+        self.gain.lock().playback_enabled
+    }
+
     pub fn sys_handle(&self) -> SharedPtr<sys_at::ffi::MediaStreamTrack> {
         audio_to_media(self.sys_handle.clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_gain() {
+        let mut gain = AudioGain::default();
+        let mut data = [100, -100, i16::MAX];
+        gain.apply(&mut data);
+        assert_eq!(data, [100, -100, i16::MAX]);
+
+        gain.volume = 2.0;
+        gain.apply(&mut data);
+        assert_eq!(data, [200, -200, i16::MAX]);
+
+        gain.playback_enabled = false;
+        gain.apply(&mut data);
+        assert_eq!(data, [0, 0, 0]);
+    }
+}
@@ -0,0 +1,307 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::audio_frame::AudioFrame;
+use crate::audio_stream::native::NativeAudioStream;
+use crate::video_frame::BoxVideoFrame;
+use crate::video_stream::native::NativeVideoStream;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::time::Sleep;
+use tokio_stream::Stream;
+use webrtc_sys::webrtc as sys_rtc;
+
+// Audio arriving later than its expected time by more than this restarts the
+// audio timeline (e.g. the playout stopped)
+const AUDIO_REANCHOR_THRESHOLD_US: i64 = 200_000;
+
+#[derive(Debug)]
+pub enum SyncedFrame {
+    Audio {
+        timestamp_us: i64,
+        frame: AudioFrame<'static>,
+    },
+    Video(BoxVideoFrame),
+}
+
+impl SyncedFrame {
+    /// Presentation time in the clock of the video frames (webrtc's monotonic clock)
+    pub fn timestamp_us(&self) -> i64 {
+        match self {
+            Self::Audio { timestamp_us, .. } => *timestamp_us,
+            Self::Video(frame) => frame.timestamp_us,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AVSynchronizerOptions {
+    /// How long a frame waits for the other stream before being delivered anyway
+    pub max_delay: Duration,
+}
+
+impl Default for AVSynchronizerOptions {
+    fn default() -> Self {
+        Self {
+            max_delay: Duration::from_millis(200),
+        }
+    }
+}
+
+/// Merges the audio and the video streams of a participant into a single stream
+/// ordered by presentation time, e.g. to feed a muxer.
+///
+/// The received video frames are timestamped with their render time, the audio
+/// frames are timestamped with the time they are played out, keeping the sample
+/// count continuous so the audio timeline doesn't jitter.
+pub struct AVSynchronizer<A = NativeAudioStream, V = NativeVideoStream> {
+    audio: Option<A>,
+    video: Option<V>,
+    clock: AudioClock,
+    queue: SyncQueue<BoxVideoFrame>,
+    timer: Option<Pin<Box<Sleep>>>,
+}
+
+impl<A, V> AVSynchronizer<A, V>
+where
+    A: Stream<Item = AudioFrame<'static>> + Unpin,
+    V: Stream<Item = BoxVideoFrame> + Unpin,
+{
+    pub fn new(audio: A, video: V, options: AVSynchronizerOptions) -> Self {
+        Self {
+            audio: Some(audio),
+            video: Some(video),
+            clock: AudioClock::default(),
+            queue: SyncQueue::new(options.max_delay.as_micros() as i64),
+            timer: None,
+        }
+    }
+}
+
+impl<A, V> Stream for AVSynchronizer<A, V>
+where
+    A: Stream<Item = AudioFrame<'static>> + Unpin,
+    V: Stream<Item = BoxVideoFrame> + Unpin,
+{
+    type Item = SyncedFrame;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        while let Some(audio) = this.audio.as_mut() {
+            match Pin::new(audio).poll_next(cx) {
+                Poll::Ready(Some(frame)) => {
+                    let timestamp_us = this.clock.stamp(sys_rtc::ffi::time_micros(), &frame);
+                    this.queue.push_audio(timestamp_us, frame);
+                }
+                Poll::Ready(None) => this.audio = None,
+                Poll::Pending => break,
+            }
+        }
+
+        while let Some(video) = this.video.as_mut() {
+            match Pin::new(video).poll_next(cx) {
+                Poll::Ready(Some(frame)) => this.queue.push_video(frame.timestamp_us, frame),
+                Poll::Ready(None) => this.video = None,
+                Poll::Pending => break,
+            }
+        }
+
+        let now_us = sys_rtc::ffi::time_micros();
+        let ended = (this.audio.is_none(), this.video.is_none());
+        if let Some(frame) = this.queue.pop(now_us, ended) {
+            this.timer = None;
+            return Poll::Ready(Some(match frame {
+                Synced::Audio(timestamp_us, frame) => SyncedFrame::Audio {
+                    timestamp_us,
+                    frame,
+                },
+                Synced::Video(frame) => SyncedFrame::Video(frame),
+            }));
+        }
+
+        if ended == (true, true) && this.queue.is_empty() {
+            return Poll::Ready(None);
+        }
+
+        // Wake up when the oldest frame stopped waiting for the other stream
+        if let Some(deadline_us) = this.queue.deadline_us() {
+            let wait = Duration::from_micros((deadline_us - now_us).max(0) as u64);
+            let deadline = tokio::time::Instant::now() + wait;
+            let timer = this
+                .timer
+                .get_or_insert_with(|| Box::pin(tokio::time::sleep_until(deadline)));
+            timer.as_mut().reset(deadline);
+            if timer.as_mut().poll(cx).is_ready() {
+                this.timer = None;
+                cx.waker().wake_by_ref();
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+/// Timestamps the audio frames from their sample count, anchored on the arrival
+/// time of the first frame
+#[derive(Debug, Default)]
+struct AudioClock {
+    anchor_us: i64,
+    sample_rate: u32,
+    samples: u64,
+}
+
+impl AudioClock {
+    fn stamp(&mut self, arrival_us: i64, frame: &AudioFrame) -> i64 {
+        let expected_us = self.anchor_us + self.elapsed_us();
+        if self.sample_rate != frame.sample_rate
+            || arrival_us - expected_us > AUDIO_REANCHOR_THRESHOLD_US
+        {
+            self.anchor_us = arrival_us;
+            self.sample_rate = frame.sample_rate;
+            self.samples = 0;
+        }
+
+        let timestamp_us = self.anchor_us + self.elapsed_us();
+        self.samples += frame.samples_per_channel as u64;
+        timestamp_us
+    }
+
+    fn elapsed_us(&self) -> i64 {
+        if self.sample_rate == 0 {
+            return 0;
+        }
+        (self.samples * 1_000_000 / self.sample_rate as u64) as i64
+    }
+}
+
+enum Synced<V> {
+    Audio(i64, AudioFrame<'static>),
+    Video(V),
+}
+
+struct SyncQueue<V> {
+    max_delay_us: i64,
+    audio: VecDeque<(i64, AudioFrame<'static>)>,
+    video: VecDeque<(i64, V)>,
+}
+
+impl<V> SyncQueue<V> {
+    fn new(max_delay_us: i64) -> Self {
+        Self {
+            max_delay_us,
+            audio: VecDeque::new(),
+            video: VecDeque::new(),
+        }
+    }
+
+    fn push_audio(&mut self, timestamp_us: i64, frame: AudioFrame<'static>) {
+        self.audio.push_back((timestamp_us, frame));
+    }
+
+    fn push_video(&mut self, timestamp_us: i64, frame: V) {
+        self.video.push_back((timestamp_us, frame));
+    }
+
+    fn is_empty(&self) -> bool {
+        self.audio.is_empty() && self.video.is_empty()
+    }
+
+    /// Time at which the oldest queued frame is delivered without waiting for the other stream
+    fn deadline_us(&self) -> Option<i64> {
+        let oldest = match (self.audio.front(), self.video.front()) {
+            (Some((a, _)), Some((v, _))) => (*a).min(*v),
+            (Some((a, _)), None) => *a,
+            (None, Some((v, _))) => *v,
+            (None, None) => return None,
+        };
+        Some(oldest + self.max_delay_us)
+    }
+
+    /// Pop the next frame in presentation order. A frame is only delivered once the other
+    /// stream has a later frame, has ended, or max_delay elapsed
+    fn pop(&mut self, now_us: i64, (audio_ended, video_ended): (bool, bool)) -> Option<Synced<V>> {
+        let audio_first = match (self.audio.front(), self.video.front()) {
+            (Some((a, _)), Some((v, _))) => a <= v,
+            (Some((a, _)), None) if video_ended || a + self.max_delay_us <= now_us => true,
+            (None, Some((v, _))) if audio_ended || v + self.max_delay_us <= now_us => false,
+            _ => return None,
+        };
+
+        if audio_first {
+            self.audio
+                .pop_front()
+                .map(|(timestamp_us, frame)| Synced::Audio(timestamp_us, frame))
+        } else {
+            self.video
+                .pop_front()
+                .map(|(_, frame)| Synced::Video(frame))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timestamps(queue: &mut SyncQueue<i64>, now_us: i64, ended: (bool, bool)) -> Vec<i64> {
+        std::iter::from_fn(|| queue.pop(now_us, ended))
+            .map(|synced| match synced {
+                Synced::Audio(timestamp_us, _) => timestamp_us,
+                Synced::Video(timestamp_us) => -timestamp_us,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn audio_clock() {
+        let frame = AudioFrame::new(48000, 1, 480);
+        let mut clock = AudioClock::default();
+        assert_eq!(clock.stamp(1_000_000, &frame), 1_000_000);
+        // Jitter on the arrival time doesn't change the timeline
+        assert_eq!(clock.stamp(1_015_000, &frame), 1_010_000);
+        assert_eq!(clock.stamp(1_018_000, &frame), 1_020_000);
+        // The playout stopped for a while
+        assert_eq!(clock.stamp(2_000_000, &frame), 2_000_000);
+        assert_eq!(
+            clock.stamp(2_000_000, &AudioFrame::new(16000, 1, 160)),
+            2_000_000
+        );
+    }
+
+    #[test]
+    fn sync_queue_order() {
+        let mut queue = SyncQueue::new(100_000);
+        queue.push_audio(0, AudioFrame::new(48000, 1, 480));
+        queue.push_audio(10_000, AudioFrame::new(48000, 1, 480));
+        queue.push_video(5_000, 5_000);
+        queue.push_audio(20_000, AudioFrame::new(48000, 1, 480));
+
+        // The audio frames after the last video frame wait for the video
+        assert_eq!(timestamps(&mut queue, 30_000, (false, false)), [0, -5_000]);
+        assert_eq!(queue.deadline_us(), Some(110_000));
+        assert_eq!(
+            timestamps(&mut queue, 120_000, (false, false)),
+            [10_000, 20_000]
+        );
+
+        queue.push_video(30_000, 30_000);
+        assert!(timestamps(&mut queue, 30_000, (false, false)).is_empty());
+        assert_eq!(timestamps(&mut queue, 30_000, (true, false)), [-30_000]);
+        assert!(queue.is_empty());
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::video_frame::new_video_frame_buffer;
+use crate::video_frame::{BoxVideoFrameBuffer, VideoFrame};
+use crate::video_source::native::NativeVideoSource;
+use crate::video_source::VideoResolution;
+use crate::{RtcError, RtcErrorType};
+use cxx::UniquePtr;
+use std::sync::Arc;
+use webrtc_sys::video_capture as sys_vc;
+
+/// A resolution and the maximum frame rate a camera supports for it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CameraFormat {
+    pub width: u32,
+    pub height: u32,
+    pub max_fps: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CameraDevice {
+    /// Unique id of the device, used to open it
+    pub id: String,
+    pub name: String,
+    pub formats: Vec<CameraFormat>,
+}
+
+impl CameraDevice {
+    /// List the cameras connected to this machine.
+    /// Uses AVFoundation on Apple platforms, DirectShow on Windows and V4L2 on Linux,
+    /// the list is always empty on Android
+    pub fn list() -> Vec<CameraDevice> {
+        sys_vc::ffi::list_camera_devices()
+            .into_iter()
+            .map(|device| CameraDevice {
+                id: device.id,
+                name: device.name,
+                formats: device
+                    .formats
+                    .into_iter()
+                    .map(|format| CameraFormat {
+                        width: format.width,
+                        height: format.height,
+                        max_fps: format.max_fps,
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+/// Captures a camera and forwards the frames to a NativeVideoSource, the
+/// capture stops when the capturer is dropped.
+pub struct CameraCapturer {
+    source: NativeVideoSource,
+    sys_handle: UniquePtr<sys_vc::ffi::CameraCapturer>,
+    started: bool,
+}
+
+impl CameraCapturer {
+    /// Open the camera with the given id and start capturing with the format
+    /// closest to the requested resolution and frame rate
+    pub fn new(device_id: &str, resolution: VideoResolution, fps: u32) -> Result<Self, RtcError> {
+        let source = NativeVideoSource::new(resolution.clone());
+        let observer = Arc::new(FrameObserver {
+            source: source.clone(),
+        });
+
+        let mut sys_handle = sys_vc::ffi::new_camera_capturer(
+            Box::new(sys_vc::CameraCapturerObserverWrapper::new(observer)),
+            device_id.to_owned(),
+        );
+
+        if sys_handle.is_null() {
+            return Err(capture_error(&format!(
+                "failed to open the camera {}",
+                device_id
+            )));
+        }
+
+        let format = sys_vc::ffi::CameraFormat {
+            width: resolution.width,
+            height: resolution.height,
+            max_fps: fps.max(1),
+        };
+
+        if !sys_handle.pin_mut().start(format) {
+            return Err(capture_error(&format!(
+                "failed to start the camera {} with {}x{}@{}",
+                device_id, format.width, format.height, format.max_fps
+            )));
+        }
+
+        Ok(Self {
+            source,
+            sys_handle,
+            started: true,
+        })
+    }
+
+    pub fn video_source(&self) -> NativeVideoSource {
+        self.source.clone()
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        !self.started
+    }
+
+    pub fn stop(&mut self) {
+        if self.started {
+            self.started = false;
+            self.sys_handle.pin_mut().stop();
+        }
+    }
+}
+
+impl Drop for CameraCapturer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+struct FrameObserver {
+    source: NativeVideoSource,
+}
+
+impl sys_vc::CameraCapturerObserver for FrameObserver {
+    fn on_frame(&self, frame: UniquePtr<webrtc_sys::video_frame::ffi::VideoFrame>) {
+        let buffer: BoxVideoFrameBuffer =
+            new_video_frame_buffer(unsafe { frame.video_frame_buffer() });
+        self.source.capture_frame(&VideoFrame {
+            rotation: frame.rotation().into(),
+            timestamp_us: frame.timestamp_us(),
+            rtp_timestamp: 0,
+            ntp_time_ms: 0,
+            buffer,
+        });
+    }
+}
+
+fn capture_error(message: &str) -> RtcError {
+    RtcError {
+        error_type: RtcErrorType::Internal,
+        message: message.to_owned(),
+    }
+}
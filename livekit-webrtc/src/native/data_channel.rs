@@ -16,12 +16,16 @@ use crate::data_channel::{
     DataBuffer, DataChannelError, DataChannelInit, DataState, OnBufferedAmountChange, OnMessage,
     OnStateChange,
 };
+use crate::rtp_parameters::Priority;
 use cxx::SharedPtr;
 use parking_lot::Mutex;
 use std::str;
 use std::sync::Arc;
 use webrtc_sys::data_channel as sys_dc;
 
+// Same as kMaxQueuedSendDataBytes, webrtc closes the data channel when it is exceeded
+pub const MAX_BUFFERED_AMOUNT: u64 = 16 * 1024 * 1024;
+
 impl From<sys_dc::ffi::DataState> for DataState {
     fn from(value: sys_dc::ffi::DataState) -> Self {
         match value {
@@ -34,6 +38,17 @@ impl From<sys_dc::ffi::DataState> for DataState {
     }
 }
 
+impl From<Priority> for sys_dc::ffi::Priority {
+    fn from(value: Priority) -> Self {
+        match value {
+            Priority::VeryLow => Self::VeryLow,
+            Priority::Low => Self::Low,
+            Priority::Medium => Self::Medium,
+            Priority::High => Self::High,
+        }
+    }
+}
+
 impl From<DataChannelInit> for sys_dc::ffi::DataChannelInit {
     fn from(value: DataChannelInit) -> Self {
         Self {
@@ -44,8 +59,8 @@ impl From<DataChannelInit> for sys_dc::ffi::DataChannelInit {
             max_retransmits: value.max_retransmits.unwrap_or_default(),
             protocol: value.protocol,
             id: value.id,
-            has_priority: false,
-            priority: sys_dc::ffi::Priority::Medium,
+            has_priority: value.priority.is_some(),
+            priority: value.priority.unwrap_or(Priority::Low).into(),
             negotiated: value.negotiated,
         }
     }
@@ -75,6 +90,10 @@ impl DataChannel {
             str::from_utf8(data)?;
         }
 
+        if self.buffered_amount() + data.len() as u64 > MAX_BUFFERED_AMOUNT {
+            return Err(DataChannelError::BufferFull);
+        }
+
         let buffer = sys_dc::ffi::DataBuffer {
             ptr: data.as_ptr(),
             len: data.len(),
@@ -99,6 +118,10 @@ impl DataChannel {
         self.sys_handle.state().into()
     }
 
+    pub fn buffered_amount(&self) -> u64 {
+        self.sys_handle.buffered_amount()
+    }
+
     pub fn close(&self) {
         self.sys_handle.close();
     }
@@ -0,0 +1,90 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::rtp_receiver::RtpReceiver;
+use cxx::SharedPtr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::sync::mpsc;
+use tokio_stream::Stream;
+use webrtc_sys::encoded_frame_tap as sys_eft;
+
+/// An audio frame as it was received, before being decoded
+#[derive(Debug, Clone)]
+pub struct EncodedAudioFrame {
+    pub rtp_timestamp: u32, // Clock rate of the codec (48kHz for opus)
+    pub payload_type: u8,   // See the negotiated codecs to find the codec
+    pub data: Vec<u8>,
+}
+
+/// Receive the encoded frames of an audio receiver, e.g. to forward them without
+/// re-encoding. The frames are still decoded as usual.
+/// This uses the frame transformer of the receiver, so it can't be used on
+/// receivers with e2ee enabled.
+pub struct EncodedAudioStream {
+    sys_handle: SharedPtr<sys_eft::ffi::EncodedFrameTap>,
+    frame_rx: mpsc::UnboundedReceiver<EncodedAudioFrame>,
+}
+
+impl EncodedAudioStream {
+    pub fn new(receiver: RtpReceiver) -> Self {
+        let (frame_tx, frame_rx) = mpsc::unbounded_channel();
+        let observer = Arc::new(TapObserver { frame_tx });
+        let sys_handle = sys_eft::ffi::new_encoded_frame_tap_for_rtp_receiver(
+            receiver.handle.sys_handle,
+            Box::new(sys_eft::EncodedFrameTapObserverWrapper::new(observer)),
+        );
+
+        Self {
+            sys_handle,
+            frame_rx,
+        }
+    }
+
+    pub fn close(&mut self) {
+        self.sys_handle.stop();
+        self.frame_rx.close();
+    }
+}
+
+impl Drop for EncodedAudioStream {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+impl Stream for EncodedAudioStream {
+    type Item = EncodedAudioFrame;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.frame_rx.poll_recv(cx)
+    }
+}
+
+struct TapObserver {
+    frame_tx: mpsc::UnboundedSender<EncodedAudioFrame>,
+}
+
+impl sys_eft::EncodedFrameTapObserver for TapObserver {
+    fn on_encoded_frame(&self, _info: sys_eft::ffi::EncodedFrameInfo, _data: &[u8]) {}
+
+    fn on_encoded_audio_frame(&self, info: sys_eft::ffi::EncodedAudioFrameInfo, data: &[u8]) {
+        let _ = self.frame_tx.send(EncodedAudioFrame {
+            rtp_timestamp: info.rtp_timestamp,
+            payload_type: info.payload_type,
+            data: data.to_vec(),
+        });
+    }
+}
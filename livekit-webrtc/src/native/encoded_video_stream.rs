@@ -0,0 +1,130 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::rtp_receiver::RtpReceiver;
+use cxx::SharedPtr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::sync::mpsc;
+use tokio_stream::Stream;
+use webrtc_sys::encoded_frame_tap as sys_eft;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodedVideoCodec {
+    Generic,
+    VP8,
+    VP9,
+    AV1,
+    H264,
+}
+
+/// A video frame as it was received, before being decoded. Also used to send already
+/// encoded frames, see NativeVideoSource::capture_encoded_frame
+#[derive(Debug, Clone)]
+pub struct EncodedVideoFrame {
+    pub codec: EncodedVideoCodec,
+    pub rtp_timestamp: u32, // 90kHz clock
+    pub key_frame: bool,
+    pub width: u32, // Only known on key frames, 0 otherwise
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+/// Receive the encoded frames of a video receiver, e.g. to record them without
+/// re-encoding. The frames are still decoded as usual.
+/// This uses the frame transformer of the receiver, so it can't be used on
+/// receivers with e2ee enabled.
+pub struct EncodedVideoStream {
+    sys_handle: SharedPtr<sys_eft::ffi::EncodedFrameTap>,
+    frame_rx: mpsc::UnboundedReceiver<EncodedVideoFrame>,
+}
+
+impl EncodedVideoStream {
+    pub fn new(receiver: RtpReceiver) -> Self {
+        let (frame_tx, frame_rx) = mpsc::unbounded_channel();
+        let observer = Arc::new(TapObserver { frame_tx });
+        let sys_handle = sys_eft::ffi::new_encoded_frame_tap_for_rtp_receiver(
+            receiver.handle.sys_handle,
+            Box::new(sys_eft::EncodedFrameTapObserverWrapper::new(observer)),
+        );
+
+        Self {
+            sys_handle,
+            frame_rx,
+        }
+    }
+
+    pub fn close(&mut self) {
+        self.sys_handle.stop();
+        self.frame_rx.close();
+    }
+}
+
+impl Drop for EncodedVideoStream {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+impl Stream for EncodedVideoStream {
+    type Item = EncodedVideoFrame;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.frame_rx.poll_recv(cx)
+    }
+}
+
+struct TapObserver {
+    frame_tx: mpsc::UnboundedSender<EncodedVideoFrame>,
+}
+
+impl sys_eft::EncodedFrameTapObserver for TapObserver {
+    fn on_encoded_frame(&self, info: sys_eft::ffi::EncodedFrameInfo, data: &[u8]) {
+        let _ = self.frame_tx.send(EncodedVideoFrame {
+            codec: info.codec.into(),
+            rtp_timestamp: info.rtp_timestamp,
+            key_frame: info.key_frame,
+            width: info.width as u32,
+            height: info.height as u32,
+            data: data.to_vec(),
+        });
+    }
+
+    fn on_encoded_audio_frame(&self, _info: sys_eft::ffi::EncodedAudioFrameInfo, _data: &[u8]) {}
+}
+
+impl From<sys_eft::ffi::EncodedVideoCodec> for EncodedVideoCodec {
+    fn from(value: sys_eft::ffi::EncodedVideoCodec) -> Self {
+        match value {
+            sys_eft::ffi::EncodedVideoCodec::VP8 => Self::VP8,
+            sys_eft::ffi::EncodedVideoCodec::VP9 => Self::VP9,
+            sys_eft::ffi::EncodedVideoCodec::AV1 => Self::AV1,
+            sys_eft::ffi::EncodedVideoCodec::H264 => Self::H264,
+            _ => Self::Generic,
+        }
+    }
+}
+
+impl From<EncodedVideoCodec> for sys_eft::ffi::EncodedVideoCodec {
+    fn from(value: EncodedVideoCodec) -> Self {
+        match value {
+            EncodedVideoCodec::Generic => Self::Generic,
+            EncodedVideoCodec::VP8 => Self::VP8,
+            EncodedVideoCodec::VP9 => Self::VP9,
+            EncodedVideoCodec::AV1 => Self::AV1,
+            EncodedVideoCodec::H264 => Self::H264,
+        }
+    }
+}
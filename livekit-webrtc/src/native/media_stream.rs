@@ -34,7 +34,7 @@ impl MediaStream {
             .get_audio_tracks()
             .into_iter()
             .map(|t| audio_track::RtcAudioTrack {
-                handle: RtcAudioTrack { sys_handle: t.ptr },
+                handle: RtcAudioTrack::new(t.ptr),
             })
             .collect()
     }
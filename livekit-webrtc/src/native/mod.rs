@@ -14,11 +14,20 @@
 
 #[cfg(target_os = "android")]
 pub mod android;
+pub mod apm;
+pub mod audio_device;
+pub mod audio_loopback;
+pub mod audio_mixer;
 pub mod audio_resampler;
+pub mod audio_ring_buffer;
 pub mod audio_source;
 pub mod audio_stream;
 pub mod audio_track;
+pub mod av_sync;
+pub mod camera;
 pub mod data_channel;
+pub mod encoded_audio_stream;
+pub mod encoded_video_stream;
 pub mod ice_candidate;
 pub mod media_stream;
 pub mod media_stream_track;
@@ -28,11 +37,18 @@ pub mod rtp_parameters;
 pub mod rtp_receiver;
 pub mod rtp_sender;
 pub mod rtp_transceiver;
+pub mod screen_capture;
 pub mod session_description;
+pub mod vad;
+pub mod video_conversion;
 pub mod video_frame;
+#[cfg(feature = "images")]
+mod video_image;
 pub mod video_source;
 pub mod video_stream;
 pub mod video_track;
+pub mod wav;
+pub mod y4m;
 pub mod yuv_helper;
 pub mod frame_cryptor;
 
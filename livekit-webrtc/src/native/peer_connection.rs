@@ -26,10 +26,11 @@ use crate::imp::session_description as imp_sdp;
 use crate::media_stream::MediaStream;
 use crate::media_stream_track::MediaStreamTrack;
 use crate::peer_connection::{
-    AnswerOptions, IceCandidateError, IceConnectionState, IceGatheringState, OfferOptions,
-    OnConnectionChange, OnDataChannel, OnIceCandidate, OnIceCandidateError, OnIceConnectionChange,
-    OnIceGatheringChange, OnNegotiationNeeded, OnSignalingChange, OnTrack, PeerConnectionState,
-    SignalingState, TrackEvent,
+    AnswerOptions, AudioReceiverStats, CandidateInfo, CandidatePairChange, IceCandidateError,
+    IceConnectionState, IceGatheringState, OfferOptions, OnConnectionChange, OnDataChannel,
+    OnIceCandidate, OnIceCandidateError, OnIceConnectionChange, OnIceGatheringChange,
+    OnNegotiationNeeded, OnSelectedCandidatePairChange, OnSignalingChange, OnTrack,
+    PeerConnectionState, SignalingState, TrackEvent,
 };
 use crate::peer_connection_factory::{
     ContinualGatheringPolicy, IceServer, IceTransportsType, RtcConfiguration,
@@ -38,6 +39,7 @@ use crate::rtp_receiver::RtpReceiver;
 use crate::rtp_sender::RtpSender;
 use crate::rtp_transceiver::RtpTransceiver;
 use crate::rtp_transceiver::RtpTransceiverInit;
+use crate::stats::{self, RtcStats};
 use crate::MediaType;
 use crate::RtcErrorType;
 use crate::{session_description::SessionDescription, RtcError};
@@ -46,6 +48,7 @@ use parking_lot::Mutex;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use tokio::sync::oneshot;
+use webrtc_sys::candidate as sys_candidate;
 use webrtc_sys::data_channel as sys_dc;
 use webrtc_sys::jsep as sys_jsep;
 use webrtc_sys::peer_connection as sys_pc;
@@ -135,6 +138,7 @@ impl From<IceServer> for sys_pc::ffi::IceServer {
             urls: value.urls,
             username: value.username,
             password: value.password,
+            hostname: value.hostname,
         }
     }
 }
@@ -173,6 +177,20 @@ impl From<RtcConfiguration> for sys_pc::ffi::RtcConfiguration {
     }
 }
 
+impl From<sys_pc::ffi::AudioReceiverStats> for AudioReceiverStats {
+    fn from(value: sys_pc::ffi::AudioReceiverStats) -> Self {
+        Self {
+            packets_received: value.packets_received,
+            packets_lost: value.packets_lost,
+            fec_packets_received: value.fec_packets_received,
+            fec_packets_discarded: value.fec_packets_discarded,
+            total_samples_received: value.total_samples_received,
+            concealed_samples: value.concealed_samples,
+            concealment_events: value.concealment_events,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct PeerConnection {
     observer: Arc<PeerObserver>,
@@ -320,6 +338,58 @@ impl PeerConnection {
         })?
     }
 
+    pub async fn get_audio_receiver_stats(
+        &self,
+        receiver: &RtpReceiver,
+    ) -> Result<AudioReceiverStats, RtcError> {
+        let (tx, rx) = oneshot::channel::<AudioReceiverStats>();
+        let ctx = Box::new(sys_pc::AsyncContext(Box::new(tx)));
+
+        self.sys_handle.get_audio_receiver_stats(
+            receiver.handle.sys_handle.clone(),
+            ctx,
+            |ctx, stats| {
+                let tx = ctx
+                    .0
+                    .downcast::<oneshot::Sender<AudioReceiverStats>>()
+                    .unwrap();
+
+                let _ = tx.send(stats.into());
+            },
+        );
+
+        rx.await.map_err(|_| RtcError {
+            error_type: RtcErrorType::Internal,
+            message: "get_audio_receiver_stats cancelled".to_owned(),
+        })
+    }
+
+    pub async fn get_stats(&self) -> Result<Vec<RtcStats>, RtcError> {
+        let (tx, rx) = oneshot::channel::<String>();
+        let ctx = Box::new(sys_pc::AsyncContext(Box::new(tx)));
+        self.sys_handle.get_stats(ctx, on_stats_json);
+        stats_report(rx).await
+    }
+
+    pub async fn get_sender_stats(&self, sender: &RtpSender) -> Result<Vec<RtcStats>, RtcError> {
+        let (tx, rx) = oneshot::channel::<String>();
+        let ctx = Box::new(sys_pc::AsyncContext(Box::new(tx)));
+        self.sys_handle
+            .get_sender_stats(sender.handle.sys_handle.clone(), ctx, on_stats_json);
+        stats_report(rx).await
+    }
+
+    pub async fn get_receiver_stats(
+        &self,
+        receiver: &RtpReceiver,
+    ) -> Result<Vec<RtcStats>, RtcError> {
+        let (tx, rx) = oneshot::channel::<String>();
+        let ctx = Box::new(sys_pc::AsyncContext(Box::new(tx)));
+        self.sys_handle
+            .get_receiver_stats(receiver.handle.sys_handle.clone(), ctx, on_stats_json);
+        stats_report(rx).await
+    }
+
     pub fn create_data_channel(
         &self,
         label: &str,
@@ -512,6 +582,31 @@ impl PeerConnection {
     pub fn on_track(&self, f: Option<OnTrack>) {
         *self.observer.track_handler.lock() = f;
     }
+
+    pub fn on_selected_candidate_pair_change(&self, f: Option<OnSelectedCandidatePairChange>) {
+        *self.observer.selected_candidate_pair_handler.lock() = f;
+    }
+}
+
+fn candidate_info(candidate: &SharedPtr<sys_candidate::ffi::Candidate>) -> CandidateInfo {
+    CandidateInfo {
+        candidate_type: candidate.candidate_type(),
+        protocol: candidate.protocol(),
+        address: candidate.address(),
+    }
+}
+
+fn on_stats_json(ctx: Box<sys_pc::AsyncContext>, json: String) {
+    let tx = ctx.0.downcast::<oneshot::Sender<String>>().unwrap();
+    let _ = tx.send(json);
+}
+
+async fn stats_report(rx: oneshot::Receiver<String>) -> Result<Vec<RtcStats>, RtcError> {
+    let json = rx.await.map_err(|_| RtcError {
+        error_type: RtcErrorType::Internal,
+        message: "get_stats cancelled".to_owned(),
+    })?;
+    stats::parse(&json)
 }
 
 #[derive(Default)]
@@ -525,6 +620,7 @@ pub struct PeerObserver {
     pub negotiation_needed_handler: Mutex<Option<OnNegotiationNeeded>>,
     pub signaling_change_handler: Mutex<Option<OnSignalingChange>>,
     pub track_handler: Mutex<Option<OnTrack>>,
+    pub selected_candidate_pair_handler: Mutex<Option<OnSelectedCandidatePairChange>>,
 }
 
 impl sys_pc::PeerConnectionObserver for PeerObserver {
@@ -611,10 +707,17 @@ impl sys_pc::PeerConnectionObserver for PeerObserver {
 
     fn on_ice_connection_receiving_change(&self, _receiving: bool) {}
 
-    fn on_ice_selected_candidate_pair_changed(
-        &self,
-        _event: sys_pc::ffi::CandidatePairChangeEvent,
-    ) {
+    fn on_ice_selected_candidate_pair_changed(&self, event: sys_pc::ffi::CandidatePairChangeEvent) {
+        if let Some(f) = self.selected_candidate_pair_handler.lock().as_mut() {
+            let pair = event.selected_candidate_pair;
+            f(CandidatePairChange {
+                local: candidate_info(&pair.local),
+                remote: candidate_info(&pair.remote),
+                reason: event.reason,
+                last_data_received_ms: event.last_data_received_ms,
+                estimated_disconnected_time_ms: event.estimated_disconnected_time_ms,
+            });
+        }
     }
 
     fn on_add_track(
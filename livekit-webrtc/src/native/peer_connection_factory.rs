@@ -18,6 +18,7 @@ use crate::imp::audio_track as imp_at;
 use crate::imp::peer_connection as imp_pc;
 use crate::imp::video_track as imp_vt;
 use crate::peer_connection::PeerConnection;
+use crate::peer_connection_factory::native::{HardwarePreference, VideoCodecCapability};
 use crate::peer_connection_factory::RtcConfiguration;
 use crate::rtp_parameters::RtpCapabilities;
 use crate::video_source::native::NativeVideoSource;
@@ -106,14 +107,20 @@ impl PeerConnectionFactory {
 
     pub fn create_audio_track(&self, label: &str, source: NativeAudioSource) -> RtcAudioTrack {
         RtcAudioTrack {
-            handle: imp_at::RtcAudioTrack {
-                sys_handle: self
-                    .sys_handle
+            handle: imp_at::RtcAudioTrack::new(
+                self.sys_handle
                     .create_audio_track(label.to_string(), source.handle.sys_handle()),
-            },
+            ),
         }
     }
 
+    pub fn audio_device_manager(
+        &self,
+    ) -> Option<SharedPtr<webrtc_sys::audio_device_manager::ffi::AudioDeviceManager>> {
+        let sys_handle = self.sys_handle.audio_device_manager();
+        (!sys_handle.is_null()).then_some(sys_handle)
+    }
+
     pub fn get_rtp_sender_capabilities(&self, media_type: MediaType) -> RtpCapabilities {
         self.sys_handle
             .rtp_sender_capabilities(media_type.into())
@@ -125,6 +132,30 @@ impl PeerConnectionFactory {
             .rtp_receiver_capabilities(media_type.into())
             .into()
     }
+
+    pub fn video_codec_capabilities(&self) -> Vec<VideoCodecCapability> {
+        self.sys_handle
+            .video_codec_capabilities()
+            .into_iter()
+            .map(|c| VideoCodecCapability {
+                codec: c.codec,
+                hardware_encoder: c.hardware_encoder,
+                software_encoder: c.software_encoder,
+                hardware_decoder: c.hardware_decoder,
+                software_decoder: c.software_decoder,
+            })
+            .collect()
+    }
+
+    pub fn set_hardware_encoder_preference(&self, codec: &str, preference: HardwarePreference) {
+        let preference = match preference {
+            HardwarePreference::Prefer => sys_pcf::ffi::HardwarePreference::Prefer,
+            HardwarePreference::Require => sys_pcf::ffi::HardwarePreference::Require,
+            HardwarePreference::Disable => sys_pcf::ffi::HardwarePreference::Disable,
+        };
+        self.sys_handle
+            .set_hardware_encoder_preference(codec.to_owned(), preference);
+    }
 }
 
 #[cfg(test)]
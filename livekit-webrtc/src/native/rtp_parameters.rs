@@ -28,6 +28,27 @@ impl From<sys_webrtc::ffi::Priority> for Priority {
     }
 }
 
+// Share of the available bitrate given to the encoding, same mapping as the browsers
+fn bitrate_priority(priority: Priority) -> f64 {
+    match priority {
+        Priority::VeryLow => sys_rp::DEFAULT_BITRATE_PRIORITY / 2.0,
+        Priority::Low => sys_rp::DEFAULT_BITRATE_PRIORITY,
+        Priority::Medium => sys_rp::DEFAULT_BITRATE_PRIORITY * 2.0,
+        Priority::High => sys_rp::DEFAULT_BITRATE_PRIORITY * 4.0,
+    }
+}
+
+impl From<DegradationPreference> for sys_rp::ffi::DegradationPreference {
+    fn from(value: DegradationPreference) -> Self {
+        match value {
+            DegradationPreference::Disabled => Self::Disabled,
+            DegradationPreference::MaintainFramerate => Self::MaintainFramerate,
+            DegradationPreference::MaintainResolution => Self::MaintainResolution,
+            DegradationPreference::Balanced => Self::Balanced,
+        }
+    }
+}
+
 impl From<sys_rp::ffi::RtpExtension> for RtpHeaderExtensionParameters {
     fn from(value: sys_rp::ffi::RtpExtension) -> Self {
         Self {
@@ -85,10 +106,50 @@ impl From<sys_rp::ffi::RtpEncodingParameters> for RtpEncodingParameters {
             scale_resolution_down_by: value
                 .has_scale_resolution_down_by
                 .then_some(value.scale_resolution_down_by),
+            scalability_mode: value.has_scalability_mode.then_some(value.scalability_mode),
+        }
+    }
+}
+
+impl From<&sys_rp::ffi::RtpEncodingParameters> for RtpEncodingParameters {
+    fn from(value: &sys_rp::ffi::RtpEncodingParameters) -> Self {
+        Self {
+            active: value.active,
+            max_bitrate: value
+                .has_max_bitrate_bps
+                .then_some(value.max_bitrate_bps as u64),
+            max_framerate: value.has_max_framerate.then_some(value.max_framerate),
+            priority: value.network_priority.into(),
+            rid: value.rid.clone(),
+            scale_resolution_down_by: value
+                .has_scale_resolution_down_by
+                .then_some(value.scale_resolution_down_by),
+            scalability_mode: value
+                .has_scalability_mode
+                .then(|| value.scalability_mode.clone()),
         }
     }
 }
 
+/// Apply the writable fields of an encoding to the native one, its read-only fields
+/// (rid, ssrc, ...) are kept so that WebRTC accepts the change
+pub(crate) fn update_native_encoding(
+    native: &mut sys_rp::ffi::RtpEncodingParameters,
+    encoding: RtpEncodingParameters,
+) {
+    native.active = encoding.active;
+    native.has_max_bitrate_bps = encoding.max_bitrate.is_some();
+    native.max_bitrate_bps = encoding.max_bitrate.unwrap_or_default() as i32;
+    native.has_max_framerate = encoding.max_framerate.is_some();
+    native.max_framerate = encoding.max_framerate.unwrap_or_default();
+    native.network_priority = encoding.priority.into();
+    native.bitrate_priority = bitrate_priority(encoding.priority);
+    native.has_scale_resolution_down_by = encoding.scale_resolution_down_by.is_some();
+    native.scale_resolution_down_by = encoding.scale_resolution_down_by.unwrap_or_default();
+    native.has_scalability_mode = encoding.scalability_mode.is_some();
+    native.scalability_mode = encoding.scalability_mode.unwrap_or_default();
+}
+
 impl From<sys_rp::ffi::RtpCodecCapability> for RtpCodecCapability {
     fn from(value: sys_rp::ffi::RtpCodecCapability) -> Self {
         Self {
@@ -226,13 +287,13 @@ impl From<RtpEncodingParameters> for sys_rp::ffi::RtpEncodingParameters {
             has_scale_resolution_down_by: value.scale_resolution_down_by.is_some(),
             scale_resolution_down_by: value.scale_resolution_down_by.unwrap_or_default(),
             adaptive_ptime: false,
-            bitrate_priority: sys_rp::DEFAULT_BITRATE_PRIORITY,
+            bitrate_priority: bitrate_priority(value.priority),
             has_min_bitrate_bps: false,
             min_bitrate_bps: 0,
             has_num_temporal_layers: false,
             num_temporal_layers: 0,
-            has_scalability_mode: false,
-            scalability_mode: "".to_string(),
+            has_scalability_mode: value.scalability_mode.is_some(),
+            scalability_mode: value.scalability_mode.unwrap_or_default(),
             has_ssrc: false,
             ssrc: 0,
         }
@@ -36,4 +36,9 @@ impl RtpReceiver {
     pub fn parameters(&self) -> RtpParameters {
         self.sys_handle.get_parameters().into()
     }
+
+    pub fn set_jitter_buffer_minimum_delay(&self, delay_seconds: Option<f64>) {
+        self.sys_handle
+            .set_jitter_buffer_minimum_delay(delay_seconds.is_some(), delay_seconds.unwrap_or(0.0))
+    }
 }
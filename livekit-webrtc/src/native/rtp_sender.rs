@@ -13,8 +13,10 @@
 // limitations under the License.
 
 use super::media_stream_track::new_media_stream_track;
+use super::rtp_parameters::update_native_encoding;
 use crate::media_stream_track::MediaStreamTrack;
-use crate::{rtp_parameters::RtpParameters, RtcError, RtcErrorType};
+use crate::rtp_parameters::{DegradationPreference, RtpEncodingParameters, RtpParameters};
+use crate::{RtcError, RtcErrorType};
 use cxx::SharedPtr;
 use webrtc_sys::rtc_error as sys_err;
 use webrtc_sys::rtp_sender as sys_rs;
@@ -57,4 +59,52 @@ impl RtpSender {
             .set_parameters(parameters.into())
             .map_err(|e| unsafe { sys_err::ffi::RtcError::from(e.what()).into() })
     }
+
+    pub fn set_encodings_active(
+        &self,
+        active: &dyn Fn(&str) -> Option<bool>,
+    ) -> Result<(), RtcError> {
+        // Modify the native parameters in place, WebRTC rejects any change
+        // to the read-only fields (transaction id, ssrcs, ...)
+        let mut parameters = self.sys_handle.get_parameters();
+        for encoding in &mut parameters.encodings {
+            if let Some(active) = active(&encoding.rid) {
+                encoding.active = active;
+            }
+        }
+
+        self.sys_handle
+            .set_parameters(parameters)
+            .map_err(|e| unsafe { sys_err::ffi::RtcError::from(e.what()).into() })
+    }
+
+    pub fn update_encodings(
+        &self,
+        update: &mut dyn FnMut(&mut [RtpEncodingParameters]),
+    ) -> Result<(), RtcError> {
+        let mut parameters = self.sys_handle.get_parameters();
+        let mut encodings: Vec<RtpEncodingParameters> =
+            parameters.encodings.iter().map(Into::into).collect();
+        update(&mut encodings);
+        for (native, encoding) in parameters.encodings.iter_mut().zip(encodings) {
+            update_native_encoding(native, encoding);
+        }
+
+        self.sys_handle
+            .set_parameters(parameters)
+            .map_err(|e| unsafe { sys_err::ffi::RtcError::from(e.what()).into() })
+    }
+
+    pub fn set_degradation_preference(
+        &self,
+        preference: DegradationPreference,
+    ) -> Result<(), RtcError> {
+        let mut parameters = self.sys_handle.get_parameters();
+        parameters.has_degradation_preference = true;
+        parameters.degradation_preference = preference.into();
+
+        self.sys_handle
+            .set_parameters(parameters)
+            .map_err(|e| unsafe { sys_err::ffi::RtcError::from(e.what()).into() })
+    }
 }
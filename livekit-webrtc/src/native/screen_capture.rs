@@ -0,0 +1,336 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::audio_source::native::NativeAudioSource;
+use crate::audio_source::AudioSourceOptions;
+use crate::video_frame::native::I420BufferExt;
+use crate::video_frame::{I420Buffer, VideoFrame, VideoRotation};
+use crate::video_source::native::NativeVideoSource;
+use crate::video_source::VideoResolution;
+use crate::{RtcError, RtcErrorType};
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use webrtc_sys::desktop_capturer as sys_dc;
+use webrtc_sys::system_audio_capturer as sys_sac;
+use webrtc_sys::webrtc as sys_rtc;
+
+use super::yuv_helper;
+
+const LOOPBACK_SAMPLE_RATE: u32 = 48000;
+const LOOPBACK_NUM_CHANNELS: u32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScreenCaptureKind {
+    Display,
+    Window,
+}
+
+/// A display or window that can be captured
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScreenCaptureSource {
+    pub kind: ScreenCaptureKind,
+    pub id: u64,
+    pub title: String,
+}
+
+impl ScreenCaptureSource {
+    pub fn displays() -> Vec<ScreenCaptureSource> {
+        list_sources(ScreenCaptureKind::Display)
+    }
+
+    pub fn windows() -> Vec<ScreenCaptureSource> {
+        list_sources(ScreenCaptureKind::Window)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ScreenCaptureOptions {
+    pub capture_cursor: bool,
+    pub max_fps: u32,
+}
+
+impl Default for ScreenCaptureOptions {
+    fn default() -> Self {
+        Self {
+            capture_cursor: true,
+            max_fps: 15,
+        }
+    }
+}
+
+/// Captures a display or a window on a dedicated thread and forwards the
+/// frames to a NativeVideoSource.
+/// Use a ScreenAudioCapturer to also share the audio played by the system.
+pub struct ScreenCapturer {
+    source: NativeVideoSource,
+    stopped: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ScreenCapturer {
+    pub fn new(
+        capture_source: &ScreenCaptureSource,
+        options: ScreenCaptureOptions,
+    ) -> Result<Self, RtcError> {
+        let source = NativeVideoSource::new(VideoResolution::default());
+        let stopped = Arc::new(AtomicBool::new(false));
+        let (ready_tx, ready_rx) = mpsc::channel();
+
+        let thread = thread::spawn({
+            let capture_source = capture_source.clone();
+            let source = source.clone();
+            let stopped = stopped.clone();
+            move || {
+                let observer = Arc::new(FrameObserver {
+                    source,
+                    stopped: stopped.clone(),
+                });
+
+                let mut capturer = sys_dc::ffi::new_desktop_capturer(
+                    Box::new(sys_dc::DesktopCapturerObserverWrapper::new(observer)),
+                    sys_options(capture_source.kind, options.capture_cursor),
+                );
+
+                if capturer.is_null() {
+                    let _ = ready_tx.send(Err(capture_error("failed to create the capturer")));
+                    return;
+                }
+
+                if !capturer.pin_mut().select_source(capture_source.id) {
+                    let _ = ready_tx.send(Err(capture_error("failed to select the source")));
+                    return;
+                }
+
+                capturer.pin_mut().start();
+                let _ = ready_tx.send(Ok(()));
+
+                let interval = Duration::from_secs_f64(1.0 / options.max_fps.max(1) as f64);
+                while !stopped.load(Ordering::Acquire) {
+                    let start = Instant::now();
+                    capturer.pin_mut().capture_frame();
+                    if let Some(remaining) = interval.checked_sub(start.elapsed()) {
+                        thread::sleep(remaining);
+                    }
+                }
+            }
+        });
+
+        match ready_rx.recv() {
+            Ok(Ok(())) => Ok(Self {
+                source,
+                stopped,
+                thread: Some(thread),
+            }),
+            Ok(Err(err)) => {
+                let _ = thread.join();
+                Err(err)
+            }
+            Err(_) => {
+                let _ = thread.join();
+                Err(capture_error("the capture thread exited unexpectedly"))
+            }
+        }
+    }
+
+    pub fn video_source(&self) -> NativeVideoSource {
+        self.source.clone()
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.stopped.load(Ordering::Acquire)
+    }
+
+    pub fn stop(&mut self) {
+        self.stopped.store(true, Ordering::Release);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for ScreenCapturer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+struct FrameObserver {
+    source: NativeVideoSource,
+    stopped: Arc<AtomicBool>,
+}
+
+impl sys_dc::DesktopCapturerObserver for FrameObserver {
+    fn on_capture_result(
+        &self,
+        result: sys_dc::ffi::CaptureResult,
+        frame: cxx::UniquePtr<sys_dc::ffi::DesktopFrame>,
+    ) {
+        match result {
+            sys_dc::ffi::CaptureResult::Success => {}
+            sys_dc::ffi::CaptureResult::ErrorPermanent => {
+                log::error!("screen capture failed permanently, stopping");
+                self.stopped.store(true, Ordering::Release);
+                return;
+            }
+            _ => return,
+        }
+
+        let Some(frame) = frame.as_ref() else {
+            return;
+        };
+
+        let (width, height) = (frame.width(), frame.height());
+        let mut buffer = I420Buffer::new(width as u32, height as u32);
+        let (stride_y, stride_u, stride_v) = buffer.strides();
+        let (data_y, data_u, data_v) = buffer.data_mut();
+
+        // DesktopFrames are BGRA in memory, which is libyuv's ARGB
+        yuv_helper::argb_to_i420(
+            frame.data(),
+            frame.stride() as u32,
+            data_y,
+            stride_y,
+            data_u,
+            stride_u,
+            data_v,
+            stride_v,
+            width,
+            height,
+        );
+
+        self.source.capture_frame(&VideoFrame {
+            rotation: VideoRotation::VideoRotation0,
+            timestamp_us: sys_rtc::ffi::time_micros(),
+            rtp_timestamp: 0,
+            ntp_time_ms: 0,
+            buffer,
+        });
+    }
+}
+
+/// Records the audio played on the default output device (audio loopback) and forwards it to
+/// a NativeAudioSource. Only supported on Windows, new fails on the other platforms.
+pub struct ScreenAudioCapturer {
+    source: NativeAudioSource,
+    _sys_handle: cxx::UniquePtr<sys_sac::ffi::SystemAudioCapturer>,
+}
+
+impl ScreenAudioCapturer {
+    pub fn new() -> Result<Self, RtcError> {
+        let source = NativeAudioSource::new(
+            AudioSourceOptions::default(),
+            LOOPBACK_SAMPLE_RATE,
+            LOOPBACK_NUM_CHANNELS,
+        );
+        let observer = Arc::new(LoopbackObserver {
+            source: source.clone(),
+            buffer: Default::default(),
+        });
+
+        let mut sys_handle = sys_sac::ffi::new_system_audio_capturer(
+            Box::new(sys_sac::SystemAudioObserverWrapper::new(observer)),
+            LOOPBACK_SAMPLE_RATE,
+            LOOPBACK_NUM_CHANNELS,
+        );
+
+        if !sys_handle.pin_mut().start() {
+            return Err(capture_error("failed to start the audio loopback"));
+        }
+
+        Ok(Self {
+            source,
+            _sys_handle: sys_handle,
+        })
+    }
+
+    pub fn audio_source(&self) -> NativeAudioSource {
+        self.source.clone()
+    }
+}
+
+struct LoopbackObserver {
+    source: NativeAudioSource,
+    buffer: Mutex<Vec<i16>>,
+}
+
+impl sys_sac::SystemAudioObserver for LoopbackObserver {
+    fn on_data(&self, data: &[i16]) {
+        // The loopback packets don't have a fixed size, the source expects 10ms frames
+        let samples_10ms = (LOOPBACK_SAMPLE_RATE / 100 * LOOPBACK_NUM_CHANNELS) as usize;
+        let mut buffer = self.buffer.lock();
+        buffer.extend_from_slice(data);
+
+        let mut chunks = buffer.chunks_exact(samples_10ms);
+        for chunk in &mut chunks {
+            self.source.handle.capture_10ms(chunk);
+        }
+
+        let consumed = buffer.len() - chunks.remainder().len();
+        buffer.drain(..consumed);
+    }
+}
+
+struct NoopObserver;
+
+impl sys_dc::DesktopCapturerObserver for NoopObserver {
+    fn on_capture_result(
+        &self,
+        _: sys_dc::ffi::CaptureResult,
+        _: cxx::UniquePtr<sys_dc::ffi::DesktopFrame>,
+    ) {
+    }
+}
+
+fn list_sources(kind: ScreenCaptureKind) -> Vec<ScreenCaptureSource> {
+    let capturer = sys_dc::ffi::new_desktop_capturer(
+        Box::new(sys_dc::DesktopCapturerObserverWrapper::new(Arc::new(
+            NoopObserver,
+        ))),
+        sys_options(kind, false),
+    );
+
+    let Some(capturer) = capturer.as_ref() else {
+        return Vec::new();
+    };
+
+    capturer
+        .get_source_list()
+        .into_iter()
+        .map(|source| ScreenCaptureSource {
+            kind,
+            id: source.id,
+            title: source.title,
+        })
+        .collect()
+}
+
+fn sys_options(
+    kind: ScreenCaptureKind,
+    capture_cursor: bool,
+) -> sys_dc::ffi::DesktopCapturerOptions {
+    sys_dc::ffi::DesktopCapturerOptions {
+        window_capturer: kind == ScreenCaptureKind::Window,
+        capture_cursor,
+    }
+}
+
+fn capture_error(message: &str) -> RtcError {
+    RtcError {
+        error_type: RtcErrorType::Internal,
+        message: message.to_owned(),
+    }
+}
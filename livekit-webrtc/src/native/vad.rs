@@ -0,0 +1,266 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::audio_frame::AudioFrame;
+use crate::audio_source::native::AudioProcessor;
+use crate::audio_stream::native::NativeAudioStream;
+use crate::{RtcError, RtcErrorType};
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+use webrtc_sys::vad as sys_vad;
+
+const SUPPORTED_SAMPLE_RATES: &[u32] = &[8000, 16000, 32000, 48000];
+
+/// How aggressively non-speech is filtered out, higher modes report less false positives
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum VadMode {
+    #[default]
+    Quality = 0,
+    LowBitrate = 1,
+    Aggressive = 2,
+    VeryAggressive = 3,
+}
+
+#[derive(Debug, Clone)]
+pub struct VadOptions {
+    pub mode: VadMode,
+    // Duration of speech before SpeechStarted is emitted
+    pub min_speech_ms: u32,
+    // Duration of silence before SpeechEnded is emitted
+    pub min_silence_ms: u32,
+}
+
+impl Default for VadOptions {
+    fn default() -> Self {
+        Self {
+            mode: VadMode::default(),
+            min_speech_ms: 60,
+            min_silence_ms: 300,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VadEvent {
+    SpeechStarted,
+    SpeechEnded,
+}
+
+/// Detects the speech on AudioFrames using the WebRTC VAD. Frames of any size and channels
+/// are accepted, the sample rate must be 8, 16, 32 or 48kHz.
+pub struct VoiceActivityDetector {
+    sys_handle: cxx::UniquePtr<sys_vad::ffi::Vad>,
+    state: SpeechState,
+    pending: Vec<i16>, // Mono samples waiting for a complete 10ms chunk
+    pending_rate: u32,
+}
+
+impl VoiceActivityDetector {
+    pub fn new(options: VadOptions) -> Result<Self, RtcError> {
+        let sys_handle = sys_vad::ffi::create_vad(options.mode as i32);
+        if sys_handle.is_null() {
+            return Err(RtcError {
+                error_type: RtcErrorType::Internal,
+                message: "failed to create the VAD".to_owned(),
+            });
+        }
+
+        Ok(Self {
+            sys_handle,
+            state: SpeechState::new(options),
+            pending: Vec::new(),
+            pending_rate: 0,
+        })
+    }
+
+    pub fn is_speaking(&self) -> bool {
+        self.state.speaking
+    }
+
+    /// Analyze the frame, returns the last transition it caused
+    pub fn process(&mut self, frame: &AudioFrame<'_>) -> Result<Option<VadEvent>, RtcError> {
+        if !SUPPORTED_SAMPLE_RATES.contains(&frame.sample_rate) {
+            return Err(RtcError {
+                error_type: RtcErrorType::InvalidState,
+                message: format!("unsupported sample rate {}", frame.sample_rate),
+            });
+        }
+
+        if frame.sample_rate != self.pending_rate {
+            self.pending.clear();
+            self.pending_rate = frame.sample_rate;
+        }
+
+        let num_channels = frame.num_channels.max(1) as usize;
+        self.pending
+            .extend(frame.data.chunks_exact(num_channels).map(|samples| {
+                (samples.iter().map(|s| *s as i32).sum::<i32>() / num_channels as i32) as i16
+            }));
+
+        let chunk_len = (frame.sample_rate / 100) as usize;
+        let mut event = None;
+        let mut read = 0;
+        while self.pending.len() - read >= chunk_len {
+            let chunk = &self.pending[read..read + chunk_len];
+            read += chunk_len;
+
+            let res = self
+                .sys_handle
+                .pin_mut()
+                .process(frame.sample_rate as i32, chunk);
+            if res < 0 {
+                self.pending.drain(..read);
+                return Err(RtcError {
+                    error_type: RtcErrorType::Internal,
+                    message: "the VAD failed to process the frame".to_owned(),
+                });
+            }
+
+            if let Some(e) = self.state.update(res == 1, 10) {
+                event = Some(e);
+            }
+        }
+
+        self.pending.drain(..read);
+        Ok(event)
+    }
+
+    /// Process the frames of a track before they are encoded, add the returned processor
+    /// to a LocalAudioTrack
+    pub fn processor(
+        options: VadOptions,
+    ) -> Result<(VadProcessor, mpsc::UnboundedReceiver<VadEvent>), RtcError> {
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+        Ok((
+            VadProcessor {
+                vad: Self::new(options)?,
+                event_tx,
+            },
+            event_rx,
+        ))
+    }
+
+    /// Detect the speech on a received track, the events stop when the stream ends
+    pub fn detect_stream(
+        mut stream: NativeAudioStream,
+        options: VadOptions,
+    ) -> Result<mpsc::UnboundedReceiver<VadEvent>, RtcError> {
+        let mut vad = Self::new(options)?;
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Some(frame) = stream.next().await {
+                match vad.process(&frame) {
+                    Ok(Some(event)) => {
+                        if event_tx.send(event).is_err() {
+                            break;
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(err) => {
+                        log::warn!("failed to detect the voice activity: {:?}", err);
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(event_rx)
+    }
+}
+
+pub struct VadProcessor {
+    vad: VoiceActivityDetector,
+    event_tx: mpsc::UnboundedSender<VadEvent>,
+}
+
+impl AudioProcessor for VadProcessor {
+    fn process(&mut self, frame: &mut AudioFrame<'_>) {
+        match self.vad.process(frame) {
+            Ok(Some(event)) => {
+                let _ = self.event_tx.send(event);
+            }
+            Ok(None) => {}
+            Err(err) => log::warn!("failed to detect the voice activity: {:?}", err),
+        }
+    }
+}
+
+/// Smooths the per chunk VAD decisions
+struct SpeechState {
+    options: VadOptions,
+    speaking: bool,
+    speech_ms: u32,
+    silence_ms: u32,
+}
+
+impl SpeechState {
+    fn new(options: VadOptions) -> Self {
+        Self {
+            options,
+            speaking: false,
+            speech_ms: 0,
+            silence_ms: 0,
+        }
+    }
+
+    fn update(&mut self, is_speech: bool, duration_ms: u32) -> Option<VadEvent> {
+        if is_speech {
+            self.speech_ms += duration_ms;
+            self.silence_ms = 0;
+        } else {
+            self.silence_ms += duration_ms;
+            self.speech_ms = 0;
+        }
+
+        if !self.speaking && self.speech_ms >= self.options.min_speech_ms {
+            self.speaking = true;
+            return Some(VadEvent::SpeechStarted);
+        }
+
+        if self.speaking && self.silence_ms >= self.options.min_silence_ms {
+            self.speaking = false;
+            return Some(VadEvent::SpeechEnded);
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn speech_hysteresis() {
+        let mut state = SpeechState::new(VadOptions {
+            min_speech_ms: 30,
+            min_silence_ms: 50,
+            ..Default::default()
+        });
+
+        assert_eq!(state.update(true, 10), None);
+        assert_eq!(state.update(false, 10), None); // Too short to be speech
+        assert_eq!(state.update(true, 10), None);
+        assert_eq!(state.update(true, 10), None);
+        assert_eq!(state.update(true, 10), Some(VadEvent::SpeechStarted));
+
+        for _ in 0..4 {
+            assert_eq!(state.update(false, 10), None);
+        }
+        assert_eq!(state.update(true, 10), None); // The silence is reset
+        for _ in 0..4 {
+            assert_eq!(state.update(false, 10), None);
+        }
+        assert_eq!(state.update(false, 10), Some(VadEvent::SpeechEnded));
+    }
+}
@@ -0,0 +1,460 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::yuv_helper;
+use crate::video_frame::{VideoFrameBuffer, VideoRotation};
+use std::borrow::Cow;
+
+pub use super::yuv_helper::FilterMode;
+
+/// Pixel layouts supported by the conversions. The RGB names follow libyuv and describe a
+/// little-endian word: ARGB is stored as B, G, R, A in memory, ABGR as R, G, B, A and
+/// RGB24 as B, G, R
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PixelFormat {
+    I420,
+    I422,
+    I444,
+    NV12,
+    ARGB,
+    ABGR,
+    RGB24,
+}
+
+impl PixelFormat {
+    pub fn num_planes(&self) -> usize {
+        match self {
+            Self::I420 | Self::I422 | Self::I444 => 3,
+            Self::NV12 => 2,
+            Self::ARGB | Self::ABGR | Self::RGB24 => 1,
+        }
+    }
+
+    /// Width in bytes and number of rows of a plane
+    pub fn plane_size(&self, plane: usize, width: u32, height: u32) -> (u32, u32) {
+        assert!(plane < self.num_planes(), "plane out of range");
+        let (chroma_width, chroma_height) = (width.div_ceil(2), height.div_ceil(2));
+        match (self, plane) {
+            (Self::ARGB | Self::ABGR, _) => (width * 4, height),
+            (Self::RGB24, _) => (width * 3, height),
+            (_, 0) | (Self::I444, _) => (width, height),
+            (Self::I420, _) => (chroma_width, chroma_height),
+            (Self::I422, _) => (chroma_width, height),
+            (Self::NV12, _) => (chroma_width * 2, chroma_height),
+        }
+    }
+}
+
+/// Frame stored in a single buffer, the planes follow each other and each plane has its own
+/// stride (in bytes)
+#[derive(Debug, Clone)]
+pub struct PixelBuffer<'a> {
+    format: PixelFormat,
+    width: u32,
+    height: u32,
+    strides: [u32; 3],
+    data: Cow<'a, [u8]>,
+}
+
+impl<'a> PixelBuffer<'a> {
+    /// Allocate a black buffer without padding between the rows
+    pub fn new(format: PixelFormat, width: u32, height: u32) -> PixelBuffer<'static> {
+        let mut strides = [0; 3];
+        for (plane, stride) in strides.iter_mut().enumerate().take(format.num_planes()) {
+            *stride = format.plane_size(plane, width, height).0;
+        }
+
+        let mut buffer = PixelBuffer {
+            format,
+            width,
+            height,
+            strides,
+            data: Cow::Owned(Vec::new()),
+        };
+
+        let len = buffer.plane_offset(format.num_planes());
+        let mut data = vec![0; len];
+        if matches!(
+            format,
+            PixelFormat::I420 | PixelFormat::I422 | PixelFormat::I444 | PixelFormat::NV12
+        ) {
+            // Neutral chroma
+            data[buffer.plane_offset(1)..].fill(128);
+        }
+        buffer.data = Cow::Owned(data);
+        buffer
+    }
+
+    /// Wrap existing data, there must be a stride for each plane of the format
+    pub fn from_data(
+        format: PixelFormat,
+        width: u32,
+        height: u32,
+        strides: &[u32],
+        data: impl Into<Cow<'a, [u8]>>,
+    ) -> Self {
+        assert_eq!(
+            strides.len(),
+            format.num_planes(),
+            "invalid number of strides"
+        );
+        for (plane, stride) in strides.iter().enumerate() {
+            let (row_len, _) = format.plane_size(plane, width, height);
+            assert!(*stride >= row_len, "stride too small");
+        }
+
+        let mut buffer = Self {
+            format,
+            width,
+            height,
+            strides: [0; 3],
+            data: data.into(),
+        };
+        buffer.strides[..strides.len()].copy_from_slice(strides);
+        assert!(
+            buffer.data.len() >= buffer.plane_offset(format.num_planes()),
+            "data isn't large enough"
+        );
+        buffer
+    }
+
+    /// Copy a buffer received from a VideoStream, buffers not matching a PixelFormat
+    /// (e.g native or I010) are converted to I420
+    pub fn from_video_buffer(buffer: &dyn VideoFrameBuffer) -> PixelBuffer<'static> {
+        let (width, height) = (buffer.width(), buffer.height());
+        let copy = |format, planes: &[(&[u8], u32)]| {
+            let mut dst = PixelBuffer::new(format, width, height);
+            for (plane, (src, src_stride)) in planes.iter().enumerate() {
+                dst.copy_plane(plane, src, *src_stride);
+            }
+            dst
+        };
+
+        if let Some(i422) = buffer.as_i422() {
+            let (y, u, v) = i422.data();
+            let (sy, su, sv) = i422.strides();
+            return copy(PixelFormat::I422, &[(y, sy), (u, su), (v, sv)]);
+        }
+
+        if let Some(i444) = buffer.as_i444() {
+            let (y, u, v) = i444.data();
+            let (sy, su, sv) = i444.strides();
+            return copy(PixelFormat::I444, &[(y, sy), (u, su), (v, sv)]);
+        }
+
+        if let Some(nv12) = buffer.as_nv12() {
+            let (y, uv) = nv12.data();
+            let (sy, suv) = nv12.strides();
+            return copy(PixelFormat::NV12, &[(y, sy), (uv, suv)]);
+        }
+
+        let i420 = buffer.to_i420();
+        let (y, u, v) = i420.data();
+        let (sy, su, sv) = i420.strides();
+        copy(PixelFormat::I420, &[(y, sy), (u, su), (v, sv)])
+    }
+
+    pub fn format(&self) -> PixelFormat {
+        self.format
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn strides(&self) -> &[u32] {
+        &self.strides[..self.format.num_planes()]
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn into_vec(self) -> Vec<u8> {
+        self.data.into_owned()
+    }
+
+    pub fn plane(&self, plane: usize) -> &[u8] {
+        &self.data[self.plane_offset(plane)..self.plane_offset(plane + 1)]
+    }
+
+    pub fn plane_mut(&mut self, plane: usize) -> &mut [u8] {
+        let (start, end) = (self.plane_offset(plane), self.plane_offset(plane + 1));
+        &mut self.data.to_mut()[start..end]
+    }
+
+    /// Convert to another format, the dimensions are kept
+    pub fn convert(&self, format: PixelFormat) -> PixelBuffer<'static> {
+        let mut dst = PixelBuffer::new(format, self.width, self.height);
+        self.convert_into(&mut dst);
+        dst
+    }
+
+    /// Convert into an existing buffer (e.g. a texture staging buffer) of the same dimensions.
+    /// Pairs without a direct libyuv conversion go through I420
+    pub fn convert_into(&self, dst: &mut PixelBuffer<'_>) {
+        use yuv_helper::*;
+        use PixelFormat::*;
+
+        assert!(
+            self.width == dst.width && self.height == dst.height,
+            "the buffers must have the same dimensions"
+        );
+
+        if self.format == dst.format {
+            for plane in 0..self.format.num_planes() {
+                dst.copy_plane(plane, self.plane(plane), self.strides[plane]);
+            }
+            return;
+        }
+
+        let (width, height) = (self.width as i32, self.height as i32);
+        let formats = (self.format, dst.format);
+        let [ss0, ss1, ss2] = self.strides;
+        let [ds0, ds1, ds2] = dst.strides;
+        let (s0, s1, s2) = (self.plane(0), self.plane(1), self.plane(2));
+        let (d0, d1, d2) = dst.planes_mut();
+
+        match formats {
+            (I420, I422) => i420_to_i422(
+                s0, ss0, s1, ss1, s2, ss2, d0, ds0, d1, ds1, d2, ds2, width, height,
+            ),
+            (I420, I444) => i420_to_i444(
+                s0, ss0, s1, ss1, s2, ss2, d0, ds0, d1, ds1, d2, ds2, width, height,
+            ),
+            (I420, NV12) => {
+                i420_to_nv12(s0, ss0, s1, ss1, s2, ss2, d0, ds0, d1, ds1, width, height)
+            }
+            (I420, ARGB) => i420_to_argb(s0, ss0, s1, ss1, s2, ss2, d0, ds0, width, height),
+            (I420, ABGR) => i420_to_abgr(s0, ss0, s1, ss1, s2, ss2, d0, ds0, width, height),
+            (I420, RGB24) => i420_to_rgb24(s0, ss0, s1, ss1, s2, ss2, d0, ds0, width, height),
+            (I422, I420) => i422_to_i420(
+                s0, ss0, s1, ss1, s2, ss2, d0, ds0, d1, ds1, d2, ds2, width, height,
+            ),
+            (I422, ARGB) => i422_to_argb(s0, ss0, s1, ss1, s2, ss2, d0, ds0, width, height),
+            (I422, ABGR) => i422_to_abgr(s0, ss0, s1, ss1, s2, ss2, d0, ds0, width, height),
+            (I444, I420) => i444_to_i420(
+                s0, ss0, s1, ss1, s2, ss2, d0, ds0, d1, ds1, d2, ds2, width, height,
+            ),
+            (I444, ARGB) => i444_to_argb(s0, ss0, s1, ss1, s2, ss2, d0, ds0, width, height),
+            (I444, ABGR) => i444_to_abgr(s0, ss0, s1, ss1, s2, ss2, d0, ds0, width, height),
+            (NV12, I420) => {
+                nv12_to_i420(s0, ss0, s1, ss1, d0, ds0, d1, ds1, d2, ds2, width, height)
+            }
+            (NV12, ARGB) => nv12_to_argb(s0, ss0, s1, ss1, d0, ds0, width, height),
+            (NV12, ABGR) => nv12_to_abgr(s0, ss0, s1, ss1, d0, ds0, width, height),
+            (ARGB, I420) => argb_to_i420(s0, ss0, d0, ds0, d1, ds1, d2, ds2, width, height),
+            (ARGB, NV12) => argb_to_nv12(s0, ss0, d0, ds0, d1, ds1, width, height),
+            (ARGB, RGB24) => argb_to_rgb24(s0, ss0, d0, ds0, width, height),
+            (ARGB, ABGR) | (ABGR, ARGB) => argb_to_abgr(s0, ss0, d0, ds0, width, height),
+            (ABGR, I420) => abgr_to_i420(s0, ss0, d0, ds0, d1, ds1, d2, ds2, width, height),
+            (ABGR, NV12) => abgr_to_nv12(s0, ss0, d0, ds0, d1, ds1, width, height),
+            (RGB24, I420) => rgb24_to_i420(s0, ss0, d0, ds0, d1, ds1, d2, ds2, width, height),
+            (RGB24, ARGB) => rgb24_to_argb(s0, ss0, d0, ds0, width, height),
+            _ => self.convert(I420).convert_into(dst),
+        }
+    }
+
+    /// Scale to the given dimensions, the format is kept
+    pub fn scale(&self, width: u32, height: u32, filtering: FilterMode) -> PixelBuffer<'static> {
+        use PixelFormat::*;
+
+        let mut dst = PixelBuffer::new(self.format, width, height);
+        match self.format {
+            I420 | I422 | I444 => {
+                for plane in 0..3 {
+                    let (src_width, src_height) =
+                        self.format.plane_size(plane, self.width, self.height);
+                    let (dst_width, dst_height) = self.format.plane_size(plane, width, height);
+                    let dst_stride = dst.strides[plane];
+                    yuv_helper::scale_plane(
+                        self.plane(plane),
+                        self.strides[plane],
+                        src_width as i32,
+                        src_height as i32,
+                        dst.plane_mut(plane),
+                        dst_stride,
+                        dst_width as i32,
+                        dst_height as i32,
+                        filtering,
+                    );
+                }
+            }
+            NV12 => {
+                let [ds0, ds1, _] = dst.strides;
+                let (d0, d1, _) = dst.planes_mut();
+                yuv_helper::nv12_scale(
+                    self.plane(0),
+                    self.strides[0],
+                    self.plane(1),
+                    self.strides[1],
+                    self.width as i32,
+                    self.height as i32,
+                    d0,
+                    ds0,
+                    d1,
+                    ds1,
+                    width as i32,
+                    height as i32,
+                    filtering,
+                );
+            }
+            ARGB | ABGR => {
+                let dst_stride = dst.strides[0];
+                yuv_helper::argb_scale(
+                    self.plane(0),
+                    self.strides[0],
+                    self.width as i32,
+                    self.height as i32,
+                    dst.plane_mut(0),
+                    dst_stride,
+                    width as i32,
+                    height as i32,
+                    filtering,
+                );
+            }
+            RGB24 => {
+                self.convert(ARGB)
+                    .scale(width, height, filtering)
+                    .convert_into(&mut dst);
+            }
+        }
+        dst
+    }
+
+    /// Rotate clockwise, the width and height are swapped for 90 and 270 degrees.
+    /// I422 and NV12 are rotated through I420 and RGB24 through ARGB
+    pub fn rotate(&self, rotation: VideoRotation) -> PixelBuffer<'static> {
+        use PixelFormat::*;
+
+        let (width, height) = match rotation {
+            VideoRotation::VideoRotation90 | VideoRotation::VideoRotation270 => {
+                (self.height, self.width)
+            }
+            _ => (self.width, self.height),
+        };
+
+        let mut dst = PixelBuffer::new(self.format, width, height);
+        match self.format {
+            I420 | I444 => {
+                for plane in 0..3 {
+                    let (src_width, src_height) =
+                        self.format.plane_size(plane, self.width, self.height);
+                    let dst_stride = dst.strides[plane];
+                    yuv_helper::rotate_plane(
+                        self.plane(plane),
+                        self.strides[plane],
+                        dst.plane_mut(plane),
+                        dst_stride,
+                        src_width as i32,
+                        src_height as i32,
+                        rotation,
+                    );
+                }
+            }
+            ARGB | ABGR => {
+                let dst_stride = dst.strides[0];
+                yuv_helper::argb_rotate(
+                    self.plane(0),
+                    self.strides[0],
+                    dst.plane_mut(0),
+                    dst_stride,
+                    self.width as i32,
+                    self.height as i32,
+                    rotation,
+                );
+            }
+            I422 | NV12 => self.convert(I420).rotate(rotation).convert_into(&mut dst),
+            RGB24 => self.convert(ARGB).rotate(rotation).convert_into(&mut dst),
+        }
+        dst
+    }
+
+    fn plane_offset(&self, plane: usize) -> usize {
+        (0..plane.min(self.format.num_planes()))
+            .map(|p| {
+                let (_, rows) = self.format.plane_size(p, self.width, self.height);
+                (self.strides[p] * rows) as usize
+            })
+            .sum()
+    }
+
+    /// Mutable access to all the planes, missing planes are empty
+    fn planes_mut(&mut self) -> (&mut [u8], &mut [u8], &mut [u8]) {
+        let (o1, o2, o3) = (
+            self.plane_offset(1),
+            self.plane_offset(2),
+            self.plane_offset(3),
+        );
+        let data = &mut self.data.to_mut()[..o3];
+        let (p0, rest) = data.split_at_mut(o1);
+        let (p1, p2) = rest.split_at_mut(o2 - o1);
+        (p0, p1, p2)
+    }
+
+    fn copy_plane(&mut self, plane: usize, src: &[u8], src_stride: u32) {
+        let (row_len, _) = self.format.plane_size(plane, self.width, self.height);
+        let dst_stride = self.strides[plane] as usize;
+        let dst = self.plane_mut(plane);
+        for (dst_row, src_row) in dst
+            .chunks_mut(dst_stride)
+            .zip(src.chunks(src_stride as usize))
+        {
+            dst_row[..row_len as usize].copy_from_slice(&src_row[..row_len as usize]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plane_layout() {
+        let buffer = PixelBuffer::new(PixelFormat::I420, 5, 3);
+        assert_eq!(buffer.strides(), &[5, 3, 3]);
+        assert_eq!(buffer.data().len(), 5 * 3 + 2 * 3 * 2);
+        assert_eq!(buffer.plane(1).len(), 6);
+        assert!(buffer.plane(2).iter().all(|v| *v == 128));
+
+        let nv12 = PixelBuffer::new(PixelFormat::NV12, 5, 3);
+        assert_eq!(nv12.strides(), &[5, 6]);
+        assert_eq!(nv12.plane(1).len(), 12);
+        assert!(nv12.plane(2).is_empty());
+
+        let rgb = PixelBuffer::new(PixelFormat::RGB24, 5, 3);
+        assert_eq!(rgb.strides(), &[15]);
+    }
+
+    #[test]
+    fn copy_with_stride() {
+        // 2x2 ARGB with 4 bytes of padding at the end of each row
+        let data: Vec<u8> = (0..24).collect();
+        let src = PixelBuffer::from_data(PixelFormat::ARGB, 2, 2, &[12], &data[..]);
+        let dst = src.convert(PixelFormat::ARGB);
+        assert_eq!(dst.strides(), &[8]);
+        assert_eq!(&dst.data()[..8], &data[..8]);
+        assert_eq!(&dst.data()[8..], &data[12..20]);
+    }
+
+    #[test]
+    #[should_panic(expected = "data isn't large enough")]
+    fn from_data_too_small() {
+        PixelBuffer::from_data(PixelFormat::I420, 4, 4, &[4, 2, 2], vec![0; 20]);
+    }
+}
@@ -16,6 +16,7 @@ use super::yuv_helper;
 use crate::video_frame::VideoRotation;
 use crate::video_frame::{self as vf, VideoFormatType};
 use cxx::UniquePtr;
+use std::ffi::c_void;
 use std::slice;
 use webrtc_sys::video_frame as vf_sys;
 use webrtc_sys::video_frame_buffer as vfb_sys;
@@ -177,6 +178,38 @@ impl NativeBuffer {
         }
     }
 
+    pub fn platform_handle(&self) -> Option<vf::native::NativeBufferHandle> {
+        let handle = self.sys_handle.native_handle();
+        let ptr = handle.handle as *mut c_void;
+        match handle.handle_type {
+            vfb_sys::ffi::NativeHandleType::CVPixelBuffer => {
+                Some(vf::native::NativeBufferHandle::CVPixelBuffer(ptr))
+            }
+            vfb_sys::ffi::NativeHandleType::D3D11Texture => {
+                Some(vf::native::NativeBufferHandle::D3D11Texture {
+                    texture: ptr,
+                    subresource: handle.subresource,
+                })
+            }
+            vfb_sys::ffi::NativeHandleType::DmaBuf => {
+                let planes = (0..handle.num_planes.min(4) as usize)
+                    .map(|i| vf::native::DmaBufPlane {
+                        fd: handle.fds[i],
+                        offset: handle.offsets[i],
+                        stride: handle.strides[i],
+                    })
+                    .collect();
+
+                Some(vf::native::NativeBufferHandle::DmaBuf {
+                    fourcc: handle.fourcc,
+                    modifier: handle.modifier,
+                    planes,
+                })
+            }
+            _ => None,
+        }
+    }
+
     pub fn to_argb(
         &self,
         format: VideoFormatType,
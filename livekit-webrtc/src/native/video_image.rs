@@ -0,0 +1,66 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::video_conversion::{PixelBuffer, PixelFormat};
+use crate::video_frame::{VideoFrame, VideoFrameBuffer, VideoRotation};
+use image::codecs::jpeg::JpegEncoder;
+use image::{ImageFormat, ImageResult, RgbaImage};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+impl<T: AsRef<dyn VideoFrameBuffer>> VideoFrame<T> {
+    /// Copy the frame into an RGBA image, the rotation of the frame is applied
+    pub fn to_image(&self) -> RgbaImage {
+        let mut buffer = PixelBuffer::from_video_buffer(self.buffer.as_ref());
+        if self.rotation != VideoRotation::VideoRotation0 {
+            buffer = buffer.rotate(self.rotation);
+        }
+        pixel_buffer_to_image(&buffer)
+    }
+
+    pub fn save_png(&self, path: impl AsRef<Path>) -> ImageResult<()> {
+        self.to_image().save_with_format(path, ImageFormat::Png)
+    }
+
+    /// The quality goes from 1 to 100
+    pub fn save_jpeg(&self, path: impl AsRef<Path>, quality: u8) -> ImageResult<()> {
+        let writer = BufWriter::new(File::create(path)?);
+        // JPEG has no alpha channel
+        let rgb = image::DynamicImage::ImageRgba8(self.to_image()).into_rgb8();
+        let mut encoder = JpegEncoder::new_with_quality(writer, quality);
+        encoder.encode_image(&rgb)
+    }
+}
+
+fn pixel_buffer_to_image(buffer: &PixelBuffer) -> RgbaImage {
+    // ABGR in libyuv is stored as R, G, B, A
+    let rgba = buffer.convert(PixelFormat::ABGR);
+    RgbaImage::from_raw(rgba.width(), rgba.height(), rgba.into_vec())
+        .expect("an ABGR buffer has no padding")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rgba_image() {
+        // A single red pixel, ARGB is stored as B, G, R, A
+        let argb = PixelBuffer::from_data(PixelFormat::ARGB, 1, 1, &[4], vec![0, 0, 255, 255]);
+        let image = pixel_buffer_to_image(&argb);
+        assert_eq!(image.dimensions(), (1, 1));
+        assert_eq!(image.get_pixel(0, 0).0, [255, 0, 0, 255]);
+    }
+}
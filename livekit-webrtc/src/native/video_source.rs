@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::encoded_video_stream::EncodedVideoFrame;
 use crate::video_frame::{VideoFrame, VideoFrameBuffer};
 use crate::video_source::VideoResolution;
 use cxx::SharedPtr;
@@ -74,6 +75,28 @@ impl NativeVideoSource {
             .on_captured_frame(&builder.pin_mut().build());
     }
 
+    pub fn capture_encoded_frame(&self, frame: &EncodedVideoFrame, timestamp_us: i64) -> bool {
+        let timestamp_us = if timestamp_us == 0 {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+            now.as_micros() as i64
+        } else {
+            timestamp_us
+        };
+
+        self.sys_handle.on_captured_encoded_frame(
+            frame.codec.into(),
+            frame.key_frame,
+            frame.width,
+            frame.height,
+            &frame.data,
+            timestamp_us,
+        )
+    }
+
+    pub fn take_key_frame_request(&self) -> bool {
+        self.sys_handle.take_key_frame_request()
+    }
+
     pub fn video_resolution(&self) -> VideoResolution {
         self.sys_handle.video_resolution().into()
     }
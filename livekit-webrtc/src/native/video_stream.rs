@@ -13,9 +13,13 @@
 // limitations under the License.
 
 use super::video_frame::new_video_frame_buffer;
-use crate::video_frame::{BoxVideoFrame, VideoFrame};
+use super::yuv_helper::{self, FilterMode};
+use crate::video_frame::native::I420BufferExt;
+use crate::video_frame::{BoxVideoFrame, I420Buffer, VideoFrame, VideoFrameBuffer};
+use crate::video_stream::native::VideoStreamOptions;
 use crate::video_track::RtcVideoTrack;
 use cxx::{SharedPtr, UniquePtr};
+use parking_lot::Mutex;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
@@ -30,9 +34,13 @@ pub struct NativeVideoStream {
 }
 
 impl NativeVideoStream {
-    pub fn new(video_track: RtcVideoTrack) -> Self {
+    pub fn new(video_track: RtcVideoTrack, options: VideoStreamOptions) -> Self {
         let (frame_tx, frame_rx) = mpsc::unbounded_channel();
-        let observer = Arc::new(VideoTrackObserver { frame_tx });
+        let observer = Arc::new(VideoTrackObserver {
+            frame_tx,
+            limiter: Mutex::new(FrameRateLimiter::new(options.max_fps)),
+            max_dimension: options.max_dimension,
+        });
         let native_sink = sys_vt::ffi::new_native_video_sink(Box::new(
             sys_vt::VideoSinkWrapper::new(observer.clone()),
         ));
@@ -75,14 +83,31 @@ impl Stream for NativeVideoStream {
 
 struct VideoTrackObserver {
     frame_tx: mpsc::UnboundedSender<BoxVideoFrame>,
+    limiter: Mutex<FrameRateLimiter>,
+    max_dimension: Option<u32>,
 }
 
 impl sys_vt::VideoSink for VideoTrackObserver {
     fn on_frame(&self, frame: UniquePtr<webrtc_sys::video_frame::ffi::VideoFrame>) {
+        let timestamp_us = frame.timestamp_us();
+        if !self.limiter.lock().accept(timestamp_us) {
+            return;
+        }
+
+        let mut buffer = new_video_frame_buffer(unsafe { frame.video_frame_buffer() });
+        if let Some((width, height)) = self
+            .max_dimension
+            .and_then(|max| capped_size(buffer.width(), buffer.height(), max))
+        {
+            buffer = Box::new(scale_i420(&buffer.to_i420(), width, height));
+        }
+
         let _ = self.frame_tx.send(VideoFrame {
             rotation: frame.rotation().into(),
-            timestamp_us: frame.timestamp_us(),
-            buffer: new_video_frame_buffer(unsafe { frame.video_frame_buffer() }),
+            timestamp_us,
+            rtp_timestamp: frame.timestamp(),
+            ntp_time_ms: frame.ntp_time_ms(),
+            buffer,
         });
     }
 
@@ -90,3 +115,104 @@ impl sys_vt::VideoSink for VideoTrackObserver {
 
     fn on_constraints_changed(&self, _constraints: sys_vt::ffi::VideoTrackSourceConstraints) {}
 }
+
+/// Drops the frames received less than 1/max_fps after the last delivered one
+struct FrameRateLimiter {
+    interval_us: Option<i64>,
+    last_us: Option<i64>,
+}
+
+impl FrameRateLimiter {
+    fn new(max_fps: Option<u32>) -> Self {
+        Self {
+            interval_us: max_fps.map(|fps| 1_000_000 / fps.max(1) as i64),
+            last_us: None,
+        }
+    }
+
+    fn accept(&mut self, timestamp_us: i64) -> bool {
+        let Some(interval_us) = self.interval_us else {
+            return true;
+        };
+
+        // Tolerate some jitter so 30fps sources aren't limited to 10fps when asking for 15fps.
+        // Timestamps going backwards (e.g. the source restarted) reset the limiter
+        if let Some(last_us) = self.last_us {
+            let elapsed = timestamp_us - last_us;
+            if elapsed >= 0 && elapsed < interval_us * 9 / 10 {
+                return false;
+            }
+        }
+
+        self.last_us = Some(timestamp_us);
+        true
+    }
+}
+
+/// The size of the frame once capped to max_dimension, None if it already fits
+fn capped_size(width: u32, height: u32, max_dimension: u32) -> Option<(u32, u32)> {
+    let largest = width.max(height);
+    if largest <= max_dimension || largest == 0 {
+        return None;
+    }
+
+    // I420 needs even dimensions
+    let scale = |v: u32| ((v as u64 * max_dimension as u64 / largest as u64) as u32 & !1).max(2);
+    Some((scale(width), scale(height)))
+}
+
+fn scale_i420(src: &I420Buffer, width: u32, height: u32) -> I420Buffer {
+    let mut dst = I420Buffer::new(width, height);
+    let (src_width, src_height) = (src.width(), src.height());
+    let src_strides = src.strides();
+    let src_data = src.data();
+    let dst_strides = dst.strides();
+    let dst_data = dst.data_mut();
+
+    let planes = [
+        (src_data.0, src_strides.0, dst_data.0, dst_strides.0, 1),
+        (src_data.1, src_strides.1, dst_data.1, dst_strides.1, 2),
+        (src_data.2, src_strides.2, dst_data.2, dst_strides.2, 2),
+    ];
+    for (src, src_stride, dst, dst_stride, div) in planes {
+        yuv_helper::scale_plane(
+            src,
+            src_stride,
+            src_width.div_ceil(div) as i32,
+            src_height.div_ceil(div) as i32,
+            dst,
+            dst_stride,
+            width.div_ceil(div) as i32,
+            height.div_ceil(div) as i32,
+            FilterMode::Box,
+        );
+    }
+    dst
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_rate_limiter() {
+        let mut limiter = FrameRateLimiter::new(Some(15));
+        let accepted: Vec<bool> = (0..6).map(|i| limiter.accept(i * 33_333)).collect();
+        assert_eq!(accepted, [true, false, true, false, true, false]);
+
+        // The source restarted
+        assert!(limiter.accept(0));
+
+        let mut limiter = FrameRateLimiter::new(None);
+        assert!((0..3).all(|_| limiter.accept(0)));
+    }
+
+    #[test]
+    fn capped_sizes() {
+        assert_eq!(capped_size(1920, 1080, 640), Some((640, 360)));
+        assert_eq!(capped_size(1080, 1920, 640), Some((360, 640)));
+        assert_eq!(capped_size(1279, 721, 640), Some((640, 360)));
+        assert_eq!(capped_size(640, 480, 640), None);
+        assert_eq!(capped_size(4000, 2, 100), Some((100, 2)));
+    }
+}
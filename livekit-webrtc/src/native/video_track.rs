@@ -14,6 +14,7 @@
 
 use super::media_stream_track::impl_media_stream_track;
 use crate::media_stream_track::RtcTrackState;
+use crate::video_track::VideoContentHint;
 use cxx::SharedPtr;
 use sys_vt::ffi::video_to_media;
 use webrtc_sys::video_track as sys_vt;
@@ -29,4 +30,34 @@ impl RtcVideoTrack {
     pub fn sys_handle(&self) -> SharedPtr<sys_vt::ffi::MediaStreamTrack> {
         video_to_media(self.sys_handle.clone())
     }
+
+    pub fn content_hint(&self) -> VideoContentHint {
+        self.sys_handle.content_hint().into()
+    }
+
+    pub fn set_content_hint(&self, hint: VideoContentHint) {
+        self.sys_handle.set_content_hint(hint.into())
+    }
+}
+
+impl From<sys_vt::ffi::ContentHint> for VideoContentHint {
+    fn from(value: sys_vt::ffi::ContentHint) -> Self {
+        match value {
+            sys_vt::ffi::ContentHint::Fluid => Self::Fluid,
+            sys_vt::ffi::ContentHint::Detailed => Self::Detailed,
+            sys_vt::ffi::ContentHint::Text => Self::Text,
+            _ => Self::None,
+        }
+    }
+}
+
+impl From<VideoContentHint> for sys_vt::ffi::ContentHint {
+    fn from(value: VideoContentHint) -> Self {
+        match value {
+            VideoContentHint::None => Self::None,
+            VideoContentHint::Fluid => Self::Fluid,
+            VideoContentHint::Detailed => Self::Detailed,
+            VideoContentHint::Text => Self::Text,
+        }
+    }
 }
@@ -0,0 +1,302 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::audio_frame::AudioFrame;
+use crate::RtcError;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use thiserror::Error;
+
+const FORMAT_PCM: u16 = 1;
+const FORMAT_EXTENSIBLE: u16 = 0xfffe;
+const HEADER_LEN: u32 = 44;
+
+#[derive(Error, Debug)]
+pub enum WavError {
+    #[error("io error: {0}")]
+    Io(#[from] io::Error),
+    #[error("invalid wav: {0}")]
+    Invalid(String),
+    #[error("unsupported wav: {0}")]
+    Unsupported(String),
+    #[error("rtc error: {0}")]
+    Rtc(#[from] RtcError),
+}
+
+/// Reads the frames of a 16-bit PCM WAV
+pub struct WavReader<R: Read> {
+    reader: R,
+    sample_rate: u32,
+    num_channels: u32,
+    remaining: u64, // Bytes left in the data chunk
+}
+
+impl<R: Read> WavReader<R> {
+    pub fn new(mut reader: R) -> Result<Self, WavError> {
+        let mut riff = [0u8; 12];
+        reader.read_exact(&mut riff)?;
+        if &riff[0..4] != b"RIFF" || &riff[8..12] != b"WAVE" {
+            return Err(WavError::Invalid("missing RIFF/WAVE header".to_owned()));
+        }
+
+        let mut format = None;
+        loop {
+            let mut chunk = [0u8; 8];
+            reader.read_exact(&mut chunk)?;
+            let len = u32::from_le_bytes(chunk[4..8].try_into().unwrap()) as u64;
+
+            match &chunk[0..4] {
+                b"fmt " => {
+                    let mut fmt = vec![0u8; len as usize];
+                    reader.read_exact(&mut fmt)?;
+                    format = Some(parse_format(&fmt)?);
+                }
+                b"data" => {
+                    let Some((sample_rate, num_channels)) = format else {
+                        return Err(WavError::Invalid("data before the fmt chunk".to_owned()));
+                    };
+
+                    return Ok(Self {
+                        reader,
+                        sample_rate,
+                        num_channels,
+                        // Streamed WAVs don't know the length of their data
+                        remaining: if len == 0 || len == u32::MAX as u64 {
+                            u64::MAX
+                        } else {
+                            len
+                        },
+                    });
+                }
+                _ => {
+                    // Chunks are padded to an even length
+                    let skip = len + len % 2;
+                    io::copy(&mut (&mut reader).take(skip), &mut io::sink())?;
+                }
+            }
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn num_channels(&self) -> u32 {
+        self.num_channels
+    }
+
+    /// Read up to samples_per_channel samples, None at the end of the data
+    pub fn read_frame(
+        &mut self,
+        samples_per_channel: u32,
+    ) -> Result<Option<AudioFrame<'static>>, WavError> {
+        let frame_len = 2 * self.num_channels as u64;
+        let wanted = (samples_per_channel as u64 * frame_len).min(self.remaining);
+        let mut bytes = Vec::with_capacity(wanted as usize);
+        (&mut self.reader).take(wanted).read_to_end(&mut bytes)?;
+        self.remaining -= bytes.len() as u64;
+
+        // Drop the incomplete sample frame at the end of a truncated file
+        bytes.truncate(bytes.len() - bytes.len() % frame_len as usize);
+        if bytes.is_empty() {
+            self.remaining = 0;
+            return Ok(None);
+        }
+
+        let data: Vec<i16> = bytes
+            .chunks_exact(2)
+            .map(|s| i16::from_le_bytes([s[0], s[1]]))
+            .collect();
+
+        Ok(Some(AudioFrame {
+            samples_per_channel: data.len() as u32 / self.num_channels,
+            data: data.into(),
+            sample_rate: self.sample_rate,
+            num_channels: self.num_channels,
+        }))
+    }
+}
+
+fn parse_format(fmt: &[u8]) -> Result<(u32, u32), WavError> {
+    if fmt.len() < 16 {
+        return Err(WavError::Invalid("fmt chunk too small".to_owned()));
+    }
+
+    let u16_at = |i: usize| u16::from_le_bytes([fmt[i], fmt[i + 1]]);
+    let mut format = u16_at(0);
+    if format == FORMAT_EXTENSIBLE && fmt.len() >= 26 {
+        format = u16_at(24); // First bytes of the SubFormat GUID
+    }
+
+    let num_channels = u16_at(2) as u32;
+    let sample_rate = u32::from_le_bytes(fmt[4..8].try_into().unwrap());
+    let bits_per_sample = u16_at(14);
+    if format != FORMAT_PCM || bits_per_sample != 16 {
+        return Err(WavError::Unsupported(format!(
+            "format {} with {} bits per sample, only 16-bit PCM is supported",
+            format, bits_per_sample
+        )));
+    }
+
+    if num_channels == 0 || sample_rate == 0 {
+        return Err(WavError::Invalid("no channels or sample rate".to_owned()));
+    }
+
+    Ok((sample_rate, num_channels))
+}
+
+/// Writes 16-bit PCM frames to a WAV, the sizes of the header are updated by finalize
+/// and when the writer is dropped
+pub struct WavWriter<W: Write + Seek> {
+    writer: W,
+    sample_rate: u32,
+    num_channels: u32,
+    data_len: u32,
+}
+
+impl<W: Write + Seek> WavWriter<W> {
+    pub fn new(mut writer: W, sample_rate: u32, num_channels: u32) -> Result<Self, WavError> {
+        let block_align = 2 * num_channels;
+        let mut header = Vec::with_capacity(HEADER_LEN as usize);
+        header.extend_from_slice(b"RIFF");
+        header.extend_from_slice(&(HEADER_LEN - 8).to_le_bytes());
+        header.extend_from_slice(b"WAVEfmt ");
+        header.extend_from_slice(&16u32.to_le_bytes());
+        header.extend_from_slice(&FORMAT_PCM.to_le_bytes());
+        header.extend_from_slice(&(num_channels as u16).to_le_bytes());
+        header.extend_from_slice(&sample_rate.to_le_bytes());
+        header.extend_from_slice(&(sample_rate * block_align).to_le_bytes());
+        header.extend_from_slice(&(block_align as u16).to_le_bytes());
+        header.extend_from_slice(&16u16.to_le_bytes());
+        header.extend_from_slice(b"data");
+        header.extend_from_slice(&0u32.to_le_bytes());
+        writer.write_all(&header)?;
+
+        Ok(Self {
+            writer,
+            sample_rate,
+            num_channels,
+            data_len: 0,
+        })
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn num_channels(&self) -> u32 {
+        self.num_channels
+    }
+
+    pub fn write_frame(&mut self, frame: &AudioFrame<'_>) -> Result<(), WavError> {
+        if frame.sample_rate != self.sample_rate || frame.num_channels != self.num_channels {
+            return Err(WavError::Unsupported(format!(
+                "the frame format ({}Hz, {} channels) doesn't match the wav",
+                frame.sample_rate, frame.num_channels
+            )));
+        }
+
+        let bytes: Vec<u8> = frame.data.iter().flat_map(|s| s.to_le_bytes()).collect();
+        self.writer.write_all(&bytes)?;
+        self.data_len = self.data_len.saturating_add(bytes.len() as u32);
+        Ok(())
+    }
+
+    /// Update the sizes of the header, the file is playable after this call
+    pub fn finalize(&mut self) -> Result<(), WavError> {
+        self.writer.seek(SeekFrom::Start(4))?;
+        self.writer
+            .write_all(&(HEADER_LEN - 8).saturating_add(self.data_len).to_le_bytes())?;
+        self.writer.seek(SeekFrom::Start(HEADER_LEN as u64 - 4))?;
+        self.writer.write_all(&self.data_len.to_le_bytes())?;
+        self.writer.seek(SeekFrom::End(0))?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+impl<W: Write + Seek> Drop for WavWriter<W> {
+    fn drop(&mut self) {
+        if let Err(err) = self.finalize() {
+            log::warn!("failed to finalize the wav: {:?}", err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn write_and_read() {
+        let mut cursor = Cursor::new(Vec::new());
+        {
+            let mut writer = WavWriter::new(&mut cursor, 16000, 2).unwrap();
+            let frame = AudioFrame {
+                data: (0..320).collect::<Vec<i16>>().into(),
+                sample_rate: 16000,
+                num_channels: 2,
+                samples_per_channel: 160,
+            };
+            writer.write_frame(&frame).unwrap();
+            writer.write_frame(&frame).unwrap();
+            assert!(writer.write_frame(&AudioFrame::new(48000, 2, 480)).is_err());
+        }
+
+        let bytes = cursor.into_inner();
+        assert_eq!(bytes.len(), 44 + 640 * 2);
+        assert_eq!(&bytes[40..44], &1280u32.to_le_bytes());
+
+        let mut reader = WavReader::new(Cursor::new(bytes)).unwrap();
+        assert_eq!((reader.sample_rate(), reader.num_channels()), (16000, 2));
+
+        let frame = reader.read_frame(200).unwrap().unwrap();
+        assert_eq!(frame.samples_per_channel, 200);
+        assert_eq!(&frame.data[..3], &[0, 1, 2]);
+        assert_eq!(frame.data[320], 0); // Start of the second frame
+
+        let frame = reader.read_frame(200).unwrap().unwrap();
+        assert_eq!(frame.samples_per_channel, 120);
+        assert!(reader.read_frame(200).unwrap().is_none());
+    }
+
+    #[test]
+    fn skip_unknown_chunks() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"RIFF\0\0\0\0WAVE");
+        bytes.extend_from_slice(b"LIST\x03\0\0\0abc\0"); // Odd chunk, padded
+        bytes.extend_from_slice(b"fmt \x10\0\0\0");
+        bytes.extend_from_slice(&[1, 0, 1, 0, 0x80, 0xbb, 0, 0, 0, 0, 0, 0, 2, 0, 16, 0]);
+        bytes.extend_from_slice(b"data\x04\0\0\0\x01\0\x02\0");
+
+        let mut reader = WavReader::new(Cursor::new(bytes)).unwrap();
+        assert_eq!((reader.sample_rate(), reader.num_channels()), (48000, 1));
+        let frame = reader.read_frame(480).unwrap().unwrap();
+        assert_eq!(&frame.data[..], &[1, 2]);
+    }
+
+    #[test]
+    fn unsupported_format() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"RIFF\0\0\0\0WAVEfmt \x10\0\0\0");
+        bytes.extend_from_slice(&[3, 0, 1, 0, 0x80, 0xbb, 0, 0, 0, 0, 0, 0, 4, 0, 32, 0]);
+        assert!(matches!(
+            WavReader::new(Cursor::new(bytes)),
+            Err(WavError::Unsupported(_))
+        ));
+        assert!(matches!(
+            WavReader::new(Cursor::new(b"not a wav file".to_vec())),
+            Err(WavError::Invalid(_))
+        ));
+    }
+}
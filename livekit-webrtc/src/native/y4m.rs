@@ -0,0 +1,113 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::video_conversion::{FilterMode, PixelBuffer, PixelFormat};
+use crate::video_frame::{VideoFrame, VideoFrameBuffer, VideoRotation};
+use std::borrow::Cow;
+use std::io::{self, Write};
+
+/// Writes the frames of a video stream to a Y4M file (raw I420 after a short header),
+/// which ffmpeg and most players can read, e.g. to debug color issues.
+/// The size of the video is the one of the first frame, the next frames are scaled to it
+pub struct Y4mWriter<W: Write> {
+    writer: W,
+    fps: u32,
+    size: Option<(u32, u32)>,
+}
+
+impl<W: Write> Y4mWriter<W> {
+    /// The frame rate is only used by the players, the frames aren't timestamped
+    pub fn new(writer: W, fps: u32) -> Self {
+        Self {
+            writer,
+            fps,
+            size: None,
+        }
+    }
+
+    /// The rotation of the frame is applied
+    pub fn write_frame<T: AsRef<dyn VideoFrameBuffer>>(
+        &mut self,
+        frame: &VideoFrame<T>,
+    ) -> io::Result<()> {
+        let mut buffer = PixelBuffer::from_video_buffer(frame.buffer.as_ref());
+        if frame.rotation != VideoRotation::VideoRotation0 {
+            buffer = buffer.rotate(frame.rotation);
+        }
+        self.write_buffer(&buffer)
+    }
+
+    pub fn write_buffer(&mut self, buffer: &PixelBuffer) -> io::Result<()> {
+        let (width, height) = match self.size {
+            Some(size) => size,
+            None => {
+                let size = (buffer.width(), buffer.height());
+                writeln!(
+                    self.writer,
+                    "YUV4MPEG2 W{} H{} F{}:1 Ip A1:1 C420mpeg2 XCOLORRANGE=LIMITED",
+                    size.0, size.1, self.fps
+                )?;
+                self.size = Some(size);
+                size
+            }
+        };
+
+        let mut buffer = Cow::Borrowed(buffer);
+        if buffer.format() != PixelFormat::I420 {
+            buffer = Cow::Owned(buffer.convert(PixelFormat::I420));
+        }
+        if (buffer.width(), buffer.height()) != (width, height) {
+            buffer = Cow::Owned(buffer.scale(width, height, FilterMode::Box));
+        }
+
+        self.writer.write_all(b"FRAME\n")?;
+        for plane in 0..3 {
+            let (row_len, rows) = PixelFormat::I420.plane_size(plane, width, height);
+            let stride = buffer.strides()[plane] as usize;
+            for row in buffer.plane(plane).chunks(stride).take(rows as usize) {
+                self.writer.write_all(&row[..row_len as usize])?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn y4m_frames() {
+        let mut writer = Y4mWriter::new(Vec::new(), 30);
+        writer
+            .write_buffer(&PixelBuffer::new(PixelFormat::I420, 5, 3))
+            .unwrap();
+        // Converted and scaled to the size of the first frame
+        writer
+            .write_buffer(&PixelBuffer::new(PixelFormat::ARGB, 10, 6))
+            .unwrap();
+
+        let out = writer.into_inner();
+        let header = b"YUV4MPEG2 W5 H3 F30:1 Ip A1:1 C420mpeg2 XCOLORRANGE=LIMITED\n";
+        assert!(out.starts_with(header));
+
+        let frame_len = b"FRAME\n".len() + 5 * 3 + 2 * (3 * 2);
+        assert_eq!(out.len(), header.len() + 2 * frame_len);
+        assert_eq!(&out[header.len() + frame_len..][..6], b"FRAME\n");
+    }
+}
@@ -14,8 +14,19 @@
 
 #![allow(clippy::too_many_arguments)]
 
+use crate::video_frame::VideoRotation;
 use webrtc_sys::yuv_helper as yuv_sys;
 
+/// Filtering used when scaling, from the fastest to the best quality
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum FilterMode {
+    None = 0,
+    Linear = 1,
+    Bilinear = 2,
+    #[default]
+    Box = 3,
+}
+
 fn argb_assert_safety(src: &[u8], src_stride: u32, _width: i32, height: i32) {
     let height_abs = height.unsigned_abs();
     let min = (src_stride * height_abs) as usize;
@@ -821,3 +832,470 @@ pub fn argb_to_nv12(
         .unwrap()
     }
 }
+
+pub fn i420_to_nv12(
+    src_y: &[u8],
+    src_stride_y: u32,
+    src_u: &[u8],
+    src_stride_u: u32,
+    src_v: &[u8],
+    src_stride_v: u32,
+    dst_y: &mut [u8],
+    dst_stride_y: u32,
+    dst_uv: &mut [u8],
+    dst_stride_uv: u32,
+    width: i32,
+    height: i32,
+) {
+    i420_assert_safety(
+        src_y,
+        src_stride_y,
+        src_u,
+        src_stride_u,
+        src_v,
+        src_stride_v,
+        width,
+        height,
+    );
+    nv12_assert_safety(dst_y, dst_stride_y, dst_uv, dst_stride_uv, width, height);
+
+    unsafe {
+        yuv_sys::ffi::i420_to_nv12(
+            src_y.as_ptr(),
+            src_stride_y as i32,
+            src_u.as_ptr(),
+            src_stride_u as i32,
+            src_v.as_ptr(),
+            src_stride_v as i32,
+            dst_y.as_mut_ptr(),
+            dst_stride_y as i32,
+            dst_uv.as_mut_ptr(),
+            dst_stride_uv as i32,
+            width,
+            height,
+        )
+        .unwrap()
+    }
+}
+
+pub fn i420_to_i422(
+    src_y: &[u8],
+    src_stride_y: u32,
+    src_u: &[u8],
+    src_stride_u: u32,
+    src_v: &[u8],
+    src_stride_v: u32,
+    dst_y: &mut [u8],
+    dst_stride_y: u32,
+    dst_u: &mut [u8],
+    dst_stride_u: u32,
+    dst_v: &mut [u8],
+    dst_stride_v: u32,
+    width: i32,
+    height: i32,
+) {
+    i420_assert_safety(
+        src_y,
+        src_stride_y,
+        src_u,
+        src_stride_u,
+        src_v,
+        src_stride_v,
+        width,
+        height,
+    );
+    i422_assert_safety(
+        dst_y,
+        dst_stride_y,
+        dst_u,
+        dst_stride_u,
+        dst_v,
+        dst_stride_v,
+        width,
+        height,
+    );
+
+    unsafe {
+        yuv_sys::ffi::i420_to_i422(
+            src_y.as_ptr(),
+            src_stride_y as i32,
+            src_u.as_ptr(),
+            src_stride_u as i32,
+            src_v.as_ptr(),
+            src_stride_v as i32,
+            dst_y.as_mut_ptr(),
+            dst_stride_y as i32,
+            dst_u.as_mut_ptr(),
+            dst_stride_u as i32,
+            dst_v.as_mut_ptr(),
+            dst_stride_v as i32,
+            width,
+            height,
+        )
+        .unwrap()
+    }
+}
+
+pub fn i420_to_i444(
+    src_y: &[u8],
+    src_stride_y: u32,
+    src_u: &[u8],
+    src_stride_u: u32,
+    src_v: &[u8],
+    src_stride_v: u32,
+    dst_y: &mut [u8],
+    dst_stride_y: u32,
+    dst_u: &mut [u8],
+    dst_stride_u: u32,
+    dst_v: &mut [u8],
+    dst_stride_v: u32,
+    width: i32,
+    height: i32,
+) {
+    i420_assert_safety(
+        src_y,
+        src_stride_y,
+        src_u,
+        src_stride_u,
+        src_v,
+        src_stride_v,
+        width,
+        height,
+    );
+    i444_assert_safety(
+        dst_y,
+        dst_stride_y,
+        dst_u,
+        dst_stride_u,
+        dst_v,
+        dst_stride_v,
+        width,
+        height,
+    );
+
+    unsafe {
+        yuv_sys::ffi::i420_to_i444(
+            src_y.as_ptr(),
+            src_stride_y as i32,
+            src_u.as_ptr(),
+            src_stride_u as i32,
+            src_v.as_ptr(),
+            src_stride_v as i32,
+            dst_y.as_mut_ptr(),
+            dst_stride_y as i32,
+            dst_u.as_mut_ptr(),
+            dst_stride_u as i32,
+            dst_v.as_mut_ptr(),
+            dst_stride_v as i32,
+            width,
+            height,
+        )
+        .unwrap()
+    }
+}
+
+pub fn i420_to_rgb24(
+    src_y: &[u8],
+    src_stride_y: u32,
+    src_u: &[u8],
+    src_stride_u: u32,
+    src_v: &[u8],
+    src_stride_v: u32,
+    dst_rgb24: &mut [u8],
+    dst_stride_rgb24: u32,
+    width: i32,
+    height: i32,
+) {
+    i420_assert_safety(
+        src_y,
+        src_stride_y,
+        src_u,
+        src_stride_u,
+        src_v,
+        src_stride_v,
+        width,
+        height,
+    );
+    argb_assert_safety(dst_rgb24, dst_stride_rgb24, width, height);
+
+    unsafe {
+        yuv_sys::ffi::i420_to_rgb24(
+            src_y.as_ptr(),
+            src_stride_y as i32,
+            src_u.as_ptr(),
+            src_stride_u as i32,
+            src_v.as_ptr(),
+            src_stride_v as i32,
+            dst_rgb24.as_mut_ptr(),
+            dst_stride_rgb24 as i32,
+            width,
+            height,
+        )
+        .unwrap()
+    }
+}
+
+pub fn rgb24_to_i420(
+    src_rgb24: &[u8],
+    src_stride_rgb24: u32,
+    dst_y: &mut [u8],
+    dst_stride_y: u32,
+    dst_u: &mut [u8],
+    dst_stride_u: u32,
+    dst_v: &mut [u8],
+    dst_stride_v: u32,
+    width: i32,
+    height: i32,
+) {
+    argb_assert_safety(src_rgb24, src_stride_rgb24, width, height);
+    i420_assert_safety(
+        dst_y,
+        dst_stride_y,
+        dst_u,
+        dst_stride_u,
+        dst_v,
+        dst_stride_v,
+        width,
+        height,
+    );
+
+    unsafe {
+        yuv_sys::ffi::rgb24_to_i420(
+            src_rgb24.as_ptr(),
+            src_stride_rgb24 as i32,
+            dst_y.as_mut_ptr(),
+            dst_stride_y as i32,
+            dst_u.as_mut_ptr(),
+            dst_stride_u as i32,
+            dst_v.as_mut_ptr(),
+            dst_stride_v as i32,
+            width,
+            height,
+        )
+        .unwrap()
+    }
+}
+
+pub fn rgb24_to_argb(
+    src_rgb24: &[u8],
+    src_stride_rgb24: u32,
+    dst_argb: &mut [u8],
+    dst_stride_argb: u32,
+    width: i32,
+    height: i32,
+) {
+    argb_assert_safety(src_rgb24, src_stride_rgb24, width, height);
+    argb_assert_safety(dst_argb, dst_stride_argb, width, height);
+
+    unsafe {
+        yuv_sys::ffi::rgb24_to_argb(
+            src_rgb24.as_ptr(),
+            src_stride_rgb24 as i32,
+            dst_argb.as_mut_ptr(),
+            dst_stride_argb as i32,
+            width,
+            height,
+        )
+        .unwrap()
+    }
+}
+
+/// Swaps the R and B channels, also converts ABGR to ARGB
+pub fn argb_to_abgr(
+    src_argb: &[u8],
+    src_stride_argb: u32,
+    dst_abgr: &mut [u8],
+    dst_stride_abgr: u32,
+    width: i32,
+    height: i32,
+) {
+    argb_assert_safety(src_argb, src_stride_argb, width, height);
+    argb_assert_safety(dst_abgr, dst_stride_abgr, width, height);
+
+    unsafe {
+        yuv_sys::ffi::argb_to_abgr(
+            src_argb.as_ptr(),
+            src_stride_argb as i32,
+            dst_abgr.as_mut_ptr(),
+            dst_stride_abgr as i32,
+            width,
+            height,
+        )
+        .unwrap()
+    }
+}
+
+/// Scale a single 8-bit plane (e.g. one of the planes of a I420 buffer)
+pub fn scale_plane(
+    src: &[u8],
+    src_stride: u32,
+    src_width: i32,
+    src_height: i32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    dst_width: i32,
+    dst_height: i32,
+    filtering: FilterMode,
+) {
+    argb_assert_safety(src, src_stride, src_width, src_height);
+    argb_assert_safety(dst, dst_stride, dst_width, dst_height);
+
+    unsafe {
+        yuv_sys::ffi::scale_plane(
+            src.as_ptr(),
+            src_stride as i32,
+            src_width,
+            src_height,
+            dst.as_mut_ptr(),
+            dst_stride as i32,
+            dst_width,
+            dst_height,
+            filtering as i32,
+        )
+        .unwrap()
+    }
+}
+
+pub fn nv12_scale(
+    src_y: &[u8],
+    src_stride_y: u32,
+    src_uv: &[u8],
+    src_stride_uv: u32,
+    src_width: i32,
+    src_height: i32,
+    dst_y: &mut [u8],
+    dst_stride_y: u32,
+    dst_uv: &mut [u8],
+    dst_stride_uv: u32,
+    dst_width: i32,
+    dst_height: i32,
+    filtering: FilterMode,
+) {
+    nv12_assert_safety(
+        src_y,
+        src_stride_y,
+        src_uv,
+        src_stride_uv,
+        src_width,
+        src_height,
+    );
+    nv12_assert_safety(
+        dst_y,
+        dst_stride_y,
+        dst_uv,
+        dst_stride_uv,
+        dst_width,
+        dst_height,
+    );
+
+    unsafe {
+        yuv_sys::ffi::nv12_scale(
+            src_y.as_ptr(),
+            src_stride_y as i32,
+            src_uv.as_ptr(),
+            src_stride_uv as i32,
+            src_width,
+            src_height,
+            dst_y.as_mut_ptr(),
+            dst_stride_y as i32,
+            dst_uv.as_mut_ptr(),
+            dst_stride_uv as i32,
+            dst_width,
+            dst_height,
+            filtering as i32,
+        )
+        .unwrap()
+    }
+}
+
+pub fn argb_scale(
+    src_argb: &[u8],
+    src_stride_argb: u32,
+    src_width: i32,
+    src_height: i32,
+    dst_argb: &mut [u8],
+    dst_stride_argb: u32,
+    dst_width: i32,
+    dst_height: i32,
+    filtering: FilterMode,
+) {
+    argb_assert_safety(src_argb, src_stride_argb, src_width, src_height);
+    argb_assert_safety(dst_argb, dst_stride_argb, dst_width, dst_height);
+
+    unsafe {
+        yuv_sys::ffi::argb_scale(
+            src_argb.as_ptr(),
+            src_stride_argb as i32,
+            src_width,
+            src_height,
+            dst_argb.as_mut_ptr(),
+            dst_stride_argb as i32,
+            dst_width,
+            dst_height,
+            filtering as i32,
+        )
+        .unwrap()
+    }
+}
+
+/// Rotate a single 8-bit plane, width and height are the dimensions of the source
+pub fn rotate_plane(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: i32,
+    height: i32,
+    rotation: VideoRotation,
+) {
+    let dst_height = match rotation {
+        VideoRotation::VideoRotation90 | VideoRotation::VideoRotation270 => width,
+        _ => height,
+    };
+    argb_assert_safety(src, src_stride, width, height);
+    argb_assert_safety(dst, dst_stride, 0, dst_height);
+
+    unsafe {
+        yuv_sys::ffi::rotate_plane(
+            src.as_ptr(),
+            src_stride as i32,
+            dst.as_mut_ptr(),
+            dst_stride as i32,
+            width,
+            height,
+            rotation as i32,
+        )
+        .unwrap()
+    }
+}
+
+/// Rotate an ARGB buffer, width and height are the dimensions of the source
+pub fn argb_rotate(
+    src_argb: &[u8],
+    src_stride_argb: u32,
+    dst_argb: &mut [u8],
+    dst_stride_argb: u32,
+    width: i32,
+    height: i32,
+    rotation: VideoRotation,
+) {
+    let dst_height = match rotation {
+        VideoRotation::VideoRotation90 | VideoRotation::VideoRotation270 => width,
+        _ => height,
+    };
+    argb_assert_safety(src_argb, src_stride_argb, width, height);
+    argb_assert_safety(dst_argb, dst_stride_argb, 0, dst_height);
+
+    unsafe {
+        yuv_sys::ffi::argb_rotate(
+            src_argb.as_ptr(),
+            src_stride_argb as i32,
+            dst_argb.as_mut_ptr(),
+            dst_stride_argb as i32,
+            width,
+            height,
+            rotation as i32,
+        )
+        .unwrap()
+    }
+}
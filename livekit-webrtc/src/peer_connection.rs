@@ -22,6 +22,7 @@ use crate::rtp_receiver::RtpReceiver;
 use crate::rtp_sender::RtpSender;
 use crate::rtp_transceiver::{RtpTransceiver, RtpTransceiverInit};
 use crate::session_description::SessionDescription;
+use crate::stats::RtcStats;
 use crate::{MediaType, RtcError};
 use std::fmt::Debug;
 
@@ -83,6 +84,25 @@ pub struct IceCandidateError {
     pub error_text: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct CandidateInfo {
+    /// host, srflx, prflx or relay
+    pub candidate_type: String,
+    pub protocol: String,
+    /// ip:port
+    pub address: String,
+}
+
+/// The ICE agent switched to another candidate pair, e.g. after a network change
+#[derive(Debug, Clone)]
+pub struct CandidatePairChange {
+    pub local: CandidateInfo,
+    pub remote: CandidateInfo,
+    pub reason: String,
+    pub last_data_received_ms: i64,
+    pub estimated_disconnected_time_ms: i64,
+}
+
 #[derive(Debug, Clone)]
 pub struct TrackEvent {
     pub receiver: RtpReceiver,
@@ -91,6 +111,27 @@ pub struct TrackEvent {
     pub transceiver: RtpTransceiver,
 }
 
+/// Cumulative stats of an audio receiver
+#[derive(Debug, Clone, Default)]
+pub struct AudioReceiverStats {
+    pub packets_received: u64,
+    pub packets_lost: i64,
+    // Redundant (RED) and FEC packets, the discarded ones weren't needed
+    pub fec_packets_received: u64,
+    pub fec_packets_discarded: u64,
+    pub total_samples_received: u64,
+    pub concealed_samples: u64,
+    pub concealment_events: u64,
+}
+
+impl AudioReceiverStats {
+    /// Number of lost packets recovered from the redundant audio
+    pub fn recovered_packets(&self) -> u64 {
+        self.fec_packets_received
+            .saturating_sub(self.fec_packets_discarded)
+    }
+}
+
 pub type OnConnectionChange = Box<dyn FnMut(PeerConnectionState) + Send + Sync>;
 pub type OnDataChannel = Box<dyn FnMut(DataChannel) + Send + Sync>;
 pub type OnIceCandidate = Box<dyn FnMut(IceCandidate) + Send + Sync>;
@@ -99,6 +140,7 @@ pub type OnIceConnectionChange = Box<dyn FnMut(IceConnectionState) + Send + Sync
 pub type OnIceGatheringChange = Box<dyn FnMut(IceGatheringState) + Send + Sync>;
 pub type OnNegotiationNeeded = Box<dyn FnMut(u32) + Send + Sync>;
 pub type OnSignalingChange = Box<dyn FnMut(SignalingState) + Send + Sync>;
+pub type OnSelectedCandidatePairChange = Box<dyn FnMut(CandidatePairChange) + Send + Sync>;
 pub type OnTrack = Box<dyn FnMut(TrackEvent) + Send + Sync>;
 
 #[derive(Clone)]
@@ -137,6 +179,32 @@ impl PeerConnection {
         self.handle.add_ice_candidate(candidate).await
     }
 
+    pub async fn get_audio_receiver_stats(
+        &self,
+        receiver: &RtpReceiver,
+    ) -> Result<AudioReceiverStats, RtcError> {
+        self.handle.get_audio_receiver_stats(receiver).await
+    }
+
+    /// Stats of the whole peer connection
+    pub async fn get_stats(&self) -> Result<Vec<RtcStats>, RtcError> {
+        self.handle.get_stats().await
+    }
+
+    /// Stats of the outbound streams of the sender and their dependencies (codecs,
+    /// transport, candidate pairs...)
+    pub async fn get_sender_stats(&self, sender: &RtpSender) -> Result<Vec<RtcStats>, RtcError> {
+        self.handle.get_sender_stats(sender).await
+    }
+
+    /// Stats of the inbound stream of the receiver and its dependencies
+    pub async fn get_receiver_stats(
+        &self,
+        receiver: &RtpReceiver,
+    ) -> Result<Vec<RtcStats>, RtcError> {
+        self.handle.get_receiver_stats(receiver).await
+    }
+
     pub fn create_data_channel(
         &self,
         label: &str,
@@ -251,6 +319,10 @@ impl PeerConnection {
     pub fn on_track(&self, f: Option<OnTrack>) {
         self.handle.on_track(f)
     }
+
+    pub fn on_selected_candidate_pair_change(&self, f: Option<OnSelectedCandidatePairChange>) {
+        self.handle.on_selected_candidate_pair_change(f)
+    }
 }
 
 impl Debug for PeerConnection {
@@ -279,6 +351,7 @@ mod tests {
                 urls: vec!["stun:stun1.l.google.com:19302".to_string()],
                 username: "".into(),
                 password: "".into(),
+                hostname: "".into(),
             }],
             continual_gathering_policy: ContinualGatheringPolicy::GatherOnce,
             ice_transport_type: IceTransportsType::All,
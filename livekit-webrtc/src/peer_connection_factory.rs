@@ -24,6 +24,9 @@ pub struct IceServer {
     pub urls: Vec<String>,
     pub username: String,
     pub password: String,
+    /// Hostname of the urls that only contain an IP address, used by TLS (SNI and
+    /// certificate validation). Can be empty
+    pub hostname: String,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -92,9 +95,33 @@ pub mod native {
     use crate::video_source::native::NativeVideoSource;
     use crate::video_track::RtcVideoTrack;
 
+    /// Use of the platform encoders (VideoToolbox, MediaCodec, ...) for a codec
+    #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+    pub enum HardwarePreference {
+        /// Use the hardware encoder when available, else the software one
+        #[default]
+        Prefer,
+        /// Only use the hardware encoder, the codec isn't offered without one
+        Require,
+        /// Always use the software encoder
+        Disable,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct VideoCodecCapability {
+        pub codec: String, // e.g "H264"
+        pub hardware_encoder: bool,
+        pub software_encoder: bool,
+        pub hardware_decoder: bool,
+        pub software_decoder: bool,
+    }
+
     pub trait PeerConnectionFactoryExt {
         fn create_video_track(&self, label: &str, source: NativeVideoSource) -> RtcVideoTrack;
         fn create_audio_track(&self, label: &str, source: NativeAudioSource) -> RtcAudioTrack;
+        fn video_codec_capabilities(&self) -> Vec<VideoCodecCapability>;
+        /// Applies to the encoders created afterwards, the codec name is case insensitive
+        fn set_hardware_encoder_preference(&self, codec: &str, preference: HardwarePreference);
     }
 
     impl PeerConnectionFactoryExt for PeerConnectionFactory {
@@ -105,5 +132,14 @@ pub mod native {
         fn create_audio_track(&self, label: &str, source: NativeAudioSource) -> RtcAudioTrack {
             self.handle.create_audio_track(label, source)
         }
+
+        fn video_codec_capabilities(&self) -> Vec<VideoCodecCapability> {
+            self.handle.video_codec_capabilities()
+        }
+
+        fn set_hardware_encoder_preference(&self, codec: &str, preference: HardwarePreference) {
+            self.handle
+                .set_hardware_encoder_preference(codec, preference)
+        }
     }
 }
@@ -22,8 +22,8 @@ pub use crate::ice_candidate::IceCandidate;
 pub use crate::media_stream::MediaStream;
 pub use crate::media_stream_track::{MediaStreamTrack, RtcTrackState};
 pub use crate::peer_connection::{
-    AnswerOptions, IceConnectionState, IceGatheringState, OfferOptions, PeerConnection,
-    PeerConnectionState, SignalingState,
+    AnswerOptions, AudioReceiverStats, CandidateInfo, CandidatePairChange, IceConnectionState,
+    IceGatheringState, OfferOptions, PeerConnection, PeerConnectionState, SignalingState,
 };
 pub use crate::peer_connection_factory::{
     ContinualGatheringPolicy, IceServer, IceTransportsType, PeerConnectionFactory, RtcConfiguration,
@@ -33,11 +33,12 @@ pub use crate::rtp_receiver::RtpReceiver;
 pub use crate::rtp_sender::RtpSender;
 pub use crate::rtp_transceiver::{RtpTransceiver, RtpTransceiverDirection, RtpTransceiverInit};
 pub use crate::session_description::{SdpType, SessionDescription};
+pub use crate::stats::RtcStats;
 pub use crate::video_frame::{
     BoxVideoFrame, BoxVideoFrameBuffer, I010Buffer, I420ABuffer, I420Buffer, I422Buffer,
     I444Buffer, NV12Buffer, VideoFormatType, VideoFrame, VideoFrameBuffer, VideoFrameBufferType,
     VideoRotation,
 };
 pub use crate::video_source::{RtcVideoSource, VideoResolution};
-pub use crate::video_track::RtcVideoTrack;
+pub use crate::video_track::{RtcVideoTrack, VideoContentHint};
 pub use crate::{MediaType, RtcError, RtcErrorType};
@@ -22,6 +22,14 @@ pub enum Priority {
     High,
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DegradationPreference {
+    Disabled,
+    MaintainFramerate,
+    MaintainResolution,
+    Balanced,
+}
+
 #[derive(Debug, Clone)]
 pub struct RtpHeaderExtensionParameters {
     pub uri: String,
@@ -58,6 +66,7 @@ pub struct RtpEncodingParameters {
     pub priority: Priority,
     pub rid: String,
     pub scale_resolution_down_by: Option<f64>,
+    pub scalability_mode: Option<String>, // e.g. L3T3_KEY, for SVC codecs
 }
 
 #[derive(Debug, Clone)]
@@ -89,6 +98,7 @@ impl Default for RtpEncodingParameters {
             priority: Priority::Low,
             rid: String::default(),
             scale_resolution_down_by: None,
+            scalability_mode: None,
         }
     }
 }
@@ -32,6 +32,11 @@ impl RtpReceiver {
     pub fn parameters(&self) -> RtpParameters {
         self.handle.parameters()
     }
+
+    /// Minimum delay kept by the jitter buffer, None restores the default behavior
+    pub fn set_jitter_buffer_minimum_delay(&self, delay_seconds: Option<f64>) {
+        self.handle.set_jitter_buffer_minimum_delay(delay_seconds)
+    }
 }
 
 impl Debug for RtpReceiver {
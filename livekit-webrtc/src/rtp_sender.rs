@@ -15,7 +15,9 @@
 use std::fmt::Debug;
 
 use crate::{
-    imp::rtp_sender as imp_rs, media_stream_track::MediaStreamTrack, rtp_parameters::RtpParameters,
+    imp::rtp_sender as imp_rs,
+    media_stream_track::MediaStreamTrack,
+    rtp_parameters::{DegradationPreference, RtpEncodingParameters, RtpParameters},
     RtcError,
 };
 
@@ -40,6 +42,30 @@ impl RtpSender {
     pub fn set_parameters(&self, parameters: RtpParameters) -> Result<(), RtcError> {
         self.handle.set_parameters(parameters)
     }
+
+    /// Enable or disable the encodings by rid, None leaves the encoding untouched
+    pub fn set_encodings_active(
+        &self,
+        active: impl Fn(&str) -> Option<bool>,
+    ) -> Result<(), RtcError> {
+        self.handle.set_encodings_active(&active)
+    }
+
+    /// Change the encodings in place (bitrate, resolution, ...), unlike set_parameters the
+    /// read-only fields of the current parameters are kept
+    pub fn update_encodings(
+        &self,
+        mut update: impl FnMut(&mut [RtpEncodingParameters]),
+    ) -> Result<(), RtcError> {
+        self.handle.update_encodings(&mut update)
+    }
+
+    pub fn set_degradation_preference(
+        &self,
+        preference: DegradationPreference,
+    ) -> Result<(), RtcError> {
+        self.handle.set_degradation_preference(preference)
+    }
 }
 
 impl Debug for RtpSender {
@@ -0,0 +1,179 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Typed subset of the W3C RTCStats dictionaries (https://www.w3.org/TR/webrtc-stats/)
+//! returned by PeerConnection::get_stats. The fields missing in a report are left to
+//! their default value
+
+use crate::{RtcError, RtcErrorType};
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum RtcStats {
+    Codec(CodecStats),
+    InboundRtp(InboundRtpStats),
+    OutboundRtp(OutboundRtpStats),
+    RemoteInboundRtp(RemoteInboundRtpStats),
+    CandidatePair(CandidatePairStats),
+    /// Transport, candidates, certificates, media sources...
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CodecStats {
+    pub id: String,
+    /// In milliseconds since the unix epoch
+    pub timestamp: f64,
+    pub payload_type: u32,
+    pub mime_type: String,
+    pub clock_rate: u32,
+    pub channels: u32,
+    pub sdp_fmtp_line: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct InboundRtpStats {
+    pub id: String,
+    pub timestamp: f64,
+    pub ssrc: u32,
+    pub kind: String,
+    pub track_identifier: String,
+    pub mid: String,
+    pub codec_id: String,
+    pub packets_received: u64,
+    pub packets_lost: i64,
+    pub bytes_received: u64,
+    /// In seconds
+    pub jitter: f64,
+    pub frames_decoded: u32,
+    pub frames_dropped: u32,
+    pub frame_width: u32,
+    pub frame_height: u32,
+    pub frames_per_second: f64,
+    pub nack_count: u32,
+    pub pli_count: u32,
+    pub concealed_samples: u64,
+    pub total_samples_received: u64,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct OutboundRtpStats {
+    pub id: String,
+    pub timestamp: f64,
+    pub ssrc: u32,
+    pub kind: String,
+    pub mid: String,
+    /// Set for the layers of a simulcast track
+    pub rid: String,
+    pub codec_id: String,
+    pub packets_sent: u64,
+    pub bytes_sent: u64,
+    pub retransmitted_packets_sent: u64,
+    /// In bits per second
+    pub target_bitrate: f64,
+    pub frames_encoded: u32,
+    pub frame_width: u32,
+    pub frame_height: u32,
+    pub frames_per_second: f64,
+    pub quality_limitation_reason: String,
+    pub nack_count: u32,
+    pub pli_count: u32,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RemoteInboundRtpStats {
+    pub id: String,
+    pub timestamp: f64,
+    pub ssrc: u32,
+    pub kind: String,
+    /// The outbound-rtp stats reported on by the remote peer
+    pub local_id: String,
+    pub codec_id: String,
+    pub packets_lost: i64,
+    pub fraction_lost: f64,
+    pub jitter: f64,
+    /// In seconds
+    pub round_trip_time: f64,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CandidatePairStats {
+    pub id: String,
+    pub timestamp: f64,
+    pub transport_id: String,
+    pub local_candidate_id: String,
+    pub remote_candidate_id: String,
+    pub state: String,
+    pub nominated: bool,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    /// In seconds
+    pub current_round_trip_time: f64,
+    /// In bits per second
+    pub available_outgoing_bitrate: f64,
+    pub available_incoming_bitrate: f64,
+}
+
+/// Parse the JSON of a native RTCStatsReport, an empty string is an empty report
+pub(crate) fn parse(json: &str) -> Result<Vec<RtcStats>, RtcError> {
+    if json.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    serde_json::from_str(json).map_err(|err| RtcError {
+        error_type: RtcErrorType::Internal,
+        message: format!("failed to parse the stats report: {}", err),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_report() {
+        let json = r#"[
+            {"type":"codec","id":"COT01_111","timestamp":1700000000000.0,"payloadType":111,
+             "mimeType":"audio/opus","clockRate":48000,"channels":2},
+            {"type":"outbound-rtp","id":"OT01V1","timestamp":1700000000000.0,"ssrc":1234,
+             "kind":"video","rid":"f","packetsSent":120,"bytesSent":96000,
+             "qualityLimitationReason":"bandwidth"},
+            {"type":"remote-inbound-rtp","id":"RI01V1","timestamp":1700000000000.0,
+             "ssrc":1234,"localId":"OT01V1","roundTripTime":0.045},
+            {"type":"candidate-pair","id":"CP01","timestamp":1700000000000.0,
+             "state":"succeeded","nominated":true,"currentRoundTripTime":0.04},
+            {"type":"transport","id":"T01","timestamp":1700000000000.0}
+        ]"#;
+
+        let stats = parse(json).unwrap();
+        assert_eq!(stats.len(), 5);
+        assert!(matches!(&stats[0], RtcStats::Codec(c) if c.mime_type == "audio/opus"));
+        assert!(matches!(
+            &stats[1],
+            RtcStats::OutboundRtp(o) if o.rid == "f" && o.frames_encoded == 0
+        ));
+        assert!(matches!(&stats[2], RtcStats::RemoteInboundRtp(r) if r.local_id == "OT01V1"));
+        assert!(matches!(&stats[3], RtcStats::CandidatePair(p) if p.nominated));
+        assert!(matches!(stats[4], RtcStats::Other));
+
+        assert!(parse("").unwrap().is_empty());
+    }
+}
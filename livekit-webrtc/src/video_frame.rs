@@ -56,7 +56,9 @@ where
     T: AsRef<dyn VideoFrameBuffer>,
 {
     pub rotation: VideoRotation,
-    pub timestamp_us: i64, // When the frame was captured in microseconds
+    pub timestamp_us: i64,  // When the frame was captured in microseconds
+    pub rtp_timestamp: u32, // 90kHz RTP timestamp of a received frame, 0 for local frames
+    pub ntp_time_ms: i64,   // Capture time in the NTP clock of the sender, 0 if unknown
     pub buffer: T,
 }
 
@@ -393,11 +395,60 @@ impl NV12Buffer {
 
 #[cfg(not(target_arch = "wasm32"))]
 pub mod native {
-    use super::{vf_imp, I420Buffer, VideoFormatType, VideoFrameBuffer, VideoFrameBufferType};
+    use super::{
+        vf_imp, I420Buffer, VideoFormatType, VideoFrame, VideoFrameBuffer, VideoFrameBufferType,
+    };
+    use std::ffi::c_void;
     use std::fmt::Debug;
 
     new_buffer_type!(NativeBuffer, Native, as_native);
 
+    /// Platform buffer behind a NativeBuffer (e.g. decoded by a hardware decoder), renderers
+    /// can use it without copying the frame to the CPU. The handle is only valid while the
+    /// NativeBuffer is alive, retain it (e.g. CVPixelBufferRetain) to keep it longer
+    #[derive(Debug, Clone)]
+    #[non_exhaustive]
+    pub enum NativeBufferHandle {
+        /// CVPixelBufferRef (macOS/iOS)
+        CVPixelBuffer(*mut c_void),
+        /// ID3D11Texture2D (Windows), usually NV12. subresource is the index inside a
+        /// texture array. Only produced by platform decoders, the builtin decoders output
+        /// I420 buffers
+        D3D11Texture {
+            texture: *mut c_void,
+            subresource: u32,
+        },
+        /// DMA-BUF (Linux), fourcc and modifier use the DRM definitions. Only produced by
+        /// platform decoders, the builtin decoders output I420 buffers
+        DmaBuf {
+            fourcc: u32,
+            modifier: u64,
+            planes: Vec<DmaBufPlane>,
+        },
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    pub struct DmaBufPlane {
+        pub fd: i32,
+        pub offset: u32,
+        pub stride: u32,
+    }
+
+    impl NativeBuffer {
+        /// None when the platform buffer isn't exposed (e.g. Android, or a buffer not
+        /// created by a platform decoder)
+        pub fn platform_handle(&self) -> Option<NativeBufferHandle> {
+            self.handle.platform_handle()
+        }
+    }
+
+    impl<T: AsRef<dyn VideoFrameBuffer>> VideoFrame<T> {
+        /// Platform buffer of the frame, None for the buffers stored in memory (e.g. I420)
+        pub fn platform_handle(&self) -> Option<NativeBufferHandle> {
+            self.buffer.as_ref().as_native()?.platform_handle()
+        }
+    }
+
     pub trait I420BufferExt {
         fn new(width: u32, height: u32) -> Self;
     }
@@ -438,6 +489,12 @@ pub mod native {
     }
 }
 
+/// Conversion, scaling and rotation between pixel formats (libyuv)
+#[cfg(not(target_arch = "wasm32"))]
+pub mod conversion {
+    pub use crate::imp::video_conversion::*;
+}
+
 #[cfg(target_arch = "wasm32")]
 pub mod web {
     use super::VideoFrameBuffer;
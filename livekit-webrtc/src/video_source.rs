@@ -41,12 +41,22 @@ impl RtcVideoSource {
 #[cfg(not(target_arch = "wasm32"))]
 pub mod native {
     use super::*;
-    use crate::video_frame::{VideoFrame, VideoFrameBuffer};
+    use crate::native::encoded_video_stream::EncodedVideoFrame;
+    use crate::video_frame::{BoxVideoFrame, VideoFrame, VideoFrameBuffer};
+    use parking_lot::Mutex;
     use std::fmt::{Debug, Formatter};
+    use std::sync::Arc;
+
+    /// Sits between the capture and the encoder, e.g. to blur or replace the background.
+    /// Returning None drops the frame.
+    pub trait VideoProcessor: Send {
+        fn process(&mut self, frame: BoxVideoFrame) -> Option<BoxVideoFrame>;
+    }
 
     #[derive(Clone)]
     pub struct NativeVideoSource {
         pub(crate) handle: vs_imp::NativeVideoSource,
+        processors: Arc<Mutex<Vec<Box<dyn VideoProcessor>>>>,
     }
 
     impl Debug for NativeVideoSource {
@@ -65,11 +75,59 @@ pub mod native {
         pub fn new(resolution: VideoResolution) -> Self {
             Self {
                 handle: vs_imp::NativeVideoSource::new(resolution),
+                processors: Default::default(),
             }
         }
 
         pub fn capture_frame<T: AsRef<dyn VideoFrameBuffer>>(&self, frame: &VideoFrame<T>) {
-            self.handle.capture_frame(frame)
+            let mut processors = self.processors.lock();
+            if processors.is_empty() {
+                drop(processors);
+                self.handle.capture_frame(frame);
+                return;
+            }
+
+            // The processors own the frames they receive, so copy the captured buffer
+            let mut processed = BoxVideoFrame {
+                rotation: frame.rotation,
+                timestamp_us: frame.timestamp_us,
+                rtp_timestamp: frame.rtp_timestamp,
+                ntp_time_ms: frame.ntp_time_ms,
+                buffer: Box::new(frame.buffer.as_ref().to_i420()),
+            };
+
+            for processor in processors.iter_mut() {
+                match processor.process(processed) {
+                    Some(frame) => processed = frame,
+                    None => return,
+                }
+            }
+
+            drop(processors);
+            self.handle.capture_frame(&processed);
+        }
+
+        /// Processors are chained in the order they are added
+        pub fn add_processor(&self, processor: Box<dyn VideoProcessor>) {
+            self.processors.lock().push(processor);
+        }
+
+        pub fn clear_processors(&self) {
+            self.processors.lock().clear();
+        }
+
+        /// Send a frame that is already encoded (e.g. forwarded from an EncodedVideoStream)
+        /// without re-encoding it. The codec must be the one negotiated for the track
+        /// (VP8, VP9 or H264) and the track must be published without simulcast.
+        /// The processors aren't applied. Returns false while waiting for the first key frame
+        pub fn capture_encoded_frame(&self, frame: &EncodedVideoFrame, timestamp_us: i64) -> bool {
+            self.handle.capture_encoded_frame(frame, timestamp_us)
+        }
+
+        /// True once after a receiver asked for a key frame, the next encoded frame should
+        /// then be a key frame
+        pub fn take_key_frame_request(&self) -> bool {
+            self.handle.take_key_frame_request()
         }
 
         pub fn video_resolution(&self) -> VideoResolution {
@@ -27,6 +27,15 @@ pub mod native {
     use std::task::{Context, Poll};
     use tokio_stream::Stream;
 
+    /// Limits applied to the frames before they are delivered, None delivers every
+    /// frame at the resolution of the decoder
+    #[derive(Debug, Default, Clone)]
+    pub struct VideoStreamOptions {
+        pub max_fps: Option<u32>,
+        // Frames whose width or height is larger are downscaled to it, keeping the aspect ratio
+        pub max_dimension: Option<u32>,
+    }
+
     pub struct NativeVideoStream {
         pub(crate) handle: stream_imp::NativeVideoStream,
     }
@@ -41,8 +50,12 @@ pub mod native {
 
     impl NativeVideoStream {
         pub fn new(video_track: RtcVideoTrack) -> Self {
+            Self::with_options(video_track, VideoStreamOptions::default())
+        }
+
+        pub fn with_options(video_track: RtcVideoTrack, options: VideoStreamOptions) -> Self {
             Self {
-                handle: stream_imp::NativeVideoStream::new(video_track),
+                handle: stream_imp::NativeVideoStream::new(video_track, options),
             }
         }
 
@@ -17,6 +17,15 @@ use crate::media_stream_track::media_stream_track;
 use crate::media_stream_track::RtcTrackState;
 use std::fmt::Debug;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VideoContentHint {
+    #[default]
+    None,
+    Fluid,
+    Detailed,
+    Text,
+}
+
 #[derive(Clone)]
 pub struct RtcVideoTrack {
     pub(crate) handle: imp_vt::RtcVideoTrack,
@@ -24,6 +33,14 @@ pub struct RtcVideoTrack {
 
 impl RtcVideoTrack {
     media_stream_track!();
+
+    pub fn content_hint(&self) -> VideoContentHint {
+        self.handle.content_hint()
+    }
+
+    pub fn set_content_hint(&self, hint: VideoContentHint) {
+        self.handle.set_content_hint(hint)
+    }
 }
 
 impl Debug for RtcVideoTrack {
@@ -13,14 +13,21 @@
 // limitations under the License.
 
 pub mod proto;
+#[cfg(feature = "recording")]
+pub mod recording;
 mod room;
 mod rtc_engine;
+pub mod track_processors;
+#[cfg(feature = "whip")]
+pub mod whip;
 
 pub mod webrtc {
     pub use livekit_webrtc::*;
 }
 
 pub use room::*;
+// Used by applications driving the engine without a Room (e.g. RtcEngine::restart_ice)
+pub use rtc_engine::{EngineError, EngineEvent, EngineOptions, RtcEngine};
 
 /// `use livekit::prelude::*;` to import livekit types
 pub mod prelude;
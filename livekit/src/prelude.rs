@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-pub use crate::participant::{ConnectionQuality, LocalParticipant, Participant, RemoteParticipant};
+pub use crate::participant::{
+    ConnectionQuality, LocalParticipant, Participant, ParticipantKind, ParticipantPermission,
+    RemoteParticipant,
+};
 
 pub use crate::{
     ConnectionState, DataPacketKind, Room, RoomError, RoomEvent, RoomOptions, RoomResult,
@@ -22,7 +25,8 @@ pub use crate::publication::{LocalTrackPublication, RemoteTrackPublication, Trac
 
 pub use crate::track::{
     AudioTrack, LocalAudioTrack, LocalTrack, LocalVideoTrack, RemoteAudioTrack, RemoteTrack,
-    RemoteVideoTrack, StreamState, Track, TrackDimension, TrackKind, TrackSource, VideoTrack,
+    RemoteVideoTrack, StreamState, Track, TrackDimension, TrackKind, TrackSource, VideoQuality,
+    VideoTrack,
 };
 
 pub use crate::id::*;
@@ -22,6 +22,28 @@ impl From<ConnectionQuality> for participant::ConnectionQuality {
             ConnectionQuality::Excellent => Self::Excellent,
             ConnectionQuality::Good => Self::Good,
             ConnectionQuality::Poor => Self::Poor,
+            ConnectionQuality::Lost => Self::Lost,
+        }
+    }
+}
+
+impl From<DisconnectReason> for crate::DisconnectReason {
+    fn from(value: DisconnectReason) -> Self {
+        match value {
+            DisconnectReason::UnknownReason => Self::UnknownReason,
+            DisconnectReason::ClientInitiated => Self::ClientInitiated,
+            DisconnectReason::DuplicateIdentity => Self::DuplicateIdentity,
+            DisconnectReason::ServerShutdown => Self::ServerShutdown,
+            DisconnectReason::ParticipantRemoved => Self::ParticipantRemoved,
+            DisconnectReason::RoomDeleted => Self::RoomDeleted,
+            DisconnectReason::StateMismatch => Self::StateMismatch,
+            DisconnectReason::JoinFailure => Self::JoinFailure,
+            DisconnectReason::Migration => Self::Migration,
+            DisconnectReason::SignalClose => Self::SignalClose,
+            DisconnectReason::RoomClosed => Self::RoomClosed,
+            DisconnectReason::UserUnavailable => Self::UserUnavailable,
+            DisconnectReason::UserRejected => Self::UserRejected,
+            DisconnectReason::SipTrunkFailure => Self::SipTrunkFailure,
         }
     }
 }
@@ -71,6 +93,43 @@ impl From<track::TrackSource> for TrackSource {
     }
 }
 
+impl From<track::VideoQuality> for VideoQuality {
+    fn from(quality: track::VideoQuality) -> Self {
+        match quality {
+            track::VideoQuality::Low => Self::Low,
+            track::VideoQuality::Medium => Self::Medium,
+            track::VideoQuality::High => Self::High,
+        }
+    }
+}
+
+impl From<participant_info::Kind> for participant::ParticipantKind {
+    fn from(kind: participant_info::Kind) -> Self {
+        match kind {
+            participant_info::Kind::Standard => Self::Standard,
+            participant_info::Kind::Ingress => Self::Ingress,
+            participant_info::Kind::Egress => Self::Egress,
+            participant_info::Kind::Sip => Self::Sip,
+            participant_info::Kind::Agent => Self::Agent,
+        }
+    }
+}
+
+impl From<ParticipantPermission> for participant::ParticipantPermission {
+    #[allow(deprecated)]
+    fn from(permission: ParticipantPermission) -> Self {
+        Self {
+            can_publish_sources: permission.can_publish_sources().map(Into::into).collect(),
+            can_subscribe: permission.can_subscribe,
+            can_publish: permission.can_publish,
+            can_publish_data: permission.can_publish_data,
+            hidden: permission.hidden,
+            recorder: permission.recorder,
+            can_update_metadata: permission.can_update_metadata,
+        }
+    }
+}
+
 impl From<DataPacketKind> for data_packet::Kind {
     fn from(kind: DataPacketKind) -> Self {
         match kind {
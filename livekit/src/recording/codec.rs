@@ -0,0 +1,479 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversion of the encoded frames received from WebRTC to the format expected
+//! by the containers, and their codec configurations.
+
+/// Pre-skip of the Opus encoder of WebRTC (libopus), at 48kHz
+pub const OPUS_PRE_SKIP: u16 = 312;
+
+const H264_NAL_SPS: u8 = 7;
+const H264_NAL_PPS: u8 = 8;
+const H264_NAL_AUD: u8 = 9;
+
+const AV1_OBU_SEQUENCE_HEADER: u8 = 1;
+const AV1_OBU_TEMPORAL_DELIMITER: u8 = 2;
+
+/// NAL units of an H264 Annex B frame (start code prefixed)
+fn annexb_nalus(data: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let mut starts = Vec::new();
+    let mut i = 0;
+    while i + 3 <= data.len() {
+        if data[i] == 0 && data[i + 1] == 0 && data[i + 2] == 1 {
+            starts.push(i + 3);
+            i += 3;
+        } else {
+            i += 1;
+        }
+    }
+
+    let ends = starts
+        .iter()
+        .skip(1)
+        .map(|next| {
+            // The 4 bytes start code has an extra leading zero
+            let end = next - 3;
+            if end > 0 && data[end - 1] == 0 {
+                end - 1
+            } else {
+                end
+            }
+        })
+        .chain(std::iter::once(data.len()))
+        .collect::<Vec<_>>();
+
+    starts
+        .into_iter()
+        .zip(ends)
+        .map(move |(start, end)| &data[start..end.max(start)])
+        .filter(|nalu| !nalu.is_empty())
+}
+
+/// Convert an Annex B frame to the 4 bytes length prefixed NAL units used by Matroska
+/// and MP4, the access unit delimiters are removed
+pub fn h264_to_avcc(data: &[u8]) -> Vec<u8> {
+    let mut avcc = Vec::with_capacity(data.len() + 16);
+    for nalu in annexb_nalus(data) {
+        if nalu[0] & 0x1F == H264_NAL_AUD {
+            continue;
+        }
+        avcc.extend_from_slice(&(nalu.len() as u32).to_be_bytes());
+        avcc.extend_from_slice(nalu);
+    }
+    avcc
+}
+
+/// AVCDecoderConfigurationRecord built from the SPS and PPS of a key frame,
+/// None if the frame doesn't contain them
+pub fn h264_config(data: &[u8]) -> Option<Vec<u8>> {
+    let sps = annexb_nalus(data).find(|nalu| nalu[0] & 0x1F == H264_NAL_SPS && nalu.len() >= 4)?;
+    let pps = annexb_nalus(data).find(|nalu| nalu[0] & 0x1F == H264_NAL_PPS)?;
+
+    let mut config = vec![1, sps[1], sps[2], sps[3], 0xFF, 0xE1];
+    config.extend_from_slice(&(sps.len() as u16).to_be_bytes());
+    config.extend_from_slice(sps);
+    config.push(1);
+    config.extend_from_slice(&(pps.len() as u16).to_be_bytes());
+    config.extend_from_slice(pps);
+    Some(config)
+}
+
+/// Profile of a VP9 frame, from its uncompressed header
+pub fn vp9_profile(data: &[u8]) -> u8 {
+    let Some(first) = data.first() else {
+        return 0;
+    };
+    let low = (first >> 5) & 1;
+    let high = (first >> 4) & 1;
+    high << 1 | low
+}
+
+struct Obu<'a> {
+    obu_type: u8,
+    data: &'a [u8], // Whole OBU, header included
+    payload: &'a [u8],
+}
+
+fn read_leb128(data: &[u8]) -> Option<(usize, usize)> {
+    let mut value = 0usize;
+    for (i, byte) in data.iter().take(8).enumerate() {
+        value |= ((byte & 0x7F) as usize) << (i * 7);
+        if byte & 0x80 == 0 {
+            return Some((value, i + 1));
+        }
+    }
+    None
+}
+
+/// OBUs of an AV1 temporal unit, stops at the first malformed OBU
+fn av1_obus(mut data: &[u8]) -> impl Iterator<Item = Obu<'_>> {
+    std::iter::from_fn(move || {
+        let header = *data.first()?;
+        let obu_type = (header >> 3) & 0x0F;
+        let header_len = if header & 0x04 != 0 { 2 } else { 1 };
+        let has_size = header & 0x02 != 0;
+
+        let (payload_start, payload_len) = if has_size {
+            let (size, len) = read_leb128(data.get(header_len..)?)?;
+            (header_len + len, size)
+        } else {
+            (header_len, data.len().checked_sub(header_len)?)
+        };
+
+        let end = payload_start.checked_add(payload_len)?;
+        let obu = Obu {
+            obu_type,
+            data: data.get(..end)?,
+            payload: data.get(payload_start..end)?,
+        };
+        data = &data[end..];
+        Some(obu)
+    })
+}
+
+/// Remove the temporal delimiters, Matroska and MP4 store the temporal units without them
+pub fn av1_strip_temporal_delimiters(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len());
+    for obu in av1_obus(data) {
+        if obu.obu_type != AV1_OBU_TEMPORAL_DELIMITER {
+            out.extend_from_slice(obu.data);
+        }
+    }
+    out
+}
+
+struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn read(&mut self, bits: u32) -> Option<u32> {
+        let mut value = 0u32;
+        for _ in 0..bits {
+            let byte = self.data.get(self.pos / 8)?;
+            let bit = (byte >> (7 - self.pos % 8)) & 1;
+            value = value << 1 | bit as u32;
+            self.pos += 1;
+        }
+        Some(value)
+    }
+
+    fn flag(&mut self) -> Option<bool> {
+        Some(self.read(1)? == 1)
+    }
+
+    fn uvlc(&mut self) -> Option<u32> {
+        let mut leading_zeros = 0;
+        while !self.flag()? {
+            leading_zeros += 1;
+            if leading_zeros >= 32 {
+                return Some(u32::MAX);
+            }
+        }
+        Some(self.read(leading_zeros)? + ((1u64 << leading_zeros) - 1) as u32)
+    }
+}
+
+/// AV1CodecConfigurationRecord of a key frame, built from its sequence header.
+/// None if the frame doesn't contain a sequence header
+pub fn av1_config(data: &[u8]) -> Option<Vec<u8>> {
+    let obu = av1_obus(data).find(|obu| obu.obu_type == AV1_OBU_SEQUENCE_HEADER)?;
+    let header = parse_av1_sequence_header(obu.payload)?;
+
+    let mut config = vec![
+        0x81, // marker and version 1
+        header.profile << 5 | header.level,
+        header.tier << 7
+            | (header.high_bitdepth as u8) << 6
+            | (header.twelve_bit as u8) << 5
+            | (header.monochrome as u8) << 4
+            | header.subsampling_x << 3
+            | header.subsampling_y << 2
+            | header.chroma_sample_position,
+        0,
+    ];
+    // configOBUs, the decoders are initialized with the sequence header
+    config.extend_from_slice(obu.data);
+    Some(config)
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Av1SequenceHeader {
+    profile: u8,
+    level: u8, // Of the first operating point
+    tier: u8,
+    high_bitdepth: bool,
+    twelve_bit: bool,
+    monochrome: bool,
+    subsampling_x: u8,
+    subsampling_y: u8,
+    chroma_sample_position: u8,
+}
+
+/// See the section 5.5 of the AV1 specification
+fn parse_av1_sequence_header(payload: &[u8]) -> Option<Av1SequenceHeader> {
+    let mut r = BitReader::new(payload);
+    let mut header = Av1SequenceHeader {
+        profile: r.read(3)? as u8,
+        ..Default::default()
+    };
+    let _still_picture = r.flag()?;
+    let reduced_still_picture_header = r.flag()?;
+
+    if reduced_still_picture_header {
+        header.level = r.read(5)? as u8;
+    } else {
+        let mut decoder_model_info_present = false;
+        let mut buffer_delay_length = 0;
+        if r.flag()? {
+            // timing_info
+            r.read(32)?;
+            r.read(32)?;
+            if r.flag()? {
+                r.uvlc()?;
+            }
+
+            decoder_model_info_present = r.flag()?;
+            if decoder_model_info_present {
+                buffer_delay_length = r.read(5)? + 1;
+                r.read(32)?;
+                r.read(5)?;
+                r.read(5)?;
+            }
+        }
+
+        let initial_display_delay_present = r.flag()?;
+        let operating_points = r.read(5)? + 1;
+        for i in 0..operating_points {
+            r.read(12)?;
+            let level = r.read(5)? as u8;
+            let tier = if level > 7 { r.read(1)? as u8 } else { 0 };
+            if decoder_model_info_present && r.flag()? {
+                r.read(buffer_delay_length)?;
+                r.read(buffer_delay_length)?;
+                r.read(1)?;
+            }
+            if initial_display_delay_present && r.flag()? {
+                r.read(4)?;
+            }
+
+            if i == 0 {
+                header.level = level;
+                header.tier = tier;
+            }
+        }
+    }
+
+    let frame_width_bits = r.read(4)? + 1;
+    let frame_height_bits = r.read(4)? + 1;
+    r.read(frame_width_bits)?;
+    r.read(frame_height_bits)?;
+
+    if !reduced_still_picture_header && r.flag()? {
+        // frame_id_numbers_present_flag
+        r.read(4)?;
+        r.read(3)?;
+    }
+
+    // use_128x128_superblock, enable_filter_intra, enable_intra_edge_filter
+    r.read(3)?;
+
+    if !reduced_still_picture_header {
+        // enable_interintra_compound, enable_masked_compound, enable_warped_motion,
+        // enable_dual_filter
+        r.read(4)?;
+        let enable_order_hint = r.flag()?;
+        if enable_order_hint {
+            // enable_jnt_comp, enable_ref_frame_mvs
+            r.read(2)?;
+        }
+
+        let force_screen_content_tools = if r.flag()? { 2 } else { r.read(1)? };
+        if force_screen_content_tools > 0 && !r.flag()? {
+            // seq_force_integer_mv
+            r.read(1)?;
+        }
+
+        if enable_order_hint {
+            r.read(3)?;
+        }
+    }
+
+    // enable_superres, enable_cdef, enable_restoration
+    r.read(3)?;
+
+    // color_config
+    header.high_bitdepth = r.flag()?;
+    if header.profile == 2 && header.high_bitdepth {
+        header.twelve_bit = r.flag()?;
+    }
+    if header.profile != 1 {
+        header.monochrome = r.flag()?;
+    }
+
+    let (color_primaries, transfer_characteristics, matrix_coefficients) = if r.flag()? {
+        (r.read(8)?, r.read(8)?, r.read(8)?)
+    } else {
+        (2, 2, 2) // Unspecified
+    };
+
+    if header.monochrome {
+        header.subsampling_x = 1;
+        header.subsampling_y = 1;
+    } else if color_primaries == 1 && transfer_characteristics == 13 && matrix_coefficients == 0 {
+        // sRGB, 4:4:4
+    } else {
+        // color_range
+        r.read(1)?;
+        match header.profile {
+            0 => {
+                header.subsampling_x = 1;
+                header.subsampling_y = 1;
+            }
+            1 => {}
+            _ if header.twelve_bit => {
+                header.subsampling_x = r.read(1)? as u8;
+                if header.subsampling_x == 1 {
+                    header.subsampling_y = r.read(1)? as u8;
+                }
+            }
+            _ => header.subsampling_x = 1,
+        }
+
+        if header.subsampling_x == 1 && header.subsampling_y == 1 {
+            header.chroma_sample_position = r.read(2)? as u8;
+        }
+    }
+
+    Some(header)
+}
+
+/// Primary encoding of a RED (RFC 2198) payload, None if the payload is malformed
+pub fn red_primary(data: &[u8]) -> Option<&[u8]> {
+    let mut pos = 0;
+    let mut redundant_len = 0;
+    loop {
+        let header = *data.get(pos)?;
+        if header & 0x80 == 0 {
+            // Last header, only contains the payload type of the primary encoding
+            pos += 1;
+            break;
+        }
+
+        let block = data.get(pos..pos + 4)?;
+        redundant_len += ((block[2] & 0x03) as usize) << 8 | block[3] as usize;
+        pos += 4;
+    }
+
+    data.get(pos + redundant_len..)
+}
+
+/// OpusHead, the CodecPrivate of Opus in Matroska
+pub fn opus_head(num_channels: u8) -> Vec<u8> {
+    let mut head = b"OpusHead".to_vec();
+    head.push(1); // version
+    head.push(num_channels);
+    head.extend_from_slice(&OPUS_PRE_SKIP.to_le_bytes());
+    head.extend_from_slice(&48000u32.to_le_bytes());
+    head.extend_from_slice(&0i16.to_le_bytes()); // output gain
+    head.push(0); // channel mapping family
+    head
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn h264_annexb() {
+        let frame = [
+            0, 0, 0, 1, 0x09, 0xF0, // AUD
+            0, 0, 0, 1, 0x67, 0x42, 0xC0, 0x1F, 0xAA, // SPS
+            0, 0, 1, 0x68, 0xCE, // PPS
+            0, 0, 0, 1, 0x65, 0x88, 0x00, // IDR
+        ];
+
+        assert_eq!(
+            h264_to_avcc(&frame),
+            [
+                0, 0, 0, 5, 0x67, 0x42, 0xC0, 0x1F, 0xAA, //
+                0, 0, 0, 2, 0x68, 0xCE, //
+                0, 0, 0, 3, 0x65, 0x88, 0x00,
+            ]
+        );
+
+        assert_eq!(
+            h264_config(&frame).unwrap(),
+            [
+                1, 0x42, 0xC0, 0x1F, 0xFF, 0xE1, 0, 5, 0x67, 0x42, 0xC0, 0x1F, 0xAA, 1, 0, 2, 0x68,
+                0xCE,
+            ]
+        );
+
+        // Delta frames don't carry the parameter sets
+        assert!(h264_config(&[0, 0, 0, 1, 0x41, 0x9A]).is_none());
+    }
+
+    // Sequence header of a 640x480 stream, main profile, level 3.0 (4), 8-bit 4:2:0
+    const AV1_SEQUENCE_HEADER: [u8; 13] = [
+        0x0A, 0x0B, // OBU header with size
+        0x00, 0x00, 0x00, 0x24, 0xC4, 0xFF, 0xDF, 0x30, 0x9E, 0x60, 0x10,
+    ];
+
+    #[test]
+    fn av1_sequence_header() {
+        let obu = av1_obus(&AV1_SEQUENCE_HEADER).next().unwrap();
+        assert_eq!(obu.obu_type, AV1_OBU_SEQUENCE_HEADER);
+
+        let header = parse_av1_sequence_header(obu.payload).unwrap();
+        assert_eq!(header.profile, 0);
+        assert_eq!(header.level, 4);
+        assert_eq!(header.tier, 0);
+        assert!(!header.high_bitdepth);
+        assert!(!header.monochrome);
+        assert_eq!((header.subsampling_x, header.subsampling_y), (1, 1));
+
+        let mut frame = vec![0x12, 0x00]; // temporal delimiter
+        frame.extend_from_slice(&AV1_SEQUENCE_HEADER);
+        let config = av1_config(&frame).unwrap();
+        assert_eq!(&config[..4], &[0x81, 0x04, 0x0C, 0x00]);
+        assert_eq!(&config[4..], &AV1_SEQUENCE_HEADER);
+
+        assert_eq!(av1_strip_temporal_delimiters(&frame), AV1_SEQUENCE_HEADER);
+    }
+
+    #[test]
+    fn red() {
+        // One redundant block of 2 bytes, then the primary encoding
+        let payload = [0xEF, 0x03, 0xC0, 0x02, 0x6F, 1, 2, 3, 4, 5];
+        assert_eq!(red_primary(&payload), Some(&[3, 4, 5][..]));
+
+        // Without redundancy
+        assert_eq!(red_primary(&[0x6F, 7, 8]), Some(&[7, 8][..]));
+
+        assert_eq!(red_primary(&[0xEF, 0x03, 0xC0, 0x09, 0x6F, 1]), None);
+    }
+
+    #[test]
+    fn vp9() {
+        assert_eq!(vp9_profile(&[0x82]), 0);
+        assert_eq!(vp9_profile(&[0xA2]), 1);
+        assert_eq!(vp9_profile(&[0x92]), 2);
+    }
+}
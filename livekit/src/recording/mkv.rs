@@ -0,0 +1,321 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Minimal streaming Matroska (and WebM) muxer. The segment is written with an unknown
+//! size so the file stays playable if the recording is interrupted.
+
+const EBML: u32 = 0x1A45DFA3;
+const EBML_VERSION: u32 = 0x4286;
+const EBML_READ_VERSION: u32 = 0x42F7;
+const EBML_MAX_ID_LENGTH: u32 = 0x42F2;
+const EBML_MAX_SIZE_LENGTH: u32 = 0x42F3;
+const DOC_TYPE: u32 = 0x4282;
+const DOC_TYPE_VERSION: u32 = 0x4287;
+const DOC_TYPE_READ_VERSION: u32 = 0x4285;
+const SEGMENT: u32 = 0x18538067;
+const INFO: u32 = 0x1549A966;
+const TIMESTAMP_SCALE: u32 = 0x2AD7B1;
+const MUXING_APP: u32 = 0x4D80;
+const WRITING_APP: u32 = 0x5741;
+const TRACKS: u32 = 0x1654AE6B;
+const TRACK_ENTRY: u32 = 0xAE;
+const TRACK_NUMBER: u32 = 0xD7;
+const TRACK_UID: u32 = 0x73C5;
+const TRACK_TYPE: u32 = 0x83;
+const FLAG_LACING: u32 = 0x9C;
+const CODEC_ID: u32 = 0x86;
+const CODEC_PRIVATE: u32 = 0x63A2;
+const CODEC_DELAY: u32 = 0x56AA;
+const SEEK_PRE_ROLL: u32 = 0x56BB;
+const VIDEO: u32 = 0xE0;
+const PIXEL_WIDTH: u32 = 0xB0;
+const PIXEL_HEIGHT: u32 = 0xBA;
+const COLOUR_SPACE: u32 = 0x2EB524;
+const AUDIO: u32 = 0xE1;
+const SAMPLING_FREQUENCY: u32 = 0xB5;
+const CHANNELS: u32 = 0x9F;
+const BIT_DEPTH: u32 = 0x6264;
+const CLUSTER: u32 = 0x1F43B675;
+const TIMESTAMP: u32 = 0xE7;
+const SIMPLE_BLOCK: u32 = 0xA3;
+
+const UNKNOWN_SIZE: [u8; 8] = [0x01, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
+const MAX_CLUSTER_DURATION_MS: i64 = 1000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MkvTrack {
+    Video {
+        codec_id: &'static str, // e.g. V_VP8
+        codec_private: Option<Vec<u8>>,
+        width: u32,
+        height: u32,
+        colour_space: Option<[u8; 4]>, // FourCC of V_UNCOMPRESSED
+    },
+    Audio {
+        codec_id: &'static str,
+        codec_private: Option<Vec<u8>>,
+        codec_delay_ns: Option<u64>,
+        seek_pre_roll_ns: Option<u64>,
+        sample_rate: u32,
+        num_channels: u32,
+        bit_depth: Option<u32>,
+    },
+}
+
+/// Writes the blocks of the tracks given on creation, the track numbers are
+/// their index + 1. Timestamps are in milliseconds.
+/// The muxed bytes are buffered until they are taken with take_output.
+pub struct MkvWriter {
+    output: Vec<u8>,
+    cluster: Vec<u8>,
+    cluster_timestamp: Option<i64>,
+}
+
+impl MkvWriter {
+    /// doc_type is "matroska" or "webm"
+    pub fn new(doc_type: &str, tracks: &[MkvTrack]) -> Self {
+        let mut header = Vec::new();
+        write_master(&mut header, EBML, |h| {
+            write_uint(h, EBML_VERSION, 1);
+            write_uint(h, EBML_READ_VERSION, 1);
+            write_uint(h, EBML_MAX_ID_LENGTH, 4);
+            write_uint(h, EBML_MAX_SIZE_LENGTH, 8);
+            write_bytes(h, DOC_TYPE, doc_type.as_bytes());
+            write_uint(h, DOC_TYPE_VERSION, 4);
+            write_uint(h, DOC_TYPE_READ_VERSION, 2);
+        });
+
+        write_id(&mut header, SEGMENT);
+        header.extend_from_slice(&UNKNOWN_SIZE);
+
+        write_master(&mut header, INFO, |h| {
+            write_uint(h, TIMESTAMP_SCALE, 1_000_000);
+            write_bytes(h, MUXING_APP, b"livekit");
+            write_bytes(h, WRITING_APP, b"livekit");
+        });
+
+        write_master(&mut header, TRACKS, |h| {
+            for (i, track) in tracks.iter().enumerate() {
+                write_master(h, TRACK_ENTRY, |e| write_track(e, i as u64 + 1, track));
+            }
+        });
+
+        Self {
+            output: header,
+            cluster: Vec::new(),
+            cluster_timestamp: None,
+        }
+    }
+
+    pub fn write_block(
+        &mut self,
+        track_number: u64,
+        timestamp_ms: i64,
+        key_frame: bool,
+        data: &[u8],
+    ) {
+        let timestamp_ms = timestamp_ms.max(0);
+        if let Some(cluster_timestamp) = self.cluster_timestamp {
+            let relative = timestamp_ms - cluster_timestamp;
+            if relative >= MAX_CLUSTER_DURATION_MS || relative < i16::MIN as i64 {
+                self.flush_cluster();
+            }
+        }
+
+        let cluster_timestamp = *self.cluster_timestamp.get_or_insert(timestamp_ms);
+        let relative = (timestamp_ms - cluster_timestamp) as i16;
+
+        let mut block = Vec::with_capacity(data.len() + 12);
+        write_size(&mut block, track_number);
+        block.extend_from_slice(&relative.to_be_bytes());
+        block.push(if key_frame { 0x80 } else { 0x00 });
+        block.extend_from_slice(data);
+        write_bytes(&mut self.cluster, SIMPLE_BLOCK, &block);
+    }
+
+    pub fn flush_cluster(&mut self) {
+        let Some(cluster_timestamp) = self.cluster_timestamp.take() else {
+            return;
+        };
+
+        let mut content = Vec::with_capacity(self.cluster.len() + 12);
+        write_uint(&mut content, TIMESTAMP, cluster_timestamp as u64);
+        content.append(&mut self.cluster);
+
+        write_bytes(&mut self.output, CLUSTER, &content);
+    }
+
+    /// The bytes muxed since the last call, the clusters are only output once complete
+    pub fn take_output(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.output)
+    }
+
+    /// Complete the last cluster, returns the remaining output
+    pub fn finish(mut self) -> Vec<u8> {
+        self.flush_cluster();
+        self.output
+    }
+}
+
+fn write_track(e: &mut Vec<u8>, number: u64, track: &MkvTrack) {
+    write_uint(e, TRACK_NUMBER, number);
+    write_uint(e, TRACK_UID, number);
+    write_uint(e, FLAG_LACING, 0);
+    match track {
+        MkvTrack::Video {
+            codec_id,
+            codec_private,
+            width,
+            height,
+            colour_space,
+        } => {
+            write_uint(e, TRACK_TYPE, 1);
+            write_bytes(e, CODEC_ID, codec_id.as_bytes());
+            if let Some(codec_private) = codec_private {
+                write_bytes(e, CODEC_PRIVATE, codec_private);
+            }
+            write_master(e, VIDEO, |v| {
+                write_uint(v, PIXEL_WIDTH, *width as u64);
+                write_uint(v, PIXEL_HEIGHT, *height as u64);
+                if let Some(fourcc) = colour_space {
+                    write_bytes(v, COLOUR_SPACE, fourcc);
+                }
+            });
+        }
+        MkvTrack::Audio {
+            codec_id,
+            codec_private,
+            codec_delay_ns,
+            seek_pre_roll_ns,
+            sample_rate,
+            num_channels,
+            bit_depth,
+        } => {
+            write_uint(e, TRACK_TYPE, 2);
+            write_bytes(e, CODEC_ID, codec_id.as_bytes());
+            if let Some(codec_private) = codec_private {
+                write_bytes(e, CODEC_PRIVATE, codec_private);
+            }
+            if let Some(codec_delay_ns) = codec_delay_ns {
+                write_uint(e, CODEC_DELAY, *codec_delay_ns);
+            }
+            if let Some(seek_pre_roll_ns) = seek_pre_roll_ns {
+                write_uint(e, SEEK_PRE_ROLL, *seek_pre_roll_ns);
+            }
+            write_master(e, AUDIO, |a| {
+                write_bytes(a, SAMPLING_FREQUENCY, &(*sample_rate as f64).to_be_bytes());
+                write_uint(a, CHANNELS, *num_channels as u64);
+                if let Some(bit_depth) = bit_depth {
+                    write_uint(a, BIT_DEPTH, *bit_depth as u64);
+                }
+            });
+        }
+    }
+}
+
+fn write_id(buf: &mut Vec<u8>, id: u32) {
+    let bytes = id.to_be_bytes();
+    let skip = bytes.iter().take_while(|b| **b == 0).count();
+    buf.extend_from_slice(&bytes[skip..]);
+}
+
+/// EBML variable size integer, using the shortest encoding
+fn write_size(buf: &mut Vec<u8>, size: u64) {
+    let mut len = 1;
+    // All ones is reserved for the unknown size
+    while len < 8 && size >= (1 << (7 * len)) - 1 {
+        len += 1;
+    }
+
+    let marked = size | (1 << (7 * len));
+    buf.extend_from_slice(&marked.to_be_bytes()[8 - len..]);
+}
+
+fn write_bytes(buf: &mut Vec<u8>, id: u32, data: &[u8]) {
+    write_id(buf, id);
+    write_size(buf, data.len() as u64);
+    buf.extend_from_slice(data);
+}
+
+fn write_uint(buf: &mut Vec<u8>, id: u32, value: u64) {
+    let bytes = value.to_be_bytes();
+    let skip = bytes.iter().take_while(|b| **b == 0).count().min(7);
+    write_bytes(buf, id, &bytes[skip..]);
+}
+
+fn write_master(buf: &mut Vec<u8>, id: u32, f: impl FnOnce(&mut Vec<u8>)) {
+    let mut content = Vec::new();
+    f(&mut content);
+    write_bytes(buf, id, &content);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ebml_encoding() {
+        let mut buf = Vec::new();
+        write_size(&mut buf, 1);
+        write_size(&mut buf, 126);
+        write_size(&mut buf, 127);
+        write_size(&mut buf, 300);
+        assert_eq!(buf, [0x81, 0xFE, 0x40, 0x7F, 0x41, 0x2C]);
+
+        let mut buf = Vec::new();
+        write_uint(&mut buf, TIMESTAMP_SCALE, 1_000_000);
+        assert_eq!(buf, [0x2A, 0xD7, 0xB1, 0x83, 0x0F, 0x42, 0x40]);
+
+        let mut buf = Vec::new();
+        write_uint(&mut buf, FLAG_LACING, 0);
+        assert_eq!(buf, [0x9C, 0x81, 0x00]);
+    }
+
+    #[test]
+    fn clusters() {
+        let tracks = [MkvTrack::Audio {
+            codec_id: "A_PCM/INT/LIT",
+            codec_private: None,
+            codec_delay_ns: None,
+            seek_pre_roll_ns: None,
+            sample_rate: 48000,
+            num_channels: 1,
+            bit_depth: Some(16),
+        }];
+        let mut mkv = MkvWriter::new("matroska", &tracks);
+        let header = mkv.take_output();
+        assert_eq!(&header[..4], &[0x1A, 0x45, 0xDF, 0xA3]);
+
+        mkv.write_block(1, 0, true, &[1, 2]);
+        mkv.write_block(1, 500, true, &[3, 4]);
+        // Nothing is output until the cluster is complete
+        assert!(mkv.take_output().is_empty());
+
+        mkv.write_block(1, 1200, true, &[5, 6]);
+        let cluster = mkv.take_output();
+        assert_eq!(&cluster[..4], &[0x1F, 0x43, 0xB6, 0x75]);
+        // Timestamp 0, then the blocks at 0 and +500ms
+        assert_eq!(
+            &cluster[5..],
+            &[
+                0xE7, 0x81, 0x00, //
+                0xA3, 0x86, 0x81, 0x00, 0x00, 0x80, 1, 2, //
+                0xA3, 0x86, 0x81, 0x01, 0xF4, 0x80, 3, 4,
+            ]
+        );
+
+        let out = mkv.finish();
+        assert_eq!(&out[5..][..4], &[0xE7, 0x82, 0x04, 0xB0]);
+    }
+}
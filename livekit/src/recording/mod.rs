@@ -0,0 +1,817 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Client-side recording of an audio and a video track into a Matroska (.mkv),
+//! WebM or fragmented MP4 file.
+//!
+//! - Matroska: the audio is written as 16-bit PCM. The video is either written
+//!   uncompressed (I420), or as received (VP8, VP9, AV1 or H264) when
+//!   `video_passthrough` is set.
+//! - WebM and MP4: nothing is re-encoded, the audio of a remote track is written as
+//!   received (Opus) and the video requires `video_passthrough` (VP8, VP9 or AV1 for
+//!   WebM, H264, VP9 or AV1 for MP4).
+//!
+//! The encoded frames are read before the E2EE decryptor, so encrypted tracks can only
+//! be recorded decoded (Matroska without `video_passthrough`).
+
+use crate::e2ee::EncryptionType;
+use crate::prelude::*;
+use futures_util::stream::{self, BoxStream, StreamExt};
+use livekit_webrtc::audio_frame::AudioFrame;
+use livekit_webrtc::audio_stream::native::{AudioStreamOptions, NativeAudioStream};
+use livekit_webrtc::native::av_sync::{AVSynchronizer, AVSynchronizerOptions, SyncedFrame};
+use livekit_webrtc::native::encoded_audio_stream::{EncodedAudioFrame, EncodedAudioStream};
+use livekit_webrtc::native::encoded_video_stream::{
+    EncodedVideoCodec, EncodedVideoFrame, EncodedVideoStream,
+};
+use livekit_webrtc::native::time_micros;
+use livekit_webrtc::rtp_receiver::RtpReceiver;
+use livekit_webrtc::video_frame::conversion::{FilterMode, PixelBuffer, PixelFormat};
+use livekit_webrtc::video_frame::BoxVideoFrame;
+use livekit_webrtc::video_stream::native::NativeVideoStream;
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use thiserror::Error;
+use tokio::fs::File;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+mod codec;
+mod mkv;
+mod mp4;
+
+use mkv::{MkvTrack, MkvWriter};
+use mp4::{Mp4Track, Mp4Writer};
+
+const SAMPLE_RATE: u32 = 48000;
+const NUM_CHANNELS: u32 = 2;
+const OPUS_CHANNELS: u8 = 2;
+const OPUS_CODEC_DELAY_NS: u64 = 6_500_000; // The pre-skip at 48kHz
+const OPUS_SEEK_PRE_ROLL_NS: u64 = 80_000_000;
+
+// Audio kept while waiting for the first video frame, the file is then written audio only
+const MAX_VIDEO_WAIT_US: i64 = 5_000_000;
+
+#[derive(Error, Debug)]
+pub enum RecordingError {
+    #[error("io error: {0}")]
+    Io(#[from] io::Error),
+    #[error("unsupported: {0}")]
+    Unsupported(String),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RecordingFormat {
+    #[default]
+    Mkv,
+    WebM,
+    Mp4,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RecordingOptions {
+    pub format: RecordingFormat,
+    /// Write the video frames as received instead of decoding them. Only supported for
+    /// remote tracks that aren't encrypted (e2ee), required by WebM and MP4
+    pub video_passthrough: bool,
+}
+
+/// Records an audio and/or a video track into a file until stopped.
+/// The file is written as the recording progresses and stays playable if the
+/// recording is interrupted.
+pub struct TrackRecorder {
+    close_tx: Option<oneshot::Sender<()>>,
+    task: JoinHandle<Result<(), RecordingError>>,
+}
+
+impl TrackRecorder {
+    pub async fn start(
+        path: impl AsRef<Path>,
+        audio: Option<AudioTrack>,
+        video: Option<VideoTrack>,
+        options: RecordingOptions,
+    ) -> Result<Self, RecordingError> {
+        if audio.is_none() && video.is_none() {
+            return Err(RecordingError::Unsupported("nothing to record".to_owned()));
+        }
+
+        let encoded = options.format != RecordingFormat::Mkv;
+        if encoded && video.is_some() && !options.video_passthrough {
+            return Err(RecordingError::Unsupported(format!(
+                "{:?} requires video_passthrough, the video isn't re-encoded",
+                options.format
+            )));
+        }
+
+        let audio_input = match audio {
+            Some(AudioTrack::Remote(track)) if encoded => {
+                if track.encryption_type() != EncryptionType::None {
+                    return Err(RecordingError::Unsupported(
+                        "encrypted tracks can only be recorded decoded".to_owned(),
+                    ));
+                }
+
+                let (Some(transceiver), Some(stream)) =
+                    (track.transceiver(), track.encoded_frame_stream())
+                else {
+                    return Err(RecordingError::Unsupported(
+                        "the audio track isn't subscribed".to_owned(),
+                    ));
+                };
+                Some(AudioInput::Encoded(
+                    stream,
+                    OpusDepacketizer::new(transceiver.receiver()),
+                ))
+            }
+            Some(AudioTrack::Local(_)) if encoded => {
+                return Err(RecordingError::Unsupported(format!(
+                    "local audio can't be recorded to {:?}, it would need to be encoded",
+                    options.format
+                )));
+            }
+            Some(track) => Some(AudioInput::Decoded(
+                NativeAudioStream::with_options(
+                    track.rtc_track(),
+                    AudioStreamOptions {
+                        sample_rate: Some(SAMPLE_RATE),
+                        num_channels: Some(NUM_CHANNELS),
+                        ..Default::default()
+                    },
+                )
+                .map_err(|err| RecordingError::Unsupported(err.message))?,
+            )),
+            None => None,
+        };
+
+        let video_input = match video {
+            Some(VideoTrack::Remote(track)) if options.video_passthrough => {
+                if track.encryption_type() != EncryptionType::None {
+                    return Err(RecordingError::Unsupported(
+                        "video_passthrough isn't supported on encrypted tracks".to_owned(),
+                    ));
+                }
+
+                let Some(stream) = track.encoded_frame_stream() else {
+                    return Err(RecordingError::Unsupported(
+                        "the video track isn't subscribed".to_owned(),
+                    ));
+                };
+                Some(VideoInput::Encoded(stream))
+            }
+            Some(VideoTrack::Local(_)) if options.video_passthrough => {
+                return Err(RecordingError::Unsupported(
+                    "video passthrough is only supported for remote tracks".to_owned(),
+                ));
+            }
+            Some(track) => Some(VideoInput::Decoded(NativeVideoStream::new(
+                track.rtc_track(),
+            ))),
+            None => None,
+        };
+
+        let file = File::create(path).await?;
+        let output = Output::new(file, options.format, &audio_input, video_input.is_some());
+        let (close_tx, close_rx) = oneshot::channel();
+        let task = match video_input {
+            Some(VideoInput::Encoded(video)) => {
+                tokio::spawn(record_passthrough(output, audio_input, video, close_rx))
+            }
+            video => {
+                let audio = audio_input.map(|input| match input {
+                    AudioInput::Decoded(stream) => stream,
+                    AudioInput::Encoded(..) => unreachable!(),
+                });
+                let video = video.map(|input| match input {
+                    VideoInput::Decoded(stream) => stream,
+                    VideoInput::Encoded(_) => unreachable!(),
+                });
+                tokio::spawn(record_raw(output, audio, video, close_rx))
+            }
+        };
+
+        Ok(Self {
+            close_tx: Some(close_tx),
+            task,
+        })
+    }
+
+    /// Stop the recording and wait for the file to be completed
+    pub async fn stop(mut self) -> Result<(), RecordingError> {
+        if let Some(close_tx) = self.close_tx.take() {
+            let _ = close_tx.send(());
+        }
+
+        match (&mut self.task).await {
+            Ok(res) => res,
+            Err(err) => Err(RecordingError::Io(io::Error::new(
+                io::ErrorKind::Other,
+                err,
+            ))),
+        }
+    }
+}
+
+impl Drop for TrackRecorder {
+    fn drop(&mut self) {
+        // The task completes the file once the close signal is received
+        if let Some(close_tx) = self.close_tx.take() {
+            let _ = close_tx.send(());
+        }
+    }
+}
+
+enum AudioInput {
+    Decoded(NativeAudioStream),
+    Encoded(EncodedAudioStream, OpusDepacketizer),
+}
+
+enum VideoInput {
+    Decoded(NativeVideoStream),
+    Encoded(EncodedVideoStream),
+}
+
+/// Decoded audio and video, synchronized using AVSynchronizer
+async fn record_raw(
+    mut output: Output,
+    audio: Option<NativeAudioStream>,
+    video: Option<NativeVideoStream>,
+    mut close_rx: oneshot::Receiver<()>,
+) -> Result<(), RecordingError> {
+    let audio: BoxStream<'static, AudioFrame<'static>> = match audio {
+        Some(audio) => audio.boxed(),
+        None => stream::empty().boxed(),
+    };
+    let video: BoxStream<'static, BoxVideoFrame> = match video {
+        Some(video) => video.boxed(),
+        None => stream::empty().boxed(),
+    };
+    let mut frames = AVSynchronizer::new(audio, video, AVSynchronizerOptions::default());
+
+    loop {
+        let frame = tokio::select! {
+            _ = &mut close_rx => break,
+            frame = frames.next() => match frame {
+                Some(frame) => frame,
+                None => break,
+            },
+        };
+
+        match frame {
+            SyncedFrame::Audio {
+                timestamp_us,
+                frame,
+            } => {
+                output.write_audio(timestamp_us, pcm_data(&frame)).await?;
+            }
+            SyncedFrame::Video(frame) => {
+                // The size of the video is only known once the first frame is received
+                let (width, height) = match output.video_size() {
+                    Some(size) => size,
+                    None => {
+                        let size = (frame.buffer.width(), frame.buffer.height());
+                        output.set_video(VideoConfig::raw(size.0, size.1)).await?;
+                        size
+                    }
+                };
+
+                let mut buffer = PixelBuffer::from_video_buffer(frame.buffer.as_ref());
+                if buffer.format() != PixelFormat::I420 {
+                    buffer = buffer.convert(PixelFormat::I420);
+                }
+                if (buffer.width(), buffer.height()) != (width, height) {
+                    buffer = buffer.scale(width, height, FilterMode::Box);
+                }
+
+                output
+                    .write_video(frame.timestamp_us, true, buffer.data().to_vec())
+                    .await?;
+            }
+        }
+    }
+
+    output.finish().await
+}
+
+/// Encoded video with decoded (Matroska) or encoded audio. The encoded frames are
+/// timestamped from their RTP timestamps, anchored on their first arrival
+async fn record_passthrough(
+    mut output: Output,
+    mut audio: Option<AudioInput>,
+    mut video: EncodedVideoStream,
+    mut close_rx: oneshot::Receiver<()>,
+) -> Result<(), RecordingError> {
+    let mut audio_clock = RtpClock::new(SAMPLE_RATE);
+    let mut video_clock = RtpClock::new(90000);
+    let mut audio_start_us = None;
+    let mut audio_samples = 0u64;
+
+    loop {
+        tokio::select! {
+            _ = &mut close_rx => break,
+            frame = next_audio(&mut audio), if audio.is_some() => {
+                match frame {
+                    Some(AudioSample::Decoded(frame)) => {
+                        // Decoded audio is continuous, it is timestamped from its sample count
+                        let start_us = *audio_start_us.get_or_insert_with(time_micros);
+                        let timestamp_us =
+                            start_us + (audio_samples * 1_000_000 / SAMPLE_RATE as u64) as i64;
+                        audio_samples += frame.samples_per_channel as u64;
+                        output.write_audio(timestamp_us, pcm_data(&frame)).await?;
+                    }
+                    Some(AudioSample::Encoded(frame)) => {
+                        let Some(AudioInput::Encoded(_, depacketizer)) = audio.as_mut() else {
+                            unreachable!();
+                        };
+
+                        if let Some(data) = depacketizer.opus_payload(&frame) {
+                            let timestamp_us = audio_clock.timestamp_us(frame.rtp_timestamp);
+                            output.write_audio(timestamp_us, data).await?;
+                        }
+                    }
+                    None => audio = None,
+                }
+            }
+            frame = video.next() => {
+                let Some(frame) = frame else {
+                    break;
+                };
+
+                if output.video_size().is_none() {
+                    // Wait for a key frame with the codec configuration, the first frames
+                    // can't be decoded without it
+                    if !frame.key_frame {
+                        continue;
+                    }
+
+                    match VideoConfig::from_key_frame(&frame, output.format)? {
+                        Some(config) => output.set_video(config).await?,
+                        None => continue,
+                    }
+                }
+
+                let timestamp_us = video_clock.timestamp_us(frame.rtp_timestamp);
+                let data = match frame.codec {
+                    EncodedVideoCodec::H264 => codec::h264_to_avcc(&frame.data),
+                    EncodedVideoCodec::AV1 => codec::av1_strip_temporal_delimiters(&frame.data),
+                    _ => frame.data,
+                };
+                output.write_video(timestamp_us, frame.key_frame, data).await?;
+            }
+        }
+    }
+
+    output.finish().await
+}
+
+enum AudioSample {
+    Decoded(AudioFrame<'static>),
+    Encoded(EncodedAudioFrame),
+}
+
+async fn next_audio(audio: &mut Option<AudioInput>) -> Option<AudioSample> {
+    match audio.as_mut()? {
+        AudioInput::Decoded(stream) => stream.next().await.map(AudioSample::Decoded),
+        AudioInput::Encoded(stream, _) => stream.next().await.map(AudioSample::Encoded),
+    }
+}
+
+fn pcm_data(frame: &AudioFrame) -> Vec<u8> {
+    frame.data.iter().flat_map(|s| s.to_le_bytes()).collect()
+}
+
+/// Converts the RTP timestamps to the time_micros clock, anchored on the arrival of
+/// the first frame
+struct RtpClock {
+    clock_rate: u32,
+    first: Option<(u32, i64)>, // (rtp timestamp, arrival)
+}
+
+impl RtpClock {
+    fn new(clock_rate: u32) -> Self {
+        Self {
+            clock_rate,
+            first: None,
+        }
+    }
+
+    fn timestamp_us(&mut self, rtp_timestamp: u32) -> i64 {
+        let (rtp, arrival_us) = *self
+            .first
+            .get_or_insert_with(|| (rtp_timestamp, time_micros()));
+        // The RTP timestamps wrap around
+        let elapsed = rtp_timestamp.wrapping_sub(rtp) as i32 as i64;
+        arrival_us + elapsed * 1_000_000 / self.clock_rate as i64
+    }
+}
+
+/// Extracts the Opus packets of the encoded audio frames, the codec of a frame is
+/// found from its payload type in the parameters of the receiver
+struct OpusDepacketizer {
+    receiver: RtpReceiver,
+    mime_types: HashMap<u8, String>,
+}
+
+impl OpusDepacketizer {
+    fn new(receiver: RtpReceiver) -> Self {
+        Self {
+            receiver,
+            mime_types: HashMap::new(),
+        }
+    }
+
+    fn opus_payload(&mut self, frame: &EncodedAudioFrame) -> Option<Vec<u8>> {
+        if !self.mime_types.contains_key(&frame.payload_type) {
+            self.mime_types = self
+                .receiver
+                .parameters()
+                .codecs
+                .into_iter()
+                .map(|codec| (codec.payload_type, codec.mime_type.to_lowercase()))
+                .collect();
+        }
+
+        match self.mime_types.get(&frame.payload_type).map(String::as_str) {
+            Some("audio/opus") => Some(frame.data.clone()),
+            // Only keep the primary encoding, the redundant ones are older packets
+            Some("audio/red") => codec::red_primary(&frame.data).map(<[u8]>::to_vec),
+            // e.g. DTMF events or comfort noise
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AudioCodec {
+    Pcm,
+    Opus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum VideoCodec {
+    Raw, // I420
+    VP8,
+    VP9 { profile: u8 },
+    AV1 { config: Vec<u8> },
+    H264 { config: Vec<u8> },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct VideoConfig {
+    codec: VideoCodec,
+    width: u32,
+    height: u32,
+}
+
+impl VideoConfig {
+    fn raw(width: u32, height: u32) -> Self {
+        Self {
+            codec: VideoCodec::Raw,
+            width,
+            height,
+        }
+    }
+
+    /// None if the key frame doesn't carry the codec configuration (the parameter sets
+    /// of H264, the sequence header of AV1)
+    fn from_key_frame(
+        frame: &EncodedVideoFrame,
+        format: RecordingFormat,
+    ) -> Result<Option<Self>, RecordingError> {
+        let codec = match (frame.codec, format) {
+            (EncodedVideoCodec::VP8, RecordingFormat::Mkv | RecordingFormat::WebM) => {
+                Some(VideoCodec::VP8)
+            }
+            (EncodedVideoCodec::VP9, _) => Some(VideoCodec::VP9 {
+                profile: codec::vp9_profile(&frame.data),
+            }),
+            (EncodedVideoCodec::AV1, _) => {
+                codec::av1_config(&frame.data).map(|config| VideoCodec::AV1 { config })
+            }
+            (EncodedVideoCodec::H264, RecordingFormat::Mkv | RecordingFormat::Mp4) => {
+                codec::h264_config(&frame.data).map(|config| VideoCodec::H264 { config })
+            }
+            (codec, format) => {
+                return Err(RecordingError::Unsupported(format!(
+                    "{:?} can't be recorded to {:?} without re-encoding",
+                    codec, format
+                )))
+            }
+        };
+
+        Ok(codec.map(|codec| Self {
+            codec,
+            width: frame.width,
+            height: frame.height,
+        }))
+    }
+}
+
+enum VideoState {
+    None,
+    Waiting,
+    Ready(VideoConfig),
+    Dropped, // No video frame was received in time, the file is audio only
+}
+
+struct Sample {
+    video: bool,
+    timestamp_us: i64,
+    key_frame: bool,
+    data: Vec<u8>,
+}
+
+enum Muxer {
+    Mkv(MkvWriter),
+    Mp4(Mp4Writer),
+}
+
+/// The file being written. The tracks are only known once the first video frame is
+/// received, the samples received before are kept until the header can be written
+struct Output {
+    file: File,
+    format: RecordingFormat,
+    audio: Option<AudioCodec>,
+    video: VideoState,
+    muxer: Option<Muxer>,
+    pending: Vec<Sample>,
+    start_us: Option<i64>,
+}
+
+impl Output {
+    fn new(
+        file: File,
+        format: RecordingFormat,
+        audio: &Option<AudioInput>,
+        has_video: bool,
+    ) -> Self {
+        Self {
+            file,
+            format,
+            audio: audio.as_ref().map(|input| match input {
+                AudioInput::Decoded(_) => AudioCodec::Pcm,
+                AudioInput::Encoded(..) => AudioCodec::Opus,
+            }),
+            video: if has_video {
+                VideoState::Waiting
+            } else {
+                VideoState::None
+            },
+            muxer: None,
+            pending: Vec::new(),
+            start_us: None,
+        }
+    }
+
+    fn video_size(&self) -> Option<(u32, u32)> {
+        match &self.video {
+            VideoState::Ready(config) => Some((config.width, config.height)),
+            _ => None,
+        }
+    }
+
+    async fn set_video(&mut self, config: VideoConfig) -> Result<(), RecordingError> {
+        if let VideoState::Waiting = self.video {
+            self.video = VideoState::Ready(config);
+            self.open()?;
+            self.write_pending().await?;
+        }
+        Ok(())
+    }
+
+    async fn write_audio(
+        &mut self,
+        timestamp_us: i64,
+        data: Vec<u8>,
+    ) -> Result<(), RecordingError> {
+        self.write(Sample {
+            video: false,
+            timestamp_us,
+            key_frame: true,
+            data,
+        })
+        .await
+    }
+
+    async fn write_video(
+        &mut self,
+        timestamp_us: i64,
+        key_frame: bool,
+        data: Vec<u8>,
+    ) -> Result<(), RecordingError> {
+        if let VideoState::Dropped = self.video {
+            return Ok(());
+        }
+
+        self.write(Sample {
+            video: true,
+            timestamp_us,
+            key_frame,
+            data,
+        })
+        .await
+    }
+
+    async fn write(&mut self, sample: Sample) -> Result<(), RecordingError> {
+        self.start_us.get_or_insert(sample.timestamp_us);
+        if self.muxer.is_none() {
+            let waited_us = sample.timestamp_us - self.start_us.unwrap();
+            self.pending.push(sample);
+            if let VideoState::Waiting = self.video {
+                if waited_us < MAX_VIDEO_WAIT_US {
+                    return Ok(());
+                }
+
+                log::warn!("no video frame received, recording the audio only");
+                self.video = VideoState::Dropped;
+            }
+
+            self.open()?;
+            return self.write_pending().await;
+        }
+
+        self.mux(sample);
+        self.flush().await
+    }
+
+    async fn write_pending(&mut self) -> Result<(), RecordingError> {
+        for sample in std::mem::take(&mut self.pending) {
+            self.mux(sample);
+        }
+        self.flush().await
+    }
+
+    fn mux(&mut self, sample: Sample) {
+        let track_index = match (sample.video, self.audio) {
+            (true, Some(_)) => 1,
+            _ => 0,
+        };
+        let timestamp_us = (sample.timestamp_us - self.start_us.unwrap()).max(0);
+        match self.muxer.as_mut().unwrap() {
+            Muxer::Mkv(mkv) => mkv.write_block(
+                track_index as u64 + 1,
+                timestamp_us / 1000,
+                sample.key_frame,
+                &sample.data,
+            ),
+            Muxer::Mp4(mp4) => {
+                mp4.write_sample(track_index, timestamp_us, sample.key_frame, sample.data)
+            }
+        }
+    }
+
+    async fn flush(&mut self) -> Result<(), RecordingError> {
+        let data = match self.muxer.as_mut().unwrap() {
+            Muxer::Mkv(mkv) => mkv.take_output(),
+            Muxer::Mp4(mp4) => mp4.take_output(),
+        };
+        if !data.is_empty() {
+            self.file.write_all(&data).await?;
+        }
+        Ok(())
+    }
+
+    /// Write the pending samples (audio only if no video was received) and complete the file
+    async fn finish(mut self) -> Result<(), RecordingError> {
+        if self.muxer.is_none() {
+            if self.audio.is_none() {
+                // Nothing was received
+                return Ok(());
+            }
+
+            self.video = VideoState::Dropped;
+            self.open()?;
+            self.write_pending().await?;
+        }
+
+        let data = match self.muxer.take().unwrap() {
+            Muxer::Mkv(mkv) => mkv.finish(),
+            Muxer::Mp4(mp4) => mp4.finish(),
+        };
+        self.file.write_all(&data).await?;
+        self.file.flush().await?;
+        Ok(())
+    }
+
+    fn open(&mut self) -> Result<(), RecordingError> {
+        let video = match &self.video {
+            VideoState::Ready(config) => Some(config),
+            _ => None,
+        };
+
+        self.muxer = Some(match self.format {
+            RecordingFormat::Mkv | RecordingFormat::WebM => {
+                let mut tracks = Vec::new();
+                if let Some(audio) = self.audio {
+                    tracks.push(mkv_audio_track(audio));
+                }
+                if let Some(video) = video {
+                    tracks.push(mkv_video_track(video));
+                }
+
+                let doc_type = match self.format {
+                    RecordingFormat::WebM => "webm",
+                    _ => "matroska",
+                };
+                Muxer::Mkv(MkvWriter::new(doc_type, &tracks))
+            }
+            RecordingFormat::Mp4 => {
+                let mut tracks = Vec::new();
+                if let Some(audio) = self.audio {
+                    tracks.push(mp4_audio_track(audio)?);
+                }
+                if let Some(video) = video {
+                    tracks.push(mp4_video_track(video)?);
+                }
+                Muxer::Mp4(Mp4Writer::new(&tracks))
+            }
+        });
+        Ok(())
+    }
+}
+
+fn mkv_audio_track(codec: AudioCodec) -> MkvTrack {
+    match codec {
+        AudioCodec::Pcm => MkvTrack::Audio {
+            codec_id: "A_PCM/INT/LIT",
+            codec_private: None,
+            codec_delay_ns: None,
+            seek_pre_roll_ns: None,
+            sample_rate: SAMPLE_RATE,
+            num_channels: NUM_CHANNELS,
+            bit_depth: Some(16),
+        },
+        AudioCodec::Opus => MkvTrack::Audio {
+            codec_id: "A_OPUS",
+            codec_private: Some(codec::opus_head(OPUS_CHANNELS)),
+            codec_delay_ns: Some(OPUS_CODEC_DELAY_NS),
+            seek_pre_roll_ns: Some(OPUS_SEEK_PRE_ROLL_NS),
+            sample_rate: SAMPLE_RATE,
+            num_channels: OPUS_CHANNELS as u32,
+            bit_depth: None,
+        },
+    }
+}
+
+fn mkv_video_track(config: &VideoConfig) -> MkvTrack {
+    let (codec_id, codec_private) = match &config.codec {
+        VideoCodec::Raw => ("V_UNCOMPRESSED", None),
+        VideoCodec::VP8 => ("V_VP8", None),
+        VideoCodec::VP9 { .. } => ("V_VP9", None),
+        VideoCodec::AV1 { config } => ("V_AV1", Some(config.clone())),
+        VideoCodec::H264 { config } => ("V_MPEG4/ISO/AVC", Some(config.clone())),
+    };
+
+    MkvTrack::Video {
+        codec_id,
+        codec_private,
+        width: config.width,
+        height: config.height,
+        colour_space: (config.codec == VideoCodec::Raw).then_some(*b"I420"),
+    }
+}
+
+fn mp4_audio_track(codec: AudioCodec) -> Result<Mp4Track, RecordingError> {
+    match codec {
+        AudioCodec::Opus => Ok(Mp4Track::Audio {
+            sample_rate: SAMPLE_RATE,
+            sample_entry: mp4::opus_sample_entry(OPUS_CHANNELS as u16, codec::OPUS_PRE_SKIP),
+        }),
+        AudioCodec::Pcm => Err(RecordingError::Unsupported(
+            "PCM audio can't be recorded to MP4".to_owned(),
+        )),
+    }
+}
+
+fn mp4_video_track(config: &VideoConfig) -> Result<Mp4Track, RecordingError> {
+    let (width, height) = (config.width, config.height);
+    let sample_entry = match &config.codec {
+        VideoCodec::H264 { config } => mp4::avc1_sample_entry(width, height, config),
+        VideoCodec::VP9 { profile } => mp4::vp09_sample_entry(width, height, *profile),
+        VideoCodec::AV1 { config } => mp4::av01_sample_entry(width, height, config),
+        codec => {
+            return Err(RecordingError::Unsupported(format!(
+                "{:?} can't be recorded to MP4",
+                codec
+            )))
+        }
+    };
+
+    Ok(Mp4Track::Video {
+        width,
+        height,
+        sample_entry,
+    })
+}
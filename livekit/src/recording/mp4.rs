@@ -0,0 +1,571 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Minimal fragmented MP4 muxer. The samples are written in fragments (moof + mdat)
+//! of about a second, so the file stays playable if the recording is interrupted.
+
+const FRAGMENT_DURATION_US: i64 = 1_000_000;
+const MOVIE_TIMESCALE: u32 = 1000;
+
+const SAMPLE_FLAGS_SYNC: u32 = 0x0200_0000; // sample_depends_on = 2
+const SAMPLE_FLAGS_NON_SYNC: u32 = 0x0101_0000; // sample_depends_on = 1, non sync
+
+const MATRIX: [u32; 9] = [0x0001_0000, 0, 0, 0, 0x0001_0000, 0, 0, 0, 0x4000_0000];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mp4Track {
+    Video {
+        width: u32,
+        height: u32,
+        sample_entry: Vec<u8>, // e.g. avc1_sample_entry
+    },
+    Audio {
+        sample_rate: u32,
+        sample_entry: Vec<u8>,
+    },
+}
+
+impl Mp4Track {
+    fn timescale(&self) -> u32 {
+        match self {
+            Self::Video { .. } => 90000,
+            Self::Audio { sample_rate, .. } => *sample_rate,
+        }
+    }
+}
+
+struct Sample {
+    timestamp: u64, // In the timescale of the track
+    key_frame: bool,
+    data: Vec<u8>,
+}
+
+struct TrackState {
+    track: Mp4Track,
+    samples: Vec<Sample>,
+    decode_time: Option<u64>, // Of the first sample not written yet
+    last_duration: u32,
+}
+
+/// Writes the samples of the tracks given on creation, the track ids are their
+/// index + 1. Timestamps are in microseconds.
+/// The muxed bytes are buffered until they are taken with take_output.
+pub struct Mp4Writer {
+    output: Vec<u8>,
+    tracks: Vec<TrackState>,
+    sequence_number: u32,
+    fragment_start_us: Option<i64>,
+}
+
+impl Mp4Writer {
+    pub fn new(tracks: &[Mp4Track]) -> Self {
+        let mut output = Vec::new();
+        write_box(&mut output, b"ftyp", |b| {
+            b.extend_from_slice(b"isom");
+            b.extend_from_slice(&0x200u32.to_be_bytes());
+            for brand in [b"isom", b"iso6", b"mp41"] {
+                b.extend_from_slice(brand);
+            }
+        });
+
+        write_box(&mut output, b"moov", |b| {
+            write_full_box(b, b"mvhd", 0, 0, |b| {
+                write_u32s(b, &[0, 0, MOVIE_TIMESCALE, 0, 0x0001_0000]);
+                b.extend_from_slice(&0x0100u16.to_be_bytes()); // volume
+                b.extend_from_slice(&[0; 10]);
+                write_u32s(b, &MATRIX);
+                b.extend_from_slice(&[0; 24]);
+                write_u32s(b, &[tracks.len() as u32 + 1]);
+            });
+
+            for (i, track) in tracks.iter().enumerate() {
+                write_trak(b, i as u32 + 1, track);
+            }
+
+            write_box(b, b"mvex", |b| {
+                for i in 0..tracks.len() {
+                    write_full_box(b, b"trex", 0, 0, |b| {
+                        write_u32s(b, &[i as u32 + 1, 1, 0, 0, 0]);
+                    });
+                }
+            });
+        });
+
+        Self {
+            output,
+            tracks: tracks
+                .iter()
+                .map(|track| TrackState {
+                    track: track.clone(),
+                    samples: Vec::new(),
+                    decode_time: None,
+                    last_duration: 0,
+                })
+                .collect(),
+            sequence_number: 0,
+            fragment_start_us: None,
+        }
+    }
+
+    pub fn write_sample(
+        &mut self,
+        track_index: usize,
+        timestamp_us: i64,
+        key_frame: bool,
+        data: Vec<u8>,
+    ) {
+        let timestamp_us = timestamp_us.max(0);
+        let has_video = self
+            .tracks
+            .iter()
+            .any(|state| matches!(state.track, Mp4Track::Video { .. }));
+        let is_video = matches!(self.tracks[track_index].track, Mp4Track::Video { .. });
+
+        // Start the fragments on the video key frames, so they can be decoded on their own
+        let fragment_start_us = *self.fragment_start_us.get_or_insert(timestamp_us);
+        if timestamp_us - fragment_start_us >= FRAGMENT_DURATION_US
+            && (!has_video || (is_video && key_frame))
+        {
+            let timescale = self.tracks[track_index].track.timescale();
+            let next = (track_index, to_timescale(timestamp_us, timescale));
+            self.write_fragment(Some(next));
+            self.fragment_start_us = Some(timestamp_us);
+        }
+
+        let state = &mut self.tracks[track_index];
+        let timestamp = to_timescale(timestamp_us, state.track.timescale());
+        state.decode_time.get_or_insert(timestamp);
+        state.samples.push(Sample {
+            timestamp,
+            key_frame: key_frame || !is_video,
+            data,
+        });
+    }
+
+    /// The bytes muxed since the last call, the fragments are only output once complete
+    pub fn take_output(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.output)
+    }
+
+    /// Write the last fragment, returns the remaining output
+    pub fn finish(mut self) -> Vec<u8> {
+        self.write_fragment(None);
+        self.output
+    }
+
+    /// The duration of a sample is only known once the next one is received, the last
+    /// sample of a track is kept for the next fragment unless its next sample is `next`
+    /// (track index, timestamp). Everything is written when `next` is None (last fragment)
+    fn write_fragment(&mut self, next: Option<(usize, u64)>) {
+        let mut runs = Vec::new();
+        for (i, state) in self.tracks.iter_mut().enumerate() {
+            let next_timestamp = match next {
+                Some((track_index, timestamp)) if track_index == i => Some(timestamp),
+                Some(_) => None,
+                None => None,
+            };
+
+            let keep_last = next.is_some() && next_timestamp.is_none();
+            let count = state.samples.len() - (keep_last && !state.samples.is_empty()) as usize;
+            if count == 0 {
+                continue;
+            }
+
+            let mut kept = state.samples.split_off(count);
+            let samples = std::mem::take(&mut state.samples);
+            state.samples.append(&mut kept);
+
+            let durations = samples
+                .iter()
+                .enumerate()
+                .map(|(j, sample)| {
+                    let next = samples
+                        .get(j + 1)
+                        .map(|next| next.timestamp)
+                        .or(next_timestamp)
+                        .or_else(|| state.samples.first().map(|kept| kept.timestamp));
+                    match next {
+                        Some(next) => next.saturating_sub(sample.timestamp) as u32,
+                        None => state.last_duration,
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            let decode_time = state.decode_time.unwrap_or_default();
+            let total: u64 = durations.iter().map(|d| *d as u64).sum();
+            state.decode_time = Some(decode_time + total);
+            state.last_duration = *durations.last().unwrap();
+
+            runs.push((i as u32 + 1, decode_time, samples, durations));
+        }
+
+        if runs.is_empty() {
+            return;
+        }
+
+        self.sequence_number += 1;
+        let build_moof = |data_offset: u32| {
+            let mut moof = Vec::new();
+            write_box(&mut moof, b"moof", |b| {
+                write_full_box(b, b"mfhd", 0, 0, |b| {
+                    write_u32s(b, &[self.sequence_number]);
+                });
+
+                let mut offset = data_offset;
+                for (track_id, decode_time, samples, durations) in &runs {
+                    write_box(b, b"traf", |b| {
+                        // default-base-is-moof
+                        write_full_box(b, b"tfhd", 0, 0x02_0000, |b| {
+                            write_u32s(b, &[*track_id]);
+                        });
+                        write_full_box(b, b"tfdt", 1, 0, |b| {
+                            b.extend_from_slice(&decode_time.to_be_bytes());
+                        });
+                        // data-offset, sample-duration, sample-size and sample-flags present
+                        write_full_box(b, b"trun", 0, 0x00_0701, |b| {
+                            write_u32s(b, &[samples.len() as u32, offset]);
+                            for (sample, duration) in samples.iter().zip(durations) {
+                                let flags = if sample.key_frame {
+                                    SAMPLE_FLAGS_SYNC
+                                } else {
+                                    SAMPLE_FLAGS_NON_SYNC
+                                };
+                                write_u32s(b, &[*duration, sample.data.len() as u32, flags]);
+                            }
+                        });
+                    });
+                    offset += samples.iter().map(|s| s.data.len() as u32).sum::<u32>();
+                }
+            });
+            moof
+        };
+
+        // The data offsets are relative to the moof, which has the same size whatever
+        // the offsets are
+        let moof_len = build_moof(0).len() as u32;
+        let moof = build_moof(moof_len + 8);
+        self.output.extend_from_slice(&moof);
+
+        let mdat_len: usize = runs
+            .iter()
+            .flat_map(|(_, _, samples, _)| samples.iter().map(|s| s.data.len()))
+            .sum();
+        self.output
+            .extend_from_slice(&(mdat_len as u32 + 8).to_be_bytes());
+        self.output.extend_from_slice(b"mdat");
+        for (_, _, samples, _) in runs {
+            for sample in samples {
+                self.output.extend_from_slice(&sample.data);
+            }
+        }
+    }
+}
+
+fn to_timescale(timestamp_us: i64, timescale: u32) -> u64 {
+    (timestamp_us as i128 * timescale as i128 / 1_000_000) as u64
+}
+
+fn write_trak(b: &mut Vec<u8>, track_id: u32, track: &Mp4Track) {
+    let (width, height, volume, handler, name) = match track {
+        Mp4Track::Video { width, height, .. } => {
+            (*width, *height, 0u16, b"vide", &b"VideoHandler\0"[..])
+        }
+        Mp4Track::Audio { .. } => (0, 0, 0x0100, b"soun", &b"SoundHandler\0"[..]),
+    };
+
+    write_box(b, b"trak", |b| {
+        // track_enabled and track_in_movie
+        write_full_box(b, b"tkhd", 0, 3, |b| {
+            write_u32s(b, &[0, 0, track_id, 0, 0, 0, 0]);
+            b.extend_from_slice(&[0; 4]); // layer and alternate_group
+            b.extend_from_slice(&volume.to_be_bytes());
+            b.extend_from_slice(&[0; 2]);
+            write_u32s(b, &MATRIX);
+            write_u32s(b, &[width << 16, height << 16]);
+        });
+
+        write_box(b, b"mdia", |b| {
+            write_full_box(b, b"mdhd", 0, 0, |b| {
+                write_u32s(b, &[0, 0, track.timescale(), 0]);
+                b.extend_from_slice(&0x55C4u16.to_be_bytes()); // "und"
+                b.extend_from_slice(&[0; 2]);
+            });
+
+            write_full_box(b, b"hdlr", 0, 0, |b| {
+                b.extend_from_slice(&[0; 4]);
+                b.extend_from_slice(handler);
+                b.extend_from_slice(&[0; 12]);
+                b.extend_from_slice(name);
+            });
+
+            write_box(b, b"minf", |b| {
+                match track {
+                    Mp4Track::Video { .. } => write_full_box(b, b"vmhd", 0, 1, |b| {
+                        b.extend_from_slice(&[0; 8]);
+                    }),
+                    Mp4Track::Audio { .. } => write_full_box(b, b"smhd", 0, 0, |b| {
+                        b.extend_from_slice(&[0; 4]);
+                    }),
+                }
+
+                write_box(b, b"dinf", |b| {
+                    write_full_box(b, b"dref", 0, 0, |b| {
+                        write_u32s(b, &[1]);
+                        // Self contained
+                        write_full_box(b, b"url ", 0, 1, |_| {});
+                    });
+                });
+
+                // The samples are described by the fragments
+                write_box(b, b"stbl", |b| {
+                    write_full_box(b, b"stsd", 0, 0, |b| {
+                        write_u32s(b, &[1]);
+                        match track {
+                            Mp4Track::Video { sample_entry, .. }
+                            | Mp4Track::Audio { sample_entry, .. } => {
+                                b.extend_from_slice(sample_entry)
+                            }
+                        }
+                    });
+                    write_full_box(b, b"stts", 0, 0, |b| write_u32s(b, &[0]));
+                    write_full_box(b, b"stsc", 0, 0, |b| write_u32s(b, &[0]));
+                    write_full_box(b, b"stsz", 0, 0, |b| write_u32s(b, &[0, 0]));
+                    write_full_box(b, b"stco", 0, 0, |b| write_u32s(b, &[0]));
+                });
+            });
+        });
+    });
+}
+
+fn visual_sample_entry(kind: &[u8; 4], width: u32, height: u32, config: &[u8]) -> Vec<u8> {
+    let mut entry = Vec::new();
+    write_box(&mut entry, kind, |b| {
+        b.extend_from_slice(&[0; 6]);
+        b.extend_from_slice(&1u16.to_be_bytes()); // data_reference_index
+        b.extend_from_slice(&[0; 16]);
+        b.extend_from_slice(&(width as u16).to_be_bytes());
+        b.extend_from_slice(&(height as u16).to_be_bytes());
+        write_u32s(b, &[0x0048_0000, 0x0048_0000, 0]); // 72 dpi
+        b.extend_from_slice(&1u16.to_be_bytes()); // frame_count
+        b.extend_from_slice(&[0; 32]); // compressorname
+        b.extend_from_slice(&0x0018u16.to_be_bytes()); // depth
+        b.extend_from_slice(&(-1i16).to_be_bytes());
+        b.extend_from_slice(config);
+    });
+    entry
+}
+
+/// H264, avcc is the AVCDecoderConfigurationRecord
+pub fn avc1_sample_entry(width: u32, height: u32, avcc: &[u8]) -> Vec<u8> {
+    let mut config = Vec::new();
+    write_box(&mut config, b"avcC", |b| b.extend_from_slice(avcc));
+    visual_sample_entry(b"avc1", width, height, &config)
+}
+
+/// AV1, av1c is the AV1CodecConfigurationRecord
+pub fn av01_sample_entry(width: u32, height: u32, av1c: &[u8]) -> Vec<u8> {
+    let mut config = Vec::new();
+    write_box(&mut config, b"av1C", |b| b.extend_from_slice(av1c));
+    visual_sample_entry(b"av01", width, height, &config)
+}
+
+/// VP9 8-bit 4:2:0 (profile 0) or 10-bit (profile 2)
+pub fn vp09_sample_entry(width: u32, height: u32, profile: u8) -> Vec<u8> {
+    let bit_depth = if profile >= 2 { 10 } else { 8 };
+    let mut config = Vec::new();
+    write_full_box(&mut config, b"vpcC", 1, 0, |b| {
+        b.push(profile);
+        b.push(vp9_level(width, height));
+        b.push(bit_depth << 4 | 1 << 1); // 4:2:0 colocated, limited range
+        b.extend_from_slice(&[2, 2, 2]); // Unspecified colour primaries, transfer, matrix
+        b.extend_from_slice(&0u16.to_be_bytes());
+    });
+    visual_sample_entry(b"vp09", width, height, &config)
+}
+
+/// Lowest VP9 level allowing the picture size
+fn vp9_level(width: u32, height: u32) -> u8 {
+    const LEVELS: [(u64, u8); 9] = [
+        (36864, 10),
+        (73728, 11),
+        (122880, 20),
+        (245760, 21),
+        (552960, 30),
+        (983040, 31),
+        (2228224, 40),
+        (8912896, 50),
+        (35651584, 60),
+    ];
+
+    let size = width as u64 * height as u64;
+    LEVELS
+        .iter()
+        .find(|(max_size, _)| size <= *max_size)
+        .map_or(62, |(_, level)| *level)
+}
+
+/// Opus at 48kHz
+pub fn opus_sample_entry(num_channels: u16, pre_skip: u16) -> Vec<u8> {
+    let mut entry = Vec::new();
+    write_box(&mut entry, b"Opus", |b| {
+        b.extend_from_slice(&[0; 6]);
+        b.extend_from_slice(&1u16.to_be_bytes()); // data_reference_index
+        b.extend_from_slice(&[0; 8]);
+        b.extend_from_slice(&num_channels.to_be_bytes());
+        b.extend_from_slice(&16u16.to_be_bytes()); // samplesize
+        b.extend_from_slice(&[0; 4]);
+        write_u32s(b, &[48000 << 16]);
+
+        write_box(b, b"dOps", |b| {
+            b.push(0); // version
+            b.push(num_channels as u8);
+            b.extend_from_slice(&pre_skip.to_be_bytes());
+            write_u32s(b, &[48000]);
+            b.extend_from_slice(&0i16.to_be_bytes()); // output gain
+            b.push(0); // channel mapping family
+        });
+    });
+    entry
+}
+
+fn write_u32s(buf: &mut Vec<u8>, values: &[u32]) {
+    for value in values {
+        buf.extend_from_slice(&value.to_be_bytes());
+    }
+}
+
+fn write_box(buf: &mut Vec<u8>, kind: &[u8; 4], f: impl FnOnce(&mut Vec<u8>)) {
+    let start = buf.len();
+    buf.extend_from_slice(&[0; 4]);
+    buf.extend_from_slice(kind);
+    f(buf);
+    let size = (buf.len() - start) as u32;
+    buf[start..start + 4].copy_from_slice(&size.to_be_bytes());
+}
+
+fn write_full_box(
+    buf: &mut Vec<u8>,
+    kind: &[u8; 4],
+    version: u8,
+    flags: u32,
+    f: impl FnOnce(&mut Vec<u8>),
+) {
+    write_box(buf, kind, |b| {
+        b.extend_from_slice(&(flags | (version as u32) << 24).to_be_bytes());
+        f(b);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// (kind, body) of the boxes of a buffer
+    fn boxes(mut data: &[u8]) -> Vec<([u8; 4], &[u8])> {
+        let mut boxes = Vec::new();
+        while data.len() >= 8 {
+            let size = u32::from_be_bytes(data[..4].try_into().unwrap()) as usize;
+            boxes.push((data[4..8].try_into().unwrap(), &data[8..size]));
+            data = &data[size..];
+        }
+        assert!(data.is_empty());
+        boxes
+    }
+
+    fn child<'a>(data: &'a [u8], kind: &[u8; 4]) -> &'a [u8] {
+        boxes(data)
+            .into_iter()
+            .find(|(k, _)| k == kind)
+            .map(|(_, body)| body)
+            .unwrap()
+    }
+
+    #[test]
+    fn fragments() {
+        let tracks = [
+            Mp4Track::Audio {
+                sample_rate: 48000,
+                sample_entry: opus_sample_entry(2, 312),
+            },
+            Mp4Track::Video {
+                width: 640,
+                height: 480,
+                sample_entry: vp09_sample_entry(640, 480, 0),
+            },
+        ];
+        let mut mp4 = Mp4Writer::new(&tracks);
+        let header = mp4.take_output();
+        let top = boxes(&header);
+        assert_eq!(top[0].0, *b"ftyp");
+        assert_eq!(top[1].0, *b"moov");
+        let traks = boxes(top[1].1)
+            .into_iter()
+            .filter(|(kind, _)| kind == b"trak")
+            .count();
+        assert_eq!(traks, 2);
+
+        mp4.write_sample(1, 0, true, vec![1; 10]);
+        mp4.write_sample(0, 0, true, vec![2; 3]);
+        mp4.write_sample(0, 20_000, true, vec![3; 3]);
+        mp4.write_sample(1, 500_000, false, vec![4; 10]);
+        // Not a key frame, the fragment continues
+        mp4.write_sample(1, 1_000_000, false, vec![5; 10]);
+        assert!(mp4.take_output().is_empty());
+
+        mp4.write_sample(1, 1_500_000, true, vec![6; 10]);
+        let fragment = mp4.take_output();
+        let top = boxes(&fragment);
+        assert_eq!(top[0].0, *b"moof");
+        assert_eq!(top[1].0, *b"mdat");
+
+        // The last audio sample waits for its duration, all the video before the key frame
+        // is written
+        let mdat = top[1].1;
+        assert_eq!(mdat.len(), 3 + 30);
+
+        let trafs = boxes(top[0].1)
+            .into_iter()
+            .filter(|(kind, _)| kind == b"traf")
+            .collect::<Vec<_>>();
+        assert_eq!(trafs.len(), 2);
+
+        let audio_trun = child(trafs[0].1, b"trun");
+        // sample_count, data_offset, then duration/size/flags
+        assert_eq!(&audio_trun[4..8], &1u32.to_be_bytes());
+        let data_offset = u32::from_be_bytes(audio_trun[8..12].try_into().unwrap()) as usize;
+        assert_eq!(data_offset, top[0].1.len() + 16);
+        assert_eq!(&audio_trun[12..16], &960u32.to_be_bytes());
+
+        let video_trun = child(trafs[1].1, b"trun");
+        assert_eq!(&video_trun[4..8], &3u32.to_be_bytes());
+        // 500ms at 90kHz, the first frame is a sync sample
+        assert_eq!(&video_trun[12..16], &45000u32.to_be_bytes());
+        assert_eq!(&video_trun[20..24], &SAMPLE_FLAGS_SYNC.to_be_bytes());
+        assert_eq!(&video_trun[32..36], &SAMPLE_FLAGS_NON_SYNC.to_be_bytes());
+
+        let video_tfdt = child(trafs[1].1, b"tfdt");
+        assert_eq!(&video_tfdt[4..], &0u64.to_be_bytes());
+
+        let out = mp4.finish();
+        let top = boxes(&out);
+        assert_eq!(top[1].1.len(), 3 + 10);
+    }
+
+    #[test]
+    fn levels() {
+        assert_eq!(vp9_level(640, 480), 30);
+        assert_eq!(vp9_level(1280, 720), 31);
+        assert_eq!(vp9_level(1920, 1080), 40);
+    }
+}
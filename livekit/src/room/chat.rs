@@ -0,0 +1,341 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Chat over the standard LiveKit chat topic, compatible with the chat components of
+//! the JS SDK. Each message is a text stream, edits and deletions are text streams
+//! reusing the id of the message (see Room::chat)
+
+use super::data_stream::{
+    StreamError, StreamTextOptions, TextStreamInfo, TextStreamOperation, TextStreamReader,
+};
+use crate::id::ParticipantIdentity;
+use crate::participant::LocalParticipant;
+use livekit_protocol::observer::Dispatcher;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+use thiserror::Error;
+use tokio::sync::mpsc;
+
+pub const CHAT_TOPIC: &str = "lk.chat";
+
+#[derive(Debug, Error)]
+pub enum ChatError {
+    #[error("only the sender of a message can edit or delete it")]
+    NotSender,
+    #[error("stream: {0}")]
+    Stream(#[from] StreamError),
+}
+
+pub type ChatResult<T> = Result<T, ChatError>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChatMessage {
+    pub id: String,
+    pub sender: ParticipantIdentity,
+    /// Unix time in milliseconds at which the message was first sent
+    pub timestamp: i64,
+    pub message: String,
+    pub edited: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum ChatEvent {
+    MessageReceived(ChatMessage),
+    MessageEdited(ChatMessage),
+    MessageDeleted {
+        id: String,
+        sender: ParticipantIdentity,
+    },
+}
+
+struct ChatEntry {
+    message: ChatMessage,
+    version: i32,
+}
+
+#[derive(Default)]
+struct ChatState {
+    entries: HashMap<String, ChatEntry>,
+}
+
+struct ChatInner {
+    local_participant: LocalParticipant,
+    dispatcher: Dispatcher<ChatEvent>,
+    state: Mutex<ChatState>,
+}
+
+/// Messages of the room on the chat topic, including the ones sent by the local participant
+#[derive(Clone)]
+pub struct Chat {
+    inner: Arc<ChatInner>,
+}
+
+impl Debug for Chat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Chat")
+            .field("messages", &self.inner.state.lock().entries.len())
+            .finish()
+    }
+}
+
+impl Chat {
+    pub(crate) fn new(local_participant: LocalParticipant) -> Self {
+        Self {
+            inner: Arc::new(ChatInner {
+                local_participant,
+                dispatcher: Default::default(),
+                state: Default::default(),
+            }),
+        }
+    }
+
+    pub fn subscribe(&self) -> mpsc::UnboundedReceiver<ChatEvent> {
+        self.inner.dispatcher.register()
+    }
+
+    /// Messages received since the chat was opened, oldest first
+    pub fn messages(&self) -> Vec<ChatMessage> {
+        let mut messages: Vec<_> = self
+            .inner
+            .state
+            .lock()
+            .entries
+            .values()
+            .map(|entry| entry.message.clone())
+            .collect();
+        messages.sort_by_key(|message| message.timestamp);
+        messages
+    }
+
+    pub async fn send_message(&self, message: String) -> ChatResult<ChatMessage> {
+        let info = self
+            .inner
+            .local_participant
+            .send_text(&message, self.options(None, TextStreamOperation::Create, 0))
+            .await?;
+
+        self.apply_local(&info, message)
+    }
+
+    pub async fn edit_message(
+        &self,
+        message: &ChatMessage,
+        text: String,
+    ) -> ChatResult<ChatMessage> {
+        let version = self.local_version(message)? + 1;
+        let options = self.options(
+            Some(message.id.clone()),
+            TextStreamOperation::Update,
+            version,
+        );
+        let info = self
+            .inner
+            .local_participant
+            .send_text(&text, options)
+            .await?;
+
+        self.apply_local(&info, text)
+    }
+
+    pub async fn delete_message(&self, message: &ChatMessage) -> ChatResult<()> {
+        let version = self.local_version(message)? + 1;
+        let options = self.options(
+            Some(message.id.clone()),
+            TextStreamOperation::Delete,
+            version,
+        );
+        let info = self.inner.local_participant.send_text("", options).await?;
+
+        self.apply_local(&info, String::new()).map(|_| ())
+    }
+
+    pub(crate) fn handle_stream(&self, reader: TextStreamReader, sender: ParticipantIdentity) {
+        let chat = self.clone();
+        tokio::spawn(async move {
+            let info = reader.info().clone();
+            match reader.read_all().await {
+                Ok(text) => chat.apply(&info, sender, text),
+                Err(err) => log::warn!("failed to receive chat message {}: {}", info.id, err),
+            }
+        });
+    }
+
+    fn options(
+        &self,
+        stream_id: Option<String>,
+        operation: TextStreamOperation,
+        version: i32,
+    ) -> StreamTextOptions {
+        StreamTextOptions {
+            topic: CHAT_TOPIC.to_owned(),
+            stream_id,
+            operation,
+            version,
+            ..Default::default()
+        }
+    }
+
+    fn local_version(&self, message: &ChatMessage) -> ChatResult<i32> {
+        if message.sender != self.inner.local_participant.identity() {
+            return Err(ChatError::NotSender);
+        }
+
+        let state = self.inner.state.lock();
+        Ok(state
+            .entries
+            .get(&message.id)
+            .map(|entry| entry.version)
+            .unwrap_or_default())
+    }
+
+    fn apply_local(&self, info: &TextStreamInfo, text: String) -> ChatResult<ChatMessage> {
+        let sender = self.inner.local_participant.identity();
+        let event = self.inner.state.lock().apply(info, sender.clone(), text);
+        if let Some(event) = &event {
+            self.inner.dispatcher.dispatch(event);
+        }
+
+        // Deletions don't leave a message behind
+        Ok(match event {
+            Some(ChatEvent::MessageReceived(message)) | Some(ChatEvent::MessageEdited(message)) => {
+                message
+            }
+            _ => ChatMessage {
+                id: info.id.clone(),
+                sender,
+                timestamp: info.timestamp,
+                message: String::new(),
+                edited: info.operation != TextStreamOperation::Create,
+            },
+        })
+    }
+
+    fn apply(&self, info: &TextStreamInfo, sender: ParticipantIdentity, text: String) {
+        let event = self.inner.state.lock().apply(info, sender, text);
+        if let Some(event) = event {
+            self.inner.dispatcher.dispatch(&event);
+        }
+    }
+}
+
+impl ChatState {
+    /// Streams can be received out of order, updates only apply to a newer version
+    fn apply(
+        &mut self,
+        info: &TextStreamInfo,
+        sender: ParticipantIdentity,
+        text: String,
+    ) -> Option<ChatEvent> {
+        if let Some(entry) = self.entries.get(&info.id) {
+            // Only the sender of a message can change it
+            if entry.message.sender != sender || info.version <= entry.version {
+                return None;
+            }
+        }
+
+        match info.operation {
+            TextStreamOperation::Create | TextStreamOperation::Update => {
+                let edited = info.operation == TextStreamOperation::Update;
+                let entry = self
+                    .entries
+                    .entry(info.id.clone())
+                    .or_insert_with(|| ChatEntry {
+                        message: ChatMessage {
+                            id: info.id.clone(),
+                            sender,
+                            timestamp: info.timestamp,
+                            message: String::new(),
+                            edited,
+                        },
+                        version: info.version,
+                    });
+
+                entry.message.message = text;
+                entry.message.edited = edited;
+                entry.version = info.version;
+
+                let message = entry.message.clone();
+                Some(if edited {
+                    ChatEvent::MessageEdited(message)
+                } else {
+                    ChatEvent::MessageReceived(message)
+                })
+            }
+            TextStreamOperation::Delete => {
+                let entry = self.entries.remove(&info.id)?;
+                Some(ChatEvent::MessageDeleted {
+                    id: info.id.clone(),
+                    sender: entry.message.sender,
+                })
+            }
+            TextStreamOperation::Reaction => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(id: &str, operation: TextStreamOperation, version: i32) -> TextStreamInfo {
+        TextStreamInfo {
+            id: id.to_owned(),
+            topic: CHAT_TOPIC.to_owned(),
+            timestamp: 1000 + version as i64,
+            mime_type: "text/plain".to_owned(),
+            total_length: None,
+            attributes: HashMap::new(),
+            reply_to_stream_id: None,
+            generated: false,
+            operation,
+            version,
+        }
+    }
+
+    #[test]
+    fn edits_and_deletions() {
+        let alice: ParticipantIdentity = "alice".to_owned().into();
+        let mut state = ChatState::default();
+
+        let event = state.apply(
+            &info("1", TextStreamOperation::Create, 0),
+            alice.clone(),
+            "hi".into(),
+        );
+        assert!(matches!(event, Some(ChatEvent::MessageReceived(m)) if m.message == "hi"));
+
+        // Edits from other participants and stale versions are ignored
+        let edit = info("1", TextStreamOperation::Update, 1);
+        assert!(state
+            .apply(&edit, "bob".to_owned().into(), "bye".into())
+            .is_none());
+        let event = state.apply(&edit, alice.clone(), "hello".into());
+        let Some(ChatEvent::MessageEdited(message)) = event else {
+            panic!("expected an edit");
+        };
+        assert_eq!(message.timestamp, 1000);
+        assert!(message.edited);
+        assert!(state.apply(&edit, alice.clone(), "hey".into()).is_none());
+
+        let event = state.apply(
+            &info("1", TextStreamOperation::Delete, 2),
+            alice,
+            String::new(),
+        );
+        assert!(matches!(event, Some(ChatEvent::MessageDeleted { id, .. }) if id == "1"));
+        assert!(state.entries.is_empty());
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! zstd compression of the reliable user data (see RoomOptions::data_compression).
+//! The participants reading compressed data advertise it with COMPRESSION_ATTRIBUTE, the
+//! data is only compressed when every receiver advertises it. A compressed payload is
+//! flagged by its topic, so the receivers restore the topic and the payload transparently
+
+use std::collections::HashMap;
+use std::io;
+
+/// Participant attribute advertising that the participant reads compressed data
+pub const COMPRESSION_ATTRIBUTE: &str = "lk.compression";
+pub const COMPRESSION_ZSTD: &str = "zstd";
+
+/// Smaller payloads are always sent as is, the gain isn't worth the cost
+pub const COMPRESSION_THRESHOLD: usize = 1024;
+
+/// Protects the receivers from payloads decompressing to huge buffers
+pub const MAX_DECOMPRESSED_SIZE: usize = 16 * 1024 * 1024;
+
+const COMPRESSION_LEVEL: i32 = 3;
+
+// Topic of the compressed payloads without topic, the others are prefixed with it
+const COMPRESSED_TOPIC: &str = "lk.zstd";
+const COMPRESSED_TOPIC_PREFIX: &str = "lk.zstd/";
+
+/// Whether the attributes of a participant advertise that it reads compressed data
+pub(crate) fn is_supported(attributes: &HashMap<String, String>) -> bool {
+    attributes
+        .get(COMPRESSION_ATTRIBUTE)
+        .is_some_and(|value| value == COMPRESSION_ZSTD)
+}
+
+/// Returns the flagged topic and the compressed payload, None when the payload is too
+/// small or doesn't get smaller
+pub(crate) fn compress(topic: Option<&str>, payload: &[u8]) -> Option<(Option<String>, Vec<u8>)> {
+    if payload.len() < COMPRESSION_THRESHOLD {
+        return None;
+    }
+
+    let compressed = match zstd::bulk::compress(payload, COMPRESSION_LEVEL) {
+        Ok(compressed) if compressed.len() < payload.len() => compressed,
+        Ok(_) => return None,
+        Err(err) => {
+            log::warn!("failed to compress data: {}", err);
+            return None;
+        }
+    };
+
+    let topic = match topic {
+        Some(topic) => format!("{}{}", COMPRESSED_TOPIC_PREFIX, topic),
+        None => COMPRESSED_TOPIC.to_owned(),
+    };
+    Some((Some(topic), compressed))
+}
+
+/// Restores the topic and the payload of a compressed packet, the other packets are
+/// returned as is
+pub(crate) fn decompress(
+    topic: Option<String>,
+    payload: Vec<u8>,
+) -> io::Result<(Option<String>, Vec<u8>)> {
+    let topic = match topic.as_deref() {
+        Some(COMPRESSED_TOPIC) => None,
+        Some(flagged) => match flagged.strip_prefix(COMPRESSED_TOPIC_PREFIX) {
+            Some(topic) => Some(topic.to_owned()),
+            None => return Ok((topic, payload)),
+        },
+        None => return Ok((None, payload)),
+    };
+
+    let payload = zstd::bulk::decompress(&payload, MAX_DECOMPRESSED_SIZE)?;
+    Ok((topic, payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compression_roundtrip() {
+        assert!(compress(None, b"{\"x\":1}").is_none());
+
+        let state = "{\"cursor\":{\"x\":10,\"y\":20}}".repeat(100);
+        let (topic, compressed) = compress(Some("state"), state.as_bytes()).unwrap();
+        assert!(compressed.len() < state.len());
+        assert_eq!(topic.as_deref(), Some("lk.zstd/state"));
+
+        let (topic, payload) = decompress(topic, compressed).unwrap();
+        assert_eq!(topic.as_deref(), Some("state"));
+        assert_eq!(payload, state.as_bytes());
+
+        let (topic, compressed) = compress(None, state.as_bytes()).unwrap();
+        assert_eq!(
+            decompress(topic, compressed).unwrap(),
+            (None, state.clone().into_bytes())
+        );
+
+        // The packets that aren't flagged are left untouched
+        let packet = (Some("state".to_owned()), b"abc".to_vec());
+        assert_eq!(
+            decompress(packet.0.clone(), packet.1.clone()).unwrap(),
+            packet
+        );
+        assert!(decompress(Some(COMPRESSED_TOPIC.to_owned()), b"abc".to_vec()).is_err());
+    }
+}
@@ -0,0 +1,870 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Byte and text streams sent over the reliable data channel, compatible with the data
+//! streams of the JS and Swift SDKs. A stream is a header packet, chunks of at most
+//! STREAM_CHUNK_SIZE bytes and a trailer packet (see LocalParticipant::stream_bytes,
+//! send_file, send_text and Room::register_byte_stream_handler).
+//! Receivers reading slower than the stream is sent ask the sender to pause it, on
+//! STREAM_FLOW_CONTROL_TOPIC. Senders that don't support it (e.g. the other SDKs) are
+//! never asked, their streams fail once STREAM_BUFFERED_CHUNKS chunks are unread
+
+use crate::id::ParticipantIdentity;
+use crate::rtc_engine::EngineError;
+use futures_util::Stream;
+use livekit_protocol as proto;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+use thiserror::Error;
+use tokio::io::{AsyncRead, ReadBuf};
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{mpsc, watch};
+
+/// Maximum size of the content of a chunk, in bytes
+pub const STREAM_CHUNK_SIZE: usize = 15_000;
+
+/// Unread chunks of an incoming stream above which the stream fails
+pub const STREAM_BUFFERED_CHUNKS: usize = 256;
+
+/// Topic of the requests to pause and resume a stream, the data received on it is not
+/// dispatched to the application
+pub const STREAM_FLOW_CONTROL_TOPIC: &str = "lk.stream_flow";
+
+// Set in the header attributes of the streams whose sender can be paused
+pub(crate) const STREAM_FLOW_CONTROL_ATTRIBUTE: &str = "lk.flow_control";
+
+// Unread chunks above which the sender is asked to pause, it is asked to resume once the
+// reader caught up below half of it
+const STREAM_PAUSE_CHUNKS: usize = STREAM_BUFFERED_CHUNKS / 2;
+
+#[derive(Debug, Error)]
+pub enum StreamError {
+    #[error("stream aborted by the sender: {0}")]
+    Aborted(String),
+    #[error("stream closed before it was complete")]
+    Incomplete,
+    #[error("the reader didn't keep up with the stream")]
+    ReaderTooSlow,
+    #[error("failed to send: {0}")]
+    Send(#[from] EngineError),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+pub type StreamResult<T> = Result<T, StreamError>;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StreamProgress {
+    pub bytes_received: u64,
+    /// Only known when the sender gave the length of the stream in its header
+    pub total_length: Option<u64>,
+}
+
+impl StreamProgress {
+    /// Between 0.0 and 1.0, None when the total length is unknown
+    pub fn fraction(&self) -> Option<f32> {
+        match self.total_length {
+            Some(0) => Some(1.0),
+            Some(total) => Some((self.bytes_received as f64 / total as f64).min(1.0) as f32),
+            None => None,
+        }
+    }
+}
+
+/// What a text stream does to the stream it refers to (by its id)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextStreamOperation {
+    #[default]
+    Create,
+    Update,
+    Delete,
+    Reaction,
+}
+
+impl From<proto::data_stream::OperationType> for TextStreamOperation {
+    fn from(value: proto::data_stream::OperationType) -> Self {
+        match value {
+            proto::data_stream::OperationType::Create => Self::Create,
+            proto::data_stream::OperationType::Update => Self::Update,
+            proto::data_stream::OperationType::Delete => Self::Delete,
+            proto::data_stream::OperationType::Reaction => Self::Reaction,
+        }
+    }
+}
+
+impl From<TextStreamOperation> for proto::data_stream::OperationType {
+    fn from(value: TextStreamOperation) -> Self {
+        match value {
+            TextStreamOperation::Create => Self::Create,
+            TextStreamOperation::Update => Self::Update,
+            TextStreamOperation::Delete => Self::Delete,
+            TextStreamOperation::Reaction => Self::Reaction,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ByteStreamInfo {
+    pub id: String,
+    pub topic: String,
+    /// Unix time in milliseconds at which the stream was opened
+    pub timestamp: i64,
+    pub mime_type: String,
+    pub total_length: Option<u64>,
+    pub attributes: HashMap<String, String>,
+    pub name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct TextStreamInfo {
+    pub id: String,
+    pub topic: String,
+    /// Unix time in milliseconds at which the stream was opened
+    pub timestamp: i64,
+    pub mime_type: String,
+    pub total_length: Option<u64>,
+    pub attributes: HashMap<String, String>,
+    pub reply_to_stream_id: Option<String>,
+    pub generated: bool,
+    pub operation: TextStreamOperation,
+    /// Incremented by each update of the stream
+    pub version: i32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StreamByteOptions {
+    pub topic: String,
+    /// Defaults to the file name with send_file
+    pub name: Option<String>,
+    /// Defaults to application/octet-stream
+    pub mime_type: Option<String>,
+    /// Lets the receivers compute their progress, defaults to the file size with send_file
+    pub total_length: Option<u64>,
+    pub attributes: HashMap<String, String>,
+    /// Sent to everyone in the room when empty
+    pub destination_identities: Vec<ParticipantIdentity>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StreamTextOptions {
+    pub topic: String,
+    pub attributes: HashMap<String, String>,
+    /// Sent to everyone in the room when empty
+    pub destination_identities: Vec<ParticipantIdentity>,
+    pub reply_to_stream_id: Option<String>,
+    /// Id of the stream to update or delete, a new id is generated when None
+    pub stream_id: Option<String>,
+    pub operation: TextStreamOperation,
+    pub version: i32,
+}
+
+impl ByteStreamInfo {
+    pub(crate) fn from_header(header: &proto::data_stream::Header, name: String) -> Self {
+        Self {
+            id: header.stream_id.clone(),
+            topic: header.topic.clone(),
+            timestamp: header.timestamp,
+            mime_type: header.mime_type.clone(),
+            total_length: header.total_length,
+            attributes: header.attributes.clone(),
+            name,
+        }
+    }
+}
+
+impl TextStreamInfo {
+    pub(crate) fn from_header(
+        header: &proto::data_stream::Header,
+        text_header: &proto::data_stream::TextHeader,
+    ) -> Self {
+        Self {
+            id: header.stream_id.clone(),
+            topic: header.topic.clone(),
+            timestamp: header.timestamp,
+            mime_type: header.mime_type.clone(),
+            total_length: header.total_length,
+            attributes: header.attributes.clone(),
+            reply_to_stream_id: (!text_header.reply_to_stream_id.is_empty())
+                .then(|| text_header.reply_to_stream_id.clone()),
+            generated: text_header.generated,
+            operation: text_header.operation_type().into(),
+            version: text_header.version,
+        }
+    }
+}
+
+/// Request of a receiver to pause or resume a stream, sent on STREAM_FLOW_CONTROL_TOPIC
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FlowControl {
+    pub stream_id: String,
+    pub paused: bool,
+}
+
+pub(crate) type FlowControlSender = mpsc::UnboundedSender<(ParticipantIdentity, FlowControl)>;
+
+#[derive(Default)]
+struct FlowState {
+    buffered: usize,
+    paused: bool,
+}
+
+/// Unread chunks of an incoming stream, shared by the room and the reader
+struct StreamFlow {
+    stream_id: String,
+    sender: ParticipantIdentity,
+    // None when the sender of the stream can't be paused
+    flow_control: Option<FlowControlSender>,
+    state: Mutex<FlowState>,
+}
+
+impl StreamFlow {
+    fn received(&self) {
+        let mut state = self.state.lock();
+        state.buffered += 1;
+        if !state.paused && state.buffered > STREAM_PAUSE_CHUNKS {
+            state.paused = true;
+            self.send(true);
+        }
+    }
+
+    fn consumed(&self, chunks: usize) {
+        let mut state = self.state.lock();
+        state.buffered = state.buffered.saturating_sub(chunks);
+        if state.paused && state.buffered <= STREAM_PAUSE_CHUNKS / 2 {
+            state.paused = false;
+            self.send(false);
+        }
+    }
+
+    // Called with the state locked, so that the requests are queued in order
+    fn send(&self, paused: bool) {
+        if let Some(flow_control) = &self.flow_control {
+            let flow = FlowControl {
+                stream_id: self.stream_id.clone(),
+                paused,
+            };
+            let _ = flow_control.send((self.sender.clone(), flow));
+        }
+    }
+}
+
+/// Chunks of an incoming stream, shared by the byte and text readers
+struct ChunkReceiver {
+    chunks: mpsc::Receiver<Vec<u8>>,
+    // Why the stream ended early, read once every chunk was received
+    end: Arc<Mutex<Option<StreamError>>>,
+    progress: watch::Receiver<StreamProgress>,
+    flow: Arc<StreamFlow>,
+}
+
+impl ChunkReceiver {
+    fn poll_chunk(&mut self, cx: &mut Context<'_>) -> Poll<Option<StreamResult<Vec<u8>>>> {
+        match ready!(self.chunks.poll_recv(cx)) {
+            Some(chunk) => {
+                self.flow.consumed(1);
+                Poll::Ready(Some(Ok(chunk)))
+            }
+            None => Poll::Ready(self.end.lock().take().map(Err)),
+        }
+    }
+
+    async fn read_all(&mut self) -> StreamResult<Vec<u8>> {
+        let mut data = Vec::new();
+        while let Some(chunk) = self.chunks.recv().await {
+            self.flow.consumed(1);
+            data.extend_from_slice(&chunk);
+        }
+        match self.end.lock().take() {
+            Some(err) => Err(err),
+            None => Ok(data),
+        }
+    }
+}
+
+impl Drop for ChunkReceiver {
+    fn drop(&mut self) {
+        // Nobody reads the stream anymore, don't leave its sender paused
+        self.flow.consumed(usize::MAX);
+    }
+}
+
+/// Incoming byte stream, yields the chunks as they are received.
+/// Also implements AsyncRead, e.g. to copy the stream into a file with tokio::io::copy
+pub struct ByteStreamReader {
+    info: ByteStreamInfo,
+    receiver: ChunkReceiver,
+    pending: Vec<u8>,
+    pending_pos: usize,
+}
+
+impl ByteStreamReader {
+    pub fn info(&self) -> &ByteStreamInfo {
+        &self.info
+    }
+
+    /// Updated each time a chunk is received, use changed() to wait for progress events
+    pub fn progress(&self) -> watch::Receiver<StreamProgress> {
+        self.receiver.progress.clone()
+    }
+
+    /// Wait for the end of the stream
+    pub async fn read_all(mut self) -> StreamResult<Vec<u8>> {
+        let mut data = self.pending.split_off(self.pending_pos);
+        data.extend(self.receiver.read_all().await?);
+        Ok(data)
+    }
+}
+
+impl Stream for ByteStreamReader {
+    type Item = StreamResult<Vec<u8>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.pending_pos < self.pending.len() {
+            let pos = self.pending_pos;
+            let rest = self.pending.split_off(pos);
+            self.pending_pos = 0;
+            self.pending.clear();
+            return Poll::Ready(Some(Ok(rest)));
+        }
+        self.receiver.poll_chunk(cx)
+    }
+}
+
+impl AsyncRead for ByteStreamReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        while self.pending_pos >= self.pending.len() {
+            match self.receiver.poll_chunk(cx) {
+                Poll::Ready(Some(Ok(chunk))) => {
+                    self.pending = chunk;
+                    self.pending_pos = 0;
+                }
+                Poll::Ready(Some(Err(err))) => {
+                    return Poll::Ready(Err(std::io::Error::new(std::io::ErrorKind::Other, err)))
+                }
+                Poll::Ready(None) => return Poll::Ready(Ok(())),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        let this = &mut *self;
+        let n = buf.remaining().min(this.pending.len() - this.pending_pos);
+        buf.put_slice(&this.pending[this.pending_pos..this.pending_pos + n]);
+        this.pending_pos += n;
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Incoming text stream, yields the text as it is received
+pub struct TextStreamReader {
+    info: TextStreamInfo,
+    receiver: ChunkReceiver,
+}
+
+impl TextStreamReader {
+    pub fn info(&self) -> &TextStreamInfo {
+        &self.info
+    }
+
+    /// Updated each time a chunk is received, use changed() to wait for progress events
+    pub fn progress(&self) -> watch::Receiver<StreamProgress> {
+        self.receiver.progress.clone()
+    }
+
+    /// Wait for the end of the stream
+    pub async fn read_all(mut self) -> StreamResult<String> {
+        let data = self.receiver.read_all().await?;
+        Ok(String::from_utf8_lossy(&data).into_owned())
+    }
+}
+
+impl Stream for TextStreamReader {
+    type Item = StreamResult<String>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // The chunks are cut on char boundaries by the senders
+        self.receiver.poll_chunk(cx).map(|chunk| {
+            chunk.map(|chunk| chunk.map(|chunk| String::from_utf8_lossy(&chunk).into_owned()))
+        })
+    }
+}
+
+pub(crate) type ByteStreamHandler =
+    Arc<dyn Fn(ByteStreamReader, ParticipantIdentity) + Send + Sync>;
+pub(crate) type TextStreamHandler =
+    Arc<dyn Fn(TextStreamReader, ParticipantIdentity) + Send + Sync>;
+
+struct IncomingStream {
+    sender: ParticipantIdentity,
+    chunks_tx: mpsc::Sender<Vec<u8>>,
+    end: Arc<Mutex<Option<StreamError>>>,
+    progress_tx: watch::Sender<StreamProgress>,
+    flow: Arc<StreamFlow>,
+    next_index: u64,
+}
+
+impl IncomingStream {
+    // The reader gets the error once it read the chunks already received
+    fn fail(self, err: StreamError) {
+        self.end.lock().get_or_insert(err);
+    }
+}
+
+/// Stream handlers registered by topic and the incoming streams being received
+#[derive(Default)]
+pub(crate) struct IncomingStreams {
+    pub byte_handlers: HashMap<String, ByteStreamHandler>,
+    /// Take precedence over byte_handlers for the streams of a participant
+    pub participant_byte_handlers: HashMap<(ParticipantIdentity, String), ByteStreamHandler>,
+    pub text_handlers: HashMap<String, TextStreamHandler>,
+    /// Where the requests to pause and resume the streams are queued, to be sent to
+    /// their senders
+    pub flow_control: Option<FlowControlSender>,
+    open: HashMap<String, IncomingStream>,
+}
+
+impl IncomingStreams {
+    /// Returns the call to the handler of the topic, to make once the lock on self is released
+    pub fn handle_header(
+        &mut self,
+        mut header: proto::data_stream::Header,
+        sender: ParticipantIdentity,
+    ) -> Option<Box<dyn FnOnce() + Send>> {
+        use proto::data_stream::header::ContentHeader;
+
+        let pausable = header
+            .attributes
+            .remove(STREAM_FLOW_CONTROL_ATTRIBUTE)
+            .is_some();
+        let (chunks_tx, chunks) = mpsc::channel(STREAM_BUFFERED_CHUNKS);
+        let (progress_tx, progress) = watch::channel(StreamProgress {
+            bytes_received: 0,
+            total_length: header.total_length,
+        });
+        let end = Arc::new(Mutex::new(None));
+        let flow = Arc::new(StreamFlow {
+            stream_id: header.stream_id.clone(),
+            sender: sender.clone(),
+            flow_control: self.flow_control.clone().filter(|_| pausable),
+            state: Default::default(),
+        });
+        let receiver = ChunkReceiver {
+            chunks,
+            end: end.clone(),
+            progress,
+            flow: flow.clone(),
+        };
+
+        let call: Box<dyn FnOnce() + Send> = match header.content_header.as_ref()? {
+            ContentHeader::ByteHeader(byte_header) => {
+                let handler = self
+                    .participant_byte_handlers
+                    .get(&(sender.clone(), header.topic.clone()))
+                    .or_else(|| self.byte_handlers.get(&header.topic))
+                    .cloned();
+                let Some(handler) = handler else {
+                    log::debug!("no byte stream handler for topic {}", header.topic);
+                    return None;
+                };
+
+                let reader = ByteStreamReader {
+                    info: ByteStreamInfo::from_header(&header, byte_header.name.clone()),
+                    receiver,
+                    pending: Vec::new(),
+                    pending_pos: 0,
+                };
+                let sender = sender.clone();
+                Box::new(move || handler(reader, sender))
+            }
+            ContentHeader::TextHeader(text_header) => {
+                let Some(handler) = self.text_handlers.get(&header.topic).cloned() else {
+                    log::debug!("no text stream handler for topic {}", header.topic);
+                    return None;
+                };
+
+                let reader = TextStreamReader {
+                    info: TextStreamInfo::from_header(&header, text_header),
+                    receiver,
+                };
+                let sender = sender.clone();
+                Box::new(move || handler(reader, sender))
+            }
+        };
+
+        self.open.insert(
+            header.stream_id,
+            IncomingStream {
+                sender,
+                chunks_tx,
+                end,
+                progress_tx,
+                flow,
+                next_index: 0,
+            },
+        );
+        Some(call)
+    }
+
+    /// Never waits for the reader, the stream fails if its reader is too far behind
+    pub fn handle_chunk(&mut self, chunk: proto::data_stream::Chunk) {
+        let Some(stream) = self.open.get_mut(&chunk.stream_id) else {
+            return;
+        };
+
+        // Chunks already received are ignored
+        if chunk.chunk_index < stream.next_index {
+            return;
+        }
+
+        stream.next_index = chunk.chunk_index + 1;
+        let len = chunk.content.len() as u64;
+        match stream.chunks_tx.try_send(chunk.content) {
+            Ok(()) => stream.flow.received(),
+            Err(TrySendError::Full(_)) => {
+                log::warn!("stream reader too slow, dropping the stream");
+                if let Some(stream) = self.open.remove(&chunk.stream_id) {
+                    stream.fail(StreamError::ReaderTooSlow);
+                }
+                return;
+            }
+            // The reader is gone, the rest of the stream is dropped
+            Err(TrySendError::Closed(_)) => {
+                self.open.remove(&chunk.stream_id);
+                return;
+            }
+        }
+        stream
+            .progress_tx
+            .send_modify(|progress| progress.bytes_received += len);
+    }
+
+    pub fn handle_trailer(&mut self, trailer: proto::data_stream::Trailer) {
+        let Some(stream) = self.open.remove(&trailer.stream_id) else {
+            return;
+        };
+
+        let progress = *stream.progress_tx.borrow();
+        if !trailer.reason.is_empty() {
+            stream.fail(StreamError::Aborted(trailer.reason));
+        } else if progress
+            .total_length
+            .is_some_and(|total| progress.bytes_received < total)
+        {
+            stream.fail(StreamError::Incomplete);
+        }
+    }
+
+    /// Fail the streams of a participant that left the room
+    pub fn participant_disconnected(&mut self, identity: &ParticipantIdentity) {
+        let ids: Vec<String> = self
+            .open
+            .iter()
+            .filter(|(_, stream)| stream.sender == *identity)
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in ids {
+            if let Some(stream) = self.open.remove(&id) {
+                stream.fail(StreamError::Incomplete);
+            }
+        }
+    }
+
+    pub fn close(&mut self) {
+        for (_, stream) in self.open.drain() {
+            stream.fail(StreamError::Incomplete);
+        }
+    }
+}
+
+/// Receivers that paused an outgoing stream, the sender waits for all of them to resume
+pub(crate) struct StreamPause(watch::Receiver<HashSet<ParticipantIdentity>>);
+
+impl StreamPause {
+    pub async fn resumed(&mut self) {
+        let _ = self.0.wait_for(|paused| paused.is_empty()).await;
+    }
+}
+
+/// Pause state of the streams sent by the local participant
+#[derive(Default)]
+pub(crate) struct OutgoingStreams {
+    paused: HashMap<String, watch::Sender<HashSet<ParticipantIdentity>>>,
+}
+
+impl OutgoingStreams {
+    /// The stream is forgotten once the returned StreamPause is dropped
+    pub fn open(&mut self, stream_id: String) -> StreamPause {
+        self.paused.retain(|_, paused| !paused.is_closed());
+
+        let (paused_tx, paused) = watch::channel(HashSet::new());
+        self.paused.insert(stream_id, paused_tx);
+        StreamPause(paused)
+    }
+
+    pub fn handle_flow_control(&mut self, receiver: ParticipantIdentity, flow: FlowControl) {
+        let Some(paused) = self.paused.get(&flow.stream_id) else {
+            return;
+        };
+
+        paused.send_modify(|paused| {
+            if flow.paused {
+                paused.insert(receiver);
+            } else {
+                paused.remove(&receiver);
+            }
+        });
+    }
+
+    /// Receivers leaving the room can't resume the streams they paused
+    pub fn participant_disconnected(&mut self, identity: &ParticipantIdentity) {
+        for paused in self.paused.values() {
+            paused.send_if_modified(|paused| paused.remove(identity));
+        }
+    }
+}
+
+/// Cut a text in chunks of at most max bytes, on char boundaries
+pub(crate) fn split_text(text: &str, max: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let mut end = max.min(rest.len());
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        let (chunk, tail) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = tail;
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+
+    fn header(id: &str, total_length: Option<u64>) -> proto::data_stream::Header {
+        proto::data_stream::Header {
+            stream_id: id.to_owned(),
+            topic: "files".to_owned(),
+            total_length,
+            content_header: Some(proto::data_stream::header::ContentHeader::ByteHeader(
+                proto::data_stream::ByteHeader {
+                    name: "file.bin".to_owned(),
+                },
+            )),
+            ..Default::default()
+        }
+    }
+
+    fn chunk(id: &str, index: u64, content: &[u8]) -> proto::data_stream::Chunk {
+        proto::data_stream::Chunk {
+            stream_id: id.to_owned(),
+            chunk_index: index,
+            content: content.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    fn open(streams: &mut IncomingStreams, id: &str, total: Option<u64>) -> ByteStreamReader {
+        let reader = Arc::new(Mutex::new(None));
+        streams.byte_handlers.insert("files".to_owned(), {
+            let reader = reader.clone();
+            Arc::new(move |r: ByteStreamReader, _: ParticipantIdentity| *reader.lock() = Some(r))
+        });
+
+        let call = streams.handle_header(header(id, total), "alice".to_owned().into());
+        call.unwrap()();
+        let taken = reader.lock().take();
+        taken.unwrap()
+    }
+
+    #[test]
+    fn packet_tags() {
+        use prost::Message;
+
+        // Same oneof tags as the other SDKs: stream_header 13, stream_chunk 14,
+        // stream_trailer 15
+        let tag = |value| {
+            let packet = proto::DataPacket {
+                value: Some(value),
+                ..Default::default()
+            };
+            packet.encode_to_vec()[0] >> 3
+        };
+        let header = proto::data_packet::Value::StreamHeader(Default::default());
+        assert_eq!(tag(header), 13);
+        assert_eq!(
+            tag(proto::data_packet::Value::StreamChunk(chunk("1", 0, b""))),
+            14
+        );
+        let trailer = proto::data_packet::Value::StreamTrailer(Default::default());
+        assert_eq!(tag(trailer), 15);
+    }
+
+    #[test]
+    fn text_chunks() {
+        assert_eq!(split_text("abcdef", 4), vec!["abcd", "ef"]);
+        assert_eq!(split_text("aéé", 2), vec!["a", "é", "é"]);
+        assert!(split_text("", 4).is_empty());
+    }
+
+    #[tokio::test]
+    async fn byte_stream() {
+        let mut streams = IncomingStreams::default();
+        let reader = open(&mut streams, "1", Some(6));
+        assert_eq!(reader.info().name, "file.bin");
+
+        let progress = reader.progress();
+        streams.handle_chunk(chunk("1", 0, b"abc"));
+        streams.handle_chunk(chunk("1", 0, b"abc"));
+        assert_eq!(progress.borrow().fraction(), Some(0.5));
+        streams.handle_chunk(chunk("1", 1, b"def"));
+        streams.handle_trailer(proto::data_stream::Trailer {
+            stream_id: "1".to_owned(),
+            ..Default::default()
+        });
+        assert_eq!(reader.read_all().await.unwrap(), b"abcdef");
+
+        // Missing chunks and senders leaving the room fail the stream
+        let reader = open(&mut streams, "2", Some(6));
+        streams.handle_chunk(chunk("2", 0, b"abc"));
+        streams.handle_trailer(proto::data_stream::Trailer {
+            stream_id: "2".to_owned(),
+            ..Default::default()
+        });
+        assert!(matches!(
+            reader.read_all().await,
+            Err(StreamError::Incomplete)
+        ));
+
+        let reader = open(&mut streams, "3", None);
+        streams.participant_disconnected(&"alice".to_owned().into());
+        assert!(matches!(
+            reader.read_all().await,
+            Err(StreamError::Incomplete)
+        ));
+
+        let call = streams.handle_header(
+            proto::data_stream::Header {
+                topic: "unknown".to_owned(),
+                ..header("4", None)
+            },
+            "alice".to_owned().into(),
+        );
+        assert!(call.is_none());
+    }
+
+    #[tokio::test]
+    async fn slow_reader() {
+        let mut streams = IncomingStreams::default();
+        let reader = open(&mut streams, "1", None);
+
+        // The room doesn't wait for the reader, the stream fails once the buffer is full
+        for index in 0..=STREAM_BUFFERED_CHUNKS as u64 {
+            streams.handle_chunk(chunk("1", index, b"abc"));
+        }
+        assert!(streams.open.is_empty());
+        assert!(matches!(
+            reader.read_all().await,
+            Err(StreamError::ReaderTooSlow)
+        ));
+
+        // Dropping the reader drops the rest of the stream
+        let reader = open(&mut streams, "2", None);
+        drop(reader);
+        streams.handle_chunk(chunk("2", 0, b"abc"));
+        assert!(streams.open.is_empty());
+    }
+
+    #[tokio::test]
+    async fn flow_control() {
+        let (flow_tx, mut flow_rx) = mpsc::unbounded_channel();
+        let mut streams = IncomingStreams {
+            flow_control: Some(flow_tx),
+            ..Default::default()
+        };
+
+        // Only the senders advertising it are asked to pause
+        let reader = open(&mut streams, "1", None);
+        for index in 0..STREAM_BUFFERED_CHUNKS as u64 {
+            streams.handle_chunk(chunk("1", index, b"abc"));
+        }
+        assert!(flow_rx.try_recv().is_err());
+        drop(reader);
+
+        let reader = Arc::new(Mutex::new(None));
+        streams.byte_handlers.insert("files".to_owned(), {
+            let reader = reader.clone();
+            Arc::new(move |r: ByteStreamReader, _: ParticipantIdentity| *reader.lock() = Some(r))
+        });
+        let mut pausable = header("2", None);
+        pausable
+            .attributes
+            .insert(STREAM_FLOW_CONTROL_ATTRIBUTE.to_owned(), "1".to_owned());
+        streams
+            .handle_header(pausable, "alice".to_owned().into())
+            .unwrap()();
+        let mut reader = reader.lock().take().unwrap();
+        assert!(reader.info().attributes.is_empty());
+
+        for index in 0..=STREAM_PAUSE_CHUNKS as u64 {
+            assert!(flow_rx.try_recv().is_err());
+            streams.handle_chunk(chunk("2", index, b"abc"));
+        }
+        let (sender, flow) = flow_rx.try_recv().unwrap();
+        assert_eq!(sender, "alice".to_owned().into());
+        assert!(flow.paused);
+
+        // The sender is resumed once the reader caught up
+        let mut consumed = 0;
+        let flow = loop {
+            if let Ok((_, flow)) = flow_rx.try_recv() {
+                break flow;
+            }
+            reader.next().await.unwrap().unwrap();
+            consumed += 1;
+        };
+        assert!(!flow.paused);
+        assert!(consumed >= STREAM_PAUSE_CHUNKS / 2);
+
+        // The sender waits until every receiver resumed the stream
+        let mut outgoing = OutgoingStreams::default();
+        let mut pause = outgoing.open("3".to_owned());
+        let paused = |paused| FlowControl {
+            stream_id: "3".to_owned(),
+            paused,
+        };
+        outgoing.handle_flow_control("alice".to_owned().into(), paused(true));
+        outgoing.handle_flow_control("bob".to_owned().into(), paused(true));
+        outgoing.handle_flow_control("alice".to_owned().into(), paused(false));
+        assert!(
+            tokio::time::timeout(std::time::Duration::from_millis(10), pause.resumed())
+                .await
+                .is_err()
+        );
+        outgoing.participant_disconnected(&"bob".to_owned().into());
+        pause.resumed().await;
+    }
+}
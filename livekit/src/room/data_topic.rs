@@ -0,0 +1,106 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Routing of the data packets to handlers registered by topic (see Room::on_data_topic)
+
+use crate::participant::RemoteParticipant;
+use crate::DataPacketKind;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+pub type DataTopicHandlerFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+pub(crate) type DataTopicHandler = Arc<dyn Fn(TopicData) -> DataTopicHandlerFuture + Send + Sync>;
+
+#[derive(Debug, Clone)]
+pub struct TopicData {
+    pub topic: String,
+    pub payload: Arc<Vec<u8>>,
+    pub kind: DataPacketKind,
+    pub participant: RemoteParticipant,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DataTopicOptions {
+    /// Handle the packets one at a time, in the order they were received.
+    /// Otherwise each packet is handled in its own task
+    pub ordered: bool,
+}
+
+impl Default for DataTopicOptions {
+    fn default() -> Self {
+        Self { ordered: true }
+    }
+}
+
+enum Route {
+    Ordered {
+        handler: DataTopicHandler,
+        // The task handling the packets is started by the first packet
+        queue: Option<mpsc::UnboundedSender<TopicData>>,
+    },
+    Unordered(DataTopicHandler),
+}
+
+#[derive(Default)]
+pub(crate) struct DataTopicRouter {
+    routes: HashMap<String, Route>,
+}
+
+impl DataTopicRouter {
+    /// Replaces the handler of the topic, the packets already queued for the previous
+    /// handler are still handled by it
+    pub fn add(&mut self, topic: String, handler: DataTopicHandler, options: DataTopicOptions) {
+        let route = if options.ordered {
+            Route::Ordered {
+                handler,
+                queue: None,
+            }
+        } else {
+            Route::Unordered(handler)
+        };
+        self.routes.insert(topic, route);
+    }
+
+    pub fn remove(&mut self, topic: &str) {
+        self.routes.remove(topic);
+    }
+
+    /// Returns false when no handler is registered for the topic
+    pub fn route(&mut self, data: TopicData) -> bool {
+        match self.routes.get_mut(&data.topic) {
+            Some(Route::Ordered { handler, queue }) => {
+                let queue = queue.get_or_insert_with(|| {
+                    let (tx, mut rx) = mpsc::unbounded_channel::<TopicData>();
+                    let handler = handler.clone();
+                    tokio::spawn(async move {
+                        while let Some(data) = rx.recv().await {
+                            handler(data).await;
+                        }
+                    });
+                    tx
+                });
+                let _ = queue.send(data);
+                true
+            }
+            Some(Route::Unordered(handler)) => {
+                tokio::spawn(handler(data));
+                true
+            }
+            None => false,
+        }
+    }
+}
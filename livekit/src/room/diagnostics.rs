@@ -0,0 +1,165 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Low level events of the peer connections (SDP, ICE candidates, states, transceivers),
+//! for debugging the negotiation failures. Enabled with RoomOptions::diagnostics
+
+use livekit_webrtc::prelude::*;
+use serde::{Serialize, Serializer};
+use std::fmt::{Debug, Display};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PeerTarget {
+    Publisher,
+    Subscriber,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum DiagnosticEvent {
+    LocalDescription {
+        target: PeerTarget,
+        #[serde(serialize_with = "display")]
+        sdp_type: SdpType,
+        sdp: String,
+    },
+    RemoteDescription {
+        target: PeerTarget,
+        #[serde(serialize_with = "display")]
+        sdp_type: SdpType,
+        sdp: String,
+    },
+    LocalCandidate {
+        target: PeerTarget,
+        candidate: String,
+    },
+    RemoteCandidate {
+        target: PeerTarget,
+        candidate: String,
+    },
+    /// Aggregate of the ICE and DTLS transport states
+    ConnectionState {
+        target: PeerTarget,
+        #[serde(serialize_with = "debug")]
+        state: PeerConnectionState,
+    },
+    IceConnectionState {
+        target: PeerTarget,
+        #[serde(serialize_with = "debug")]
+        state: IceConnectionState,
+    },
+    IceGatheringState {
+        target: PeerTarget,
+        #[serde(serialize_with = "debug")]
+        state: IceGatheringState,
+    },
+    SignalingState {
+        target: PeerTarget,
+        #[serde(serialize_with = "debug")]
+        state: SignalingState,
+    },
+    /// A sender was added to the publisher, or the subscriber received a track
+    Transceiver {
+        target: PeerTarget,
+        mid: Option<String>,
+        #[serde(serialize_with = "debug")]
+        direction: RtpTransceiverDirection,
+    },
+}
+
+/// Receives the diagnostic events, called from the WebRTC threads so it must not block
+#[derive(Clone)]
+pub struct DiagnosticsHandler(Arc<dyn Fn(DiagnosticEvent) + Send + Sync>);
+
+impl DiagnosticsHandler {
+    pub fn new(handler: impl Fn(DiagnosticEvent) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(handler))
+    }
+
+    /// Write the events to a file, one JSON object per line with the time of the event
+    /// (`timestamp`, in milliseconds since the unix epoch)
+    pub fn log_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut writer = BufWriter::new(std::fs::File::create(path)?);
+        let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+        std::thread::spawn(move || {
+            while let Some(line) = rx.blocking_recv() {
+                if let Err(err) = writeln!(writer, "{}", line).and_then(|_| writer.flush()) {
+                    log::error!("failed to write the diagnostics log: {}", err);
+                    break;
+                }
+            }
+        });
+
+        Ok(Self::new(move |event| {
+            let _ = tx.send(log_line(&event));
+        }))
+    }
+
+    pub(crate) fn emit(&self, event: DiagnosticEvent) {
+        (self.0)(event)
+    }
+}
+
+impl Debug for DiagnosticsHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("DiagnosticsHandler").finish()
+    }
+}
+
+fn log_line(event: &DiagnosticEvent) -> String {
+    #[derive(Serialize)]
+    struct Record<'a> {
+        timestamp: u64,
+        #[serde(flatten)]
+        event: &'a DiagnosticEvent,
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64;
+    serde_json::to_string(&Record { timestamp, event }).unwrap_or_default()
+}
+
+fn debug<T: Debug, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&format_args!("{:?}", value))
+}
+
+fn display<T: Display, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_lines() {
+        let line = log_line(&DiagnosticEvent::IceConnectionState {
+            target: PeerTarget::Subscriber,
+            state: IceConnectionState::Checking,
+        });
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["event"], "ice_connection_state");
+        assert_eq!(value["target"], "subscriber");
+        assert_eq!(value["state"], "Checking");
+        assert!(value["timestamp"].as_u64().unwrap() > 0);
+    }
+}
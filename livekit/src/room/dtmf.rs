@@ -0,0 +1,103 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::f64::consts::PI;
+
+/// Tone amplitude of each of the two frequencies (~-9 dBFS combined)
+const TONE_AMPLITUDE: f64 = 0.35 * i16::MAX as f64;
+
+/// Returns the RFC 4733 event code of a DTMF digit, e.g. 10 for '*'
+pub fn dtmf_code(digit: char) -> Option<u32> {
+    match digit.to_ascii_uppercase() {
+        d @ '0'..='9' => d.to_digit(10),
+        '*' => Some(10),
+        '#' => Some(11),
+        d @ 'A'..='D' => Some(12 + (d as u32 - 'A' as u32)),
+        _ => None,
+    }
+}
+
+/// Returns the DTMF digit of an RFC 4733 event code
+pub fn dtmf_digit(code: u32) -> Option<char> {
+    match code {
+        0..=9 => char::from_digit(code, 10),
+        10 => Some('*'),
+        11 => Some('#'),
+        12..=15 => char::from_u32('A' as u32 + code - 12),
+        _ => None,
+    }
+}
+
+/// Low and high frequencies (Hz) of a DTMF digit
+fn dtmf_frequencies(digit: char) -> Option<(f64, f64)> {
+    const LOW: [f64; 4] = [697.0, 770.0, 852.0, 941.0];
+    const HIGH: [f64; 4] = [1209.0, 1336.0, 1477.0, 1633.0];
+    const KEYPAD: [[char; 4]; 4] = [
+        ['1', '2', '3', 'A'],
+        ['4', '5', '6', 'B'],
+        ['7', '8', '9', 'C'],
+        ['*', '0', '#', 'D'],
+    ];
+
+    let digit = digit.to_ascii_uppercase();
+    KEYPAD.iter().enumerate().find_map(|(row, keys)| {
+        keys.iter()
+            .position(|k| *k == digit)
+            .map(|col| (LOW[row], HIGH[col]))
+    })
+}
+
+/// Generates the in-band tone of a DTMF digit as interleaved i16 samples
+pub fn generate_dtmf_tone(
+    digit: char,
+    duration_ms: u32,
+    sample_rate: u32,
+    num_channels: u32,
+) -> Option<Vec<i16>> {
+    let (low, high) = dtmf_frequencies(digit)?;
+    let samples = (sample_rate as u64 * duration_ms as u64 / 1000) as usize;
+    let mut data = Vec::with_capacity(samples * num_channels as usize);
+    for i in 0..samples {
+        let t = i as f64 / sample_rate as f64;
+        let v = TONE_AMPLITUDE * ((2.0 * PI * low * t).sin() + (2.0 * PI * high * t).sin());
+        let v = v.round().clamp(i16::MIN as f64, i16::MAX as f64) as i16;
+        data.extend(std::iter::repeat(v).take(num_channels as usize));
+    }
+    Some(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_roundtrip() {
+        for digit in "0123456789*#ABCD".chars() {
+            let code = dtmf_code(digit).unwrap();
+            assert_eq!(dtmf_digit(code), Some(digit));
+        }
+        assert_eq!(dtmf_code('#'), Some(11));
+        assert_eq!(dtmf_code('d'), Some(15));
+        assert_eq!(dtmf_code('x'), None);
+        assert_eq!(dtmf_digit(16), None);
+    }
+
+    #[test]
+    fn tone_length() {
+        let tone = generate_dtmf_tone('5', 100, 48000, 2).unwrap();
+        assert_eq!(tone.len(), 4800 * 2);
+        assert!(tone.iter().any(|s| *s != 0));
+        assert!(generate_dtmf_tone('x', 100, 48000, 1).is_none());
+    }
+}
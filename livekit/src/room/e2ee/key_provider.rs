@@ -12,8 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use livekit_webrtc::native::frame_cryptor as fc;
+use livekit_webrtc::native::frame_cryptor::{self as fc, EncryptionAlgorithm};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
 
+use super::key_store::{KeyMaterial, KeySlot, KeyStore};
 use crate::id::ParticipantIdentity;
 
 const DEFAULT_RATCHET_SALT: &str = "LKFrameEncryptionKey";
@@ -25,6 +32,16 @@ pub struct KeyProviderOptions {
     pub ratchet_window_size: i32,
     pub ratchet_salt: Vec<u8>,
     pub uncrypted_magic_bytes: Vec<u8>,
+    /// Trailer identifying server injected frames (SIF), these frames are passed through
+    /// without decryption. When None, the trailer received in the JoinResponse is used
+    pub sif_trailer: Option<Vec<u8>>,
+    /// Ignore the trailer sent by the server, SIF are then treated like any other frame
+    pub disable_server_sif_trailer: bool,
+    /// Copy of the keys used to recover keys evicted from the frame cryptors.
+    /// When None, the keys are only kept by the native key provider
+    pub key_store: Option<Arc<dyn KeyStore>>,
+    /// Cipher used by the frame cryptors, AesCbc is only needed to interop with older clients
+    pub algorithm: EncryptionAlgorithm,
 }
 
 impl Default for KeyProviderOptions {
@@ -33,45 +50,113 @@ impl Default for KeyProviderOptions {
             ratchet_window_size: DEFAULT_RATCHET_WINDOW_SIZE,
             ratchet_salt: DEFAULT_RATCHET_SALT.to_owned().into_bytes(),
             uncrypted_magic_bytes: DEFAULT_MAGIC_BYTES.to_owned().into_bytes(),
+            sif_trailer: None,
+            disable_server_sif_trailer: false,
+            key_store: None,
+            algorithm: EncryptionAlgorithm::AesGcm,
         }
     }
 }
 
+pub type KeyResolverFuture = Pin<Box<dyn Future<Output = Option<Vec<u8>>> + Send>>;
+
+/// Application callback used to fetch a key on demand.
+/// The SDK calls `resolve_key` when a frame cryptor reports a missing key for a participant,
+/// the resolved key is then set on the KeyProvider. Returning None leaves the key missing.
+pub trait KeyResolver: Send + Sync {
+    fn resolve_key(&self, identity: ParticipantIdentity, key_index: i32) -> KeyResolverFuture;
+}
+
+/// Snapshot of the keys known by a KeyProvider, see KeyProvider::export_keys
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerializedKeyRing {
+    pub keys: Vec<SerializedKey>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerializedKey {
+    /// None for shared keys
+    pub participant_identity: Option<String>,
+    pub key_index: i32,
+    /// Current material, already ratcheted `ratchet_count` times
+    pub material: Vec<u8>,
+    pub ratchet_count: u32,
+}
+
+// (None, index) for shared keys, the value is the number of ratchets since the key was set
+type KeyRing = HashMap<(Option<ParticipantIdentity>, i32), u32>;
+
 #[derive(Clone)]
 pub struct KeyProvider {
     pub(crate) handle: fc::KeyProvider,
+    resolver: Option<Arc<dyn KeyResolver>>,
+    pending_requests: Arc<Mutex<HashSet<(ParticipantIdentity, i32)>>>,
+    key_ring: Arc<Mutex<KeyRing>>,
+    key_store: Option<Arc<dyn KeyStore>>,
+    algorithm: EncryptionAlgorithm,
+    server_sif_trailer: bool,
 }
 
 impl KeyProvider {
     /// By default, the key provider is not shared
     pub fn new(options: KeyProviderOptions) -> Self {
-        Self {
-            handle: fc::KeyProvider::new(fc::KeyProviderOptions {
-                shared_key: false,
-                ratchet_window_size: options.ratchet_window_size,
-                ratchet_salt: options.ratchet_salt,
-                uncrypted_magic_bytes: options.uncrypted_magic_bytes,
-            }),
-        }
+        Self::with_options(options, false)
+    }
+
+    /// Per-participant key provider that asks the resolver for keys it doesn't know yet
+    pub fn with_key_resolver(
+        options: KeyProviderOptions,
+        resolver: impl KeyResolver + 'static,
+    ) -> Self {
+        let mut provider = Self::new(options);
+        provider.resolver = Some(Arc::new(resolver));
+        provider
     }
 
     pub fn with_shared_key(options: KeyProviderOptions, shared_key: Vec<u8>) -> Self {
+        let provider = Self::with_options(options, true);
+        provider.set_shared_key(shared_key, 0);
+        provider
+    }
+
+    fn with_options(options: KeyProviderOptions, shared_key: bool) -> Self {
         let handle = fc::KeyProvider::new(fc::KeyProviderOptions {
-            shared_key: true,
+            shared_key,
             ratchet_window_size: options.ratchet_window_size,
             ratchet_salt: options.ratchet_salt,
             uncrypted_magic_bytes: options.uncrypted_magic_bytes,
         });
-        handle.set_shared_key(0, shared_key);
-        Self { handle }
+
+        // A custom trailer always takes precedence over the server one
+        let custom_sif_trailer = options.sif_trailer.is_some();
+        if let Some(trailer) = options.sif_trailer {
+            handle.set_sif_trailer(trailer);
+        }
+
+        Self {
+            handle,
+            resolver: None,
+            pending_requests: Default::default(),
+            key_ring: Default::default(),
+            key_store: options.key_store,
+            algorithm: options.algorithm,
+            server_sif_trailer: !custom_sif_trailer && !options.disable_server_sif_trailer,
+        }
     }
 
     pub fn set_shared_key(&self, shared_key: Vec<u8>, key_index: i32) {
-        self.handle.set_shared_key(key_index, shared_key);
+        let shared_key = KeyMaterial::from(shared_key);
+        if self.handle.set_shared_key(key_index, shared_key.to_vec()) {
+            self.key_ring.lock().insert((None, key_index), 0);
+            self.store_key(None, key_index, &shared_key);
+        }
     }
 
     pub fn ratchet_shared_key(&self, key_index: i32) -> Option<Vec<u8>> {
-        self.handle.ratchet_shared_key(key_index)
+        let new_key = self.handle.ratchet_shared_key(key_index)?;
+        *self.key_ring.lock().entry((None, key_index)).or_default() += 1;
+        self.store_key(None, key_index, &new_key);
+        Some(new_key)
     }
 
     pub fn get_shared_key(&self, key_index: i32) -> Option<Vec<u8>> {
@@ -79,14 +164,195 @@ impl KeyProvider {
     }
 
     pub fn set_key(&self, identity: &ParticipantIdentity, key_index: i32, key: Vec<u8>) -> bool {
-        self.handle.set_key(identity.to_string(), key_index, key)
+        let key = KeyMaterial::from(key);
+        let ok = self
+            .handle
+            .set_key(identity.to_string(), key_index, key.to_vec());
+        if ok {
+            self.key_ring
+                .lock()
+                .insert((Some(identity.clone()), key_index), 0);
+            self.store_key(Some(identity.clone()), key_index, &key);
+        }
+        ok
     }
 
     pub fn ratchet_key(&self, identity: &ParticipantIdentity, key_index: i32) -> Option<Vec<u8>> {
-        self.handle.ratchet_key(identity.to_string(), key_index)
+        let new_key = self.handle.ratchet_key(identity.to_string(), key_index)?;
+        *self
+            .key_ring
+            .lock()
+            .entry((Some(identity.clone()), key_index))
+            .or_default() += 1;
+        self.store_key(Some(identity.clone()), key_index, &new_key);
+        Some(new_key)
     }
 
     pub fn get_key(&self, identity: &ParticipantIdentity, key_index: i32) -> Option<Vec<u8>> {
         self.handle.get_key(identity.to_string(), key_index)
     }
+
+    /// Export every key set on this provider (shared and per-participant) with its current
+    /// (ratcheted) material, so it can be restored later with import_keys
+    pub fn export_keys(&self) -> SerializedKeyRing {
+        let key_ring = self.key_ring.lock().clone();
+        let mut keys = Vec::with_capacity(key_ring.len());
+        for ((identity, key_index), ratchet_count) in key_ring {
+            let material = match &identity {
+                Some(identity) => self.get_key(identity, key_index),
+                None => self.get_shared_key(key_index),
+            };
+
+            if let Some(material) = material {
+                keys.push(SerializedKey {
+                    participant_identity: identity.map(String::from),
+                    key_index,
+                    material,
+                    ratchet_count,
+                });
+            }
+        }
+
+        keys.sort_by(|a, b| {
+            (&a.participant_identity, a.key_index).cmp(&(&b.participant_identity, b.key_index))
+        });
+        SerializedKeyRing { keys }
+    }
+
+    /// Restore keys previously exported with export_keys.
+    /// Existing keys with the same identity/index are overwritten.
+    pub fn import_keys(&self, key_ring: SerializedKeyRing) {
+        for key in key_ring.keys {
+            let identity = key.participant_identity.map(ParticipantIdentity::from);
+            let material = KeyMaterial::from(key.material);
+            let ok = match &identity {
+                Some(identity) => {
+                    self.handle
+                        .set_key(identity.to_string(), key.key_index, material.to_vec())
+                }
+                None => self.handle.set_shared_key(key.key_index, material.to_vec()),
+            };
+
+            if ok {
+                self.key_ring
+                    .lock()
+                    .insert((identity.clone(), key.key_index), key.ratchet_count);
+                self.store_key(identity, key.key_index, &material);
+            } else {
+                log::warn!("failed to import e2ee key (index {})", key.key_index);
+            }
+        }
+    }
+
+    pub fn algorithm(&self) -> EncryptionAlgorithm {
+        self.algorithm
+    }
+
+    pub fn key_store(&self) -> Option<Arc<dyn KeyStore>> {
+        self.key_store.clone()
+    }
+
+    /// Remove the keys of a participant from the KeyStore and from the exported key ring,
+    /// e.g. once the participant left the room for good
+    pub fn forget_participant(&self, identity: &ParticipantIdentity) {
+        let mut key_ring = self.key_ring.lock();
+        let key_indexes: Vec<i32> = key_ring
+            .keys()
+            .filter(|(id, _)| id.as_ref() == Some(identity))
+            .map(|(_, key_index)| *key_index)
+            .collect();
+
+        for key_index in key_indexes {
+            key_ring.remove(&(Some(identity.clone()), key_index));
+            if let Some(key_store) = &self.key_store {
+                key_store.remove_key(&KeySlot {
+                    participant_identity: Some(identity.clone()),
+                    key_index,
+                });
+            }
+        }
+    }
+
+    fn store_key(
+        &self,
+        participant_identity: Option<ParticipantIdentity>,
+        key_index: i32,
+        key: &[u8],
+    ) {
+        // Only copy the key when there is somewhere to keep it
+        if let Some(key_store) = &self.key_store {
+            key_store.set_key(
+                KeySlot {
+                    participant_identity,
+                    key_index,
+                },
+                KeyMaterial::from(key.to_vec()),
+            );
+        }
+    }
+
+    pub fn set_sif_trailer(&self, trailer: Vec<u8>) {
+        self.handle.set_sif_trailer(trailer);
+    }
+
+    /// Called with the trailer received inside the JoinResponse
+    pub(crate) fn set_server_sif_trailer(&self, trailer: Vec<u8>) {
+        if self.server_sif_trailer && !trailer.is_empty() {
+            self.handle.set_sif_trailer(trailer);
+        }
+    }
+
+    /// Recover a missing key from the KeyStore or ask the resolver for it.
+    /// Only one request per (identity, key_index) can be in flight at a time.
+    pub(crate) fn request_key(
+        &self,
+        runtime: &tokio::runtime::Handle,
+        identity: ParticipantIdentity,
+        key_index: i32,
+    ) {
+        // The key may have been evicted from the frame cryptor, but still be in the store
+        let slot = KeySlot {
+            participant_identity: Some(identity.clone()),
+            key_index,
+        };
+        if let Some(key) = self
+            .key_store
+            .as_ref()
+            .and_then(|store| store.get_key(&slot))
+        {
+            self.handle
+                .set_key(identity.to_string(), key_index, key.to_vec());
+            return;
+        }
+
+        let Some(resolver) = self.resolver.clone() else {
+            return;
+        };
+
+        if !self
+            .pending_requests
+            .lock()
+            .insert((identity.clone(), key_index))
+        {
+            return;
+        }
+
+        let provider = self.clone();
+        runtime.spawn(async move {
+            let key = resolver.resolve_key(identity.clone(), key_index).await;
+            if let Some(key) = key {
+                provider.set_key(&identity, key_index, key);
+            } else {
+                log::debug!(
+                    "key resolver returned no key for {} (index {})",
+                    identity,
+                    key_index
+                );
+            }
+            provider
+                .pending_requests
+                .lock()
+                .remove(&(identity, key_index));
+        });
+    }
 }
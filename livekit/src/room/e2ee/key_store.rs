@@ -0,0 +1,154 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::ops::Deref;
+use std::sync::atomic::{self, Ordering};
+
+use crate::id::ParticipantIdentity;
+
+/// Key bytes that are wiped from memory when dropped
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct KeyMaterial(Vec<u8>);
+
+impl KeyMaterial {
+    pub fn new(key: Vec<u8>) -> Self {
+        Self(key)
+    }
+}
+
+impl From<Vec<u8>> for KeyMaterial {
+    fn from(value: Vec<u8>) -> Self {
+        Self(value)
+    }
+}
+
+impl Deref for KeyMaterial {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Debug for KeyMaterial {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("KeyMaterial").field(&"<redacted>").finish()
+    }
+}
+
+impl Drop for KeyMaterial {
+    fn drop(&mut self) {
+        wipe(&mut self.0);
+    }
+}
+
+/// Zero the whole allocation (including the spare capacity) and clear the Vec
+fn wipe(bytes: &mut Vec<u8>) {
+    let ptr = bytes.as_mut_ptr();
+    for i in 0..bytes.capacity() {
+        // Volatile writes so the compiler can't optimize the zeroing away
+        unsafe { std::ptr::write_volatile(ptr.add(i), 0) };
+    }
+    atomic::compiler_fence(Ordering::SeqCst);
+    bytes.clear();
+}
+
+/// Location of a key, the participant identity is None for shared keys
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeySlot {
+    pub participant_identity: Option<ParticipantIdentity>,
+    pub key_index: i32,
+}
+
+/// Storage backend of the KeyProvider.
+/// Implement this trait to keep the keys inside an OS keychain, a TPM, ...
+/// The store is used to recover keys when a frame cryptor reports a missing key.
+pub trait KeyStore: Send + Sync {
+    fn set_key(&self, slot: KeySlot, key: KeyMaterial);
+    /// The returned KeyMaterial is wiped when dropped
+    fn get_key(&self, slot: &KeySlot) -> Option<KeyMaterial>;
+    fn remove_key(&self, slot: &KeySlot);
+}
+
+#[derive(Default)]
+pub struct InMemoryKeyStore {
+    keys: Mutex<HashMap<KeySlot, KeyMaterial>>,
+}
+
+impl KeyStore for InMemoryKeyStore {
+    fn set_key(&self, slot: KeySlot, key: KeyMaterial) {
+        self.keys.lock().insert(slot, key);
+    }
+
+    fn get_key(&self, slot: &KeySlot) -> Option<KeyMaterial> {
+        self.keys.lock().get(slot).cloned()
+    }
+
+    fn remove_key(&self, slot: &KeySlot) {
+        self.keys.lock().remove(slot);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wipe_spare_capacity() {
+        let mut bytes = Vec::with_capacity(32);
+        bytes.extend_from_slice(&[0xab; 32]);
+        bytes.truncate(8);
+
+        wipe(&mut bytes);
+        assert!(bytes.is_empty());
+
+        // Every byte of the allocation has been written, so it is initialized
+        let capacity = bytes.capacity();
+        unsafe { bytes.set_len(capacity) };
+        assert!(bytes.iter().all(|b| *b == 0));
+    }
+
+    #[test]
+    fn redacted_debug() {
+        let key = KeyMaterial::new(b"secret".to_vec());
+        assert_eq!(format!("{:?}", key), "KeyMaterial(\"<redacted>\")");
+        assert_eq!(&*key, b"secret");
+    }
+
+    #[test]
+    fn in_memory_store() {
+        let store = InMemoryKeyStore::default();
+        let slot = KeySlot {
+            participant_identity: Some("alice".to_owned().into()),
+            key_index: 1,
+        };
+        let shared = KeySlot {
+            participant_identity: None,
+            key_index: 1,
+        };
+
+        store.set_key(slot.clone(), KeyMaterial::new(vec![1, 2, 3]));
+        assert_eq!(store.get_key(&slot).as_deref(), Some(&[1, 2, 3][..]));
+        assert!(store.get_key(&shared).is_none());
+
+        store.set_key(slot.clone(), KeyMaterial::new(vec![4, 5, 6]));
+        assert_eq!(store.get_key(&slot).as_deref(), Some(&[4, 5, 6][..]));
+
+        store.remove_key(&slot);
+        assert!(store.get_key(&slot).is_none());
+    }
+}
@@ -13,29 +13,85 @@
 // limitations under the License.
 
 use super::key_provider::KeyProvider;
-use super::EncryptionType;
+use super::{EncryptionType, KeyRotationOptions};
 use crate::e2ee::E2eeOptions;
 use crate::id::{ParticipantIdentity, TrackSid};
 use crate::participant::{LocalParticipant, RemoteParticipant};
 use crate::prelude::{LocalTrack, LocalTrackPublication, RemoteTrack, RemoteTrackPublication};
-use livekit_webrtc::native::frame_cryptor::{EncryptionAlgorithm, EncryptionState, FrameCryptor};
-use livekit_webrtc::{rtp_receiver::RtpReceiver, rtp_sender::RtpSender};
+use livekit_webrtc::native::frame_cryptor::{EncryptionState, FrameCryptor};
+use livekit_webrtc::stats::RtcStats;
+use livekit_webrtc::{
+    rtp_receiver::RtpReceiver, rtp_sender::RtpSender, rtp_transceiver::RtpTransceiver,
+};
 use parking_lot::Mutex;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
 
-type StateChangedHandler = Box<dyn Fn(ParticipantIdentity, EncryptionState) + Send>;
+// How often the sent frames are counted when rotating after a number of frames
+const SENT_FRAMES_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+type StateChangedHandler = Box<dyn Fn(ParticipantIdentity, TrackSid, EncryptionState) + Send>;
+type KeyRatchetedHandler = Box<dyn Fn(i32, Vec<u8>) + Send>;
+type StatsFuture = Pin<Box<dyn Future<Output = Vec<RtcStats>> + Send>>;
+type PublisherStatsProvider = Arc<dyn Fn() -> StatsFuture + Send + Sync>;
+
+/// Health of a frame cryptor.
+/// libwebrtc doesn't expose per-frame counters, the `*_events` fields count how many
+/// times the cryptor entered the matching state, not the number of frames affected
+#[derive(Debug, Clone)]
+pub struct FrameCryptorStats {
+    pub participant_identity: ParticipantIdentity,
+    pub track_sid: TrackSid,
+    pub enabled: bool,
+    pub key_index: i32,
+    pub state: EncryptionState,
+    pub encryption_failed_events: u32,
+    pub decryption_failed_events: u32,
+    pub missing_key_events: u32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CryptorCounters {
+    state: EncryptionState,
+    encryption_failed_events: u32,
+    decryption_failed_events: u32,
+    missing_key_events: u32,
+}
+
+impl Default for CryptorCounters {
+    fn default() -> Self {
+        Self {
+            state: EncryptionState::New,
+            encryption_failed_events: 0,
+            decryption_failed_events: 0,
+            missing_key_events: 0,
+        }
+    }
+}
 
 struct ManagerInner {
     options: Option<E2eeOptions>, // If Some, it means the e2ee was initialized
     enabled: bool,                // Used to enable/disable e2ee
     frame_cryptors: HashMap<(ParticipantIdentity, TrackSid), FrameCryptor>,
+    // Transceivers of the encrypted local tracks, used to count the sent frames
+    encrypted_senders: HashMap<TrackSid, RtpTransceiver>,
+    key_index: i32, // Shared key index currently used to encrypt
+    rotation_task: Option<JoinHandle<()>>,
 }
 
 #[derive(Clone)]
 pub struct E2eeManager {
     inner: Arc<Mutex<ManagerInner>>,
     state_changed: Arc<Mutex<Option<StateChangedHandler>>>,
+    key_ratcheted: Arc<Mutex<Option<KeyRatchetedHandler>>>,
+    publisher_stats: Arc<Mutex<Option<PublisherStatsProvider>>>,
+    // Kept outside of ManagerInner, it is updated from the cryptor observers
+    counters: Arc<Mutex<HashMap<(ParticipantIdentity, TrackSid), CryptorCounters>>>,
+    runtime: tokio::runtime::Handle,
 }
 
 impl E2eeManager {
@@ -46,28 +102,55 @@ impl E2eeManager {
                 enabled: options.is_some(), // Enabled by default if options is provided
                 options,
                 frame_cryptors: HashMap::new(),
+                encrypted_senders: HashMap::new(),
+                key_index: 0,
+                rotation_task: None,
             })),
             state_changed: Default::default(),
+            key_ratcheted: Default::default(),
+            publisher_stats: Default::default(),
+            counters: Default::default(),
+            runtime: tokio::runtime::Handle::current(),
         }
     }
 
     pub(crate) fn cleanup(&self) {
         let mut inner = self.inner.lock();
+        if let Some(task) = inner.rotation_task.take() {
+            task.abort();
+        }
         for cryptor in inner.frame_cryptors.values() {
             cryptor.set_enabled(false);
         }
         inner.frame_cryptors.clear();
+        inner.encrypted_senders.clear();
+        self.counters.lock().clear();
     }
 
     /// Register to e2ee state changes
     /// Used by the room to dispatch the event to the room dispatcher
     pub(crate) fn on_state_changed(
         &self,
-        handler: impl Fn(ParticipantIdentity, EncryptionState) + Send + 'static,
+        handler: impl Fn(ParticipantIdentity, TrackSid, EncryptionState) + Send + 'static,
     ) {
         *self.state_changed.lock() = Some(Box::new(handler));
     }
 
+    /// Register to shared key rotations
+    /// Used by the room to dispatch the new key material
+    pub(crate) fn on_key_ratcheted(&self, handler: impl Fn(i32, Vec<u8>) + Send + 'static) {
+        *self.key_ratcheted.lock() = Some(Box::new(handler));
+    }
+
+    /// Register the source of the publisher stats
+    /// Used to count the frames sent with the current key
+    pub(crate) fn on_publisher_stats(
+        &self,
+        provider: impl Fn() -> StatsFuture + Send + Sync + 'static,
+    ) {
+        *self.publisher_stats.lock() = Some(Arc::new(provider));
+    }
+
     pub(crate) fn initialized(&self) -> bool {
         self.inner.lock().options.is_some()
     }
@@ -90,7 +173,7 @@ impl E2eeManager {
         let identity = participant.identity();
         let receiver = track.transceiver().unwrap().receiver();
         let frame_cryptor = self.setup_rtp_receiver(&identity, receiver);
-        self.setup_cryptor(&frame_cryptor);
+        self.setup_cryptor(&frame_cryptor, publication.sid());
 
         let mut inner = self.inner.lock();
         inner
@@ -114,21 +197,60 @@ impl E2eeManager {
         }
 
         let identity = participant.identity();
-        let sender = track.transceiver().unwrap().sender();
-        let frame_cryptor = self.setup_rtp_sender(&identity, sender);
-        self.setup_cryptor(&frame_cryptor);
+        let transceiver = track.transceiver().unwrap();
+        let frame_cryptor = self.setup_rtp_sender(&identity, transceiver.sender());
+        self.setup_cryptor(&frame_cryptor, publication.sid());
 
         let mut inner = self.inner.lock();
         inner
             .frame_cryptors
             .insert((identity, publication.sid()), frame_cryptor.clone());
+        inner
+            .encrypted_senders
+            .insert(publication.sid(), transceiver);
     }
 
-    fn setup_cryptor(&self, frame_cryptor: &FrameCryptor) {
+    fn setup_cryptor(&self, frame_cryptor: &FrameCryptor, track_sid: TrackSid) {
         let state_changed = self.state_changed.clone();
+        let inner = Arc::downgrade(&self.inner);
+        let runtime = self.runtime.clone();
+        let counters = self.counters.clone();
         frame_cryptor.on_state_change(Some(Box::new(move |participant_identity, state| {
+            let participant_identity: ParticipantIdentity = participant_identity.into();
+
+            {
+                let mut counters = counters.lock();
+                let counters = counters
+                    .entry((participant_identity.clone(), track_sid.clone()))
+                    .or_default();
+                counters.state = state;
+                match state {
+                    EncryptionState::EncryptionFailed => counters.encryption_failed_events += 1,
+                    EncryptionState::DecryptionFailed => counters.decryption_failed_events += 1,
+                    EncryptionState::MissingKey => counters.missing_key_events += 1,
+                    _ => {}
+                }
+            }
+
+            if state == EncryptionState::MissingKey {
+                // Ask the application for the key (if a KeyResolver is used)
+                if let Some(inner) = inner.upgrade() {
+                    let inner = inner.lock();
+                    let key = (participant_identity.clone(), track_sid.clone());
+                    if let (Some(options), Some(cryptor)) =
+                        (inner.options.as_ref(), inner.frame_cryptors.get(&key))
+                    {
+                        options.key_provider.request_key(
+                            &runtime,
+                            participant_identity.clone(),
+                            cryptor.key_index(),
+                        );
+                    }
+                }
+            }
+
             if let Some(state_changed) = state_changed.lock().as_ref() {
-                state_changed(participant_identity.try_into().unwrap(), state);
+                state_changed(participant_identity, track_sid.clone(), state);
             }
         })));
     }
@@ -156,6 +278,31 @@ impl E2eeManager {
         self.inner.lock().frame_cryptors.clone()
     }
 
+    pub fn stats(&self) -> Vec<FrameCryptorStats> {
+        let frame_cryptors = self.frame_cryptors();
+        let counters = self.counters.lock();
+        frame_cryptors
+            .into_iter()
+            .map(|((participant_identity, track_sid), cryptor)| {
+                let c = counters
+                    .get(&(participant_identity.clone(), track_sid.clone()))
+                    .copied()
+                    .unwrap_or_default();
+
+                FrameCryptorStats {
+                    participant_identity,
+                    track_sid,
+                    enabled: cryptor.enabled(),
+                    key_index: cryptor.key_index(),
+                    state: c.state,
+                    encryption_failed_events: c.encryption_failed_events,
+                    decryption_failed_events: c.decryption_failed_events,
+                    missing_key_events: c.missing_key_events,
+                }
+            })
+            .collect()
+    }
+
     pub fn enabled(&self) -> bool {
         self.inner.lock().enabled && self.initialized()
     }
@@ -171,6 +318,112 @@ impl E2eeManager {
         }
     }
 
+    /// Ratchet the shared key into the next slot of the key ring and switch the encryptors to it.
+    /// The previous slot is left untouched so in-flight frames can still be decrypted.
+    /// Returns the new key index and the key material that needs to be distributed.
+    pub fn ratchet_shared_key(&self, key_ring_size: i32) -> Option<(i32, Vec<u8>)> {
+        let (key_index, material) = {
+            let mut inner = self.inner.lock();
+            let key_provider = inner.options.as_ref()?.key_provider.clone();
+
+            let current = inner.key_index;
+            let next = (current + 1) % key_ring_size.max(1);
+            let previous = key_provider.get_shared_key(current)?;
+            let material = key_provider.ratchet_shared_key(current)?;
+            key_provider.set_shared_key(previous, current);
+            key_provider.set_shared_key(material.clone(), next);
+
+            // Receivers read the key index from the frame trailer, so this only affects encryption
+            inner.key_index = next;
+            for cryptor in inner.frame_cryptors.values() {
+                cryptor.set_key_index(next);
+            }
+
+            (next, material)
+        };
+
+        if let Some(key_ratcheted) = self.key_ratcheted.lock().as_ref() {
+            key_ratcheted(key_index, material.clone());
+        }
+
+        Some((key_index, material))
+    }
+
+    /// Ratchet the shared key periodically and/or after a number of sent frames,
+    /// replacing any previous rotation
+    pub fn start_key_rotation(&self, options: KeyRotationOptions) {
+        let manager = self.clone();
+        let task = self.runtime.spawn(async move {
+            let period = match (options.max_frames, options.interval) {
+                (Some(_), Some(interval)) => interval.min(SENT_FRAMES_POLL_INTERVAL),
+                (Some(_), None) => SENT_FRAMES_POLL_INTERVAL,
+                (None, Some(interval)) => interval,
+                (None, None) => return,
+            };
+
+            let mut ticker = tokio::time::interval(period);
+            ticker.tick().await; // The first tick completes immediately
+
+            let mut rotated_at = tokio::time::Instant::now();
+            let mut frames_at_rotation = manager.sent_frames(&options).await;
+            loop {
+                ticker.tick().await;
+
+                // Unpublished tracks aren't counted anymore, so the total can decrease
+                let frames = manager.sent_frames(&options).await;
+                frames_at_rotation = frames_at_rotation.min(frames);
+
+                let interval_elapsed = options
+                    .interval
+                    .is_some_and(|interval| rotated_at.elapsed() >= interval);
+                let frames_exceeded = options
+                    .max_frames
+                    .is_some_and(|max_frames| frames - frames_at_rotation >= max_frames);
+                if !interval_elapsed && !frames_exceeded {
+                    continue;
+                }
+
+                if manager.ratchet_shared_key(options.key_ring_size).is_none() {
+                    log::warn!("failed to rotate the e2ee shared key");
+                }
+                rotated_at = tokio::time::Instant::now();
+                frames_at_rotation = frames;
+            }
+        });
+
+        if let Some(previous) = self.inner.lock().rotation_task.replace(task) {
+            previous.abort();
+        }
+    }
+
+    pub fn stop_key_rotation(&self) {
+        if let Some(task) = self.inner.lock().rotation_task.take() {
+            task.abort();
+        }
+    }
+
+    /// Total of the frames sent by the encrypted local tracks
+    async fn sent_frames(&self, options: &KeyRotationOptions) -> u64 {
+        if options.max_frames.is_none() {
+            return 0;
+        }
+
+        let Some(provider) = self.publisher_stats.lock().clone() else {
+            return 0;
+        };
+
+        let mids: HashSet<String> = {
+            let inner = self.inner.lock();
+            inner
+                .encrypted_senders
+                .values()
+                .filter_map(|transceiver| transceiver.mid())
+                .collect()
+        };
+
+        count_sent_frames(&provider().await, &mids)
+    }
+
     pub fn key_provider(&self) -> Option<KeyProvider> {
         let inner = self.inner.lock();
         inner.options.as_ref().map(|opts| opts.key_provider.clone())
@@ -195,10 +448,11 @@ impl E2eeManager {
 
         let frame_cryptor = FrameCryptor::new_for_rtp_sender(
             participant_identity.to_string(),
-            EncryptionAlgorithm::AesGcm,
+            options.key_provider.algorithm(),
             options.key_provider.handle.clone(),
             sender,
         );
+        frame_cryptor.set_key_index(inner.key_index);
         frame_cryptor.set_enabled(inner.enabled);
         frame_cryptor
     }
@@ -213,7 +467,7 @@ impl E2eeManager {
 
         let frame_cryptor = FrameCryptor::new_for_rtp_receiver(
             participant_identity.to_string(),
-            EncryptionAlgorithm::AesGcm,
+            options.key_provider.algorithm(),
             options.key_provider.handle.clone(),
             receiver,
         );
@@ -224,9 +478,57 @@ impl E2eeManager {
     fn remove_frame_cryptor(&self, participant_identity: ParticipantIdentity, track_sid: TrackSid) {
         log::debug!("removing frame cryptor for {}", participant_identity);
 
+        self.inner.lock().encrypted_senders.remove(&track_sid);
+        let key = (participant_identity, track_sid);
+        self.counters.lock().remove(&key);
         let mut inner = self.inner.lock();
-        inner
-            .frame_cryptors
-            .remove(&(participant_identity, track_sid));
+        inner.frame_cryptors.remove(&key);
+    }
+}
+
+fn count_sent_frames(stats: &[RtcStats], mids: &HashSet<String>) -> u64 {
+    stats
+        .iter()
+        .filter_map(|stats| match stats {
+            RtcStats::OutboundRtp(outbound) if mids.contains(&outbound.mid) => {
+                // Audio frames are sent one per packet
+                Some(match outbound.kind.as_str() {
+                    "video" => outbound.frames_encoded as u64,
+                    _ => outbound.packets_sent,
+                })
+            }
+            _ => None,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use livekit_webrtc::stats::OutboundRtpStats;
+
+    fn outbound(mid: &str, kind: &str, frames_encoded: u32, packets_sent: u64) -> RtcStats {
+        RtcStats::OutboundRtp(OutboundRtpStats {
+            mid: mid.to_owned(),
+            kind: kind.to_owned(),
+            frames_encoded,
+            packets_sent,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn sent_frames_of_encrypted_tracks() {
+        let stats = vec![
+            // Simulcast layers share the mid of their track
+            outbound("0", "video", 100, 900),
+            outbound("0", "video", 50, 300),
+            outbound("1", "audio", 0, 500),
+            outbound("2", "video", 1000, 5000),
+        ];
+
+        let mids = HashSet::from(["0".to_owned(), "1".to_owned()]);
+        assert_eq!(count_sent_frames(&stats, &mids), 650);
+        assert_eq!(count_sent_frames(&stats, &HashSet::new()), 0);
     }
 }
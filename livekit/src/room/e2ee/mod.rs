@@ -13,8 +13,12 @@
 // limitations under the License.
 
 use self::key_provider::KeyProvider;
+use std::time::Duration;
+
+pub use livekit_webrtc::native::frame_cryptor::{EncryptionAlgorithm, EncryptionState};
 
 pub mod key_provider;
+pub mod key_store;
 pub mod manager;
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
@@ -29,4 +33,28 @@ pub enum EncryptionType {
 pub struct E2eeOptions {
     pub encryption_type: EncryptionType,
     pub key_provider: KeyProvider,
+    /// Rotate the shared key automatically once connected
+    pub key_rotation: Option<KeyRotationOptions>,
+}
+
+/// Automatic rotation of the shared key, see E2eeManager::start_key_rotation.
+/// The key is rotated as soon as one of the limits is reached
+#[derive(Debug, Clone)]
+pub struct KeyRotationOptions {
+    pub interval: Option<Duration>,
+    /// Frames sent by the encrypted local tracks with the same key
+    /// (packets for audio tracks)
+    pub max_frames: Option<u64>,
+    /// Number of key slots used before wrapping back to index 0
+    pub key_ring_size: i32,
+}
+
+impl Default for KeyRotationOptions {
+    fn default() -> Self {
+        Self {
+            interval: Some(Duration::from_secs(10 * 60)),
+            max_frames: None,
+            key_ring_size: 16,
+        }
+    }
 }
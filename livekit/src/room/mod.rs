@@ -12,37 +12,83 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use self::chat::{Chat, CHAT_TOPIC};
+use self::data_stream::{
+    ByteStreamReader, IncomingStreams, TextStreamReader, STREAM_FLOW_CONTROL_TOPIC,
+};
+use self::data_topic::{DataTopicHandlerFuture, DataTopicOptions, DataTopicRouter, TopicData};
+use self::diagnostics::DiagnosticsHandler;
 use self::e2ee::manager::E2eeManager;
 use self::e2ee::E2eeOptions;
-use crate::participant::ConnectionQuality;
+use self::options::{HardwarePreference, TrackPublishOptions, VideoCodec};
+use self::publication::QualityChange;
+use self::region::{CloudRegionUrlProvider, RegionUrlProvider};
+use self::snapshot::RoomSnapshot;
+use self::transcription::TranscriptionSegment;
+use crate::participant::{AgentState, ConnectionQuality, AGENT_STATE_ATTRIBUTE};
 use crate::prelude::*;
+use crate::rtc_engine::lk_runtime::LkRuntime;
 use crate::rtc_engine::EngineError;
-use crate::rtc_engine::{EngineEvent, EngineEvents, EngineResult, RtcEngine};
-use livekit_api::signal_client::SignalOptions;
+use crate::rtc_engine::{
+    EngineEvent, EngineEvents, EngineOptions, EngineResult, RtcEngine, ICE_CONNECT_TIMEOUT,
+};
+use livekit_api::access_token::Claims;
+use livekit_api::services::room::RoomClient;
+use livekit_api::services::{ServiceError, TwirpError, TwirpErrorCode};
+use livekit_api::signal_client::{
+    SignalClient, SignalError, SignalOptions, CONNECT_TIMEOUT, JOIN_RESPONSE_TIMEOUT,
+};
 use livekit_protocol as proto;
 use livekit_protocol::observer::Dispatcher;
+use livekit_webrtc::native::audio_device::AudioPlayer;
 use livekit_webrtc::native::frame_cryptor::EncryptionState;
-use parking_lot::RwLock;
+use livekit_webrtc::peer_connection_factory::native::PeerConnectionFactoryExt;
+use livekit_webrtc::prelude::{AudioReceiverStats, RtcConfiguration, RtcStats};
+use parking_lot::{Mutex, RwLock};
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use tokio::sync::Mutex as AsyncMutex;
 use tokio::sync::{mpsc, oneshot};
 use tokio::task::JoinHandle;
 
-pub use crate::rtc_engine::SimulateScenario;
-pub use proto::DisconnectReason;
-
+pub use crate::rtc_engine::{
+    ReconnectPolicy, SessionStats, SimulateScenario, TokenProvider, TransportPriority,
+};
+pub use livekit_api::signal_client::{
+    ClientCertificate, ProxyOptions, SignalTransport, TlsOptions, TransportFuture, TransportStream,
+};
+
+pub mod chat;
+pub mod data_compression;
+pub mod data_stream;
+pub mod data_topic;
+pub mod diagnostics;
+pub mod dtmf;
 pub mod e2ee;
 pub mod id;
 pub mod options;
 pub mod participant;
 pub mod publication;
+pub mod region;
+pub mod rpc;
+pub mod snapshot;
 pub mod track;
+pub mod transcription;
 
 pub type RoomResult<T> = Result<T, RoomError>;
 
+// Interval of the polling of the bandwidth estimation
+const BANDWIDTH_POLL_INTERVAL: Duration = Duration::from_secs(2);
+// Retries of the subscriptions failed on the server, the delay grows with each attempt
+const SUBSCRIPTION_RETRY_ATTEMPTS: u32 = 3;
+const SUBSCRIPTION_RETRY_DELAY: Duration = Duration::from_secs(1);
+// Interval of the checks of the quality limitation of the published video
+const CPU_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
 #[derive(Error, Debug)]
 pub enum RoomError {
     #[error("engine: {0}")]
@@ -53,6 +99,12 @@ pub enum RoomError {
     TrackAlreadyPublished,
     #[error("already closed")]
     AlreadyClosed,
+    #[error("timed out: {0}")]
+    Timeout(String),
+    #[error("permission denied: {0}")]
+    PermissionDenied(String),
+    #[error("unknown destination identities: {0:?}")]
+    UnknownDestinations(Vec<ParticipantIdentity>),
 }
 
 #[derive(Clone, Debug)]
@@ -69,6 +121,13 @@ pub enum RoomEvent {
         publication: LocalTrackPublication,
         participant: LocalParticipant,
     },
+    /// The encoder of the video track is limited by the CPU, its resolution and framerate
+    /// have been reduced by one step (see LocalTrackPublication::quality_reductions).
+    /// The steps are restored one by one once the CPU has recovered
+    LocalTrackCpuConstrained {
+        publication: LocalTrackPublication,
+        participant: LocalParticipant,
+    },
     TrackSubscribed {
         track: RemoteTrack,
         publication: RemoteTrackPublication,
@@ -79,6 +138,8 @@ pub enum RoomEvent {
         publication: RemoteTrackPublication,
         participant: RemoteParticipant,
     },
+    /// Also emitted when the server still fails to subscribe to the track after the
+    /// automatic retries
     TrackSubscriptionFailed {
         participant: RemoteParticipant,
         error: track::TrackError,
@@ -101,22 +162,75 @@ pub enum RoomEvent {
         participant: Participant,
         publication: TrackPublication,
     },
+    /// The server (e.g. a moderator) muted or unmuted a local track, emitted after the
+    /// TrackMuted or TrackUnmuted event
+    TrackMutedByServer {
+        participant: LocalParticipant,
+        publication: LocalTrackPublication,
+        muted: bool,
+    },
     ActiveSpeakersChanged {
         speakers: Vec<Participant>,
     },
+    ParticipantMetadataChanged {
+        participant: Participant,
+        old_metadata: String,
+        metadata: String,
+    },
+    ParticipantNameChanged {
+        participant: Participant,
+        old_name: String,
+        name: String,
+    },
+    ParticipantPermissionsChanged {
+        participant: Participant,
+        old: ParticipantPermission,
+        new: ParticipantPermission,
+    },
+    ParticipantAttributesChanged {
+        participant: Participant,
+        /// Added, modified and removed keys
+        changed_keys: Vec<String>,
+    },
+    /// An agent published a new state in its attributes
+    AgentStateChanged {
+        participant: RemoteParticipant,
+        state: AgentState,
+    },
     ConnectionQualityChanged {
         quality: ConnectionQuality,
         participant: Participant,
     },
     DataReceived {
         payload: Arc<Vec<u8>>,
+        topic: Option<String>,
         kind: DataPacketKind,
         participant: RemoteParticipant,
     },
+    /// Speech transcribed by an agent or by the local participant, e.g. to display captions.
+    /// The participant and track are None when they left the room
+    TranscriptionReceived {
+        segments: Vec<TranscriptionSegment>,
+        participant: Option<Participant>,
+        track: Option<TrackPublication>,
+    },
+    /// The data buffered by the local participant went below
+    /// RoomOptions::data_buffered_amount_low_threshold, more data can be published
+    DataBufferedAmountLow {
+        kind: DataPacketKind,
+    },
+    /// Emitted by the frame cryptors, e.g. MissingKey or DecryptionFailed when a remote track
+    /// can't be decrypted
     E2eeStateChanged {
         participant: Participant,
+        track_sid: TrackSid,
         state: EncryptionState,
     },
+    /// The shared key was rotated, the material must be distributed to the other participants
+    E2eeKeyRatcheted {
+        key_index: i32,
+        material: Vec<u8>,
+    },
     ConnectionStateChanged(ConnectionState),
     Connected {
         /// Initial participants & their tracks prior to joining the room
@@ -129,6 +243,42 @@ pub enum RoomEvent {
     },
     Reconnecting,
     Reconnected,
+    /// The network interfaces changed, the connection is resumed on the new network
+    NetworkChanged,
+    /// Round trip time of the signal connection, updated at each ping interval of the server.
+    /// Unlike the media stats, it doesn't depend on the peer connections
+    SignalRttUpdated(Duration),
+    /// Stats of the peer connections, emitted at each RoomOptions::stats_interval
+    StatsReport(SessionStats),
+    /// Bandwidth estimation of the peer connections in bits per second, emitted with
+    /// RoomOptions::bandwidth_estimation. See LocalTrackPublication::set_encoding_parameters
+    /// to share the uplink between the tracks
+    EstimatedBandwidthChanged {
+        uplink: Option<u64>,
+        downlink: Option<u64>,
+    },
+    /// The server rejected the token while reconnecting, see Room::update_token
+    TokenRefreshRequired,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum DisconnectReason {
+    UnknownReason,
+    ClientInitiated,
+    DuplicateIdentity,
+    ServerShutdown,
+    ParticipantRemoved,
+    RoomDeleted,
+    StateMismatch,
+    JoinFailure,
+    Migration,
+    SignalClose,
+    RoomClosed,
+    UserUnavailable,
+    UserRejected,
+    SipTrunkFailure,
+    /// The connection was lost and every reconnection attempt failed
+    ReconnectFailed,
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
@@ -148,8 +298,69 @@ pub enum DataPacketKind {
 pub struct RoomOptions {
     pub auto_subscribe: bool,
     pub adaptive_stream: bool,
+    /// Pause the simulcast layers of the local tracks that no one is receiving
     pub dynacast: bool,
     pub e2ee: Option<E2eeOptions>,
+    pub reconnect_policy: ReconnectPolicy,
+    /// Used to find the closest region when connecting, None connects directly to the url
+    pub region_url_provider: Option<Arc<dyn RegionUrlProvider>>,
+    /// Only connect to these regions (e.g "eu-central"), tried in the given order
+    pub region_override: Vec<String>,
+    /// Custom ICE servers and transport policy (e.g relay only)
+    pub rtc_config: Option<RtcConfiguration>,
+    /// Only use the ice servers of `rtc_config`, ignoring the ones sent by the server
+    pub replace_ice_servers: bool,
+    /// Proxy used to reach the signal server, see SignalOptions::proxy
+    pub proxy: Option<ProxyOptions>,
+    /// Custom connection to the signal server, see SignalOptions::transport
+    pub signal_transport: Option<Arc<dyn SignalTransport>>,
+    /// Custom CA certificates and client certificate of the signal connection
+    pub tls: TlsOptions,
+    /// Used to get a fresh token before reconnecting
+    pub token_provider: Option<TokenProvider>,
+    /// Minimum delay between two ActiveSpeakersChanged events, zero emits every server update
+    pub active_speakers_interval: Duration,
+    /// Accept redundant audio (RED) from the publishers, recovering the audio of lost packets
+    /// at the cost of more bandwidth
+    pub audio_red: bool,
+    /// Managed playback: the subscribed audio tracks are played by this player
+    /// (see RemoteAudioTrack::set_volume) instead of being pulled by the app
+    pub audio_player: Option<AudioPlayer>,
+    /// Use of the hardware encoders per codec (see VideoCodecCapabilities::query).
+    /// The encoders are shared by all the rooms, so this is process wide
+    pub hardware_encoder: HashMap<VideoCodec, HardwarePreference>,
+    /// DataBufferedAmountLow is emitted when the buffered amount of a data channel
+    /// goes below this threshold (in bytes)
+    pub data_buffered_amount_low_threshold: u64,
+    /// Timeout of the WebSocket connection to the signal server, per region
+    pub ws_connect_timeout: Duration,
+    /// Timeout of the JoinResponse once the WebSocket is connected
+    pub join_timeout: Duration,
+    /// Timeout of the peer connections when resuming and of the publisher when sending data
+    pub ice_connect_timeout: Duration,
+    /// Time to wait for the publication of a received track before failing its subscription
+    pub track_subscribed_timeout: Duration,
+    /// Emit a RoomEvent::StatsReport at this interval, None disables the reports
+    pub stats_interval: Option<Duration>,
+    /// Emit RoomEvent::EstimatedBandwidthChanged, the stats of the peer connections are
+    /// polled every couple of seconds while the room is connected
+    pub bandwidth_estimation: bool,
+    /// Resume the connection as soon as the network interfaces change (e.g Wi-Fi to Ethernet,
+    /// VPN up/down) instead of waiting for the peer connections to time out
+    pub detect_network_changes: bool,
+    /// Receives the low level events of the peer connections (SDP, ICE candidates, states),
+    /// see DiagnosticsHandler::log_file to record them
+    pub diagnostics: Option<DiagnosticsHandler>,
+    /// Priorities of the audio, video and data, see LocalTrackPublication::set_encoding_parameters
+    /// to change the priority of a single track
+    pub transport_priority: TransportPriority,
+    /// Reduce the resolution and framerate of the published video when the encoder is
+    /// limited by the CPU, instead of letting the framerate collapse
+    pub cpu_adaptation: bool,
+    /// Advertise that the local participant reads compressed data (the lk.compression attribute,
+    /// which needs the permission to update the metadata or to be set in the token) and compress
+    /// the reliable data above COMPRESSION_THRESHOLD sent to participants that all advertise it
+    pub data_compression: bool,
 }
 
 impl Default for RoomOptions {
@@ -159,6 +370,31 @@ impl Default for RoomOptions {
             adaptive_stream: false,
             dynacast: false,
             e2ee: None,
+            reconnect_policy: ReconnectPolicy::default(),
+            region_url_provider: Some(Arc::new(CloudRegionUrlProvider)),
+            region_override: Vec::new(),
+            rtc_config: None,
+            replace_ice_servers: false,
+            proxy: None,
+            signal_transport: None,
+            tls: TlsOptions::default(),
+            token_provider: None,
+            active_speakers_interval: Duration::ZERO,
+            audio_red: true,
+            audio_player: None,
+            hardware_encoder: HashMap::new(),
+            data_buffered_amount_low_threshold: 64 * 1024,
+            ws_connect_timeout: CONNECT_TIMEOUT,
+            join_timeout: JOIN_RESPONSE_TIMEOUT,
+            ice_connect_timeout: ICE_CONNECT_TIMEOUT,
+            track_subscribed_timeout: Duration::from_secs(5),
+            stats_interval: None,
+            bandwidth_estimation: false,
+            detect_network_changes: true,
+            diagnostics: None,
+            transport_priority: TransportPriority::default(),
+            cpu_adaptation: true,
+            data_compression: false,
         }
     }
 }
@@ -183,36 +419,92 @@ impl Debug for Room {
     }
 }
 
+fn signal_options(options: &RoomOptions) -> SignalOptions {
+    SignalOptions {
+        auto_subscribe: options.auto_subscribe,
+        adaptive_stream: options.adaptive_stream,
+        proxy: options.proxy.clone(),
+        transport: options.signal_transport.clone(),
+        tls: options.tls.clone(),
+        connect_timeout: options.ws_connect_timeout,
+        join_timeout: options.join_timeout,
+    }
+}
+
 impl Room {
+    /// Dropping the future before it completes closes the connection being established
     pub async fn connect(
         url: &str,
         token: &str,
         options: RoomOptions,
     ) -> RoomResult<(Self, mpsc::UnboundedReceiver<RoomEvent>)> {
+        let (room, events, _) = Self::connect_with_tracks(url, token, options, Vec::new()).await?;
+        Ok((room, events))
+    }
+
+    /// Connect and publish the tracks as soon as the room is joined. The tracks are added
+    /// concurrently while the subscriber is set up, and the publisher is negotiated once with
+    /// all of them instead of being negotiated empty first.
+    /// The publication results are in the same order as the given tracks
+    pub async fn connect_with_tracks(
+        url: &str,
+        token: &str,
+        options: RoomOptions,
+        tracks: Vec<(LocalTrack, TrackPublishOptions)>,
+    ) -> RoomResult<(
+        Self,
+        mpsc::UnboundedReceiver<RoomEvent>,
+        Vec<RoomResult<LocalTrackPublication>>,
+    )> {
         let e2ee_options = options.e2ee.clone();
 
         let e2ee_manager = E2eeManager::new(e2ee_options);
-        let (rtc_engine, engine_events) = RtcEngine::connect(
-            url,
-            token,
-            SignalOptions {
-                auto_subscribe: options.auto_subscribe,
-                adaptive_stream: options.adaptive_stream,
-            },
-        )
-        .await?;
+
+        // Kept alive until the engine holds the runtime, the preferences live in its factory
+        let lk_runtime = LkRuntime::instance();
+        for (codec, preference) in &options.hardware_encoder {
+            lk_runtime
+                .pc_factory()
+                .set_hardware_encoder_preference(codec.as_str(), *preference);
+        }
+
+        let engine_options = EngineOptions {
+            signal_options: signal_options(&options),
+            reconnect_policy: options.reconnect_policy.clone(),
+            rtc_config: options.rtc_config.clone(),
+            replace_ice_servers: options.replace_ice_servers,
+            token_provider: options.token_provider.clone(),
+            disable_audio_red: !options.audio_red,
+            data_buffered_amount_low_threshold: options.data_buffered_amount_low_threshold,
+            ice_connect_timeout: options.ice_connect_timeout,
+            detect_network_changes: options.detect_network_changes,
+            diagnostics: options.diagnostics.clone(),
+            transport_priority: options.transport_priority,
+            publish_on_join: !tracks.is_empty(),
+        };
+
+        let (rtc_engine, engine_events) =
+            Self::connect_engine(url, token, &options, engine_options).await?;
         let rtc_engine = Arc::new(rtc_engine);
+        drop(lk_runtime);
 
         let join_response = rtc_engine.last_info().join_response;
+        if let Some(key_provider) = e2ee_manager.key_provider() {
+            key_provider.set_server_sif_trailer(join_response.sif_trailer.clone());
+        }
+
         let pi = join_response.participant.unwrap();
         let local_participant = LocalParticipant::new(
             rtc_engine.clone(),
-            pi.sid.try_into().unwrap(),
-            pi.identity.into(),
-            pi.name,
-            pi.metadata,
+            pi.sid.clone().try_into().unwrap(),
+            pi.identity.clone().into(),
+            pi.name.clone(),
+            pi.metadata.clone(),
+            pi.attributes.clone(),
+            pi.kind().into(),
             e2ee_manager.encryption_type(),
         );
+        local_participant.update_info(pi); // initial permissions
 
         let dispatcher = Dispatcher::<RoomEvent>::default();
         local_participant.on_local_track_published({
@@ -265,6 +557,49 @@ impl Room {
             }
         });
 
+        local_participant.on_attributes_changed({
+            let dispatcher = dispatcher.clone();
+            move |participant, changed_keys| {
+                dispatcher.dispatch(&RoomEvent::ParticipantAttributesChanged {
+                    participant,
+                    changed_keys,
+                });
+            }
+        });
+
+        local_participant.on_metadata_changed({
+            let dispatcher = dispatcher.clone();
+            move |participant, old_metadata, metadata| {
+                dispatcher.dispatch(&RoomEvent::ParticipantMetadataChanged {
+                    participant,
+                    old_metadata,
+                    metadata,
+                });
+            }
+        });
+
+        local_participant.on_name_changed({
+            let dispatcher = dispatcher.clone();
+            move |participant, old_name, name| {
+                dispatcher.dispatch(&RoomEvent::ParticipantNameChanged {
+                    participant,
+                    old_name,
+                    name,
+                });
+            }
+        });
+
+        local_participant.on_permissions_changed({
+            let dispatcher = dispatcher.clone();
+            move |participant, old, new| {
+                dispatcher.dispatch(&RoomEvent::ParticipantPermissionsChanged {
+                    participant,
+                    old,
+                    new,
+                });
+            }
+        });
+
         let room_info = join_response.room.unwrap();
         let inner = Arc::new(RoomSession {
             sid: room_info.sid.try_into().unwrap(),
@@ -275,30 +610,63 @@ impl Room {
             }),
             participants: Default::default(),
             active_speakers: Default::default(),
+            speakers_throttle: Default::default(),
             options,
             rtc_engine,
             local_participant,
             dispatcher: dispatcher.clone(),
             e2ee_manager: e2ee_manager.clone(),
+            data_streams: Default::default(),
+            chat: Default::default(),
+            data_topics: Default::default(),
+            signal_rtt: Default::default(),
         });
 
         for pi in join_response.other_participants {
             let participant = {
                 let pi = pi.clone();
+                let kind = pi.kind().into();
                 inner.create_participant(
                     pi.sid.try_into().unwrap(),
                     pi.identity.into(),
                     pi.name,
                     pi.metadata,
+                    pi.attributes,
+                    kind,
                 )
             };
             participant.update_info(pi.clone());
         }
 
+        inner.local_participant.set_remote_participants_provider({
+            let inner = Arc::downgrade(&inner);
+            move || {
+                let Some(inner) = inner.upgrade() else {
+                    return Vec::new();
+                };
+                let participants = inner.participants.read();
+                participants.values().cloned().collect()
+            }
+        });
+
+        // Requests of the readers to pause or resume the streams sent to us
+        let (flow_tx, mut flow_rx) = mpsc::unbounded_channel();
+        inner.data_streams.lock().flow_control = Some(flow_tx);
+        tokio::spawn({
+            let local_participant = inner.local_participant.clone();
+            async move {
+                while let Some((sender, flow)) = flow_rx.recv().await {
+                    local_participant
+                        .send_stream_flow_control(sender, flow)
+                        .await;
+                }
+            }
+        });
+
         e2ee_manager.on_state_changed({
             let dispatcher = dispatcher.clone();
             let inner = inner.clone();
-            move |participant_identity, state| {
+            move |participant_identity, track_sid, state| {
                 // Forward e2ee events to the room
                 // (Ignore if the participant is not in the room anymore)
 
@@ -320,10 +688,54 @@ impl Room {
                     }
                 };
 
-                dispatcher.dispatch(&RoomEvent::E2eeStateChanged { participant, state });
+                if let Some(publication) = participant.tracks().get(&track_sid) {
+                    publication.set_encryption_state(state);
+                }
+
+                dispatcher.dispatch(&RoomEvent::E2eeStateChanged {
+                    participant,
+                    track_sid,
+                    state,
+                });
+            }
+        });
+
+        e2ee_manager.on_key_ratcheted({
+            let dispatcher = dispatcher.clone();
+            move |key_index, material| {
+                dispatcher.dispatch(&RoomEvent::E2eeKeyRatcheted {
+                    key_index,
+                    material,
+                });
+            }
+        });
+
+        e2ee_manager.on_publisher_stats({
+            let rtc_engine = Arc::downgrade(&inner.rtc_engine);
+            move || {
+                let rtc_engine = rtc_engine.upgrade();
+                Box::pin(async move {
+                    let Some(rtc_engine) = rtc_engine else {
+                        return Vec::new();
+                    };
+                    rtc_engine
+                        .get_stats()
+                        .await
+                        .map(|stats| stats.publisher_stats)
+                        .unwrap_or_default()
+                })
             }
         });
 
+        if let Some(key_rotation) = inner
+            .options
+            .e2ee
+            .as_ref()
+            .and_then(|e2ee| e2ee.key_rotation.clone())
+        {
+            e2ee_manager.start_key_rotation(key_rotation);
+        }
+
         // Get the initial states (Can be useful on some usecases, like the FfiServer)
         // Getting them here ensure nothing happening before (Like a new participant joining) because the room task
         // is not started yet
@@ -341,6 +753,19 @@ impl Room {
         });
         inner.update_connection_state(ConnectionState::Connected);
 
+        if let Some(interval) = inner.options.stats_interval {
+            tokio::spawn(stats_task(Arc::downgrade(&inner), interval));
+        }
+        if inner.options.bandwidth_estimation {
+            tokio::spawn(bandwidth_task(Arc::downgrade(&inner)));
+        }
+        if inner.options.cpu_adaptation {
+            tokio::spawn(cpu_task(Arc::downgrade(&inner)));
+        }
+        if inner.options.data_compression {
+            inner.advertise_data_compression();
+        }
+
         let (close_emitter, close_receiver) = oneshot::channel();
         let session_task = tokio::spawn(inner.clone().room_task(engine_events, close_receiver));
 
@@ -352,7 +777,87 @@ impl Room {
             })),
         };
 
-        Ok((session, events))
+        let publish_on_join = !tracks.is_empty();
+        let publications = session.local_participant().publish_tracks(tracks).await;
+        if publish_on_join && publications.iter().all(Result::is_err) {
+            // Nothing negotiated the publisher, it may be the primary peer connection
+            session.inner.rtc_engine.publisher_negotiation_needed();
+        }
+
+        Ok((session, events, publications))
+    }
+
+    /// Prepare the connection ahead of connect with the options that will be used to connect:
+    /// fetch the regions, validate the token and open the connection to the signal server, so a
+    /// bad token or an unreachable server is reported early. connect reuses the connection if
+    /// called within PREPARED_STREAM_TTL, only the TLS and WebSocket handshakes are left to it.
+    /// The room isn't joined until then.
+    pub async fn prepare_connection(
+        url: &str,
+        token: &str,
+        options: &RoomOptions,
+    ) -> RoomResult<()> {
+        let regions = match options.region_url_provider.as_ref() {
+            Some(provider) => {
+                provider
+                    .regions(url.to_owned(), token.to_owned(), options.proxy.clone())
+                    .await
+            }
+            None => Vec::new(),
+        };
+        let regions = region::filter_regions(regions, &options.region_override);
+        let url = regions.first().map(|r| r.url.as_str()).unwrap_or(url);
+
+        SignalClient::prepare(url, token, &signal_options(options))
+            .await
+            .map_err(|err| RoomError::Engine(err.into()))
+    }
+
+    /// Connect to the closest region first, and fallback to the next regions on failure
+    async fn connect_engine(
+        url: &str,
+        token: &str,
+        options: &RoomOptions,
+        engine_options: EngineOptions,
+    ) -> RoomResult<(RtcEngine, EngineEvents)> {
+        let regions = match options.region_url_provider.as_ref() {
+            Some(provider) => {
+                provider
+                    .regions(url.to_owned(), token.to_owned(), options.proxy.clone())
+                    .await
+            }
+            None => Vec::new(),
+        };
+        let regions = region::filter_regions(regions, &options.region_override);
+
+        if regions.is_empty() {
+            if !options.region_override.is_empty() {
+                return Err(RoomError::Internal(format!(
+                    "none of the regions {:?} are available",
+                    options.region_override
+                )));
+            }
+
+            return Ok(RtcEngine::connect(url, token, engine_options).await?);
+        }
+
+        let mut last_err = None;
+        for region in regions {
+            log::info!("connecting to region {} ({})", region.region, region.url);
+            match RtcEngine::connect(&region.url, token, engine_options.clone()).await {
+                Ok(res) => return Ok(res),
+                Err(err @ EngineError::Signal(SignalError::Client(..))) => {
+                    // The token was rejected, other regions will also reject it
+                    return Err(err.into());
+                }
+                Err(err) => {
+                    log::warn!("failed to connect to region {}: {}", region.region, err);
+                    last_err = Some(err);
+                }
+            }
+        }
+
+        Err(last_err.unwrap().into())
     }
 
     pub async fn close(&self) -> RoomResult<()> {
@@ -386,6 +891,165 @@ impl Room {
         self.inner.local_participant.clone()
     }
 
+    /// Handle the byte streams of a topic (see LocalParticipant::stream_bytes and send_file).
+    /// The handler is called from the room task, spawn a task to read the stream
+    pub fn register_byte_stream_handler<F>(&self, topic: String, handler: F)
+    where
+        F: Fn(ByteStreamReader, ParticipantIdentity) + Send + Sync + 'static,
+    {
+        self.inner
+            .data_streams
+            .lock()
+            .byte_handlers
+            .insert(topic, Arc::new(handler));
+    }
+
+    pub fn unregister_byte_stream_handler(&self, topic: &str) {
+        self.inner.data_streams.lock().byte_handlers.remove(topic);
+    }
+
+    /// Handle the byte streams of a topic sent by one participant, instead of the
+    /// handler registered for every participant with register_byte_stream_handler.
+    /// A reader that is behind pauses the sender, or fails the stream when the sender can't pause
+    pub fn register_participant_byte_stream_handler<F>(
+        &self,
+        identity: ParticipantIdentity,
+        topic: String,
+        handler: F,
+    ) where
+        F: Fn(ByteStreamReader, ParticipantIdentity) + Send + Sync + 'static,
+    {
+        self.inner
+            .data_streams
+            .lock()
+            .participant_byte_handlers
+            .insert((identity, topic), Arc::new(handler));
+    }
+
+    pub fn unregister_participant_byte_stream_handler(
+        &self,
+        identity: ParticipantIdentity,
+        topic: String,
+    ) {
+        self.inner
+            .data_streams
+            .lock()
+            .participant_byte_handlers
+            .remove(&(identity, topic));
+    }
+
+    /// Handle the text streams of a topic (see LocalParticipant::send_text).
+    /// The handler is called from the room task, spawn a task to read the stream
+    pub fn register_text_stream_handler<F>(&self, topic: String, handler: F)
+    where
+        F: Fn(TextStreamReader, ParticipantIdentity) + Send + Sync + 'static,
+    {
+        self.inner
+            .data_streams
+            .lock()
+            .text_handlers
+            .insert(topic, Arc::new(handler));
+    }
+
+    pub fn unregister_text_stream_handler(&self, topic: &str) {
+        self.inner.data_streams.lock().text_handlers.remove(topic);
+    }
+
+    /// Handle the data packets published with this topic, one at a time in the order
+    /// they were received. The packets are still emitted as RoomEvent::DataReceived
+    pub fn on_data_topic<F, Fut>(&self, topic: &str, handler: F)
+    where
+        F: Fn(TopicData) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.on_data_topic_with_options(topic, DataTopicOptions::default(), handler)
+    }
+
+    pub fn on_data_topic_with_options<F, Fut>(
+        &self,
+        topic: &str,
+        options: DataTopicOptions,
+        handler: F,
+    ) where
+        F: Fn(TopicData) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let handler = Arc::new(move |data| Box::pin(handler(data)) as DataTopicHandlerFuture);
+        self.inner
+            .data_topics
+            .lock()
+            .add(topic.to_owned(), handler, options);
+    }
+
+    pub fn off_data_topic(&self, topic: &str) {
+        self.inner.data_topics.lock().remove(topic);
+    }
+
+    /// Chat on the standard chat topic, registers the text stream handler of this topic
+    /// the first time it is called
+    pub fn chat(&self) -> Chat {
+        let mut chat = self.inner.chat.lock();
+        chat.get_or_insert_with(|| {
+            let chat = Chat::new(self.inner.local_participant.clone());
+            self.register_text_stream_handler(CHAT_TOPIC.to_owned(), {
+                let chat = chat.clone();
+                move |reader, sender| chat.handle_stream(reader, sender)
+            });
+            chat
+        })
+        .clone()
+    }
+
+    /// Remove a participant from the room using the RoomService API,
+    /// the token used to connect must have the roomAdmin grant
+    pub async fn remove_participant(&self, identity: &ParticipantIdentity) -> RoomResult<()> {
+        let Some((url, token)) = self.inner.rtc_engine.signal_credentials().await else {
+            return Err(RoomError::AlreadyClosed);
+        };
+
+        let claims = Claims::from_unverified(&token)
+            .map_err(|e| RoomError::Internal(format!("invalid token: {}", e)))?;
+        if !claims.video.room_admin {
+            return Err(RoomError::PermissionDenied(
+                "the roomAdmin grant is required".to_owned(),
+            ));
+        }
+
+        let host = url
+            .replacen("wss://", "https://", 1)
+            .replacen("ws://", "http://", 1);
+        RoomClient::with_token(&host, &token)
+            .remove_participant(&self.inner.name, &identity.0)
+            .await
+            .map_err(|e| match e {
+                ServiceError::Twirp(TwirpError::Twirp(code))
+                    if code.code == TwirpErrorCode::PERMISSION_DENIED
+                        || code.code == TwirpErrorCode::UNAUTHENTICATED =>
+                {
+                    RoomError::PermissionDenied(code.msg)
+                }
+                e => RoomError::Internal(format!("failed to remove participant: {}", e)),
+            })
+    }
+
+    /// Currently speaking participants, loudest first
+    pub fn active_speakers(&self) -> Vec<Participant> {
+        self.inner.active_speakers.read().clone()
+    }
+
+    /// Latest round trip time of the signal connection, None until the server answered
+    /// a ping (see RoomEvent::SignalRttUpdated)
+    pub fn signal_rtt(&self) -> Option<Duration> {
+        *self.inner.signal_rtt.lock()
+    }
+
+    /// Whether the server made the subscriber the primary peer connection. The publisher
+    /// peer connection is still created on join, but it is only negotiated once something
+    /// is published (or data is sent)
+    pub fn subscriber_primary(&self) -> bool {
+        self.inner.rtc_engine.subscriber_primary()
+    }
+
     pub fn connection_state(&self) -> ConnectionState {
         self.inner.info.read().state
     }
@@ -394,13 +1058,220 @@ impl Room {
         self.inner.participants.read().clone()
     }
 
+    /// Copy the state of every participant at once, no participant can join or leave
+    /// while the snapshot is taken
+    pub fn participants_snapshot(&self) -> RoomSnapshot {
+        let participants = self.inner.participants.read();
+        let info = self.inner.info.read();
+
+        RoomSnapshot {
+            sid: self.inner.sid.clone(),
+            name: self.inner.name.clone(),
+            metadata: info.metadata.clone(),
+            connection_state: info.state,
+            local_participant: (&Participant::Local(self.inner.local_participant.clone())).into(),
+            remote_participants: participants
+                .iter()
+                .map(|(sid, participant)| {
+                    (
+                        sid.clone(),
+                        (&Participant::Remote(participant.clone())).into(),
+                    )
+                })
+                .collect(),
+            active_speakers: self
+                .inner
+                .active_speakers
+                .read()
+                .iter()
+                .map(|participant| participant.sid())
+                .collect(),
+        }
+    }
+
     pub async fn simulate_scenario(&self, scenario: SimulateScenario) -> EngineResult<()> {
         self.inner.rtc_engine.simulate_scenario(scenario).await
     }
 
+    /// Replace the token used when reconnecting (e.g after a TokenRefreshRequired event)
+    pub async fn update_token(&self, token: String) {
+        self.inner.rtc_engine.update_token(token).await
+    }
+
+    /// Receive stats of a subscribed audio track, see AudioReceiverStats::recovered_packets
+    pub async fn audio_receiver_stats(
+        &self,
+        track: &RemoteAudioTrack,
+    ) -> RoomResult<AudioReceiverStats> {
+        let Some(transceiver) = track.transceiver() else {
+            return Err(RoomError::Internal("the track isn't subscribed".to_owned()));
+        };
+
+        self.inner
+            .rtc_engine
+            .get_audio_receiver_stats(&transceiver.receiver())
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Stats of the publisher and subscriber peer connections
+    pub async fn get_stats(&self) -> RoomResult<SessionStats> {
+        self.inner.rtc_engine.get_stats().await.map_err(Into::into)
+    }
+
+    /// Stats of a published or subscribed track (outbound-rtp or inbound-rtp, with their
+    /// codecs and candidate pairs)
+    pub async fn get_track_stats(&self, track: &Track) -> RoomResult<Vec<RtcStats>> {
+        let Some(transceiver) = track.transceiver() else {
+            return Err(RoomError::Internal(
+                "the track isn't published or subscribed".to_owned(),
+            ));
+        };
+
+        let engine = &self.inner.rtc_engine;
+        match track {
+            Track::LocalAudio(_) | Track::LocalVideo(_) => {
+                engine.get_sender_stats(&transceiver.sender()).await
+            }
+            Track::RemoteAudio(_) | Track::RemoteVideo(_) => {
+                engine.get_receiver_stats(&transceiver.receiver()).await
+            }
+        }
+        .map_err(Into::into)
+    }
+
     pub fn e2ee_manager(&self) -> &E2eeManager {
         &self.inner.e2ee_manager
     }
+
+    pub fn e2ee_stats(&self) -> Vec<e2ee::manager::FrameCryptorStats> {
+        self.inner.e2ee_manager.stats()
+    }
+}
+
+/// Dispatch the stats of the session until the room is disconnected
+async fn stats_task(inner: std::sync::Weak<RoomSession>, interval: Duration) {
+    let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        let Some(inner) = inner.upgrade() else {
+            break;
+        };
+
+        match inner.info.read().state {
+            ConnectionState::Disconnected => break,
+            ConnectionState::Reconnecting => continue,
+            ConnectionState::Connected => {}
+        }
+
+        match inner.rtc_engine.get_stats().await {
+            Ok(stats) => inner.dispatcher.dispatch(&RoomEvent::StatsReport(stats)),
+            Err(err) => log::warn!("failed to get the session stats: {:?}", err),
+        }
+    }
+}
+
+/// Dispatch the changes of the bandwidth estimation until the room is disconnected
+async fn bandwidth_task(inner: std::sync::Weak<RoomSession>) {
+    let mut interval = tokio::time::interval(BANDWIDTH_POLL_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut last = (None, None);
+    loop {
+        interval.tick().await;
+        let Some(inner) = inner.upgrade() else {
+            break;
+        };
+
+        match inner.info.read().state {
+            ConnectionState::Disconnected => break,
+            ConnectionState::Reconnecting => continue,
+            ConnectionState::Connected => {}
+        }
+
+        let Ok(stats) = inner.rtc_engine.get_stats().await else {
+            continue;
+        };
+        let (uplink, downlink) = (stats.uplink_bandwidth(), stats.downlink_bandwidth());
+        if (uplink, downlink) != last {
+            last = (uplink, downlink);
+            inner
+                .dispatcher
+                .dispatch(&RoomEvent::EstimatedBandwidthChanged { uplink, downlink });
+        }
+    }
+}
+
+/// Reduce the quality of the published video tracks limited by the CPU, and restore it once
+/// the CPU has recovered, until the room is disconnected
+async fn cpu_task(inner: std::sync::Weak<RoomSession>) {
+    let mut interval = tokio::time::interval(CPU_POLL_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        let Some(inner) = inner.upgrade() else {
+            break;
+        };
+
+        match inner.info.read().state {
+            ConnectionState::Disconnected => break,
+            ConnectionState::Reconnecting => continue,
+            ConnectionState::Connected => {}
+        }
+
+        for publication in inner.local_participant.tracks().into_values() {
+            let Some(LocalTrack::Video(track)) = publication.track() else {
+                continue;
+            };
+            let Some(transceiver) = track.transceiver() else {
+                continue;
+            };
+            let Ok(stats) = inner
+                .rtc_engine
+                .get_sender_stats(&transceiver.sender())
+                .await
+            else {
+                continue;
+            };
+
+            let cpu_limited = stats.iter().any(|stats| match stats {
+                RtcStats::OutboundRtp(outbound) => outbound.quality_limitation_reason == "cpu",
+                _ => false,
+            });
+            match publication.update_cpu_limitation(cpu_limited) {
+                Ok(Some(QualityChange::Reduced)) => {
+                    log::info!(
+                        "track {} is limited by the cpu, reducing its quality",
+                        publication.sid()
+                    );
+                    inner
+                        .dispatcher
+                        .dispatch(&RoomEvent::LocalTrackCpuConstrained {
+                            publication,
+                            participant: inner.local_participant.clone(),
+                        });
+                }
+                Ok(Some(QualityChange::Restored)) => {
+                    log::info!(
+                        "the cpu has recovered, restoring the quality of {}",
+                        publication.sid()
+                    );
+                }
+                Ok(None) => {}
+                Err(err) => log::warn!(
+                    "failed to change the quality of {}: {:?}",
+                    publication.sid(),
+                    err
+                ),
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct SpeakersThrottle {
+    last_dispatch: Option<Instant>,
+    scheduled: bool,
 }
 
 struct RoomInfo {
@@ -416,9 +1287,14 @@ pub(crate) struct RoomSession {
     dispatcher: Dispatcher<RoomEvent>,
     options: RoomOptions,
     active_speakers: RwLock<Vec<Participant>>,
+    speakers_throttle: Mutex<SpeakersThrottle>,
     local_participant: LocalParticipant,
     participants: RwLock<HashMap<ParticipantSid, RemoteParticipant>>,
     e2ee_manager: E2eeManager,
+    data_streams: Mutex<IncomingStreams>,
+    chat: Mutex<Option<Chat>>,
+    data_topics: Mutex<DataTopicRouter>,
+    signal_rtt: Mutex<Option<Duration>>,
 }
 
 impl Debug for RoomSession {
@@ -478,9 +1354,10 @@ impl RoomSession {
                 let remote_participant = self.get_participant(&participant_sid);
 
                 if let Some(remote_participant) = remote_participant {
+                    let timeout = self.options.track_subscribed_timeout;
                     tokio::spawn(async move {
                         remote_participant
-                            .add_subscribed_media_track(track_sid, track, transceiver)
+                            .add_subscribed_media_track(track_sid, track, transceiver, timeout)
                             .await;
                     });
                 } else {
@@ -499,32 +1376,179 @@ impl RoomSession {
             EngineEvent::Restarted => self.handle_restarted(),
             EngineEvent::SignalRestarted => self.clone().handle_signal_restarted(),
             EngineEvent::Disconnected { reason } => self.handle_disconnected(reason),
+            EngineEvent::TokenRefreshRequired => {
+                self.dispatcher.dispatch(&RoomEvent::TokenRefreshRequired)
+            }
             EngineEvent::Data {
                 payload,
+                topic,
                 kind,
                 participant_sid,
             } => {
+                let Some(participant) = self.get_participant(&participant_sid) else {
+                    return Ok(());
+                };
+
+                let (topic, payload) = match data_compression::decompress(topic, payload) {
+                    Ok(data) => data,
+                    Err(err) => {
+                        log::warn!("failed to decompress data packet: {}", err);
+                        return Ok(());
+                    }
+                };
+
+                if topic.as_deref() == Some(STREAM_FLOW_CONTROL_TOPIC) {
+                    self.local_participant
+                        .handle_stream_flow_control(participant.identity(), &payload);
+                    return Ok(());
+                }
+
                 let payload = Arc::new(payload);
-                if let Some(participant) = self.get_participant(&participant_sid) {
-                    self.dispatcher.dispatch(&RoomEvent::DataReceived {
-                        payload,
+                if let Some(topic) = topic.clone() {
+                    self.data_topics.lock().route(TopicData {
+                        topic,
+                        payload: payload.clone(),
                         kind,
-                        participant,
+                        participant: participant.clone(),
                     });
                 }
+
+                self.dispatcher.dispatch(&RoomEvent::DataReceived {
+                    payload,
+                    topic,
+                    kind,
+                    participant,
+                });
+            }
+            EngineEvent::DataBufferedAmountLow { kind } => {
+                self.dispatcher
+                    .dispatch(&RoomEvent::DataBufferedAmountLow { kind });
+            }
+            EngineEvent::Transcription { transcription } => {
+                self.handle_transcription(transcription)
+            }
+            EngineEvent::RpcRequest {
+                caller_identity,
+                request,
+            } => {
+                let local_participant = self.local_participant.clone();
+                tokio::spawn(async move {
+                    local_participant
+                        .handle_incoming_rpc_request(caller_identity, request)
+                        .await;
+                });
+            }
+            EngineEvent::RpcAck { request_id } => {
+                self.local_participant.handle_incoming_rpc_ack(request_id);
+            }
+            EngineEvent::RpcResponse { response } => {
+                self.local_participant
+                    .handle_incoming_rpc_response(response);
+            }
+            EngineEvent::StreamHeader {
+                participant_identity,
+                header,
+            } => {
+                let call = self
+                    .data_streams
+                    .lock()
+                    .handle_header(header, participant_identity);
+                if let Some(call) = call {
+                    call();
+                }
+            }
+            EngineEvent::StreamChunk { chunk } => {
+                self.data_streams.lock().handle_chunk(chunk);
+            }
+            EngineEvent::StreamTrailer { trailer } => {
+                self.data_streams.lock().handle_trailer(trailer);
             }
             EngineEvent::SpeakersChanged { speakers } => self.handle_speakers_changed(speakers),
             EngineEvent::ConnectionQuality { updates } => {
                 self.handle_connection_quality_update(updates)
             }
+            EngineEvent::SubscribedQualityUpdate { update } => {
+                if self.options.dynacast {
+                    self.local_participant
+                        .handle_subscribed_quality_update(update);
+                }
+            }
+            EngineEvent::RemoteMute { sid, muted } => {
+                let Ok(sid) = sid.try_into() else {
+                    return Ok(());
+                };
+                if let Some(publication) = self.local_participant.handle_remote_mute(sid, muted) {
+                    self.dispatcher.dispatch(&RoomEvent::TrackMutedByServer {
+                        participant: self.local_participant.clone(),
+                        publication,
+                        muted,
+                    });
+                }
+            }
+            EngineEvent::SignalRtt { rtt } => {
+                *self.signal_rtt.lock() = Some(rtt);
+                self.dispatcher.dispatch(&RoomEvent::SignalRttUpdated(rtt));
+            }
+            EngineEvent::NetworkChanged => self.dispatcher.dispatch(&RoomEvent::NetworkChanged),
+            EngineEvent::CandidatePairChange { .. } => {}
+            EngineEvent::SubscriptionFailed { track_sid, error } => {
+                if let Ok(track_sid) = track_sid.try_into() {
+                    self.handle_subscription_failed(track_sid, error.into());
+                }
+            }
         }
 
         Ok(())
     }
 
+    /// Retry the subscription if the error can be transient, or give up and notify
+    fn handle_subscription_failed(&self, track_sid: TrackSid, error: track::SubscriptionError) {
+        let found = self.participants.read().values().find_map(|participant| {
+            let publication = participant.get_track_publication(&track_sid)?;
+            Some((participant.clone(), publication))
+        });
+        let Some((participant, publication)) = found else {
+            log::warn!("subscription failed for an unknown track: {:?}", track_sid);
+            return;
+        };
+
+        // With auto_subscribe, the server subscribes us without the publication being desired
+        let auto_subscribe = self.options.auto_subscribe;
+
+        // The subscription was cancelled or succeeded in the meantime
+        if !(publication.is_desired() || auto_subscribe) || publication.is_subscribed() {
+            return;
+        }
+
+        let attempts = publication.subscription_failed();
+        if error.is_retryable() && attempts <= SUBSCRIPTION_RETRY_ATTEMPTS {
+            log::warn!(
+                "subscription to {:?} failed: {:?}, retrying (attempt {})",
+                track_sid,
+                error,
+                attempts
+            );
+            tokio::spawn(async move {
+                tokio::time::sleep(SUBSCRIPTION_RETRY_DELAY * attempts).await;
+                if (publication.is_desired() || auto_subscribe) && !publication.is_subscribed() {
+                    publication.request_subscription(true);
+                }
+            });
+            return;
+        }
+
+        self.dispatcher
+            .dispatch(&RoomEvent::TrackSubscriptionFailed {
+                participant,
+                track_sid: track_sid.clone(),
+                error: track::TrackError::SubscriptionFailed(track_sid, error),
+            });
+    }
+
     async fn close(&self) {
         self.rtc_engine.close().await;
         self.e2ee_manager.cleanup();
+        self.data_streams.lock().close();
     }
 
     /// Change the connection state and emit an event
@@ -545,6 +1569,44 @@ impl RoomSession {
     /// Update the participants inside a Room.
     /// It'll create, update or remove a participant
     /// It also update the participant tracks.
+    /// Unpublish the tracks we're no longer allowed to publish and resubscribe
+    /// when the subscribe permission is granted back
+    fn handle_local_permissions_changed(
+        self: &Arc<Self>,
+        old: ParticipantPermission,
+        new: ParticipantPermission,
+    ) {
+        let revoked: Vec<TrackSid> = self
+            .local_participant
+            .tracks()
+            .into_iter()
+            .filter(|(_, publication)| !new.can_publish_source(publication.source()))
+            .map(|(sid, _)| sid)
+            .collect();
+
+        if !revoked.is_empty() {
+            let local_participant = self.local_participant.clone();
+            tokio::spawn(async move {
+                for sid in revoked {
+                    log::info!("publish permission revoked, unpublishing {:?}", sid);
+                    if let Err(err) = local_participant.unpublish_track(&sid).await {
+                        log::error!("failed to unpublish {:?}: {:?}", sid, err);
+                    }
+                }
+            });
+        }
+
+        if !old.can_subscribe && new.can_subscribe && self.options.auto_subscribe {
+            for participant in self.participants.read().values() {
+                for publication in participant.tracks().values() {
+                    if !publication.is_subscribed() {
+                        publication.request_subscription(true);
+                    }
+                }
+            }
+        }
+    }
+
     fn handle_participant_update(self: &Arc<Self>, updates: Vec<proto::ParticipantInfo>) {
         for pi in updates {
             let participant_sid = pi.sid.clone().try_into().unwrap();
@@ -553,7 +1615,13 @@ impl RoomSession {
             if participant_sid == self.local_participant.sid()
                 || participant_identity == self.local_participant.identity()
             {
+                let old_permission = self.local_participant.permissions();
                 self.local_participant.clone().update_info(pi);
+
+                let new_permission = self.local_participant.permissions();
+                if old_permission != new_permission {
+                    self.handle_local_permissions_changed(old_permission, new_permission);
+                }
                 continue;
             }
 
@@ -574,11 +1642,14 @@ impl RoomSession {
                 log::info!("Participant connected: {:?}", participant_sid);
                 let remote_participant = {
                     let pi = pi.clone();
+                    let kind = pi.kind().into();
                     self.create_participant(
                         pi.sid.try_into().unwrap(),
                         pi.identity.into(),
                         pi.name,
                         pi.metadata,
+                        pi.attributes,
+                        kind,
                     )
                 };
 
@@ -591,9 +1662,10 @@ impl RoomSession {
     }
 
     /// Active speakers changed
-    /// Update the participants & sort the active_speakers by audio_level
-    fn handle_speakers_changed(&self, speakers_info: Vec<proto::SpeakerInfo>) {
-        let mut speakers = Vec::new();
+    /// The server only sends the speakers that changed, merge them into active_speakers
+    /// and sort it by audio_level (loudest first)
+    fn handle_speakers_changed(self: &Arc<Self>, speakers_info: Vec<proto::SpeakerInfo>) {
+        let mut speakers = self.active_speakers.read().clone();
 
         for speaker in speakers_info {
             let sid: ParticipantSid = speaker.sid.try_into().unwrap();
@@ -610,16 +1682,54 @@ impl RoomSession {
             participant.set_speaking(speaker.active);
             participant.set_audio_level(speaker.level);
 
+            speakers.retain(|p| p.sid() != sid);
             if speaker.active {
                 speakers.push(participant);
             }
         }
 
-        speakers.sort_by(|a, b| a.audio_level().partial_cmp(&b.audio_level()).unwrap());
-        *self.active_speakers.write() = speakers.clone();
+        speakers.sort_by(|a, b| b.audio_level().total_cmp(&a.audio_level()));
+        *self.active_speakers.write() = speakers;
+        self.dispatch_active_speakers();
+    }
 
-        self.dispatcher
-            .dispatch(&RoomEvent::ActiveSpeakersChanged { speakers });
+    /// Emit ActiveSpeakersChanged at most once every active_speakers_interval,
+    /// updates received in between are coalesced into a single event
+    fn dispatch_active_speakers(self: &Arc<Self>) {
+        let interval = self.options.active_speakers_interval;
+        let mut throttle = self.speakers_throttle.lock();
+        if throttle.scheduled {
+            return; // The pending task will send the latest speakers
+        }
+
+        let elapsed = throttle.last_dispatch.map(|last| last.elapsed());
+        match elapsed {
+            Some(elapsed) if elapsed < interval => {
+                throttle.scheduled = true;
+                let session = self.clone();
+                tokio::spawn(async move {
+                    tokio::time::sleep(interval - elapsed).await;
+                    {
+                        let mut throttle = session.speakers_throttle.lock();
+                        throttle.scheduled = false;
+                        throttle.last_dispatch = Some(Instant::now());
+                    }
+
+                    let speakers = session.active_speakers.read().clone();
+                    session
+                        .dispatcher
+                        .dispatch(&RoomEvent::ActiveSpeakersChanged { speakers });
+                });
+            }
+            _ => {
+                throttle.last_dispatch = Some(Instant::now());
+                drop(throttle);
+
+                let speakers = self.active_speakers.read().clone();
+                self.dispatcher
+                    .dispatch(&RoomEvent::ActiveSpeakersChanged { speakers });
+            }
+        }
     }
 
     /// Handle a connection quality update
@@ -684,6 +1794,7 @@ impl RoomSession {
             }),
             publish_tracks: self.local_participant.published_tracks_info(),
             data_channels: last_info.data_channels_info,
+            track_sids_disabled: Vec::new(),
         };
 
         log::info!("sending sync state {:?}", sync_state);
@@ -731,13 +1842,39 @@ impl RoomSession {
         self.dispatcher.dispatch(&RoomEvent::Reconnected);
     }
 
+    fn advertise_data_compression(&self) {
+        let local_participant = self.local_participant.clone();
+        if data_compression::is_supported(&local_participant.attributes()) {
+            return;
+        }
+
+        tokio::spawn(async move {
+            let attributes = HashMap::from([(
+                data_compression::COMPRESSION_ATTRIBUTE.to_owned(),
+                data_compression::COMPRESSION_ZSTD.to_owned(),
+            )]);
+            if let Err(err) = local_participant.set_attributes(attributes).await {
+                log::warn!("failed to advertise the data compression: {}", err);
+            }
+        });
+    }
+
     fn handle_signal_restarted(self: Arc<Self>) {
         let join_response = self.rtc_engine.last_info().join_response;
+        if let Some(key_provider) = self.e2ee_manager.key_provider() {
+            key_provider.set_server_sif_trailer(join_response.sif_trailer.clone());
+        }
+
         self.local_participant
             .update_info(join_response.participant.unwrap()); // The sid may have changed
 
         self.handle_participant_update(join_response.other_participants);
 
+        // The attributes of the previous session are gone
+        if self.options.data_compression {
+            self.advertise_data_compression();
+        }
+
         // unpublish & republish tracks
         let published_tracks = self.local_participant.tracks();
         tokio::spawn(async move {
@@ -757,6 +1894,30 @@ impl RoomSession {
         });
     }
 
+    fn handle_transcription(&self, transcription: proto::Transcription) {
+        let identity: ParticipantIdentity = transcription.transcribed_participant_identity.into();
+        let participant = if identity == self.local_participant.identity() {
+            Some(Participant::Local(self.local_participant.clone()))
+        } else {
+            self.participants
+                .read()
+                .values()
+                .find(|p| p.identity() == identity)
+                .map(|p| Participant::Remote(p.clone()))
+        };
+
+        let track = participant.as_ref().and_then(|participant| {
+            let sid: TrackSid = transcription.track_id.try_into().ok()?;
+            participant.tracks().remove(&sid)
+        });
+
+        self.dispatcher.dispatch(&RoomEvent::TranscriptionReceived {
+            segments: transcription.segments.into_iter().map(Into::into).collect(),
+            participant,
+            track,
+        });
+    }
+
     fn handle_disconnected(&self, reason: DisconnectReason) {
         log::info!("disconnected from room,: {:?}", reason);
         if self.update_connection_state(ConnectionState::Disconnected) {
@@ -773,6 +1934,8 @@ impl RoomSession {
         identity: ParticipantIdentity,
         name: String,
         metadata: String,
+        attributes: HashMap<String, String>,
+        kind: ParticipantKind,
     ) -> RemoteParticipant {
         let participant = RemoteParticipant::new(
             self.rtc_engine.clone(),
@@ -780,6 +1943,8 @@ impl RoomSession {
             identity,
             name,
             metadata,
+            attributes,
+            kind,
         );
 
         participant.on_track_published({
@@ -805,7 +1970,12 @@ impl RoomSession {
         participant.on_track_subscribed({
             let dispatcher = self.dispatcher.clone();
             let e2ee_manager = self.e2ee_manager.clone();
+            let audio_player = self.options.audio_player.clone();
             move |participant, publication, track| {
+                if let (Some(player), RemoteTrack::Audio(audio_track)) = (&audio_player, &track) {
+                    player.add_track(audio_track.rtc_track());
+                }
+
                 let event = RoomEvent::TrackSubscribed {
                     participant: participant.clone(),
                     track: track.clone(),
@@ -819,7 +1989,12 @@ impl RoomSession {
         participant.on_track_unsubscribed({
             let dispatcher = self.dispatcher.clone();
             let e2ee_manager = self.e2ee_manager.clone();
+            let audio_player = self.options.audio_player.clone();
             move |participant, publication, track| {
+                if let (Some(player), RemoteTrack::Audio(audio_track)) = (&audio_player, &track) {
+                    player.remove_track(&audio_track.rtc_track().id());
+                }
+
                 let event = RoomEvent::TrackUnsubscribed {
                     participant: participant.clone(),
                     track: track.clone(),
@@ -863,6 +2038,62 @@ impl RoomSession {
             }
         });
 
+        participant.on_attributes_changed({
+            let dispatcher = self.dispatcher.clone();
+            move |participant, changed_keys| {
+                let agent_state = match &participant {
+                    Participant::Remote(remote)
+                        if changed_keys.iter().any(|key| key == AGENT_STATE_ATTRIBUTE) =>
+                    {
+                        remote.agent_state().map(|state| (remote.clone(), state))
+                    }
+                    _ => None,
+                };
+
+                dispatcher.dispatch(&RoomEvent::ParticipantAttributesChanged {
+                    participant,
+                    changed_keys,
+                });
+
+                if let Some((participant, state)) = agent_state {
+                    dispatcher.dispatch(&RoomEvent::AgentStateChanged { participant, state });
+                }
+            }
+        });
+
+        participant.on_metadata_changed({
+            let dispatcher = self.dispatcher.clone();
+            move |participant, old_metadata, metadata| {
+                dispatcher.dispatch(&RoomEvent::ParticipantMetadataChanged {
+                    participant,
+                    old_metadata,
+                    metadata,
+                });
+            }
+        });
+
+        participant.on_name_changed({
+            let dispatcher = self.dispatcher.clone();
+            move |participant, old_name, name| {
+                dispatcher.dispatch(&RoomEvent::ParticipantNameChanged {
+                    participant,
+                    old_name,
+                    name,
+                });
+            }
+        });
+
+        participant.on_permissions_changed({
+            let dispatcher = self.dispatcher.clone();
+            move |participant, old, new| {
+                dispatcher.dispatch(&RoomEvent::ParticipantPermissionsChanged {
+                    participant,
+                    old,
+                    new,
+                });
+            }
+        });
+
         self.participants.write().insert(sid, participant.clone());
 
         participant
@@ -876,6 +2107,14 @@ impl RoomSession {
         }
 
         self.participants.write().remove(&remote_participant.sid());
+        self.local_participant
+            .handle_participant_disconnected(&remote_participant.identity());
+        self.data_streams
+            .lock()
+            .participant_disconnected(&remote_participant.identity());
+        self.active_speakers
+            .write()
+            .retain(|p| p.sid() != remote_participant.sid());
         self.dispatcher
             .dispatch(&RoomEvent::ParticipantDisconnected(remote_participant));
     }
@@ -12,14 +12,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::e2ee::EncryptionType;
 use crate::prelude::*;
+use crate::rtc_engine::lk_runtime::LkRuntime;
 use livekit_protocol as proto;
+use livekit_webrtc::peer_connection_factory::native::PeerConnectionFactoryExt;
 use livekit_webrtc::prelude::*;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub use livekit_webrtc::peer_connection_factory::native::{
+    HardwarePreference, VideoCodecCapability,
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum VideoCodec {
     VP8,
     H264,
+    VP9,
     AV1,
 }
 
@@ -28,9 +36,56 @@ impl VideoCodec {
         match self {
             VideoCodec::VP8 => "vp8",
             VideoCodec::H264 => "h264",
+            VideoCodec::VP9 => "vp9",
             VideoCodec::AV1 => "av1",
         }
     }
+
+    /// SVC codecs send a single encoding containing all the layers instead of simulcast
+    pub fn is_svc(&self) -> bool {
+        matches!(self, VideoCodec::VP9 | VideoCodec::AV1)
+    }
+}
+
+/// Encoders and decoders available on this device, used to pick the publish codec
+/// (e.g. H264 when only it is hardware accelerated)
+#[derive(Debug, Clone, Default)]
+pub struct VideoCodecCapabilities {
+    pub codecs: Vec<VideoCodecCapability>,
+}
+
+impl VideoCodecCapabilities {
+    pub fn query() -> Self {
+        Self {
+            codecs: LkRuntime::instance()
+                .pc_factory()
+                .video_codec_capabilities(),
+        }
+    }
+
+    pub fn get(&self, codec: VideoCodec) -> Option<&VideoCodecCapability> {
+        self.codecs
+            .iter()
+            .find(|c| c.codec.eq_ignore_ascii_case(codec.as_str()))
+    }
+
+    pub fn hardware_encoder(&self, codec: VideoCodec) -> bool {
+        self.get(codec).is_some_and(|c| c.hardware_encoder)
+    }
+
+    pub fn hardware_decoder(&self, codec: VideoCodec) -> bool {
+        self.get(codec).is_some_and(|c| c.hardware_decoder)
+    }
+
+    pub fn can_encode(&self, codec: VideoCodec) -> bool {
+        self.get(codec)
+            .is_some_and(|c| c.hardware_encoder || c.software_encoder)
+    }
+
+    pub fn can_decode(&self, codec: VideoCodec) -> bool {
+        self.get(codec)
+            .is_some_and(|c| c.hardware_decoder || c.software_decoder)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -81,8 +136,101 @@ pub struct TrackPublishOptions {
     pub dtx: bool,
     pub red: bool,
     pub simulcast: bool,
+    // Lower simulcast layers (e.g video::H180, video::H360), the defaults are used when empty.
+    // At most two layers smaller than the published resolution are kept
+    pub simulcast_layers: Vec<VideoPreset>,
     // pub name: String,
     pub source: TrackSource,
+    // Override the encryption of this track, if None the room encryption is used.
+    // Ignored when e2ee isn't enabled on the room
+    pub encryption: Option<EncryptionType>,
+    // How the encoder degrades under constrained bandwidth (video only).
+    // If None, screenshares maintain their resolution and WebRTC decides otherwise
+    pub degradation_preference: Option<DegradationPreference>,
+    // If None, screenshares are hinted as Detailed
+    pub content_hint: Option<VideoContentHint>,
+    // Only used by SVC codecs (VP9, AV1), defaults to L3T3_KEY
+    pub scalability_mode: Option<String>,
+    // Published alongside SVC codecs when a subscriber can't decode them, not for encrypted tracks
+    pub backup_codec: Option<VideoCodec>,
+    // Publish the audio in stereo, defaults to the MUSIC_STEREO preset when no audio_encoding is set
+    pub stereo: bool,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct DataPublishOptions {
+    // Send on the reliable data channel and wait until the data left the send buffer
+    // (handed to the SCTP transport) instead of returning once queued
+    pub reliable: bool,
+    // Participants receiving the data, everyone if empty
+    pub destination_sids: Vec<String>,
+    // Same as destination_sids, publishing fails if one of them isn't in the room
+    pub destination_identities: Vec<ParticipantIdentity>,
+    // Lets the receivers route the data to a handler (see Room::on_data_topic)
+    pub topic: Option<String>,
+    // Packets per second of the topic (at least one per hour), only for lossy data. The data
+    // published faster is coalesced: only the latest packet is sent at the next slot of the topic
+    pub max_rate: Option<f64>,
+}
+
+/// Shorthand for the audio related fields of TrackPublishOptions.
+/// Music sharing usually wants stereo at 128-256kbps with DTX disabled.
+#[derive(Clone, Debug)]
+pub struct AudioPublishOptions {
+    pub dtx: bool,
+    // Send redundant audio (RED) so the subscribers can recover the lost packets
+    pub red: bool,
+    pub stereo: bool,
+    pub bitrate: u32,
+    pub source: TrackSource,
+}
+
+impl Default for AudioPublishOptions {
+    fn default() -> Self {
+        Self {
+            dtx: true,
+            red: true,
+            stereo: false,
+            bitrate: audio::SPEECH.encoding.max_bitrate as u32,
+            source: TrackSource::Microphone,
+        }
+    }
+}
+
+impl From<AudioPublishOptions> for TrackPublishOptions {
+    fn from(options: AudioPublishOptions) -> Self {
+        Self {
+            audio_encoding: Some(AudioEncoding {
+                max_bitrate: options.bitrate as u64,
+            }),
+            dtx: options.dtx,
+            red: options.red,
+            stereo: options.stereo,
+            source: options.source,
+            ..Default::default()
+        }
+    }
+}
+
+impl TrackPublishOptions {
+    pub(crate) fn effective_degradation_preference(&self) -> Option<DegradationPreference> {
+        self.degradation_preference.or_else(|| {
+            (self.source == TrackSource::Screenshare)
+                .then_some(DegradationPreference::MaintainResolution)
+        })
+    }
+
+    pub(crate) fn effective_content_hint(&self) -> Option<VideoContentHint> {
+        self.content_hint.or_else(|| {
+            (self.source == TrackSource::Screenshare).then_some(VideoContentHint::Detailed)
+        })
+    }
+
+    /// The backup codec is only used when the primary codec is SVC
+    pub(crate) fn effective_backup_codec(&self) -> Option<VideoCodec> {
+        self.backup_codec
+            .filter(|codec| self.video_codec.is_svc() && *codec != self.video_codec)
+    }
 }
 
 impl Default for TrackPublishOptions {
@@ -94,7 +242,33 @@ impl Default for TrackPublishOptions {
             dtx: true,
             red: true,
             simulcast: true,
+            simulcast_layers: Vec::new(),
             source: TrackSource::Unknown,
+            encryption: None,
+            degradation_preference: None,
+            content_hint: None,
+            scalability_mode: None,
+            backup_codec: Some(VideoCodec::VP8),
+            stereo: false,
+        }
+    }
+}
+
+/// Bitrate allocation of a published track, see LocalTrackPublication::set_encoding_parameters
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrackEncodingParameters {
+    // Cap of the bitrate of each encoding (bps), None restores the bitrates of the publish options
+    pub max_bitrate: Option<u64>,
+    // Share of the uplink given to the track relative to the other tracks when the
+    // bandwidth is constrained, e.g. High for a screenshare over a Low camera
+    pub priority: Priority,
+}
+
+impl Default for TrackEncodingParameters {
+    fn default() -> Self {
+        Self {
+            max_bitrate: None,
+            priority: Priority::Low,
         }
     }
 }
@@ -129,7 +303,10 @@ pub fn compute_video_encodings(
     options: &TrackPublishOptions,
 ) -> Vec<RtpEncodingParameters> {
     let screenshare = options.source == TrackSource::Screenshare;
-    let encoding = compute_appropriate_encoding(screenshare, width, height);
+    let encoding = options
+        .video_encoding
+        .clone()
+        .unwrap_or_else(|| compute_appropriate_encoding(screenshare, width, height));
 
     let initial_preset = VideoPreset {
         width,
@@ -140,10 +317,30 @@ pub fn compute_video_encodings(
         },
     };
 
+    if options.video_codec.is_svc() {
+        let scalability_mode = options
+            .scalability_mode
+            .clone()
+            .unwrap_or_else(|| DEFAULT_SCALABILITY_MODE.to_owned());
+
+        return vec![RtpEncodingParameters {
+            max_bitrate: Some(initial_preset.encoding.max_bitrate),
+            max_framerate: Some(initial_preset.encoding.max_framerate),
+            scalability_mode: Some(scalability_mode),
+            ..Default::default()
+        }];
+    }
+
     if !options.simulcast {
         return into_rtp_encodings(width, height, &[initial_preset]);
     }
 
+    if !options.simulcast_layers.is_empty() {
+        let mut presets = custom_simulcast_presets(&initial_preset, &options.simulcast_layers);
+        presets.push(initial_preset);
+        return into_rtp_encodings(width, height, &presets);
+    }
+
     let mut simulcast_presets = compute_default_simulcast_presets(screenshare, &initial_preset);
 
     let mid_preset = simulcast_presets.pop();
@@ -219,6 +416,21 @@ pub fn compute_default_simulcast_presets(
     video43::DEFAULT_SIMULCAST_PRESETS.to_owned()
 }
 
+/// Keep the two biggest layers smaller than the initial preset, in ascending order
+fn custom_simulcast_presets(initial: &VideoPreset, layers: &[VideoPreset]) -> Vec<VideoPreset> {
+    let size = u32::max(initial.width, initial.height);
+    let mut presets: Vec<VideoPreset> = layers
+        .iter()
+        .filter(|preset| u32::max(preset.width, preset.height) < size)
+        .cloned()
+        .collect();
+
+    presets.sort_by_key(|preset| u32::max(preset.width, preset.height));
+    let skip = presets.len().saturating_sub(VIDEO_RIDS.len() - 1);
+    presets.drain(..skip);
+    presets
+}
+
 pub fn landscape_aspect_ratio(width: u32, height: u32) -> f32 {
     if width > height {
         width as f32 / height as f32
@@ -251,6 +463,41 @@ pub fn into_rtp_encodings(
     encodings
 }
 
+/// Number of spatial layers of a scalability mode (e.g. 3 for L3T3_KEY)
+fn spatial_layers(scalability_mode: &str) -> u32 {
+    scalability_mode
+        .strip_prefix(['L', 'S'])
+        .and_then(|mode| mode.split('T').next())
+        .and_then(|spatial| spatial.parse().ok())
+        .unwrap_or(1)
+        .clamp(1, 3)
+}
+
+fn svc_video_layers(
+    width: u32,
+    height: u32,
+    encoding: &RtpEncodingParameters,
+    scalability_mode: &str,
+) -> Vec<proto::VideoLayer> {
+    let qualities = [
+        proto::VideoQuality::High,
+        proto::VideoQuality::Medium,
+        proto::VideoQuality::Low,
+    ];
+
+    (0..spatial_layers(scalability_mode))
+        .map(|i| proto::VideoLayer {
+            quality: qualities[i as usize] as i32,
+            width: (width as f64 / 2f64.powi(i as i32)).ceil() as u32,
+            height: (height as f64 / 2f64.powi(i as i32)).ceil() as u32,
+            bitrate: encoding.max_bitrate.map_or(0, |bitrate| {
+                (bitrate as f64 / 3f64.powi(i as i32)).ceil() as u32
+            }),
+            ssrc: 0,
+        })
+        .collect()
+}
+
 pub fn video_quality_for_rid(rid: &str) -> Option<proto::VideoQuality> {
     match rid {
         "f" => Some(proto::VideoQuality::High),
@@ -275,6 +522,12 @@ pub fn video_layers_from_encodings(
         }];
     }
 
+    if let [encoding] = encodings {
+        if let Some(scalability_mode) = encoding.scalability_mode.as_ref() {
+            return svc_video_layers(width, height, encoding, scalability_mode);
+        }
+    }
+
     let mut layers = Vec::with_capacity(encodings.len());
     for encoding in encodings {
         let scale = encoding.scale_resolution_down_by.unwrap_or(1.0);
@@ -293,6 +546,7 @@ pub fn video_layers_from_encodings(
 }
 
 const VIDEO_RIDS: &[char] = &['q', 'h', 'f'];
+const DEFAULT_SCALABILITY_MODE: &str = "L3T3_KEY";
 
 pub mod audio {
     use super::AudioPreset;
@@ -380,3 +634,54 @@ pub mod screenshare {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_simulcast_layers() {
+        let options = TrackPublishOptions {
+            simulcast_layers: vec![video::H720, video::H90, video::H360, video::H180],
+            ..Default::default()
+        };
+
+        let encodings = compute_video_encodings(1280, 720, &options);
+        let rids: Vec<&str> = encodings.iter().map(|e| e.rid.as_str()).collect();
+        assert_eq!(rids, vec!["q", "h", "f"]);
+        assert_eq!(
+            encodings[0].max_bitrate,
+            Some(video::H180.encoding.max_bitrate)
+        );
+        assert_eq!(
+            encodings[1].max_bitrate,
+            Some(video::H360.encoding.max_bitrate)
+        );
+        assert_eq!(encodings[0].scale_resolution_down_by, Some(4.0));
+
+        let options = TrackPublishOptions {
+            simulcast: false,
+            ..options
+        };
+        assert_eq!(compute_video_encodings(1280, 720, &options).len(), 1);
+    }
+
+    #[test]
+    fn test_svc_encodings() {
+        let options = TrackPublishOptions {
+            video_codec: VideoCodec::AV1,
+            ..Default::default()
+        };
+
+        let encodings = compute_video_encodings(1280, 720, &options);
+        assert_eq!(encodings.len(), 1);
+        assert_eq!(encodings[0].scalability_mode.as_deref(), Some("L3T3_KEY"));
+
+        let layers = video_layers_from_encodings(1280, 720, &encodings);
+        let sizes: Vec<(u32, u32)> = layers.iter().map(|l| (l.width, l.height)).collect();
+        assert_eq!(sizes, vec![(1280, 720), (640, 360), (320, 180)]);
+
+        assert_eq!(spatial_layers("L1T3"), 1);
+        assert_eq!(spatial_layers("S2T3"), 2);
+    }
+}
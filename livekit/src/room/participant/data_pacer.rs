@@ -0,0 +1,113 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Pacing<T> {
+    /// The rate allows sending the packet right away
+    SendNow(T),
+    /// The packet must be sent at this instant with take_due, unless a newer packet of
+    /// the same topic replaces it before
+    Schedule(Instant),
+    /// Replaced the packet already waiting for the next slot of the topic
+    Coalesced,
+}
+
+struct TopicPacing<T> {
+    last_sent: Instant,
+    pending: Option<T>,
+}
+
+/// Limits the rate of the packets of each topic, keeping only the latest packet
+/// when a topic publishes faster than its rate
+pub struct DataPacer<T> {
+    topics: HashMap<String, TopicPacing<T>>,
+}
+
+impl<T> Default for DataPacer<T> {
+    fn default() -> Self {
+        Self {
+            topics: HashMap::new(),
+        }
+    }
+}
+
+impl<T> DataPacer<T> {
+    pub fn offer(&mut self, topic: &str, packet: T, interval: Duration, now: Instant) -> Pacing<T> {
+        let Some(pacing) = self.topics.get_mut(topic) else {
+            self.topics.insert(
+                topic.to_owned(),
+                TopicPacing {
+                    last_sent: now,
+                    pending: None,
+                },
+            );
+            return Pacing::SendNow(packet);
+        };
+
+        if pacing.pending.replace(packet).is_some() {
+            return Pacing::Coalesced;
+        }
+
+        let next = pacing.last_sent + interval;
+        if now >= next {
+            pacing.last_sent = now;
+            return Pacing::SendNow(pacing.pending.take().unwrap());
+        }
+        Pacing::Schedule(next)
+    }
+
+    /// Latest packet of the topic, to send at the scheduled instant
+    pub fn take_due(&mut self, topic: &str, now: Instant) -> Option<T> {
+        let pacing = self.topics.get_mut(topic)?;
+        let packet = pacing.pending.take()?;
+        pacing.last_sent = now;
+        Some(packet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coalescing() {
+        let mut pacer = DataPacer::default();
+        let interval = Duration::from_millis(100);
+        let start = Instant::now();
+
+        assert_eq!(
+            pacer.offer("cursor", 1, interval, start),
+            Pacing::SendNow(1)
+        );
+        assert_eq!(pacer.offer("game", 1, interval, start), Pacing::SendNow(1));
+
+        let at = start + Duration::from_millis(10);
+        assert_eq!(
+            pacer.offer("cursor", 2, interval, at),
+            Pacing::Schedule(start + interval)
+        );
+        assert_eq!(pacer.offer("cursor", 3, interval, at), Pacing::Coalesced);
+        assert_eq!(pacer.take_due("cursor", start + interval), Some(3));
+        assert_eq!(pacer.take_due("cursor", start + interval), None);
+
+        let later = start + interval * 3;
+        assert_eq!(
+            pacer.offer("cursor", 4, interval, later),
+            Pacing::SendNow(4)
+        );
+    }
+}
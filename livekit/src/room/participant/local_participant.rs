@@ -12,35 +12,90 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::data_pacer::{DataPacer, Pacing};
 use super::ConnectionQuality;
+use super::ParticipantEvent;
 use super::ParticipantInner;
 use crate::e2ee::EncryptionType;
 use crate::options;
 use crate::options::compute_video_encodings;
 use crate::options::video_layers_from_encodings;
+use crate::options::DataPublishOptions;
 use crate::options::TrackPublishOptions;
+use crate::options::VideoCodec;
 use crate::prelude::*;
+use crate::room::data_compression;
+use crate::room::data_stream::{
+    split_text, ByteStreamInfo, FlowControl, OutgoingStreams, StreamByteOptions, StreamResult,
+    StreamTextOptions, TextStreamInfo, STREAM_CHUNK_SIZE, STREAM_FLOW_CONTROL_ATTRIBUTE,
+    STREAM_FLOW_CONTROL_TOPIC,
+};
+use crate::room::rpc::{
+    PendingResponse, PerformRpcData, RpcError, RpcErrorCode, RpcHandler, RpcHandlerFuture,
+    RpcInvocationData, RpcState, MAX_PAYLOAD_BYTES, MAX_ROUND_TRIP_LATENCY, RPC_VERSION,
+};
+use crate::room::transcription::Transcription;
 use crate::rtc_engine::RtcEngine;
 use crate::DataPacketKind;
 use livekit_protocol as proto;
 use livekit_webrtc::rtp_parameters::RtpEncodingParameters;
+use livekit_webrtc::rtp_transceiver::RtpTransceiver;
 use parking_lot::Mutex;
 use std::collections::HashMap;
 use std::fmt::Debug;
-use std::sync::Arc;
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::sync::{mpsc, oneshot, Notify};
+
+const METADATA_UPDATE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Above this amount of buffered data, the streams wait for their chunks to be sent
+const STREAM_HIGH_WATER_MARK: u64 = 1024 * 1024;
+
+// Lowest max_rate of a data topic (one packet per hour), the slot interval must fit an Instant
+const MIN_DATA_RATE: f64 = 1.0 / 3600.0;
 
 type LocalTrackPublishedHandler = Box<dyn Fn(LocalParticipant, LocalTrackPublication) + Send>;
 type LocalTrackUnpublishedHandler = Box<dyn Fn(LocalParticipant, LocalTrackPublication) + Send>;
+type RemoteParticipantsProvider = Box<dyn Fn() -> Vec<RemoteParticipant> + Send>;
 
 #[derive(Default)]
 struct LocalEvents {
     local_track_published: Mutex<Option<LocalTrackPublishedHandler>>,
     local_track_unpublished: Mutex<Option<LocalTrackUnpublishedHandler>>,
+    remote_participants: Mutex<Option<RemoteParticipantsProvider>>,
+}
+
+/// Allows a remote participant to subscribe to all or some of the local tracks
+#[derive(Debug, Clone)]
+pub struct TrackPermission {
+    pub participant_identity: ParticipantIdentity,
+    pub all_tracks: bool,
+    pub track_sids: Vec<TrackSid>,
+}
+
+impl From<TrackPermission> for proto::TrackPermission {
+    fn from(permission: TrackPermission) -> Self {
+        Self {
+            participant_identity: permission.participant_identity.into(),
+            all_tracks: permission.all_tracks,
+            track_sids: permission.track_sids.into_iter().map(Into::into).collect(),
+            ..Default::default()
+        }
+    }
 }
 
 struct LocalInfo {
     events: LocalEvents,
     encryption_type: EncryptionType,
+    info_updated: Notify, // Notified each time the server updates the ParticipantInfo
+    rpc: Mutex<RpcState>,
+    data_pacer: Mutex<DataPacer<proto::DataPacket>>,
+    outgoing_streams: Mutex<OutgoingStreams>,
 }
 
 #[derive(Clone)]
@@ -66,13 +121,19 @@ impl LocalParticipant {
         identity: ParticipantIdentity,
         name: String,
         metadata: String,
+        attributes: HashMap<String, String>,
+        kind: ParticipantKind,
         encryption_type: EncryptionType,
     ) -> Self {
         Self {
-            inner: super::new_inner(rtc_engine, sid, identity, name, metadata),
+            inner: super::new_inner(rtc_engine, sid, identity, name, metadata, attributes, kind),
             local: Arc::new(LocalInfo {
                 events: LocalEvents::default(),
                 encryption_type,
+                info_updated: Notify::new(),
+                rpc: Default::default(),
+                data_pacer: Default::default(),
+                outgoing_streams: Default::default(),
             }),
         }
     }
@@ -82,7 +143,19 @@ impl LocalParticipant {
     }
 
     pub(crate) fn update_info(&self, info: proto::ParticipantInfo) {
-        super::update_info(&self.inner, &Participant::Local(self.clone()), info);
+        super::update_info(&self.inner, &Participant::Local(self.clone()), info.clone());
+
+        for track in info.tracks {
+            let Ok(sid) = track.sid.clone().try_into() else {
+                continue;
+            };
+
+            if let Some(publication) = self.get_track_publication(&sid) {
+                publication.update_info(track);
+            }
+        }
+
+        self.local.info_updated.notify_waiters();
     }
 
     pub(crate) fn set_speaking(&self, speaking: bool) {
@@ -104,6 +177,15 @@ impl LocalParticipant {
         *self.local.events.local_track_published.lock() = Some(Box::new(handler));
     }
 
+    /// Participants in the room, used to validate the destinations of the data and to know
+    /// whether they read compressed data
+    pub(crate) fn set_remote_participants_provider(
+        &self,
+        provider: impl Fn() -> Vec<RemoteParticipant> + Send + 'static,
+    ) {
+        *self.local.events.remote_participants.lock() = Some(Box::new(provider));
+    }
+
     pub(crate) fn on_local_track_unpublished(
         &self,
         handler: impl Fn(LocalParticipant, LocalTrackPublication) + Send + 'static,
@@ -125,6 +207,34 @@ impl LocalParticipant {
         super::on_track_unmuted(&self.inner, handler)
     }
 
+    pub(crate) fn on_attributes_changed(
+        &self,
+        handler: impl Fn(Participant, Vec<String>) + Send + 'static,
+    ) {
+        super::on_attributes_changed(&self.inner, handler)
+    }
+
+    pub(crate) fn on_permissions_changed(
+        &self,
+        handler: impl Fn(Participant, ParticipantPermission, ParticipantPermission) + Send + 'static,
+    ) {
+        super::on_permissions_changed(&self.inner, handler)
+    }
+
+    pub(crate) fn on_metadata_changed(
+        &self,
+        handler: impl Fn(Participant, String, String) + Send + 'static,
+    ) {
+        super::on_metadata_changed(&self.inner, handler)
+    }
+
+    pub(crate) fn on_name_changed(
+        &self,
+        handler: impl Fn(Participant, String, String) + Send + 'static,
+    ) {
+        super::on_name_changed(&self.inner, handler)
+    }
+
     pub(crate) fn add_publication(&self, publication: TrackPublication) {
         super::add_publication(&self.inner, &Participant::Local(self.clone()), publication);
     }
@@ -154,6 +264,45 @@ impl LocalParticipant {
         track: LocalTrack,
         options: TrackPublishOptions,
     ) -> RoomResult<LocalTrackPublication> {
+        let publication = self.create_publication(track, options).await?;
+        self.inner.rtc_engine.publisher_negotiation_needed();
+        Ok(publication)
+    }
+
+    /// Publish multiple tracks concurrently. The publisher is negotiated once, after the
+    /// senders of all the tracks are created.
+    /// The results are in the same order as the given tracks.
+    pub async fn publish_tracks(
+        &self,
+        tracks: Vec<(LocalTrack, TrackPublishOptions)>,
+    ) -> Vec<RoomResult<LocalTrackPublication>> {
+        let publish = tracks
+            .into_iter()
+            .map(|(track, options)| self.create_publication(track, options));
+
+        let results = futures_util::future::join_all(publish).await;
+        if results.iter().any(Result::is_ok) {
+            self.inner.rtc_engine.publisher_negotiation_needed();
+        }
+        results
+    }
+
+    /// Add the track on the server and create its sender, without negotiating the publisher
+    async fn create_publication(
+        &self,
+        track: LocalTrack,
+        mut options: TrackPublishOptions,
+    ) -> RoomResult<LocalTrackPublication> {
+        let encryption_type = match self.local.encryption_type {
+            EncryptionType::None => EncryptionType::None,
+            room_encryption => options.encryption.unwrap_or(room_encryption),
+        };
+
+        // The frame cryptors are only attached to the primary sender
+        if encryption_type != EncryptionType::None && options.backup_codec.take().is_some() {
+            log::debug!("backup codecs aren't published for encrypted tracks");
+        }
+
         let mut req = proto::AddTrackRequest {
             cid: track.rtc_track().id(),
             name: track.name(),
@@ -162,7 +311,7 @@ impl LocalParticipant {
             source: proto::TrackSource::from(options.source) as i32,
             disable_dtx: !options.dtx,
             disable_red: !options.red,
-            encryption: proto::encryption::Type::from(self.local.encryption_type) as i32,
+            encryption: proto::encryption::Type::from(encryption_type) as i32,
             ..Default::default()
         };
 
@@ -177,13 +326,36 @@ impl LocalParticipant {
 
                 encodings = compute_video_encodings(req.width, req.height, &options);
                 req.layers = video_layers_from_encodings(req.width, req.height, &encodings);
+                req.simulcast_codecs = vec![proto::SimulcastCodec {
+                    codec: options.video_codec.as_str().to_owned(),
+                    cid: track.rtc_track().id(),
+                }];
+
+                // The backup codec is only published once a subscriber needs it
+                if let Some(backup_codec) = options.effective_backup_codec() {
+                    req.simulcast_codecs.push(proto::SimulcastCodec {
+                        codec: backup_codec.as_str().to_owned(),
+                        ..Default::default()
+                    });
+                }
+
+                if let Some(hint) = options.effective_content_hint() {
+                    video_track.rtc_track().set_content_hint(hint);
+                }
             }
             LocalTrack::Audio(_audio_track) => {
+                req.stereo = options.stereo;
+
                 // Setup audio encoding
+                let default_preset = if options.stereo {
+                    &options::audio::MUSIC_STEREO
+                } else {
+                    &options::audio::SPEECH
+                };
                 let audio_encoding = options
                     .audio_encoding
                     .as_ref()
-                    .unwrap_or(&options::audio::SPEECH.encoding);
+                    .unwrap_or(&default_preset.encoding);
 
                 encodings.push(RtpEncodingParameters {
                     max_bitrate: Some(audio_encoding.max_bitrate),
@@ -202,11 +374,35 @@ impl LocalParticipant {
             .create_sender(track.clone(), options.clone(), encodings)
             .await?;
 
-        track.set_transceiver(Some(transceiver));
+        if let (LocalTrack::Video(_), Some(preference)) =
+            (&track, options.effective_degradation_preference())
+        {
+            if let Err(err) = transceiver.sender().set_degradation_preference(preference) {
+                log::warn!("failed to set the degradation preference: {:?}", err);
+            }
+        }
 
-        self.inner.rtc_engine.publisher_negotiation_needed();
+        track.set_transceiver(Some(transceiver));
 
         publication.update_publish_options(options);
+        publication.on_mute_update_needed({
+            let rtc_engine = self.inner.rtc_engine.clone();
+            let inner = Arc::downgrade(&self.inner);
+            move |publication, muted| {
+                Self::send_mute_request(rtc_engine.clone(), inner.clone(), publication, muted)
+            }
+        });
+        publication.on_republish_needed({
+            let inner = Arc::downgrade(&self.inner);
+            let local = Arc::downgrade(&self.local);
+            move |publication, options| {
+                let participant = inner
+                    .upgrade()
+                    .zip(local.upgrade())
+                    .map(|(inner, local)| LocalParticipant { inner, local });
+                Self::republish_track(participant, publication, options)
+            }
+        });
         self.add_publication(TrackPublication::Local(publication.clone()));
 
         if let Some(local_track_published) = self.local.events.local_track_published.lock().as_ref()
@@ -219,6 +415,191 @@ impl LocalParticipant {
         Ok(publication)
     }
 
+    /// The server muted one of our tracks (e.g. a moderator)
+    pub(crate) fn handle_remote_mute(
+        &self,
+        sid: TrackSid,
+        muted: bool,
+    ) -> Option<LocalTrackPublication> {
+        let publication = self.get_track_publication(&sid)?;
+        log::info!("track {:?} muted by the server: {}", sid, muted);
+        publication.handle_server_mute(muted);
+        Some(publication)
+    }
+
+    /// Dynacast, pause the simulcast layers no subscriber is receiving
+    pub(crate) fn handle_subscribed_quality_update(&self, update: proto::SubscribedQualityUpdate) {
+        let Ok(sid) = update.track_sid.clone().try_into() else {
+            return;
+        };
+
+        let Some(publication) = self.get_track_publication(&sid) else {
+            log::warn!("received a quality update for an unknown track: {:?}", sid);
+            return;
+        };
+
+        let Some(track) = publication.track() else {
+            return;
+        };
+
+        let Some(transceiver) = track.transceiver() else {
+            return;
+        };
+
+        // Without a codec specific update, use the qualities of the primary codec
+        if update.subscribed_codecs.is_empty() {
+            Self::update_encodings(&sid, &transceiver, &update.subscribed_qualities);
+            return;
+        }
+
+        let options = publication.publish_options();
+        let backup_codec = options.effective_backup_codec();
+        for subscribed in update.subscribed_codecs {
+            let codec = subscribed.codec.to_lowercase();
+            if codec == options.video_codec.as_str() {
+                Self::update_encodings(&sid, &transceiver, &subscribed.qualities);
+            } else if backup_codec.map(|c| c.as_str()) == Some(codec.as_str()) {
+                if let Some(backup) = publication.backup_track() {
+                    if let Some(transceiver) = backup.transceiver() {
+                        Self::update_encodings(&sid, &transceiver, &subscribed.qualities);
+                    }
+                } else if subscribed.qualities.iter().any(|q| q.enabled) {
+                    self.publish_backup_codec(publication.clone(), backup_codec.unwrap());
+                }
+            } else {
+                log::warn!(
+                    "server requested an unpublished codec for {:?}: {}",
+                    sid,
+                    codec
+                );
+            }
+        }
+    }
+
+    /// Fails if the participant isn't allowed to publish the track anymore when unmuting
+    fn send_mute_request(
+        rtc_engine: Arc<RtcEngine>,
+        inner: Weak<ParticipantInner>,
+        publication: LocalTrackPublication,
+        muted: bool,
+    ) -> RoomResult<Pin<Box<dyn Future<Output = RoomResult<()>> + Send>>> {
+        if !muted {
+            let permission = inner
+                .upgrade()
+                .and_then(|inner| inner.info.read().permission.clone());
+
+            if let Some(permission) = permission {
+                if !permission.can_publish_source(publication.source()) {
+                    return Err(RoomError::PermissionDenied(format!(
+                        "not allowed to publish {:?}",
+                        publication.source()
+                    )));
+                }
+            }
+        }
+
+        Ok(Box::pin(async move {
+            rtc_engine
+                .mute_track(proto::MuteTrackRequest {
+                    sid: publication.sid().into(),
+                    muted,
+                })
+                .await?;
+            Ok(())
+        }))
+    }
+
+    /// A subscriber can't decode the primary codec, publish the backup codec
+    /// of the track using a new sender
+    fn publish_backup_codec(&self, publication: LocalTrackPublication, codec: VideoCodec) {
+        let Some(LocalTrack::Video(video_track)) = publication.track() else {
+            return;
+        };
+
+        // Set before sending the request so concurrent updates don't publish it twice
+        let backup = video_track.create_backup_track();
+        publication.set_backup_track(Some(backup.clone()));
+
+        let participant = self.clone();
+        tokio::spawn(async move {
+            let mut options = publication.publish_options();
+            options.video_codec = codec;
+            options.scalability_mode = None;
+
+            let resolution = backup.rtc_source().video_resolution();
+            let encodings = compute_video_encodings(resolution.width, resolution.height, &options);
+            let req = proto::AddTrackRequest {
+                cid: backup.rtc_track().id(),
+                sid: publication.sid().into(),
+                name: publication.name(),
+                r#type: proto::TrackType::Video as i32,
+                width: resolution.width,
+                height: resolution.height,
+                muted: publication.is_muted(),
+                source: proto::TrackSource::from(options.source) as i32,
+                layers: video_layers_from_encodings(
+                    resolution.width,
+                    resolution.height,
+                    &encodings,
+                ),
+                simulcast_codecs: vec![proto::SimulcastCodec {
+                    codec: codec.as_str().to_owned(),
+                    cid: backup.rtc_track().id(),
+                }],
+                ..Default::default()
+            };
+
+            let rtc_engine = participant.inner.rtc_engine.clone();
+            let res = async {
+                rtc_engine.add_track(req).await?;
+                rtc_engine
+                    .create_sender(LocalTrack::Video(backup.clone()), options, encodings)
+                    .await
+            }
+            .await;
+
+            match res {
+                Ok(transceiver) => {
+                    log::debug!(
+                        "published backup codec {:?} of {:?}",
+                        codec,
+                        publication.sid()
+                    );
+                    backup.set_transceiver(Some(transceiver));
+                    rtc_engine.publisher_negotiation_needed();
+                }
+                Err(err) => {
+                    log::error!("failed to publish the backup codec: {:?}", err);
+                    publication.set_backup_track(None);
+                }
+            }
+        });
+    }
+
+    fn update_encodings(
+        sid: &TrackSid,
+        transceiver: &RtpTransceiver,
+        qualities: &[proto::SubscribedQuality],
+    ) {
+        log::debug!("updating layers of {:?}: {:?}", sid, qualities);
+        let res = transceiver.sender().set_encodings_active(|rid| {
+            // Non simulcast tracks have a single encoding without rid
+            let quality = options::video_quality_for_rid(rid).unwrap_or(proto::VideoQuality::High);
+            qualities
+                .iter()
+                .find(|q| q.quality() == quality)
+                .map(|q| q.enabled)
+        });
+
+        if let Err(err) = res {
+            log::error!(
+                "failed to update the simulcast layers of {:?}: {:?}",
+                sid,
+                err
+            );
+        }
+    }
+
     pub async fn unpublish_track(
         &self,
         sid: &TrackSid,
@@ -232,6 +613,21 @@ impl LocalParticipant {
             self.inner.rtc_engine.remove_track(sender).await?;
             track.set_transceiver(None);
 
+            if let Some(backup) = publication.backup_track() {
+                if let Some(transceiver) = backup.transceiver() {
+                    self.inner
+                        .rtc_engine
+                        .remove_track(transceiver.sender())
+                        .await?;
+                    backup.set_transceiver(None);
+                }
+                publication.set_backup_track(None);
+            }
+            publication.on_mute_update_needed(|_, _| Ok(Box::pin(async { Ok(()) })));
+            publication.on_republish_needed(|_, _| {
+                Box::pin(async { Err(RoomError::Internal("track isn't published".to_owned())) })
+            });
+
             if let Some(local_track_unpublished) =
                 self.local.events.local_track_unpublished.lock().as_ref()
             {
@@ -247,6 +643,37 @@ impl LocalParticipant {
         }
     }
 
+    // Not an async fn, publish_track and this future would otherwise depend on each other
+    fn republish_track(
+        participant: Option<LocalParticipant>,
+        publication: LocalTrackPublication,
+        options: TrackPublishOptions,
+    ) -> Pin<Box<dyn Future<Output = RoomResult<LocalTrackPublication>> + Send>> {
+        Box::pin(async move {
+            let Some(participant) = participant else {
+                return Err(RoomError::Internal("room closed".to_owned()));
+            };
+
+            let encrypted = options
+                .encryption
+                .is_some_and(|encryption| encryption != EncryptionType::None);
+            if encrypted && participant.local.encryption_type == EncryptionType::None {
+                return Err(RoomError::Internal(
+                    "e2ee isn't enabled on the room".to_owned(),
+                ));
+            }
+
+            let Some(track) = publication.track() else {
+                return Err(RoomError::Internal("publication has no track".to_owned()));
+            };
+
+            participant.unpublish_track(&publication.sid()).await?;
+            participant.publish_track(track, options).await
+        })
+    }
+
+    /// Returns once the data is queued, fails if the send buffer of the data channel is full
+    #[allow(deprecated)]
     pub async fn publish_data(
         &self,
         data: Vec<u8>,
@@ -260,15 +687,717 @@ impl LocalParticipant {
                 destination_sids: destination_sids.to_owned(),
                 ..Default::default()
             })),
+            ..Default::default()
         };
 
         self.inner
             .rtc_engine
             .publish_data(&data, kind)
             .await
+            .map(|_| ())
             .map_err(Into::into)
     }
 
+    /// With `reliable`, resolves once the data left the send buffer of the data channel.
+    /// Use data_buffered_amount and RoomEvent::DataBufferedAmountLow to apply backpressure
+    #[allow(deprecated)]
+    pub async fn publish_data_with_options(
+        &self,
+        data: Vec<u8>,
+        options: DataPublishOptions,
+    ) -> RoomResult<()> {
+        let kind = if options.reliable {
+            DataPacketKind::Reliable
+        } else {
+            DataPacketKind::Lossy
+        };
+
+        let remote_participants = match self.local.events.remote_participants.lock().as_ref() {
+            Some(provider) => provider(),
+            None => Vec::new(),
+        };
+
+        if !options.destination_identities.is_empty() {
+            let unknown: Vec<_> = options
+                .destination_identities
+                .iter()
+                .filter(|identity| {
+                    !remote_participants
+                        .iter()
+                        .any(|p| p.identity() == **identity)
+                })
+                .cloned()
+                .collect();
+
+            // Nobody would receive the data sent to these participants
+            if !unknown.is_empty() {
+                return Err(RoomError::UnknownDestinations(unknown));
+            }
+        }
+
+        // Compressed when the local participant and every receiver advertise that they read
+        // compressed data (see RoomOptions::data_compression), lossy packets are small
+        let mut topic = options.topic;
+        let mut data = data;
+        if options.reliable && data_compression::is_supported(&self.attributes()) {
+            let broadcast =
+                options.destination_identities.is_empty() && options.destination_sids.is_empty();
+            let mut receivers = remote_participants.iter().filter(|p| {
+                broadcast
+                    || options.destination_identities.contains(&p.identity())
+                    || options.destination_sids.contains(&p.sid().to_string())
+            });
+            let supported = receivers.clone().next().is_some()
+                && receivers.all(|p| data_compression::is_supported(&p.attributes()));
+            if supported {
+                if let Some((compressed_topic, compressed)) =
+                    data_compression::compress(topic.as_deref(), &data)
+                {
+                    topic = compressed_topic;
+                    data = compressed;
+                }
+            }
+        }
+
+        let pacing_topic = topic.clone().unwrap_or_default();
+        let data = proto::DataPacket {
+            kind: kind as i32,
+            destination_identities: options
+                .destination_identities
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            value: Some(proto::data_packet::Value::User(proto::UserPacket {
+                payload: data,
+                destination_sids: options.destination_sids,
+                topic,
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+
+        let data = match options
+            .max_rate
+            .filter(|rate| *rate > 0.0 && !options.reliable)
+        {
+            Some(rate) => {
+                let interval = Duration::from_secs_f64(1.0 / rate.max(MIN_DATA_RATE));
+                let pacing = self.local.data_pacer.lock().offer(
+                    &pacing_topic,
+                    data,
+                    interval,
+                    Instant::now(),
+                );
+
+                match pacing {
+                    Pacing::SendNow(data) => data,
+                    Pacing::Schedule(at) => {
+                        self.publish_paced_data(pacing_topic, at);
+                        return Ok(());
+                    }
+                    Pacing::Coalesced => return Ok(()),
+                }
+            }
+            None => data,
+        };
+
+        let receipt = self.inner.rtc_engine.publish_data(&data, kind).await?;
+        if options.reliable {
+            receipt.wait().await?;
+        }
+        Ok(())
+    }
+
+    /// Send the latest packet of a rate limited topic at its next slot
+    fn publish_paced_data(&self, topic: String, at: Instant) {
+        let participant = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep_until(at.into()).await;
+            let data = participant
+                .local
+                .data_pacer
+                .lock()
+                .take_due(&topic, Instant::now());
+            if let Some(data) = data {
+                if let Err(err) = participant
+                    .inner
+                    .rtc_engine
+                    .publish_data(&data, DataPacketKind::Lossy)
+                    .await
+                {
+                    log::warn!("failed to publish the data of topic {}: {}", topic, err);
+                }
+            }
+        });
+    }
+
+    /// Bytes published on the data channel of this kind that weren't sent yet
+    pub async fn data_buffered_amount(&self, kind: DataPacketKind) -> u64 {
+        self.inner.rtc_engine.data_buffered_amount(kind).await
+    }
+
+    /// Publish the transcribed speech of a track to everyone in the room
+    #[allow(deprecated)]
+    pub async fn publish_transcription(&self, transcription: Transcription) -> RoomResult<()> {
+        let data = proto::DataPacket {
+            kind: DataPacketKind::Reliable as i32,
+            value: Some(proto::data_packet::Value::Transcription(
+                transcription.into(),
+            )),
+            ..Default::default()
+        };
+
+        self.inner
+            .rtc_engine
+            .publish_data(&data, DataPacketKind::Reliable)
+            .await
+            .map(|_| ())
+            .map_err(Into::into)
+    }
+
+    /// Send a DTMF digit to the SIP participants of the room (e.g. for IVR navigation).
+    /// The code is the RFC 4733 event code, see dtmf::dtmf_code
+    #[allow(deprecated)]
+    pub async fn publish_dtmf(&self, code: u32, digit: String) -> RoomResult<()> {
+        let data = proto::DataPacket {
+            kind: DataPacketKind::Reliable as i32,
+            value: Some(proto::data_packet::Value::SipDtmf(proto::SipDtmf {
+                code,
+                digit,
+            })),
+            ..Default::default()
+        };
+
+        self.inner
+            .rtc_engine
+            .publish_data(&data, DataPacketKind::Reliable)
+            .await
+            .map(|_| ())
+            .map_err(Into::into)
+    }
+
+    /// Send a text to the text stream handlers of the topic, in chunks when it is long
+    pub async fn send_text(
+        &self,
+        text: &str,
+        options: StreamTextOptions,
+    ) -> StreamResult<TextStreamInfo> {
+        let text_header = proto::data_stream::TextHeader {
+            operation_type: proto::data_stream::OperationType::from(options.operation) as i32,
+            version: options.version,
+            reply_to_stream_id: options.reply_to_stream_id.unwrap_or_default(),
+            ..Default::default()
+        };
+        let mut header = proto::data_stream::Header {
+            stream_id: options
+                .stream_id
+                .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+            timestamp: unix_time_ms(),
+            topic: options.topic,
+            mime_type: "text/plain".to_owned(),
+            total_length: Some(text.len() as u64),
+            encryption_type: proto::encryption::Type::None as i32,
+            attributes: options.attributes,
+            content_header: Some(proto::data_stream::header::ContentHeader::TextHeader(
+                text_header.clone(),
+            )),
+        };
+        let info = TextStreamInfo::from_header(&header, &text_header);
+        let destinations = options.destination_identities;
+        header
+            .attributes
+            .insert(STREAM_FLOW_CONTROL_ATTRIBUTE.to_owned(), "1".to_owned());
+        let mut pause = self.local.outgoing_streams.lock().open(info.id.clone());
+
+        self.send_stream_packet(
+            proto::data_packet::Value::StreamHeader(header),
+            &destinations,
+        )
+        .await?;
+        for (index, chunk) in split_text(text, STREAM_CHUNK_SIZE).into_iter().enumerate() {
+            pause.resumed().await;
+            self.send_stream_chunk(
+                &info.id,
+                index as u64,
+                chunk.as_bytes().to_vec(),
+                &destinations,
+            )
+            .await?;
+        }
+        self.send_stream_trailer(&info.id, String::new(), &destinations)
+            .await?;
+        Ok(info)
+    }
+
+    /// Send the content of a reader to the byte stream handlers of the topic.
+    /// Resolves once the whole content was sent, the stream is aborted on read errors.
+    /// The sending is paused while a receiver of this SDK is too far behind
+    pub async fn stream_bytes<R: AsyncRead + Unpin>(
+        &self,
+        mut reader: R,
+        options: StreamByteOptions,
+    ) -> StreamResult<ByteStreamInfo> {
+        let name = options.name.unwrap_or_default();
+        let mut header = proto::data_stream::Header {
+            stream_id: uuid::Uuid::new_v4().to_string(),
+            timestamp: unix_time_ms(),
+            topic: options.topic,
+            mime_type: options
+                .mime_type
+                .unwrap_or_else(|| "application/octet-stream".to_owned()),
+            total_length: options.total_length,
+            encryption_type: proto::encryption::Type::None as i32,
+            attributes: options.attributes,
+            content_header: Some(proto::data_stream::header::ContentHeader::ByteHeader(
+                proto::data_stream::ByteHeader { name: name.clone() },
+            )),
+        };
+        let info = ByteStreamInfo::from_header(&header, name);
+        let destinations = options.destination_identities;
+        header
+            .attributes
+            .insert(STREAM_FLOW_CONTROL_ATTRIBUTE.to_owned(), "1".to_owned());
+        let mut pause = self.local.outgoing_streams.lock().open(info.id.clone());
+
+        self.send_stream_packet(
+            proto::data_packet::Value::StreamHeader(header),
+            &destinations,
+        )
+        .await?;
+
+        let mut index = 0;
+        loop {
+            let chunk = match read_chunk(&mut reader).await {
+                Ok(chunk) => chunk,
+                Err(err) => {
+                    self.send_stream_trailer(&info.id, err.to_string(), &destinations)
+                        .await?;
+                    return Err(err.into());
+                }
+            };
+
+            if chunk.is_empty() {
+                break;
+            }
+
+            pause.resumed().await;
+            self.send_stream_chunk(&info.id, index, chunk, &destinations)
+                .await?;
+            index += 1;
+        }
+
+        self.send_stream_trailer(&info.id, String::new(), &destinations)
+            .await?;
+        Ok(info)
+    }
+
+    /// Send a file to the byte stream handlers of the topic, the name and total length
+    /// default to the ones of the file
+    pub async fn send_file(
+        &self,
+        path: impl AsRef<Path>,
+        mut options: StreamByteOptions,
+    ) -> StreamResult<ByteStreamInfo> {
+        let path = path.as_ref();
+        let file = tokio::fs::File::open(path).await?;
+        if options.name.is_none() {
+            options.name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned());
+        }
+        if options.total_length.is_none() {
+            options.total_length = Some(file.metadata().await?.len());
+        }
+
+        self.stream_bytes(file, options).await
+    }
+
+    async fn send_stream_chunk(
+        &self,
+        stream_id: &str,
+        index: u64,
+        content: Vec<u8>,
+        destinations: &[ParticipantIdentity],
+    ) -> StreamResult<()> {
+        let chunk = proto::data_stream::Chunk {
+            stream_id: stream_id.to_owned(),
+            chunk_index: index,
+            content,
+            ..Default::default()
+        };
+        self.send_stream_packet(proto::data_packet::Value::StreamChunk(chunk), destinations)
+            .await
+    }
+
+    async fn send_stream_trailer(
+        &self,
+        stream_id: &str,
+        reason: String,
+        destinations: &[ParticipantIdentity],
+    ) -> StreamResult<()> {
+        let trailer = proto::data_stream::Trailer {
+            stream_id: stream_id.to_owned(),
+            reason,
+            ..Default::default()
+        };
+        self.send_stream_packet(
+            proto::data_packet::Value::StreamTrailer(trailer),
+            destinations,
+        )
+        .await
+    }
+
+    #[allow(deprecated)]
+    async fn send_stream_packet(
+        &self,
+        value: proto::data_packet::Value,
+        destinations: &[ParticipantIdentity],
+    ) -> StreamResult<()> {
+        let packet = proto::DataPacket {
+            kind: DataPacketKind::Reliable as i32,
+            destination_identities: destinations.iter().map(ToString::to_string).collect(),
+            value: Some(value),
+            ..Default::default()
+        };
+
+        let engine = &self.inner.rtc_engine;
+        let receipt = engine
+            .publish_data(&packet, DataPacketKind::Reliable)
+            .await?;
+        if engine.data_buffered_amount(DataPacketKind::Reliable).await > STREAM_HIGH_WATER_MARK {
+            receipt.wait().await?;
+        }
+        Ok(())
+    }
+
+    /// Call a method registered by another participant and wait for its response.
+    /// Fails with RecipientDisconnected if the destination leaves the room before responding
+    #[allow(deprecated)]
+    pub async fn perform_rpc(&self, data: PerformRpcData) -> Result<String, RpcError> {
+        if data.payload.len() > MAX_PAYLOAD_BYTES {
+            return Err(RpcError::built_in(
+                RpcErrorCode::RequestPayloadTooLarge,
+                None,
+            ));
+        }
+
+        let request_id = uuid::Uuid::new_v4().to_string();
+        let (ack_tx, ack_rx) = oneshot::channel();
+        let (response_tx, response_rx) = oneshot::channel();
+        {
+            let mut rpc = self.local.rpc.lock();
+            rpc.pending_acks.insert(request_id.clone(), ack_tx);
+            rpc.pending_responses.insert(
+                request_id.clone(),
+                PendingResponse {
+                    destination: data.destination_identity.clone().into(),
+                    tx: response_tx,
+                },
+            );
+        }
+
+        let response_timeout = data.response_timeout.saturating_sub(MAX_ROUND_TRIP_LATENCY);
+        let packet = proto::DataPacket {
+            kind: DataPacketKind::Reliable as i32,
+            destination_identities: vec![data.destination_identity],
+            value: Some(proto::data_packet::Value::RpcRequest(proto::RpcRequest {
+                id: request_id.clone(),
+                method: data.method,
+                payload: data.payload,
+                response_timeout_ms: response_timeout.as_millis() as u32,
+                version: RPC_VERSION,
+            })),
+            ..Default::default()
+        };
+
+        let result = match self
+            .inner
+            .rtc_engine
+            .publish_data(&packet, DataPacketKind::Reliable)
+            .await
+        {
+            Ok(_) => wait_rpc_response(ack_rx, response_rx, data.response_timeout).await,
+            Err(err) => Err(RpcError::built_in(
+                RpcErrorCode::SendFailed,
+                Some(err.to_string()),
+            )),
+        };
+
+        let mut rpc = self.local.rpc.lock();
+        rpc.pending_acks.remove(&request_id);
+        rpc.pending_responses.remove(&request_id);
+        result
+    }
+
+    /// Register a method that other participants can call with perform_rpc.
+    /// Errors returned by the handler are forwarded to the caller, replaces any handler
+    /// already registered for this method
+    pub fn register_rpc_method<F, Fut>(&self, method: String, handler: F)
+    where
+        F: Fn(RpcInvocationData) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<String, RpcError>> + Send + 'static,
+    {
+        let handler: RpcHandler = Arc::new(move |data| Box::pin(handler(data)) as RpcHandlerFuture);
+        self.local.rpc.lock().handlers.insert(method, handler);
+    }
+
+    pub fn unregister_rpc_method(&self, method: &str) {
+        self.local.rpc.lock().handlers.remove(method);
+    }
+
+    pub(crate) async fn handle_incoming_rpc_request(
+        &self,
+        caller_identity: ParticipantIdentity,
+        request: proto::RpcRequest,
+    ) {
+        // The ack is sent right away so the caller knows the request was received
+        self.publish_rpc_packet(
+            &caller_identity,
+            proto::data_packet::Value::RpcAck(proto::RpcAck {
+                request_id: request.id.clone(),
+            }),
+        )
+        .await;
+
+        let handler = self.local.rpc.lock().handlers.get(&request.method).cloned();
+        let result = if request.version != RPC_VERSION {
+            Err(RpcError::built_in(RpcErrorCode::UnsupportedVersion, None))
+        } else if let Some(handler) = handler {
+            let invocation = RpcInvocationData {
+                request_id: request.id.clone(),
+                caller_identity: caller_identity.clone(),
+                payload: request.payload,
+                response_timeout: Duration::from_millis(request.response_timeout_ms as u64),
+            };
+
+            // Run the handler in its own task so a panic is reported to the caller
+            match tokio::spawn(handler(invocation)).await {
+                Ok(Ok(payload)) if payload.len() > MAX_PAYLOAD_BYTES => Err(RpcError::built_in(
+                    RpcErrorCode::ResponsePayloadTooLarge,
+                    None,
+                )),
+                Ok(result) => result,
+                Err(err) => {
+                    log::error!("rpc handler for {} failed: {}", request.method, err);
+                    Err(RpcError::built_in(RpcErrorCode::ApplicationError, None))
+                }
+            }
+        } else {
+            Err(RpcError::built_in(RpcErrorCode::UnsupportedMethod, None))
+        };
+
+        let value = match result {
+            Ok(payload) => proto::rpc_response::Value::Payload(payload),
+            Err(err) => proto::rpc_response::Value::Error(err.to_proto()),
+        };
+
+        self.publish_rpc_packet(
+            &caller_identity,
+            proto::data_packet::Value::RpcResponse(proto::RpcResponse {
+                request_id: request.id,
+                value: Some(value),
+            }),
+        )
+        .await;
+    }
+
+    pub(crate) fn handle_incoming_rpc_ack(&self, request_id: String) {
+        if let Some(tx) = self.local.rpc.lock().pending_acks.remove(&request_id) {
+            let _ = tx.send(());
+        } else {
+            log::warn!("received an ack for an unknown rpc request: {}", request_id);
+        }
+    }
+
+    pub(crate) fn handle_incoming_rpc_response(&self, response: proto::RpcResponse) {
+        let mut rpc = self.local.rpc.lock();
+        let Some(pending) = rpc.pending_responses.remove(&response.request_id) else {
+            log::warn!(
+                "received a response for an unknown rpc request: {}",
+                response.request_id
+            );
+            return;
+        };
+
+        rpc.pending_acks.remove(&response.request_id);
+        let result = match response.value {
+            Some(proto::rpc_response::Value::Payload(payload)) => Ok(payload),
+            Some(proto::rpc_response::Value::Error(err)) => Err(RpcError::from_proto(err)),
+            None => Ok(String::new()),
+        };
+        let _ = pending.tx.send(result);
+    }
+
+    pub(crate) fn handle_participant_disconnected(&self, identity: &ParticipantIdentity) {
+        self.local.rpc.lock().participant_disconnected(identity);
+        self.local
+            .outgoing_streams
+            .lock()
+            .participant_disconnected(identity);
+    }
+
+    pub(crate) fn handle_stream_flow_control(&self, receiver: ParticipantIdentity, payload: &[u8]) {
+        match serde_json::from_slice::<FlowControl>(payload) {
+            Ok(flow) => self
+                .local
+                .outgoing_streams
+                .lock()
+                .handle_flow_control(receiver, flow),
+            Err(err) => log::warn!("invalid stream flow control: {}", err),
+        }
+    }
+
+    /// Ask the sender of an incoming stream to pause or resume it
+    #[allow(deprecated)]
+    pub(crate) async fn send_stream_flow_control(
+        &self,
+        sender: ParticipantIdentity,
+        flow: FlowControl,
+    ) {
+        let packet = proto::DataPacket {
+            kind: DataPacketKind::Reliable as i32,
+            destination_identities: vec![sender.to_string()],
+            value: Some(proto::data_packet::Value::User(proto::UserPacket {
+                payload: serde_json::to_vec(&flow).unwrap_or_default(),
+                topic: Some(STREAM_FLOW_CONTROL_TOPIC.to_owned()),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+
+        let publish = self
+            .inner
+            .rtc_engine
+            .publish_data(&packet, DataPacketKind::Reliable)
+            .await;
+        if let Err(err) = publish {
+            log::warn!("failed to send stream flow control: {}", err);
+        }
+    }
+
+    #[allow(deprecated)]
+    async fn publish_rpc_packet(
+        &self,
+        destination: &ParticipantIdentity,
+        value: proto::data_packet::Value,
+    ) {
+        let packet = proto::DataPacket {
+            kind: DataPacketKind::Reliable as i32,
+            destination_identities: vec![destination.to_string()],
+            value: Some(value),
+            ..Default::default()
+        };
+
+        if let Err(err) = self
+            .inner
+            .rtc_engine
+            .publish_data(&packet, DataPacketKind::Reliable)
+            .await
+        {
+            log::error!("failed to send rpc packet to {}: {}", destination, err);
+        }
+    }
+
+    /// Update the metadata of the local participant,
+    /// resolves once the server acknowledged the change
+    pub async fn set_metadata(&self, metadata: String) -> RoomResult<()> {
+        let update = proto::UpdateParticipantMetadata {
+            metadata: metadata.clone(),
+            name: self.name(),
+            ..Default::default()
+        };
+
+        self.update_metadata(update, move |p| p.metadata() == metadata)
+            .await
+    }
+
+    /// Update the name of the local participant,
+    /// resolves once the server acknowledged the change
+    pub async fn set_name(&self, name: String) -> RoomResult<()> {
+        let update = proto::UpdateParticipantMetadata {
+            metadata: self.metadata(),
+            name: name.clone(),
+            ..Default::default()
+        };
+
+        self.update_metadata(update, move |p| p.name() == name)
+            .await
+    }
+
+    /// Update the attributes of the local participant, an empty value deletes the key.
+    /// Resolves once the server acknowledged the change.
+    pub async fn set_attributes(&self, attributes: HashMap<String, String>) -> RoomResult<()> {
+        let update = proto::UpdateParticipantMetadata {
+            metadata: self.metadata(),
+            name: self.name(),
+            attributes: attributes.clone(),
+            ..Default::default()
+        };
+
+        self.update_metadata(update, move |p| {
+            let current = p.attributes();
+            attributes.iter().all(|(key, value)| {
+                if value.is_empty() {
+                    !current.contains_key(key)
+                } else {
+                    current.get(key) == Some(value)
+                }
+            })
+        })
+        .await
+    }
+
+    /// Restrict which participants can subscribe to the local tracks.
+    /// When `all_allowed` is true, `per_participant` is ignored and everyone can subscribe
+    pub async fn set_track_subscription_permissions(
+        &self,
+        all_allowed: bool,
+        per_participant: Vec<TrackPermission>,
+    ) -> RoomResult<()> {
+        let permission = proto::SubscriptionPermission {
+            all_participants: all_allowed,
+            track_permissions: per_participant.into_iter().map(Into::into).collect(),
+        };
+
+        self.inner
+            .rtc_engine
+            .send_or_queue_request(proto::signal_request::Message::SubscriptionPermission(
+                permission,
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Send an UpdateParticipantMetadata request and wait for the participant update
+    /// satisfying `acked`. A request made during a signal reconnection is sent once the
+    /// connection resumed
+    async fn update_metadata(
+        &self,
+        update: proto::UpdateParticipantMetadata,
+        acked: impl Fn(&LocalParticipant) -> bool,
+    ) -> RoomResult<()> {
+        self.inner
+            .rtc_engine
+            .send_or_queue_request(proto::signal_request::Message::UpdateMetadata(update))
+            .await?;
+
+        let wait_ack = async {
+            loop {
+                // Notified receives the notify_waiters calls made after its creation
+                let updated = self.local.info_updated.notified();
+                if acked(self) {
+                    return;
+                }
+                updated.await;
+            }
+        };
+
+        tokio::time::timeout(METADATA_UPDATE_TIMEOUT, wait_ack)
+            .await
+            .map_err(|_| RoomError::Timeout("the server didn't acknowledge the update".to_owned()))
+    }
+
     pub fn get_track_publication(&self, sid: &TrackSid) -> Option<LocalTrackPublication> {
         self.inner.tracks.read().get(sid).map(|track| {
             if let TrackPublication::Local(local) = track {
@@ -295,6 +1424,28 @@ impl LocalParticipant {
         self.inner.info.read().metadata.clone()
     }
 
+    pub fn attributes(&self) -> HashMap<String, String> {
+        self.inner.info.read().attributes.clone()
+    }
+
+    /// Receive the events concerning this participant only
+    pub fn events(&self) -> mpsc::UnboundedReceiver<ParticipantEvent> {
+        super::events(&self.inner)
+    }
+
+    pub fn kind(&self) -> ParticipantKind {
+        self.inner.info.read().kind
+    }
+
+    pub fn permissions(&self) -> ParticipantPermission {
+        self.inner
+            .info
+            .read()
+            .permission
+            .clone()
+            .unwrap_or_default()
+    }
+
     pub fn is_speaking(&self) -> bool {
         self.inner.info.read().speaking
     }
@@ -323,3 +1474,63 @@ impl LocalParticipant {
         self.inner.info.read().connection_quality
     }
 }
+
+fn unix_time_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_millis() as i64)
+        .unwrap_or_default()
+}
+
+/// Fill a chunk from the reader, empty at the end of the reader
+async fn read_chunk<R: AsyncRead + Unpin>(reader: &mut R) -> std::io::Result<Vec<u8>> {
+    let mut chunk = vec![0; STREAM_CHUNK_SIZE];
+    let mut len = 0;
+    while len < chunk.len() {
+        let n = reader.read(&mut chunk[len..]).await?;
+        if n == 0 {
+            break;
+        }
+        len += n;
+    }
+    chunk.truncate(len);
+    Ok(chunk)
+}
+
+async fn wait_rpc_response(
+    ack_rx: oneshot::Receiver<()>,
+    mut response_rx: oneshot::Receiver<Result<String, RpcError>>,
+    response_timeout: Duration,
+) -> Result<String, RpcError> {
+    let response_deadline = tokio::time::sleep(response_timeout);
+    tokio::pin!(response_deadline);
+
+    // The response can arrive before the ack was processed
+    tokio::select! {
+        biased;
+        res = &mut response_rx => return flatten_rpc_response(res),
+        ack = tokio::time::timeout(MAX_ROUND_TRIP_LATENCY, ack_rx) => {
+            // A dropped sender means the request was completed, the result is in response_rx
+            if ack.is_err() {
+                return Err(RpcError::built_in(RpcErrorCode::ConnectionTimeout, None));
+            }
+        }
+    }
+
+    tokio::select! {
+        res = &mut response_rx => flatten_rpc_response(res),
+        _ = &mut response_deadline => Err(RpcError::built_in(RpcErrorCode::ResponseTimeout, None)),
+    }
+}
+
+fn flatten_rpc_response(
+    res: Result<Result<String, RpcError>, oneshot::error::RecvError>,
+) -> Result<String, RpcError> {
+    // The sender is only dropped when the request is cleaned up
+    res.unwrap_or_else(|_| {
+        Err(RpcError::built_in(
+            RpcErrorCode::RecipientDisconnected,
+            None,
+        ))
+    })
+}
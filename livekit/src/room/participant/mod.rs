@@ -16,11 +16,14 @@ use crate::prelude::*;
 use crate::rtc_engine::RtcEngine;
 use livekit_protocol as proto;
 use livekit_protocol::enum_dispatch;
+use livekit_protocol::observer::Dispatcher;
 use parking_lot::{Mutex, RwLock};
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::sync::Arc;
+use tokio::sync::mpsc;
 
+mod data_pacer;
 mod local_participant;
 mod remote_participant;
 
@@ -32,6 +35,96 @@ pub enum ConnectionQuality {
     Excellent,
     Good,
     Poor,
+    Lost,
+}
+
+/// What the server currently allows a participant to do
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParticipantPermission {
+    pub can_subscribe: bool,
+    pub can_publish: bool,
+    pub can_publish_data: bool,
+    /// Sources allowed to be published, empty means all sources are allowed
+    pub can_publish_sources: Vec<TrackSource>,
+    pub hidden: bool,
+    pub recorder: bool,
+    pub can_update_metadata: bool,
+}
+
+impl ParticipantPermission {
+    pub fn can_publish_source(&self, source: TrackSource) -> bool {
+        self.can_publish
+            && (self.can_publish_sources.is_empty() || self.can_publish_sources.contains(&source))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ParticipantKind {
+    Standard,
+    Ingress,
+    Egress,
+    Sip,
+    Agent,
+}
+
+/// Attribute in which the LiveKit agents publish their state
+pub const AGENT_STATE_ATTRIBUTE: &str = "lk.agent.state";
+
+/// State of a voice agent, see RemoteParticipant::agent_state
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum AgentState {
+    Initializing,
+    Listening,
+    Thinking,
+    Speaking,
+}
+
+impl AgentState {
+    pub fn from_attribute(value: &str) -> Option<Self> {
+        match value {
+            "initializing" => Some(Self::Initializing),
+            "listening" => Some(Self::Listening),
+            "thinking" => Some(Self::Thinking),
+            "speaking" => Some(Self::Speaking),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Initializing => "initializing",
+            Self::Listening => "listening",
+            Self::Thinking => "thinking",
+            Self::Speaking => "speaking",
+        }
+    }
+}
+
+/// Events scoped to a single participant, see Participant::events
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum ParticipantEvent {
+    TrackPublished(TrackPublication),
+    TrackUnpublished(TrackPublication),
+    TrackMuted(TrackPublication),
+    TrackUnmuted(TrackPublication),
+    MetadataChanged {
+        old_metadata: String,
+        metadata: String,
+    },
+    NameChanged {
+        old_name: String,
+        name: String,
+    },
+    AttributesChanged {
+        changed_keys: Vec<String>,
+    },
+    SpeakingChanged {
+        speaking: bool,
+    },
+    ConnectionQualityChanged {
+        quality: ConnectionQuality,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -47,9 +140,13 @@ impl Participant {
         pub fn identity(self: &Self) -> ParticipantIdentity;
         pub fn name(self: &Self) -> String;
         pub fn metadata(self: &Self) -> String;
+        pub fn attributes(self: &Self) -> HashMap<String, String>;
+        pub fn permissions(self: &Self) -> ParticipantPermission;
+        pub fn kind(self: &Self) -> ParticipantKind;
         pub fn is_speaking(self: &Self) -> bool;
         pub fn audio_level(self: &Self) -> f32;
         pub fn connection_quality(self: &Self) -> ConnectionQuality;
+        pub fn events(self: &Self) -> mpsc::UnboundedReceiver<ParticipantEvent>;
 
         pub(crate) fn update_info(self: &Self, info: proto::ParticipantInfo) -> ();
 
@@ -74,6 +171,9 @@ struct ParticipantInfo {
     pub identity: ParticipantIdentity,
     pub name: String,
     pub metadata: String,
+    pub attributes: HashMap<String, String>,
+    pub permission: Option<ParticipantPermission>,
+    pub kind: ParticipantKind,
     pub speaking: bool,
     pub audio_level: f32,
     pub connection_quality: ConnectionQuality,
@@ -81,11 +181,20 @@ struct ParticipantInfo {
 
 type TrackMutedHandler = Box<dyn Fn(Participant, TrackPublication) + Send>;
 type TrackUnmutedHandler = Box<dyn Fn(Participant, TrackPublication) + Send>;
+type AttributesChangedHandler = Box<dyn Fn(Participant, Vec<String>) + Send>;
+type MetadataChangedHandler = Box<dyn Fn(Participant, String, String) + Send>;
+type NameChangedHandler = Box<dyn Fn(Participant, String, String) + Send>;
+type PermissionsChangedHandler =
+    Box<dyn Fn(Participant, ParticipantPermission, ParticipantPermission) + Send>;
 
 #[derive(Default)]
 struct ParticipantEvents {
     track_muted: Mutex<Option<TrackMutedHandler>>,
     track_unmuted: Mutex<Option<TrackUnmutedHandler>>,
+    attributes_changed: Mutex<Option<AttributesChangedHandler>>,
+    metadata_changed: Mutex<Option<MetadataChangedHandler>>,
+    name_changed: Mutex<Option<NameChangedHandler>>,
+    permissions_changed: Mutex<Option<PermissionsChangedHandler>>,
 }
 
 pub(super) struct ParticipantInner {
@@ -93,6 +202,7 @@ pub(super) struct ParticipantInner {
     info: RwLock<ParticipantInfo>,
     tracks: RwLock<HashMap<TrackSid, TrackPublication>>,
     events: Arc<ParticipantEvents>,
+    dispatcher: Dispatcher<ParticipantEvent>,
 }
 
 pub(super) fn new_inner(
@@ -101,6 +211,8 @@ pub(super) fn new_inner(
     identity: ParticipantIdentity,
     name: String,
     metadata: String,
+    attributes: HashMap<String, String>,
+    kind: ParticipantKind,
 ) -> Arc<ParticipantInner> {
     Arc::new(ParticipantInner {
         rtc_engine,
@@ -109,25 +221,99 @@ pub(super) fn new_inner(
             identity,
             name,
             metadata,
+            attributes,
+            permission: None,
+            kind,
             speaking: false,
             audio_level: 0.0,
             connection_quality: ConnectionQuality::Excellent,
         }),
         tracks: Default::default(),
         events: Default::default(),
+        dispatcher: Default::default(),
     })
 }
 
+pub(super) fn events(inner: &Arc<ParticipantInner>) -> mpsc::UnboundedReceiver<ParticipantEvent> {
+    inner.dispatcher.register()
+}
+
 pub(super) fn update_info(
     inner: &Arc<ParticipantInner>,
-    _participant: &Participant,
+    participant: &Participant,
     new_info: proto::ParticipantInfo,
 ) {
     let mut info = inner.info.write();
+    info.kind = new_info.kind().into();
     info.sid = new_info.sid.try_into().unwrap();
-    info.name = new_info.name;
     info.identity = new_info.identity.into();
-    info.metadata = new_info.metadata; // TODO(theomonnom): callback MetadataChanged
+    let old_name = std::mem::replace(&mut info.name, new_info.name.clone());
+    let old_metadata = std::mem::replace(&mut info.metadata, new_info.metadata.clone());
+
+    let changed_keys = changed_attributes(&info.attributes, &new_info.attributes);
+    info.attributes = new_info.attributes;
+
+    // The first permission received isn't a change
+    let new_permission = new_info.permission.map(ParticipantPermission::from);
+    let old_permission = std::mem::replace(&mut info.permission, new_permission.clone());
+    drop(info);
+
+    if old_name != new_info.name {
+        inner.dispatcher.dispatch(&ParticipantEvent::NameChanged {
+            old_name: old_name.clone(),
+            name: new_info.name.clone(),
+        });
+
+        if let Some(name_changed) = inner.events.name_changed.lock().as_ref() {
+            name_changed(participant.clone(), old_name, new_info.name);
+        }
+    }
+
+    if old_metadata != new_info.metadata {
+        inner
+            .dispatcher
+            .dispatch(&ParticipantEvent::MetadataChanged {
+                old_metadata: old_metadata.clone(),
+                metadata: new_info.metadata.clone(),
+            });
+
+        if let Some(metadata_changed) = inner.events.metadata_changed.lock().as_ref() {
+            metadata_changed(participant.clone(), old_metadata, new_info.metadata);
+        }
+    }
+
+    if !changed_keys.is_empty() {
+        inner
+            .dispatcher
+            .dispatch(&ParticipantEvent::AttributesChanged {
+                changed_keys: changed_keys.clone(),
+            });
+
+        if let Some(attributes_changed) = inner.events.attributes_changed.lock().as_ref() {
+            attributes_changed(participant.clone(), changed_keys);
+        }
+    }
+
+    if let (Some(old_permission), Some(new_permission)) = (old_permission, new_permission) {
+        if old_permission != new_permission {
+            if let Some(permissions_changed) = inner.events.permissions_changed.lock().as_ref() {
+                permissions_changed(participant.clone(), old_permission, new_permission);
+            }
+        }
+    }
+}
+
+/// Keys that were added, removed or modified, sorted
+fn changed_attributes(old: &HashMap<String, String>, new: &HashMap<String, String>) -> Vec<String> {
+    let mut changed: Vec<String> = new
+        .iter()
+        .filter(|(key, value)| old.get(*key) != Some(*value))
+        .map(|(key, _)| key.clone())
+        .chain(old.keys().filter(|key| !new.contains_key(*key)).cloned())
+        .collect();
+
+    changed.sort();
+    changed
 }
 
 pub(super) fn set_speaking(
@@ -135,7 +321,12 @@ pub(super) fn set_speaking(
     _participant: &Participant,
     speaking: bool,
 ) {
-    inner.info.write().speaking = speaking;
+    let old_speaking = std::mem::replace(&mut inner.info.write().speaking, speaking);
+    if old_speaking != speaking {
+        inner
+            .dispatcher
+            .dispatch(&ParticipantEvent::SpeakingChanged { speaking });
+    }
 }
 
 pub(super) fn set_audio_level(
@@ -151,7 +342,12 @@ pub(super) fn set_connection_quality(
     _participant: &Participant,
     quality: ConnectionQuality,
 ) {
-    inner.info.write().connection_quality = quality;
+    let old_quality = std::mem::replace(&mut inner.info.write().connection_quality, quality);
+    if old_quality != quality {
+        inner
+            .dispatcher
+            .dispatch(&ParticipantEvent::ConnectionQualityChanged { quality });
+    }
 }
 
 pub(super) fn on_track_muted(
@@ -161,6 +357,34 @@ pub(super) fn on_track_muted(
     *inner.events.track_muted.lock() = Some(Box::new(handler));
 }
 
+pub(super) fn on_attributes_changed(
+    inner: &Arc<ParticipantInner>,
+    handler: impl Fn(Participant, Vec<String>) + Send + 'static,
+) {
+    *inner.events.attributes_changed.lock() = Some(Box::new(handler));
+}
+
+pub(super) fn on_metadata_changed(
+    inner: &Arc<ParticipantInner>,
+    handler: impl Fn(Participant, String, String) + Send + 'static,
+) {
+    *inner.events.metadata_changed.lock() = Some(Box::new(handler));
+}
+
+pub(super) fn on_name_changed(
+    inner: &Arc<ParticipantInner>,
+    handler: impl Fn(Participant, String, String) + Send + 'static,
+) {
+    *inner.events.name_changed.lock() = Some(Box::new(handler));
+}
+
+pub(super) fn on_permissions_changed(
+    inner: &Arc<ParticipantInner>,
+    handler: impl Fn(Participant, ParticipantPermission, ParticipantPermission) + Send + 'static,
+) {
+    *inner.events.permissions_changed.lock() = Some(Box::new(handler));
+}
+
 pub(super) fn on_track_unmuted(
     inner: &Arc<ParticipantInner>,
     handler: impl Fn(Participant, TrackPublication) + Send + 'static,
@@ -179,6 +403,9 @@ pub(super) fn remove_publication(
         // remove events
         publication.on_muted(|_| {});
         publication.on_unmuted(|_| {});
+        inner
+            .dispatcher
+            .dispatch(&ParticipantEvent::TrackUnpublished(publication));
     } else {
         // shouldn't happen (internal)
         log::warn!("could not find publication to remove: {:?}", sid);
@@ -194,11 +421,16 @@ pub(super) fn add_publication(
 ) {
     let mut tracks = inner.tracks.write();
     tracks.insert(publication.sid(), publication.clone());
+    inner
+        .dispatcher
+        .dispatch(&ParticipantEvent::TrackPublished(publication.clone()));
 
     publication.on_muted({
         let events = inner.events.clone();
+        let dispatcher = inner.dispatcher.clone();
         let participant = participant.clone();
         move |publication| {
+            dispatcher.dispatch(&ParticipantEvent::TrackMuted(publication.clone()));
             if let Some(cb) = events.track_muted.lock().as_ref() {
                 cb(participant.clone(), publication);
             }
@@ -207,11 +439,67 @@ pub(super) fn add_publication(
 
     publication.on_unmuted({
         let events = inner.events.clone();
+        let dispatcher = inner.dispatcher.clone();
         let participant = participant.clone();
         move |publication| {
+            dispatcher.dispatch(&ParticipantEvent::TrackUnmuted(publication.clone()));
             if let Some(cb) = events.track_unmuted.lock().as_ref() {
                 cb(participant.clone(), publication);
             }
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed_attributes() {
+        let old = HashMap::from([
+            ("kept".to_owned(), "a".to_owned()),
+            ("modified".to_owned(), "b".to_owned()),
+            ("removed".to_owned(), "c".to_owned()),
+        ]);
+        let new = HashMap::from([
+            ("kept".to_owned(), "a".to_owned()),
+            ("modified".to_owned(), "B".to_owned()),
+            ("added".to_owned(), "d".to_owned()),
+        ]);
+
+        assert_eq!(
+            changed_attributes(&old, &new),
+            vec!["added", "modified", "removed"]
+        );
+        assert!(changed_attributes(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn test_agent_state() {
+        for state in [
+            AgentState::Initializing,
+            AgentState::Listening,
+            AgentState::Thinking,
+            AgentState::Speaking,
+        ] {
+            assert_eq!(AgentState::from_attribute(state.as_str()), Some(state));
+        }
+        assert_eq!(AgentState::from_attribute("sleeping"), None);
+    }
+
+    #[test]
+    fn test_can_publish_source() {
+        let mut permission = ParticipantPermission {
+            can_publish: true,
+            ..Default::default()
+        };
+        assert!(permission.can_publish_source(TrackSource::Screenshare));
+
+        permission.can_publish_sources = vec![TrackSource::Camera, TrackSource::Microphone];
+        assert!(permission.can_publish_source(TrackSource::Camera));
+        assert!(!permission.can_publish_source(TrackSource::Screenshare));
+
+        permission.can_publish = false;
+        assert!(!permission.can_publish_source(TrackSource::Camera));
+    }
+}
@@ -13,7 +13,9 @@
 // limitations under the License.
 
 use super::TrackKind;
-use super::{ConnectionQuality, ParticipantInner};
+use super::{
+    AgentState, ConnectionQuality, ParticipantEvent, ParticipantInner, AGENT_STATE_ATTRIBUTE,
+};
 use crate::prelude::*;
 use crate::rtc_engine::RtcEngine;
 use crate::track::TrackError;
@@ -24,10 +26,9 @@ use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::mpsc;
 use tokio::time::timeout;
 
-const ADD_TRACK_TIMEOUT: Duration = Duration::from_secs(5);
-
 type TrackPublishedHandler = Box<dyn Fn(RemoteParticipant, RemoteTrackPublication) + Send>;
 type TrackUnpublishedHandler = Box<dyn Fn(RemoteParticipant, RemoteTrackPublication) + Send>;
 type TrackSubscribedHandler =
@@ -72,9 +73,11 @@ impl RemoteParticipant {
         identity: ParticipantIdentity,
         name: String,
         metadata: String,
+        attributes: HashMap<String, String>,
+        kind: ParticipantKind,
     ) -> Self {
         Self {
-            inner: super::new_inner(rtc_engine, sid, identity, name, metadata),
+            inner: super::new_inner(rtc_engine, sid, identity, name, metadata, attributes, kind),
             remote: Arc::new(RemoteInfo {
                 events: Default::default(),
             }),
@@ -90,6 +93,7 @@ impl RemoteParticipant {
         sid: TrackSid,
         media_track: MediaStreamTrack,
         transceiver: RtpTransceiver,
+        add_timeout: Duration,
     ) {
         let wait_publication = {
             let participant = self.clone();
@@ -106,7 +110,7 @@ impl RemoteParticipant {
             }
         };
 
-        if let Ok(remote_publication) = timeout(ADD_TRACK_TIMEOUT, wait_publication).await {
+        if let Ok(remote_publication) = timeout(add_timeout, wait_publication).await {
             let track = match remote_publication.kind() {
                 TrackKind::Audio => {
                     if let MediaStreamTrack::Audio(rtc_track) = media_track {
@@ -144,6 +148,8 @@ impl RemoteParticipant {
                 name: remote_publication.name(),
                 r#type: proto::TrackType::from(remote_publication.kind()) as i32,
                 source: proto::TrackSource::from(remote_publication.source()) as i32,
+                encryption: proto::encryption::Type::from(remote_publication.encryption_type())
+                    as i32,
                 ..Default::default()
             });
 
@@ -273,6 +279,34 @@ impl RemoteParticipant {
         super::on_track_unmuted(&self.inner, handler)
     }
 
+    pub(crate) fn on_attributes_changed(
+        &self,
+        handler: impl Fn(Participant, Vec<String>) + Send + 'static,
+    ) {
+        super::on_attributes_changed(&self.inner, handler)
+    }
+
+    pub(crate) fn on_permissions_changed(
+        &self,
+        handler: impl Fn(Participant, ParticipantPermission, ParticipantPermission) + Send + 'static,
+    ) {
+        super::on_permissions_changed(&self.inner, handler)
+    }
+
+    pub(crate) fn on_metadata_changed(
+        &self,
+        handler: impl Fn(Participant, String, String) + Send + 'static,
+    ) {
+        super::on_metadata_changed(&self.inner, handler)
+    }
+
+    pub(crate) fn on_name_changed(
+        &self,
+        handler: impl Fn(Participant, String, String) + Send + 'static,
+    ) {
+        super::on_name_changed(&self.inner, handler)
+    }
+
     pub(crate) fn set_speaking(&self, speaking: bool) {
         super::set_speaking(&self.inner, &Participant::Remote(self.clone()), speaking);
     }
@@ -313,10 +347,20 @@ impl RemoteParticipant {
                         }],
                     };
 
+                    if let Err(err) = rtc_engine.update_subscription(update_subscription).await {
+                        log::warn!("failed to update the subscription: {}", err);
+                    }
+                });
+            }
+        });
+
+        publication.on_track_settings_update_needed({
+            let rtc_engine = self.inner.rtc_engine.clone();
+            move |_, settings| {
+                let rtc_engine = rtc_engine.clone();
+                tokio::spawn(async move {
                     let _ = rtc_engine
-                        .send_request(proto::signal_request::Message::Subscription(
-                            update_subscription,
-                        ))
+                        .send_request(proto::signal_request::Message::TrackSetting(settings))
                         .await;
                 });
             }
@@ -353,6 +397,7 @@ impl RemoteParticipant {
             };
 
             publication.on_subscription_update_needed(|_, _| {});
+            publication.on_track_settings_update_needed(|_, _| {});
             publication.on_subscribed(|_, _| {});
             publication.on_unsubscribed(|_, _| {});
         }
@@ -385,6 +430,36 @@ impl RemoteParticipant {
         self.inner.info.read().metadata.clone()
     }
 
+    pub fn attributes(&self) -> HashMap<String, String> {
+        self.inner.info.read().attributes.clone()
+    }
+
+    /// Receive the events concerning this participant only
+    pub fn events(&self) -> mpsc::UnboundedReceiver<ParticipantEvent> {
+        super::events(&self.inner)
+    }
+
+    pub fn kind(&self) -> ParticipantKind {
+        self.inner.info.read().kind
+    }
+
+    /// None when the participant isn't an agent or didn't publish its state yet
+    pub fn agent_state(&self) -> Option<AgentState> {
+        let info = self.inner.info.read();
+        info.attributes
+            .get(AGENT_STATE_ATTRIBUTE)
+            .and_then(|state| AgentState::from_attribute(state))
+    }
+
+    pub fn permissions(&self) -> ParticipantPermission {
+        self.inner
+            .info
+            .read()
+            .permission
+            .clone()
+            .unwrap_or_default()
+    }
+
     pub fn is_speaking(&self) -> bool {
         self.inner.info.read().speaking
     }
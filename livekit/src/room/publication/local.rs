@@ -13,18 +13,85 @@
 // limitations under the License.
 
 use super::TrackPublicationInner;
-use crate::e2ee::EncryptionType;
-use crate::options::TrackPublishOptions;
+use crate::e2ee::{EncryptionState, EncryptionType};
+use crate::options::{TrackEncodingParameters, TrackPublishOptions};
 use crate::prelude::*;
 
 use livekit_protocol as proto;
+use livekit_webrtc::prelude::RtpEncodingParameters;
 use parking_lot::Mutex;
 use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 use std::sync::Arc;
+// Each step scales down the resolution by 1.5 and the framerate by 0.75
+const MAX_QUALITY_REDUCTIONS: u8 = 3;
+const MIN_REDUCED_FRAMERATE: f64 = 10.0;
+// Consecutive polls of the CPU limitation before changing the quality by one step.
+// WebRTC adapts the encoder first, the quality is only reduced if the limitation persists
+const CPU_LIMITED_POLLS: u32 = 2;
+const CPU_RECOVERED_POLLS: u32 = 6;
+
+type MuteFuture = Pin<Box<dyn Future<Output = RoomResult<()>> + Send>>;
+type MuteUpdateNeededHandler =
+    Box<dyn Fn(LocalTrackPublication, bool) -> RoomResult<MuteFuture> + Send>;
+type RepublishFuture = Pin<Box<dyn Future<Output = RoomResult<LocalTrackPublication>> + Send>>;
+type RepublishNeededHandler =
+    Box<dyn Fn(LocalTrackPublication, TrackPublishOptions) -> RepublishFuture + Send>;
+// Scale and framerate of an encoding before the first quality reduction
+type UnreducedEncoding = (Option<f64>, Option<f64>);
+
+#[derive(Default)]
+struct LocalEvents {
+    mute_update_needed: Mutex<Option<MuteUpdateNeededHandler>>,
+    republish_needed: Mutex<Option<RepublishNeededHandler>>,
+}
 
 #[derive(Default)]
 struct LocalInfo {
     publish_options: Mutex<TrackPublishOptions>,
+    muted_by_server: AtomicBool,
+    backup_track: Mutex<Option<LocalVideoTrack>>, // Publishes the backup codec
+    // Bitrates of the encodings before the first set_encoding_parameters
+    initial_bitrates: Mutex<Option<Vec<Option<u64>>>>,
+    quality_reductions: AtomicU8,
+    unreduced_encodings: Mutex<Option<Vec<UnreducedEncoding>>>,
+    cpu_polls: Mutex<CpuPolls>,
+    events: LocalEvents,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum QualityChange {
+    Reduced,
+    Restored,
+}
+
+#[derive(Default)]
+struct CpuPolls {
+    limited: u32,
+    recovered: u32,
+}
+
+impl CpuPolls {
+    fn update(&mut self, cpu_limited: bool, reductions: u8) -> Option<QualityChange> {
+        if cpu_limited {
+            self.recovered = 0;
+            self.limited = self.limited.saturating_add(1);
+            if self.limited >= CPU_LIMITED_POLLS && reductions < MAX_QUALITY_REDUCTIONS {
+                self.limited = 0;
+                return Some(QualityChange::Reduced);
+            }
+        } else {
+            self.limited = 0;
+            self.recovered = self.recovered.saturating_add(1);
+            if self.recovered >= CPU_RECOVERED_POLLS && reductions > 0 {
+                self.recovered = 0;
+                return Some(QualityChange::Restored);
+            }
+        }
+        None
+    }
 }
 
 #[derive(Clone)]
@@ -67,11 +134,51 @@ impl LocalTrackPublication {
         self.inner.info.read().proto_info.clone()
     }
 
-    #[allow(dead_code)]
     pub(crate) fn update_info(&self, info: proto::TrackInfo) {
         super::update_info(&self.inner, &TrackPublication::Local(self.clone()), info);
     }
 
+    /// The handler sends the MuteTrackRequest and resolves once the server applied it, it
+    /// fails if the participant isn't allowed to publish this track anymore
+    pub(crate) fn on_mute_update_needed(
+        &self,
+        f: impl Fn(LocalTrackPublication, bool) -> RoomResult<MuteFuture> + Send + 'static,
+    ) {
+        *self.local.events.mute_update_needed.lock() = Some(Box::new(f));
+    }
+
+    /// The handler unpublishes the track and publishes it again with the given options
+    pub(crate) fn on_republish_needed(
+        &self,
+        f: impl Fn(LocalTrackPublication, TrackPublishOptions) -> RepublishFuture + Send + 'static,
+    ) {
+        *self.local.events.republish_needed.lock() = Some(Box::new(f));
+    }
+
+    /// Called when a moderator muted (or unmuted) this track
+    pub(crate) fn handle_server_mute(&self, muted: bool) {
+        let Some(track) = self.track() else {
+            return;
+        };
+
+        self.local.muted_by_server.store(muted, Ordering::Release);
+        self.apply_muted(&track, muted);
+    }
+
+    pub(crate) fn backup_track(&self) -> Option<LocalVideoTrack> {
+        self.local.backup_track.lock().clone()
+    }
+
+    pub(crate) fn set_backup_track(&self, track: Option<LocalVideoTrack>) {
+        *self.local.backup_track.lock() = track;
+    }
+
+    fn sync_backup_track(&self, muted: bool) {
+        if let Some(backup) = self.backup_track() {
+            backup.rtc_track().set_enabled(!muted);
+        }
+    }
+
     pub(crate) fn update_publish_options(&self, opts: TrackPublishOptions) {
         *self.local.publish_options.lock() = opts;
     }
@@ -80,16 +187,212 @@ impl LocalTrackPublication {
         self.local.publish_options.lock().clone()
     }
 
-    pub fn mute(&self) {
-        if let Some(track) = self.track() {
+    /// Swap the published track (e.g. when switching devices) without renegotiating,
+    /// the publication keeps its sid and remote participants stay subscribed.
+    pub fn replace_track(&self, track: LocalTrack) -> RoomResult<()> {
+        let Some(old_track) = self.track() else {
+            return Err(RoomError::Internal("publication has no track".to_owned()));
+        };
+
+        if old_track.kind() != track.kind() {
+            return Err(RoomError::Internal(
+                "cannot replace a track by a track of another kind".to_owned(),
+            ));
+        }
+
+        let Some(transceiver) = old_track.transceiver() else {
+            return Err(RoomError::Internal("track isn't published".to_owned()));
+        };
+
+        transceiver
+            .sender()
+            .set_track(Some(track.rtc_track()))
+            .map_err(|e| RoomError::Internal(format!("failed to replace track: {}", e)))?;
+
+        track.update_info(self.proto_info()); // Update sid + source
+        if old_track.is_muted() {
             track.mute();
+        } else {
+            track.unmute();
+            track.enable();
+        }
+
+        // The backup codec must keep encoding the same content as the primary one
+        if let (Some(backup), LocalTrack::Video(video_track)) = (self.backup_track(), &track) {
+            if let Some(backup_transceiver) = backup.transceiver() {
+                let new_backup = video_track.create_backup_track();
+                backup_transceiver
+                    .sender()
+                    .set_track(Some(new_backup.rtc_track().into()))
+                    .map_err(|e| RoomError::Internal(format!("failed to replace track: {}", e)))?;
+
+                backup.set_transceiver(None);
+                new_backup.set_transceiver(Some(backup_transceiver));
+                self.set_backup_track(Some(new_backup));
+            }
         }
+
+        old_track.set_transceiver(None);
+        track.set_transceiver(Some(transceiver));
+        self.set_track(Some(track.into()));
+        Ok(())
     }
 
-    pub fn unmute(&self) {
-        if let Some(track) = self.track() {
+    /// Encrypt (or stop encrypting) this track, fails if E2EE isn't enabled on the room.
+    /// Subscribers learn the encryption of a track when it is published, so the track is
+    /// republished and the returned publication has a new sid
+    pub async fn set_encryption(
+        &self,
+        encryption: EncryptionType,
+    ) -> RoomResult<LocalTrackPublication> {
+        if self.encryption_type() == encryption {
+            return Ok(self.clone());
+        }
+
+        let mut options = self.publish_options();
+        options.encryption = Some(encryption);
+
+        let republish = {
+            let republish_needed = self.local.events.republish_needed.lock();
+            let Some(republish_needed) = republish_needed.as_ref() else {
+                return Err(RoomError::Internal("track isn't published".to_owned()));
+            };
+            republish_needed(self.clone(), options)
+        };
+        republish.await
+    }
+
+    /// Cap the bitrate and set the priority of the encodings of the track. Under a
+    /// constrained uplink, the bandwidth is shared between the tracks by their priority
+    pub fn set_encoding_parameters(&self, parameters: TrackEncodingParameters) -> RoomResult<()> {
+        let Some(transceiver) = self.track().and_then(|track| track.transceiver()) else {
+            return Err(RoomError::Internal("track isn't published".to_owned()));
+        };
+
+        transceiver
+            .sender()
+            .update_encodings(|encodings| {
+                let mut initial_bitrates = self.local.initial_bitrates.lock();
+                let initial_bitrates = initial_bitrates.get_or_insert_with(|| {
+                    encodings
+                        .iter()
+                        .map(|encoding| encoding.max_bitrate)
+                        .collect()
+                });
+
+                for (encoding, initial) in encodings.iter_mut().zip(initial_bitrates.iter()) {
+                    encoding.max_bitrate = capped_bitrate(*initial, parameters.max_bitrate);
+                    encoding.priority = parameters.priority;
+                }
+            })
+            .map_err(|e| {
+                RoomError::Internal(format!("failed to set the encoding parameters: {}", e))
+            })
+    }
+
+    /// Step the resolution and the framerate of the encodings down while the encoder stays
+    /// limited by the CPU, and back up once it has recovered
+    pub(crate) fn update_cpu_limitation(
+        &self,
+        cpu_limited: bool,
+    ) -> RoomResult<Option<QualityChange>> {
+        let reductions = self.quality_reductions();
+        let change = self.local.cpu_polls.lock().update(cpu_limited, reductions);
+        let reductions = match change {
+            Some(QualityChange::Reduced) => reductions + 1,
+            Some(QualityChange::Restored) => reductions - 1,
+            None => return Ok(None),
+        };
+
+        let Some(transceiver) = self.track().and_then(|track| track.transceiver()) else {
+            return Err(RoomError::Internal("track isn't published".to_owned()));
+        };
+
+        transceiver
+            .sender()
+            .update_encodings(|encodings| {
+                let mut unreduced = self.local.unreduced_encodings.lock();
+                let unreduced = unreduced.get_or_insert_with(|| {
+                    encodings
+                        .iter()
+                        .map(|encoding| (encoding.scale_resolution_down_by, encoding.max_framerate))
+                        .collect()
+                });
+
+                for (encoding, unreduced) in encodings.iter_mut().zip(unreduced.iter()) {
+                    reduce_encoding(encoding, *unreduced, reductions);
+                }
+            })
+            .map_err(|e| RoomError::Internal(format!("failed to change the quality: {}", e)))?;
+
+        self.local
+            .quality_reductions
+            .store(reductions, Ordering::Release);
+        Ok(change)
+    }
+
+    /// Number of steps the quality of the track has been reduced because of the CPU usage
+    pub fn quality_reductions(&self) -> u8 {
+        self.local.quality_reductions.load(Ordering::Acquire)
+    }
+
+    /// Mute the track and wait for the server to acknowledge it, the track is unmuted again
+    /// if the server doesn't.
+    /// The track stops sending media right away, sources fed by the application should also
+    /// stop capturing until the track is unmuted.
+    pub async fn mute(&self) -> RoomResult<()> {
+        self.set_muted(true).await
+    }
+
+    /// Unmute the track, fails with PermissionDenied if the participant isn't allowed to
+    /// publish this source anymore
+    pub async fn unmute(&self) -> RoomResult<()> {
+        self.set_muted(false).await
+    }
+
+    async fn set_muted(&self, muted: bool) -> RoomResult<()> {
+        let Some(track) = self.track() else {
+            return Err(RoomError::Internal("publication has no track".to_owned()));
+        };
+
+        let mut mute_update = None;
+        if track.transceiver().is_some() {
+            if let Some(mute_update_needed) = self.local.events.mute_update_needed.lock().as_ref() {
+                mute_update = Some(mute_update_needed(self.clone(), muted)?);
+            }
+        }
+
+        let was_muted = track.is_muted();
+        let was_muted_by_server = self.local.muted_by_server.swap(false, Ordering::AcqRel);
+        self.apply_muted(&track, muted);
+
+        let Some(mute_update) = mute_update else {
+            return Ok(());
+        };
+
+        // The server didn't apply the request, the track gets back the state it knows
+        if let Err(err) = mute_update.await {
+            self.local
+                .muted_by_server
+                .store(was_muted_by_server, Ordering::Release);
+            self.apply_muted(&track, was_muted);
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    fn apply_muted(&self, track: &LocalTrack, muted: bool) {
+        if muted {
+            track.mute();
+        } else {
             track.unmute();
         }
+        self.sync_backup_track(muted);
+    }
+
+    /// Whether the track has been muted by the server (e.g. by a moderator)
+    pub fn is_muted_by_server(&self) -> bool {
+        self.local.muted_by_server.load(Ordering::Acquire)
     }
 
     pub fn sid(&self) -> TrackSid {
@@ -144,4 +447,93 @@ impl LocalTrackPublication {
     pub fn encryption_type(&self) -> EncryptionType {
         self.inner.info.read().encryption_type
     }
+
+    /// Last state reported by the frame cryptor of the track, New if the track isn't encrypted
+    pub fn encryption_state(&self) -> EncryptionState {
+        self.inner.info.read().encryption_state
+    }
+
+    pub(crate) fn set_encryption_state(&self, state: EncryptionState) {
+        self.inner.info.write().encryption_state = state;
+    }
+}
+
+/// Apply the quality reductions to the scale and framerate the encoding had before them
+fn reduce_encoding(
+    encoding: &mut RtpEncodingParameters,
+    (scale, framerate): UnreducedEncoding,
+    reductions: u8,
+) {
+    if reductions == 0 {
+        encoding.scale_resolution_down_by = scale;
+        encoding.max_framerate = framerate;
+        return;
+    }
+
+    let steps = reductions as i32;
+    encoding.scale_resolution_down_by = Some(scale.unwrap_or(1.0) * 1.5f64.powi(steps));
+    encoding.max_framerate = framerate.map(|framerate| {
+        (framerate * 0.75f64.powi(steps)).max(MIN_REDUCED_FRAMERATE.min(framerate))
+    });
+}
+
+fn capped_bitrate(initial: Option<u64>, cap: Option<u64>) -> Option<u64> {
+    match (initial, cap) {
+        (Some(initial), Some(cap)) => Some(initial.min(cap)),
+        (initial, cap) => initial.or(cap),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cpu_polls_hysteresis() {
+        let mut polls = CpuPolls::default();
+        assert_eq!(polls.update(true, 0), None);
+        assert_eq!(polls.update(true, 0), Some(QualityChange::Reduced));
+
+        // A single recovered poll resets the limited ones
+        assert_eq!(polls.update(true, 1), None);
+        assert_eq!(polls.update(false, 1), None);
+        assert_eq!(polls.update(true, 1), None);
+        assert_eq!(polls.update(true, 1), Some(QualityChange::Reduced));
+
+        // Nothing left to reduce
+        assert_eq!(polls.update(true, MAX_QUALITY_REDUCTIONS), None);
+        assert_eq!(polls.update(true, MAX_QUALITY_REDUCTIONS), None);
+
+        for _ in 1..CPU_RECOVERED_POLLS {
+            assert_eq!(polls.update(false, 2), None);
+        }
+        assert_eq!(polls.update(false, 2), Some(QualityChange::Restored));
+
+        // Nothing left to restore
+        for _ in 0..CPU_RECOVERED_POLLS {
+            assert_eq!(polls.update(false, 0), None);
+        }
+    }
+
+    #[test]
+    fn reduce_and_restore_encoding() {
+        let mut encoding = RtpEncodingParameters::default();
+        let unreduced = (Some(2.0), Some(15.0));
+
+        reduce_encoding(&mut encoding, unreduced, 1);
+        assert_eq!(encoding.scale_resolution_down_by, Some(3.0));
+        assert_eq!(encoding.max_framerate, Some(11.25));
+
+        reduce_encoding(&mut encoding, unreduced, 3);
+        assert_eq!(encoding.scale_resolution_down_by, Some(6.75));
+        assert_eq!(encoding.max_framerate, Some(MIN_REDUCED_FRAMERATE));
+
+        reduce_encoding(&mut encoding, unreduced, 0);
+        assert_eq!(encoding.scale_resolution_down_by, Some(2.0));
+        assert_eq!(encoding.max_framerate, Some(15.0));
+
+        reduce_encoding(&mut encoding, (None, None), 1);
+        assert_eq!(encoding.scale_resolution_down_by, Some(1.5));
+        assert_eq!(encoding.max_framerate, None);
+    }
 }
@@ -13,8 +13,9 @@
 // limitations under the License.
 
 use super::track::TrackDimension;
+use crate::e2ee::{EncryptionState, EncryptionType};
+use crate::prelude::*;
 use crate::track::Track;
-use crate::{e2ee::EncryptionType, prelude::*};
 use livekit_protocol as proto;
 use livekit_protocol::enum_dispatch;
 use parking_lot::{Mutex, RwLock};
@@ -58,11 +59,13 @@ impl TrackPublication {
         pub fn is_muted(self: &Self) -> bool;
         pub fn is_remote(self: &Self) -> bool;
         pub fn encryption_type(self: &Self) -> EncryptionType;
+        pub fn encryption_state(self: &Self) -> EncryptionState;
 
         pub(crate) fn on_muted(self: &Self, on_mute: impl Fn(TrackPublication) + Send + 'static) -> ();
         pub(crate) fn on_unmuted(self: &Self, on_unmute: impl Fn(TrackPublication) + Send + 'static) -> ();
         pub(crate) fn proto_info(self: &Self) -> proto::TrackInfo;
         pub(crate) fn update_info(self: &Self, info: proto::TrackInfo) -> ();
+        pub(crate) fn set_encryption_state(self: &Self, state: EncryptionState) -> ();
     );
 
     #[allow(dead_code)]
@@ -93,6 +96,7 @@ struct PublicationInfo {
     pub muted: bool,
     pub proto_info: proto::TrackInfo,
     pub encryption_type: EncryptionType,
+    pub encryption_state: EncryptionState,
 }
 
 pub(crate) type MutedHandler = Box<dyn Fn(TrackPublication) + Send>;
@@ -119,6 +123,7 @@ pub(super) fn new_inner(
         source: info.source().try_into().unwrap(),
         kind: info.r#type().try_into().unwrap(),
         encryption_type: info.encryption().into(),
+        encryption_state: EncryptionState::New,
         name: info.name,
         sid: info.sid.try_into().unwrap(),
         simulcasted: info.simulcast,
@@ -162,6 +167,10 @@ pub(super) fn set_track(
     }
 
     info.track = track.clone();
+    if track.is_none() {
+        // The frame cryptor is removed with the track
+        info.encryption_state = EncryptionState::New;
+    }
 
     if let Some(track) = track.as_ref() {
         info.sid = track.sid();
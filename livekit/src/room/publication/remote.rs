@@ -13,12 +13,17 @@
 // limitations under the License.
 
 use super::{PermissionStatus, SubscriptionStatus, TrackPublication, TrackPublicationInner};
-use crate::e2ee::EncryptionType;
+use crate::e2ee::{EncryptionState, EncryptionType};
 use crate::prelude::*;
+use crate::track::TrackError;
 use livekit_protocol as proto;
 use parking_lot::{Mutex, RwLock};
 use std::fmt::Debug;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::timeout;
+
+const SUBSCRIBE_TIMEOUT: Duration = Duration::from_secs(10);
 
 type SubscribedHandler = Box<dyn Fn(RemoteTrackPublication, RemoteTrack) + Send>;
 type UnsubscribedHandler = Box<dyn Fn(RemoteTrackPublication, RemoteTrack) + Send>;
@@ -27,6 +32,8 @@ type SubscriptionStatusChangedHandler =
 type PermissionStatusChangedHandler =
     Box<dyn Fn(RemoteTrackPublication, PermissionStatus, PermissionStatus) + Send>; // old_status, new_status
 type SubscriptionUpdateNeededHandler = Box<dyn Fn(RemoteTrackPublication, bool) + Send>;
+type TrackSettingsUpdateNeededHandler =
+    Box<dyn Fn(RemoteTrackPublication, proto::UpdateTrackSettings) + Send>;
 
 #[derive(Default)]
 struct RemoteEvents {
@@ -35,12 +42,36 @@ struct RemoteEvents {
     subscription_status_changed: Mutex<Option<SubscriptionStatusChangedHandler>>,
     permission_status_changed: Mutex<Option<PermissionStatusChangedHandler>>,
     subscription_update_needed: Mutex<Option<SubscriptionUpdateNeededHandler>>,
+    track_settings_update_needed: Mutex<Option<TrackSettingsUpdateNeededHandler>>,
+}
+
+/// How the application wants to receive a remote video track
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct VideoSettings {
+    visible: bool,
+    dimension: Option<TrackDimension>, // Takes precedence over quality
+    quality: Option<VideoQuality>,
+    fps: Option<u32>,
+}
+
+impl Default for VideoSettings {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            dimension: None,
+            quality: None,
+            fps: None,
+        }
+    }
 }
 
 #[derive(Debug)]
 struct RemoteInfo {
     subscribed: bool,
     allowed: bool,
+    video_settings: Option<VideoSettings>, // None until the application changes them
+    playout_delay: Option<u32>,            // min_ms
+    subscription_attempts: u32,            // Failed attempts since the last subscription
 }
 
 struct RemoteInner {
@@ -71,6 +102,9 @@ impl RemoteTrackPublication {
                 info: RwLock::new(RemoteInfo {
                     subscribed: false,
                     allowed: true,
+                    video_settings: None,
+                    playout_delay: None,
+                    subscription_attempts: 0,
                 }),
                 events: Default::default(),
             }),
@@ -99,6 +133,12 @@ impl RemoteTrackPublication {
         );
 
         if let Some(track) = track {
+            self.remote.info.write().subscription_attempts = 0;
+
+            // The server forgets the settings of a track when it is unsubscribed
+            self.emit_track_settings_update();
+            self.apply_playout_delay();
+
             if let Some(subscribed) = self.remote.events.subscribed.lock().as_ref() {
                 subscribed(self.clone(), track);
             }
@@ -215,7 +255,144 @@ impl RemoteTrackPublication {
         *self.remote.events.subscription_update_needed.lock() = Some(Box::new(f));
     }
 
-    pub fn set_subscribed(&self, subscribed: bool) {
+    pub(crate) fn on_track_settings_update_needed(
+        &self,
+        f: impl Fn(RemoteTrackPublication, proto::UpdateTrackSettings) + Send + 'static,
+    ) {
+        *self.remote.events.track_settings_update_needed.lock() = Some(Box::new(f));
+    }
+
+    /// Report the size at which this video track is rendered, the server then sends the
+    /// simulcast layer fitting these dimensions. Tracks that aren't visible are paused.
+    pub fn set_video_quality_hint(&self, width: u32, height: u32, visible: bool) {
+        if self.kind() != TrackKind::Video {
+            return;
+        }
+
+        self.update_video_settings(|settings| {
+            settings.visible = visible;
+            settings.dimension = Some(TrackDimension(width, height));
+        });
+    }
+
+    /// Request a specific simulcast quality, this replaces the dimensions set by
+    /// set_video_quality_hint
+    pub fn set_video_quality(&self, quality: VideoQuality) {
+        if self.kind() != TrackKind::Video {
+            return;
+        }
+
+        self.update_video_settings(|settings| {
+            settings.dimension = None;
+            settings.quality = Some(quality);
+        });
+    }
+
+    /// Cap the framerate sent by the server for this track
+    pub fn set_video_fps(&self, fps: u32) {
+        if self.kind() != TrackKind::Video {
+            return;
+        }
+
+        self.update_video_settings(|settings| {
+            settings.fps = Some(fps);
+        });
+    }
+
+    /// Choose the minimum time the received media is buffered before being played.
+    /// 0 favors latency ("zero buffer"), a larger delay tolerates more jitter.
+    /// libwebrtc has no maximum, the jitter buffer grows if the network requires it
+    pub fn set_playout_delay(&self, min_ms: u32) {
+        self.remote.info.write().playout_delay = Some(min_ms);
+        self.apply_playout_delay();
+    }
+
+    fn apply_playout_delay(&self) {
+        let Some(min_ms) = self.remote.info.read().playout_delay else {
+            return;
+        };
+
+        let Some(transceiver) = self.track().and_then(|track| track.transceiver()) else {
+            return;
+        };
+
+        transceiver
+            .receiver()
+            .set_jitter_buffer_minimum_delay(Some(min_ms as f64 / 1000.0));
+    }
+
+    fn update_video_settings(&self, f: impl FnOnce(&mut VideoSettings)) {
+        {
+            let mut info = self.remote.info.write();
+            let old_settings = info.video_settings;
+            let settings = info.video_settings.get_or_insert_with(Default::default);
+            f(settings);
+
+            if old_settings == Some(*settings) {
+                return;
+            }
+        }
+
+        self.emit_track_settings_update();
+    }
+
+    fn emit_track_settings_update(&self) {
+        let Some(video_settings) = self.remote.info.read().video_settings else {
+            return;
+        };
+
+        if let Some(track_settings_update_needed) = self
+            .remote
+            .events
+            .track_settings_update_needed
+            .lock()
+            .as_ref()
+        {
+            let TrackDimension(width, height) =
+                video_settings.dimension.unwrap_or(TrackDimension(0, 0));
+            let quality = video_settings.quality.unwrap_or(VideoQuality::High);
+
+            let settings = proto::UpdateTrackSettings {
+                track_sids: vec![self.sid().into()],
+                disabled: !video_settings.visible,
+                quality: proto::VideoQuality::from(quality) as i32,
+                width,
+                height,
+                fps: video_settings.fps.unwrap_or(0),
+                ..Default::default()
+            };
+
+            track_settings_update_needed(self.clone(), settings);
+        }
+    }
+
+    /// Change the subscription of this track, resolves once the track is received when
+    /// subscribing, or as soon as it is removed when unsubscribing.
+    pub async fn set_subscribed(&self, subscribed: bool) -> Result<(), TrackError> {
+        if subscribed && self.is_subscribed() {
+            return Ok(());
+        }
+
+        self.remote.info.write().subscription_attempts = 0;
+        self.request_subscription(subscribed);
+        if !subscribed {
+            return Ok(());
+        }
+
+        let wait_track = async {
+            // Stop waiting if the subscription is cancelled in the meantime
+            while self.is_desired() && !self.is_subscribed() {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        };
+
+        timeout(SUBSCRIBE_TIMEOUT, wait_track)
+            .await
+            .map_err(|_| TrackError::SubscriptionTimeout(self.sid()))
+    }
+
+    /// Update the desired subscription state and notify the SFU without waiting
+    pub(crate) fn request_subscription(&self, subscribed: bool) {
         let old_subscription_state = self.subscription_status();
         let old_permission_state = self.permission_status();
 
@@ -228,8 +405,10 @@ impl RemoteTrackPublication {
             }
         }
 
-        // TODO(theomonnom): Wait for the PC onRemoveTrack event instead?
-        self.set_track(None);
+        if !subscribed {
+            // TODO(theomonnom): Wait for the PC onRemoveTrack event instead?
+            self.set_track(None);
+        }
 
         // Request to send an update to the SFU
         if let Some(subscription_update_needed) = self
@@ -246,6 +425,13 @@ impl RemoteTrackPublication {
         self.emit_permission_update(old_permission_state);
     }
 
+    /// Count a failed subscription, returns the number of failures
+    pub(crate) fn subscription_failed(&self) -> u32 {
+        let mut info = self.remote.info.write();
+        info.subscription_attempts += 1;
+        info.subscription_attempts
+    }
+
     pub fn subscription_status(&self) -> SubscriptionStatus {
         if !self.remote.info.read().subscribed {
             return SubscriptionStatus::Unsubscribed;
@@ -326,4 +512,14 @@ impl RemoteTrackPublication {
     pub fn encryption_type(&self) -> EncryptionType {
         self.inner.info.read().encryption_type
     }
+
+    /// Last state reported by the frame cryptor of the track, DecryptionFailed or MissingKey
+    /// when the track can't be decrypted. New if the track isn't encrypted or subscribed
+    pub fn encryption_state(&self) -> EncryptionState {
+        self.inner.info.read().encryption_state
+    }
+
+    pub(crate) fn set_encryption_state(&self, state: EncryptionState) {
+        self.inner.info.write().encryption_state = state;
+    }
 }
@@ -0,0 +1,118 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use lazy_static::lazy_static;
+use livekit_api::signal_client::{region, ProxyOptions};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+
+pub use livekit_api::signal_client::region::RegionInfo;
+
+const REGION_CACHE_TTL: Duration = Duration::from_secs(30);
+
+lazy_static! {
+    // Regions fetched by url, shared between prepare_connection and connect
+    static ref REGION_CACHE: Mutex<HashMap<String, (Instant, Vec<RegionInfo>)>> =
+        Mutex::new(HashMap::new());
+}
+
+pub type RegionUrlFuture = Pin<Box<dyn Future<Output = Vec<RegionInfo>> + Send>>;
+
+/// Provides the regions Room::connect can try, the first region is tried first.
+/// Returning an empty list makes the room connect directly to the given url.
+/// The proxy is the one of the RoomOptions, requests should go through it.
+pub trait RegionUrlProvider: Send + Sync {
+    fn regions(&self, url: String, token: String, proxy: Option<ProxyOptions>) -> RegionUrlFuture;
+}
+
+/// Fetch the regions from the LiveKit Cloud region settings endpoint,
+/// the result is cached for a short time
+#[derive(Debug, Default, Clone)]
+pub struct CloudRegionUrlProvider;
+
+impl RegionUrlProvider for CloudRegionUrlProvider {
+    fn regions(&self, url: String, token: String, proxy: Option<ProxyOptions>) -> RegionUrlFuture {
+        Box::pin(async move {
+            if !region::is_cloud_url(&url) {
+                return Vec::new();
+            }
+
+            if let Some((fetched_at, regions)) = REGION_CACHE.lock().get(&url) {
+                if fetched_at.elapsed() < REGION_CACHE_TTL {
+                    return regions.clone();
+                }
+            }
+
+            match region::fetch_regions(&url, &token, proxy).await {
+                Ok(regions) => {
+                    REGION_CACHE
+                        .lock()
+                        .insert(url, (Instant::now(), regions.clone()));
+                    regions
+                }
+                Err(err) => {
+                    log::warn!("failed to fetch the regions, using {}: {}", url, err);
+                    Vec::new()
+                }
+            }
+        })
+    }
+}
+
+/// Keep the regions listed in `region_override`, in the order of the override list
+pub(crate) fn filter_regions(
+    regions: Vec<RegionInfo>,
+    region_override: &[String],
+) -> Vec<RegionInfo> {
+    if region_override.is_empty() {
+        return regions;
+    }
+
+    region_override
+        .iter()
+        .filter_map(|name| regions.iter().find(|r| &r.region == name).cloned())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(name: &str, distance: i64) -> RegionInfo {
+        RegionInfo {
+            region: name.to_owned(),
+            url: format!("https://{}.livekit.cloud", name),
+            distance,
+        }
+    }
+
+    #[test]
+    fn test_filter_regions() {
+        let regions = vec![
+            region("us-east", 10),
+            region("eu-west", 50),
+            region("eu-central", 80),
+        ];
+
+        let all = filter_regions(regions.clone(), &[]);
+        assert_eq!(all.len(), 3);
+
+        let pinned = filter_regions(regions, &["eu-central".to_owned(), "eu-west".to_owned()]);
+        let names: Vec<_> = pinned.iter().map(|r| r.region.as_str()).collect();
+        assert_eq!(names, vec!["eu-central", "eu-west"]);
+    }
+}
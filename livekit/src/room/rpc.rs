@@ -0,0 +1,261 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Remote procedure calls between participants, compatible with the RPC of the
+//! JS and Swift SDKs (see LocalParticipant::perform_rpc and register_rpc_method).
+
+use crate::id::ParticipantIdentity;
+use livekit_protocol as proto;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::oneshot;
+
+/// Maximum size of the request and response payloads, in bytes
+pub const MAX_PAYLOAD_BYTES: usize = 15360;
+const MAX_MESSAGE_BYTES: usize = 256;
+
+// Time given to the recipient to acknowledge a request, subtracted from the
+// response timeout sent to the recipient
+pub(crate) const MAX_ROUND_TRIP_LATENCY: Duration = Duration::from_secs(2);
+pub(crate) const RPC_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum RpcErrorCode {
+    UnsupportedMethod = 1400,
+    RecipientNotFound = 1401,
+    RequestPayloadTooLarge = 1402,
+    UnsupportedServer = 1403,
+    UnsupportedVersion = 1404,
+    ApplicationError = 1500,
+    ConnectionTimeout = 1501,
+    ResponseTimeout = 1502,
+    RecipientDisconnected = 1503,
+    ResponsePayloadTooLarge = 1504,
+    SendFailed = 1505,
+}
+
+impl RpcErrorCode {
+    /// Same messages as the other SDKs
+    pub fn message(&self) -> &'static str {
+        match self {
+            Self::UnsupportedMethod => "Method not supported at destination",
+            Self::RecipientNotFound => "Recipient not found",
+            Self::RequestPayloadTooLarge => "Request payload too large",
+            Self::UnsupportedServer => "RPC not supported by server",
+            Self::UnsupportedVersion => "Unsupported RPC version",
+            Self::ApplicationError => "Application error in method handler",
+            Self::ConnectionTimeout => "Connection timeout",
+            Self::ResponseTimeout => "Response timeout",
+            Self::RecipientDisconnected => "Recipient disconnected",
+            Self::ResponsePayloadTooLarge => "Response payload too large",
+            Self::SendFailed => "Failed to send",
+        }
+    }
+}
+
+/// Returned by perform_rpc. Method handlers can return their own errors, the codes
+/// 1000-1999 are reserved for the built-in errors (see RpcErrorCode)
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("rpc error {code}: {message}")]
+pub struct RpcError {
+    pub code: u32,
+    pub message: String,
+    pub data: Option<String>,
+}
+
+impl RpcError {
+    pub fn new(code: u32, message: String, data: Option<String>) -> Self {
+        Self {
+            code,
+            message,
+            data,
+        }
+    }
+
+    pub fn built_in(code: RpcErrorCode, data: Option<String>) -> Self {
+        Self::new(code as u32, code.message().to_owned(), data)
+    }
+
+    pub fn is(&self, code: RpcErrorCode) -> bool {
+        self.code == code as u32
+    }
+
+    pub(crate) fn from_proto(error: proto::RpcError) -> Self {
+        Self {
+            code: error.code,
+            message: error.message,
+            data: (!error.data.is_empty()).then_some(error.data),
+        }
+    }
+
+    pub(crate) fn to_proto(&self) -> proto::RpcError {
+        proto::RpcError {
+            code: self.code,
+            message: truncate(&self.message, MAX_MESSAGE_BYTES).to_owned(),
+            data: truncate(self.data.as_deref().unwrap_or_default(), MAX_PAYLOAD_BYTES).to_owned(),
+        }
+    }
+}
+
+/// Received by the method handlers
+#[derive(Debug, Clone)]
+pub struct RpcInvocationData {
+    pub request_id: String,
+    pub caller_identity: ParticipantIdentity,
+    pub payload: String,
+    /// Time left to the caller before it gives up waiting for the response
+    pub response_timeout: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct PerformRpcData {
+    pub destination_identity: String,
+    pub method: String,
+    pub payload: String,
+    pub response_timeout: Duration,
+}
+
+impl Default for PerformRpcData {
+    fn default() -> Self {
+        Self {
+            destination_identity: String::new(),
+            method: String::new(),
+            payload: String::new(),
+            response_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+pub type RpcHandlerFuture = Pin<Box<dyn Future<Output = Result<String, RpcError>> + Send>>;
+pub(crate) type RpcHandler = Arc<dyn Fn(RpcInvocationData) -> RpcHandlerFuture + Send + Sync>;
+
+/// Registered methods and calls waiting for an ack or a response
+#[derive(Default)]
+pub(crate) struct RpcState {
+    pub handlers: HashMap<String, RpcHandler>,
+    pub pending_acks: HashMap<String, oneshot::Sender<()>>,
+    pub pending_responses: HashMap<String, PendingResponse>,
+}
+
+pub(crate) struct PendingResponse {
+    pub destination: ParticipantIdentity,
+    pub tx: oneshot::Sender<Result<String, RpcError>>,
+}
+
+impl RpcState {
+    /// Fail the calls made to a participant that left the room
+    pub fn participant_disconnected(&mut self, identity: &ParticipantIdentity) {
+        let request_ids: Vec<_> = self
+            .pending_responses
+            .iter()
+            .filter(|(_, pending)| pending.destination == *identity)
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        for id in request_ids {
+            self.pending_acks.remove(&id);
+            if let Some(pending) = self.pending_responses.remove(&id) {
+                let _ = pending.tx.send(Err(RpcError::built_in(
+                    RpcErrorCode::RecipientDisconnected,
+                    None,
+                )));
+            }
+        }
+    }
+}
+
+/// Longest prefix of s of at most max bytes, cut on a char boundary
+fn truncate(s: &str, max: usize) -> &str {
+    if s.len() <= max {
+        return s;
+    }
+
+    let mut end = max;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_proto() {
+        let error = RpcError::built_in(RpcErrorCode::UnsupportedMethod, None);
+        assert_eq!(error.code, 1400);
+        assert_eq!(RpcError::from_proto(error.to_proto()), error);
+
+        let error = RpcError::new(42, "é".repeat(200), Some("data".to_owned()));
+        let proto = error.to_proto();
+        assert_eq!(proto.message.len(), 256);
+        assert_eq!(proto.data, "data");
+        assert_eq!(truncate("aé", 2), "a");
+    }
+
+    #[test]
+    fn packet_tags() {
+        use prost::Message;
+
+        // Same oneof tags as the other SDKs: rpc_request 10, rpc_ack 11, rpc_response 12
+        let tag = |value| {
+            let packet = proto::DataPacket {
+                value: Some(value),
+                ..Default::default()
+            };
+            packet.encode_to_vec()[0] >> 3
+        };
+        let request = proto::data_packet::Value::RpcRequest(Default::default());
+        assert_eq!(tag(request), 10);
+        assert_eq!(
+            tag(proto::data_packet::Value::RpcAck(Default::default())),
+            11
+        );
+        let response = proto::data_packet::Value::RpcResponse(Default::default());
+        assert_eq!(tag(response), 12);
+    }
+
+    #[test]
+    fn disconnected_recipient() {
+        let mut state = RpcState::default();
+        let mut receivers = Vec::new();
+        for (id, identity) in [("1", "alice"), ("2", "bob")] {
+            let (tx, rx) = oneshot::channel();
+            state
+                .pending_acks
+                .insert(id.to_owned(), oneshot::channel().0);
+            state.pending_responses.insert(
+                id.to_owned(),
+                PendingResponse {
+                    destination: identity.to_owned().into(),
+                    tx,
+                },
+            );
+            receivers.push(rx);
+        }
+
+        state.participant_disconnected(&"alice".to_owned().into());
+        let err = receivers[0].try_recv().unwrap().unwrap_err();
+        assert!(err.is(RpcErrorCode::RecipientDisconnected));
+        assert!(receivers[1].try_recv().is_err());
+        assert_eq!(state.pending_acks.len(), 1);
+        assert!(state.pending_responses.contains_key("2"));
+    }
+}
@@ -0,0 +1,106 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::participant::{ConnectionQuality, ParticipantKind};
+use crate::prelude::*;
+use crate::publication::{PermissionStatus, SubscriptionStatus};
+use std::collections::HashMap;
+
+/// State of a track publication when the snapshot was taken
+#[derive(Debug, Clone)]
+pub struct PublicationSnapshot {
+    pub sid: TrackSid,
+    pub name: String,
+    pub kind: TrackKind,
+    pub source: TrackSource,
+    pub simulcasted: bool,
+    pub muted: bool,
+    /// None for local publications
+    pub subscription_status: Option<SubscriptionStatus>,
+    pub permission_status: Option<PermissionStatus>,
+}
+
+impl From<&TrackPublication> for PublicationSnapshot {
+    fn from(publication: &TrackPublication) -> Self {
+        let (subscription_status, permission_status) = match publication {
+            TrackPublication::Remote(remote) => (
+                Some(remote.subscription_status()),
+                Some(remote.permission_status()),
+            ),
+            TrackPublication::Local(_) => (None, None),
+        };
+
+        Self {
+            sid: publication.sid(),
+            name: publication.name(),
+            kind: publication.kind(),
+            source: publication.source(),
+            simulcasted: publication.simulcasted(),
+            muted: publication.is_muted(),
+            subscription_status,
+            permission_status,
+        }
+    }
+}
+
+/// State of a participant and its publications when the snapshot was taken
+#[derive(Debug, Clone)]
+pub struct ParticipantSnapshot {
+    pub sid: ParticipantSid,
+    pub identity: ParticipantIdentity,
+    pub name: String,
+    pub metadata: String,
+    pub attributes: HashMap<String, String>,
+    pub kind: ParticipantKind,
+    pub permissions: ParticipantPermission,
+    pub is_speaking: bool,
+    pub audio_level: f32,
+    pub connection_quality: ConnectionQuality,
+    pub publications: HashMap<TrackSid, PublicationSnapshot>,
+}
+
+impl From<&Participant> for ParticipantSnapshot {
+    fn from(participant: &Participant) -> Self {
+        Self {
+            sid: participant.sid(),
+            identity: participant.identity(),
+            name: participant.name(),
+            metadata: participant.metadata(),
+            attributes: participant.attributes(),
+            kind: participant.kind(),
+            permissions: participant.permissions(),
+            is_speaking: participant.is_speaking(),
+            audio_level: participant.audio_level(),
+            connection_quality: participant.connection_quality(),
+            publications: participant
+                .tracks()
+                .iter()
+                .map(|(sid, publication)| (sid.clone(), publication.into()))
+                .collect(),
+        }
+    }
+}
+
+/// Plain copy of the room state, see Room::participants_snapshot
+#[derive(Debug, Clone)]
+pub struct RoomSnapshot {
+    pub sid: RoomSid,
+    pub name: String,
+    pub metadata: String,
+    pub connection_state: ConnectionState,
+    pub local_participant: ParticipantSnapshot,
+    pub remote_participants: HashMap<ParticipantSid, ParticipantSnapshot>,
+    /// Loudest first
+    pub active_speakers: Vec<ParticipantSid>,
+}
@@ -14,9 +14,17 @@
 
 use super::TrackInner;
 use crate::prelude::*;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::room::dtmf;
 use crate::rtc_engine::lk_runtime::LkRuntime;
 use core::panic;
 use livekit_protocol as proto;
+#[cfg(not(target_arch = "wasm32"))]
+use livekit_webrtc::audio_source::native::AudioProcessor;
+#[cfg(not(target_arch = "wasm32"))]
+use livekit_webrtc::native::audio_device::{AudioDeviceManager, MicrophoneCapturer};
+#[cfg(not(target_arch = "wasm32"))]
+use livekit_webrtc::native::screen_capture::ScreenAudioCapturer;
 use livekit_webrtc::prelude::*;
 use std::fmt::Debug;
 use std::sync::Arc;
@@ -25,6 +33,9 @@ use std::sync::Arc;
 pub struct LocalAudioTrack {
     inner: Arc<TrackInner>,
     source: RtcAudioSource,
+    // Paused while the track is muted
+    #[cfg(not(target_arch = "wasm32"))]
+    capturer: Option<MicrophoneCapturer>,
 }
 
 impl Debug for LocalAudioTrack {
@@ -47,6 +58,8 @@ impl LocalAudioTrack {
                 MediaStreamTrack::Audio(rtc_track),
             )),
             source,
+            #[cfg(not(target_arch = "wasm32"))]
+            capturer: None,
         }
     }
 
@@ -65,6 +78,40 @@ impl LocalAudioTrack {
         Self::new(name.to_string(), rtc_track, source)
     }
 
+    /// The device manager of the audio device module used by the rooms
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn audio_device_manager() -> Result<AudioDeviceManager, RtcError> {
+        AudioDeviceManager::new(LkRuntime::instance().pc_factory())
+    }
+
+    /// Create a track recording the input device selected on the manager.
+    /// The microphone is released while the track is muted, the recording stops once the track
+    /// and the returned capturer are dropped.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn create_microphone_track(
+        name: &str,
+        manager: &AudioDeviceManager,
+        options: AudioSourceOptions,
+    ) -> Result<(LocalAudioTrack, MicrophoneCapturer), RtcError> {
+        let capturer = MicrophoneCapturer::new(manager, options)?;
+        let mut track =
+            Self::create_audio_track(name, RtcAudioSource::Native(capturer.audio_source()));
+        track.capturer = Some(capturer.clone());
+        Ok((track, capturer))
+    }
+
+    /// Create a track with the audio played by the system (audio loopback), to publish with the
+    /// ScreenshareAudio source. The returned capturer must be kept alive for as long as the
+    /// track is used, fails on the platforms without loopback support.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn create_screen_audio_track(
+        name: &str,
+    ) -> Result<(LocalAudioTrack, ScreenAudioCapturer), RtcError> {
+        let capturer = ScreenAudioCapturer::new()?;
+        let track = Self::create_audio_track(name, RtcAudioSource::Native(capturer.audio_source()));
+        Ok((track, capturer))
+    }
+
     pub fn sid(&self) -> TrackSid {
         self.inner.info.read().sid.clone()
     }
@@ -99,9 +146,21 @@ impl LocalAudioTrack {
 
     pub fn mute(&self) {
         super::set_muted(&self.inner, &Track::LocalAudio(self.clone()), true);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(capturer) = &self.capturer {
+            capturer.pause();
+        }
     }
 
     pub fn unmute(&self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(capturer) = &self.capturer {
+            if let Err(err) = capturer.resume() {
+                log::error!("failed to restart the microphone: {:?}", err);
+            }
+        }
+
         super::set_muted(&self.inner, &Track::LocalAudio(self.clone()), false);
     }
 
@@ -116,6 +175,51 @@ impl LocalAudioTrack {
         self.source.clone()
     }
 
+    /// Process each 10ms frame before it is encoded, processors are chained in the order they
+    /// are added
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn add_processor(&self, processor: impl AudioProcessor + 'static) {
+        match &self.source {
+            RtcAudioSource::Native(source) => source.add_processor(Box::new(processor)),
+            _ => log::warn!("audio processors aren't supported on this source"),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn clear_processors(&self) {
+        if let RtcAudioSource::Native(source) = &self.source {
+            source.clear_processors();
+        }
+    }
+
+    /// Play the in-band tone of a DTMF digit on this track, for SIP gateways
+    /// that don't forward the DTMF data messages
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn play_dtmf_tone(&self, digit: char, duration_ms: u32) -> Result<(), RtcError> {
+        let RtcAudioSource::Native(source) = &self.source else {
+            return Err(RtcError {
+                error_type: RtcErrorType::InvalidState,
+                message: "dtmf tones aren't supported on this source".to_owned(),
+            });
+        };
+
+        let sample_rate = source.sample_rate();
+        let num_channels = source.num_channels();
+        let tone = dtmf::generate_dtmf_tone(digit, duration_ms, sample_rate, num_channels)
+            .ok_or_else(|| RtcError {
+                error_type: RtcErrorType::InvalidState,
+                message: format!("invalid dtmf digit: {}", digit),
+            })?;
+
+        let frame = AudioFrame {
+            samples_per_channel: (tone.len() / num_channels as usize) as u32,
+            data: tone.into(),
+            sample_rate,
+            num_channels,
+        };
+        source.capture_frame(&frame).await
+    }
+
     pub fn is_remote(&self) -> bool {
         false
     }
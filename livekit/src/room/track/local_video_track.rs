@@ -16,7 +16,15 @@ use super::TrackInner;
 use crate::prelude::*;
 use crate::rtc_engine::lk_runtime::LkRuntime;
 use livekit_protocol as proto;
+#[cfg(not(target_arch = "wasm32"))]
+use livekit_webrtc::native::camera::CameraCapturer;
+#[cfg(not(target_arch = "wasm32"))]
+use livekit_webrtc::native::screen_capture::{
+    ScreenCaptureOptions, ScreenCaptureSource, ScreenCapturer,
+};
 use livekit_webrtc::prelude::*;
+#[cfg(not(target_arch = "wasm32"))]
+use livekit_webrtc::video_source::native::VideoProcessor;
 use std::fmt::Debug;
 use std::sync::Arc;
 
@@ -65,6 +73,60 @@ impl LocalVideoTrack {
         Self::new(name.to_string(), rtc_track, source)
     }
 
+    /// Create a track capturing the given display or window, the returned
+    /// capturer must be kept alive for as long as the track is used.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn create_screen_track(
+        name: &str,
+        capture_source: &ScreenCaptureSource,
+        options: ScreenCaptureOptions,
+    ) -> Result<(LocalVideoTrack, ScreenCapturer), RtcError> {
+        use livekit_webrtc::peer_connection_factory::native::PeerConnectionFactoryExt;
+
+        let capturer = ScreenCapturer::new(capture_source, options)?;
+        let native_source = capturer.video_source();
+        let rtc_track = LkRuntime::instance().pc_factory().create_video_track(
+            &livekit_webrtc::native::create_random_uuid(),
+            native_source.clone(),
+        );
+        rtc_track.set_content_hint(VideoContentHint::Detailed);
+
+        let track = Self::new(
+            name.to_string(),
+            rtc_track,
+            RtcVideoSource::Native(native_source),
+        );
+        Ok((track, capturer))
+    }
+
+    /// Create a track capturing the camera with the given device id (see
+    /// `CameraDevice::list`), the returned capturer must be kept alive for as
+    /// long as the track is used.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn create_camera_track(
+        name: &str,
+        device_id: &str,
+        resolution: VideoResolution,
+        fps: u32,
+    ) -> Result<(LocalVideoTrack, CameraCapturer), RtcError> {
+        use livekit_webrtc::peer_connection_factory::native::PeerConnectionFactoryExt;
+
+        let capturer = CameraCapturer::new(device_id, resolution, fps)?;
+        let native_source = capturer.video_source();
+        let rtc_track = LkRuntime::instance().pc_factory().create_video_track(
+            &livekit_webrtc::native::create_random_uuid(),
+            native_source.clone(),
+        );
+        rtc_track.set_content_hint(VideoContentHint::Fluid);
+
+        let track = Self::new(
+            name.to_string(),
+            rtc_track,
+            RtcVideoSource::Native(native_source),
+        );
+        Ok((track, capturer))
+    }
+
     pub fn sid(&self) -> TrackSid {
         self.inner.info.read().sid.clone()
     }
@@ -120,6 +182,23 @@ impl LocalVideoTrack {
         self.source.clone()
     }
 
+    /// Process the captured frames before they are encoded, processors are chained in the
+    /// order they are added
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn add_processor(&self, processor: impl VideoProcessor + 'static) {
+        match &self.source {
+            RtcVideoSource::Native(source) => source.add_processor(Box::new(processor)),
+            _ => log::warn!("video processors aren't supported on this source"),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn clear_processors(&self) {
+        if let RtcVideoSource::Native(source) = &self.source {
+            source.clear_processors();
+        }
+    }
+
     pub fn on_muted(&self, f: impl Fn(Track) + Send + 'static) {
         *self.inner.events.muted.lock() = Some(Box::new(f));
     }
@@ -128,6 +207,16 @@ impl LocalVideoTrack {
         *self.inner.events.unmuted.lock() = Some(Box::new(f));
     }
 
+    /// Create another track of the same source, used to publish a backup codec
+    pub(crate) fn create_backup_track(&self) -> LocalVideoTrack {
+        let backup = Self::create_video_track(&self.name(), self.source.clone());
+        backup
+            .rtc_track()
+            .set_content_hint(self.rtc_track().content_hint());
+        backup.rtc_track().set_enabled(!self.is_muted());
+        backup
+    }
+
     pub(crate) fn transceiver(&self) -> Option<RtpTransceiver> {
         self.inner.info.read().transceiver.clone()
     }
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::e2ee::EncryptionType;
 use crate::prelude::*;
 use livekit_protocol as proto;
 use livekit_protocol::enum_dispatch;
@@ -43,6 +44,37 @@ pub use video_track::*;
 pub enum TrackError {
     #[error("could not find published track with sid: {0:?}")]
     TrackNotFound(TrackSid),
+    #[error("timed out while subscribing to track: {0:?}")]
+    SubscriptionTimeout(TrackSid),
+    #[error("the server failed to subscribe to track {0:?}: {1:?}")]
+    SubscriptionFailed(TrackSid, SubscriptionError),
+}
+
+/// Reason given by the server for a failed subscription
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubscriptionError {
+    Unknown,
+    /// None of the codecs of the track can be decoded by this participant
+    CodecUnsupported,
+    TrackNotFound,
+}
+
+impl SubscriptionError {
+    /// The subscription can succeed later, e.g. the track wasn't yet known by the
+    /// server handling the subscription
+    pub fn is_retryable(&self) -> bool {
+        !matches!(self, Self::CodecUnsupported)
+    }
+}
+
+impl From<proto::SubscriptionError> for SubscriptionError {
+    fn from(error: proto::SubscriptionError) -> Self {
+        match error {
+            proto::SubscriptionError::SeUnknown => Self::Unknown,
+            proto::SubscriptionError::SeCodecUnsupported => Self::CodecUnsupported,
+            proto::SubscriptionError::SeTrackNotfound => Self::TrackNotFound,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -69,6 +101,13 @@ pub enum TrackSource {
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct TrackDimension(pub u32, pub u32);
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoQuality {
+    Low,
+    Medium,
+    High,
+}
+
 macro_rules! track_dispatch {
     ([$($variant:ident),+]) => {
         enum_dispatch!(
@@ -133,6 +172,7 @@ struct TrackInfo {
     pub source: TrackSource,
     pub stream_state: StreamState,
     pub muted: bool,
+    pub encryption_type: EncryptionType,
     pub transceiver: Option<RtpTransceiver>,
 }
 
@@ -156,6 +196,7 @@ pub(super) fn new_inner(
             source: TrackSource::Unknown,
             stream_state: StreamState::Active,
             muted: false,
+            encryption_type: EncryptionType::None,
             transceiver: None,
         }),
         rtc_track,
@@ -193,6 +234,7 @@ pub(super) fn update_info(inner: &Arc<TrackInner>, _track: &Track, new_info: pro
     let mut info = inner.info.write();
     info.kind = TrackKind::try_from(new_info.r#type()).unwrap();
     info.source = TrackSource::from(new_info.source());
+    info.encryption_type = new_info.encryption().into();
     info.name = new_info.name;
     info.sid = new_info.sid.try_into().unwrap();
 }
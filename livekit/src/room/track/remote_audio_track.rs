@@ -13,8 +13,10 @@
 // limitations under the License.
 
 use super::{remote_track, TrackInner};
+use crate::e2ee::EncryptionType;
 use crate::prelude::*;
 use livekit_protocol as proto;
+use livekit_webrtc::native::encoded_audio_stream::EncodedAudioStream;
 use livekit_webrtc::prelude::*;
 use std::fmt::Debug;
 use std::sync::Arc;
@@ -74,6 +76,25 @@ impl RemoteAudioTrack {
         self.inner.rtc_track.set_enabled(false);
     }
 
+    /// Locally change the volume of this participant (1.0 by default),
+    /// applied to the audio streams created from this track
+    pub fn set_volume(&self, volume: f32) {
+        self.rtc_track().set_volume(volume)
+    }
+
+    pub fn volume(&self) -> f32 {
+        self.rtc_track().volume()
+    }
+
+    /// Locally mute this track while keeping the subscription
+    pub fn set_playback_enabled(&self, enabled: bool) {
+        self.rtc_track().set_playback_enabled(enabled)
+    }
+
+    pub fn is_playback_enabled(&self) -> bool {
+        self.rtc_track().playback_enabled()
+    }
+
     pub fn is_muted(&self) -> bool {
         self.inner.info.read().muted
     }
@@ -97,6 +118,23 @@ impl RemoteAudioTrack {
         *self.inner.events.unmuted.lock() = Some(Box::new(f));
     }
 
+    /// Receive the encoded frames of this track before they are decoded, e.g. to forward
+    /// them. None if the track isn't subscribed or is encrypted: the stream would replace the
+    /// E2EE decryptor of the track
+    pub fn encoded_frame_stream(&self) -> Option<EncodedAudioStream> {
+        if self.encryption_type() != EncryptionType::None {
+            log::warn!("encoded frame streams aren't supported on encrypted tracks");
+            return None;
+        }
+
+        self.transceiver()
+            .map(|transceiver| EncodedAudioStream::new(transceiver.receiver()))
+    }
+
+    pub(crate) fn encryption_type(&self) -> EncryptionType {
+        self.inner.info.read().encryption_type
+    }
+
     #[allow(dead_code)]
     pub(crate) fn transceiver(&self) -> Option<RtpTransceiver> {
         self.inner.info.read().transceiver.clone()
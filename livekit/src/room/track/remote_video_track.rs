@@ -14,8 +14,10 @@
 
 use super::remote_track;
 use super::TrackInner;
+use crate::e2ee::EncryptionType;
 use crate::prelude::*;
 use livekit_protocol as proto;
+use livekit_webrtc::native::encoded_video_stream::EncodedVideoStream;
 use livekit_webrtc::prelude::*;
 use std::fmt::Debug;
 use std::sync::Arc;
@@ -98,6 +100,23 @@ impl RemoteVideoTrack {
         *self.inner.events.unmuted.lock() = Some(Box::new(f));
     }
 
+    /// Receive the encoded frames of this track before they are decoded, e.g. to forward
+    /// them. None if the track isn't subscribed or is encrypted: the stream would replace the
+    /// E2EE decryptor of the track
+    pub fn encoded_frame_stream(&self) -> Option<EncodedVideoStream> {
+        if self.encryption_type() != EncryptionType::None {
+            log::warn!("encoded frame streams aren't supported on encrypted tracks");
+            return None;
+        }
+
+        self.transceiver()
+            .map(|transceiver| EncodedVideoStream::new(transceiver.receiver()))
+    }
+
+    pub(crate) fn encryption_type(&self) -> EncryptionType {
+        self.inner.info.read().encryption_type
+    }
+
     #[allow(dead_code)]
     pub(crate) fn transceiver(&self) -> Option<RtpTransceiver> {
         self.inner.info.read().transceiver.clone()
@@ -0,0 +1,73 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::id::{ParticipantIdentity, TrackSid};
+use livekit_protocol as proto;
+
+/// Transcribed speech of a track, published with LocalParticipant::publish_transcription
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transcription {
+    /// Participant whose speech was transcribed
+    pub participant_identity: ParticipantIdentity,
+    pub track_sid: TrackSid,
+    pub segments: Vec<TranscriptionSegment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TranscriptionSegment {
+    /// Segments with the same id replace each other until the final one
+    pub id: String,
+    pub text: String,
+    /// In milliseconds, relative to the start of the track
+    pub start_time: u64,
+    pub end_time: u64,
+    pub is_final: bool,
+    pub language: String,
+}
+
+impl From<proto::TranscriptionSegment> for TranscriptionSegment {
+    fn from(segment: proto::TranscriptionSegment) -> Self {
+        Self {
+            id: segment.id,
+            text: segment.text,
+            start_time: segment.start_time,
+            end_time: segment.end_time,
+            is_final: segment.r#final,
+            language: segment.language,
+        }
+    }
+}
+
+impl From<TranscriptionSegment> for proto::TranscriptionSegment {
+    fn from(segment: TranscriptionSegment) -> Self {
+        Self {
+            id: segment.id,
+            text: segment.text,
+            start_time: segment.start_time,
+            end_time: segment.end_time,
+            r#final: segment.is_final,
+            language: segment.language,
+        }
+    }
+}
+
+impl From<Transcription> for proto::Transcription {
+    fn from(transcription: Transcription) -> Self {
+        Self {
+            transcribed_participant_identity: transcription.participant_identity.into(),
+            track_id: transcription.track_sid.into(),
+            segments: transcription.segments.into_iter().map(Into::into).collect(),
+        }
+    }
+}
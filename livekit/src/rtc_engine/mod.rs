@@ -12,33 +12,41 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::id::ParticipantSid;
+use crate::id::{ParticipantIdentity, ParticipantSid};
 use crate::options::TrackPublishOptions;
 use crate::prelude::LocalTrack;
+use crate::room::diagnostics::{DiagnosticsHandler, PeerTarget};
 use crate::room::DisconnectReason;
 use crate::rtc_engine::lk_runtime::LkRuntime;
+use crate::rtc_engine::network_monitor::NetworkMonitor;
 use crate::rtc_engine::rtc_session::{RtcSession, SessionEvent, SessionEvents};
+use crate::rtc_engine::send_tracker::SendReceipt;
 use crate::DataPacketKind;
-use livekit_api::signal_client::{SignalError, SignalOptions};
+use livekit_api::signal_client::{SignalClient, SignalError, SignalOptions};
 use livekit_protocol as proto;
 use livekit_webrtc::prelude::*;
 use livekit_webrtc::session_description::SdpParseError;
+use livekit_webrtc::stats::CandidatePairStats;
 use parking_lot::Mutex;
 use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
 use std::time::Duration;
 use thiserror::Error;
+use tokio::sync::Notify;
 use tokio::sync::RwLock as AsyncRwLock;
 use tokio::sync::{mpsc, oneshot};
-use tokio::sync::{Mutex as AsyncMutex, Notify};
 use tokio::task::JoinHandle;
-use tokio::time::{interval, Interval, MissedTickBehavior};
 
 pub mod lk_runtime;
+mod network_monitor;
 mod peer_transport;
 mod rtc_events;
 mod rtc_session;
+mod send_tracker;
+mod turn_proxy;
 
 pub(crate) type EngineEmitter = mpsc::Sender<EngineEvent>;
 pub(crate) type EngineEvents = mpsc::Receiver<EngineEvent>;
@@ -47,12 +55,16 @@ pub(crate) type EngineResult<T> = Result<T, EngineError>;
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum SimulateScenario {
     SignalReconnect,
+    /// Act as if the server asked for a full reconnect, without any server roundtrip
+    FullReconnect,
     Speaker,
     NodeFailure,
     ServerLeave,
     Migration,
     ForceTcp,
     ForceTls,
+    /// Limit the subscriber bandwidth (bps), 0 clears the limit
+    SubscriberBandwidth(i64),
 }
 
 #[derive(Error, Debug)]
@@ -89,14 +101,62 @@ pub enum EngineEvent {
     Data {
         participant_sid: ParticipantSid,
         payload: Vec<u8>,
+        topic: Option<String>,
         kind: DataPacketKind,
     },
+    DataBufferedAmountLow {
+        kind: DataPacketKind,
+    },
+    Transcription {
+        transcription: proto::Transcription,
+    },
+    RpcRequest {
+        caller_identity: ParticipantIdentity,
+        request: proto::RpcRequest,
+    },
+    RpcAck {
+        request_id: String,
+    },
+    RpcResponse {
+        response: proto::RpcResponse,
+    },
+    StreamHeader {
+        participant_identity: ParticipantIdentity,
+        header: proto::data_stream::Header,
+    },
+    StreamChunk {
+        chunk: proto::data_stream::Chunk,
+    },
+    StreamTrailer {
+        trailer: proto::data_stream::Trailer,
+    },
     SpeakersChanged {
         speakers: Vec<proto::SpeakerInfo>,
     },
     ConnectionQuality {
         updates: Vec<proto::ConnectionQualityInfo>,
     },
+    SubscribedQualityUpdate {
+        update: proto::SubscribedQualityUpdate,
+    },
+    RemoteMute {
+        sid: String,
+        muted: bool,
+    },
+    SignalRtt {
+        rtt: Duration,
+    },
+    /// The ICE agent of a peer connection switched to another candidate pair
+    CandidatePairChange {
+        target: PeerTarget,
+        change: CandidatePairChange,
+    },
+    /// The server couldn't subscribe to the track, sent for the auto subscriptions too
+    SubscriptionFailed {
+        track_sid: String,
+        error: proto::SubscriptionError,
+    },
+    NetworkChanged,
     Resuming,
     Resumed,
     SignalResumed,
@@ -106,10 +166,135 @@ pub enum EngineEvent {
     Disconnected {
         reason: DisconnectReason,
     },
+    TokenRefreshRequired,
 }
 
 pub const RECONNECT_ATTEMPTS: u32 = 10;
 pub const RECONNECT_INTERVAL: Duration = Duration::from_secs(5);
+pub const ICE_CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Controls how the engine retries after losing the connection.
+/// The delay between two attempts starts at `initial_delay` and is multiplied by
+/// `backoff_factor` after each failed attempt, up to `max_delay`.
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+    pub max_attempts: u32,
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub backoff_factor: f64,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: RECONNECT_ATTEMPTS,
+            initial_delay: RECONNECT_INTERVAL,
+            max_delay: RECONNECT_INTERVAL,
+            backoff_factor: 1.0,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Delay to wait after the failed attempt `attempt` (starting at 0)
+    pub fn delay(&self, attempt: u32) -> Duration {
+        // Clamped in f64, the factor overflows a Duration after a few attempts
+        let factor = self.backoff_factor.max(1.0).powf(attempt as f64);
+        let max_delay = self.max_delay.max(self.initial_delay);
+        let delay = (self.initial_delay.as_secs_f64() * factor).min(max_delay.as_secs_f64());
+        Duration::try_from_secs_f64(delay).unwrap_or(max_delay)
+    }
+}
+
+pub type TokenFuture = Pin<Box<dyn Future<Output = Option<String>> + Send>>;
+
+/// Called before each full reconnection to get a fresh token,
+/// returning None keeps the last known token
+#[derive(Clone)]
+pub struct TokenProvider(Arc<dyn Fn() -> TokenFuture + Send + Sync>);
+
+impl TokenProvider {
+    pub fn new<F, Fut>(provider: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Option<String>> + Send + 'static,
+    {
+        Self(Arc::new(move || Box::pin(provider())))
+    }
+
+    pub async fn token(&self) -> Option<String> {
+        (self.0)().await
+    }
+}
+
+impl Debug for TokenProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("TokenProvider").finish()
+    }
+}
+
+/// Priorities of the media and data sent on the transport. When the uplink is constrained,
+/// the bitrate is shared between the tracks by their priority.
+/// The data priority is set on the data channels and only changes their DSCP marking: SCTP
+/// isn't scheduled against the RTP media, so large data streams can still delay the tracks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransportPriority {
+    pub audio: Priority,
+    pub video: Priority,
+    pub data: Priority,
+}
+
+impl Default for TransportPriority {
+    fn default() -> Self {
+        Self {
+            audio: Priority::Low,
+            video: Priority::Low,
+            data: Priority::Low,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EngineOptions {
+    pub signal_options: SignalOptions,
+    pub reconnect_policy: ReconnectPolicy,
+    /// Applied on top of the configuration sent by the server
+    pub rtc_config: Option<RtcConfiguration>,
+    /// Only use the ice servers of `rtc_config`, ignoring the ones sent by the server
+    pub replace_ice_servers: bool,
+    pub token_provider: Option<TokenProvider>,
+    /// Don't accept redundant audio (RED) in the subscriber answers
+    pub disable_audio_red: bool,
+    pub data_buffered_amount_low_threshold: u64,
+    /// Timeout of the peer connections when resuming and of the publisher when sending data
+    pub ice_connect_timeout: Duration,
+    /// Resume the connection when the addresses of the network interfaces change
+    pub detect_network_changes: bool,
+    pub diagnostics: Option<DiagnosticsHandler>,
+    pub transport_priority: TransportPriority,
+    /// Tracks are published right after joining, the publisher is negotiated once with them
+    /// instead of on its own. Only used by the first session
+    pub publish_on_join: bool,
+}
+
+impl Default for EngineOptions {
+    fn default() -> Self {
+        Self {
+            signal_options: Default::default(),
+            reconnect_policy: Default::default(),
+            rtc_config: None,
+            replace_ice_servers: false,
+            token_provider: None,
+            disable_audio_red: false,
+            data_buffered_amount_low_threshold: 0,
+            ice_connect_timeout: ICE_CONNECT_TIMEOUT,
+            detect_network_changes: true,
+            diagnostics: None,
+            transport_priority: TransportPriority::default(),
+            publish_on_join: false,
+        }
+    }
+}
 
 /// Represents a running RTCSession with the ability to close the session
 /// and the engine_task
@@ -120,6 +305,37 @@ struct EngineHandle {
     close_sender: oneshot::Sender<()>,
 }
 
+/// Stats of the two peer connections of the session
+#[derive(Default, Debug, Clone)]
+pub struct SessionStats {
+    pub publisher_stats: Vec<RtcStats>,
+    pub subscriber_stats: Vec<RtcStats>,
+}
+
+impl SessionStats {
+    /// Bandwidth estimation of the publisher (bps), from its selected candidate pair
+    pub fn uplink_bandwidth(&self) -> Option<u64> {
+        selected_pair(&self.publisher_stats)
+            .map(|pair| pair.available_outgoing_bitrate as u64)
+            .filter(|bitrate| *bitrate > 0)
+    }
+
+    /// Bandwidth estimation of the subscriber (bps), only set when the receive side
+    /// estimation is enabled
+    pub fn downlink_bandwidth(&self) -> Option<u64> {
+        selected_pair(&self.subscriber_stats)
+            .map(|pair| pair.available_incoming_bitrate as u64)
+            .filter(|bitrate| *bitrate > 0)
+    }
+}
+
+fn selected_pair(stats: &[RtcStats]) -> Option<&CandidatePairStats> {
+    stats.iter().find_map(|stats| match stats {
+        RtcStats::CandidatePair(pair) if pair.nominated && pair.state == "succeeded" => Some(pair),
+        _ => None,
+    })
+}
+
 #[derive(Default, Debug, Clone)]
 pub struct LastInfo {
     // The join response is updated each time a full reconnect is done
@@ -146,8 +362,12 @@ struct EngineInner {
     closed: AtomicBool, // True if closed or the reconnection failed (Note that this is false when reconnecting or resuming)
     reconnecting: AtomicBool,
     full_reconnect: AtomicBool, // If true, the next reconnect attempt will skip resume and directly try a full reconnect
-    reconnect_interval: AsyncMutex<Interval>,
+    options: EngineOptions,
+    // Token given with update_token, used by the next full reconnect
+    pending_token: Mutex<Option<String>>,
+    reconnect_now: Notify, // Skip the delay before the next reconnection attempt
     reconnect_notifier: Arc<Notify>, // Called when the reconnection task finisehd, successful or not
+    network_task: Mutex<Option<JoinHandle<()>>>,
 }
 
 impl Debug for EngineInner {
@@ -169,13 +389,10 @@ impl RtcEngine {
     pub async fn connect(
         url: &str,
         token: &str,
-        options: SignalOptions,
+        options: EngineOptions,
     ) -> EngineResult<(Self, EngineEvents)> {
         let (engine_emitter, engine_events) = mpsc::channel(8);
 
-        let mut reconnect_interval = interval(RECONNECT_INTERVAL);
-        reconnect_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
-
         let inner = Arc::new(EngineInner {
             lk_runtime: LkRuntime::instance(),
             running_handle: Default::default(),
@@ -184,11 +401,28 @@ impl RtcEngine {
             closed: Default::default(),
             reconnecting: Default::default(),
             full_reconnect: Default::default(),
-            reconnect_interval: AsyncMutex::new(reconnect_interval),
+            // The reconnections don't publish anything on join
+            options: EngineOptions {
+                publish_on_join: false,
+                ..options.clone()
+            },
+            pending_token: Default::default(),
+            reconnect_now: Notify::new(),
             reconnect_notifier: Arc::new(Notify::new()),
+            network_task: Default::default(),
         });
 
         inner.connect(url, token, options).await?;
+
+        if inner.options.detect_network_changes {
+            match NetworkMonitor::new() {
+                Ok(monitor) => {
+                    let task = tokio::spawn(network_task(Arc::downgrade(&inner), monitor));
+                    *inner.network_task.lock() = Some(task);
+                }
+                Err(err) => log::warn!("failed to watch the network interfaces: {}", err),
+            }
+        }
         Ok((Self { inner }, engine_events))
     }
 
@@ -196,11 +430,13 @@ impl RtcEngine {
         self.inner.close(DisconnectReason::ClientInitiated).await
     }
 
+    /// The returned receipt can be used to wait for the data to leave the send buffer,
+    /// without blocking the reconnection
     pub async fn publish_data(
         &self,
         data: &proto::DataPacket,
         kind: DataPacketKind,
-    ) -> EngineResult<()> {
+    ) -> EngineResult<SendReceipt> {
         // Make sure we are connected before trying to send data
         self.inner.wait_reconnection().await?;
         let handle = self.inner.running_handle.read().await;
@@ -208,6 +444,21 @@ impl RtcEngine {
         session.publish_data(data, kind).await
     }
 
+    pub async fn data_buffered_amount(&self, kind: DataPacketKind) -> u64 {
+        let handle = self.inner.running_handle.read().await;
+        handle
+            .as_ref()
+            .map_or(0, |handle| handle.session.data_buffered_amount(kind))
+    }
+
+    /// Resume the session now: the signal connection is restored and both peer connections
+    /// restart ICE (the server sends a new subscriber offer). Resolves once reconnected
+    pub async fn restart_ice(&self) -> EngineResult<()> {
+        self.inner.wait_reconnection().await?;
+        self.inner.try_reconnect(true, false);
+        self.inner.wait_reconnection().await
+    }
+
     pub async fn simulate_scenario(&self, scenario: SimulateScenario) -> EngineResult<()> {
         self.inner.wait_reconnection().await?;
         let handle = self.inner.running_handle.read().await;
@@ -241,6 +492,38 @@ impl RtcEngine {
         session.create_sender(track, options, encodings).await
     }
 
+    /// Stats of a subscribed audio track, including the packets recovered by RED
+    pub async fn get_audio_receiver_stats(
+        &self,
+        receiver: &RtpReceiver,
+    ) -> EngineResult<AudioReceiverStats> {
+        self.inner.wait_reconnection().await?;
+        let handle = self.inner.running_handle.read().await;
+        let session = &handle.as_ref().unwrap().session;
+        session.get_audio_receiver_stats(receiver).await
+    }
+
+    pub async fn get_stats(&self) -> EngineResult<SessionStats> {
+        self.inner.wait_reconnection().await?;
+        let handle = self.inner.running_handle.read().await;
+        let session = &handle.as_ref().unwrap().session;
+        session.get_stats().await
+    }
+
+    pub async fn get_sender_stats(&self, sender: &RtpSender) -> EngineResult<Vec<RtcStats>> {
+        self.inner.wait_reconnection().await?;
+        let handle = self.inner.running_handle.read().await;
+        let session = &handle.as_ref().unwrap().session;
+        session.get_sender_stats(sender).await
+    }
+
+    pub async fn get_receiver_stats(&self, receiver: &RtpReceiver) -> EngineResult<Vec<RtcStats>> {
+        self.inner.wait_reconnection().await?;
+        let handle = self.inner.running_handle.read().await;
+        let session = &handle.as_ref().unwrap().session;
+        session.get_receiver_stats(receiver).await
+    }
+
     pub fn publisher_negotiation_needed(&self) {
         let inner = self.inner.clone();
         tokio::spawn(async move {
@@ -252,6 +535,26 @@ impl RtcEngine {
         });
     }
 
+    /// Resolves with the info of the track once the server applied its muted state
+    pub async fn mute_track(&self, req: proto::MuteTrackRequest) -> EngineResult<proto::TrackInfo> {
+        let signal_client = self.signal_client().await?;
+        Ok(signal_client.mute_track(req).await?)
+    }
+
+    /// Fails if the server rejected the subscription to one of the tracks
+    pub async fn update_subscription(&self, req: proto::UpdateSubscription) -> EngineResult<()> {
+        let signal_client = self.signal_client().await?;
+        Ok(signal_client.update_subscription(req).await?)
+    }
+
+    // Don't keep the session locked while waiting for the server responses
+    async fn signal_client(&self) -> EngineResult<Arc<SignalClient>> {
+        self.inner.wait_reconnection().await?;
+        let handle = self.inner.running_handle.read().await;
+        let session = &handle.as_ref().unwrap().session;
+        Ok(session.signal_client().clone())
+    }
+
     pub async fn send_request(&self, msg: proto::signal_request::Message) -> EngineResult<()> {
         let handle = self.inner.running_handle.read().await;
 
@@ -263,9 +566,42 @@ impl RtcEngine {
         Ok(())
     }
 
+    /// Unlike send_request, a request made while the signal connection is down is sent
+    /// once it resumes. Fails if the connection couldn't be resumed
+    pub async fn send_or_queue_request(
+        &self,
+        msg: proto::signal_request::Message,
+    ) -> EngineResult<()> {
+        let signal_client = self.signal_client().await?;
+        Ok(signal_client.send_or_queue(msg).await?)
+    }
+
+    /// Url and token of the current signal connection
+    pub async fn signal_credentials(&self) -> Option<(String, String)> {
+        let handle = self.inner.running_handle.read().await;
+        handle.as_ref().map(|handle| {
+            let signal_client = handle.session.signal_client();
+            (signal_client.url(), signal_client.token())
+        })
+    }
+
     pub fn last_info(&self) -> LastInfo {
         self.inner.last_info.lock().clone()
     }
+
+    /// Whether the subscriber is the primary peer connection of the current session,
+    /// in which case the publisher is created but only negotiated on the first publication
+    pub fn subscriber_primary(&self) -> bool {
+        self.inner.last_info.lock().join_response.subscriber_primary
+    }
+
+    /// Use a new token for the next reconnections
+    pub async fn update_token(&self, token: String) {
+        *self.inner.pending_token.lock() = Some(token.clone());
+        if let Some(handle) = self.inner.running_handle.read().await.as_ref() {
+            handle.session.signal_client().set_token(token);
+        }
+    }
 }
 
 impl EngineInner {
@@ -317,6 +653,7 @@ impl EngineInner {
             SessionEvent::Data {
                 participant_sid,
                 payload,
+                topic,
                 kind,
             } => {
                 let _ = self
@@ -324,10 +661,71 @@ impl EngineInner {
                     .send(EngineEvent::Data {
                         participant_sid,
                         payload,
+                        topic,
                         kind,
                     })
                     .await;
             }
+            SessionEvent::DataBufferedAmountLow { kind } => {
+                let _ = self
+                    .engine_emitter
+                    .send(EngineEvent::DataBufferedAmountLow { kind })
+                    .await;
+            }
+            SessionEvent::Transcription { transcription } => {
+                let _ = self
+                    .engine_emitter
+                    .send(EngineEvent::Transcription { transcription })
+                    .await;
+            }
+            SessionEvent::RpcRequest {
+                caller_identity,
+                request,
+            } => {
+                let _ = self
+                    .engine_emitter
+                    .send(EngineEvent::RpcRequest {
+                        caller_identity,
+                        request,
+                    })
+                    .await;
+            }
+            SessionEvent::RpcAck { request_id } => {
+                let _ = self
+                    .engine_emitter
+                    .send(EngineEvent::RpcAck { request_id })
+                    .await;
+            }
+            SessionEvent::RpcResponse { response } => {
+                let _ = self
+                    .engine_emitter
+                    .send(EngineEvent::RpcResponse { response })
+                    .await;
+            }
+            SessionEvent::StreamHeader {
+                participant_identity,
+                header,
+            } => {
+                let _ = self
+                    .engine_emitter
+                    .send(EngineEvent::StreamHeader {
+                        participant_identity,
+                        header,
+                    })
+                    .await;
+            }
+            SessionEvent::StreamChunk { chunk } => {
+                let _ = self
+                    .engine_emitter
+                    .send(EngineEvent::StreamChunk { chunk })
+                    .await;
+            }
+            SessionEvent::StreamTrailer { trailer } => {
+                let _ = self
+                    .engine_emitter
+                    .send(EngineEvent::StreamTrailer { trailer })
+                    .await;
+            }
             SessionEvent::MediaTrack {
                 track,
                 stream,
@@ -362,6 +760,36 @@ impl EngineInner {
                     .send(EngineEvent::ConnectionQuality { updates })
                     .await;
             }
+            SessionEvent::SubscribedQualityUpdate { update } => {
+                let _ = self
+                    .engine_emitter
+                    .send(EngineEvent::SubscribedQualityUpdate { update })
+                    .await;
+            }
+            SessionEvent::RemoteMute { sid, muted } => {
+                let _ = self
+                    .engine_emitter
+                    .send(EngineEvent::RemoteMute { sid, muted })
+                    .await;
+            }
+            SessionEvent::SignalRtt { rtt } => {
+                let _ = self
+                    .engine_emitter
+                    .send(EngineEvent::SignalRtt { rtt })
+                    .await;
+            }
+            SessionEvent::CandidatePairChange { target, change } => {
+                let _ = self
+                    .engine_emitter
+                    .send(EngineEvent::CandidatePairChange { target, change })
+                    .await;
+            }
+            SessionEvent::SubscriptionFailed { track_sid, error } => {
+                let _ = self
+                    .engine_emitter
+                    .send(EngineEvent::SubscriptionFailed { track_sid, error })
+                    .await;
+            }
             SessionEvent::Connected => {}
         }
         Ok(())
@@ -371,7 +799,7 @@ impl EngineInner {
         self: &Arc<Self>,
         url: &str,
         token: &str,
-        options: SignalOptions,
+        options: EngineOptions,
     ) -> EngineResult<()> {
         let mut running_handle = self.running_handle.write().await;
         if running_handle.is_some() {
@@ -380,6 +808,11 @@ impl EngineInner {
 
         let (session, session_events) = RtcSession::connect(url, token, options).await?;
 
+        // Always update the join response after a new session is created (first session or full reconnect)
+        self.set_last_info(&session);
+
+        // No await from here: if the connect future is dropped, the session must not be
+        // left running with its engine_task
         let (close_sender, close_receiver) = oneshot::channel();
         let engine_task = tokio::spawn(self.clone().engine_task(session_events, close_receiver));
 
@@ -390,26 +823,25 @@ impl EngineInner {
         };
 
         *running_handle = Some(engine_handle);
-
-        // Always update the join response after a new session is created (first session or full reconnect)
-        drop(running_handle);
-        self.update_last_info().await;
-
         Ok(())
     }
 
     async fn update_last_info(&self) {
         if let Some(handle) = self.running_handle.read().await.as_ref() {
-            let mut last_info = self.last_info.lock();
-            let subscriber_pc = handle.session.subscriber().peer_connection();
-
-            last_info.join_response = handle.session.signal_client().join_response();
-            last_info.subscriber_offer = subscriber_pc.current_remote_description();
-            last_info.subscriber_answer = subscriber_pc.current_local_description();
-            last_info.data_channels_info = handle.session.data_channels_info();
+            self.set_last_info(&handle.session);
         }
     }
 
+    fn set_last_info(&self, session: &RtcSession) {
+        let mut last_info = self.last_info.lock();
+        let subscriber_pc = session.subscriber().peer_connection();
+
+        last_info.join_response = session.signal_client().join_response();
+        last_info.subscriber_offer = subscriber_pc.current_remote_description();
+        last_info.subscriber_answer = subscriber_pc.current_local_description();
+        last_info.data_channels_info = session.data_channels_info();
+    }
+
     async fn terminate_session(&self) {
         if let Some(handle) = self.running_handle.write().await.take() {
             handle.session.close().await;
@@ -420,6 +852,9 @@ impl EngineInner {
 
     async fn close(&self, reason: DisconnectReason) {
         self.closed.store(true, Ordering::Release);
+        if let Some(network_task) = self.network_task.lock().take() {
+            network_task.abort();
+        }
         self.terminate_session().await;
         let _ = self
             .engine_emitter
@@ -449,6 +884,22 @@ impl EngineInner {
         Ok(())
     }
 
+    /// The selected candidates may be unusable, restart ICE right away instead of waiting
+    /// for the peer connections to time out
+    async fn on_network_changed(self: &Arc<Self>) {
+        if self.closed.load(Ordering::Acquire) {
+            return;
+        }
+
+        log::info!("network changed, resuming the connection");
+        let _ = self.engine_emitter.send(EngineEvent::NetworkChanged).await;
+
+        // Keep the full reconnect already decided by the running reconnection
+        let full_reconnect = self.reconnecting.load(Ordering::Acquire)
+            && self.full_reconnect.load(Ordering::Acquire);
+        self.try_reconnect(true, full_reconnect);
+    }
+
     /// Start the reconnect task if not already started
     /// Ask to retry directly if `retry_now` is true
     /// Ask for a full reconnect if `full_reconnect` is true
@@ -458,11 +909,9 @@ impl EngineInner {
         }
 
         self.full_reconnect.store(full_reconnect, Ordering::Release);
-        let inner = self.clone();
         if retry_now {
-            tokio::spawn(async move {
-                inner.reconnect_interval.lock().await.reset();
-            });
+            // Only wakes a pending delay, no permit is kept for the next attempts
+            self.reconnect_now.notify_waiters();
         }
 
         if self
@@ -477,7 +926,6 @@ impl EngineInner {
             let inner = self.clone();
             async move {
                 // Reconnetion logic
-                inner.reconnect_interval.lock().await.reset();
                 inner
                     .full_reconnect
                     .store(full_reconnect, Ordering::Release);
@@ -488,8 +936,11 @@ impl EngineInner {
                 if res.is_ok() {
                     log::info!("RtcEngine successfully reconnected")
                 } else {
-                    log::error!("failed to reconnect after {} attempts", RECONNECT_ATTEMPTS);
-                    inner.close(DisconnectReason::UnknownReason).await;
+                    log::error!(
+                        "failed to reconnect after {} attempts",
+                        inner.options.reconnect_policy.max_attempts
+                    );
+                    inner.close(DisconnectReason::ReconnectFailed).await;
                 }
 
                 inner.reconnect_notifier.notify_waiters();
@@ -501,20 +952,24 @@ impl EngineInner {
     /// We first try to resume the connection, if it fails, we start a full reconnect.
     async fn reconnect_task(self: &Arc<Self>) -> EngineResult<()> {
         // Get the latest connection info from the signal_client (including the refreshed token because the initial join token may have expired)
-        let (url, token, options) = {
+        let (url, mut token, options) = {
             let running_handle = self.running_handle.read().await;
             let signal_client = running_handle.as_ref().unwrap().session.signal_client();
             (
                 signal_client.url(),
                 signal_client.token(), // Refreshed token
-                signal_client.options(),
+                EngineOptions {
+                    signal_options: signal_client.options(),
+                    ..self.options.clone()
+                },
             )
         };
 
         // Update last info before trying to reconnect/resume
         self.update_last_info().await;
 
-        for i in 0..RECONNECT_ATTEMPTS {
+        let reconnect_policy = &self.options.reconnect_policy;
+        for i in 0..reconnect_policy.max_attempts {
             if self.closed.load(Ordering::Acquire) {
                 // The user closed the RTCEngine, cancel the reconnection task
                 return Ok(());
@@ -525,12 +980,30 @@ impl EngineInner {
                     let _ = self.engine_emitter.send(EngineEvent::Restarting).await;
                 }
 
+                if let Some(new_token) = self.pending_token.lock().take() {
+                    token = new_token;
+                }
+                if let Some(token_provider) = self.options.token_provider.as_ref() {
+                    if let Some(new_token) = token_provider.token().await {
+                        token = new_token;
+                    }
+                }
+
                 log::error!("restarting connection... attempt: {}", i);
                 if let Err(err) = self
                     .try_restart_connection(&url, &token, options.clone())
                     .await
                 {
                     log::error!("restarting connection failed: {}", err);
+                    if let EngineError::Signal(SignalError::Client(status, _)) = &err {
+                        if status.as_u16() == 401 {
+                            // The token probably expired, ask the user for a new one
+                            let _ = self
+                                .engine_emitter
+                                .send(EngineEvent::TokenRefreshRequired)
+                                .await;
+                        }
+                    }
                 } else {
                     let _ = self.engine_emitter.send(EngineEvent::Restarted).await;
                     return Ok(());
@@ -552,7 +1025,10 @@ impl EngineInner {
                 }
             }
 
-            self.reconnect_interval.lock().await.tick().await;
+            tokio::select! {
+                _ = tokio::time::sleep(reconnect_policy.delay(i)) => {},
+                _ = self.reconnect_now.notified() => {},
+            }
         }
 
         Err(EngineError::Connection("failed to reconnect".to_owned()))
@@ -564,7 +1040,7 @@ impl EngineInner {
         self: &Arc<Self>,
         url: &str,
         token: &str,
-        options: SignalOptions,
+        options: EngineOptions,
     ) -> EngineResult<()> {
         self.terminate_session().await;
         self.connect(url, token, options).await?;
@@ -586,3 +1062,64 @@ impl EngineInner {
         session.wait_pc_connection().await
     }
 }
+
+async fn network_task(inner: Weak<EngineInner>, mut monitor: NetworkMonitor) {
+    while monitor.changed().await {
+        let Some(inner) = inner.upgrade() else {
+            break;
+        };
+        inner.on_network_changed().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ReconnectPolicy, SessionStats};
+    use livekit_webrtc::stats::{CandidatePairStats, RtcStats};
+    use std::time::Duration;
+
+    #[test]
+    fn test_reconnect_policy_backoff() {
+        let policy = ReconnectPolicy {
+            max_attempts: 5,
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(3),
+            backoff_factor: 2.0,
+        };
+
+        assert_eq!(policy.delay(0), Duration::from_millis(500));
+        assert_eq!(policy.delay(1), Duration::from_secs(1));
+        assert_eq!(policy.delay(2), Duration::from_secs(2));
+        assert_eq!(policy.delay(3), Duration::from_secs(3));
+        // The factor overflows a Duration (and f64 for the last one)
+        assert_eq!(policy.delay(100), Duration::from_secs(3));
+        assert_eq!(policy.delay(u32::MAX), Duration::from_secs(3));
+
+        let default = ReconnectPolicy::default();
+        assert_eq!(default.delay(0), default.delay(8));
+    }
+
+    #[test]
+    fn bandwidth_estimation() {
+        let pair = |nominated, outgoing| {
+            RtcStats::CandidatePair(CandidatePairStats {
+                state: "succeeded".to_owned(),
+                nominated,
+                available_outgoing_bitrate: outgoing,
+                ..Default::default()
+            })
+        };
+
+        let stats = SessionStats {
+            publisher_stats: vec![
+                RtcStats::Other,
+                pair(false, 100_000.0),
+                pair(true, 2_500_000.0),
+            ],
+            subscriber_stats: vec![pair(true, 300_000.0)],
+        };
+        assert_eq!(stats.uplink_bandwidth(), Some(2_500_000));
+        assert_eq!(stats.downlink_bandwidth(), None);
+        assert_eq!(SessionStats::default().uplink_bandwidth(), None);
+    }
+}
@@ -0,0 +1,122 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Watch the addresses of the network interfaces. A new or lost address usually means
+//! the selected ICE candidates are unusable (e.g. switching from Wi-Fi to Ethernet,
+//! a VPN going up or down)
+
+use futures_util::StreamExt;
+use if_watch::tokio::IfWatcher;
+use if_watch::IfEvent;
+use std::collections::HashSet;
+use std::net::IpAddr;
+use std::time::Duration;
+
+// The interfaces change in bursts (e.g. IPv4 and IPv6 addresses of the same interface)
+const NETWORK_CHANGE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Addresses that can carry the media, loopback and link-local ones are ignored
+#[derive(Debug, Default)]
+struct NetworkInterfaces {
+    addresses: HashSet<IpAddr>,
+}
+
+impl NetworkInterfaces {
+    /// Returns true if the usable addresses changed
+    fn update(&mut self, event: &IfEvent) -> bool {
+        match event {
+            IfEvent::Up(net) if is_usable(&net.addr()) => self.addresses.insert(net.addr()),
+            IfEvent::Down(net) => self.addresses.remove(&net.addr()),
+            IfEvent::Up(_) => false,
+        }
+    }
+}
+
+fn is_usable(addr: &IpAddr) -> bool {
+    match addr {
+        IpAddr::V4(addr) => !addr.is_loopback() && !addr.is_link_local(),
+        IpAddr::V6(addr) => !addr.is_loopback() && (addr.segments()[0] & 0xffc0) != 0xfe80,
+    }
+}
+
+pub(crate) struct NetworkMonitor {
+    watcher: IfWatcher,
+    interfaces: NetworkInterfaces,
+    // The addresses present when the watcher starts are reported first
+    baseline: bool,
+}
+
+impl NetworkMonitor {
+    pub fn new() -> std::io::Result<Self> {
+        Ok(Self {
+            watcher: IfWatcher::new()?,
+            interfaces: NetworkInterfaces::default(),
+            baseline: true,
+        })
+    }
+
+    /// Wait for the next change of the usable addresses, returns false when the
+    /// OS notifications stopped
+    pub async fn changed(&mut self) -> bool {
+        loop {
+            let Some(event) = self.watcher.next().await else {
+                return false;
+            };
+            let mut changed = self.update(event);
+
+            loop {
+                match tokio::time::timeout(NETWORK_CHANGE_DEBOUNCE, self.watcher.next()).await {
+                    Ok(Some(event)) => changed |= self.update(event),
+                    Ok(None) => return false,
+                    Err(_) => break,
+                }
+            }
+
+            if !std::mem::take(&mut self.baseline) && changed {
+                log::debug!("network addresses changed: {:?}", self.interfaces.addresses);
+                return true;
+            }
+        }
+    }
+
+    fn update(&mut self, event: std::io::Result<IfEvent>) -> bool {
+        match event {
+            Ok(event) => self.interfaces.update(&event),
+            Err(err) => {
+                log::warn!("failed to watch the network interfaces: {}", err);
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn usable_addresses() {
+        let mut interfaces = NetworkInterfaces::default();
+        let net = |addr: &str| addr.parse().unwrap();
+
+        assert!(interfaces.update(&IfEvent::Up(net("192.168.1.20/24"))));
+        assert!(!interfaces.update(&IfEvent::Up(net("192.168.1.20/24"))));
+        assert!(!interfaces.update(&IfEvent::Up(net("127.0.0.1/8"))));
+        assert!(!interfaces.update(&IfEvent::Up(net("fe80::1c2a:3bff:fe4d:5e6f/64"))));
+        assert!(interfaces.update(&IfEvent::Up(net("2001:db8::20/64"))));
+
+        assert!(interfaces.update(&IfEvent::Down(net("192.168.1.20/24"))));
+        assert!(!interfaces.update(&IfEvent::Down(net("10.8.0.2/24"))));
+    }
+}
@@ -12,14 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::room::diagnostics::{DiagnosticEvent, DiagnosticsHandler, PeerTarget};
 use livekit_protocol as proto;
 use livekit_webrtc::prelude::*;
 use parking_lot::Mutex;
+use std::collections::HashSet;
 use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::Mutex as AsyncMutex;
 
 use super::EngineResult;
+use crate::rtc_engine::lk_runtime::LkRuntime;
+
+const RED_MIME_TYPE: &str = "audio/red";
 
 pub type OnOfferCreated = Box<dyn FnMut(SessionDescription) + Send + Sync>;
 
@@ -33,6 +39,9 @@ pub struct PeerTransport {
     signal_target: proto::SignalTarget,
     peer_connection: PeerConnection,
     on_offer_handler: Mutex<Option<OnOfferCreated>>,
+    stereo_tracks: Mutex<HashSet<String>>, // Ids of the audio tracks sent in stereo
+    audio_red: AtomicBool,
+    diagnostics: Option<DiagnosticsHandler>,
     inner: Arc<AsyncMutex<TransportInner>>,
 }
 
@@ -50,6 +59,9 @@ impl PeerTransport {
             signal_target,
             peer_connection,
             on_offer_handler: Mutex::new(None),
+            stereo_tracks: Default::default(),
+            audio_red: AtomicBool::new(true),
+            diagnostics: None,
             inner: Arc::new(AsyncMutex::new(TransportInner {
                 pending_candidates: Vec::default(),
                 renegotiate: false,
@@ -81,7 +93,44 @@ impl PeerTransport {
         self.peer_connection.close();
     }
 
+    /// Enable Opus stereo for this track in the next offers
+    pub fn add_stereo_track(&self, track_id: String) {
+        self.stereo_tracks.lock().insert(track_id);
+    }
+
+    /// Accept redundant audio (RED) when answering the remote offers
+    pub fn set_audio_red(&self, enabled: bool) {
+        self.audio_red.store(enabled, Ordering::Release);
+    }
+
+    pub fn set_diagnostics(&mut self, handler: Option<DiagnosticsHandler>) {
+        self.diagnostics = handler;
+    }
+
+    pub fn diagnostics(&self) -> Option<&DiagnosticsHandler> {
+        self.diagnostics.as_ref()
+    }
+
+    /// Only builds the event when the diagnostics are enabled
+    pub fn diagnose(&self, event: impl FnOnce(PeerTarget) -> DiagnosticEvent) {
+        if let Some(handler) = &self.diagnostics {
+            handler.emit(event(self.peer_target()));
+        }
+    }
+
+    pub fn peer_target(&self) -> PeerTarget {
+        match self.signal_target {
+            proto::SignalTarget::Publisher => PeerTarget::Publisher,
+            proto::SignalTarget::Subscriber => PeerTarget::Subscriber,
+        }
+    }
+
     pub async fn add_ice_candidate(&self, ice_candidate: IceCandidate) -> EngineResult<()> {
+        self.diagnose(|target| DiagnosticEvent::RemoteCandidate {
+            target,
+            candidate: ice_candidate.candidate(),
+        });
+
         let mut inner = self.inner.lock().await;
 
         if self.peer_connection.current_remote_description().is_some() && !inner.restarting_ice {
@@ -103,6 +152,11 @@ impl PeerTransport {
     ) -> EngineResult<()> {
         let mut inner = self.inner.lock().await;
 
+        self.diagnose(|target| DiagnosticEvent::RemoteDescription {
+            target,
+            sdp_type: remote_description.sdp_type(),
+            sdp: remote_description.to_string(),
+        });
         self.peer_connection
             .set_remote_description(remote_description)
             .await?;
@@ -127,7 +181,26 @@ impl PeerTransport {
         options: AnswerOptions,
     ) -> EngineResult<SessionDescription> {
         self.set_remote_description(offer).await?;
+
+        if !self.audio_red.load(Ordering::Acquire) {
+            let capabilities = LkRuntime::instance()
+                .pc_factory()
+                .get_rtp_receiver_capabilities(MediaType::Audio);
+            let codecs = audio_codec_preferences(capabilities.codecs, false);
+
+            for transceiver in self.peer_connection.transceivers() {
+                if let Some(MediaStreamTrack::Audio(_)) = transceiver.receiver().track() {
+                    transceiver.set_codec_preferences(codecs.clone())?;
+                }
+            }
+        }
+
         let answer = self.peer_connection().create_answer(options).await?;
+        self.diagnose(|target| DiagnosticEvent::LocalDescription {
+            target,
+            sdp_type: answer.sdp_type(),
+            sdp: answer.to_string(),
+        });
         self.peer_connection()
             .set_local_description(answer.clone())
             .await?;
@@ -162,7 +235,20 @@ impl PeerTransport {
             return Ok(());
         }
 
-        let offer = self.peer_connection.create_offer(options).await?;
+        let mut offer = self.peer_connection.create_offer(options).await?;
+        {
+            let stereo_tracks = self.stereo_tracks.lock();
+            if !stereo_tracks.is_empty() {
+                let sdp = enable_opus_stereo(&offer.to_string(), &stereo_tracks);
+                offer = SessionDescription::parse(&sdp, offer.sdp_type())?;
+            }
+        }
+
+        self.diagnose(|target| DiagnosticEvent::LocalDescription {
+            target,
+            sdp_type: offer.sdp_type(),
+            sdp: offer.to_string(),
+        });
         self.peer_connection
             .set_local_description(offer.clone())
             .await?;
@@ -174,3 +260,131 @@ impl PeerTransport {
         Ok(())
     }
 }
+
+/// WebRTC doesn't negotiate stereo Opus by default, munge the fmtp line of the audio sections
+/// sending one of the given tracks (identified by the msid)
+fn enable_opus_stereo(sdp: &str, track_ids: &HashSet<String>) -> String {
+    let mut lines: Vec<String> = sdp.split("\r\n").map(ToOwned::to_owned).collect();
+
+    let mut section_start = 0;
+    for i in 0..=lines.len() {
+        if i == lines.len() || (i > section_start && lines[i].starts_with("m=")) {
+            enable_section_stereo(&mut lines[section_start..i], track_ids);
+            section_start = i;
+        }
+    }
+
+    lines.join("\r\n")
+}
+
+fn enable_section_stereo(section: &mut [String], track_ids: &HashSet<String>) {
+    if !section.first().map_or(false, |l| l.starts_with("m=audio")) {
+        return;
+    }
+
+    let is_stereo = section.iter().any(|line| {
+        line.strip_prefix("a=msid:")
+            .and_then(|msid| msid.split(' ').nth(1))
+            .map_or(false, |track_id| track_ids.contains(track_id))
+    });
+
+    if !is_stereo {
+        return;
+    }
+
+    let opus_pt = section.iter().find_map(|line| {
+        let (pt, codec) = line.strip_prefix("a=rtpmap:")?.split_once(' ')?;
+        codec
+            .to_lowercase()
+            .starts_with("opus/")
+            .then(|| pt.to_owned())
+    });
+
+    let Some(opus_pt) = opus_pt else {
+        return;
+    };
+
+    let fmtp_prefix = format!("a=fmtp:{} ", opus_pt);
+    for line in section.iter_mut() {
+        if line.starts_with(&fmtp_prefix) && !line.split(';').any(|p| p.ends_with("stereo=1")) {
+            line.push_str(";stereo=1;sprop-stereo=1");
+        }
+    }
+}
+
+/// Put RED first so it is preferred over plain Opus, or remove it when disabled
+pub fn audio_codec_preferences(
+    codecs: Vec<RtpCodecCapability>,
+    red: bool,
+) -> Vec<RtpCodecCapability> {
+    let (red_codecs, mut others): (Vec<_>, Vec<_>) = codecs
+        .into_iter()
+        .partition(|codec| codec.mime_type.eq_ignore_ascii_case(RED_MIME_TYPE));
+
+    if !red {
+        return others;
+    }
+
+    let mut preferences = red_codecs;
+    preferences.append(&mut others);
+    preferences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SDP: &str = "v=0\r\n\
+        m=audio 9 UDP/TLS/RTP/SAVPF 111 63\r\n\
+        a=mid:0\r\n\
+        a=msid:- mic\r\n\
+        a=rtpmap:111 opus/48000/2\r\n\
+        a=fmtp:111 minptime=10;useinbandfec=1\r\n\
+        m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+        a=mid:1\r\n\
+        a=msid:- music\r\n\
+        a=rtpmap:111 opus/48000/2\r\n\
+        a=fmtp:111 minptime=10;useinbandfec=1\r\n";
+
+    #[test]
+    fn opus_stereo_only_for_stereo_tracks() {
+        let tracks = HashSet::from(["music".to_owned()]);
+        let sdp = enable_opus_stereo(SDP, &tracks);
+        let fmtps: Vec<&str> = sdp
+            .split("\r\n")
+            .filter(|l| l.starts_with("a=fmtp"))
+            .collect();
+
+        assert_eq!(fmtps[0], "a=fmtp:111 minptime=10;useinbandfec=1");
+        assert_eq!(
+            fmtps[1],
+            "a=fmtp:111 minptime=10;useinbandfec=1;stereo=1;sprop-stereo=1"
+        );
+
+        // Munging twice is a no-op
+        assert_eq!(enable_opus_stereo(&sdp, &tracks), sdp);
+    }
+
+    fn codec(mime_type: &str) -> RtpCodecCapability {
+        RtpCodecCapability {
+            channels: Some(2),
+            clock_rate: Some(48000),
+            mime_type: mime_type.to_owned(),
+            sdp_fmtp_line: None,
+        }
+    }
+
+    #[test]
+    fn red_preferences() {
+        let codecs = vec![codec("audio/opus"), codec("audio/red"), codec("audio/G722")];
+
+        let enabled = audio_codec_preferences(codecs.clone(), true);
+        assert_eq!(enabled[0].mime_type, "audio/red");
+        assert_eq!(enabled[1].mime_type, "audio/opus");
+        assert_eq!(enabled.len(), 3);
+
+        let disabled = audio_codec_preferences(codecs, false);
+        assert!(disabled.iter().all(|c| c.mime_type != "audio/red"));
+        assert_eq!(disabled.len(), 2);
+    }
+}
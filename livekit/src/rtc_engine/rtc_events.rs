@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use super::peer_transport::PeerTransport;
+use crate::room::diagnostics::{DiagnosticEvent, DiagnosticsHandler};
 use crate::rtc_engine::peer_transport::OnOfferCreated;
 use livekit_protocol as proto;
 use livekit_webrtc::{self as rtc, prelude::*};
@@ -52,6 +53,10 @@ pub enum RtcEvent {
         data: Vec<u8>,
         binary: bool,
     },
+    CandidatePairChange {
+        change: CandidatePairChange,
+        target: proto::SignalTarget,
+    },
 }
 
 /// Handlers used to forward events to a channel
@@ -110,6 +115,15 @@ fn on_track(target: proto::SignalTarget, emitter: RtcEmitter) -> rtc::peer_conne
     })
 }
 
+fn on_selected_candidate_pair_change(
+    target: proto::SignalTarget,
+    emitter: RtcEmitter,
+) -> rtc::peer_connection::OnSelectedCandidatePairChange {
+    Box::new(move |change| {
+        let _ = emitter.send(RtcEvent::CandidatePairChange { change, target });
+    })
+}
+
 fn on_ice_candidate_error(
     _target: proto::SignalTarget,
     _emitter: RtcEmitter,
@@ -147,7 +161,38 @@ pub fn forward_pc_events(transport: &mut PeerTransport, rtc_emitter: RtcEmitter)
             rtc_emitter.clone(),
         )));
 
+    transport
+        .peer_connection()
+        .on_selected_candidate_pair_change(Some(on_selected_candidate_pair_change(
+            signal_target,
+            rtc_emitter.clone(),
+        )));
+
     transport.on_offer(Some(on_offer(signal_target, rtc_emitter)));
+
+    if let Some(handler) = transport.diagnostics() {
+        forward_diagnostics(transport, handler.clone());
+    }
+}
+
+/// The states that aren't used by the session are only observed for the diagnostics
+fn forward_diagnostics(transport: &PeerTransport, handler: DiagnosticsHandler) {
+    let target = transport.peer_target();
+    let peer_connection = transport.peer_connection();
+
+    peer_connection.on_ice_connection_state_change(Some(Box::new({
+        let handler = handler.clone();
+        move |state| handler.emit(DiagnosticEvent::IceConnectionState { target, state })
+    })));
+
+    peer_connection.on_ice_gathering_state_change(Some(Box::new({
+        let handler = handler.clone();
+        move |state| handler.emit(DiagnosticEvent::IceGatheringState { target, state })
+    })));
+
+    peer_connection.on_signaling_state_change(Some(Box::new(move |state| {
+        handler.emit(DiagnosticEvent::SignalingState { target, state })
+    })));
 }
 
 fn on_message(emitter: RtcEmitter) -> rtc::data_channel::OnMessage {
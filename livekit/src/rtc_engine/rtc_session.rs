@@ -12,35 +12,35 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{rtc_events, EngineError, EngineResult, SimulateScenario};
-use crate::id::ParticipantSid;
+use super::{rtc_events, EngineError, EngineOptions, EngineResult, SessionStats, SimulateScenario};
+use crate::id::{ParticipantIdentity, ParticipantSid};
 use crate::options::TrackPublishOptions;
 use crate::prelude::TrackKind;
+use crate::room::diagnostics::{DiagnosticEvent, PeerTarget};
 use crate::room::DisconnectReason;
 use crate::rtc_engine::lk_runtime::LkRuntime;
-use crate::rtc_engine::peer_transport::PeerTransport;
+use crate::rtc_engine::peer_transport::{audio_codec_preferences, PeerTransport};
 use crate::rtc_engine::rtc_events::{RtcEvent, RtcEvents};
+use crate::rtc_engine::send_tracker::{SendReceipt, SendTracker};
+use crate::rtc_engine::turn_proxy::TurnProxy;
 use crate::track::LocalTrack;
 use crate::DataPacketKind;
-use livekit_api::signal_client::{SignalClient, SignalEvent, SignalEvents, SignalOptions};
+use livekit_api::signal_client::{SignalClient, SignalEvent, SignalEvents};
 use livekit_protocol as proto;
 use livekit_webrtc::prelude::*;
 use parking_lot::Mutex;
 use prost::Message;
 use proto::debouncer::{self, Debouncer};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 use std::convert::TryInto;
 use std::fmt::Debug;
 use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::{mpsc, oneshot, watch};
+use tokio::sync::{mpsc, watch};
 use tokio::task::JoinHandle;
 use tokio::time::sleep;
 
-pub const ICE_CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
-pub const TRACK_PUBLISH_TIMEOUT: Duration = Duration::from_secs(10);
 pub const LOSSY_DC_LABEL: &str = "_lossy";
 pub const RELIABLE_DC_LABEL: &str = "_reliable";
 pub const PUBLISHER_NEGOTIATION_FREQUENCY: Duration = Duration::from_millis(150);
@@ -56,8 +56,35 @@ pub enum SessionEvent {
     Data {
         participant_sid: ParticipantSid,
         payload: Vec<u8>,
+        topic: Option<String>,
         kind: DataPacketKind,
     },
+    DataBufferedAmountLow {
+        kind: DataPacketKind,
+    },
+    Transcription {
+        transcription: proto::Transcription,
+    },
+    RpcRequest {
+        caller_identity: ParticipantIdentity,
+        request: proto::RpcRequest,
+    },
+    RpcAck {
+        request_id: String,
+    },
+    RpcResponse {
+        response: proto::RpcResponse,
+    },
+    StreamHeader {
+        participant_identity: ParticipantIdentity,
+        header: proto::data_stream::Header,
+    },
+    StreamChunk {
+        chunk: proto::data_stream::Chunk,
+    },
+    StreamTrailer {
+        trailer: proto::data_stream::Trailer,
+    },
     MediaTrack {
         track: MediaStreamTrack,
         stream: MediaStream,
@@ -70,6 +97,24 @@ pub enum SessionEvent {
     ConnectionQuality {
         updates: Vec<proto::ConnectionQualityInfo>,
     },
+    SubscribedQualityUpdate {
+        update: proto::SubscribedQualityUpdate,
+    },
+    RemoteMute {
+        sid: String,
+        muted: bool,
+    },
+    SignalRtt {
+        rtt: Duration,
+    },
+    SubscriptionFailed {
+        track_sid: String,
+        error: proto::SubscriptionError,
+    },
+    CandidatePairChange {
+        target: PeerTarget,
+        change: CandidatePairChange,
+    },
     // TODO(theomonnom): Move entirely the reconnection logic on mod.rs
     Close {
         source: String,
@@ -118,16 +163,19 @@ struct SessionInner {
     signal_client: Arc<SignalClient>,
     pc_state: AtomicU8, // PcState
     has_published: AtomicBool,
+    // The primary peer connection drives the session state, the other one is only
+    // negotiated when needed (publishing or subscribing)
+    subscriber_primary: bool,
 
     publisher_pc: PeerTransport,
     subscriber_pc: PeerTransport,
 
-    pending_tracks: Mutex<HashMap<String, oneshot::Sender<proto::TrackInfo>>>,
-
     // Publisher data channels
     // used to send data to other participants (The SFU forwards the messages)
     lossy_dc: DataChannel,
     reliable_dc: DataChannel,
+    lossy_tracker: Arc<SendTracker>,
+    reliable_tracker: Arc<SendTracker>,
 
     // Keep a strong reference to the subscriber datachannels,
     // so we can receive data from other participants
@@ -137,6 +185,11 @@ struct SessionInner {
     emitter: SessionEmitter,
 
     negotiation_debouncer: Mutex<Option<Debouncer>>,
+
+    // Local relays of the TURN servers reached through the proxy
+    turn_proxy: Mutex<TurnProxy>,
+
+    options: EngineOptions,
 }
 
 impl Debug for SessionInner {
@@ -144,6 +197,7 @@ impl Debug for SessionInner {
         f.debug_struct("SessionInner")
             .field("pc_state", &self.pc_state)
             .field("has_published", &self.has_published)
+            .field("subscriber_primary", &self.subscriber_primary)
             .field("closed", &self.closed)
             .finish()
     }
@@ -165,17 +219,21 @@ impl RtcSession {
     pub async fn connect(
         url: &str,
         token: &str,
-        options: SignalOptions,
+        options: EngineOptions,
     ) -> EngineResult<(Self, SessionEvents)> {
         let (session_emitter, session_events) = mpsc::unbounded_channel();
 
         let (signal_client, join_response, signal_events) =
-            SignalClient::connect(url, token, options).await?;
+            SignalClient::connect(url, token, options.signal_options.clone()).await?;
         let signal_client = Arc::new(signal_client);
         log::debug!("received JoinResponse: {:?}", join_response);
 
+        let subscriber_primary = join_response.subscriber_primary;
+        let negotiate_publisher = negotiate_publisher_on_join(&join_response, &options);
+
         let (rtc_emitter, rtc_events) = mpsc::unbounded_channel();
-        let rtc_config = make_rtc_config_join(join_response);
+        let rtc_config = merge_rtc_config(make_rtc_config_join(join_response), &options);
+        let (rtc_config, turn_proxy) = relay_turn_servers(rtc_config, &options)?;
 
         let lk_runtime = LkRuntime::instance();
         let mut publisher_pc = PeerTransport::new(
@@ -189,12 +247,16 @@ impl RtcSession {
             lk_runtime.pc_factory().create_peer_connection(rtc_config)?,
             proto::SignalTarget::Subscriber,
         );
+        subscriber_pc.set_audio_red(!options.disable_audio_red);
+        publisher_pc.set_diagnostics(options.diagnostics.clone());
+        subscriber_pc.set_diagnostics(options.diagnostics.clone());
 
         let mut lossy_dc = publisher_pc.peer_connection().create_data_channel(
             LOSSY_DC_LABEL,
             DataChannelInit {
                 ordered: true,
                 max_retransmits: Some(0),
+                priority: Some(options.transport_priority.data),
                 ..DataChannelInit::default()
             },
         )?;
@@ -203,6 +265,7 @@ impl RtcSession {
             RELIABLE_DC_LABEL,
             DataChannelInit {
                 ordered: true,
+                priority: Some(options.transport_priority.data),
                 ..DataChannelInit::default()
             },
         )?;
@@ -213,26 +276,50 @@ impl RtcSession {
         rtc_events::forward_dc_events(&mut lossy_dc, rtc_emitter.clone());
         rtc_events::forward_dc_events(&mut reliable_dc, rtc_emitter);
 
+        let threshold = options.data_buffered_amount_low_threshold;
+        let lossy_tracker = Arc::new(SendTracker::new(threshold));
+        let reliable_tracker = Arc::new(SendTracker::new(threshold));
+        track_dc_sends(
+            &lossy_dc,
+            lossy_tracker.clone(),
+            DataPacketKind::Lossy,
+            session_emitter.clone(),
+        );
+        track_dc_sends(
+            &reliable_dc,
+            reliable_tracker.clone(),
+            DataPacketKind::Reliable,
+            session_emitter.clone(),
+        );
+
         let (close_tx, close_rx) = watch::channel(false);
         let inner = Arc::new(SessionInner {
             pc_state: AtomicU8::new(PeerState::New as u8),
             has_published: Default::default(),
+            subscriber_primary,
             signal_client,
             publisher_pc,
             subscriber_pc,
-            pending_tracks: Default::default(),
             lossy_dc,
             reliable_dc,
+            lossy_tracker,
+            reliable_tracker,
             subscriber_dc: Default::default(),
             closed: Default::default(),
             emitter: session_emitter,
             negotiation_debouncer: Default::default(),
+            turn_proxy: Mutex::new(turn_proxy),
+            options,
         });
 
         // Start session tasks
         let signal_task = tokio::spawn(inner.clone().signal_task(signal_events, close_rx.clone()));
         let rtc_task = tokio::spawn(inner.clone().rtc_session_task(rtc_events, close_rx));
 
+        if negotiate_publisher {
+            inner.publisher_negotiation_needed();
+        }
+
         let session = Self {
             inner,
             close_tx,
@@ -265,22 +352,26 @@ impl RtcSession {
     }
 
     /// Close the PeerConnections and the SignalClient
-    pub async fn close(self) {
+    pub async fn close(mut self) {
         // Close the tasks
         self.inner.close().await;
         let _ = self.close_tx.send(true);
-        let _ = self.rtc_task.await;
-        let _ = self.signal_task.await;
+        let _ = (&mut self.rtc_task).await;
+        let _ = (&mut self.signal_task).await;
     }
 
     pub async fn publish_data(
         &self,
         data: &proto::DataPacket,
         kind: DataPacketKind,
-    ) -> Result<(), EngineError> {
+    ) -> Result<SendReceipt, EngineError> {
         self.inner.publish_data(data, kind).await
     }
 
+    pub fn data_buffered_amount(&self, kind: DataPacketKind) -> u64 {
+        self.inner.data_channel(kind).buffered_amount()
+    }
+
     pub async fn restart(&self) -> EngineResult<()> {
         self.inner.restart_session().await
     }
@@ -311,6 +402,47 @@ impl RtcSession {
         &self.inner.subscriber_pc
     }
 
+    pub async fn get_audio_receiver_stats(
+        &self,
+        receiver: &RtpReceiver,
+    ) -> EngineResult<AudioReceiverStats> {
+        Ok(self
+            .inner
+            .subscriber_pc
+            .peer_connection()
+            .get_audio_receiver_stats(receiver)
+            .await?)
+    }
+
+    pub async fn get_stats(&self) -> EngineResult<SessionStats> {
+        let publisher_pc = self.inner.publisher_pc.peer_connection();
+        let subscriber_pc = self.inner.subscriber_pc.peer_connection();
+        let (publisher_stats, subscriber_stats) =
+            tokio::try_join!(publisher_pc.get_stats(), subscriber_pc.get_stats())?;
+        Ok(SessionStats {
+            publisher_stats,
+            subscriber_stats,
+        })
+    }
+
+    pub async fn get_sender_stats(&self, sender: &RtpSender) -> EngineResult<Vec<RtcStats>> {
+        Ok(self
+            .inner
+            .publisher_pc
+            .peer_connection()
+            .get_sender_stats(sender)
+            .await?)
+    }
+
+    pub async fn get_receiver_stats(&self, receiver: &RtpReceiver) -> EngineResult<Vec<RtcStats>> {
+        Ok(self
+            .inner
+            .subscriber_pc
+            .peer_connection()
+            .get_receiver_stats(receiver)
+            .await?)
+    }
+
     pub fn signal_client(&self) -> &Arc<SignalClient> {
         &self.inner.signal_client
     }
@@ -325,6 +457,18 @@ impl RtcSession {
     }
 }
 
+impl Drop for RtcSession {
+    // A session dropped without close (e.g. its engine was cancelled while connecting) must
+    // not be kept alive by its tasks
+    fn drop(&mut self) {
+        if !self.inner.closed.swap(true, Ordering::AcqRel) {
+            self.inner.publisher_pc.close();
+            self.inner.subscriber_pc.close();
+        }
+        let _ = self.close_tx.send(true);
+    }
+}
+
 impl SessionInner {
     async fn rtc_session_task(
         self: Arc<Self>,
@@ -426,12 +570,19 @@ impl SessionInner {
             }
             proto::signal_response::Message::Leave(leave) => {
                 log::debug!("received leave request: {:?}", leave);
+                // Older servers only set can_reconnect and always expect a full reconnect
+                let (can_reconnect, full_reconnect) = match leave.action() {
+                    proto::leave_request::Action::Resume => (true, false),
+                    proto::leave_request::Action::Reconnect => (true, true),
+                    proto::leave_request::Action::Disconnect => (leave.can_reconnect, true),
+                };
+
                 self.on_session_disconnected(
                     "server request to leave",
-                    leave.reason(),
-                    leave.can_reconnect,
-                    true,
+                    leave.reason().into(),
+                    can_reconnect,
                     true,
+                    full_reconnect,
                 );
             }
             proto::signal_response::Message::Update(update) => {
@@ -449,18 +600,36 @@ impl SessionInner {
                     updates: quality.updates,
                 });
             }
-            proto::signal_response::Message::TrackPublished(publish_res) => {
-                let mut pending_tracks = self.pending_tracks.lock();
-                if let Some(tx) = pending_tracks.remove(&publish_res.cid) {
-                    let _ = tx.send(publish_res.track.unwrap());
+            proto::signal_response::Message::SubscribedQualityUpdate(update) => {
+                let _ = self
+                    .emitter
+                    .send(SessionEvent::SubscribedQualityUpdate { update });
+            }
+            proto::signal_response::Message::Mute(mute) => {
+                let _ = self.emitter.send(SessionEvent::RemoteMute {
+                    sid: mute.sid,
+                    muted: mute.muted,
+                });
+            }
+            proto::signal_response::Message::PongResp(_) => {
+                // The rtt is computed by the SignalClient when receiving the pong
+                if let Some(rtt) = self.signal_client.rtt() {
+                    let _ = self.emitter.send(SessionEvent::SignalRtt { rtt });
                 }
             }
+            proto::signal_response::Message::SubscriptionResponse(response) => {
+                let _ = self.emitter.send(SessionEvent::SubscriptionFailed {
+                    error: response.err(),
+                    track_sid: response.track_sid,
+                });
+            }
             _ => {}
         }
 
         Ok(())
     }
 
+    #[allow(deprecated)]
     async fn on_rtc_event(&self, event: RtcEvent) -> EngineResult<()> {
         match event {
             RtcEvent::IceCandidate {
@@ -468,6 +637,11 @@ impl SessionInner {
                 target,
             } => {
                 log::debug!("local ice_candidate {:?} {:?}", ice_candidate, target);
+                self.transport(target)
+                    .diagnose(|target| DiagnosticEvent::LocalCandidate {
+                        target,
+                        candidate: ice_candidate.candidate(),
+                    });
                 self.signal_client
                     .send(proto::signal_request::Message::Trickle(
                         proto::TrickleRequest {
@@ -477,24 +651,27 @@ impl SessionInner {
                                 candidate: ice_candidate.candidate(),
                             })?,
                             target: target as i32,
+                            r#final: false,
                         },
                     ))
                     .await;
             }
             RtcEvent::ConnectionChange { state, target } => {
                 log::debug!("connection change, {:?} {:?}", state, target);
+                self.transport(target)
+                    .diagnose(|target| DiagnosticEvent::ConnectionState { target, state });
 
-                // The subscriber is always the primary peer connection
-                if target == proto::SignalTarget::Subscriber
-                    && state == PeerConnectionState::Connected
-                {
+                if target == self.primary_target() && state == PeerConnectionState::Connected {
                     let old_state = self
                         .pc_state
                         .swap(PeerState::Connected as u8, Ordering::SeqCst);
                     if old_state == PeerState::New as u8 {
                         let _ = self.emitter.send(SessionEvent::Connected);
                     }
-                } else if state == PeerConnectionState::Failed {
+                } else if state == PeerConnectionState::Failed
+                    && (target == self.primary_target()
+                        || self.has_published.load(Ordering::Acquire))
+                {
                     log::error!("{:?} pc state failed", target);
                     self.pc_state
                         .store(PeerState::Disconnected as u8, Ordering::SeqCst);
@@ -532,8 +709,15 @@ impl SessionInner {
                 mut streams,
                 track,
                 transceiver,
-                target: _,
+                target,
             } => {
+                self.transport(target)
+                    .diagnose(|target| DiagnosticEvent::Transceiver {
+                        target,
+                        mid: transceiver.mid(),
+                        direction: transceiver.direction(),
+                    });
+
                 if !streams.is_empty() {
                     let _ = self.emitter.send(SessionEvent::MediaTrack {
                         stream: streams.remove(0),
@@ -545,6 +729,13 @@ impl SessionInner {
                     log::warn!("Track event with no streams");
                 }
             }
+            RtcEvent::CandidatePairChange { change, target } => {
+                log::debug!("selected candidate pair changed: {:?} {:?}", change, target);
+                let _ = self.emitter.send(SessionEvent::CandidatePairChange {
+                    target: self.transport(target).peer_target(),
+                    change,
+                });
+            }
             RtcEvent::Data { data, binary } => {
                 if !binary {
                     Err(EngineError::Internal(
@@ -553,15 +744,60 @@ impl SessionInner {
                 }
 
                 let data = proto::DataPacket::decode(&*data)?;
-                match data.value.as_ref().unwrap() {
+                // Packets added by newer protocol versions are ignored
+                let Some(value) = data.value.as_ref() else {
+                    return Ok(());
+                };
+                match value {
                     proto::data_packet::Value::User(user) => {
                         let _ = self.emitter.send(SessionEvent::Data {
                             kind: data.kind().into(),
                             participant_sid: user.participant_sid.clone().try_into().unwrap(),
                             payload: user.payload.clone(),
+                            topic: user.topic.clone(),
                         });
                     }
                     proto::data_packet::Value::Speaker(_) => {}
+                    proto::data_packet::Value::SipDtmf(_) => {}
+                    proto::data_packet::Value::Metrics(_) => {}
+                    proto::data_packet::Value::ChatMessage(_) => {}
+                    proto::data_packet::Value::Transcription(transcription) => {
+                        let _ = self.emitter.send(SessionEvent::Transcription {
+                            transcription: transcription.clone(),
+                        });
+                    }
+                    proto::data_packet::Value::RpcRequest(request) => {
+                        let _ = self.emitter.send(SessionEvent::RpcRequest {
+                            caller_identity: data.participant_identity.clone().into(),
+                            request: request.clone(),
+                        });
+                    }
+                    proto::data_packet::Value::RpcAck(ack) => {
+                        let _ = self.emitter.send(SessionEvent::RpcAck {
+                            request_id: ack.request_id.clone(),
+                        });
+                    }
+                    proto::data_packet::Value::RpcResponse(response) => {
+                        let _ = self.emitter.send(SessionEvent::RpcResponse {
+                            response: response.clone(),
+                        });
+                    }
+                    proto::data_packet::Value::StreamHeader(header) => {
+                        let _ = self.emitter.send(SessionEvent::StreamHeader {
+                            participant_identity: data.participant_identity.clone().into(),
+                            header: header.clone(),
+                        });
+                    }
+                    proto::data_packet::Value::StreamChunk(chunk) => {
+                        let _ = self.emitter.send(SessionEvent::StreamChunk {
+                            chunk: chunk.clone(),
+                        });
+                    }
+                    proto::data_packet::Value::StreamTrailer(trailer) => {
+                        let _ = self.emitter.send(SessionEvent::StreamTrailer {
+                            trailer: trailer.clone(),
+                        });
+                    }
                 }
             }
         }
@@ -570,40 +806,13 @@ impl SessionInner {
     }
 
     async fn add_track(&self, req: proto::AddTrackRequest) -> EngineResult<proto::TrackInfo> {
-        let (tx, rx) = oneshot::channel();
-        let cid = req.cid.clone();
-        {
-            let mut pendings_tracks = self.pending_tracks.lock();
-            if pendings_tracks.contains_key(&req.cid) {
-                Err(EngineError::Internal("track already published".to_string()))?;
-            }
-
-            pendings_tracks.insert(cid.clone(), tx);
-        }
-
-        self.signal_client
-            .send(proto::signal_request::Message::AddTrack(req))
-            .await;
-
         // Wait the result from the server (TrackInfo)
-        tokio::select! {
-            Ok(info) = rx => Ok(info),
-            _ = sleep(TRACK_PUBLISH_TIMEOUT) => {
-                self.pending_tracks.lock().remove(&cid);
-                Err(EngineError::Internal("track publication timed out, no response received from the server".to_string()))
-            },
-            else => {
-                Err(EngineError::Internal(
-                    "track publication cancelled".to_string(),
-                ))
-            }
-        }
+        Ok(self.signal_client.add_track(req).await?)
     }
 
     async fn remove_track(&self, sender: RtpSender) -> EngineResult<()> {
         if let Some(track) = sender.track() {
-            let mut pending_tracks = self.pending_tracks.lock();
-            pending_tracks.remove(&track.id());
+            self.signal_client.cancel_add_track(&track.id());
         }
 
         self.publisher_pc.peer_connection().remove_track(sender)?;
@@ -615,8 +824,16 @@ impl SessionInner {
         &self,
         track: LocalTrack,
         options: TrackPublishOptions,
-        encodings: Vec<RtpEncodingParameters>,
+        mut encodings: Vec<RtpEncodingParameters>,
     ) -> EngineResult<RtpTransceiver> {
+        let priority = match track.kind() {
+            TrackKind::Audio => self.options.transport_priority.audio,
+            TrackKind::Video => self.options.transport_priority.video,
+        };
+        for encoding in &mut encodings {
+            encoding.priority = priority;
+        }
+
         let init = RtpTransceiverInit {
             direction: RtpTransceiverDirection::SendOnly,
             stream_ids: Default::default(),
@@ -627,6 +844,25 @@ impl SessionInner {
             .publisher_pc
             .peer_connection()
             .add_transceiver(track.rtc_track(), init)?;
+        self.publisher_pc
+            .diagnose(|target| DiagnosticEvent::Transceiver {
+                target,
+                mid: transceiver.mid(),
+                direction: transceiver.direction(),
+            });
+
+        if track.kind() == TrackKind::Audio && options.stereo {
+            self.publisher_pc.add_stereo_track(track.rtc_track().id());
+        }
+
+        if track.kind() == TrackKind::Audio {
+            let capabilities = LkRuntime::instance()
+                .pc_factory()
+                .get_rtp_sender_capabilities(MediaType::Audio);
+
+            transceiver
+                .set_codec_preferences(audio_codec_preferences(capabilities.codecs, options.red))?;
+        }
 
         if track.kind() == TrackKind::Video {
             let capabilities = LkRuntime::instance()
@@ -689,7 +925,9 @@ impl SessionInner {
         self.signal_client
             .send(proto::signal_request::Message::Leave(proto::LeaveRequest {
                 can_reconnect: false,
-                reason: DisconnectReason::ClientInitiated as i32,
+                reason: proto::DisconnectReason::ClientInitiated as i32,
+                action: proto::leave_request::Action::Disconnect as i32,
+                regions: None,
             }))
             .await;
 
@@ -697,14 +935,18 @@ impl SessionInner {
         self.signal_client.close().await;
         self.publisher_pc.close();
         self.subscriber_pc.close();
+        self.lossy_tracker.close();
+        self.reliable_tracker.close();
     }
 
     async fn simulate_scenario(&self, scenario: SimulateScenario) -> EngineResult<()> {
         let simulate_leave = || {
             self.on_signal_event(proto::signal_response::Message::Leave(
                 proto::LeaveRequest {
-                    reason: DisconnectReason::ClientInitiated as i32,
+                    reason: proto::DisconnectReason::ClientInitiated as i32,
                     can_reconnect: true,
+                    action: proto::leave_request::Action::Reconnect as i32,
+                    regions: None,
                 },
             ))
         };
@@ -713,6 +955,7 @@ impl SessionInner {
             SimulateScenario::SignalReconnect => {
                 self.signal_client.close().await;
             }
+            SimulateScenario::FullReconnect => simulate_leave().await?,
             SimulateScenario::Speaker => {
                 self.signal_client
                     .send(proto::signal_request::Message::Simulate(
@@ -779,6 +1022,17 @@ impl SessionInner {
 
                 simulate_leave().await?
             }
+            SimulateScenario::SubscriberBandwidth(bandwidth) => {
+                self.signal_client
+                    .send(proto::signal_request::Message::Simulate(
+                        proto::SimulateScenario {
+                            scenario: Some(
+                                proto::simulate_scenario::Scenario::SubscriberBandwidth(bandwidth),
+                            ),
+                        },
+                    ))
+                    .await;
+            }
         }
         Ok(())
     }
@@ -787,10 +1041,16 @@ impl SessionInner {
         self: &Arc<Self>,
         data: &proto::DataPacket,
         kind: DataPacketKind,
-    ) -> Result<(), EngineError> {
+    ) -> Result<SendReceipt, EngineError> {
         self.ensure_publisher_connected(kind).await?;
-        self.data_channel(kind)
-            .send(&data.encode_to_vec(), true)
+        let tracker = if kind == DataPacketKind::Reliable {
+            &self.reliable_tracker
+        } else {
+            &self.lossy_tracker
+        };
+
+        tracker
+            .send(self.data_channel(kind), &data.encode_to_vec())
             .map_err(Into::into)
     }
 
@@ -800,13 +1060,17 @@ impl SessionInner {
         let reconnect_response = self.signal_client.restart().await?;
         log::info!("received reconnect response: {:?}", reconnect_response);
 
-        let rtc_config = make_rtc_config_reconnect(reconnect_response);
+        let rtc_config =
+            merge_rtc_config(make_rtc_config_reconnect(reconnect_response), &self.options);
+        let (rtc_config, turn_proxy) = relay_turn_servers(rtc_config, &self.options)?;
         self.publisher_pc
             .peer_connection()
             .set_configuration(rtc_config.clone())?;
         self.subscriber_pc
             .peer_connection()
             .set_configuration(rtc_config)?;
+        // The previous relays are closed once the new configuration is applied
+        *self.turn_proxy.lock() = turn_proxy;
 
         if self.has_published.load(Ordering::Acquire) {
             self.publisher_pc
@@ -824,7 +1088,7 @@ impl SessionInner {
     }
 
     /// Wait for PeerState to become PeerState::Connected
-    /// Timeout after ['MAX_ICE_CONNECT_TIMEOUT']
+    /// Timeout after EngineOptions::ice_connect_timeout
     async fn wait_pc_connection(&self) -> EngineResult<()> {
         let wait_connected = async move {
             while self.pc_state.load(Ordering::Acquire) != PeerState::Connected as u8 {
@@ -840,13 +1104,28 @@ impl SessionInner {
 
         tokio::select! {
             res = wait_connected => res,
-            _ = sleep(ICE_CONNECT_TIMEOUT) => {
+            _ = sleep(self.options.ice_connect_timeout) => {
                 let err = EngineError::Connection("wait_pc_connection timed out".to_string());
                 Err(err)
             }
         }
     }
 
+    fn transport(&self, target: proto::SignalTarget) -> &PeerTransport {
+        match target {
+            proto::SignalTarget::Publisher => &self.publisher_pc,
+            proto::SignalTarget::Subscriber => &self.subscriber_pc,
+        }
+    }
+
+    fn primary_target(&self) -> proto::SignalTarget {
+        if self.subscriber_primary {
+            proto::SignalTarget::Subscriber
+        } else {
+            proto::SignalTarget::Publisher
+        }
+    }
+
     /// Start publisher negotiation
     fn publisher_negotiation_needed(self: &Arc<Self>) {
         self.has_published.store(true, Ordering::Release);
@@ -905,7 +1184,7 @@ impl SessionInner {
 
         tokio::select! {
             res = wait_connected => res,
-            _ = sleep(ICE_CONNECT_TIMEOUT) => {
+            _ = sleep(self.options.ice_connect_timeout) => {
                 let err = EngineError::Connection("could not establish publisher connection: timeout".to_string());
                 log::error!("{}", err);
                 Err(err)
@@ -962,6 +1241,7 @@ macro_rules! make_rtc_config {
                             urls: ice_server.urls,
                             username: ice_server.username,
                             password: ice_server.credential,
+                            hostname: String::new(),
                         })
                     }
                     servers
@@ -983,3 +1263,161 @@ macro_rules! make_rtc_config {
 
 make_rtc_config!(make_rtc_config_join, proto::JoinResponse);
 make_rtc_config!(make_rtc_config_reconnect, proto::ReconnectResponse);
+
+/// Apply the RtcConfiguration of the EngineOptions on top of the server configuration.
+/// A relay policy forced by the server is always kept.
+/// The publisher is only negotiated before the first publication when it is the primary
+/// peer connection, or when the participant is expected to publish (no auto subscribe).
+/// View-only participants never negotiate it, and the tracks published on join negotiate it
+/// themselves
+fn negotiate_publisher_on_join(
+    join_response: &proto::JoinResponse,
+    options: &EngineOptions,
+) -> bool {
+    if options.publish_on_join {
+        return false;
+    }
+
+    let can_publish = join_response
+        .participant
+        .as_ref()
+        .and_then(|participant| participant.permission.as_ref())
+        .map_or(true, |permission| {
+            permission.can_publish || permission.can_publish_data
+        });
+    !join_response.subscriber_primary || (can_publish && !options.signal_options.auto_subscribe)
+}
+
+/// libwebrtc can't use a proxy, route the TURN TCP/TLS servers through local relays
+fn relay_turn_servers(
+    mut config: RtcConfiguration,
+    options: &EngineOptions,
+) -> EngineResult<(RtcConfiguration, TurnProxy)> {
+    let (ice_servers, turn_proxy) =
+        TurnProxy::relay_ice_servers(config.ice_servers, options.signal_options.proxy.as_ref())
+            .map_err(|err| {
+                EngineError::Internal(format!("failed to relay the TURN servers: {}", err))
+            })?;
+    config.ice_servers = ice_servers;
+    Ok((config, turn_proxy))
+}
+
+fn merge_rtc_config(server: RtcConfiguration, options: &EngineOptions) -> RtcConfiguration {
+    let Some(custom) = options.rtc_config.clone() else {
+        return server;
+    };
+
+    let mut ice_servers = custom.ice_servers;
+    if !options.replace_ice_servers {
+        ice_servers.extend(server.ice_servers);
+    }
+
+    let ice_transport_type = if server.ice_transport_type == IceTransportsType::Relay {
+        IceTransportsType::Relay
+    } else {
+        custom.ice_transport_type
+    };
+
+    RtcConfiguration {
+        ice_servers,
+        continual_gathering_policy: server.continual_gathering_policy,
+        ice_transport_type,
+    }
+}
+
+/// Feed the buffered amount changes of a publisher data channel to its SendTracker.
+/// Called on the signaling thread
+fn track_dc_sends(
+    dc: &DataChannel,
+    tracker: Arc<SendTracker>,
+    kind: DataPacketKind,
+    emitter: SessionEmitter,
+) {
+    dc.on_buffered_amount_change(Some(Box::new({
+        let tracker = tracker.clone();
+        move |sent_data_size| {
+            if tracker.on_drained(sent_data_size) {
+                let _ = emitter.send(SessionEvent::DataBufferedAmountLow { kind });
+            }
+        }
+    })));
+
+    dc.on_state_change(Some(Box::new(move |state| {
+        if state == DataState::Closed {
+            tracker.close();
+        }
+    })));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ice_server(url: &str) -> IceServer {
+        IceServer {
+            urls: vec![url.to_owned()],
+            username: String::default(),
+            password: String::default(),
+            hostname: String::default(),
+        }
+    }
+
+    #[test]
+    fn test_merge_rtc_config() {
+        let server = RtcConfiguration {
+            ice_servers: vec![ice_server("stun:server")],
+            continual_gathering_policy: ContinualGatheringPolicy::GatherContinually,
+            ice_transport_type: IceTransportsType::All,
+        };
+
+        let mut options = EngineOptions {
+            rtc_config: Some(RtcConfiguration {
+                ice_servers: vec![ice_server("turn:custom")],
+                ice_transport_type: IceTransportsType::Relay,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let merged = merge_rtc_config(server.clone(), &options);
+        assert_eq!(merged.ice_servers.len(), 2);
+        assert_eq!(merged.ice_transport_type, IceTransportsType::Relay);
+
+        options.replace_ice_servers = true;
+        let replaced = merge_rtc_config(server, &options);
+        assert_eq!(replaced.ice_servers.len(), 1);
+        assert_eq!(replaced.ice_servers[0].urls[0], "turn:custom");
+    }
+
+    #[test]
+    fn test_negotiate_publisher_on_join() {
+        let mut join_response = proto::JoinResponse {
+            subscriber_primary: true,
+            ..Default::default()
+        };
+        let mut options = EngineOptions::default();
+        options.signal_options.auto_subscribe = true;
+
+        // View-only participant
+        assert!(!negotiate_publisher_on_join(&join_response, &options));
+
+        join_response.subscriber_primary = false;
+        assert!(negotiate_publisher_on_join(&join_response, &options));
+
+        // The tracks published on join negotiate the publisher once
+        options.publish_on_join = true;
+        assert!(!negotiate_publisher_on_join(&join_response, &options));
+
+        // Expected to publish without subscribing
+        join_response.subscriber_primary = true;
+        options.publish_on_join = false;
+        options.signal_options.auto_subscribe = false;
+        assert!(negotiate_publisher_on_join(&join_response, &options));
+
+        join_response.participant = Some(proto::ParticipantInfo {
+            permission: Some(proto::ParticipantPermission::default()),
+            ..Default::default()
+        });
+        assert!(!negotiate_publisher_on_join(&join_response, &options));
+    }
+}
@@ -0,0 +1,148 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{EngineError, EngineResult};
+use livekit_webrtc::data_channel::{DataChannel, DataChannelError};
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::sync::watch;
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Drained {
+    bytes: u64,
+    closed: bool,
+}
+
+/// Counts the bytes given to a data channel and the bytes that left its send buffer
+/// (reported by on_buffered_amount_change), so a send can wait for its data to be
+/// handed to the SCTP transport
+#[derive(Debug)]
+pub struct SendTracker {
+    send_lock: Mutex<()>,
+    queued: AtomicU64,
+    drained_tx: watch::Sender<Drained>,
+    low_threshold: u64,
+}
+
+/// Resolves once the data of a send left the buffer of the data channel
+#[derive(Debug)]
+pub struct SendReceipt {
+    drained_rx: watch::Receiver<Drained>,
+    target: u64,
+}
+
+impl SendTracker {
+    pub fn new(low_threshold: u64) -> Self {
+        Self {
+            send_lock: Default::default(),
+            queued: Default::default(),
+            drained_tx: watch::channel(Drained::default()).0,
+            low_threshold,
+        }
+    }
+
+    pub fn send(&self, dc: &DataChannel, data: &[u8]) -> Result<SendReceipt, DataChannelError> {
+        // The queued bytes are counted before sending since the buffered amount change
+        // can be reported before send returns
+        let _guard = self.send_lock.lock();
+        let target = self.queued.fetch_add(data.len() as u64, Ordering::AcqRel) + data.len() as u64;
+        if let Err(err) = dc.send(data, true) {
+            self.queued.fetch_sub(data.len() as u64, Ordering::AcqRel);
+            return Err(err);
+        }
+
+        Ok(SendReceipt {
+            drained_rx: self.drained_tx.subscribe(),
+            target,
+        })
+    }
+
+    /// Returns true when the buffered amount went below the low threshold
+    pub fn on_drained(&self, size: u64) -> bool {
+        let mut low = false;
+        self.drained_tx.send_modify(|drained| {
+            let queued = self.queued.load(Ordering::Acquire);
+            let before = queued.saturating_sub(drained.bytes);
+            drained.bytes += size;
+            let after = queued.saturating_sub(drained.bytes);
+            low = before > self.low_threshold && after <= self.low_threshold;
+        });
+        low
+    }
+
+    /// Fails the pending receipts, the remaining data will never be sent
+    pub fn close(&self) {
+        self.drained_tx.send_modify(|drained| drained.closed = true);
+    }
+}
+
+impl SendReceipt {
+    pub async fn wait(mut self) -> EngineResult<()> {
+        loop {
+            let drained = *self.drained_rx.borrow_and_update();
+            if drained.bytes >= self.target {
+                return Ok(());
+            }
+
+            if drained.closed || self.drained_rx.changed().await.is_err() {
+                return Err(EngineError::Connection(
+                    "the data channel closed before the data was sent".to_owned(),
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn receipt(tracker: &SendTracker, len: u64) -> SendReceipt {
+        let target = tracker.queued.fetch_add(len, Ordering::AcqRel) + len;
+        SendReceipt {
+            drained_rx: tracker.drained_tx.subscribe(),
+            target,
+        }
+    }
+
+    fn buffered_amount(tracker: &SendTracker) -> u64 {
+        let drained = tracker.drained_tx.borrow().bytes;
+        tracker
+            .queued
+            .load(Ordering::Acquire)
+            .saturating_sub(drained)
+    }
+
+    #[tokio::test]
+    async fn receipts() {
+        let tracker = SendTracker::new(10);
+        let first = receipt(&tracker, 8);
+        let second = receipt(&tracker, 8);
+        assert_eq!(buffered_amount(&tracker), 16);
+
+        assert!(tracker.on_drained(8));
+        assert_eq!(buffered_amount(&tracker), 8);
+        first.wait().await.unwrap();
+
+        let waiting = tokio::spawn(second.wait());
+        tokio::task::yield_now().await;
+        assert!(!waiting.is_finished());
+        assert!(!tracker.on_drained(8));
+        waiting.await.unwrap().unwrap();
+
+        let third = receipt(&tracker, 4);
+        tracker.close();
+        assert!(third.wait().await.is_err());
+    }
+}
@@ -0,0 +1,241 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use livekit_api::signal_client::ProxyOptions;
+use livekit_webrtc::prelude::IceServer;
+use std::io;
+use std::net::Ipv4Addr;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+/// Forwards the TURN TCP/TLS connections of libwebrtc through a proxy, libwebrtc can't use
+/// one. Each TURN server is replaced by a local listener tunneling its connections through
+/// the proxy. The listeners are closed when the TurnProxy is dropped
+#[derive(Debug, Default)]
+pub struct TurnProxy {
+    relays: Vec<JoinHandle<()>>,
+}
+
+impl TurnProxy {
+    /// Replace the TCP and TLS TURN urls by local relays. The explicit proxy is used for every
+    /// server, otherwise the proxy of the env vars. UDP can't go through the proxy, the other
+    /// urls are kept
+    pub fn relay_ice_servers(
+        ice_servers: Vec<IceServer>,
+        proxy: Option<&ProxyOptions>,
+    ) -> io::Result<(Vec<IceServer>, Self)> {
+        let mut relays = Vec::new();
+        let mut servers = Vec::with_capacity(ice_servers.len());
+
+        for server in ice_servers {
+            let mut direct_urls = Vec::new();
+            for url in server.urls {
+                let turn_url = TurnUrl::parse(&url);
+                let proxy = turn_url.as_ref().and_then(|turn_url| {
+                    proxy
+                        .cloned()
+                        .or_else(|| ProxyOptions::from_env_for_host(&turn_url.host, true))
+                });
+
+                let (Some(turn_url), Some(proxy)) = (turn_url, proxy) else {
+                    direct_urls.push(url);
+                    continue;
+                };
+
+                let (local_port, relay) = spawn_relay(proxy, turn_url.host.clone(), turn_url.port)?;
+                relays.push(relay);
+                servers.push(IceServer {
+                    urls: vec![turn_url.relayed_url(local_port)],
+                    username: server.username.clone(),
+                    password: server.password.clone(),
+                    // The TLS connection is still validated against the TURN server
+                    hostname: turn_url.host,
+                });
+            }
+
+            if !direct_urls.is_empty() {
+                servers.push(IceServer {
+                    urls: direct_urls,
+                    ..server
+                });
+            }
+        }
+
+        Ok((servers, Self { relays }))
+    }
+}
+
+impl Drop for TurnProxy {
+    fn drop(&mut self) {
+        for relay in &self.relays {
+            relay.abort();
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct TurnUrl {
+    secure: bool,
+    host: String,
+    port: u16,
+}
+
+impl TurnUrl {
+    /// None for the urls that don't use TCP (STUN, TURN over UDP)
+    fn parse(url: &str) -> Option<Self> {
+        let (secure, rest) = if let Some(rest) = url.strip_prefix("turns:") {
+            (true, rest)
+        } else {
+            (false, url.strip_prefix("turn:")?)
+        };
+
+        let (addr, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let transport = query
+            .split('&')
+            .find_map(|param| param.strip_prefix("transport="));
+        match transport {
+            Some("tcp") => {}
+            None if secure => {}
+            _ => return None,
+        }
+
+        let (host, port) = match addr.strip_prefix('[') {
+            Some(ipv6) => {
+                let (host, rest) = ipv6.split_once(']')?;
+                (host, rest.strip_prefix(':'))
+            }
+            None => match addr.rsplit_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (addr, None),
+            },
+        };
+
+        if host.is_empty() {
+            return None;
+        }
+
+        let port = match port {
+            Some(port) => port.parse().ok()?,
+            None if secure => 5349,
+            None => 3478,
+        };
+
+        Some(Self {
+            secure,
+            host: host.to_owned(),
+            port,
+        })
+    }
+
+    fn relayed_url(&self, local_port: u16) -> String {
+        let scheme = if self.secure { "turns" } else { "turn" };
+        format!("{}:127.0.0.1:{}?transport=tcp", scheme, local_port)
+    }
+}
+
+fn spawn_relay(proxy: ProxyOptions, host: String, port: u16) -> io::Result<(u16, JoinHandle<()>)> {
+    let listener = std::net::TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+    listener.set_nonblocking(true)?;
+    let listener = TcpListener::from_std(listener)?;
+    let local_port = listener.local_addr()?.port();
+
+    let relay = tokio::spawn(async move {
+        loop {
+            let mut local = match listener.accept().await {
+                Ok((local, _)) => local,
+                Err(err) => {
+                    log::error!("TURN relay of {}:{} stopped: {}", host, port, err);
+                    break;
+                }
+            };
+
+            let proxy = proxy.clone();
+            let host = host.clone();
+            tokio::spawn(async move {
+                match proxy.connect(&host, port).await {
+                    Ok(mut upstream) => {
+                        let _ = tokio::io::copy_bidirectional(&mut local, &mut upstream).await;
+                    }
+                    Err(err) => {
+                        log::warn!("failed to reach the TURN server {}:{}: {}", host, port, err);
+                    }
+                }
+            });
+        }
+    });
+
+    Ok((local_port, relay))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_turn_urls() {
+        assert_eq!(
+            TurnUrl::parse("turns:turn.example.com:443?transport=tcp"),
+            Some(TurnUrl {
+                secure: true,
+                host: "turn.example.com".to_owned(),
+                port: 443,
+            })
+        );
+        assert_eq!(
+            TurnUrl::parse("turn:[::1]?transport=tcp"),
+            Some(TurnUrl {
+                secure: false,
+                host: "::1".to_owned(),
+                port: 3478,
+            })
+        );
+        assert_eq!(TurnUrl::parse("turns:turn.example.com").unwrap().port, 5349);
+        assert_eq!(
+            TurnUrl::parse("turn:turn.example.com:3478?transport=udp"),
+            None
+        );
+        assert_eq!(TurnUrl::parse("turn:turn.example.com:3478"), None);
+        assert_eq!(TurnUrl::parse("stun:stun.example.com:3478"), None);
+    }
+
+    #[tokio::test]
+    async fn relay_tcp_servers() {
+        let proxy = ProxyOptions {
+            url: "http://127.0.0.1:3128".to_owned(),
+            ..Default::default()
+        };
+        let server = IceServer {
+            urls: vec![
+                "turn:turn.example.com:3478?transport=udp".to_owned(),
+                "turns:turn.example.com:443?transport=tcp".to_owned(),
+            ],
+            username: "user".to_owned(),
+            password: "pass".to_owned(),
+            hostname: String::new(),
+        };
+
+        let (servers, _proxy) = TurnProxy::relay_ice_servers(vec![server], Some(&proxy)).unwrap();
+        assert_eq!(servers.len(), 2);
+
+        let relayed = &servers[0];
+        assert!(relayed.urls[0].starts_with("turns:127.0.0.1:"));
+        assert_eq!(relayed.hostname, "turn.example.com");
+        assert_eq!(relayed.username, "user");
+
+        assert_eq!(
+            servers[1].urls,
+            vec!["turn:turn.example.com:3478?transport=udp"]
+        );
+    }
+}
@@ -0,0 +1,295 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Segmenter;
+use livekit_webrtc::native::yuv_helper;
+use livekit_webrtc::video_frame::conversion::{FilterMode, PixelBuffer, PixelFormat};
+use livekit_webrtc::video_frame::native::I420BufferExt;
+use livekit_webrtc::video_frame::{BoxVideoFrame, I420Buffer};
+use livekit_webrtc::video_source::native::VideoProcessor;
+use std::borrow::Cow;
+
+/// What is shown behind the person
+#[derive(Debug, Clone)]
+pub enum Background {
+    /// Blur radius in pixels, at the resolution of the frames
+    Blur(u32),
+    /// Image scaled to the size of the frames
+    Image(PixelBuffer<'static>),
+}
+
+#[derive(Debug, Clone)]
+pub struct BackgroundOptions {
+    pub background: Background,
+    /// Weight of the previous masks (0-1), reduces the flickering on the edges
+    pub mask_smoothing: f32,
+}
+
+impl Default for BackgroundOptions {
+    fn default() -> Self {
+        Self {
+            background: Background::Blur(10),
+            mask_smoothing: 0.5,
+        }
+    }
+}
+
+/// Blurs or replaces the background of the frames using a Segmenter
+pub struct BackgroundProcessor<S: Segmenter> {
+    segmenter: S,
+    options: BackgroundOptions,
+    mask: Vec<u8>,                       // Smoothed, at the input size of the segmenter
+    image: Option<PixelBuffer<'static>>, // Background image at the size of the frames
+}
+
+impl<S: Segmenter> BackgroundProcessor<S> {
+    pub fn new(segmenter: S, options: BackgroundOptions) -> Self {
+        Self {
+            segmenter,
+            options,
+            mask: Vec::new(),
+            image: None,
+        }
+    }
+}
+
+impl<S: Segmenter> VideoProcessor for BackgroundProcessor<S> {
+    fn process(&mut self, frame: BoxVideoFrame) -> Option<BoxVideoFrame> {
+        let mut buffer = PixelBuffer::from_video_buffer(frame.buffer.as_ref());
+        if buffer.format() != PixelFormat::I420 {
+            buffer = buffer.convert(PixelFormat::I420);
+        }
+
+        let (width, height) = (buffer.width(), buffer.height());
+        let (mask_width, mask_height) = self.segmenter.input_size();
+        let input = buffer
+            .scale(mask_width, mask_height, FilterMode::Box)
+            .convert(PixelFormat::ABGR); // R, G, B, A in memory
+
+        let Some(mask) = self.segmenter.segment(input.data()) else {
+            return Some(frame);
+        };
+        if mask.len() != (mask_width * mask_height) as usize {
+            log::warn!("the segmentation mask doesn't match the input size");
+            return Some(frame);
+        }
+        smooth_mask(&mut self.mask, &mask, self.options.mask_smoothing);
+
+        let background = background(&self.options.background, &mut self.image, &buffer);
+        let mut output = I420Buffer::new(width, height);
+        let (stride_y, stride_u, stride_v) = output.strides();
+        let (data_y, data_u, data_v) = output.data_mut();
+        let planes = [(data_y, stride_y), (data_u, stride_u), (data_v, stride_v)];
+        for (plane, (dst, dst_stride)) in planes.into_iter().enumerate() {
+            let (plane_width, plane_height) = PixelFormat::I420.plane_size(plane, width, height);
+            let mut plane_mask = vec![0; (plane_width * plane_height) as usize];
+            yuv_helper::scale_plane(
+                &self.mask,
+                mask_width,
+                mask_width as i32,
+                mask_height as i32,
+                &mut plane_mask,
+                plane_width,
+                plane_width as i32,
+                plane_height as i32,
+                FilterMode::Bilinear,
+            );
+
+            blend_plane(
+                buffer.plane(plane),
+                buffer.strides()[plane] as usize,
+                background.plane(plane),
+                background.strides()[plane] as usize,
+                &plane_mask,
+                dst,
+                dst_stride as usize,
+                plane_width as usize,
+                plane_height as usize,
+            );
+        }
+
+        Some(BoxVideoFrame {
+            rotation: frame.rotation,
+            timestamp_us: frame.timestamp_us,
+            rtp_timestamp: frame.rtp_timestamp,
+            ntp_time_ms: frame.ntp_time_ms,
+            buffer: Box::new(output),
+        })
+    }
+}
+
+fn background<'a>(
+    background: &Background,
+    image: &'a mut Option<PixelBuffer<'static>>,
+    frame: &PixelBuffer,
+) -> Cow<'a, PixelBuffer<'static>> {
+    let (width, height) = (frame.width(), frame.height());
+    match background {
+        Background::Blur(radius) => Cow::Owned(blur(frame, *radius)),
+        Background::Image(source) => {
+            let scaled = image.get_or_insert_with(|| source.convert(PixelFormat::I420));
+            if (scaled.width(), scaled.height()) != (width, height) {
+                *scaled =
+                    source
+                        .convert(PixelFormat::I420)
+                        .scale(width, height, FilterMode::Bilinear);
+            }
+            Cow::Borrowed(scaled)
+        }
+    }
+}
+
+/// Approximation of a gaussian blur, the frame is downscaled, box blurred twice and
+/// upscaled back
+fn blur(frame: &PixelBuffer, radius: u32) -> PixelBuffer<'static> {
+    let (width, height) = (frame.width(), frame.height());
+    let factor = (radius / 2).clamp(1, 8);
+    let mut small = frame.scale(
+        (width / factor).max(2),
+        (height / factor).max(2),
+        FilterMode::Box,
+    );
+
+    let (small_width, small_height) = (small.width(), small.height());
+    let small_radius = (radius / factor).max(1) as usize;
+    for plane in 0..3 {
+        let (plane_width, plane_height) =
+            PixelFormat::I420.plane_size(plane, small_width, small_height);
+        let stride = small.strides()[plane] as usize;
+        // The chroma planes have half the resolution
+        let radius = if plane == 0 {
+            small_radius
+        } else {
+            small_radius.div_ceil(2)
+        };
+        for _ in 0..2 {
+            box_blur_plane(
+                small.plane_mut(plane),
+                stride,
+                plane_width as usize,
+                plane_height as usize,
+                radius,
+            );
+        }
+    }
+
+    small.scale(width, height, FilterMode::Bilinear)
+}
+
+/// Blurs in place with a box filter (horizontal then vertical), the edges are clamped
+fn box_blur_plane(plane: &mut [u8], stride: usize, width: usize, height: usize, radius: usize) {
+    let mut line = vec![0; width.max(height)];
+    for y in 0..height {
+        let row = &mut plane[y * stride..][..width];
+        line[..width].copy_from_slice(row);
+        blur_line(&line[..width], radius, |x, v| row[x] = v);
+    }
+
+    for x in 0..width {
+        for (y, v) in line[..height].iter_mut().enumerate() {
+            *v = plane[y * stride + x];
+        }
+        blur_line(&line[..height], radius, |y, v| plane[y * stride + x] = v);
+    }
+}
+
+fn blur_line(src: &[u8], radius: usize, mut out: impl FnMut(usize, u8)) {
+    if src.is_empty() {
+        return;
+    }
+
+    let (len, radius) = (src.len() as isize, radius as isize);
+    let at = |i: isize| src[i.clamp(0, len - 1) as usize] as u32;
+    let window = 2 * radius as u32 + 1;
+    let mut sum: u32 = (-radius..=radius).map(at).sum();
+    for i in 0..len {
+        out(i as usize, ((sum + window / 2) / window) as u8);
+        sum = sum + at(i + radius + 1) - at(i - radius);
+    }
+}
+
+/// dst = fg * mask + bg * (1 - mask), the mask has no padding
+#[allow(clippy::too_many_arguments)]
+fn blend_plane(
+    fg: &[u8],
+    fg_stride: usize,
+    bg: &[u8],
+    bg_stride: usize,
+    mask: &[u8],
+    dst: &mut [u8],
+    dst_stride: usize,
+    width: usize,
+    height: usize,
+) {
+    for y in 0..height {
+        let fg = &fg[y * fg_stride..][..width];
+        let bg = &bg[y * bg_stride..][..width];
+        let mask = &mask[y * width..][..width];
+        let dst = &mut dst[y * dst_stride..][..width];
+        for x in 0..width {
+            let m = mask[x] as u32;
+            dst[x] = ((fg[x] as u32 * m + bg[x] as u32 * (255 - m) + 127) / 255) as u8;
+        }
+    }
+}
+
+/// Exponential moving average of the masks
+fn smooth_mask(smoothed: &mut Vec<u8>, mask: &[u8], smoothing: f32) {
+    if smoothed.len() != mask.len() {
+        smoothed.clear();
+        smoothed.extend_from_slice(mask);
+        return;
+    }
+
+    let smoothing = smoothing.clamp(0.0, 1.0);
+    for (s, m) in smoothed.iter_mut().zip(mask) {
+        *s = (*s as f32 * smoothing + *m as f32 * (1.0 - smoothing)).round() as u8;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn box_blur() {
+        // 5x3 plane with a stride of 6
+        let mut plane = vec![90; 6 * 3];
+        box_blur_plane(&mut plane, 6, 5, 3, 2);
+        assert!(plane.iter().all(|v| *v == 90));
+
+        let mut plane = vec![0; 6 * 3];
+        plane[6 + 2] = 225;
+        plane[5] = 7; // The padding isn't read
+        box_blur_plane(&mut plane, 6, 5, 3, 1);
+        assert_eq!(&plane[..5], &[0, 25, 25, 25, 0]);
+        assert_eq!(&plane[6..11], &[0, 25, 25, 25, 0]);
+        assert_eq!(plane[5], 7);
+    }
+
+    #[test]
+    fn blend() {
+        let fg = [200, 200, 200, 0];
+        let bg = [100, 100, 100, 0];
+        let mut dst = [0; 3];
+        blend_plane(&fg, 4, &bg, 4, &[255, 0, 128], &mut dst, 3, 3, 1);
+        assert_eq!(dst, [200, 100, 150]);
+
+        let mut smoothed = Vec::new();
+        smooth_mask(&mut smoothed, &[0, 255], 0.5);
+        assert_eq!(smoothed, [0, 255]);
+        smooth_mask(&mut smoothed, &[255, 255], 0.5);
+        assert_eq!(smoothed, [128, 255]);
+    }
+}
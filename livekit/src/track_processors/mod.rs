@@ -0,0 +1,48 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Video processors matching the track-processors of the JS SDK: background blur and
+//! virtual backgrounds, plus overlays (logos, name badges, timestamps).
+//!
+//! ```ignore
+//! let segmenter = OnnxSegmenter::new("selfie_segmentation.onnx", 256, 256)?;
+//! let processor = BackgroundProcessor::new(segmenter, BackgroundOptions::default());
+//! track.add_processor(processor);
+//! ```
+//!
+//! OnnxSegmenter requires the `track-processors` feature and the text overlays
+//! require the `overlay-text` feature.
+
+mod background;
+#[cfg(feature = "track-processors")]
+mod onnx;
+mod overlay;
+
+pub use background::{Background, BackgroundOptions, BackgroundProcessor};
+#[cfg(feature = "track-processors")]
+pub use onnx::OnnxSegmenter;
+#[cfg(feature = "overlay-text")]
+pub use overlay::TextStyle;
+pub use overlay::{OverlayOptions, OverlayPosition, OverlayProcessor};
+
+/// Separates the person from the background
+pub trait Segmenter: Send {
+    /// Size of the images given to segment
+    fn input_size(&self) -> (u32, u32);
+
+    /// Receives an RGBA image of input_size (without padding) and returns the
+    /// probability (0-255) of each pixel to be in the foreground, row by row.
+    /// Returning None leaves the frame unchanged
+    fn segment(&mut self, rgba: &[u8]) -> Option<Vec<u8>>;
+}
@@ -0,0 +1,84 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Segmenter;
+use std::path::Path;
+use tract_onnx::prelude::*;
+
+/// Segmentation using an ONNX model (run with tract), e.g. MediaPipe's selfie segmentation.
+/// The model takes a 1xHxWx3 RGB image with values between 0 and 1, and returns the
+/// foreground probability of each pixel (the last channel is used when there are several)
+pub struct OnnxSegmenter {
+    model: TypedRunnableModel<TypedModel>,
+    width: u32,
+    height: u32,
+}
+
+impl OnnxSegmenter {
+    pub fn new(model_path: impl AsRef<Path>, width: u32, height: u32) -> TractResult<Self> {
+        let model = tract_onnx::onnx()
+            .model_for_path(model_path)?
+            .with_input_fact(0, f32::fact([1, height as usize, width as usize, 3]).into())?
+            .into_optimized()?
+            .into_runnable()?;
+
+        Ok(Self {
+            model,
+            width,
+            height,
+        })
+    }
+
+    fn run(&self, rgba: &[u8]) -> TractResult<Vec<u8>> {
+        let (width, height) = (self.width as usize, self.height as usize);
+        let input: Tensor =
+            tract_ndarray::Array4::from_shape_fn((1, height, width, 3), |(_, y, x, c)| {
+                rgba[(y * width + x) * 4 + c] as f32 / 255.0
+            })
+            .into();
+
+        let outputs = self.model.run(tvec!(input.into()))?;
+        let output = outputs[0].to_array_view::<f32>()?;
+        let channels = output.len() / (width * height);
+        if channels == 0 {
+            return Err(TractError::msg(format!(
+                "unexpected output shape {:?}",
+                output.shape()
+            )));
+        }
+
+        Ok(output
+            .iter()
+            .skip(channels - 1)
+            .step_by(channels)
+            .map(|p| (p.clamp(0.0, 1.0) * 255.0).round() as u8)
+            .collect())
+    }
+}
+
+impl Segmenter for OnnxSegmenter {
+    fn input_size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn segment(&mut self, rgba: &[u8]) -> Option<Vec<u8>> {
+        match self.run(rgba) {
+            Ok(mask) => Some(mask),
+            Err(err) => {
+                log::error!("failed to run the segmentation model: {}", err);
+                None
+            }
+        }
+    }
+}
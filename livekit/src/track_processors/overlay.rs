@@ -0,0 +1,498 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use livekit_webrtc::video_frame::conversion::{PixelBuffer, PixelFormat};
+use livekit_webrtc::video_frame::native::I420BufferExt;
+use livekit_webrtc::video_frame::{BoxVideoFrame, I420Buffer};
+use livekit_webrtc::video_source::native::VideoProcessor;
+#[cfg(feature = "overlay-text")]
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverlayPosition {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    Center,
+    /// Top left corner of the overlay in pixels, the margin is ignored
+    Custom {
+        x: i32,
+        y: i32,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct OverlayOptions {
+    pub position: OverlayPosition,
+    /// Distance to the edges of the frame in pixels
+    pub margin: u32,
+    /// Multiplied with the alpha of the overlay (0-1)
+    pub opacity: f32,
+}
+
+impl Default for OverlayOptions {
+    fn default() -> Self {
+        Self {
+            position: OverlayPosition::TopLeft,
+            margin: 16,
+            opacity: 1.0,
+        }
+    }
+}
+
+/// Font and colors of a text overlay, the colors are RGBA
+#[cfg(feature = "overlay-text")]
+#[derive(Clone)]
+pub struct TextStyle {
+    pub font: Arc<fontdue::Font>,
+    /// Height of the text in pixels
+    pub size: f32,
+    pub color: [u8; 4],
+    /// Filled box behind the text, e.g. for name badges
+    pub background: Option<[u8; 4]>,
+    /// Space between the text and the edges of the background in pixels
+    pub padding: u32,
+}
+
+#[cfg(feature = "overlay-text")]
+impl TextStyle {
+    pub fn new(font: fontdue::Font) -> Self {
+        Self {
+            font: Arc::new(font),
+            size: 24.0,
+            color: [255, 255, 255, 255],
+            background: Some([0, 0, 0, 160]),
+            padding: 6,
+        }
+    }
+}
+
+enum Content {
+    Image(YuvaImage),
+    #[cfg(feature = "overlay-text")]
+    Text {
+        text: Box<dyn FnMut() -> String + Send>,
+        style: TextStyle,
+        rendered: Option<(String, YuvaImage)>,
+    },
+}
+
+struct Layer {
+    content: Content,
+    options: OverlayOptions,
+}
+
+/// Alpha-composites images and text onto the frames, in the order they were added.
+/// The overlays are drawn at their own size whatever the resolution of the frames,
+/// the parts outside of the frames are clipped
+#[derive(Default)]
+pub struct OverlayProcessor {
+    layers: Vec<Layer>,
+}
+
+impl OverlayProcessor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Formats without an alpha channel are drawn opaque
+    pub fn add_image(&mut self, image: &PixelBuffer, options: OverlayOptions) {
+        let converted;
+        let rgba = if image.format() == PixelFormat::ABGR {
+            image
+        } else {
+            converted = image.convert(PixelFormat::ABGR); // R, G, B, A in memory
+            &converted
+        };
+
+        let image = YuvaImage::from_rgba(
+            rgba.data(),
+            rgba.strides()[0] as usize,
+            rgba.width(),
+            rgba.height(),
+            options.opacity,
+        );
+        self.layers.push(Layer {
+            content: Content::Image(image),
+            options,
+        });
+    }
+
+    #[cfg(feature = "overlay-text")]
+    pub fn add_text(&mut self, text: impl Into<String>, style: TextStyle, options: OverlayOptions) {
+        let text = text.into();
+        self.add_dynamic_text(move || text.clone(), style, options);
+    }
+
+    /// The text is queried for each frame (e.g. a clock) and only rendered again when it
+    /// changes
+    #[cfg(feature = "overlay-text")]
+    pub fn add_dynamic_text(
+        &mut self,
+        text: impl FnMut() -> String + Send + 'static,
+        style: TextStyle,
+        options: OverlayOptions,
+    ) {
+        self.layers.push(Layer {
+            content: Content::Text {
+                text: Box::new(text),
+                style,
+                rendered: None,
+            },
+            options,
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.layers.clear();
+    }
+}
+
+impl VideoProcessor for OverlayProcessor {
+    fn process(&mut self, frame: BoxVideoFrame) -> Option<BoxVideoFrame> {
+        if self.layers.is_empty() {
+            return Some(frame);
+        }
+
+        let (width, height) = (frame.buffer.width(), frame.buffer.height());
+        let mut output = I420Buffer::new(width, height);
+        let mut buffer = PixelBuffer::from_video_buffer(frame.buffer.as_ref());
+        if buffer.format() != PixelFormat::I420 {
+            buffer = buffer.convert(PixelFormat::I420);
+        }
+
+        let (stride_y, stride_u, stride_v) = output.strides();
+        let (data_y, data_u, data_v) = output.data_mut();
+        let planes = [(data_y, stride_y), (data_u, stride_u), (data_v, stride_v)];
+        let mut planes = planes.map(|(data, stride)| (data, stride as usize));
+        for (plane, (dst, dst_stride)) in planes.iter_mut().enumerate() {
+            let (plane_width, plane_height) = PixelFormat::I420.plane_size(plane, width, height);
+            let src = buffer.plane(plane);
+            let src_stride = buffer.strides()[plane] as usize;
+            for y in 0..plane_height as usize {
+                dst[y * *dst_stride..][..plane_width as usize]
+                    .copy_from_slice(&src[y * src_stride..][..plane_width as usize]);
+            }
+        }
+
+        for layer in &mut self.layers {
+            let image = match &mut layer.content {
+                Content::Image(image) => &*image,
+                #[cfg(feature = "overlay-text")]
+                Content::Text {
+                    text,
+                    style,
+                    rendered,
+                } => {
+                    let text = text();
+                    if rendered.as_ref().map_or(true, |(last, _)| *last != text) {
+                        let (rgba, text_width, text_height) = render_text(&text, style);
+                        let image = YuvaImage::from_rgba(
+                            &rgba,
+                            text_width as usize * 4,
+                            text_width,
+                            text_height,
+                            layer.options.opacity,
+                        );
+                        *rendered = Some((text, image));
+                    }
+                    &rendered.as_ref().unwrap().1
+                }
+            };
+
+            let (x, y) = position(&layer.options, width, height, image.width, image.height);
+            image.composite(&mut planes, width as usize, height as usize, x, y);
+        }
+
+        Some(BoxVideoFrame {
+            rotation: frame.rotation,
+            timestamp_us: frame.timestamp_us,
+            rtp_timestamp: frame.rtp_timestamp,
+            ntp_time_ms: frame.ntp_time_ms,
+            buffer: Box::new(output),
+        })
+    }
+}
+
+/// Top left corner of the overlay, rounded down to even coordinates so the
+/// chroma of the overlay lines up with the chroma of the frame
+fn position(options: &OverlayOptions, width: u32, height: u32, w: u32, h: u32) -> (i64, i64) {
+    let (width, height, w, h) = (width as i64, height as i64, w as i64, h as i64);
+    let margin = options.margin as i64;
+    let (x, y) = match options.position {
+        OverlayPosition::TopLeft => (margin, margin),
+        OverlayPosition::TopRight => (width - w - margin, margin),
+        OverlayPosition::BottomLeft => (margin, height - h - margin),
+        OverlayPosition::BottomRight => (width - w - margin, height - h - margin),
+        OverlayPosition::Center => ((width - w) / 2, (height - h) / 2),
+        OverlayPosition::Custom { x, y } => (x as i64, y as i64),
+    };
+    (x - x.rem_euclid(2), y - y.rem_euclid(2))
+}
+
+/// Overlay converted to I420 (BT.601, limited range) with an alpha plane for the luma
+/// and one for the chroma, the opacity is applied to the alpha
+#[derive(Debug, Clone)]
+struct YuvaImage {
+    width: u32,
+    height: u32,
+    y: Vec<u8>,
+    a: Vec<u8>,
+    u: Vec<u8>,
+    v: Vec<u8>,
+    chroma_a: Vec<u8>,
+}
+
+impl YuvaImage {
+    fn from_rgba(rgba: &[u8], stride: usize, width: u32, height: u32, opacity: f32) -> Self {
+        let (w, h) = (width as usize, height as usize);
+        let (cw, ch) = (w.div_ceil(2), h.div_ceil(2));
+        let opacity = (opacity.clamp(0.0, 1.0) * 255.0).round() as u32;
+        let pixel = |x: usize, y: usize| {
+            let p = &rgba[y * stride + x * 4..][..4];
+            let a = (p[3] as u32 * opacity + 127) / 255;
+            (p[0] as i32, p[1] as i32, p[2] as i32, a)
+        };
+
+        let mut image = Self {
+            width,
+            height,
+            y: Vec::with_capacity(w * h),
+            a: Vec::with_capacity(w * h),
+            u: Vec::with_capacity(cw * ch),
+            v: Vec::with_capacity(cw * ch),
+            chroma_a: Vec::with_capacity(cw * ch),
+        };
+
+        for y in 0..h {
+            for x in 0..w {
+                let (r, g, b, a) = pixel(x, y);
+                image
+                    .y
+                    .push((((66 * r + 129 * g + 25 * b + 128) >> 8) + 16) as u8);
+                image.a.push(a as u8);
+            }
+        }
+
+        // The color of a 2x2 block is the average weighted by the alpha, so the
+        // transparent pixels don't bleed on the edges
+        for cy in 0..ch {
+            for cx in 0..cw {
+                let (mut sum_r, mut sum_g, mut sum_b, mut sum_a, mut count) = (0, 0, 0, 0, 0);
+                for y in (cy * 2)..(cy * 2 + 2).min(h) {
+                    for x in (cx * 2)..(cx * 2 + 2).min(w) {
+                        let (r, g, b, a) = pixel(x, y);
+                        sum_r += r * a as i32;
+                        sum_g += g * a as i32;
+                        sum_b += b * a as i32;
+                        sum_a += a;
+                        count += 1;
+                    }
+                }
+
+                let (r, g, b) = match sum_a as i32 {
+                    0 => (0, 0, 0),
+                    a => (sum_r / a, sum_g / a, sum_b / a),
+                };
+                image
+                    .u
+                    .push((((-38 * r - 74 * g + 112 * b + 128) >> 8) + 128) as u8);
+                image
+                    .v
+                    .push((((112 * r - 94 * g - 18 * b + 128) >> 8) + 128) as u8);
+                image.chroma_a.push((sum_a / count) as u8);
+            }
+        }
+        image
+    }
+
+    /// Blends the image at (x, y) of an I420 frame, x and y must be even
+    fn composite(
+        &self,
+        planes: &mut [(&mut [u8], usize); 3],
+        width: usize,
+        height: usize,
+        x: i64,
+        y: i64,
+    ) {
+        let (w, h) = (self.width as usize, self.height as usize);
+        let [(data_y, stride_y), (data_u, stride_u), (data_v, stride_v)] = planes;
+        let size = (width, height, w, h);
+        composite_plane(data_y, *stride_y, size, &self.y, &self.a, x, y);
+
+        let size = (
+            width.div_ceil(2),
+            height.div_ceil(2),
+            w.div_ceil(2),
+            h.div_ceil(2),
+        );
+        let (x, y) = (x / 2, y / 2);
+        composite_plane(data_u, *stride_u, size, &self.u, &self.chroma_a, x, y);
+        composite_plane(data_v, *stride_v, size, &self.v, &self.chroma_a, x, y);
+    }
+}
+
+/// Alpha blends src at (x, y) of dst, the parts outside of dst are clipped.
+/// size is (dst width, dst height, src width, src height), src has no padding
+fn composite_plane(
+    dst: &mut [u8],
+    dst_stride: usize,
+    (dst_width, dst_height, src_width, src_height): (usize, usize, usize, usize),
+    src: &[u8],
+    alpha: &[u8],
+    x: i64,
+    y: i64,
+) {
+    let x_start = x.max(0) as usize;
+    let y_start = y.max(0) as usize;
+    let x_end = (x + src_width as i64).clamp(0, dst_width as i64) as usize;
+    let y_end = (y + src_height as i64).clamp(0, dst_height as i64) as usize;
+    if x_start >= x_end || y_start >= y_end {
+        return;
+    }
+
+    let len = x_end - x_start;
+    let sx = (x_start as i64 - x) as usize;
+    for dy in y_start..y_end {
+        let offset = (dy as i64 - y) as usize * src_width + sx;
+        let src = &src[offset..][..len];
+        let alpha = &alpha[offset..][..len];
+        let dst = &mut dst[dy * dst_stride + x_start..][..len];
+        for ((d, s), a) in dst.iter_mut().zip(src).zip(alpha) {
+            let a = *a as u32;
+            *d = ((*s as u32 * a + *d as u32 * (255 - a) + 127) / 255) as u8;
+        }
+    }
+}
+
+/// Renders the lines of text into an RGBA image (without padding)
+#[cfg(feature = "overlay-text")]
+fn render_text(text: &str, style: &TextStyle) -> (Vec<u8>, u32, u32) {
+    let font = &style.font;
+    let (ascent, line_height) = match font.horizontal_line_metrics(style.size) {
+        Some(metrics) => (metrics.ascent, metrics.new_line_size),
+        None => (style.size, style.size * 1.2),
+    };
+
+    let lines: Vec<_> = text.lines().collect();
+    let line_width = |line: &str| -> f32 {
+        line.chars()
+            .map(|c| font.metrics(c, style.size).advance_width)
+            .sum()
+    };
+    let text_width = lines.iter().map(|l| line_width(l)).fold(0.0, f32::max);
+    let padding = style.padding as usize;
+    let width = text_width.ceil() as usize + padding * 2;
+    let height = (line_height * lines.len().max(1) as f32).ceil() as usize + padding * 2;
+
+    let mut rgba = vec![0; width * height * 4];
+    if let Some(background) = style.background {
+        for pixel in rgba.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&background);
+        }
+    }
+
+    for (i, line) in lines.iter().enumerate() {
+        let baseline = padding as f32 + ascent + line_height * i as f32;
+        let mut pen_x = padding as f32;
+        for c in line.chars() {
+            let (metrics, coverage) = font.rasterize(c, style.size);
+            let left = pen_x.round() as i64 + metrics.xmin as i64;
+            let top = baseline.round() as i64 - metrics.height as i64 - metrics.ymin as i64;
+            for (j, coverage) in coverage.iter().enumerate() {
+                let x = left + (j % metrics.width.max(1)) as i64;
+                let y = top + (j / metrics.width.max(1)) as i64;
+                if x < 0 || y < 0 || x >= width as i64 || y >= height as i64 {
+                    continue;
+                }
+
+                let offset = (y as usize * width + x as usize) * 4;
+                blend_over(&mut rgba[offset..][..4], style.color, *coverage);
+            }
+            pen_x += metrics.advance_width;
+        }
+    }
+
+    (rgba, width as u32, height as u32)
+}
+
+/// Draws color with the given coverage over an RGBA pixel (non-premultiplied)
+#[cfg(feature = "overlay-text")]
+fn blend_over(dst: &mut [u8], color: [u8; 4], coverage: u8) {
+    let src_a = color[3] as u32 * coverage as u32 / 255;
+    let dst_a = dst[3] as u32 * (255 - src_a) / 255;
+    let out_a = src_a + dst_a;
+    if out_a == 0 {
+        return;
+    }
+
+    for (d, c) in dst[..3].iter_mut().zip(color) {
+        *d = ((c as u32 * src_a + *d as u32 * dst_a) / out_a) as u8;
+    }
+    dst[3] = out_a as u8;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn yuva_conversion() {
+        // 3x1 with a padding of one pixel: opaque white, transparent black and
+        // half transparent red
+        let rgba = [255, 255, 255, 255, 0, 0, 0, 0, 255, 0, 0, 128, 9, 9, 9, 9];
+        let image = YuvaImage::from_rgba(&rgba, 16, 3, 1, 1.0);
+        assert_eq!(image.y, [235, 16, 82]);
+        assert_eq!(image.a, [255, 0, 128]);
+        // The transparent pixel doesn't change the color of the first block
+        assert_eq!(image.u, [128, 90]);
+        assert_eq!(image.v, [128, 240]);
+        assert_eq!(image.chroma_a, [127, 128]);
+
+        let image = YuvaImage::from_rgba(&rgba, 16, 3, 1, 0.5);
+        assert_eq!(image.a, [128, 0, 64]);
+    }
+
+    #[test]
+    fn composite_clipping() {
+        // 3x3 plane with a stride of 4
+        let mut dst = vec![0; 4 * 3];
+        let (src, alpha) = ([100; 4], [255, 255, 255, 0]);
+        composite_plane(&mut dst, 4, (3, 3, 2, 2), &src, &alpha, 2, -1);
+        assert_eq!(dst, [0, 0, 100, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+        composite_plane(&mut dst, 4, (3, 3, 2, 2), &src, &alpha, 0, 1);
+        assert_eq!(dst, [0, 0, 100, 0, 100, 100, 0, 0, 100, 0, 0, 0]);
+
+        composite_plane(&mut dst, 4, (3, 3, 2, 2), &src, &alpha, 5, 5);
+        assert_eq!(dst, [0, 0, 100, 0, 100, 100, 0, 0, 100, 0, 0, 0]);
+    }
+
+    #[test]
+    fn positions() {
+        let options = |position| OverlayOptions {
+            position,
+            margin: 10,
+            ..Default::default()
+        };
+        let pos = |p| position(&options(p), 640, 360, 101, 50);
+        assert_eq!(pos(OverlayPosition::TopLeft), (10, 10));
+        assert_eq!(pos(OverlayPosition::TopRight), (528, 10));
+        assert_eq!(pos(OverlayPosition::BottomLeft), (10, 300));
+        assert_eq!(pos(OverlayPosition::BottomRight), (528, 300));
+        assert_eq!(pos(OverlayPosition::Center), (268, 154));
+        assert_eq!(pos(OverlayPosition::Custom { x: -3, y: 7 }), (-4, 6));
+    }
+}
@@ -0,0 +1,272 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Publishing to a WHIP endpoint (e.g. LiveKit Ingress) and playing from a WHEP endpoint,
+//! without a room session. The candidates are gathered before sending the offer, the
+//! endpoints don't need to support trickle ICE
+
+use crate::prelude::LocalTrack;
+use crate::rtc_engine::lk_runtime::LkRuntime;
+use livekit_webrtc::prelude::*;
+use livekit_webrtc::session_description::SdpParseError;
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, LOCATION};
+use reqwest::StatusCode;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::{mpsc, watch};
+
+const SDP_CONTENT_TYPE: &str = "application/sdp";
+
+#[derive(Error, Debug)]
+pub enum WhipError {
+    #[error("http error: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("endpoint responded with {0}: {1}")]
+    Status(StatusCode, String),
+    #[error("invalid url: {0}")]
+    Url(#[from] url::ParseError),
+    #[error("rtc error: {0}")]
+    Rtc(#[from] RtcError),
+    #[error("invalid answer: {0}")]
+    Sdp(#[from] SdpParseError),
+    #[error("missing local description")]
+    NoLocalDescription,
+}
+
+pub type WhipResult<T> = Result<T, WhipError>;
+
+#[derive(Debug, Clone)]
+pub struct WhipOptions {
+    /// Sent as a bearer token, e.g. the stream key of a LiveKit Ingress
+    pub token: Option<String>,
+    pub ice_servers: Vec<IceServer>,
+    /// The offer is sent with the candidates gathered within this delay
+    pub ice_gathering_timeout: Duration,
+}
+
+impl Default for WhipOptions {
+    fn default() -> Self {
+        Self {
+            token: None,
+            ice_servers: Vec::new(),
+            ice_gathering_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Peer connection negotiated with a WHIP or WHEP endpoint, the resource is deleted on close
+pub struct WhipSession {
+    peer_connection: PeerConnection,
+    resource_url: Option<url::Url>,
+    token: Option<String>,
+    client: reqwest::Client,
+    _lk_runtime: Arc<LkRuntime>,
+}
+
+impl std::fmt::Debug for WhipSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WhipSession")
+            .field("resource_url", &self.resource_url)
+            .field("state", &self.connection_state())
+            .finish()
+    }
+}
+
+impl WhipSession {
+    /// Publish the tracks to a WHIP endpoint
+    pub async fn publish(
+        endpoint: &str,
+        tracks: &[LocalTrack],
+        options: WhipOptions,
+    ) -> WhipResult<Self> {
+        let (lk_runtime, peer_connection) = create_peer_connection(&options)?;
+        let stream_id = uuid::Uuid::new_v4().to_string();
+        for track in tracks {
+            peer_connection.add_transceiver(
+                track.rtc_track(),
+                RtpTransceiverInit {
+                    direction: RtpTransceiverDirection::SendOnly,
+                    stream_ids: vec![stream_id.clone()],
+                    send_encodings: Vec::new(),
+                },
+            )?;
+        }
+
+        Self::negotiate(endpoint, lk_runtime, peer_connection, options).await
+    }
+
+    /// Play the audio and video of a WHEP endpoint, the received tracks are sent on the
+    /// returned channel
+    pub async fn play(
+        endpoint: &str,
+        options: WhipOptions,
+    ) -> WhipResult<(Self, mpsc::UnboundedReceiver<MediaStreamTrack>)> {
+        let (lk_runtime, peer_connection) = create_peer_connection(&options)?;
+        for media_type in [MediaType::Audio, MediaType::Video] {
+            peer_connection.add_transceiver_for_media(
+                media_type,
+                RtpTransceiverInit {
+                    direction: RtpTransceiverDirection::RecvOnly,
+                    stream_ids: Vec::new(),
+                    send_encodings: Vec::new(),
+                },
+            )?;
+        }
+
+        let (track_tx, track_rx) = mpsc::unbounded_channel();
+        peer_connection.on_track(Some(Box::new(move |event| {
+            let _ = track_tx.send(event.track);
+        })));
+
+        let session = Self::negotiate(endpoint, lk_runtime, peer_connection, options).await?;
+        Ok((session, track_rx))
+    }
+
+    pub fn peer_connection(&self) -> &PeerConnection {
+        &self.peer_connection
+    }
+
+    pub fn connection_state(&self) -> PeerConnectionState {
+        self.peer_connection.connection_state()
+    }
+
+    /// Close the peer connection and delete the resource on the endpoint
+    pub async fn close(self) -> WhipResult<()> {
+        self.peer_connection.close();
+
+        let Some(resource_url) = self.resource_url else {
+            return Ok(());
+        };
+        let mut request = self.client.delete(resource_url);
+        if let Some(token) = &self.token {
+            request = request.header(AUTHORIZATION, format!("Bearer {}", token));
+        }
+        check_status(request.send().await?).await?;
+        Ok(())
+    }
+
+    async fn negotiate(
+        endpoint: &str,
+        lk_runtime: Arc<LkRuntime>,
+        peer_connection: PeerConnection,
+        options: WhipOptions,
+    ) -> WhipResult<Self> {
+        let endpoint = url::Url::parse(endpoint)?;
+        let offer = gather_offer(&peer_connection, options.ice_gathering_timeout).await?;
+
+        let client = reqwest::Client::new();
+        let mut request = client
+            .post(endpoint.clone())
+            .header(CONTENT_TYPE, SDP_CONTENT_TYPE)
+            .body(offer.to_string());
+        if let Some(token) = &options.token {
+            request = request.header(AUTHORIZATION, format!("Bearer {}", token));
+        }
+
+        let response = check_status(request.send().await?).await?;
+        let location = response
+            .headers()
+            .get(LOCATION)
+            .and_then(|location| location.to_str().ok());
+        let resource_url = resource_url(&endpoint, location)?;
+
+        let answer = SessionDescription::parse(&response.text().await?, SdpType::Answer)?;
+        peer_connection.set_remote_description(answer).await?;
+
+        Ok(Self {
+            peer_connection,
+            resource_url,
+            token: options.token,
+            client,
+            _lk_runtime: lk_runtime,
+        })
+    }
+}
+
+fn create_peer_connection(options: &WhipOptions) -> WhipResult<(Arc<LkRuntime>, PeerConnection)> {
+    let lk_runtime = LkRuntime::instance();
+    let peer_connection = lk_runtime
+        .pc_factory()
+        .create_peer_connection(RtcConfiguration {
+            ice_servers: options.ice_servers.clone(),
+            continual_gathering_policy: ContinualGatheringPolicy::GatherOnce,
+            ice_transport_type: IceTransportsType::All,
+        })?;
+    Ok((lk_runtime, peer_connection))
+}
+
+/// Offer including the candidates gathered before the timeout
+async fn gather_offer(
+    peer_connection: &PeerConnection,
+    timeout: Duration,
+) -> WhipResult<SessionDescription> {
+    let (gathering_tx, mut gathering_rx) = watch::channel(IceGatheringState::New);
+    peer_connection.on_ice_gathering_state_change(Some(Box::new(move |state| {
+        let _ = gathering_tx.send(state);
+    })));
+
+    let offer = peer_connection
+        .create_offer(OfferOptions::default())
+        .await?;
+    peer_connection.set_local_description(offer).await?;
+
+    let gathered = gathering_rx.wait_for(|state| *state == IceGatheringState::Complete);
+    if tokio::time::timeout(timeout, gathered).await.is_err() {
+        log::warn!("ice gathering timed out, sending the offer with the gathered candidates");
+    }
+    peer_connection.on_ice_gathering_state_change(None);
+
+    peer_connection
+        .current_local_description()
+        .ok_or(WhipError::NoLocalDescription)
+}
+
+/// The location of the created resource can be relative to the endpoint
+fn resource_url(endpoint: &url::Url, location: Option<&str>) -> WhipResult<Option<url::Url>> {
+    Ok(location
+        .map(|location| endpoint.join(location))
+        .transpose()?)
+}
+
+async fn check_status(response: reqwest::Response) -> WhipResult<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let body = response.text().await.unwrap_or_default();
+    Err(WhipError::Status(status, body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resource_location() {
+        let endpoint = url::Url::parse("https://ingress.example.com/w/stream").unwrap();
+        let url = |location| resource_url(&endpoint, location).unwrap().map(String::from);
+
+        assert_eq!(
+            url(Some("/w/stream/resource/1")).as_deref(),
+            Some("https://ingress.example.com/w/stream/resource/1")
+        );
+        assert_eq!(
+            url(Some("https://edge.example.com/r/1")).as_deref(),
+            Some("https://edge.example.com/r/1")
+        );
+        assert_eq!(url(None), None);
+    }
+}
@@ -33,7 +33,13 @@ fn main() {
         "src/peer_connection_factory.rs",
         "src/media_stream.rs",
         "src/media_stream_track.rs",
+        "src/apm.rs",
         "src/audio_track.rs",
+        "src/audio_device_manager.rs",
+        "src/desktop_capturer.rs",
+        "src/system_audio_capturer.rs",
+        "src/encoded_frame_tap.rs",
+        "src/video_capture.rs",
         "src/video_track.rs",
         "src/data_channel.rs",
         "src/frame_cryptor.rs",
@@ -50,6 +56,7 @@ fn main() {
         "src/helper.rs",
         "src/yuv_helper.rs",
         "src/audio_resampler.rs",
+        "src/vad.rs",
         "src/android.rs",
     ]);
 
@@ -70,6 +77,10 @@ fn main() {
         "src/media_stream.cpp",
         "src/media_stream_track.cpp",
         "src/audio_track.cpp",
+        "src/desktop_capturer.cpp",
+        "src/system_audio_capturer.cpp",
+        "src/encoded_frame_tap.cpp",
+        "src/video_capture.cpp",
         "src/video_track.cpp",
         "src/data_channel.cpp",
         "src/jsep.cpp",
@@ -83,8 +94,12 @@ fn main() {
         "src/video_frame.cpp",
         "src/video_frame_buffer.cpp",
         "src/video_encoder_factory.cpp",
+        "src/passthrough_video_encoder.cpp",
         "src/video_decoder_factory.cpp",
         "src/audio_device.cpp",
+        "src/audio_device_manager.cpp",
+        "src/apm.cpp",
+        "src/vad.cpp",
         "src/audio_resampler.cpp",
         "src/frame_cryptor.cpp",
     ]);
@@ -154,6 +169,8 @@ fn main() {
 
             builder
                 .file("src/objc_video_factory.mm")
+                .file("src/objc_video_frame_buffer.mm")
+                .file("src/objc_video_capture.mm")
                 .flag("-stdlib=libc++")
                 .flag("-std=c++20");
         }
@@ -175,7 +192,11 @@ fn main() {
 
             configure_darwin_sysroot(&mut builder);
 
-            builder.file("src/objc_video_factory.mm").flag("-std=c++20");
+            builder
+                .file("src/objc_video_factory.mm")
+                .file("src/objc_video_frame_buffer.mm")
+                .file("src/objc_video_capture.mm")
+                .flag("-std=c++20");
         }
         "android" => {
             webrtc_sys_build::configure_jni_symbols().unwrap();
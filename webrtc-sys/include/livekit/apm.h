@@ -0,0 +1,57 @@
+/*
+ * Copyright 2023 LiveKit
+ *
+ * Licensed under the Apache License, Version 2.0 (the “License”);
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an “AS IS” BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#pragma once
+
+#include <memory>
+
+#include "modules/audio_processing/include/audio_processing.h"
+#include "rust/cxx.h"
+
+namespace livekit {
+
+// Wraps the WebRTC APM, the frames must contain 10ms of audio and are
+// processed in place
+class AudioProcessingModule {
+ public:
+  AudioProcessingModule(bool echo_canceller_enabled,
+                        bool noise_suppression_enabled,
+                        bool gain_controller_enabled);
+
+  void apply_config(bool echo_canceller_enabled,
+                    bool noise_suppression_enabled,
+                    bool gain_controller_enabled);
+
+  int32_t process_stream(rust::Slice<int16_t> data,
+                         int32_t sample_rate,
+                         int32_t num_channels);
+
+  int32_t process_reverse_stream(rust::Slice<int16_t> data,
+                                 int32_t sample_rate,
+                                 int32_t num_channels);
+
+  int32_t set_stream_delay_ms(int32_t delay_ms);
+
+ private:
+  rtc::scoped_refptr<webrtc::AudioProcessing> apm_;
+};
+
+std::unique_ptr<AudioProcessingModule> create_apm(
+    bool echo_canceller_enabled,
+    bool noise_suppression_enabled,
+    bool gain_controller_enabled);
+
+}  // namespace livekit
@@ -0,0 +1,118 @@
+/*
+ * Copyright 2023 LiveKit
+ *
+ * Licensed under the Apache License, Version 2.0 (the “License”);
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an “AS IS” BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#pragma once
+
+#include <memory>
+#include <mutex>
+#include <optional>
+
+#include "api/task_queue/task_queue_factory.h"
+#include "modules/audio_device/include/audio_device.h"
+#include "modules/audio_processing/include/audio_processing.h"
+#include "rtc_base/thread.h"
+#include "rust/cxx.h"
+
+namespace livekit {
+class AudioDeviceManager;
+}  // namespace livekit
+#include "webrtc-sys/src/audio_device_manager.rs.h"
+
+namespace livekit {
+
+// Runs the APM on the recorded audio and forwards it to Rust, pulls the
+// played audio from Rust and uses it as the echo canceller reference
+class DeviceTransport : public webrtc::AudioTransport {
+ public:
+  DeviceTransport();
+
+  void set_recording(rust::Box<AudioRecordingObserverWrapper> observer,
+                     RecordingOptions options);
+  void clear_recording();
+  bool recording() const;
+
+  void set_playout(rust::Box<AudioPlayoutSourceWrapper> source);
+  void clear_playout();
+  bool playing() const;
+
+  int32_t RecordedDataIsAvailable(const void* audioSamples,
+                                  size_t nSamples,
+                                  size_t nBytesPerSample,
+                                  size_t nChannels,
+                                  uint32_t samplesPerSec,
+                                  uint32_t totalDelayMS,
+                                  int32_t clockDrift,
+                                  uint32_t currentMicLevel,
+                                  bool keyPressed,
+                                  uint32_t& newMicLevel) override;
+
+  int32_t NeedMorePlayData(size_t nSamples,
+                           size_t nBytesPerSample,
+                           size_t nChannels,
+                           uint32_t samplesPerSec,
+                           void* audioSamples,
+                           size_t& nSamplesOut,
+                           int64_t* elapsed_time_ms,
+                           int64_t* ntp_time_ms) override;
+
+  void PullRenderData(int bits_per_sample,
+                      int sample_rate,
+                      size_t number_of_channels,
+                      size_t number_of_frames,
+                      void* audio_data,
+                      int64_t* elapsed_time_ms,
+                      int64_t* ntp_time_ms) override;
+
+ private:
+  mutable std::mutex mutex_;
+  std::optional<rust::Box<AudioRecordingObserverWrapper>> observer_;
+  std::optional<rust::Box<AudioPlayoutSourceWrapper>> source_;
+  rtc::scoped_refptr<webrtc::AudioProcessing> apm_;
+  std::vector<int16_t> buffer_;
+  int sample_rate_ = 0;
+  size_t num_channels_ = 0;
+};
+
+// Owns the platform AudioDeviceModule of a PeerConnectionFactory, used to
+// list and select the devices, to record the microphone and to play the
+// received audio. All the calls are made on the worker thread of the factory.
+class AudioDeviceManager {
+ public:
+  AudioDeviceManager(rtc::Thread* worker_thread,
+                     webrtc::TaskQueueFactory* task_queue_factory);
+  ~AudioDeviceManager();
+
+  bool initialized() const { return adm_ != nullptr; }
+
+  rust::Vec<AudioDeviceInfo> recording_devices() const;
+  rust::Vec<AudioDeviceInfo> playout_devices() const;
+  bool set_recording_device(uint16_t index) const;
+  bool set_playout_device(uint16_t index) const;
+
+  bool start_recording(rust::Box<AudioRecordingObserverWrapper> observer,
+                       RecordingOptions options) const;
+  void stop_recording() const;
+
+  bool start_playout(rust::Box<AudioPlayoutSourceWrapper> source) const;
+  void stop_playout() const;
+
+ private:
+  rtc::Thread* thread_;
+  rtc::scoped_refptr<webrtc::AudioDeviceModule> adm_;
+  std::unique_ptr<DeviceTransport> transport_;
+};
+
+}  // namespace livekit
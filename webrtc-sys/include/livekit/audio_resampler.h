@@ -44,4 +44,24 @@ class AudioResampler {
 
 std::unique_ptr<AudioResampler> create_audio_resampler();
 
+// Resamples chunks of 10ms of interleaved audio, the sinc resampler of WebRTC
+// uses SSE/AVX/NEON when available
+class PushAudioResampler {
+ public:
+  PushAudioResampler(size_t num_channels, int src_rate, int dst_rate);
+
+  int32_t resample_i16(rust::Slice<const int16_t> src,
+                       rust::Slice<int16_t> dst);
+  int32_t resample_f32(rust::Slice<const float> src, rust::Slice<float> dst);
+
+ private:
+  webrtc::PushResampler<int16_t> resampler_i16_;
+  webrtc::PushResampler<float> resampler_f32_;
+};
+
+std::unique_ptr<PushAudioResampler> create_push_audio_resampler(
+    size_t num_channels,
+    int32_t src_rate,
+    int32_t dst_rate);
+
 }  // namespace livekit
@@ -19,6 +19,7 @@
 #include <memory>
 
 #include "api/candidate.h"
+#include "rust/cxx.h"
 
 namespace livekit {
 class Candidate;
@@ -32,6 +33,10 @@ class Candidate {
  public:
   explicit Candidate(const cricket::Candidate& candidate);
 
+  rust::String candidate_type() const;
+  rust::String protocol() const;
+  rust::String address() const;
+
  private:
   cricket::Candidate candidate_;
 };
@@ -48,6 +48,7 @@ class DataChannel {
   int id() const;
   rust::String label() const;
   DataState state() const;
+  uint64_t buffered_amount() const;
   void close() const;
 
  private:
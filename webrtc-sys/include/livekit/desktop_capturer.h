@@ -0,0 +1,69 @@
+/*
+ * Copyright 2023 LiveKit
+ *
+ * Licensed under the Apache License, Version 2.0 (the “License”);
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an “AS IS” BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#pragma once
+
+#include <memory>
+
+#include "modules/desktop_capture/desktop_capture_options.h"
+#include "modules/desktop_capture/desktop_capturer.h"
+#include "modules/desktop_capture/desktop_frame.h"
+#include "rust/cxx.h"
+
+namespace livekit {
+class DesktopCapturer;
+class DesktopFrame;
+}  // namespace livekit
+#include "webrtc-sys/src/desktop_capturer.rs.h"
+
+namespace livekit {
+
+class DesktopFrame {
+ public:
+  explicit DesktopFrame(std::unique_ptr<webrtc::DesktopFrame> frame);
+
+  int32_t width() const;
+  int32_t height() const;
+  int32_t stride() const;
+  rust::Slice<const uint8_t> data() const;
+
+ private:
+  std::unique_ptr<webrtc::DesktopFrame> frame_;
+};
+
+class DesktopCapturer : public webrtc::DesktopCapturer::Callback {
+ public:
+  DesktopCapturer(rust::Box<DesktopCapturerObserverWrapper> observer,
+                  std::unique_ptr<webrtc::DesktopCapturer> capturer);
+
+  void OnCaptureResult(webrtc::DesktopCapturer::Result result,
+                       std::unique_ptr<webrtc::DesktopFrame> frame) override;
+
+  rust::Vec<CaptureSource> get_source_list() const;
+  bool select_source(uint64_t id);
+  void start();
+  void capture_frame();
+
+ private:
+  rust::Box<DesktopCapturerObserverWrapper> observer_;
+  std::unique_ptr<webrtc::DesktopCapturer> capturer_;
+};
+
+std::unique_ptr<DesktopCapturer> new_desktop_capturer(
+    rust::Box<DesktopCapturerObserverWrapper> observer,
+    DesktopCapturerOptions options);
+
+}  // namespace livekit
@@ -0,0 +1,83 @@
+/*
+ * Copyright 2023 LiveKit
+ *
+ * Licensed under the Apache License, Version 2.0 (the “License”);
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an “AS IS” BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#pragma once
+
+#include <map>
+#include <memory>
+
+#include "api/frame_transformer_interface.h"
+#include "livekit/rtp_receiver.h"
+#include "rtc_base/synchronization/mutex.h"
+#include "rust/cxx.h"
+
+namespace livekit {
+class EncodedFrameTap;
+}  // namespace livekit
+#include "webrtc-sys/src/encoded_frame_tap.rs.h"
+
+namespace livekit {
+
+// Passes the frames through unchanged after forwarding a copy to the observer
+class TapFrameTransformer : public webrtc::FrameTransformerInterface {
+ public:
+  TapFrameTransformer(bool video,
+                      rust::Box<EncodedFrameTapObserverWrapper> observer);
+
+  void Transform(
+      std::unique_ptr<webrtc::TransformableFrameInterface> frame) override;
+
+  void RegisterTransformedFrameCallback(
+      rtc::scoped_refptr<webrtc::TransformedFrameCallback> callback) override;
+  void RegisterTransformedFrameSinkCallback(
+      rtc::scoped_refptr<webrtc::TransformedFrameCallback> callback,
+      uint32_t ssrc) override;
+  void UnregisterTransformedFrameCallback() override;
+  void UnregisterTransformedFrameSinkCallback(uint32_t ssrc) override;
+
+  void stop();
+
+ private:
+  void ForwardFrame(const webrtc::TransformableFrameInterface& frame);
+
+  webrtc::Mutex mutex_;
+  const bool video_;
+  bool stopped_ RTC_GUARDED_BY(mutex_) = false;
+  rust::Box<EncodedFrameTapObserverWrapper> observer_;
+  rtc::scoped_refptr<webrtc::TransformedFrameCallback> callback_
+      RTC_GUARDED_BY(mutex_);
+  std::map<uint32_t, rtc::scoped_refptr<webrtc::TransformedFrameCallback>>
+      sink_callbacks_ RTC_GUARDED_BY(mutex_);
+};
+
+class EncodedFrameTap {
+ public:
+  EncodedFrameTap(rtc::scoped_refptr<webrtc::RtpReceiverInterface> receiver,
+                  rust::Box<EncodedFrameTapObserverWrapper> observer);
+
+  // Stop forwarding the frames, they keep being passed through to the decoder
+  void stop() const;
+
+ private:
+  rtc::scoped_refptr<webrtc::RtpReceiverInterface> receiver_;
+  rtc::scoped_refptr<TapFrameTransformer> transformer_;
+};
+
+std::shared_ptr<EncodedFrameTap> new_encoded_frame_tap_for_rtp_receiver(
+    std::shared_ptr<RtpReceiver> receiver,
+    rust::Box<EncodedFrameTapObserverWrapper> observer);
+
+}  // namespace livekit
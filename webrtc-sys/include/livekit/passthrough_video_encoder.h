@@ -0,0 +1,103 @@
+/*
+ * Copyright 2023 LiveKit
+ *
+ * Licensed under the Apache License, Version 2.0 (the “License”);
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an “AS IS” BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#pragma once
+
+#include <atomic>
+#include <memory>
+#include <vector>
+
+#include "api/video/encoded_image.h"
+#include "api/video/video_frame.h"
+#include "api/video/video_frame_buffer.h"
+#include "api/video_codecs/sdp_video_format.h"
+#include "api/video_codecs/video_encoder.h"
+#include "livekit/encoded_frame_tap.h"
+
+namespace livekit {
+
+// Carries an already encoded frame through the video pipeline up to the
+// PassthroughVideoEncoder. Converting it to I420 (e.g. for a local preview)
+// gives a black frame
+class EncodedFrameBuffer : public webrtc::VideoFrameBuffer {
+ public:
+  EncodedFrameBuffer(EncodedVideoCodec codec,
+                     bool key_frame,
+                     int width,
+                     int height,
+                     rtc::ArrayView<const uint8_t> data,
+                     std::shared_ptr<std::atomic<bool>> key_frame_request);
+  ~EncodedFrameBuffer() override;
+
+  // nullptr if the buffer isn't an EncodedFrameBuffer (webrtc is built
+  // without RTTI, the live buffers are tracked instead)
+  static const EncodedFrameBuffer* FromBuffer(
+      const webrtc::VideoFrameBuffer* buffer);
+
+  Type type() const override { return Type::kNative; }
+  int width() const override { return width_; }
+  int height() const override { return height_; }
+  rtc::scoped_refptr<webrtc::I420BufferInterface> ToI420() override;
+
+  EncodedVideoCodec codec() const { return codec_; }
+  bool key_frame() const { return key_frame_; }
+  rtc::scoped_refptr<webrtc::EncodedImageBuffer> data() const { return data_; }
+
+  // Ask the source for a key frame, see VideoTrackSource
+  void RequestKeyFrame() const;
+
+ private:
+  EncodedVideoCodec codec_;
+  bool key_frame_;
+  int width_;
+  int height_;
+  rtc::scoped_refptr<webrtc::EncodedImageBuffer> data_;
+  std::shared_ptr<std::atomic<bool>> key_frame_request_;
+};
+
+// Sends the frames of EncodedFrameBuffers as they are, the other frames are
+// encoded by the wrapped encoder
+class PassthroughVideoEncoder : public webrtc::VideoEncoder {
+ public:
+  PassthroughVideoEncoder(const webrtc::SdpVideoFormat& format,
+                          std::unique_ptr<webrtc::VideoEncoder> encoder);
+
+  void SetFecControllerOverride(
+      webrtc::FecControllerOverride* fec_controller_override) override;
+  int InitEncode(const webrtc::VideoCodec* codec_settings,
+                 const VideoEncoder::Settings& settings) override;
+  int32_t RegisterEncodeCompleteCallback(
+      webrtc::EncodedImageCallback* callback) override;
+  int32_t Release() override;
+  int32_t Encode(
+      const webrtc::VideoFrame& frame,
+      const std::vector<webrtc::VideoFrameType>* frame_types) override;
+  void SetRates(const RateControlParameters& parameters) override;
+  void OnPacketLossRateUpdate(float packet_loss_rate) override;
+  void OnRttUpdate(int64_t rtt_ms) override;
+  void OnLossNotification(const LossNotification& loss_notification) override;
+  EncoderInfo GetEncoderInfo() const override;
+
+ private:
+  int32_t SendEncodedFrame(const webrtc::VideoFrame& frame,
+                           const EncodedFrameBuffer& buffer);
+
+  EncodedVideoCodec codec_;
+  std::unique_ptr<webrtc::VideoEncoder> encoder_;
+  webrtc::EncodedImageCallback* callback_ = nullptr;
+};
+
+}  // namespace livekit
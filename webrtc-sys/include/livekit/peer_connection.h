@@ -92,6 +92,27 @@ class PeerConnection {
 
   void remove_track(std::shared_ptr<RtpSender> sender) const;
 
+  void get_audio_receiver_stats(
+      std::shared_ptr<RtpReceiver> receiver,
+      rust::Box<AsyncContext> ctx,
+      rust::Fn<void(rust::Box<AsyncContext>, AudioReceiverStats)> on_stats)
+      const;
+
+  // The stats are delivered as the JSON of the RTCStatsReport
+  void get_stats(
+      rust::Box<AsyncContext> ctx,
+      rust::Fn<void(rust::Box<AsyncContext>, rust::String)> on_stats) const;
+
+  void get_sender_stats(
+      std::shared_ptr<RtpSender> sender,
+      rust::Box<AsyncContext> ctx,
+      rust::Fn<void(rust::Box<AsyncContext>, rust::String)> on_stats) const;
+
+  void get_receiver_stats(
+      std::shared_ptr<RtpReceiver> receiver,
+      rust::Box<AsyncContext> ctx,
+      rust::Fn<void(rust::Box<AsyncContext>, rust::String)> on_stats) const;
+
   void restart_ice() const;
 
   std::shared_ptr<RtpTransceiver> add_transceiver(
@@ -17,8 +17,11 @@
 #pragma once
 
 #include "api/peer_connection_interface.h"
+#include <mutex>
+
 #include "api/scoped_refptr.h"
 #include "livekit/audio_device.h"
+#include "livekit/audio_device_manager.h"
 #include "media_stream.h"
 #include "peer_connection.h"
 #include "rtp_parameters.h"
@@ -27,6 +30,8 @@
 
 namespace livekit {
 class PeerConnectionFactory;
+class VideoEncoderFactory;
+class VideoDecoderFactory;
 }  // namespace livekit
 #include "webrtc-sys/src/peer_connection_factory.rs.h"
 
@@ -56,9 +61,25 @@ class PeerConnectionFactory {
 
   RtpCapabilities rtp_receiver_capabilities(MediaType type) const;
 
+  rust::Vec<VideoCodecCapability> video_codec_capabilities() const;
+
+  void set_hardware_encoder_preference(rust::String codec,
+                                       HardwarePreference preference) const;
+
+  // Created on the first call, nullptr if the platform ADM failed to
+  // initialize
+  std::shared_ptr<AudioDeviceManager> audio_device_manager() const;
+
  private:
   std::shared_ptr<RtcRuntime> rtc_runtime_;
   rtc::scoped_refptr<AudioDevice> audio_device_;
+  // Owned by the PeerConnectionFactoryInterface
+  webrtc::TaskQueueFactory* task_queue_factory_ = nullptr;
+  mutable std::mutex device_manager_mutex_;
+  mutable std::shared_ptr<AudioDeviceManager> device_manager_;
+  // Owned by the media engine
+  VideoEncoderFactory* video_encoder_factory_ = nullptr;
+  VideoDecoderFactory* video_decoder_factory_ = nullptr;
   rtc::scoped_refptr<webrtc::PeerConnectionFactoryInterface> peer_factory_;
 };
 
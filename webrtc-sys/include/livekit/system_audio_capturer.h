@@ -0,0 +1,62 @@
+/*
+ * Copyright 2023 LiveKit
+ *
+ * Licensed under the Apache License, Version 2.0 (the “License”);
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an “AS IS” BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#pragma once
+
+#include <atomic>
+#include <cstdint>
+#include <future>
+#include <memory>
+#include <thread>
+
+#include "rust/cxx.h"
+
+namespace livekit {
+class SystemAudioCapturer;
+}  // namespace livekit
+#include "webrtc-sys/src/system_audio_capturer.rs.h"
+
+namespace livekit {
+
+// Records the audio played on the default output device (loopback) as
+// interleaved 16 bits PCM. Only supported on Windows (WASAPI loopback), start
+// fails on the other platforms.
+class SystemAudioCapturer {
+ public:
+  SystemAudioCapturer(rust::Box<SystemAudioObserverWrapper> observer,
+                      uint32_t sample_rate,
+                      uint32_t num_channels);
+  ~SystemAudioCapturer();
+
+  bool start();
+  void stop();
+
+ private:
+  void run(std::promise<bool> started);
+
+  rust::Box<SystemAudioObserverWrapper> observer_;
+  uint32_t sample_rate_;
+  uint32_t num_channels_;
+  std::atomic<bool> running_{false};
+  std::thread thread_;
+};
+
+std::unique_ptr<SystemAudioCapturer> new_system_audio_capturer(
+    rust::Box<SystemAudioObserverWrapper> observer,
+    uint32_t sample_rate,
+    uint32_t num_channels);
+
+}  // namespace livekit
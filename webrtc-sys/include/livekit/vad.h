@@ -0,0 +1,44 @@
+/*
+ * Copyright 2023 LiveKit
+ *
+ * Licensed under the Apache License, Version 2.0 (the “License”);
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an “AS IS” BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#pragma once
+
+#include <memory>
+
+#include "common_audio/vad/include/webrtc_vad.h"
+#include "rust/cxx.h"
+
+namespace livekit {
+
+// The VAD of WebRTC, the frames must be mono and contain 10, 20 or 30ms of
+// audio at 8, 16, 32 or 48kHz
+class Vad {
+ public:
+  explicit Vad(VadInst* vad);
+  ~Vad();
+
+  bool set_mode(int32_t mode);
+
+  // 1 if the frame contains speech, 0 otherwise and -1 on error
+  int32_t process(int32_t sample_rate, rust::Slice<const int16_t> data);
+
+ private:
+  VadInst* vad_;
+};
+
+std::unique_ptr<Vad> create_vad(int32_t mode);
+
+}  // namespace livekit
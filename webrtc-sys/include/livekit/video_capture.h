@@ -0,0 +1,58 @@
+/*
+ * Copyright 2023 LiveKit
+ *
+ * Licensed under the Apache License, Version 2.0 (the “License”);
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an “AS IS” BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#pragma once
+
+#include <memory>
+
+#include "api/video/video_frame.h"
+#include "livekit/video_frame.h"
+#include "rust/cxx.h"
+
+namespace livekit {
+class CameraCapturer;
+}  // namespace livekit
+#include "webrtc-sys/src/video_capture.rs.h"
+
+namespace livekit {
+
+// Implemented with the VideoCaptureModule of webrtc (V4L2, DirectShow) and
+// with RTCCameraVideoCapturer (AVFoundation) on Apple platforms
+class CameraCapturer {
+ public:
+  explicit CameraCapturer(rust::Box<CameraCapturerObserverWrapper> observer)
+      : observer_(std::move(observer)) {}
+  virtual ~CameraCapturer() = default;
+
+  virtual bool start(CameraFormat format) = 0;
+  virtual void stop() = 0;
+
+ protected:
+  void on_frame(const webrtc::VideoFrame& frame) {
+    observer_->on_frame(std::make_unique<VideoFrame>(frame));
+  }
+
+ private:
+  rust::Box<CameraCapturerObserverWrapper> observer_;
+};
+
+rust::Vec<CameraDeviceInfo> list_camera_devices();
+
+std::unique_ptr<CameraCapturer> new_camera_capturer(
+    rust::Box<CameraCapturerObserverWrapper> observer,
+    rust::String device_id);
+
+}  // namespace livekit
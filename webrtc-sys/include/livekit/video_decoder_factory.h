@@ -24,6 +24,10 @@ class VideoDecoderFactory : public webrtc::VideoDecoderFactory {
  public:
   VideoDecoderFactory();
 
+  // Formats of the platform (hardware) decoders and of the builtin ones
+  std::vector<webrtc::SdpVideoFormat> GetHardwareFormats() const;
+  std::vector<webrtc::SdpVideoFormat> GetSoftwareFormats() const;
+
   std::vector<webrtc::SdpVideoFormat> GetSupportedFormats() const override;
 
   CodecSupport QueryCodecSupport(const webrtc::SdpVideoFormat& format,
@@ -16,15 +16,30 @@
 
 #pragma once
 
+#include <map>
+#include <string>
+
 #include "api/video_codecs/video_encoder.h"
 #include "api/video_codecs/video_encoder_factory.h"
+#include "rtc_base/synchronization/mutex.h"
 
 namespace livekit {
 class VideoEncoderFactory : public webrtc::VideoEncoderFactory {
+ public:
+  enum class HardwarePreference { kPrefer, kRequire, kDisable };
+
+ private:
   class InternalFactory : public webrtc::VideoEncoderFactory {
    public:
     InternalFactory();
 
+    // Formats of the platform (hardware) encoders and of the builtin ones
+    std::vector<webrtc::SdpVideoFormat> GetHardwareFormats() const;
+    std::vector<webrtc::SdpVideoFormat> GetSoftwareFormats() const;
+
+    void SetHardwarePreference(const std::string& codec,
+                               HardwarePreference preference);
+
     std::vector<webrtc::SdpVideoFormat> GetSupportedFormats() const override;
 
     CodecSupport QueryCodecSupport(
@@ -35,12 +50,23 @@ class VideoEncoderFactory : public webrtc::VideoEncoderFactory {
         const webrtc::SdpVideoFormat& format) override;
 
    private:
+    HardwarePreference GetHardwarePreference(const std::string& codec) const;
+
     std::vector<std::unique_ptr<webrtc::VideoEncoderFactory>> factories_;
+    mutable webrtc::Mutex mutex_;
+    std::map<std::string, HardwarePreference> preferences_;  // lowercase codec
   };
 
  public:
   VideoEncoderFactory();
 
+  std::vector<webrtc::SdpVideoFormat> GetHardwareFormats() const;
+  std::vector<webrtc::SdpVideoFormat> GetSoftwareFormats() const;
+
+  // Require removes the codec when there is no hardware encoder for it
+  void SetHardwarePreference(const std::string& codec,
+                             HardwarePreference preference);
+
   std::vector<webrtc::SdpVideoFormat> GetSupportedFormats() const override;
 
   CodecSupport QueryCodecSupport(
@@ -49,6 +49,8 @@ class VideoFrameBuffer {
   unsigned int width() const;
   unsigned int height() const;
 
+  NativeHandle native_handle() const;
+
   std::unique_ptr<I420Buffer> to_i420() const;
 
   // Requires ownership
@@ -132,6 +134,28 @@ class BiplanarYuv8Buffer : public BiplanarYuvBuffer {
   webrtc::BiplanarYuv8Buffer* buffer() const;
 };
 
+// Native buffers produced by the hardware decoders of Windows (D3D11) and Linux
+// (DMA-BUF) derive from this class to expose their platform handle. The builtin
+// decoders of these platforms output I420 buffers, so no handle is exposed
+// without a platform decoder
+class PlatformNativeBuffer : public webrtc::VideoFrameBuffer {
+ public:
+  PlatformNativeBuffer();
+  ~PlatformNativeBuffer() override;
+
+  // nullptr if the buffer isn't a PlatformNativeBuffer (webrtc is built
+  // without RTTI, the live buffers are tracked instead)
+  static const PlatformNativeBuffer* FromBuffer(
+      const webrtc::VideoFrameBuffer* buffer);
+
+  Type type() const override { return Type::kNative; }
+  virtual NativeHandle native_handle() const = 0;
+};
+
+// Implemented in objc_video_frame_buffer.mm on macOS/iOS
+NativeHandle get_native_handle(
+    const rtc::scoped_refptr<webrtc::VideoFrameBuffer>& buffer);
+
 std::unique_ptr<I420Buffer> new_i420_buffer(int width, int height);
 std::unique_ptr<I420Buffer> copy_i420_buffer(
     const std::unique_ptr<I420Buffer>& i420);
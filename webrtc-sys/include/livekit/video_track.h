@@ -16,10 +16,12 @@
 
 #pragma once
 
+#include <atomic>
 #include <memory>
 
 #include "api/media_stream_interface.h"
 #include "api/video/video_frame.h"
+#include "livekit/encoded_frame_tap.h"
 #include "livekit/helper.h"
 #include "livekit/media_stream_track.h"
 #include "livekit/video_frame.h"
@@ -99,11 +101,19 @@ class VideoTrackSource {
     bool remote() const override;
     VideoResolution video_resolution() const;
     bool on_captured_frame(const webrtc::VideoFrame& frame);
+    bool on_captured_encoded_frame(EncodedVideoCodec codec,
+                                   bool key_frame,
+                                   uint32_t width,
+                                   uint32_t height,
+                                   rust::Slice<const uint8_t> data,
+                                   int64_t timestamp_us);
+    bool take_key_frame_request();
 
    private:
     mutable webrtc::Mutex mutex_;
     rtc::TimestampAligner timestamp_aligner_;
     VideoResolution resolution_;
+    std::shared_ptr<std::atomic<bool>> key_frame_request_;
   };
 
  public:
@@ -114,6 +124,18 @@ class VideoTrackSource {
   bool on_captured_frame(const std::unique_ptr<VideoFrame>& frame)
       const;  // frames pushed from Rust (+interior mutability)
 
+  // Already encoded frames, sent without being re-encoded. The size of the
+  // delta frames is the one of the last key frame
+  bool on_captured_encoded_frame(EncodedVideoCodec codec,
+                                 bool key_frame,
+                                 uint32_t width,
+                                 uint32_t height,
+                                 rust::Slice<const uint8_t> data,
+                                 int64_t timestamp_us) const;
+
+  // True once after the receivers asked for a key frame
+  bool take_key_frame_request() const;
+
   rtc::scoped_refptr<InternalSource> get() const;
 
  private:
@@ -113,4 +113,6 @@ std::unique_ptr<LogSink> new_log_sink(
 
 rust::String create_random_uuid();
 
+int64_t time_micros();
+
 }  // namespace livekit
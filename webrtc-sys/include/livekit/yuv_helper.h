@@ -21,6 +21,7 @@
 #include <string>
 
 #include "api/video/yuv_helper.h"
+#include "libyuv.h"
 #include "webrtc-sys/src/yuv_helper.rs.h"
 
 namespace livekit {
@@ -361,4 +362,183 @@ static void argb_to_nv12(const uint8_t* src_argb,
                                     height));
 }
 
+static void i420_to_nv12(const uint8_t* src_y,
+                         int src_stride_y,
+                         const uint8_t* src_u,
+                         int src_stride_u,
+                         const uint8_t* src_v,
+                         int src_stride_v,
+                         uint8_t* dst_y,
+                         int dst_stride_y,
+                         uint8_t* dst_uv,
+                         int dst_stride_uv,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::I420ToNV12(src_y, src_stride_y, src_u, src_stride_u,
+                                    src_v, src_stride_v, dst_y, dst_stride_y,
+                                    dst_uv, dst_stride_uv, width, height));
+}
+
+static void i420_to_i422(const uint8_t* src_y,
+                         int src_stride_y,
+                         const uint8_t* src_u,
+                         int src_stride_u,
+                         const uint8_t* src_v,
+                         int src_stride_v,
+                         uint8_t* dst_y,
+                         int dst_stride_y,
+                         uint8_t* dst_u,
+                         int dst_stride_u,
+                         uint8_t* dst_v,
+                         int dst_stride_v,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::I420ToI422(src_y, src_stride_y, src_u, src_stride_u,
+                                    src_v, src_stride_v, dst_y, dst_stride_y,
+                                    dst_u, dst_stride_u, dst_v, dst_stride_v,
+                                    width, height));
+}
+
+static void i420_to_i444(const uint8_t* src_y,
+                         int src_stride_y,
+                         const uint8_t* src_u,
+                         int src_stride_u,
+                         const uint8_t* src_v,
+                         int src_stride_v,
+                         uint8_t* dst_y,
+                         int dst_stride_y,
+                         uint8_t* dst_u,
+                         int dst_stride_u,
+                         uint8_t* dst_v,
+                         int dst_stride_v,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::I420ToI444(src_y, src_stride_y, src_u, src_stride_u,
+                                    src_v, src_stride_v, dst_y, dst_stride_y,
+                                    dst_u, dst_stride_u, dst_v, dst_stride_v,
+                                    width, height));
+}
+
+static void i420_to_rgb24(const uint8_t* src_y,
+                          int src_stride_y,
+                          const uint8_t* src_u,
+                          int src_stride_u,
+                          const uint8_t* src_v,
+                          int src_stride_v,
+                          uint8_t* dst_rgb24,
+                          int dst_stride_rgb24,
+                          int width,
+                          int height) {
+  THROW_ON_ERROR(libyuv::I420ToRGB24(src_y, src_stride_y, src_u, src_stride_u,
+                                     src_v, src_stride_v, dst_rgb24,
+                                     dst_stride_rgb24, width, height));
+}
+
+static void rgb24_to_i420(const uint8_t* src_rgb24,
+                          int src_stride_rgb24,
+                          uint8_t* dst_y,
+                          int dst_stride_y,
+                          uint8_t* dst_u,
+                          int dst_stride_u,
+                          uint8_t* dst_v,
+                          int dst_stride_v,
+                          int width,
+                          int height) {
+  THROW_ON_ERROR(libyuv::RGB24ToI420(src_rgb24, src_stride_rgb24, dst_y,
+                                     dst_stride_y, dst_u, dst_stride_u, dst_v,
+                                     dst_stride_v, width, height));
+}
+
+static void rgb24_to_argb(const uint8_t* src_rgb24,
+                          int src_stride_rgb24,
+                          uint8_t* dst_argb,
+                          int dst_stride_argb,
+                          int width,
+                          int height) {
+  THROW_ON_ERROR(libyuv::RGB24ToARGB(src_rgb24, src_stride_rgb24, dst_argb,
+                                     dst_stride_argb, width, height));
+}
+
+// Swaps R and B, also converts ABGR to ARGB
+static void argb_to_abgr(const uint8_t* src_argb,
+                         int src_stride_argb,
+                         uint8_t* dst_abgr,
+                         int dst_stride_abgr,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::ARGBToABGR(src_argb, src_stride_argb, dst_abgr,
+                                    dst_stride_abgr, width, height));
+}
+
+static void scale_plane(const uint8_t* src,
+                        int src_stride,
+                        int src_width,
+                        int src_height,
+                        uint8_t* dst,
+                        int dst_stride,
+                        int dst_width,
+                        int dst_height,
+                        int filtering) {
+  libyuv::ScalePlane(src, src_stride, src_width, src_height, dst, dst_stride,
+                     dst_width, dst_height,
+                     static_cast<libyuv::FilterMode>(filtering));
+}
+
+static void nv12_scale(const uint8_t* src_y,
+                       int src_stride_y,
+                       const uint8_t* src_uv,
+                       int src_stride_uv,
+                       int src_width,
+                       int src_height,
+                       uint8_t* dst_y,
+                       int dst_stride_y,
+                       uint8_t* dst_uv,
+                       int dst_stride_uv,
+                       int dst_width,
+                       int dst_height,
+                       int filtering) {
+  THROW_ON_ERROR(libyuv::NV12Scale(
+      src_y, src_stride_y, src_uv, src_stride_uv, src_width, src_height, dst_y,
+      dst_stride_y, dst_uv, dst_stride_uv, dst_width, dst_height,
+      static_cast<libyuv::FilterMode>(filtering)));
+}
+
+static void argb_scale(const uint8_t* src_argb,
+                       int src_stride_argb,
+                       int src_width,
+                       int src_height,
+                       uint8_t* dst_argb,
+                       int dst_stride_argb,
+                       int dst_width,
+                       int dst_height,
+                       int filtering) {
+  THROW_ON_ERROR(libyuv::ARGBScale(
+      src_argb, src_stride_argb, src_width, src_height, dst_argb,
+      dst_stride_argb, dst_width, dst_height,
+      static_cast<libyuv::FilterMode>(filtering)));
+}
+
+static void rotate_plane(const uint8_t* src,
+                         int src_stride,
+                         uint8_t* dst,
+                         int dst_stride,
+                         int width,
+                         int height,
+                         int mode) {
+  libyuv::RotatePlane(src, src_stride, dst, dst_stride, width, height,
+                      static_cast<libyuv::RotationMode>(mode));
+}
+
+static void argb_rotate(const uint8_t* src_argb,
+                        int src_stride_argb,
+                        uint8_t* dst_argb,
+                        int dst_stride_argb,
+                        int width,
+                        int height,
+                        int mode) {
+  THROW_ON_ERROR(libyuv::ARGBRotate(src_argb, src_stride_argb, dst_argb,
+                                    dst_stride_argb, width, height,
+                                    static_cast<libyuv::RotationMode>(mode)));
+}
+
 }  // namespace livekit
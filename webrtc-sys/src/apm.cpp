@@ -0,0 +1,71 @@
+/*
+ * Copyright 2023 LiveKit
+ *
+ * Licensed under the Apache License, Version 2.0 (the “License”);
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an “AS IS” BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#include "livekit/apm.h"
+
+#include <memory>
+
+namespace livekit {
+
+AudioProcessingModule::AudioProcessingModule(bool echo_canceller_enabled,
+                                             bool noise_suppression_enabled,
+                                             bool gain_controller_enabled)
+    : apm_(webrtc::AudioProcessingBuilder().Create()) {
+  apply_config(echo_canceller_enabled, noise_suppression_enabled,
+               gain_controller_enabled);
+}
+
+void AudioProcessingModule::apply_config(bool echo_canceller_enabled,
+                                         bool noise_suppression_enabled,
+                                         bool gain_controller_enabled) {
+  webrtc::AudioProcessing::Config config;
+  config.echo_canceller.enabled = echo_canceller_enabled;
+  config.noise_suppression.enabled = noise_suppression_enabled;
+  config.gain_controller1.enabled = gain_controller_enabled;
+  config.gain_controller1.mode =
+      webrtc::AudioProcessing::Config::GainController1::kAdaptiveDigital;
+  apm_->ApplyConfig(config);
+}
+
+int32_t AudioProcessingModule::process_stream(rust::Slice<int16_t> data,
+                                              int32_t sample_rate,
+                                              int32_t num_channels) {
+  webrtc::StreamConfig config(sample_rate, num_channels);
+  return apm_->ProcessStream(data.data(), config, config, data.data());
+}
+
+int32_t AudioProcessingModule::process_reverse_stream(
+    rust::Slice<int16_t> data,
+    int32_t sample_rate,
+    int32_t num_channels) {
+  webrtc::StreamConfig config(sample_rate, num_channels);
+  return apm_->ProcessReverseStream(data.data(), config, config, data.data());
+}
+
+int32_t AudioProcessingModule::set_stream_delay_ms(int32_t delay_ms) {
+  return apm_->set_stream_delay_ms(delay_ms);
+}
+
+std::unique_ptr<AudioProcessingModule> create_apm(
+    bool echo_canceller_enabled,
+    bool noise_suppression_enabled,
+    bool gain_controller_enabled) {
+  return std::make_unique<AudioProcessingModule>(
+      echo_canceller_enabled, noise_suppression_enabled,
+      gain_controller_enabled);
+}
+
+}  // namespace livekit
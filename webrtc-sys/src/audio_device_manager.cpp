@@ -0,0 +1,290 @@
+/*
+ * Copyright 2023 LiveKit
+ *
+ * Licensed under the Apache License, Version 2.0 (the “License”);
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an “AS IS” BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#include "livekit/audio_device_manager.h"
+
+#include <cstring>
+#include <memory>
+
+#include "rtc_base/logging.h"
+
+namespace livekit {
+
+DeviceTransport::DeviceTransport()
+    : apm_(webrtc::AudioProcessingBuilder().Create()) {}
+
+void DeviceTransport::set_recording(
+    rust::Box<AudioRecordingObserverWrapper> observer,
+    RecordingOptions options) {
+  webrtc::AudioProcessing::Config config;
+  config.echo_canceller.enabled = options.echo_cancellation;
+  config.noise_suppression.enabled = options.noise_suppression;
+  config.gain_controller1.enabled = options.auto_gain_control;
+  config.gain_controller1.mode =
+      webrtc::AudioProcessing::Config::GainController1::kAdaptiveDigital;
+  apm_->ApplyConfig(config);
+
+  std::lock_guard<std::mutex> lock(mutex_);
+  observer_ = std::move(observer);
+  buffer_.resize(options.sample_rate / 100 * options.num_channels);
+  sample_rate_ = options.sample_rate;
+  num_channels_ = options.num_channels;
+}
+
+void DeviceTransport::clear_recording() {
+  std::lock_guard<std::mutex> lock(mutex_);
+  observer_.reset();
+}
+
+bool DeviceTransport::recording() const {
+  std::lock_guard<std::mutex> lock(mutex_);
+  return observer_.has_value();
+}
+
+void DeviceTransport::set_playout(
+    rust::Box<AudioPlayoutSourceWrapper> source) {
+  std::lock_guard<std::mutex> lock(mutex_);
+  source_ = std::move(source);
+}
+
+void DeviceTransport::clear_playout() {
+  std::lock_guard<std::mutex> lock(mutex_);
+  source_.reset();
+}
+
+bool DeviceTransport::playing() const {
+  std::lock_guard<std::mutex> lock(mutex_);
+  return source_.has_value();
+}
+
+int32_t DeviceTransport::RecordedDataIsAvailable(const void* audioSamples,
+                                                 size_t nSamples,
+                                                 size_t nBytesPerSample,
+                                                 size_t nChannels,
+                                                 uint32_t samplesPerSec,
+                                                 uint32_t totalDelayMS,
+                                                 int32_t clockDrift,
+                                                 uint32_t currentMicLevel,
+                                                 bool keyPressed,
+                                                 uint32_t& newMicLevel) {
+  newMicLevel = currentMicLevel;
+
+  std::lock_guard<std::mutex> lock(mutex_);
+  if (!observer_)
+    return 0;
+
+  // The APM also converts the audio to the requested format
+  webrtc::StreamConfig input_config(samplesPerSec, nChannels);
+  webrtc::StreamConfig output_config(sample_rate_, num_channels_);
+
+  apm_->set_stream_delay_ms(totalDelayMS);
+  int err = apm_->ProcessStream(static_cast<const int16_t*>(audioSamples),
+                                input_config, output_config, buffer_.data());
+  if (err != webrtc::AudioProcessing::kNoError) {
+    RTC_LOG(LS_WARNING) << "failed to process the recorded audio: " << err;
+    return 0;
+  }
+
+  (*observer_)->on_recorded_data(
+      rust::Slice<const int16_t>(buffer_.data(), buffer_.size()));
+  return 0;
+}
+
+int32_t DeviceTransport::NeedMorePlayData(size_t nSamples,
+                                          size_t nBytesPerSample,
+                                          size_t nChannels,
+                                          uint32_t samplesPerSec,
+                                          void* audioSamples,
+                                          size_t& nSamplesOut,
+                                          int64_t* elapsed_time_ms,
+                                          int64_t* ntp_time_ms) {
+  int16_t* data = static_cast<int16_t*>(audioSamples);
+  std::memset(data, 0, nSamples * nChannels * sizeof(int16_t));
+  nSamplesOut = nSamples;
+
+  std::lock_guard<std::mutex> lock(mutex_);
+  if (!source_)
+    return 0;
+
+  (*source_)->on_playout_data(
+      rust::Slice<int16_t>(data, nSamples * nChannels), samplesPerSec,
+      nChannels);
+
+  // The played audio is the reference of the echo canceller
+  webrtc::StreamConfig config(samplesPerSec, nChannels);
+  int err = apm_->ProcessReverseStream(data, config, config, data);
+  if (err != webrtc::AudioProcessing::kNoError)
+    RTC_LOG(LS_WARNING) << "failed to process the played audio: " << err;
+
+  return 0;
+}
+
+void DeviceTransport::PullRenderData(int bits_per_sample,
+                                     int sample_rate,
+                                     size_t number_of_channels,
+                                     size_t number_of_frames,
+                                     void* audio_data,
+                                     int64_t* elapsed_time_ms,
+                                     int64_t* ntp_time_ms) {}
+
+AudioDeviceManager::AudioDeviceManager(
+    rtc::Thread* worker_thread,
+    webrtc::TaskQueueFactory* task_queue_factory)
+    : thread_(worker_thread), transport_(std::make_unique<DeviceTransport>()) {
+  adm_ = thread_->BlockingCall(
+      [&]() -> rtc::scoped_refptr<webrtc::AudioDeviceModule> {
+        auto adm = webrtc::AudioDeviceModule::Create(
+            webrtc::AudioDeviceModule::kPlatformDefaultAudio,
+            task_queue_factory);
+
+        if (!adm || adm->Init() != 0) {
+          RTC_LOG(LS_ERROR) << "failed to initialize the audio device module";
+          return nullptr;
+        }
+
+        adm->RegisterAudioCallback(transport_.get());
+        return adm;
+      });
+}
+
+AudioDeviceManager::~AudioDeviceManager() {
+  thread_->BlockingCall([this] {
+    if (adm_) {
+      adm_->StopRecording();
+      adm_->StopPlayout();
+      adm_->RegisterAudioCallback(nullptr);
+      adm_->Terminate();
+      adm_ = nullptr;
+    }
+  });
+}
+
+rust::Vec<AudioDeviceInfo> AudioDeviceManager::recording_devices() const {
+  return thread_->BlockingCall([this] {
+    rust::Vec<AudioDeviceInfo> devices;
+    int16_t count = adm_->RecordingDevices();
+    for (int16_t i = 0; i < count; i++) {
+      char name[webrtc::kAdmMaxDeviceNameSize] = {0};
+      char guid[webrtc::kAdmMaxGuidSize] = {0};
+      if (adm_->RecordingDeviceName(i, name, guid) == 0)
+        devices.push_back(
+            AudioDeviceInfo{static_cast<uint16_t>(i), name, guid});
+    }
+    return devices;
+  });
+}
+
+rust::Vec<AudioDeviceInfo> AudioDeviceManager::playout_devices() const {
+  return thread_->BlockingCall([this] {
+    rust::Vec<AudioDeviceInfo> devices;
+    int16_t count = adm_->PlayoutDevices();
+    for (int16_t i = 0; i < count; i++) {
+      char name[webrtc::kAdmMaxDeviceNameSize] = {0};
+      char guid[webrtc::kAdmMaxGuidSize] = {0};
+      if (adm_->PlayoutDeviceName(i, name, guid) == 0)
+        devices.push_back(
+            AudioDeviceInfo{static_cast<uint16_t>(i), name, guid});
+    }
+    return devices;
+  });
+}
+
+bool AudioDeviceManager::set_recording_device(uint16_t index) const {
+  return thread_->BlockingCall([&] {
+    bool recording = adm_->Recording();
+    if (recording)
+      adm_->StopRecording();
+
+    if (adm_->SetRecordingDevice(index) != 0)
+      return false;
+
+    if (recording)
+      return adm_->InitRecording() == 0 && adm_->StartRecording() == 0;
+
+    return true;
+  });
+}
+
+bool AudioDeviceManager::set_playout_device(uint16_t index) const {
+  return thread_->BlockingCall([&] {
+    bool playing = adm_->Playing();
+    if (playing)
+      adm_->StopPlayout();
+
+    if (adm_->SetPlayoutDevice(index) != 0)
+      return false;
+
+    if (playing)
+      return adm_->InitPlayout() == 0 && adm_->StartPlayout() == 0;
+
+    return true;
+  });
+}
+
+bool AudioDeviceManager::start_recording(
+    rust::Box<AudioRecordingObserverWrapper> observer,
+    RecordingOptions options) const {
+  return thread_->BlockingCall([&] {
+    if (transport_->recording())
+      return false;  // Already recording
+
+    transport_->set_recording(std::move(observer), options);
+    if (adm_->InitRecording() != 0 || adm_->StartRecording() != 0) {
+      transport_->clear_recording();
+      return false;
+    }
+
+    return true;
+  });
+}
+
+void AudioDeviceManager::stop_recording() const {
+  thread_->BlockingCall([this] {
+    if (!transport_->recording())
+      return;
+
+    adm_->StopRecording();
+    transport_->clear_recording();
+  });
+}
+
+bool AudioDeviceManager::start_playout(
+    rust::Box<AudioPlayoutSourceWrapper> source) const {
+  return thread_->BlockingCall([&] {
+    if (transport_->playing())
+      return false;  // Already playing
+
+    transport_->set_playout(std::move(source));
+    if (adm_->InitPlayout() != 0 || adm_->StartPlayout() != 0) {
+      transport_->clear_playout();
+      return false;
+    }
+
+    return true;
+  });
+}
+
+void AudioDeviceManager::stop_playout() const {
+  thread_->BlockingCall([this] {
+    if (!transport_->playing())
+      return;
+
+    adm_->StopPlayout();
+    transport_->clear_playout();
+  });
+}
+
+}  // namespace livekit
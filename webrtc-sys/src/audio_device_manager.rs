@@ -0,0 +1,111 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::impl_thread_safety;
+use std::sync::Arc;
+
+#[cxx::bridge(namespace = "livekit")]
+pub mod ffi {
+    #[derive(Debug, Clone)]
+    pub struct AudioDeviceInfo {
+        pub index: u16,
+        pub name: String,
+        pub guid: String,
+    }
+
+    #[derive(Debug)]
+    pub struct RecordingOptions {
+        pub echo_cancellation: bool,
+        pub noise_suppression: bool,
+        pub auto_gain_control: bool,
+        pub sample_rate: u32,
+        pub num_channels: u32,
+    }
+
+    unsafe extern "C++" {
+        include!("livekit/audio_device_manager.h");
+
+        type AudioDeviceManager;
+
+        fn recording_devices(self: &AudioDeviceManager) -> Vec<AudioDeviceInfo>;
+        fn playout_devices(self: &AudioDeviceManager) -> Vec<AudioDeviceInfo>;
+        fn set_recording_device(self: &AudioDeviceManager, index: u16) -> bool;
+        fn set_playout_device(self: &AudioDeviceManager, index: u16) -> bool;
+        fn start_recording(
+            self: &AudioDeviceManager,
+            observer: Box<AudioRecordingObserverWrapper>,
+            options: RecordingOptions,
+        ) -> bool;
+        fn stop_recording(self: &AudioDeviceManager);
+        fn start_playout(self: &AudioDeviceManager, source: Box<AudioPlayoutSourceWrapper>)
+            -> bool;
+        fn stop_playout(self: &AudioDeviceManager);
+    }
+
+    extern "Rust" {
+        type AudioRecordingObserverWrapper;
+        type AudioPlayoutSourceWrapper;
+
+        fn on_recorded_data(self: &AudioRecordingObserverWrapper, data: &[i16]);
+        fn on_playout_data(
+            self: &AudioPlayoutSourceWrapper,
+            data: &mut [i16],
+            sample_rate: u32,
+            num_channels: u32,
+        );
+    }
+
+    // Returned by the PeerConnectionFactory
+    impl SharedPtr<AudioDeviceManager> {}
+}
+
+impl_thread_safety!(ffi::AudioDeviceManager, Send + Sync);
+
+/// Receives 10ms of processed audio at the sample rate and channels of the RecordingOptions
+pub trait AudioRecordingObserver: Send + Sync {
+    fn on_recorded_data(&self, data: &[i16]);
+}
+
+pub struct AudioRecordingObserverWrapper {
+    observer: Arc<dyn AudioRecordingObserver>,
+}
+
+impl AudioRecordingObserverWrapper {
+    pub fn new(observer: Arc<dyn AudioRecordingObserver>) -> Self {
+        Self { observer }
+    }
+
+    fn on_recorded_data(&self, data: &[i16]) {
+        self.observer.on_recorded_data(data);
+    }
+}
+
+/// Fills 10ms of interleaved audio played on the output device, the played audio is used as
+/// the echo canceller reference of the recording
+pub trait AudioPlayoutSource: Send + Sync {
+    fn on_playout_data(&self, data: &mut [i16], sample_rate: u32, num_channels: u32);
+}
+
+pub struct AudioPlayoutSourceWrapper {
+    source: Arc<dyn AudioPlayoutSource>,
+}
+
+impl AudioPlayoutSourceWrapper {
+    pub fn new(source: Arc<dyn AudioPlayoutSource>) -> Self {
+        Self { source }
+    }
+
+    fn on_playout_data(&self, data: &mut [i16], sample_rate: u32, num_channels: u32) {
+        self.source.on_playout_data(data, sample_rate, num_channels);
+    }
+}
@@ -44,4 +44,31 @@ std::unique_ptr<AudioResampler> create_audio_resampler() {
   return std::make_unique<AudioResampler>();
 }
 
-}  // namespace livekit
\ No newline at end of file
+PushAudioResampler::PushAudioResampler(size_t num_channels,
+                                       int src_rate,
+                                       int dst_rate) {
+  resampler_i16_.InitializeIfNeeded(src_rate, dst_rate, num_channels);
+  resampler_f32_.InitializeIfNeeded(src_rate, dst_rate, num_channels);
+}
+
+int32_t PushAudioResampler::resample_i16(rust::Slice<const int16_t> src,
+                                         rust::Slice<int16_t> dst) {
+  return resampler_i16_.Resample(src.data(), src.size(), dst.data(),
+                                 dst.size());
+}
+
+int32_t PushAudioResampler::resample_f32(rust::Slice<const float> src,
+                                         rust::Slice<float> dst) {
+  return resampler_f32_.Resample(src.data(), src.size(), dst.data(),
+                                 dst.size());
+}
+
+std::unique_ptr<PushAudioResampler> create_push_audio_resampler(
+    size_t num_channels,
+    int32_t src_rate,
+    int32_t dst_rate) {
+  return std::make_unique<PushAudioResampler>(num_channels, src_rate,
+                                              dst_rate);
+}
+
+}  // namespace livekit
@@ -34,7 +34,19 @@ pub mod ffi {
         unsafe fn data(self: &AudioResampler) -> *const i16;
 
         fn create_audio_resampler() -> UniquePtr<AudioResampler>;
+
+        type PushAudioResampler;
+
+        fn resample_i16(self: Pin<&mut PushAudioResampler>, src: &[i16], dst: &mut [i16]) -> i32;
+        fn resample_f32(self: Pin<&mut PushAudioResampler>, src: &[f32], dst: &mut [f32]) -> i32;
+
+        fn create_push_audio_resampler(
+            num_channels: usize,
+            src_rate: i32,
+            dst_rate: i32,
+        ) -> UniquePtr<PushAudioResampler>;
     }
 }
 
 impl_thread_safety!(ffi::AudioResampler, Send + Sync);
+impl_thread_safety!(ffi::PushAudioResampler, Send + Sync);
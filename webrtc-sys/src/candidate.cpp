@@ -19,4 +19,24 @@
 namespace livekit {
 Candidate::Candidate(const cricket::Candidate& candidate)
     : candidate_(candidate) {}
+
+rust::String Candidate::candidate_type() const {
+  // libwebrtc names the host and srflx candidates after their port type
+  std::string type(candidate_.type());
+  if (type == "local") {
+    return rust::String("host");
+  }
+  if (type == "stun") {
+    return rust::String("srflx");
+  }
+  return rust::String(type);
+}
+
+rust::String Candidate::protocol() const {
+  return rust::String(candidate_.protocol());
+}
+
+rust::String Candidate::address() const {
+  return rust::String(candidate_.address().ToString());
+}
 }  // namespace livekit
@@ -19,6 +19,11 @@ pub mod ffi {
 
         type Candidate; // cricket::Candidate
 
+        /// host, srflx, prflx or relay
+        fn candidate_type(self: &Candidate) -> String;
+        fn protocol(self: &Candidate) -> String;
+        fn address(self: &Candidate) -> String;
+
         fn _shared_candidate() -> SharedPtr<Candidate>;
     }
 }
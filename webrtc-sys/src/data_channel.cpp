@@ -88,6 +88,10 @@ DataState DataChannel::state() const {
   return static_cast<DataState>(data_channel_->state());
 }
 
+uint64_t DataChannel::buffered_amount() const {
+  return data_channel_->buffered_amount();
+}
+
 void DataChannel::close() const {
   return data_channel_->Close();
 }
@@ -68,6 +68,7 @@ pub mod ffi {
         fn id(self: &DataChannel) -> i32;
         fn label(self: &DataChannel) -> String;
         fn state(self: &DataChannel) -> DataState;
+        fn buffered_amount(self: &DataChannel) -> u64;
         fn close(self: &DataChannel);
 
         fn _shared_data_channel() -> SharedPtr<DataChannel>; // Ignore
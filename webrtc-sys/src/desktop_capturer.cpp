@@ -0,0 +1,128 @@
+/*
+ * Copyright 2023 LiveKit
+ *
+ * Licensed under the Apache License, Version 2.0 (the “License”);
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an “AS IS” BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#include "livekit/desktop_capturer.h"
+
+#include <memory>
+
+#include "modules/desktop_capture/desktop_and_cursor_composer.h"
+#include "rust/cxx.h"
+#include "webrtc-sys/src/desktop_capturer.rs.h"
+
+namespace livekit {
+
+DesktopFrame::DesktopFrame(std::unique_ptr<webrtc::DesktopFrame> frame)
+    : frame_(std::move(frame)) {}
+
+int32_t DesktopFrame::width() const {
+  return frame_->size().width();
+}
+
+int32_t DesktopFrame::height() const {
+  return frame_->size().height();
+}
+
+int32_t DesktopFrame::stride() const {
+  return frame_->stride();
+}
+
+rust::Slice<const uint8_t> DesktopFrame::data() const {
+  return rust::Slice<const uint8_t>(
+      frame_->data(), frame_->stride() * frame_->size().height());
+}
+
+DesktopCapturer::DesktopCapturer(
+    rust::Box<DesktopCapturerObserverWrapper> observer,
+    std::unique_ptr<webrtc::DesktopCapturer> capturer)
+    : observer_(std::move(observer)), capturer_(std::move(capturer)) {}
+
+void DesktopCapturer::OnCaptureResult(
+    webrtc::DesktopCapturer::Result result,
+    std::unique_ptr<webrtc::DesktopFrame> frame) {
+  CaptureResult res = CaptureResult::Success;
+  switch (result) {
+    case webrtc::DesktopCapturer::Result::SUCCESS:
+      res = CaptureResult::Success;
+      break;
+    case webrtc::DesktopCapturer::Result::ERROR_TEMPORARY:
+      res = CaptureResult::ErrorTemporary;
+      break;
+    case webrtc::DesktopCapturer::Result::ERROR_PERMANENT:
+      res = CaptureResult::ErrorPermanent;
+      break;
+  }
+
+  std::unique_ptr<DesktopFrame> desktop_frame = nullptr;
+  if (frame)
+    desktop_frame = std::make_unique<DesktopFrame>(std::move(frame));
+
+  observer_->on_capture_result(res, std::move(desktop_frame));
+}
+
+rust::Vec<CaptureSource> DesktopCapturer::get_source_list() const {
+  rust::Vec<CaptureSource> sources;
+  webrtc::DesktopCapturer::SourceList list;
+  if (!capturer_->GetSourceList(&list))
+    return sources;
+
+  for (const auto& source : list) {
+    sources.push_back(CaptureSource{static_cast<uint64_t>(source.id),
+                                    rust::String(source.title),
+                                    source.display_id});
+  }
+  return sources;
+}
+
+bool DesktopCapturer::select_source(uint64_t id) {
+  return capturer_->SelectSource(
+      static_cast<webrtc::DesktopCapturer::SourceId>(id));
+}
+
+void DesktopCapturer::start() {
+  capturer_->Start(this);
+}
+
+void DesktopCapturer::capture_frame() {
+  capturer_->CaptureFrame();
+}
+
+std::unique_ptr<DesktopCapturer> new_desktop_capturer(
+    rust::Box<DesktopCapturerObserverWrapper> observer,
+    DesktopCapturerOptions options) {
+  webrtc::DesktopCaptureOptions capture_options =
+      webrtc::DesktopCaptureOptions::CreateDefault();
+#ifdef _WIN32
+  capture_options.set_allow_directx_capturer(true);
+#endif
+
+  std::unique_ptr<webrtc::DesktopCapturer> capturer =
+      options.window_capturer
+          ? webrtc::DesktopCapturer::CreateWindowCapturer(capture_options)
+          : webrtc::DesktopCapturer::CreateScreenCapturer(capture_options);
+
+  if (!capturer)
+    return nullptr;
+
+  if (options.capture_cursor) {
+    capturer = std::make_unique<webrtc::DesktopAndCursorComposer>(
+        std::move(capturer), capture_options);
+  }
+
+  return std::make_unique<DesktopCapturer>(std::move(observer),
+                                           std::move(capturer));
+}
+
+}  // namespace livekit
@@ -0,0 +1,100 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::impl_thread_safety;
+use std::sync::Arc;
+
+#[cxx::bridge(namespace = "livekit")]
+pub mod ffi {
+    #[derive(Debug, Clone)]
+    pub struct CaptureSource {
+        pub id: u64,
+        pub title: String,
+        pub display_id: i64,
+    }
+
+    #[derive(Debug)]
+    #[repr(i32)]
+    pub enum CaptureResult {
+        Success,
+        ErrorTemporary,
+        ErrorPermanent,
+    }
+
+    #[derive(Debug)]
+    pub struct DesktopCapturerOptions {
+        pub window_capturer: bool,
+        pub capture_cursor: bool,
+    }
+
+    unsafe extern "C++" {
+        include!("livekit/desktop_capturer.h");
+
+        type DesktopCapturer;
+        type DesktopFrame;
+
+        fn new_desktop_capturer(
+            observer: Box<DesktopCapturerObserverWrapper>,
+            options: DesktopCapturerOptions,
+        ) -> UniquePtr<DesktopCapturer>;
+
+        fn get_source_list(self: &DesktopCapturer) -> Vec<CaptureSource>;
+        fn select_source(self: Pin<&mut DesktopCapturer>, id: u64) -> bool;
+        fn start(self: Pin<&mut DesktopCapturer>);
+        fn capture_frame(self: Pin<&mut DesktopCapturer>);
+
+        fn width(self: &DesktopFrame) -> i32;
+        fn height(self: &DesktopFrame) -> i32;
+        fn stride(self: &DesktopFrame) -> i32;
+        fn data(self: &DesktopFrame) -> &[u8];
+    }
+
+    extern "Rust" {
+        type DesktopCapturerObserverWrapper;
+
+        fn on_capture_result(
+            self: &DesktopCapturerObserverWrapper,
+            result: CaptureResult,
+            frame: UniquePtr<DesktopFrame>,
+        );
+    }
+}
+
+impl_thread_safety!(ffi::DesktopFrame, Send);
+
+pub trait DesktopCapturerObserver: Send + Sync {
+    fn on_capture_result(
+        &self,
+        result: ffi::CaptureResult,
+        frame: cxx::UniquePtr<ffi::DesktopFrame>,
+    );
+}
+
+pub struct DesktopCapturerObserverWrapper {
+    observer: Arc<dyn DesktopCapturerObserver>,
+}
+
+impl DesktopCapturerObserverWrapper {
+    pub fn new(observer: Arc<dyn DesktopCapturerObserver>) -> Self {
+        Self { observer }
+    }
+
+    fn on_capture_result(
+        &self,
+        result: ffi::CaptureResult,
+        frame: cxx::UniquePtr<ffi::DesktopFrame>,
+    ) {
+        self.observer.on_capture_result(result, frame);
+    }
+}
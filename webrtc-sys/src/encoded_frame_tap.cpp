@@ -0,0 +1,133 @@
+/*
+ * Copyright 2023 LiveKit
+ *
+ * Licensed under the Apache License, Version 2.0 (the “License”);
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an “AS IS” BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#include "livekit/encoded_frame_tap.h"
+
+#include <memory>
+
+#include "api/make_ref_counted.h"
+#include "webrtc-sys/src/encoded_frame_tap.rs.h"
+
+namespace livekit {
+
+static EncodedVideoCodec to_encoded_video_codec(webrtc::VideoCodecType codec) {
+  switch (codec) {
+    case webrtc::kVideoCodecVP8:
+      return EncodedVideoCodec::VP8;
+    case webrtc::kVideoCodecVP9:
+      return EncodedVideoCodec::VP9;
+    case webrtc::kVideoCodecAV1:
+      return EncodedVideoCodec::AV1;
+    case webrtc::kVideoCodecH264:
+      return EncodedVideoCodec::H264;
+    default:
+      return EncodedVideoCodec::Generic;
+  }
+}
+
+TapFrameTransformer::TapFrameTransformer(
+    bool video,
+    rust::Box<EncodedFrameTapObserverWrapper> observer)
+    : video_(video), observer_(std::move(observer)) {}
+
+void TapFrameTransformer::ForwardFrame(
+    const webrtc::TransformableFrameInterface& frame) {
+  rtc::ArrayView<const uint8_t> data = frame.GetData();
+  rust::Slice<const uint8_t> slice(data.data(), data.size());
+
+  if (!video_) {
+    EncodedAudioFrameInfo info{frame.GetTimestamp(), frame.GetPayloadType()};
+    observer_->on_encoded_audio_frame(info, slice);
+    return;
+  }
+
+  auto& video_frame =
+      static_cast<const webrtc::TransformableVideoFrameInterface&>(frame);
+  webrtc::VideoFrameMetadata metadata = video_frame.Metadata();
+  EncodedFrameInfo info{to_encoded_video_codec(metadata.GetCodec()),
+                        frame.GetTimestamp(), video_frame.IsKeyFrame(),
+                        metadata.GetWidth(), metadata.GetHeight()};
+  observer_->on_encoded_frame(info, slice);
+}
+
+void TapFrameTransformer::Transform(
+    std::unique_ptr<webrtc::TransformableFrameInterface> frame) {
+  webrtc::MutexLock lock(&mutex_);
+  if (!stopped_ &&
+      frame->GetDirection() ==
+          webrtc::TransformableFrameInterface::Direction::kReceiver) {
+    ForwardFrame(*frame);
+  }
+
+  auto it = sink_callbacks_.find(frame->GetSsrc());
+  if (it != sink_callbacks_.end()) {
+    it->second->OnTransformedFrame(std::move(frame));
+  } else if (callback_) {
+    callback_->OnTransformedFrame(std::move(frame));
+  }
+}
+
+void TapFrameTransformer::RegisterTransformedFrameCallback(
+    rtc::scoped_refptr<webrtc::TransformedFrameCallback> callback) {
+  webrtc::MutexLock lock(&mutex_);
+  callback_ = callback;
+}
+
+void TapFrameTransformer::RegisterTransformedFrameSinkCallback(
+    rtc::scoped_refptr<webrtc::TransformedFrameCallback> callback,
+    uint32_t ssrc) {
+  webrtc::MutexLock lock(&mutex_);
+  sink_callbacks_[ssrc] = callback;
+}
+
+void TapFrameTransformer::UnregisterTransformedFrameCallback() {
+  webrtc::MutexLock lock(&mutex_);
+  callback_ = nullptr;
+}
+
+void TapFrameTransformer::UnregisterTransformedFrameSinkCallback(
+    uint32_t ssrc) {
+  webrtc::MutexLock lock(&mutex_);
+  sink_callbacks_.erase(ssrc);
+}
+
+void TapFrameTransformer::stop() {
+  webrtc::MutexLock lock(&mutex_);
+  stopped_ = true;
+}
+
+EncodedFrameTap::EncodedFrameTap(
+    rtc::scoped_refptr<webrtc::RtpReceiverInterface> receiver,
+    rust::Box<EncodedFrameTapObserverWrapper> observer)
+    : receiver_(receiver),
+      transformer_(rtc::make_ref_counted<TapFrameTransformer>(
+          receiver->media_type() == cricket::MEDIA_TYPE_VIDEO,
+          std::move(observer))) {
+  receiver_->SetDepacketizerToDecoderFrameTransformer(transformer_);
+}
+
+void EncodedFrameTap::stop() const {
+  transformer_->stop();
+}
+
+std::shared_ptr<EncodedFrameTap> new_encoded_frame_tap_for_rtp_receiver(
+    std::shared_ptr<RtpReceiver> receiver,
+    rust::Box<EncodedFrameTapObserverWrapper> observer) {
+  return std::make_shared<EncodedFrameTap>(receiver->rtc_receiver(),
+                                           std::move(observer));
+}
+
+}  // namespace livekit
@@ -0,0 +1,107 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::impl_thread_safety;
+use std::sync::Arc;
+
+#[cxx::bridge(namespace = "livekit")]
+pub mod ffi {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[repr(i32)]
+    pub enum EncodedVideoCodec {
+        Generic,
+        VP8,
+        VP9,
+        AV1,
+        H264,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct EncodedFrameInfo {
+        pub codec: EncodedVideoCodec,
+        pub rtp_timestamp: u32,
+        pub key_frame: bool,
+        pub width: u16,
+        pub height: u16,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct EncodedAudioFrameInfo {
+        pub rtp_timestamp: u32,
+        pub payload_type: u8,
+    }
+
+    extern "C++" {
+        include!("livekit/rtp_receiver.h");
+
+        type RtpReceiver = crate::rtp_receiver::ffi::RtpReceiver;
+    }
+
+    unsafe extern "C++" {
+        include!("livekit/encoded_frame_tap.h");
+
+        type EncodedFrameTap;
+
+        /// Forward a copy of the encoded frames received by the receiver (audio or video)
+        /// before they are decoded. This replaces the frame transformer of the receiver, it can't be used
+        /// together with a FrameCryptor
+        fn new_encoded_frame_tap_for_rtp_receiver(
+            receiver: SharedPtr<RtpReceiver>,
+            observer: Box<EncodedFrameTapObserverWrapper>,
+        ) -> SharedPtr<EncodedFrameTap>;
+
+        fn stop(self: &EncodedFrameTap);
+    }
+
+    extern "Rust" {
+        type EncodedFrameTapObserverWrapper;
+
+        fn on_encoded_frame(
+            self: &EncodedFrameTapObserverWrapper,
+            info: EncodedFrameInfo,
+            data: &[u8],
+        );
+
+        fn on_encoded_audio_frame(
+            self: &EncodedFrameTapObserverWrapper,
+            info: EncodedAudioFrameInfo,
+            data: &[u8],
+        );
+    }
+}
+
+impl_thread_safety!(ffi::EncodedFrameTap, Send + Sync);
+
+pub trait EncodedFrameTapObserver: Send + Sync {
+    fn on_encoded_frame(&self, info: ffi::EncodedFrameInfo, data: &[u8]);
+    fn on_encoded_audio_frame(&self, info: ffi::EncodedAudioFrameInfo, data: &[u8]);
+}
+
+pub struct EncodedFrameTapObserverWrapper {
+    observer: Arc<dyn EncodedFrameTapObserver>,
+}
+
+impl EncodedFrameTapObserverWrapper {
+    pub fn new(observer: Arc<dyn EncodedFrameTapObserver>) -> Self {
+        Self { observer }
+    }
+
+    fn on_encoded_frame(&self, info: ffi::EncodedFrameInfo, data: &[u8]) {
+        self.observer.on_encoded_frame(info, data);
+    }
+
+    fn on_encoded_audio_frame(&self, info: ffi::EncodedAudioFrameInfo, data: &[u8]) {
+        self.observer.on_encoded_audio_frame(info, data);
+    }
+}
@@ -77,6 +77,8 @@ pub mod ffi {
             participant_id: String,
             key_index: i32,
         ) -> Result<Vec<u8>>;
+
+        pub fn set_sif_trailer(self: &KeyProvider, trailer: Vec<u8>);
     }
 
     unsafe extern "C++" {
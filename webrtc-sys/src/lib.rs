@@ -14,10 +14,15 @@
 
 #[cfg(target_os = "android")]
 pub mod android;
+pub mod apm;
+pub mod audio_device_manager;
 pub mod audio_resampler;
 pub mod audio_track;
 pub mod candidate;
 pub mod data_channel;
+pub mod desktop_capturer;
+pub mod encoded_frame_tap;
+pub mod frame_cryptor;
 pub mod helper;
 pub mod jsep;
 pub mod media_stream;
@@ -29,12 +34,14 @@ pub mod rtp_parameters;
 pub mod rtp_receiver;
 pub mod rtp_sender;
 pub mod rtp_transceiver;
+pub mod system_audio_capturer;
+pub mod vad;
+pub mod video_capture;
 pub mod video_frame;
 pub mod video_frame_buffer;
 pub mod video_track;
 pub mod webrtc;
 pub mod yuv_helper;
-pub mod frame_cryptor;
 
 pub const MEDIA_TYPE_VIDEO: &str = "video";
 pub const MEDIA_TYPE_AUDIO: &str = "audio";
@@ -0,0 +1,159 @@
+/*
+ * Copyright 2023 LiveKit
+ *
+ * Licensed under the Apache License, Version 2.0 (the “License”);
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an “AS IS” BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#include "livekit/video_capture.h"
+
+#import <AVFoundation/AVFoundation.h>
+#import <sdk/objc/components/capturer/RTCCameraVideoCapturer.h>
+#include "sdk/objc/native/api/video_frame.h"
+#include "webrtc-sys/src/video_capture.rs.h"
+
+namespace livekit {
+class ObjCCameraCapturer;
+}  // namespace livekit
+
+@interface LKCameraCapturerDelegate : NSObject <RTCVideoCapturerDelegate>
+- (instancetype)initWithCapturer:(livekit::ObjCCameraCapturer*)capturer;
+@end
+
+namespace livekit {
+
+static uint32_t max_frame_rate(AVCaptureDeviceFormat* format) {
+  Float64 max_fps = 0;
+  for (AVFrameRateRange* range in format.videoSupportedFrameRateRanges) {
+    max_fps = MAX(max_fps, range.maxFrameRate);
+  }
+  return static_cast<uint32_t>(max_fps);
+}
+
+static AVCaptureDevice* find_device(const std::string& device_id) {
+  NSString* unique_id = [NSString stringWithUTF8String:device_id.c_str()];
+  for (AVCaptureDevice* device in [RTCCameraVideoCapturer captureDevices]) {
+    if ([device.uniqueID isEqualToString:unique_id])
+      return device;
+  }
+  return nil;
+}
+
+class ObjCCameraCapturer : public CameraCapturer {
+ public:
+  ObjCCameraCapturer(rust::Box<CameraCapturerObserverWrapper> observer,
+                     AVCaptureDevice* device)
+      : CameraCapturer(std::move(observer)), device_(device) {
+    delegate_ = [[LKCameraCapturerDelegate alloc] initWithCapturer:this];
+    capturer_ = [[RTCCameraVideoCapturer alloc] initWithDelegate:delegate_];
+  }
+
+  ~ObjCCameraCapturer() override { stop(); }
+
+  bool start(CameraFormat format) override {
+    // Pick the closest resolution supporting the requested frame rate
+    AVCaptureDeviceFormat* selected = nil;
+    int64_t best_diff = INT64_MAX;
+    for (AVCaptureDeviceFormat* candidate in
+         [RTCCameraVideoCapturer supportedFormatsForDevice:device_]) {
+      CMVideoDimensions dimensions =
+          CMVideoFormatDescriptionGetDimensions(candidate.formatDescription);
+      int64_t diff = std::abs(dimensions.width - (int64_t)format.width) +
+                     std::abs(dimensions.height - (int64_t)format.height);
+      if (max_frame_rate(candidate) >= format.max_fps && diff < best_diff) {
+        selected = candidate;
+        best_diff = diff;
+      }
+    }
+
+    if (!selected)
+      return false;
+
+    __block BOOL started = NO;
+    dispatch_semaphore_t done = dispatch_semaphore_create(0);
+    [capturer_ startCaptureWithDevice:device_
+                               format:selected
+                                  fps:format.max_fps
+                    completionHandler:^(NSError* error) {
+                      started = error == nil;
+                      dispatch_semaphore_signal(done);
+                    }];
+    dispatch_semaphore_wait(done, DISPATCH_TIME_FOREVER);
+    return started;
+  }
+
+  void stop() override {
+    dispatch_semaphore_t done = dispatch_semaphore_create(0);
+    [capturer_ stopCaptureWithCompletionHandler:^{
+      dispatch_semaphore_signal(done);
+    }];
+    dispatch_semaphore_wait(done, DISPATCH_TIME_FOREVER);
+  }
+
+  void on_objc_frame(RTCVideoFrame* frame) {
+    on_frame(webrtc::NativeVideoFrameFromRTCVideoFrame(frame));
+  }
+
+ private:
+  AVCaptureDevice* device_;
+  LKCameraCapturerDelegate* delegate_;
+  RTCCameraVideoCapturer* capturer_;
+};
+
+rust::Vec<CameraDeviceInfo> list_camera_devices() {
+  rust::Vec<CameraDeviceInfo> devices;
+  for (AVCaptureDevice* device in [RTCCameraVideoCapturer captureDevices]) {
+    CameraDeviceInfo info{rust::String(device.uniqueID.UTF8String),
+                          rust::String(device.localizedName.UTF8String),
+                          {}};
+    for (AVCaptureDeviceFormat* format in
+         [RTCCameraVideoCapturer supportedFormatsForDevice:device]) {
+      CMVideoDimensions dimensions =
+          CMVideoFormatDescriptionGetDimensions(format.formatDescription);
+      info.formats.push_back(CameraFormat{
+          static_cast<uint32_t>(dimensions.width),
+          static_cast<uint32_t>(dimensions.height), max_frame_rate(format)});
+    }
+    devices.push_back(std::move(info));
+  }
+  return devices;
+}
+
+std::unique_ptr<CameraCapturer> new_camera_capturer(
+    rust::Box<CameraCapturerObserverWrapper> observer,
+    rust::String device_id) {
+  AVCaptureDevice* device = find_device(std::string(device_id));
+  if (!device)
+    return nullptr;
+
+  return std::make_unique<ObjCCameraCapturer>(std::move(observer), device);
+}
+
+}  // namespace livekit
+
+@implementation LKCameraCapturerDelegate {
+  livekit::ObjCCameraCapturer* _capturer;
+}
+
+- (instancetype)initWithCapturer:(livekit::ObjCCameraCapturer*)capturer {
+  if (self = [super init]) {
+    _capturer = capturer;
+  }
+  return self;
+}
+
+- (void)capturer:(RTCVideoCapturer*)capturer
+    didCaptureVideoFrame:(RTCVideoFrame*)frame {
+  _capturer->on_objc_frame(frame);
+}
+
+@end
@@ -0,0 +1,45 @@
+/*
+ * Copyright 2023 LiveKit
+ *
+ * Licensed under the Apache License, Version 2.0 (the “License”);
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an “AS IS” BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#include "livekit/video_frame_buffer.h"
+
+#import <sdk/objc/components/video_frame_buffer/RTCCVPixelBuffer.h>
+#include "livekit/passthrough_video_encoder.h"
+#include "sdk/objc/native/src/objc_frame_buffer.h"
+
+namespace livekit {
+
+// On Apple platforms, the other native buffers are always ObjCFrameBuffer
+NativeHandle get_native_handle(
+    const rtc::scoped_refptr<webrtc::VideoFrameBuffer>& buffer) {
+  NativeHandle handle{NativeHandleType::None};
+  if (buffer->type() != webrtc::VideoFrameBuffer::Type::kNative ||
+      EncodedFrameBuffer::FromBuffer(buffer.get())) {
+    return handle;
+  }
+
+  id<RTCVideoFrameBuffer> objc_buffer =
+      static_cast<webrtc::ObjCFrameBuffer*>(buffer.get())
+          ->wrapped_frame_buffer();
+  if ([objc_buffer isKindOfClass:[RTCCVPixelBuffer class]]) {
+    handle.handle_type = NativeHandleType::CVPixelBuffer;
+    handle.handle = reinterpret_cast<size_t>(
+        ((RTCCVPixelBuffer*)objc_buffer).pixelBuffer);
+  }
+  return handle;
+}
+
+}  // namespace livekit
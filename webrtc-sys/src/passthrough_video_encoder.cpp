@@ -0,0 +1,259 @@
+/*
+ * Copyright 2023 LiveKit
+ *
+ * Licensed under the Apache License, Version 2.0 (the “License”);
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an “AS IS” BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#include "livekit/passthrough_video_encoder.h"
+
+#include <set>
+
+#include "absl/strings/match.h"
+#include "api/video/i420_buffer.h"
+#include "modules/video_coding/codecs/vp9/include/vp9_globals.h"
+#include "modules/video_coding/include/video_codec_interface.h"
+#include "modules/video_coding/include/video_error_codes.h"
+#include "rtc_base/logging.h"
+#include "rtc_base/synchronization/mutex.h"
+
+namespace livekit {
+
+namespace {
+
+webrtc::Mutex& buffers_mutex() {
+  static webrtc::Mutex* mutex = new webrtc::Mutex();
+  return *mutex;
+}
+
+std::set<const webrtc::VideoFrameBuffer*>& live_buffers() {
+  static auto* buffers = new std::set<const webrtc::VideoFrameBuffer*>();
+  return *buffers;
+}
+
+EncodedVideoCodec to_encoded_video_codec(const webrtc::SdpVideoFormat& format) {
+  if (absl::EqualsIgnoreCase(format.name, "VP8"))
+    return EncodedVideoCodec::VP8;
+  if (absl::EqualsIgnoreCase(format.name, "VP9"))
+    return EncodedVideoCodec::VP9;
+  if (absl::EqualsIgnoreCase(format.name, "AV1"))
+    return EncodedVideoCodec::AV1;
+  if (absl::EqualsIgnoreCase(format.name, "H264"))
+    return EncodedVideoCodec::H264;
+  return EncodedVideoCodec::Generic;
+}
+
+}  // namespace
+
+EncodedFrameBuffer::EncodedFrameBuffer(
+    EncodedVideoCodec codec,
+    bool key_frame,
+    int width,
+    int height,
+    rtc::ArrayView<const uint8_t> data,
+    std::shared_ptr<std::atomic<bool>> key_frame_request)
+    : codec_(codec),
+      key_frame_(key_frame),
+      width_(width),
+      height_(height),
+      data_(webrtc::EncodedImageBuffer::Create(data.data(), data.size())),
+      key_frame_request_(std::move(key_frame_request)) {
+  webrtc::MutexLock lock(&buffers_mutex());
+  live_buffers().insert(this);
+}
+
+EncodedFrameBuffer::~EncodedFrameBuffer() {
+  webrtc::MutexLock lock(&buffers_mutex());
+  live_buffers().erase(this);
+}
+
+const EncodedFrameBuffer* EncodedFrameBuffer::FromBuffer(
+    const webrtc::VideoFrameBuffer* buffer) {
+  if (buffer->type() != Type::kNative)
+    return nullptr;
+
+  webrtc::MutexLock lock(&buffers_mutex());
+  if (live_buffers().count(buffer) == 0)
+    return nullptr;
+
+  return static_cast<const EncodedFrameBuffer*>(buffer);
+}
+
+rtc::scoped_refptr<webrtc::I420BufferInterface> EncodedFrameBuffer::ToI420() {
+  rtc::scoped_refptr<webrtc::I420Buffer> buffer =
+      webrtc::I420Buffer::Create(width_, height_);
+  webrtc::I420Buffer::SetBlack(buffer.get());
+  return buffer;
+}
+
+void EncodedFrameBuffer::RequestKeyFrame() const {
+  key_frame_request_->store(true);
+}
+
+PassthroughVideoEncoder::PassthroughVideoEncoder(
+    const webrtc::SdpVideoFormat& format,
+    std::unique_ptr<webrtc::VideoEncoder> encoder)
+    : codec_(to_encoded_video_codec(format)), encoder_(std::move(encoder)) {}
+
+void PassthroughVideoEncoder::SetFecControllerOverride(
+    webrtc::FecControllerOverride* fec_controller_override) {
+  encoder_->SetFecControllerOverride(fec_controller_override);
+}
+
+int PassthroughVideoEncoder::InitEncode(
+    const webrtc::VideoCodec* codec_settings,
+    const VideoEncoder::Settings& settings) {
+  return encoder_->InitEncode(codec_settings, settings);
+}
+
+int32_t PassthroughVideoEncoder::RegisterEncodeCompleteCallback(
+    webrtc::EncodedImageCallback* callback) {
+  callback_ = callback;
+  return encoder_->RegisterEncodeCompleteCallback(callback);
+}
+
+int32_t PassthroughVideoEncoder::Release() {
+  callback_ = nullptr;
+  return encoder_->Release();
+}
+
+int32_t PassthroughVideoEncoder::Encode(
+    const webrtc::VideoFrame& frame,
+    const std::vector<webrtc::VideoFrameType>* frame_types) {
+  rtc::scoped_refptr<webrtc::VideoFrameBuffer> buffer =
+      frame.video_frame_buffer();
+
+  if (const EncodedFrameBuffer* encoded =
+          EncodedFrameBuffer::FromBuffer(buffer.get())) {
+    if (frame_types) {
+      for (webrtc::VideoFrameType type : *frame_types) {
+        if (type == webrtc::VideoFrameType::kVideoFrameKey) {
+          encoded->RequestKeyFrame();
+          break;
+        }
+      }
+    }
+    return SendEncodedFrame(frame, *encoded);
+  }
+
+  // GetEncoderInfo claims the support of native buffers for the encoded
+  // frames, convert the other ones when the wrapped encoder can't use them
+  if (buffer->type() == webrtc::VideoFrameBuffer::Type::kNative &&
+      !encoder_->GetEncoderInfo().supports_native_handle) {
+    webrtc::VideoFrame converted(frame);
+    converted.set_video_frame_buffer(buffer->ToI420());
+    return encoder_->Encode(converted, frame_types);
+  }
+
+  return encoder_->Encode(frame, frame_types);
+}
+
+int32_t PassthroughVideoEncoder::SendEncodedFrame(
+    const webrtc::VideoFrame& frame,
+    const EncodedFrameBuffer& buffer) {
+  if (!callback_)
+    return WEBRTC_VIDEO_CODEC_UNINITIALIZED;
+
+  if (buffer.codec() != codec_) {
+    RTC_LOG(LS_ERROR) << "The encoded frame doesn't match the negotiated codec";
+    return WEBRTC_VIDEO_CODEC_ERROR;
+  }
+
+  webrtc::EncodedImage image;
+  image.SetEncodedData(buffer.data());
+  image.SetTimestamp(frame.timestamp());
+  image._encodedWidth = buffer.width();
+  image._encodedHeight = buffer.height();
+  image._frameType = buffer.key_frame() ? webrtc::VideoFrameType::kVideoFrameKey
+                                        : webrtc::VideoFrameType::kVideoFrameDelta;
+  image.capture_time_ms_ = frame.render_time_ms();
+  image.ntp_time_ms_ = frame.ntp_time_ms();
+  image.rotation_ = frame.rotation();
+
+  // The frames are expected to use a single spatial/temporal layer
+  webrtc::CodecSpecificInfo info;
+  switch (codec_) {
+    case EncodedVideoCodec::VP8:
+      info.codecType = webrtc::kVideoCodecVP8;
+      info.codecSpecific.VP8.nonReference = false;
+      info.codecSpecific.VP8.temporalIdx = webrtc::kNoTemporalIdx;
+      info.codecSpecific.VP8.layerSync = false;
+      info.codecSpecific.VP8.keyIdx = webrtc::kNoKeyIdx;
+      break;
+    case EncodedVideoCodec::VP9:
+      info.codecType = webrtc::kVideoCodecVP9;
+      info.codecSpecific.VP9.first_frame_in_picture = true;
+      info.codecSpecific.VP9.inter_pic_predicted = !buffer.key_frame();
+      info.codecSpecific.VP9.flexible_mode = false;
+      info.codecSpecific.VP9.ss_data_available = buffer.key_frame();
+      info.codecSpecific.VP9.non_ref_for_inter_layer_pred = false;
+      info.codecSpecific.VP9.temporal_idx = webrtc::kNoTemporalIdx;
+      info.codecSpecific.VP9.temporal_up_switch = false;
+      info.codecSpecific.VP9.inter_layer_predicted = false;
+      info.codecSpecific.VP9.gof_idx = 0;
+      info.codecSpecific.VP9.num_spatial_layers = 1;
+      info.codecSpecific.VP9.first_active_layer = 0;
+      info.codecSpecific.VP9.spatial_layer_resolution_present =
+          buffer.key_frame();
+      info.codecSpecific.VP9.width[0] = buffer.width();
+      info.codecSpecific.VP9.height[0] = buffer.height();
+      info.codecSpecific.VP9.gof.SetGofInfoVP9(
+          webrtc::TemporalStructureMode::kTemporalStructureMode1);
+      break;
+    case EncodedVideoCodec::H264:
+      info.codecType = webrtc::kVideoCodecH264;
+      info.codecSpecific.H264.packetization_mode =
+          webrtc::H264PacketizationMode::NonInterleaved;
+      info.codecSpecific.H264.temporal_idx = webrtc::kNoTemporalIdx;
+      info.codecSpecific.H264.base_layer_sync = false;
+      info.codecSpecific.H264.idr_frame = buffer.key_frame();
+      break;
+    default:
+      // AV1 requires the dependency descriptor of the frames
+      RTC_LOG(LS_ERROR) << "Sending encoded frames isn't supported for this "
+                           "codec";
+      return WEBRTC_VIDEO_CODEC_ERROR;
+  }
+
+  webrtc::EncodedImageCallback::Result result =
+      callback_->OnEncodedImage(image, &info);
+  return result.error == webrtc::EncodedImageCallback::Result::OK
+             ? WEBRTC_VIDEO_CODEC_OK
+             : WEBRTC_VIDEO_CODEC_ERROR;
+}
+
+void PassthroughVideoEncoder::SetRates(
+    const RateControlParameters& parameters) {
+  encoder_->SetRates(parameters);
+}
+
+void PassthroughVideoEncoder::OnPacketLossRateUpdate(float packet_loss_rate) {
+  encoder_->OnPacketLossRateUpdate(packet_loss_rate);
+}
+
+void PassthroughVideoEncoder::OnRttUpdate(int64_t rtt_ms) {
+  encoder_->OnRttUpdate(rtt_ms);
+}
+
+void PassthroughVideoEncoder::OnLossNotification(
+    const LossNotification& loss_notification) {
+  encoder_->OnLossNotification(loss_notification);
+}
+
+webrtc::VideoEncoder::EncoderInfo PassthroughVideoEncoder::GetEncoderInfo()
+    const {
+  EncoderInfo info = encoder_->GetEncoderInfo();
+  info.supports_native_handle = true;
+  return info;
+}
+
+}  // namespace livekit
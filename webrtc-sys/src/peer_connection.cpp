@@ -20,6 +20,7 @@
 
 #include "api/data_channel_interface.h"
 #include "api/scoped_refptr.h"
+#include "api/stats/rtcstats_objects.h"
 #include "livekit/data_channel.h"
 #include "livekit/jsep.h"
 #include "livekit/media_stream.h"
@@ -39,6 +40,7 @@ webrtc::PeerConnectionInterface::RTCConfiguration to_native_rtc_configuration(
     webrtc::PeerConnectionInterface::IceServer ice_server;
     ice_server.username = item.username.c_str();
     ice_server.password = item.password.c_str();
+    ice_server.hostname = item.hostname.c_str();
 
     for (auto url : item.urls)
       ice_server.urls.emplace_back(url.c_str());
@@ -158,6 +160,96 @@ void PeerConnection::add_ice_candidate(
       });
 }
 
+class NativeAudioReceiverStatsCallback
+    : public webrtc::RTCStatsCollectorCallback {
+ public:
+  NativeAudioReceiverStatsCallback(
+      rust::Box<AsyncContext> ctx,
+      rust::Fn<void(rust::Box<AsyncContext>, AudioReceiverStats)> on_stats)
+      : ctx_(std::move(ctx)), on_stats_(on_stats) {}
+
+  void OnStatsDelivered(
+      const rtc::scoped_refptr<const webrtc::RTCStatsReport>& report) override {
+    AudioReceiverStats stats{};
+    for (auto inbound :
+         report->GetStatsOfType<webrtc::RTCInboundRTPStreamStats>()) {
+      if (inbound->kind.ValueOrDefault("") != "audio")
+        continue;
+
+      stats.packets_received += inbound->packets_received.ValueOrDefault(0);
+      stats.packets_lost += inbound->packets_lost.ValueOrDefault(0);
+      stats.fec_packets_received +=
+          inbound->fec_packets_received.ValueOrDefault(0);
+      stats.fec_packets_discarded +=
+          inbound->fec_packets_discarded.ValueOrDefault(0);
+      stats.total_samples_received +=
+          inbound->total_samples_received.ValueOrDefault(0);
+      stats.concealed_samples += inbound->concealed_samples.ValueOrDefault(0);
+      stats.concealment_events +=
+          inbound->concealment_events.ValueOrDefault(0);
+    }
+
+    on_stats_(std::move(ctx_), stats);
+  }
+
+ private:
+  rust::Box<AsyncContext> ctx_;
+  rust::Fn<void(rust::Box<AsyncContext>, AudioReceiverStats)> on_stats_;
+};
+
+void PeerConnection::get_audio_receiver_stats(
+    std::shared_ptr<RtpReceiver> receiver,
+    rust::Box<AsyncContext> ctx,
+    rust::Fn<void(rust::Box<AsyncContext>, AudioReceiverStats)> on_stats)
+    const {
+  auto callback = rtc::make_ref_counted<NativeAudioReceiverStatsCallback>(
+      std::move(ctx), on_stats);
+  peer_connection_->GetStats(receiver->rtc_receiver(), callback);
+}
+
+class NativeStatsCallback : public webrtc::RTCStatsCollectorCallback {
+ public:
+  NativeStatsCallback(
+      rust::Box<AsyncContext> ctx,
+      rust::Fn<void(rust::Box<AsyncContext>, rust::String)> on_stats)
+      : ctx_(std::move(ctx)), on_stats_(on_stats) {}
+
+  void OnStatsDelivered(
+      const rtc::scoped_refptr<const webrtc::RTCStatsReport>& report) override {
+    on_stats_(std::move(ctx_), rust::String(report->ToJson()));
+  }
+
+ private:
+  rust::Box<AsyncContext> ctx_;
+  rust::Fn<void(rust::Box<AsyncContext>, rust::String)> on_stats_;
+};
+
+void PeerConnection::get_stats(
+    rust::Box<AsyncContext> ctx,
+    rust::Fn<void(rust::Box<AsyncContext>, rust::String)> on_stats) const {
+  auto callback =
+      rtc::make_ref_counted<NativeStatsCallback>(std::move(ctx), on_stats);
+  peer_connection_->GetStats(callback.get());
+}
+
+void PeerConnection::get_sender_stats(
+    std::shared_ptr<RtpSender> sender,
+    rust::Box<AsyncContext> ctx,
+    rust::Fn<void(rust::Box<AsyncContext>, rust::String)> on_stats) const {
+  auto callback =
+      rtc::make_ref_counted<NativeStatsCallback>(std::move(ctx), on_stats);
+  peer_connection_->GetStats(sender->rtc_sender(), callback);
+}
+
+void PeerConnection::get_receiver_stats(
+    std::shared_ptr<RtpReceiver> receiver,
+    rust::Box<AsyncContext> ctx,
+    rust::Fn<void(rust::Box<AsyncContext>, rust::String)> on_stats) const {
+  auto callback =
+      rtc::make_ref_counted<NativeStatsCallback>(std::move(ctx), on_stats);
+  peer_connection_->GetStats(receiver->rtc_receiver(), callback);
+}
+
 std::shared_ptr<DataChannel> PeerConnection::create_data_channel(
     rust::String label,
     DataChannelInit init) const {
@@ -26,15 +26,15 @@ use std::sync::Arc;
 #[cxx::bridge(namespace = "livekit")]
 pub mod ffi {
     pub struct CandidatePair {
-        local: SharedPtr<Candidate>,
-        remote: SharedPtr<Candidate>,
+        pub local: SharedPtr<Candidate>,
+        pub remote: SharedPtr<Candidate>,
     }
 
     pub struct CandidatePairChangeEvent {
-        selected_candidate_pair: CandidatePair,
-        last_data_received_ms: i64,
-        reason: String,
-        estimated_disconnected_time_ms: i64,
+        pub selected_candidate_pair: CandidatePair,
+        pub last_data_received_ms: i64,
+        pub reason: String,
+        pub estimated_disconnected_time_ms: i64,
     }
 
     #[repr(i32)]
@@ -91,6 +91,7 @@ pub mod ffi {
         pub urls: Vec<String>,
         pub username: String,
         pub password: String,
+        pub hostname: String,
     }
 
     #[repr(i32)]
@@ -113,6 +114,17 @@ pub mod ffi {
         pub ice_transport_type: IceTransportsType,
     }
 
+    // Subset of the inbound-rtp stats of an audio receiver
+    pub struct AudioReceiverStats {
+        pub packets_received: u64,
+        pub packets_lost: i64,
+        pub fec_packets_received: u64,
+        pub fec_packets_discarded: u64,
+        pub total_samples_received: u64,
+        pub concealed_samples: u64,
+        pub concealment_events: u64,
+    }
+
     extern "C++" {
         include!("livekit/rtc_error.h");
         include!("livekit/helper.h");
@@ -217,6 +229,29 @@ pub mod ffi {
             ctx: Box<AsyncContext>,
             on_complete: fn(ctx: Box<AsyncContext>, error: RtcError),
         );
+        fn get_audio_receiver_stats(
+            self: &PeerConnection,
+            receiver: SharedPtr<RtpReceiver>,
+            ctx: Box<AsyncContext>,
+            on_stats: fn(ctx: Box<AsyncContext>, stats: AudioReceiverStats),
+        );
+        fn get_stats(
+            self: &PeerConnection,
+            ctx: Box<AsyncContext>,
+            on_stats: fn(ctx: Box<AsyncContext>, json: String),
+        );
+        fn get_sender_stats(
+            self: &PeerConnection,
+            sender: SharedPtr<RtpSender>,
+            ctx: Box<AsyncContext>,
+            on_stats: fn(ctx: Box<AsyncContext>, json: String),
+        );
+        fn get_receiver_stats(
+            self: &PeerConnection,
+            receiver: SharedPtr<RtpReceiver>,
+            ctx: Box<AsyncContext>,
+            on_stats: fn(ctx: Box<AsyncContext>, json: String),
+        );
         fn restart_ice(self: &PeerConnection);
         fn current_local_description(self: &PeerConnection) -> UniquePtr<SessionDescription>;
         fn current_remote_description(self: &PeerConnection) -> UniquePtr<SessionDescription>;
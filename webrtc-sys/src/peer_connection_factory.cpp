@@ -16,6 +16,7 @@
 
 #include "livekit/peer_connection_factory.h"
 
+#include <map>
 #include <memory>
 #include <utility>
 
@@ -58,6 +59,7 @@ PeerConnectionFactory::PeerConnectionFactory(
 
   cricket::MediaEngineDependencies media_deps;
   media_deps.task_queue_factory = dependencies.task_queue_factory.get();
+  task_queue_factory_ = dependencies.task_queue_factory.get();
 
   audio_device_ = rtc_runtime_->worker_thread()->BlockingCall([&] {
     return rtc::make_ref_counted<livekit::AudioDevice>(
@@ -66,10 +68,12 @@ PeerConnectionFactory::PeerConnectionFactory(
 
   media_deps.adm = audio_device_;
 
-  media_deps.video_encoder_factory =
-      std::move(std::make_unique<livekit::VideoEncoderFactory>());
-  media_deps.video_decoder_factory =
-      std::move(std::make_unique<livekit::VideoDecoderFactory>());
+  auto video_encoder_factory = std::make_unique<livekit::VideoEncoderFactory>();
+  auto video_decoder_factory = std::make_unique<livekit::VideoDecoderFactory>();
+  video_encoder_factory_ = video_encoder_factory.get();
+  video_decoder_factory_ = video_decoder_factory.get();
+  media_deps.video_encoder_factory = std::move(video_encoder_factory);
+  media_deps.video_decoder_factory = std::move(video_decoder_factory);
   media_deps.audio_encoder_factory = webrtc::CreateBuiltinAudioEncoderFactory();
   media_deps.audio_decoder_factory = webrtc::CreateBuiltinAudioDecoderFactory();
   media_deps.audio_processing = webrtc::AudioProcessingBuilder().Create();
@@ -89,6 +93,7 @@ PeerConnectionFactory::PeerConnectionFactory(
 PeerConnectionFactory::~PeerConnectionFactory() {
   RTC_LOG(LS_VERBOSE) << "PeerConnectionFactory::~PeerConnectionFactory()";
 
+  device_manager_ = nullptr;
   peer_factory_ = nullptr;
   rtc_runtime_->worker_thread()->BlockingCall(
       [this] { audio_device_ = nullptr; });
@@ -110,6 +115,21 @@ std::shared_ptr<PeerConnection> PeerConnectionFactory::create_peer_connection(
                                           result.value());
 }
 
+std::shared_ptr<AudioDeviceManager>
+PeerConnectionFactory::audio_device_manager() const {
+  std::lock_guard<std::mutex> lock(device_manager_mutex_);
+  if (!device_manager_) {
+    auto manager = std::make_shared<AudioDeviceManager>(
+        rtc_runtime_->worker_thread(), task_queue_factory_);
+    if (!manager->initialized())
+      return nullptr;
+
+    device_manager_ = manager;
+  }
+
+  return device_manager_;
+}
+
 std::shared_ptr<VideoTrack> PeerConnectionFactory::create_video_track(
     rust::String label,
     std::shared_ptr<VideoTrackSource> source) const {
@@ -137,6 +157,53 @@ RtpCapabilities PeerConnectionFactory::rtp_receiver_capabilities(
       static_cast<cricket::MediaType>(type)));
 }
 
+rust::Vec<VideoCodecCapability>
+PeerConnectionFactory::video_codec_capabilities() const {
+  std::map<std::string, VideoCodecCapability> capabilities;
+  auto add = [&](const std::vector<webrtc::SdpVideoFormat>& formats,
+                 bool VideoCodecCapability::*supported) {
+    for (const auto& format : formats) {
+      auto& capability = capabilities[format.name];
+      capability.codec = format.name;
+      capability.*supported = true;
+    }
+  };
+
+  add(video_encoder_factory_->GetHardwareFormats(),
+      &VideoCodecCapability::hardware_encoder);
+  add(video_encoder_factory_->GetSoftwareFormats(),
+      &VideoCodecCapability::software_encoder);
+  add(video_decoder_factory_->GetHardwareFormats(),
+      &VideoCodecCapability::hardware_decoder);
+  add(video_decoder_factory_->GetSoftwareFormats(),
+      &VideoCodecCapability::software_decoder);
+
+  rust::Vec<VideoCodecCapability> result;
+  for (auto& [name, capability] : capabilities)
+    result.push_back(capability);
+
+  return result;
+}
+
+void PeerConnectionFactory::set_hardware_encoder_preference(
+    rust::String codec,
+    HardwarePreference preference) const {
+  VideoEncoderFactory::HardwarePreference native_preference;
+  switch (preference) {
+    case HardwarePreference::Require:
+      native_preference = VideoEncoderFactory::HardwarePreference::kRequire;
+      break;
+    case HardwarePreference::Disable:
+      native_preference = VideoEncoderFactory::HardwarePreference::kDisable;
+      break;
+    default:
+      native_preference = VideoEncoderFactory::HardwarePreference::kPrefer;
+  }
+
+  video_encoder_factory_->SetHardwarePreference(std::string(codec),
+                                                native_preference);
+}
+
 std::shared_ptr<PeerConnectionFactory> create_peer_connection_factory() {
   return std::make_shared<PeerConnectionFactory>(RtcRuntime::create());
 }
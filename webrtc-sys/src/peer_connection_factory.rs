@@ -16,12 +16,29 @@ use crate::impl_thread_safety;
 
 #[cxx::bridge(namespace = "livekit")]
 pub mod ffi {
+    #[derive(Debug)]
+    #[repr(i32)]
+    pub enum HardwarePreference {
+        Prefer,
+        Require,
+        Disable,
+    }
+
+    #[derive(Debug)]
+    pub struct VideoCodecCapability {
+        pub codec: String,
+        pub hardware_encoder: bool,
+        pub software_encoder: bool,
+        pub hardware_decoder: bool,
+        pub software_decoder: bool,
+    }
 
     extern "C++" {
         include!("livekit/media_stream.h");
         include!("livekit/webrtc.h");
         include!("livekit/peer_connection_factory.h");
         include!("livekit/rtp_parameters.h");
+        include!("livekit/audio_device_manager.h");
 
         type AudioTrackSource = crate::audio_track::ffi::AudioTrackSource;
         type VideoTrackSource = crate::video_track::ffi::VideoTrackSource;
@@ -32,6 +49,7 @@ pub mod ffi {
         type NativePeerConnectionObserver =
             crate::peer_connection::ffi::NativePeerConnectionObserver;
         type RtcConfiguration = crate::peer_connection::ffi::RtcConfiguration;
+        type AudioDeviceManager = crate::audio_device_manager::ffi::AudioDeviceManager;
     }
 
     unsafe extern "C++" {
@@ -69,6 +87,16 @@ pub mod ffi {
             self: &PeerConnectionFactory,
             kind: MediaType,
         ) -> RtpCapabilities;
+
+        fn video_codec_capabilities(self: &PeerConnectionFactory) -> Vec<VideoCodecCapability>;
+
+        fn set_hardware_encoder_preference(
+            self: &PeerConnectionFactory,
+            codec: String,
+            preference: HardwarePreference,
+        );
+
+        fn audio_device_manager(self: &PeerConnectionFactory) -> SharedPtr<AudioDeviceManager>;
     }
 }
 
@@ -0,0 +1,169 @@
+/*
+ * Copyright 2023 LiveKit
+ *
+ * Licensed under the Apache License, Version 2.0 (the “License”);
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an “AS IS” BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#include "livekit/system_audio_capturer.h"
+
+#include <vector>
+
+#include "rtc_base/logging.h"
+
+#ifdef WEBRTC_WIN
+#include <audioclient.h>
+#include <mmdeviceapi.h>
+#include <windows.h>
+#include <wrl/client.h>
+#endif
+
+namespace livekit {
+
+#ifdef WEBRTC_WIN
+// Size of the WASAPI buffer, in 100ns units
+constexpr REFERENCE_TIME kBufferDuration = 100 * 10000;
+constexpr DWORD kPollIntervalMs = 10;
+#endif
+
+SystemAudioCapturer::SystemAudioCapturer(
+    rust::Box<SystemAudioObserverWrapper> observer,
+    uint32_t sample_rate,
+    uint32_t num_channels)
+    : observer_(std::move(observer)),
+      sample_rate_(sample_rate),
+      num_channels_(num_channels) {}
+
+SystemAudioCapturer::~SystemAudioCapturer() {
+  stop();
+}
+
+bool SystemAudioCapturer::start() {
+#ifdef WEBRTC_WIN
+  if (running_.exchange(true))
+    return true;
+
+  std::promise<bool> started;
+  std::future<bool> result = started.get_future();
+  thread_ = std::thread(&SystemAudioCapturer::run, this, std::move(started));
+  if (!result.get()) {
+    stop();
+    return false;
+  }
+
+  return true;
+#else
+  RTC_LOG(LS_WARNING) << "audio loopback isn't supported on this platform";
+  return false;
+#endif
+}
+
+void SystemAudioCapturer::stop() {
+  running_ = false;
+  if (thread_.joinable())
+    thread_.join();
+}
+
+void SystemAudioCapturer::run(std::promise<bool> started) {
+#ifdef WEBRTC_WIN
+  using Microsoft::WRL::ComPtr;
+
+  HRESULT hr = CoInitializeEx(nullptr, COINIT_MULTITHREADED);
+  bool com_initialized = SUCCEEDED(hr);
+
+  {
+    ComPtr<IMMDeviceEnumerator> enumerator;
+    ComPtr<IMMDevice> device;
+    ComPtr<IAudioClient> client;
+    ComPtr<IAudioCaptureClient> capture;
+
+    hr = CoCreateInstance(__uuidof(MMDeviceEnumerator), nullptr, CLSCTX_ALL,
+                          IID_PPV_ARGS(&enumerator));
+    if (SUCCEEDED(hr))
+      hr = enumerator->GetDefaultAudioEndpoint(eRender, eConsole, &device);
+    if (SUCCEEDED(hr))
+      hr = device->Activate(__uuidof(IAudioClient), CLSCTX_ALL, nullptr,
+                            reinterpret_cast<void**>(client.GetAddressOf()));
+
+    if (SUCCEEDED(hr)) {
+      // Let WASAPI convert the mix format of the device to our format
+      WAVEFORMATEX format = {};
+      format.wFormatTag = WAVE_FORMAT_PCM;
+      format.nChannels = static_cast<WORD>(num_channels_);
+      format.nSamplesPerSec = sample_rate_;
+      format.wBitsPerSample = 16;
+      format.nBlockAlign = format.nChannels * format.wBitsPerSample / 8;
+      format.nAvgBytesPerSec = format.nSamplesPerSec * format.nBlockAlign;
+
+      hr = client->Initialize(AUDCLNT_SHAREMODE_SHARED,
+                              AUDCLNT_STREAMFLAGS_LOOPBACK |
+                                  AUDCLNT_STREAMFLAGS_AUTOCONVERTPCM |
+                                  AUDCLNT_STREAMFLAGS_SRC_DEFAULT_QUALITY,
+                              kBufferDuration, 0, &format, nullptr);
+    }
+    if (SUCCEEDED(hr))
+      hr = client->GetService(IID_PPV_ARGS(&capture));
+    if (SUCCEEDED(hr))
+      hr = client->Start();
+
+    if (FAILED(hr)) {
+      RTC_LOG(LS_ERROR) << "failed to start the audio loopback: " << hr;
+      started.set_value(false);
+    } else {
+      started.set_value(true);
+
+      std::vector<int16_t> silence;
+      while (running_) {
+        Sleep(kPollIntervalMs);
+
+        UINT32 packet_size = 0;
+        while (SUCCEEDED(capture->GetNextPacketSize(&packet_size)) &&
+               packet_size > 0) {
+          BYTE* data = nullptr;
+          UINT32 frames = 0;
+          DWORD flags = 0;
+          if (FAILED(capture->GetBuffer(&data, &frames, &flags, nullptr,
+                                        nullptr)))
+            break;
+
+          size_t len = static_cast<size_t>(frames) * num_channels_;
+          const int16_t* samples = reinterpret_cast<const int16_t*>(data);
+          if (flags & AUDCLNT_BUFFERFLAGS_SILENT) {
+            silence.assign(len, 0);
+            samples = silence.data();
+          }
+
+          observer_->on_data(rust::Slice<const int16_t>(samples, len));
+          capture->ReleaseBuffer(frames);
+        }
+      }
+
+      client->Stop();
+    }
+  }
+
+  if (com_initialized)
+    CoUninitialize();
+#else
+  started.set_value(false);
+#endif
+}
+
+std::unique_ptr<SystemAudioCapturer> new_system_audio_capturer(
+    rust::Box<SystemAudioObserverWrapper> observer,
+    uint32_t sample_rate,
+    uint32_t num_channels) {
+  return std::make_unique<SystemAudioCapturer>(std::move(observer),
+                                               sample_rate, num_channels);
+}
+
+}  // namespace livekit
@@ -0,0 +1,61 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::impl_thread_safety;
+use std::sync::Arc;
+
+#[cxx::bridge(namespace = "livekit")]
+pub mod ffi {
+    unsafe extern "C++" {
+        include!("livekit/system_audio_capturer.h");
+
+        type SystemAudioCapturer;
+
+        fn new_system_audio_capturer(
+            observer: Box<SystemAudioObserverWrapper>,
+            sample_rate: u32,
+            num_channels: u32,
+        ) -> UniquePtr<SystemAudioCapturer>;
+
+        fn start(self: Pin<&mut SystemAudioCapturer>) -> bool;
+        fn stop(self: Pin<&mut SystemAudioCapturer>);
+    }
+
+    extern "Rust" {
+        type SystemAudioObserverWrapper;
+
+        fn on_data(self: &SystemAudioObserverWrapper, data: &[i16]);
+    }
+}
+
+impl_thread_safety!(ffi::SystemAudioCapturer, Send);
+
+/// Receives the interleaved audio of the loopback, the packets don't have a fixed size
+pub trait SystemAudioObserver: Send + Sync {
+    fn on_data(&self, data: &[i16]);
+}
+
+pub struct SystemAudioObserverWrapper {
+    observer: Arc<dyn SystemAudioObserver>,
+}
+
+impl SystemAudioObserverWrapper {
+    pub fn new(observer: Arc<dyn SystemAudioObserver>) -> Self {
+        Self { observer }
+    }
+
+    fn on_data(&self, data: &[i16]) {
+        self.observer.on_data(data);
+    }
+}
@@ -0,0 +1,54 @@
+/*
+ * Copyright 2023 LiveKit
+ *
+ * Licensed under the Apache License, Version 2.0 (the “License”);
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an “AS IS” BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#include "livekit/vad.h"
+
+#include <memory>
+
+namespace livekit {
+
+Vad::Vad(VadInst* vad) : vad_(vad) {}
+
+Vad::~Vad() {
+  WebRtcVad_Free(vad_);
+}
+
+bool Vad::set_mode(int32_t mode) {
+  return WebRtcVad_set_mode(vad_, mode) == 0;
+}
+
+int32_t Vad::process(int32_t sample_rate, rust::Slice<const int16_t> data) {
+  return WebRtcVad_Process(vad_, sample_rate, data.data(), data.size());
+}
+
+std::unique_ptr<Vad> create_vad(int32_t mode) {
+  VadInst* vad = WebRtcVad_Create();
+  if (vad == nullptr)
+    return nullptr;
+
+  if (WebRtcVad_Init(vad) != 0) {
+    WebRtcVad_Free(vad);
+    return nullptr;
+  }
+
+  auto wrapper = std::make_unique<Vad>(vad);
+  if (!wrapper->set_mode(mode))
+    return nullptr;
+
+  return wrapper;
+}
+
+}  // namespace livekit
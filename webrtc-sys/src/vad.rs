@@ -0,0 +1,30 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::impl_thread_safety;
+
+#[cxx::bridge(namespace = "livekit")]
+pub mod ffi {
+    unsafe extern "C++" {
+        include!("livekit/vad.h");
+
+        type Vad;
+
+        fn create_vad(mode: i32) -> UniquePtr<Vad>;
+
+        fn set_mode(self: Pin<&mut Vad>, mode: i32) -> bool;
+        fn process(self: Pin<&mut Vad>, sample_rate: i32, data: &[i16]) -> i32;
+    }
+}
+
+impl_thread_safety!(ffi::Vad, Send);
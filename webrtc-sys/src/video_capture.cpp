@@ -0,0 +1,147 @@
+/*
+ * Copyright 2023 LiveKit
+ *
+ * Licensed under the Apache License, Version 2.0 (the “License”);
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an “AS IS” BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#include "livekit/video_capture.h"
+
+// On Apple platforms, the capturer is implemented in objc_video_capture.mm
+#if !defined(__APPLE__)
+
+#include <memory>
+
+#include "api/video/video_sink_interface.h"
+#include "rust/cxx.h"
+#include "webrtc-sys/src/video_capture.rs.h"
+
+#if !defined(__ANDROID__)
+#include "modules/video_capture/video_capture_factory.h"
+#endif
+
+namespace livekit {
+
+#if !defined(__ANDROID__)
+
+class ModuleCameraCapturer : public CameraCapturer,
+                             public rtc::VideoSinkInterface<webrtc::VideoFrame> {
+ public:
+  ModuleCameraCapturer(rust::Box<CameraCapturerObserverWrapper> observer,
+                       rtc::scoped_refptr<webrtc::VideoCaptureModule> module,
+                       std::string device_id)
+      : CameraCapturer(std::move(observer)),
+        module_(std::move(module)),
+        device_id_(std::move(device_id)) {
+    module_->RegisterCaptureDataCallback(this);
+  }
+
+  ~ModuleCameraCapturer() override {
+    stop();
+    module_->DeRegisterCaptureDataCallback();
+  }
+
+  bool start(CameraFormat format) override {
+    std::unique_ptr<webrtc::VideoCaptureModule::DeviceInfo> info(
+        webrtc::VideoCaptureFactory::CreateDeviceInfo());
+    if (!info)
+      return false;
+
+    webrtc::VideoCaptureCapability requested;
+    requested.width = static_cast<int32_t>(format.width);
+    requested.height = static_cast<int32_t>(format.height);
+    requested.maxFPS = static_cast<int32_t>(format.max_fps);
+
+    // Use the pixel format the device prefers for the requested resolution
+    webrtc::VideoCaptureCapability capability;
+    if (info->GetBestMatchedCapability(device_id_.c_str(), requested,
+                                       capability) < 0)
+      capability = requested;
+    capability.maxFPS = requested.maxFPS;
+
+    return module_->StartCapture(capability) == 0;
+  }
+
+  void stop() override {
+    if (module_->CaptureStarted())
+      module_->StopCapture();
+  }
+
+  void OnFrame(const webrtc::VideoFrame& frame) override { on_frame(frame); }
+
+ private:
+  rtc::scoped_refptr<webrtc::VideoCaptureModule> module_;
+  std::string device_id_;
+};
+
+rust::Vec<CameraDeviceInfo> list_camera_devices() {
+  rust::Vec<CameraDeviceInfo> devices;
+  std::unique_ptr<webrtc::VideoCaptureModule::DeviceInfo> info(
+      webrtc::VideoCaptureFactory::CreateDeviceInfo());
+  if (!info)
+    return devices;
+
+  for (uint32_t i = 0; i < info->NumberOfDevices(); ++i) {
+    char name[256] = {0};
+    char id[256] = {0};
+    if (info->GetDeviceName(i, name, sizeof(name), id, sizeof(id)) != 0)
+      continue;
+
+    CameraDeviceInfo device{rust::String(id), rust::String(name), {}};
+    int32_t count = info->NumberOfCapabilities(id);
+    for (int32_t j = 0; j < count; ++j) {
+      webrtc::VideoCaptureCapability capability;
+      if (info->GetCapability(id, j, capability) != 0)
+        continue;
+
+      device.formats.push_back(
+          CameraFormat{static_cast<uint32_t>(capability.width),
+                       static_cast<uint32_t>(capability.height),
+                       static_cast<uint32_t>(capability.maxFPS)});
+    }
+    devices.push_back(std::move(device));
+  }
+  return devices;
+}
+
+std::unique_ptr<CameraCapturer> new_camera_capturer(
+    rust::Box<CameraCapturerObserverWrapper> observer,
+    rust::String device_id) {
+  std::string id(device_id);
+  rtc::scoped_refptr<webrtc::VideoCaptureModule> module =
+      webrtc::VideoCaptureFactory::Create(id.c_str());
+  if (!module)
+    return nullptr;
+
+  return std::make_unique<ModuleCameraCapturer>(std::move(observer),
+                                                std::move(module), id);
+}
+
+#else
+
+// Camera capture on Android goes through the Java Camera2 APIs, which aren't
+// wrapped yet
+rust::Vec<CameraDeviceInfo> list_camera_devices() {
+  return rust::Vec<CameraDeviceInfo>();
+}
+
+std::unique_ptr<CameraCapturer> new_camera_capturer(
+    rust::Box<CameraCapturerObserverWrapper> observer,
+    rust::String device_id) {
+  return nullptr;
+}
+
+#endif
+
+}  // namespace livekit
+
+#endif
@@ -0,0 +1,80 @@
+// Copyright 2023 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::impl_thread_safety;
+use std::sync::Arc;
+
+#[cxx::bridge(namespace = "livekit")]
+pub mod ffi {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct CameraFormat {
+        pub width: u32,
+        pub height: u32,
+        pub max_fps: u32,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct CameraDeviceInfo {
+        pub id: String,
+        pub name: String,
+        pub formats: Vec<CameraFormat>,
+    }
+
+    extern "C++" {
+        include!("livekit/video_frame.h");
+
+        type VideoFrame = crate::video_frame::ffi::VideoFrame;
+    }
+
+    unsafe extern "C++" {
+        include!("livekit/video_capture.h");
+
+        type CameraCapturer;
+
+        fn list_camera_devices() -> Vec<CameraDeviceInfo>;
+        fn new_camera_capturer(
+            observer: Box<CameraCapturerObserverWrapper>,
+            device_id: String,
+        ) -> UniquePtr<CameraCapturer>;
+
+        fn start(self: Pin<&mut CameraCapturer>, format: CameraFormat) -> bool;
+        fn stop(self: Pin<&mut CameraCapturer>);
+    }
+
+    extern "Rust" {
+        type CameraCapturerObserverWrapper;
+
+        fn on_frame(self: &CameraCapturerObserverWrapper, frame: UniquePtr<VideoFrame>);
+    }
+}
+
+impl_thread_safety!(ffi::CameraCapturer, Send);
+
+pub trait CameraCapturerObserver: Send + Sync {
+    fn on_frame(&self, frame: cxx::UniquePtr<ffi::VideoFrame>);
+}
+
+pub struct CameraCapturerObserverWrapper {
+    observer: Arc<dyn CameraCapturerObserver>,
+}
+
+impl CameraCapturerObserverWrapper {
+    pub fn new(observer: Arc<dyn CameraCapturerObserver>) -> Self {
+        Self { observer }
+    }
+
+    fn on_frame(&self, frame: cxx::UniquePtr<ffi::VideoFrame>) {
+        self.observer.on_frame(frame);
+    }
+}
@@ -41,16 +41,20 @@ VideoDecoderFactory::VideoDecoderFactory() {
   // TODO(theomonnom): Add other HW decoders here
 }
 
-std::vector<webrtc::SdpVideoFormat> VideoDecoderFactory::GetSupportedFormats()
+std::vector<webrtc::SdpVideoFormat> VideoDecoderFactory::GetHardwareFormats()
     const {
   std::vector<webrtc::SdpVideoFormat> formats;
-
   for (const auto& factory : factories_) {
     auto supported_formats = factory->GetSupportedFormats();
     formats.insert(formats.end(), supported_formats.begin(),
                    supported_formats.end());
   }
+  return formats;
+}
 
+std::vector<webrtc::SdpVideoFormat> VideoDecoderFactory::GetSoftwareFormats()
+    const {
+  std::vector<webrtc::SdpVideoFormat> formats;
   formats.push_back(webrtc::SdpVideoFormat(cricket::kVp8CodecName));
   for (const webrtc::SdpVideoFormat& h264_format :
        webrtc::SupportedH264DecoderCodecs())
@@ -59,6 +63,15 @@ std::vector<webrtc::SdpVideoFormat> VideoDecoderFactory::GetSupportedFormats()
   return formats;
 }
 
+std::vector<webrtc::SdpVideoFormat> VideoDecoderFactory::GetSupportedFormats()
+    const {
+  std::vector<webrtc::SdpVideoFormat> formats = GetHardwareFormats();
+  auto software_formats = GetSoftwareFormats();
+  formats.insert(formats.end(), software_formats.begin(),
+                 software_formats.end());
+  return formats;
+}
+
 VideoDecoderFactory::CodecSupport VideoDecoderFactory::QueryCodecSupport(
     const webrtc::SdpVideoFormat& format,
     bool reference_scaling) const {
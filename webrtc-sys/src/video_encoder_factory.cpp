@@ -16,11 +16,13 @@
 
 #include "livekit/video_encoder_factory.h"
 
+#include "absl/strings/ascii.h"
 #include "api/video_codecs/sdp_video_format.h"
 #include "api/video_codecs/video_encoder.h"
 #include "api/video_codecs/video_encoder_factory_template.h"
 #include "api/video_codecs/video_encoder_factory_template_libvpx_vp8_adapter.h"
 #include "livekit/objc_video_factory.h"
+#include "livekit/passthrough_video_encoder.h"
 #include "media/base/media_constants.h"
 #include "media/engine/simulcast_encoder_adapter.h"
 #include "rtc_base/logging.h"
@@ -56,9 +58,8 @@ VideoEncoderFactory::InternalFactory::InternalFactory() {
 }
 
 std::vector<webrtc::SdpVideoFormat>
-VideoEncoderFactory::InternalFactory::GetSupportedFormats() const {
-  std::vector<webrtc::SdpVideoFormat> formats = Factory().GetSupportedFormats();
-
+VideoEncoderFactory::InternalFactory::GetHardwareFormats() const {
+  std::vector<webrtc::SdpVideoFormat> formats;
   for (const auto& factory : factories_) {
     auto supported_formats = factory->GetSupportedFormats();
     formats.insert(formats.end(), supported_formats.begin(),
@@ -67,6 +68,41 @@ VideoEncoderFactory::InternalFactory::GetSupportedFormats() const {
   return formats;
 }
 
+std::vector<webrtc::SdpVideoFormat>
+VideoEncoderFactory::InternalFactory::GetSoftwareFormats() const {
+  return Factory().GetSupportedFormats();
+}
+
+void VideoEncoderFactory::InternalFactory::SetHardwarePreference(
+    const std::string& codec,
+    HardwarePreference preference) {
+  webrtc::MutexLock lock(&mutex_);
+  preferences_[absl::AsciiStrToLower(codec)] = preference;
+}
+
+VideoEncoderFactory::HardwarePreference
+VideoEncoderFactory::InternalFactory::GetHardwarePreference(
+    const std::string& codec) const {
+  webrtc::MutexLock lock(&mutex_);
+  auto it = preferences_.find(absl::AsciiStrToLower(codec));
+  return it != preferences_.end() ? it->second : HardwarePreference::kPrefer;
+}
+
+std::vector<webrtc::SdpVideoFormat>
+VideoEncoderFactory::InternalFactory::GetSupportedFormats() const {
+  std::vector<webrtc::SdpVideoFormat> formats;
+  for (const auto& format : GetHardwareFormats()) {
+    if (GetHardwarePreference(format.name) != HardwarePreference::kDisable)
+      formats.push_back(format);
+  }
+
+  for (const auto& format : GetSoftwareFormats()) {
+    if (GetHardwarePreference(format.name) != HardwarePreference::kRequire)
+      formats.push_back(format);
+  }
+  return formats;
+}
+
 VideoEncoderFactory::CodecSupport
 VideoEncoderFactory::InternalFactory::QueryCodecSupport(
     const webrtc::SdpVideoFormat& format,
@@ -81,13 +117,21 @@ VideoEncoderFactory::InternalFactory::QueryCodecSupport(
 std::unique_ptr<webrtc::VideoEncoder>
 VideoEncoderFactory::InternalFactory::CreateVideoEncoder(
     const webrtc::SdpVideoFormat& format) {
-  for (const auto& factory : factories_) {
-    for (const auto& supported_format : factory->GetSupportedFormats()) {
-      if (supported_format.IsSameCodec(format))
-        return factory->CreateVideoEncoder(format);
+  HardwarePreference preference = GetHardwarePreference(format.name);
+  if (preference != HardwarePreference::kDisable) {
+    for (const auto& factory : factories_) {
+      for (const auto& supported_format : factory->GetSupportedFormats()) {
+        if (supported_format.IsSameCodec(format))
+          return factory->CreateVideoEncoder(format);
+      }
     }
   }
 
+  if (preference == HardwarePreference::kRequire) {
+    RTC_LOG(LS_ERROR) << "No hardware VideoEncoder found for " << format.name;
+    return nullptr;
+  }
+
   auto original_format =
       webrtc::FuzzyMatchSdpVideoFormat(Factory().GetSupportedFormats(), format);
 
@@ -108,6 +152,22 @@ std::vector<webrtc::SdpVideoFormat> VideoEncoderFactory::GetSupportedFormats()
   return internal_factory_->GetSupportedFormats();
 }
 
+std::vector<webrtc::SdpVideoFormat> VideoEncoderFactory::GetHardwareFormats()
+    const {
+  return internal_factory_->GetHardwareFormats();
+}
+
+std::vector<webrtc::SdpVideoFormat> VideoEncoderFactory::GetSoftwareFormats()
+    const {
+  return internal_factory_->GetSoftwareFormats();
+}
+
+void VideoEncoderFactory::SetHardwarePreference(
+    const std::string& codec,
+    HardwarePreference preference) {
+  internal_factory_->SetHardwarePreference(codec, preference);
+}
+
 VideoEncoderFactory::CodecSupport VideoEncoderFactory::QueryCodecSupport(
     const webrtc::SdpVideoFormat& format,
     absl::optional<std::string> scalability_mode) const {
@@ -118,8 +178,10 @@ std::unique_ptr<webrtc::VideoEncoder> VideoEncoderFactory::CreateVideoEncoder(
     const webrtc::SdpVideoFormat& format) {
   std::unique_ptr<webrtc::VideoEncoder> encoder;
   if (format.IsCodecInList(internal_factory_->GetSupportedFormats())) {
-    encoder = std::make_unique<webrtc::SimulcastEncoderAdapter>(
-        internal_factory_.get(), format);
+    // Frames captured already encoded skip the encoder
+    encoder = std::make_unique<PassthroughVideoEncoder>(
+        format, std::make_unique<webrtc::SimulcastEncoderAdapter>(
+                    internal_factory_.get(), format));
   }
 
   return encoder;
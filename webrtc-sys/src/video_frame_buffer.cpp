@@ -16,8 +16,26 @@
 
 #include "livekit/video_frame_buffer.h"
 
+#include <set>
+
+#include "rtc_base/synchronization/mutex.h"
+
 namespace livekit {
 
+namespace {
+
+webrtc::Mutex& platform_buffers_mutex() {
+  static webrtc::Mutex* mutex = new webrtc::Mutex();
+  return *mutex;
+}
+
+std::set<const webrtc::VideoFrameBuffer*>& live_platform_buffers() {
+  static auto* buffers = new std::set<const webrtc::VideoFrameBuffer*>();
+  return *buffers;
+}
+
+}  // namespace
+
 VideoFrameBuffer::VideoFrameBuffer(
     rtc::scoped_refptr<webrtc::VideoFrameBuffer> buffer)
     : buffer_(std::move(buffer)) {}
@@ -34,6 +52,51 @@ unsigned int VideoFrameBuffer::height() const {
   return buffer_->height();
 }
 
+NativeHandle VideoFrameBuffer::native_handle() const {
+  return get_native_handle(buffer_);
+}
+
+PlatformNativeBuffer::PlatformNativeBuffer() {
+  webrtc::MutexLock lock(&platform_buffers_mutex());
+  live_platform_buffers().insert(this);
+}
+
+PlatformNativeBuffer::~PlatformNativeBuffer() {
+  webrtc::MutexLock lock(&platform_buffers_mutex());
+  live_platform_buffers().erase(this);
+}
+
+const PlatformNativeBuffer* PlatformNativeBuffer::FromBuffer(
+    const webrtc::VideoFrameBuffer* buffer) {
+  if (buffer->type() != Type::kNative)
+    return nullptr;
+
+  webrtc::MutexLock lock(&platform_buffers_mutex());
+  if (live_platform_buffers().count(buffer) == 0)
+    return nullptr;
+
+  return static_cast<const PlatformNativeBuffer*>(buffer);
+}
+
+#if defined(__ANDROID__)
+NativeHandle get_native_handle(
+    const rtc::scoped_refptr<webrtc::VideoFrameBuffer>& buffer) {
+  // AndroidVideoBuffer (MediaCodec) isn't exposed
+  return NativeHandle{NativeHandleType::None};
+}
+#elif !defined(__APPLE__)
+NativeHandle get_native_handle(
+    const rtc::scoped_refptr<webrtc::VideoFrameBuffer>& buffer) {
+  // Other native buffers (e.g. EncodedFrameBuffer) don't have a handle
+  const PlatformNativeBuffer* platform_buffer =
+      PlatformNativeBuffer::FromBuffer(buffer.get());
+  if (!platform_buffer)
+    return NativeHandle{NativeHandleType::None};
+
+  return platform_buffer->native_handle();
+}
+#endif
+
 std::unique_ptr<I420Buffer> VideoFrameBuffer::to_i420() const {
   return std::make_unique<I420Buffer>(buffer_->ToI420());
 }
@@ -28,6 +28,30 @@ pub mod ffi {
         NV12,
     }
 
+    #[derive(Debug)]
+    #[repr(i32)]
+    pub enum NativeHandleType {
+        None,
+        CVPixelBuffer,
+        D3D11Texture,
+        DmaBuf,
+    }
+
+    /// Platform handle of a native buffer, the pointers are only valid while the
+    /// VideoFrameBuffer is alive
+    #[derive(Debug)]
+    pub struct NativeHandle {
+        pub handle_type: NativeHandleType,
+        pub handle: usize,    // CVPixelBufferRef or ID3D11Texture2D*
+        pub subresource: u32, // Index inside a D3D11 texture array
+        pub fourcc: u32,
+        pub modifier: u64,
+        pub num_planes: u32,
+        pub fds: [i32; 4],
+        pub offsets: [u32; 4],
+        pub strides: [u32; 4],
+    }
+
     unsafe extern "C++" {
         include!("livekit/video_frame_buffer.h");
 
@@ -47,6 +71,7 @@ pub mod ffi {
         fn buffer_type(self: &VideoFrameBuffer) -> VideoFrameBufferType;
         fn width(self: &VideoFrameBuffer) -> u32;
         fn height(self: &VideoFrameBuffer) -> u32;
+        fn native_handle(self: &VideoFrameBuffer) -> NativeHandle;
 
         /// # SAFETY
         /// If the buffer type is I420, the buffer must be cloned before
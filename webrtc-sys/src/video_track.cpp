@@ -26,6 +26,7 @@
 #include "audio/remix_resample.h"
 #include "common_audio/include/audio_util.h"
 #include "livekit/media_stream.h"
+#include "livekit/passthrough_video_encoder.h"
 #include "livekit/video_track.h"
 #include "rtc_base/logging.h"
 #include "rtc_base/ref_counted_object.h"
@@ -106,7 +107,9 @@ std::shared_ptr<NativeVideoSink> new_native_video_sink(
 
 VideoTrackSource::InternalSource::InternalSource(
     const VideoResolution& resolution)
-    : rtc::AdaptedVideoTrackSource(4), resolution_(resolution) {}
+    : rtc::AdaptedVideoTrackSource(4),
+      resolution_(resolution),
+      key_frame_request_(std::make_shared<std::atomic<bool>>(false)) {}
 
 VideoTrackSource::InternalSource::~InternalSource() {}
 
@@ -175,6 +178,44 @@ bool VideoTrackSource::InternalSource::on_captured_frame(
   return true;
 }
 
+bool VideoTrackSource::InternalSource::on_captured_encoded_frame(
+    EncodedVideoCodec codec,
+    bool key_frame,
+    uint32_t width,
+    uint32_t height,
+    rust::Slice<const uint8_t> data,
+    int64_t timestamp_us) {
+  webrtc::MutexLock lock(&mutex_);
+
+  if (key_frame && width != 0 && height != 0) {
+    resolution_ = VideoResolution{width, height};
+  }
+
+  if (resolution_.width == 0 || resolution_.height == 0) {
+    return false;  // Waiting for the first key frame
+  }
+
+  int64_t aligned_timestamp_us =
+      timestamp_aligner_.TranslateTimestamp(timestamp_us, rtc::TimeMicros());
+
+  // The frame can't be scaled or cropped, AdaptFrame is skipped
+  auto buffer = rtc::make_ref_counted<EncodedFrameBuffer>(
+      codec, key_frame, resolution_.width, resolution_.height,
+      rtc::ArrayView<const uint8_t>(data.data(), data.size()),
+      key_frame_request_);
+
+  OnFrame(webrtc::VideoFrame::Builder()
+              .set_video_frame_buffer(buffer)
+              .set_timestamp_us(aligned_timestamp_us)
+              .build());
+
+  return true;
+}
+
+bool VideoTrackSource::InternalSource::take_key_frame_request() {
+  return key_frame_request_->exchange(false);
+}
+
 VideoTrackSource::VideoTrackSource(const VideoResolution& resolution) {
   source_ = rtc::make_ref_counted<InternalSource>(resolution);
 }
@@ -189,6 +230,21 @@ bool VideoTrackSource::on_captured_frame(
   return source_->on_captured_frame(rtc_frame);
 }
 
+bool VideoTrackSource::on_captured_encoded_frame(
+    EncodedVideoCodec codec,
+    bool key_frame,
+    uint32_t width,
+    uint32_t height,
+    rust::Slice<const uint8_t> data,
+    int64_t timestamp_us) const {
+  return source_->on_captured_encoded_frame(codec, key_frame, width, height,
+                                            data, timestamp_us);
+}
+
+bool VideoTrackSource::take_key_frame_request() const {
+  return source_->take_key_frame_request();
+}
+
 rtc::scoped_refptr<VideoTrackSource::InternalSource> VideoTrackSource::get()
     const {
   return source_;
@@ -44,9 +44,11 @@ pub mod ffi {
     extern "C++" {
         include!("livekit/video_frame.h");
         include!("livekit/media_stream_track.h");
+        include!("livekit/encoded_frame_tap.h");
 
         type VideoFrame = crate::video_frame::ffi::VideoFrame;
         type MediaStreamTrack = crate::media_stream_track::ffi::MediaStreamTrack;
+        type EncodedVideoCodec = crate::encoded_frame_tap::ffi::EncodedVideoCodec;
     }
 
     unsafe extern "C++" {
@@ -66,6 +68,16 @@ pub mod ffi {
 
         fn video_resolution(self: &VideoTrackSource) -> VideoResolution;
         fn on_captured_frame(self: &VideoTrackSource, frame: &UniquePtr<VideoFrame>) -> bool;
+        fn on_captured_encoded_frame(
+            self: &VideoTrackSource,
+            codec: EncodedVideoCodec,
+            key_frame: bool,
+            width: u32,
+            height: u32,
+            data: &[u8],
+            timestamp_us: i64,
+        ) -> bool;
+        fn take_key_frame_request(self: &VideoTrackSource) -> bool;
         fn new_video_track_source(resolution: &VideoResolution) -> SharedPtr<VideoTrackSource>;
         fn video_to_media(track: SharedPtr<VideoTrack>) -> SharedPtr<MediaStreamTrack>;
         unsafe fn media_to_video(track: SharedPtr<MediaStreamTrack>) -> SharedPtr<VideoTrack>;
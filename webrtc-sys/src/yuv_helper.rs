@@ -293,5 +293,158 @@ pub mod ffi {
             width: i32,
             height: i32,
         ) -> Result<()>;
+
+        unsafe fn i420_to_nv12(
+            src_y: *const u8,
+            src_stride_y: i32,
+            src_u: *const u8,
+            src_stride_u: i32,
+            src_v: *const u8,
+            src_stride_v: i32,
+            dst_y: *mut u8,
+            dst_stride_y: i32,
+            dst_uv: *mut u8,
+            dst_stride_uv: i32,
+            width: i32,
+            height: i32,
+        ) -> Result<()>;
+
+        unsafe fn i420_to_i422(
+            src_y: *const u8,
+            src_stride_y: i32,
+            src_u: *const u8,
+            src_stride_u: i32,
+            src_v: *const u8,
+            src_stride_v: i32,
+            dst_y: *mut u8,
+            dst_stride_y: i32,
+            dst_u: *mut u8,
+            dst_stride_u: i32,
+            dst_v: *mut u8,
+            dst_stride_v: i32,
+            width: i32,
+            height: i32,
+        ) -> Result<()>;
+
+        unsafe fn i420_to_i444(
+            src_y: *const u8,
+            src_stride_y: i32,
+            src_u: *const u8,
+            src_stride_u: i32,
+            src_v: *const u8,
+            src_stride_v: i32,
+            dst_y: *mut u8,
+            dst_stride_y: i32,
+            dst_u: *mut u8,
+            dst_stride_u: i32,
+            dst_v: *mut u8,
+            dst_stride_v: i32,
+            width: i32,
+            height: i32,
+        ) -> Result<()>;
+
+        unsafe fn i420_to_rgb24(
+            src_y: *const u8,
+            src_stride_y: i32,
+            src_u: *const u8,
+            src_stride_u: i32,
+            src_v: *const u8,
+            src_stride_v: i32,
+            dst_rgb24: *mut u8,
+            dst_stride_rgb24: i32,
+            width: i32,
+            height: i32,
+        ) -> Result<()>;
+
+        unsafe fn rgb24_to_i420(
+            src_rgb24: *const u8,
+            src_stride_rgb24: i32,
+            dst_y: *mut u8,
+            dst_stride_y: i32,
+            dst_u: *mut u8,
+            dst_stride_u: i32,
+            dst_v: *mut u8,
+            dst_stride_v: i32,
+            width: i32,
+            height: i32,
+        ) -> Result<()>;
+
+        unsafe fn rgb24_to_argb(
+            src_rgb24: *const u8,
+            src_stride_rgb24: i32,
+            dst_argb: *mut u8,
+            dst_stride_argb: i32,
+            width: i32,
+            height: i32,
+        ) -> Result<()>;
+
+        unsafe fn argb_to_abgr(
+            src_argb: *const u8,
+            src_stride_argb: i32,
+            dst_abgr: *mut u8,
+            dst_stride_abgr: i32,
+            width: i32,
+            height: i32,
+        ) -> Result<()>;
+
+        unsafe fn scale_plane(
+            src: *const u8,
+            src_stride: i32,
+            src_width: i32,
+            src_height: i32,
+            dst: *mut u8,
+            dst_stride: i32,
+            dst_width: i32,
+            dst_height: i32,
+            filtering: i32,
+        ) -> Result<()>;
+
+        unsafe fn nv12_scale(
+            src_y: *const u8,
+            src_stride_y: i32,
+            src_uv: *const u8,
+            src_stride_uv: i32,
+            src_width: i32,
+            src_height: i32,
+            dst_y: *mut u8,
+            dst_stride_y: i32,
+            dst_uv: *mut u8,
+            dst_stride_uv: i32,
+            dst_width: i32,
+            dst_height: i32,
+            filtering: i32,
+        ) -> Result<()>;
+
+        unsafe fn argb_scale(
+            src_argb: *const u8,
+            src_stride_argb: i32,
+            src_width: i32,
+            src_height: i32,
+            dst_argb: *mut u8,
+            dst_stride_argb: i32,
+            dst_width: i32,
+            dst_height: i32,
+            filtering: i32,
+        ) -> Result<()>;
+
+        unsafe fn rotate_plane(
+            src: *const u8,
+            src_stride: i32,
+            dst: *mut u8,
+            dst_stride: i32,
+            width: i32,
+            height: i32,
+            mode: i32,
+        ) -> Result<()>;
+
+        unsafe fn argb_rotate(
+            src_argb: *const u8,
+            src_stride_argb: i32,
+            dst_argb: *mut u8,
+            dst_stride_argb: i32,
+            width: i32,
+            height: i32,
+            mode: i32,
+        ) -> Result<()>;
     }
 }